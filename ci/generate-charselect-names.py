@@ -0,0 +1,68 @@
+#!/usr/bin/env python3
+# Generates wezterm-gui/src/overlay/charselect/names.rs, which holds the
+# names of the characters that can be picked using the CharSelect overlay.
+# The names are taken from the unicodedata module that ships with python,
+# so re-running this with a newer python picks up newer unicode data.
+import os
+import sys
+import unicodedata
+
+# Each group is the name of the rust constant to generate, followed by
+# the inclusive codepoint ranges that make up the group.
+GROUPS = [
+    ("EMOTICONS", [(0x1F600, 0x1F64F)]),
+    (
+        "PICTOGRAPHS",
+        [(0x1F300, 0x1F5FF), (0x1F900, 0x1F9FF), (0x1FA70, 0x1FAFF)],
+    ),
+    ("TRANSPORT_AND_MAP", [(0x1F680, 0x1F6FF)]),
+    ("SYMBOLS_AND_DINGBATS", [(0x2600, 0x26FF), (0x2700, 0x27BF)]),
+    ("ARROWS", [(0x2190, 0x21FF), (0x27F0, 0x27FF), (0x2900, 0x297F)]),
+    ("MATHEMATICAL", [(0x2200, 0x22FF), (0x27C0, 0x27EF), (0x2980, 0x29FF)]),
+    ("TECHNICAL", [(0x2300, 0x23FF)]),
+    ("BOX_DRAWING", [(0x2500, 0x259F)]),
+    ("GEOMETRIC_SHAPES", [(0x25A0, 0x25FF), (0x1F780, 0x1F7FF)]),
+    (
+        "PUNCTUATION_AND_CURRENCY",
+        [(0x2010, 0x205E), (0x20A0, 0x20CF), (0x2100, 0x214F)],
+    ),
+    ("LATIN", [(0x00A1, 0x024F)]),
+    ("GREEK", [(0x0370, 0x03FF)]),
+]
+
+
+def group_entries(ranges):
+    for (start, end) in ranges:
+        for cp in range(start, end + 1):
+            c = chr(cp)
+            name = unicodedata.name(c, None)
+            if name is None:
+                continue
+            if unicodedata.category(c) in ("Cc", "Cf", "Mn", "Me", "Co", "Cn"):
+                continue
+            yield (cp, name.lower())
+
+
+def main():
+    root = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+    dest = os.path.join(root, "wezterm-gui/src/overlay/charselect/names.rs")
+
+    lines = [
+        "//! This file was generated by ci/generate-charselect-names.py",
+        "//! using unicode %s; do not edit it by hand."
+        % unicodedata.unidata_version,
+        "",
+    ]
+    for (group, ranges) in GROUPS:
+        lines.append("pub const %s: &[(char, &str)] = &[" % group)
+        for (cp, name) in group_entries(ranges):
+            lines.append("    ('\\u{%X}', %s)," % (cp, '"' + name + '"'))
+        lines.append("];")
+        lines.append("")
+
+    with open(dest, "w") as f:
+        f.write("\n".join(lines).rstrip("\n") + "\n")
+
+
+if __name__ == "__main__":
+    sys.exit(main())
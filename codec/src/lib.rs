@@ -16,20 +16,23 @@ use leb128;
 use mux::domain::DomainId;
 use mux::pane::PaneId;
 use mux::renderable::{RenderableDimensions, StableCursorPosition};
-use mux::tab::{PaneNode, SerdeUrl, SplitDirection, TabId};
+use mux::tab::{PaneNode, SerdeUrl, SplitRequest, TabId};
 use mux::window::WindowId;
 use portable_pty::{CommandBuilder, PtySize};
 use rangeset::*;
 use serde::{Deserialize, Serialize};
 use smol::io::AsyncWriteExt;
 use smol::prelude::*;
+use std::collections::HashMap;
 use std::convert::TryInto;
 use std::io::Cursor;
 use std::ops::Range;
 use std::sync::Arc;
+use termwiz::cell::{Cell, CellAttributes};
 use termwiz::hyperlink::Hyperlink;
 use termwiz::surface::Line;
 use varbincode;
+use wezterm_term::color::RgbColor;
 use wezterm_term::{ClipboardSelection, StableRowIndex};
 
 /// Returns the encoded length of the leb128 representation of value
@@ -400,7 +403,24 @@ macro_rules! pdu {
 /// The overall version of the codec.
 /// This must be bumped when backwards incompatible changes
 /// are made to the types and protocol.
-pub const CODEC_VERSION: usize = 7;
+pub const CODEC_VERSION: usize = 21;
+
+/// The oldest codec version that this build can interoperate with.
+/// Changes that only add new Pdu variants should bump CODEC_VERSION
+/// but leave this alone, so that slightly older peers can still connect;
+/// changing the shape of an existing Pdu requires bumping both.
+pub const CODEC_MIN_COMPATIBLE_VERSION: usize = 21;
+
+/// Optional protocol features supported by this build.
+/// Either side should only make use of a feature if the peer
+/// advertised it during the `Handshake`.
+pub const CODEC_CAPABILITIES: &[&str] = &["pane_writer"];
+
+/// Returns true if a peer speaking `peer_vers`, and which is able to
+/// understand peers as old as `peer_min_vers`, can talk to us.
+pub fn codec_version_is_compatible(peer_vers: usize, peer_min_vers: usize) -> bool {
+    peer_vers >= CODEC_MIN_COMPATIBLE_VERSION && CODEC_VERSION >= peer_min_vers
+}
 
 // Defines the Pdu enum.
 // Each struct has an explicit identifying number.
@@ -436,6 +456,17 @@ pdu! {
     SplitPane: 34,
     KillPane: 35,
     SpawnV2: 36,
+    SetPaneInputMode: 37,
+    Handshake: 38,
+    HandshakeResponse: 39,
+    SetWindowPosition: 40,
+    CapturePane: 41,
+    Authenticate: 42,
+    AuthenticateResponse: 43,
+    SetReadOnly: 44,
+    SetClientName: 45,
+    PaneWriterChanged: 46,
+    SetLogFilters: 47,
 }
 
 impl Pdu {
@@ -511,6 +542,7 @@ impl Pdu {
                 Some(*pane_id)
             }
             Pdu::SetClipboard(SetClipboard { pane_id, .. }) => Some(*pane_id),
+            Pdu::PaneWriterChanged(PaneWriterChanged { pane_id, .. }) => Some(*pane_id),
             _ => None,
         }
     }
@@ -533,6 +565,59 @@ pub struct GetCodecVersionResponse {
     pub version_string: String,
 }
 
+/// Sent by the client when it first connects, to negotiate
+/// the protocol with the server.  Older servers don't recognize
+/// this and respond with an error, in which case the client falls
+/// back to `GetCodecVersion`.
+#[derive(Deserialize, Serialize, PartialEq, Debug, Clone)]
+pub struct Handshake {
+    pub codec_vers: usize,
+    pub min_codec_vers: usize,
+    pub version_string: String,
+    pub capabilities: Vec<String>,
+}
+
+impl Handshake {
+    /// Describe the protocol spoken by this build
+    pub fn local() -> Self {
+        Self {
+            codec_vers: CODEC_VERSION,
+            min_codec_vers: CODEC_MIN_COMPATIBLE_VERSION,
+            version_string: config::wezterm_version().to_owned(),
+            capabilities: CODEC_CAPABILITIES.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    pub fn is_compatible(&self) -> bool {
+        codec_version_is_compatible(self.codec_vers, self.min_codec_vers)
+    }
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Debug, Clone)]
+pub struct HandshakeResponse {
+    pub codec_vers: usize,
+    pub min_codec_vers: usize,
+    pub version_string: String,
+    pub capabilities: Vec<String>,
+}
+
+impl HandshakeResponse {
+    pub fn is_compatible(&self) -> bool {
+        codec_version_is_compatible(self.codec_vers, self.min_codec_vers)
+    }
+}
+
+impl From<Handshake> for HandshakeResponse {
+    fn from(h: Handshake) -> Self {
+        Self {
+            codec_vers: h.codec_vers,
+            min_codec_vers: h.min_codec_vers,
+            version_string: h.version_string,
+            capabilities: h.capabilities,
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, PartialEq, Debug)]
 pub struct Ping {}
 #[derive(Deserialize, Serialize, PartialEq, Debug)]
@@ -573,7 +658,7 @@ pub struct Spawn {
 #[derive(Deserialize, Serialize, PartialEq, Debug)]
 pub struct SplitPane {
     pub pane_id: PaneId,
-    pub direction: SplitDirection,
+    pub request: SplitRequest,
     pub command: Option<CommandBuilder>,
     pub command_dir: Option<String>,
     pub domain: config::keyassignment::SpawnTabDomain,
@@ -682,6 +767,72 @@ pub struct SetPaneZoomed {
     pub zoomed: bool,
 }
 
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct SetPaneInputMode {
+    pub pane_id: PaneId,
+    pub mode: config::keyassignment::PaneInputMode,
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct SetWindowPosition {
+    /// The window containing this pane is positioned
+    pub pane_id: PaneId,
+    pub position: config::keyassignment::SetWindowPositionArguments,
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct CapturePane {
+    pub pane_id: PaneId,
+    /// Capture the whole window containing the pane, rather than
+    /// just the pane
+    pub whole_window: bool,
+    pub args: config::keyassignment::CaptureArguments,
+}
+
+/// Presents a shared secret to a server that requires one before
+/// it will grant access to its panes
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct Authenticate {
+    pub token: String,
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct AuthenticateResponse {
+    /// If true, the client may view panes but not change them
+    /// or spawn new ones
+    pub read_only: bool,
+}
+
+/// Asks the server to treat this client as read-only, so that it
+/// can watch a session without any risk of interfering with it
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct SetReadOnly {}
+
+/// Tells the server how to describe this client to the other clients
+/// that share its panes
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct SetClientName {
+    pub name: String,
+}
+
+/// Sent by the server, to clients that advertise the `pane_writer`
+/// capability, when a different client than before sends input to
+/// a pane that they are displaying
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct PaneWriterChanged {
+    pub pane_id: PaneId,
+    /// The name of the client that sent the input, or None if it
+    /// was the client that receives this
+    pub writer: Option<String>,
+}
+
+/// Replaces the log filters of the server.  `filters` uses the same
+/// syntax as the `WEZTERM_LOG` environment variable
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct SetLogFilters {
+    pub filters: String,
+}
+
 #[derive(Deserialize, Serialize, PartialEq, Debug)]
 pub struct GetPaneRenderChanges {
     pub pane_id: PaneId,
@@ -707,6 +858,9 @@ pub struct GetPaneRenderChangesResponse {
     pub bonus_lines: SerializedLines,
 
     pub input_serial: Option<InputSerial>,
+    /// The cursor color that was set by an escape sequence (OSC 12),
+    /// or None if the pane uses the configured cursor color
+    pub cursor_color: Option<RgbColor>,
 }
 
 #[derive(Deserialize, Serialize, PartialEq, Debug)]
@@ -727,6 +881,69 @@ struct LineHyperlink {
     coords: Vec<CellCoordinates>,
 }
 
+/// A line whose cell attributes are run length encoded.
+/// Most lines consist of a handful of runs of cells that share the
+/// same attributes, so we only send one copy of the attributes for
+/// each run rather than one per cell.
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+struct RunLengthLine {
+    /// The text of each cell
+    text: Vec<String>,
+    /// The attributes and number of cells of each run, in order
+    runs: Vec<(CellAttributes, usize)>,
+}
+
+impl RunLengthLine {
+    fn new(cells: &[Cell]) -> Self {
+        let mut text = Vec::with_capacity(cells.len());
+        let mut runs: Vec<(CellAttributes, usize)> = vec![];
+        for cell in cells {
+            text.push(cell.str().to_string());
+            match runs.last_mut() {
+                Some((attrs, count)) if attrs == cell.attrs() => *count += 1,
+                _ => runs.push((cell.attrs().clone(), 1)),
+            }
+        }
+        Self { text, runs }
+    }
+
+    fn into_cells(self) -> Vec<Cell> {
+        let mut cells = Vec::with_capacity(self.text.len());
+        let mut text = self.text.into_iter();
+        for (attrs, count) in self.runs {
+            for t in text.by_ref().take(count) {
+                cells.push(Cell::new_grapheme(&t, attrs.clone()));
+            }
+        }
+        cells
+    }
+}
+
+/// A line, either in full, or as the cells that changed since the
+/// line at the same row was last sent; see `LineDeltaBase`.
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+enum SerializedLine {
+    Full(RunLengthLine),
+    /// The cells before `first_col` are the same as those of the base
+    /// line; the rest of the line is replaced by `cells`
+    Delta {
+        first_col: usize,
+        cells: RunLengthLine,
+    },
+}
+
+/// The lines that were most recently sent in a `SerializedLines`
+/// by `SerializedLines::with_deltas`, without their hyperlinks.
+/// The sender and the receiver each keep one of these for a pane, so
+/// that the lines that are sent repeatedly, such as the bonus lines of
+/// `GetPaneRenderChangesResponse`, only need to include the cells that
+/// changed.  This relies on every such `SerializedLines` being received
+/// in the order that it was sent.
+#[derive(Default, Debug)]
+pub struct LineDeltaBase {
+    lines: HashMap<StableRowIndex, Vec<Cell>>,
+}
+
 /// What's all this?
 /// Cells hold references to Arc<Hyperlink> and it is important to us to
 /// maintain identity of the hyperlinks in the individual cells, while also
@@ -736,7 +953,7 @@ struct LineHyperlink {
 /// method is called.
 #[derive(Deserialize, Serialize, PartialEq, Debug)]
 pub struct SerializedLines {
-    lines: Vec<(StableRowIndex, Line)>,
+    lines: Vec<(StableRowIndex, SerializedLine)>,
     hyperlinks: Vec<LineHyperlink>,
     // TODO: image references
 }
@@ -745,64 +962,142 @@ impl SerializedLines {
     pub fn lines(self) -> Vec<(StableRowIndex, Line)> {
         self.into()
     }
-}
 
-impl From<Vec<(StableRowIndex, Line)>> for SerializedLines {
-    fn from(mut lines: Vec<(StableRowIndex, Line)>) -> Self {
-        let mut hyperlinks = vec![];
+    /// Serializes `lines`, sending only the cells that changed for
+    /// lines whose row is present in `base`, and then replaces the
+    /// content of `base` with `lines`.
+    pub fn with_deltas(lines: Vec<(StableRowIndex, Line)>, base: &mut LineDeltaBase) -> Self {
+        let (lines, hyperlinks) = extract_hyperlinks(lines);
+        let serialized = lines
+            .iter()
+            .map(|(row, line)| {
+                let cells = line.cells();
+                let line = match base.lines.get(row) {
+                    Some(prior) => {
+                        let first_col = prior
+                            .iter()
+                            .zip(cells.iter())
+                            .position(|(a, b)| a != b)
+                            .unwrap_or_else(|| prior.len().min(cells.len()));
+                        SerializedLine::Delta {
+                            first_col,
+                            cells: RunLengthLine::new(&cells[first_col..]),
+                        }
+                    }
+                    None => SerializedLine::Full(RunLengthLine::new(cells)),
+                };
+                (*row, line)
+            })
+            .collect();
 
-        for (line_idx, (_, line)) in lines.iter_mut().enumerate() {
-            let mut current_link: Option<Arc<Hyperlink>> = None;
-            let mut current_range = 0..0;
+        base.lines = lines
+            .into_iter()
+            .map(|(row, line)| (row, line.cells().to_vec()))
+            .collect();
 
-            for (x, cell) in line
-                .cells_mut_for_attr_changes_only()
-                .iter_mut()
-                .enumerate()
-            {
-                // Unset the hyperlink on the cell, if any, and record that
-                // in the hyperlinks data for later restoration.
-                if let Some(link) = cell.attrs_mut().hyperlink().map(Arc::clone) {
-                    cell.attrs_mut().set_hyperlink(None);
-                    match current_link.as_ref() {
-                        Some(current) if Arc::ptr_eq(&current, &link) => {
-                            // Continue the current streak
-                            current_range = range_union(current_range, x..x + 1);
-                        }
-                        Some(prior) => {
-                            // It's a different URL, push the current data and start a new one
-                            hyperlinks.push(LineHyperlink {
-                                link: (**prior).clone(),
-                                coords: vec![CellCoordinates {
-                                    line_idx,
-                                    cols: current_range,
-                                }],
-                            });
-                            current_range = x..x + 1;
-                            current_link = Some(link);
-                        }
-                        None => {
-                            // Starting a new streak
-                            current_range = x..x + 1;
-                            current_link = Some(link);
+        Self {
+            lines: serialized,
+            hyperlinks,
+        }
+    }
+
+    /// Returns the lines that were serialized by `with_deltas`, using
+    /// and then updating the `base` of the receiver.
+    /// A line that was sent as a delta against a line that is not in
+    /// `base` cannot be reconstructed, and is returned as None.
+    pub fn lines_with_deltas(
+        self,
+        base: &mut LineDeltaBase,
+    ) -> Vec<(StableRowIndex, Option<Line>)> {
+        let mut lines: Vec<(StableRowIndex, Option<Vec<Cell>>)> = self
+            .lines
+            .into_iter()
+            .map(|(row, line)| {
+                let cells = match line {
+                    SerializedLine::Full(line) => Some(line.into_cells()),
+                    SerializedLine::Delta { first_col, cells } => {
+                        base.lines.get(&row).and_then(|prior| {
+                            let mut prior = prior.get(..first_col)?.to_vec();
+                            prior.extend(cells.into_cells());
+                            Some(prior)
+                        })
+                    }
+                };
+                (row, cells)
+            })
+            .collect();
+
+        base.lines = lines
+            .iter()
+            .filter_map(|(row, cells)| cells.as_ref().map(|cells| (*row, cells.clone())))
+            .collect();
+
+        for link in self.hyperlinks {
+            let url = Arc::new(link.link);
+
+            for coord in link.coords {
+                if let Some((_, Some(cells))) = lines.get_mut(coord.line_idx) {
+                    if let Some(cells) = cells.get_mut(coord.cols) {
+                        for cell in cells {
+                            cell.attrs_mut().set_hyperlink(Some(Arc::clone(&url)));
                         }
                     }
-                } else if let Some(link) = current_link.take() {
-                    // Wrap up a prior streak
-                    hyperlinks.push(LineHyperlink {
-                        link: (*link).clone(),
-                        coords: vec![CellCoordinates {
-                            line_idx,
-                            cols: current_range,
-                        }],
-                    });
-                    current_range = 0..0;
                 }
-
-                // TODO: something smart for image cells
             }
-            if let Some(link) = current_link.take() {
-                // Wrap up final streak
+        }
+
+        lines
+            .into_iter()
+            .map(|(row, cells)| (row, cells.map(Line::from_cells)))
+            .collect()
+    }
+}
+
+/// Removes the hyperlinks from the cells of `lines`, returning the
+/// lines along with a description of where the hyperlinks were
+fn extract_hyperlinks(
+    mut lines: Vec<(StableRowIndex, Line)>,
+) -> (Vec<(StableRowIndex, Line)>, Vec<LineHyperlink>) {
+    let mut hyperlinks = vec![];
+
+    for (line_idx, (_, line)) in lines.iter_mut().enumerate() {
+        let mut current_link: Option<Arc<Hyperlink>> = None;
+        let mut current_range = 0..0;
+
+        for (x, cell) in line
+            .cells_mut_for_attr_changes_only()
+            .iter_mut()
+            .enumerate()
+        {
+            // Unset the hyperlink on the cell, if any, and record that
+            // in the hyperlinks data for later restoration.
+            if let Some(link) = cell.attrs_mut().hyperlink().map(Arc::clone) {
+                cell.attrs_mut().set_hyperlink(None);
+                match current_link.as_ref() {
+                    Some(current) if Arc::ptr_eq(&current, &link) => {
+                        // Continue the current streak
+                        current_range = range_union(current_range, x..x + 1);
+                    }
+                    Some(prior) => {
+                        // It's a different URL, push the current data and start a new one
+                        hyperlinks.push(LineHyperlink {
+                            link: (**prior).clone(),
+                            coords: vec![CellCoordinates {
+                                line_idx,
+                                cols: current_range,
+                            }],
+                        });
+                        current_range = x..x + 1;
+                        current_link = Some(link);
+                    }
+                    None => {
+                        // Starting a new streak
+                        current_range = x..x + 1;
+                        current_link = Some(link);
+                    }
+                }
+            } else if let Some(link) = current_link.take() {
+                // Wrap up a prior streak
                 hyperlinks.push(LineHyperlink {
                     link: (*link).clone(),
                     coords: vec![CellCoordinates {
@@ -810,8 +1105,33 @@ impl From<Vec<(StableRowIndex, Line)>> for SerializedLines {
                         cols: current_range,
                     }],
                 });
+                current_range = 0..0;
             }
+
+            // TODO: something smart for image cells
+        }
+        if let Some(link) = current_link.take() {
+            // Wrap up final streak
+            hyperlinks.push(LineHyperlink {
+                link: (*link).clone(),
+                coords: vec![CellCoordinates {
+                    line_idx,
+                    cols: current_range,
+                }],
+            });
         }
+    }
+
+    (lines, hyperlinks)
+}
+
+impl From<Vec<(StableRowIndex, Line)>> for SerializedLines {
+    fn from(lines: Vec<(StableRowIndex, Line)>) -> Self {
+        let (lines, hyperlinks) = extract_hyperlinks(lines);
+        let lines = lines
+            .iter()
+            .map(|(row, line)| (*row, SerializedLine::Full(RunLengthLine::new(line.cells()))))
+            .collect();
 
         Self { lines, hyperlinks }
     }
@@ -819,31 +1139,14 @@ impl From<Vec<(StableRowIndex, Line)>> for SerializedLines {
 
 /// Reconsitute hyperlinks or other attributes that were decomposed for
 /// serialization, and return the line data.
+/// Lines that were sent as deltas are omitted, as there is no base
+/// to apply them to; use `lines_with_deltas` to receive those.
 impl Into<Vec<(StableRowIndex, Line)>> for SerializedLines {
     fn into(self) -> Vec<(StableRowIndex, Line)> {
-        if self.hyperlinks.is_empty() {
-            self.lines
-        } else {
-            let mut lines = self.lines;
-
-            for link in self.hyperlinks {
-                let url = Arc::new(link.link);
-
-                for coord in link.coords {
-                    if let Some((_, line)) = lines.get_mut(coord.line_idx) {
-                        if let Some(cells) =
-                            line.cells_mut_for_attr_changes_only().get_mut(coord.cols)
-                        {
-                            for cell in cells {
-                                cell.attrs_mut().set_hyperlink(Some(Arc::clone(&url)));
-                            }
-                        }
-                    }
-                }
-            }
-
-            lines
-        }
+        self.lines_with_deltas(&mut LineDeltaBase::default())
+            .into_iter()
+            .filter_map(|(row, line)| line.map(|line| (row, line)))
+            .collect()
     }
 }
 
@@ -972,6 +1275,114 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_version_compat() {
+        assert!(codec_version_is_compatible(
+            CODEC_VERSION,
+            CODEC_MIN_COMPATIBLE_VERSION
+        ));
+        // A newer peer that can still speak our version
+        assert!(codec_version_is_compatible(
+            CODEC_VERSION + 1,
+            CODEC_VERSION
+        ));
+        // A newer peer that dropped support for our version
+        assert!(!codec_version_is_compatible(
+            CODEC_VERSION + 1,
+            CODEC_VERSION + 1
+        ));
+        // A peer older than we're prepared to talk to
+        assert!(!codec_version_is_compatible(
+            CODEC_MIN_COMPATIBLE_VERSION - 1,
+            CODEC_MIN_COMPATIBLE_VERSION - 1
+        ));
+    }
+
+    #[test]
+    fn test_serialized_lines_round_trip() {
+        let mut bold = CellAttributes::default();
+        bold.set_intensity(termwiz::cell::Intensity::Bold);
+        let link = Arc::new(Hyperlink::new("https://example.com"));
+        let mut linked = CellAttributes::default();
+        linked.set_hyperlink(Some(Arc::clone(&link)));
+
+        let mut line = Line::from_text("hello ", &bold);
+        line.append_line(Line::from_text("wide 中 ", &CellAttributes::default()));
+        line.append_line(Line::from_text("link", &linked));
+        line.set_last_cell_was_wrapped(true);
+
+        let lines = vec![(1, line.clone()), (2, Line::with_width(4))];
+        let serialized: SerializedLines = lines.clone().into();
+        match &serialized.lines[0].1 {
+            SerializedLine::Full(line) => assert_eq!(line.runs.len(), 3),
+            line => panic!("unexpected {:?}", line),
+        }
+
+        let mut encoded = vec![];
+        Pdu::GetLinesResponse(GetLinesResponse {
+            pane_id: 0,
+            lines: serialized,
+        })
+        .encode(&mut encoded, 1)
+        .unwrap();
+        let decoded = match Pdu::decode(encoded.as_slice()).unwrap().pdu {
+            Pdu::GetLinesResponse(res) => res.lines.lines(),
+            pdu => panic!("unexpected {:?}", pdu),
+        };
+
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0].1.cells(), line.cells());
+        assert!(decoded[0].1.last_cell_was_wrapped());
+        assert!(decoded[0].1.has_hyperlink());
+        assert_eq!(decoded[1].1.cells(), lines[1].1.cells());
+    }
+
+    #[test]
+    fn test_serialized_line_deltas() {
+        let link = Arc::new(Hyperlink::new("https://example.com"));
+        let mut linked = CellAttributes::default();
+        linked.set_hyperlink(Some(Arc::clone(&link)));
+
+        let mut prompt = Line::from_text("$ ls ", &CellAttributes::default());
+        prompt.append_line(Line::from_text("link", &linked));
+        let lines = vec![(1, prompt.clone()), (2, Line::from_text("a", &linked))];
+
+        let mut sender = LineDeltaBase::default();
+        let mut receiver = LineDeltaBase::default();
+
+        let first = SerializedLines::with_deltas(lines.clone(), &mut sender);
+        assert!(matches!(first.lines[0].1, SerializedLine::Full(_)));
+        let decoded = first.lines_with_deltas(&mut receiver);
+        assert_eq!(decoded[0].1.as_ref().unwrap().cells(), prompt.cells());
+
+        // Only the changed tail of row 1 is sent; row 3 is new
+        let mut typed = Line::from_text("$ ls -l", &CellAttributes::default());
+        typed.append_line(Line::from_text("link", &linked));
+        let lines = vec![(1, typed.clone()), (3, Line::from_text("b", &linked))];
+        let second = SerializedLines::with_deltas(lines, &mut sender);
+        match &second.lines[0].1 {
+            SerializedLine::Delta { first_col, cells } => {
+                assert_eq!(*first_col, 5);
+                assert_eq!(cells.text.len(), 6);
+            }
+            line => panic!("unexpected {:?}", line),
+        }
+        assert!(matches!(second.lines[1].1, SerializedLine::Full(_)));
+
+        let decoded = second.lines_with_deltas(&mut receiver);
+        let line = decoded[0].1.as_ref().unwrap();
+        assert_eq!(line.cells(), typed.cells());
+        assert!(line.has_hyperlink());
+        assert_eq!(decoded[1].0, 3);
+
+        // Without the prior line, a delta cannot be applied
+        let mut lines = vec![(1, typed.clone())];
+        lines[0].1.set_last_cell_was_wrapped(true);
+        let third = SerializedLines::with_deltas(lines, &mut sender);
+        let decoded = third.lines_with_deltas(&mut LineDeltaBase::default());
+        assert!(decoded[0].1.is_none());
+    }
+
     #[test]
     fn test_bogus_pdu() {
         let mut encoded = Vec::new();
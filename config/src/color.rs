@@ -24,6 +24,57 @@ impl Default for HsbTransform {
     }
 }
 
+/// A color that can also be given as "none", for colors that need
+/// not override the color of the cell
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ColorOrNone {
+    Color(RgbColor),
+    None,
+}
+
+impl From<RgbColor> for ColorOrNone {
+    fn from(color: RgbColor) -> Self {
+        Self::Color(color)
+    }
+}
+
+impl From<ColorOrNone> for Option<RgbColor> {
+    fn from(color: ColorOrNone) -> Self {
+        match color {
+            ColorOrNone::Color(color) => Some(color),
+            ColorOrNone::None => None,
+        }
+    }
+}
+
+impl Serialize for ColorOrNone {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Self::Color(color) => color.serialize(serializer),
+            Self::None => "none".serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ColorOrNone {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        if s.eq_ignore_ascii_case("none") {
+            return Ok(Self::None);
+        }
+        RgbColor::from_named_or_rgb_string(&s)
+            .map(Self::Color)
+            .ok_or_else(|| format!("unknown color name: {}", s))
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 #[derive(Default, Debug, Deserialize, Serialize, Clone)]
 pub struct Palette {
     /// The text color to use when the attributes are reset to default
@@ -34,8 +85,8 @@ pub struct Palette {
     pub cursor_fg: Option<RgbColor>,
     pub cursor_bg: Option<RgbColor>,
     pub cursor_border: Option<RgbColor>,
-    /// The color of selected text
-    pub selection_fg: Option<RgbColor>,
+    /// The color of selected text; "none" keeps the color of the text
+    pub selection_fg: Option<ColorOrNone>,
     pub selection_bg: Option<RgbColor>,
     /// A list of 8 colors corresponding to the basic ANSI palette
     pub ansi: Option<[RgbColor; 8]>,
@@ -49,6 +100,15 @@ pub struct Palette {
     pub scrollbar_thumb: Option<RgbColor>,
     /// The color of the split line between panes
     pub split: Option<RgbColor>,
+    /// The color of the parts of the split lines that border the
+    /// active pane
+    pub active_split: Option<RgbColor>,
+    /// The background color used to mark invisible characters
+    /// when they are being shown
+    pub invisible_character: Option<RgbColor>,
+    /// The color that flashes over the pane or window when the visual
+    /// bell rings
+    pub visual_bell: Option<RgbColor>,
 }
 impl_lua_conversion!(Palette);
 
@@ -67,10 +127,15 @@ impl From<Palette> for wezterm_term::color::ColorPalette {
         apply_color!(cursor_fg);
         apply_color!(cursor_bg);
         apply_color!(cursor_border);
-        apply_color!(selection_fg);
+        if let Some(selection_fg) = cfg.selection_fg {
+            p.selection_fg = selection_fg.into();
+        }
         apply_color!(selection_bg);
         apply_color!(scrollbar_thumb);
         apply_color!(split);
+        apply_color!(active_split);
+        apply_color!(invisible_character);
+        apply_color!(visual_bell);
 
         if let Some(ansi) = cfg.ansi {
             for (idx, col) in ansi.iter().enumerate() {
@@ -86,6 +151,43 @@ impl From<Palette> for wezterm_term::color::ColorPalette {
     }
 }
 
+/// The system-wide light/dark appearance as reported by the
+/// windowing environment.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum Appearance {
+    Light,
+    Dark,
+}
+impl_lua_conversion!(Appearance);
+
+impl Default for Appearance {
+    fn default() -> Self {
+        Self::Light
+    }
+}
+
+/// Selects a color scheme based on the current system `Appearance`
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct AppearanceColorSchemes {
+    /// The name of the scheme to use when the appearance is `Light`
+    #[serde(default)]
+    pub light: Option<String>,
+    /// The name of the scheme to use when the appearance is `Dark`
+    #[serde(default)]
+    pub dark: Option<String>,
+}
+impl_lua_conversion!(AppearanceColorSchemes);
+
+impl AppearanceColorSchemes {
+    /// Returns the name of the scheme for `appearance`, if one was set
+    pub fn scheme_for(&self, appearance: Appearance) -> Option<&String> {
+        match appearance {
+            Appearance::Light => self.light.as_ref(),
+            Appearance::Dark => self.dark.as_ref(),
+        }
+    }
+}
+
 /// Specify the text styling for a tab in the tab bar
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
 pub struct TabBarColor {
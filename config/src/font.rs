@@ -187,6 +187,11 @@ pub struct TextStyle {
     /// useful in a `[[font_rules]]` section to implement changing
     /// the text color for eg: bold text.
     pub foreground: Option<RgbColor>,
+
+    /// If set, overrides the `allow_square_glyphs_to_overflow_width`
+    /// configuration for text rendered using this style.
+    #[serde(default)]
+    pub allow_square_glyphs_to_overflow_width: Option<AllowSquareGlyphOverflow>,
 }
 impl_lua_conversion!(TextStyle);
 
@@ -195,6 +200,7 @@ impl Default for TextStyle {
         Self {
             foreground: None,
             font: vec![FontAttributes::default()],
+            allow_square_glyphs_to_overflow_width: None,
         }
     }
 }
@@ -233,6 +239,7 @@ impl TextStyle {
         }
         Self {
             foreground: self.foreground,
+            allow_square_glyphs_to_overflow_width: self.allow_square_glyphs_to_overflow_width,
             font: self
                 .font
                 .iter()
@@ -252,6 +259,7 @@ impl TextStyle {
     pub fn make_bold(&self) -> Self {
         Self {
             foreground: self.foreground,
+            allow_square_glyphs_to_overflow_width: self.allow_square_glyphs_to_overflow_width,
             font: self
                 .font
                 .iter()
@@ -269,6 +277,7 @@ impl TextStyle {
     pub fn make_italic(&self) -> Self {
         Self {
             foreground: self.foreground,
+            allow_square_glyphs_to_overflow_width: self.allow_square_glyphs_to_overflow_width,
             font: self
                 .font
                 .iter()
@@ -351,7 +360,7 @@ pub struct StyleRule {
 }
 impl_lua_conversion!(StyleRule);
 
-#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AllowSquareGlyphOverflow {
     Never,
     Always,
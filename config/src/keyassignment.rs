@@ -1,10 +1,11 @@
 use crate::configuration;
-use crate::LeaderKey;
+use crate::{KeyNoAction, LeaderKey};
 use luahelper::impl_lua_conversion;
+use portable_pty::CommandBuilder;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
-use wezterm_input_types::{KeyCode, Modifiers};
+use wezterm_input_types::{KeyCode, Modifiers, SwipeDirection};
 use wezterm_term::input::MouseButton;
 
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq)]
@@ -57,6 +58,22 @@ pub enum MouseEventTrigger {
     Up { streak: usize, button: MouseButton },
 }
 
+/// A touchpad gesture that can trigger an action
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub enum GestureTrigger {
+    /// The fingers are moving apart.  This is triggered repeatedly
+    /// as the pinch continues.
+    PinchOut,
+    /// The fingers are moving together.  This is triggered repeatedly
+    /// as the pinch continues.
+    PinchIn,
+    /// The fingers swiped in `direction` and were lifted
+    Swipe {
+        direction: SwipeDirection,
+        fingers: u8,
+    },
+}
+
 /// When spawning a tab, specify which domain should be used to
 /// host/spawn that tab.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
@@ -103,6 +120,288 @@ pub struct SpawnCommand {
     pub domain: SpawnTabDomain,
 }
 
+impl SpawnCommand {
+    /// Builds the command described by `args`, or returns None if the
+    /// default program for the domain should be used
+    pub fn command_builder(&self) -> Option<CommandBuilder> {
+        let args = self.args.as_ref()?;
+        let mut builder = CommandBuilder::from_argv(args.iter().map(Into::into).collect());
+        for (k, v) in self.set_environment_variables.iter() {
+            builder.env(k, v);
+        }
+        if let Some(cwd) = self.cwd.as_ref() {
+            builder.cwd(cwd);
+        }
+        Some(builder)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SplitDirection {
+    Horizontal,
+    Vertical,
+}
+
+/// The size of the pane that is created by splitting another pane
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum SplitSize {
+    /// A number of cells
+    Cells(usize),
+    /// A percentage of the space occupied by the pane being split
+    Percent(u8),
+}
+
+impl Default for SplitSize {
+    fn default() -> Self {
+        Self::Percent(50)
+    }
+}
+
+impl SplitSize {
+    /// Evaluates the size of the new pane in cells, when splitting
+    /// a pane that is `total` cells in size.  `total` includes the
+    /// cell that is used to render the split itself.
+    pub fn evaluate(self, total: usize) -> usize {
+        match self {
+            Self::Cells(n) => n,
+            Self::Percent(p) => total.saturating_sub(1) * p.min(100) as usize / 100,
+        }
+    }
+}
+
+impl std::str::FromStr for SplitSize {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let s = s.trim();
+        if let Some(p) = s.strip_suffix('%') {
+            Ok(Self::Percent(p.trim().parse()?))
+        } else {
+            Ok(Self::Cells(s.parse().map_err(|_| {
+                anyhow::anyhow!("invalid size {}; expected eg: 20 or 50%", s)
+            })?))
+        }
+    }
+}
+
+/// Describes a tree of panes to be created in a new tab.
+/// The tab starts out with a single pane running `command`, and then
+/// each of the `splits` is split off from that pane in turn.
+#[derive(Default, Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PaneLayout {
+    #[serde(default)]
+    pub command: SpawnCommand,
+    #[serde(default)]
+    pub splits: Vec<PaneLayoutSplit>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PaneLayoutSplit {
+    pub direction: SplitDirection,
+    /// The size of the newly created pane
+    #[serde(default)]
+    pub size: SplitSize,
+    /// The contents of the newly created pane.  The `domain` of its
+    /// command is ignored; splits are always created in the same
+    /// domain as the pane that is being split.
+    #[serde(default)]
+    pub layout: PaneLayout,
+}
+
+/// A preset arrangement of the panes in a tab, used by `ArrangePanes`
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum PaneArrangement {
+    /// The active pane fills the left of the tab, and the others
+    /// are stacked top to bottom on the right
+    MainVertical,
+    /// The active pane fills the top of the tab, and the others
+    /// are placed side by side below it
+    MainHorizontal,
+    /// The panes are arranged in a grid of equally sized cells
+    Tiled,
+}
+
+/// Where the text to be opened by `OpenWith` is taken from
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum OpenWithSource {
+    /// The hyperlink under the mouse cursor
+    LinkAtMouseCursor,
+    /// The selected text in the active pane
+    Selection,
+}
+
+impl Default for OpenWithSource {
+    fn default() -> Self {
+        Self::LinkAtMouseCursor
+    }
+}
+
+/// Activates the named key table from the `key_tables` configuration.
+/// The table remains active until a key from it is pressed, an
+/// unmapped key is pressed, or the optional timeout elapses.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ActivateKeyTable {
+    pub name: String,
+    #[serde(default)]
+    pub timeout_milliseconds: Option<u64>,
+}
+
+/// What happens to the pane that is chosen by `PaneSelect`
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum PaneSelectMode {
+    /// Make the chosen pane the active pane
+    Activate,
+    /// Swap the position of the chosen pane with the active pane
+    SwapWithActive,
+}
+
+impl Default for PaneSelectMode {
+    fn default() -> Self {
+        Self::Activate
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PaneSelectArguments {
+    /// The characters used to label the panes, in pane order.
+    #[serde(default = "default_pane_select_alphabet")]
+    pub alphabet: String,
+    #[serde(default)]
+    pub mode: PaneSelectMode,
+}
+
+impl Default for PaneSelectArguments {
+    fn default() -> Self {
+        Self {
+            alphabet: default_pane_select_alphabet(),
+            mode: PaneSelectMode::default(),
+        }
+    }
+}
+
+fn default_pane_select_alphabet() -> String {
+    "1234567890abcdefghijklmnopqrstuvwxyz".to_string()
+}
+
+/// A coordinate or size used by `SetWindowPosition`, expressed either
+/// in pixels or as a percentage of the size of the monitor
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum WindowDimension {
+    Pixels(isize),
+    Percent(u16),
+}
+
+impl WindowDimension {
+    /// Evaluates the dimension in pixels, relative to `total` pixels
+    pub fn evaluate(self, total: isize) -> isize {
+        match self {
+            Self::Pixels(n) => n,
+            Self::Percent(p) => total * p as isize / 100,
+        }
+    }
+}
+
+impl std::str::FromStr for WindowDimension {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let s = s.trim();
+        if let Some(p) = s.strip_suffix('%') {
+            Ok(Self::Percent(p.trim().parse()?))
+        } else {
+            let n = s.strip_suffix("px").unwrap_or(s);
+            Ok(Self::Pixels(n.trim().parse().map_err(|_| {
+                anyhow::anyhow!("invalid dimension {}; expected eg: 800 or 50%", s)
+            })?))
+        }
+    }
+}
+
+/// Moves and/or resizes the window.  Coordinates are relative to the
+/// top left corner of `monitor`.  The window is only moved if `x` or
+/// `y` is specified, with the other defaulting to 0, and is only
+/// resized if `width` or `height` is specified.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct SetWindowPositionArguments {
+    /// The monitor to position the window on; 0 is the primary monitor
+    #[serde(default)]
+    pub monitor: usize,
+    #[serde(default)]
+    pub x: Option<WindowDimension>,
+    #[serde(default)]
+    pub y: Option<WindowDimension>,
+    #[serde(default)]
+    pub width: Option<WindowDimension>,
+    #[serde(default)]
+    pub height: Option<WindowDimension>,
+}
+
+/// Controls where `CapturePane` and `CaptureWindow` save the image
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct CaptureArguments {
+    /// The PNG file to write.  When not specified, a file named after
+    /// the current time is created in the home directory.
+    #[serde(default)]
+    pub path: Option<PathBuf>,
+    /// Whether `CaptureWindow` includes the tab bar in the image
+    #[serde(default = "crate::default_true")]
+    pub include_tab_bar: bool,
+}
+
+impl Default for CaptureArguments {
+    fn default() -> Self {
+        Self {
+            path: None,
+            include_tab_bar: true,
+        }
+    }
+}
+
+impl CaptureArguments {
+    /// Returns the file that the capture should be written to
+    pub fn resolve_path(&self) -> PathBuf {
+        match &self.path {
+            Some(path) => path.clone(),
+            None => crate::HOME_DIR.join(format!(
+                "wezterm-capture-{}.png",
+                chrono::Local::now().format("%Y%m%d-%H%M%S")
+            )),
+        }
+    }
+}
+
+/// Where the command launched by `OpenWith` should run
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum OpenWithTarget {
+    /// Run the command in the background, detached from any pane
+    Background,
+    /// Run the command in a new tab in the current window
+    NewTab,
+    /// Run the command in a new window
+    NewWindow,
+}
+
+impl Default for OpenWithTarget {
+    fn default() -> Self {
+        Self::Background
+    }
+}
+
+#[derive(Default, Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct OpenWithCommand {
+    /// The command line template.
+    /// Each argument may reference `{url}`, `{file}`, `{line}` and
+    /// `{column}`, which are substituted with the corresponding
+    /// portions of the text that is being opened.
+    /// An argument of the form `$NAME` is replaced by the
+    /// whitespace separated words of the environment variable `NAME`.
+    pub args: Vec<String>,
+
+    #[serde(default)]
+    pub source: OpenWithSource,
+
+    #[serde(default)]
+    pub target: OpenWithTarget,
+}
+
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
 pub enum PaneDirection {
     Up,
@@ -148,6 +447,45 @@ impl Default for ClipboardPasteSource {
     }
 }
 
+/// Controls what happens to keyboard and paste input that
+/// is sent to a pane
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum PaneInputMode {
+    /// Input is delivered to the pane
+    Normal,
+    /// Input is discarded
+    ReadOnly,
+    /// Input is delivered to all of the panes in the same tab
+    /// that are not read-only
+    Broadcast,
+    /// The pane is part of the selected set of its tab; input is
+    /// delivered to every pane in the same tab that is in this mode
+    BroadcastToSelected,
+}
+
+impl Default for PaneInputMode {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+impl std::str::FromStr for PaneInputMode {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.to_lowercase().as_str() {
+            "normal" => Ok(Self::Normal),
+            "readonly" | "read-only" => Ok(Self::ReadOnly),
+            "broadcast" => Ok(Self::Broadcast),
+            "broadcasttoselected" | "broadcast-to-selected" => Ok(Self::BroadcastToSelected),
+            _ => anyhow::bail!(
+                "invalid input mode {}; expected one of Normal, ReadOnly, \
+                 Broadcast, BroadcastToSelected",
+                s
+            ),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
 pub enum KeyAssignment {
     SpawnTab(SpawnTabDomain),
@@ -155,6 +493,8 @@ pub enum KeyAssignment {
     ToggleFullScreen,
     Copy,
     CopyTo(ClipboardCopyDestination),
+    CopyAsHtml(ClipboardCopyDestination),
+    CopyAsAnsi(ClipboardCopyDestination),
     Paste,
     PastePrimarySelection,
     PasteFrom(ClipboardPasteSource),
@@ -165,7 +505,11 @@ pub enum KeyAssignment {
     ResetFontAndWindowSize,
     ActivateTab(isize),
     ActivateLastTab,
+    ActivateTabRelativeMRU(isize),
+    ActivateWindow(usize),
+    ActivateWindowRelative(isize),
     SendString(String),
+    SendKey(KeyNoAction),
     Nop,
     DisableDefaultAssignment,
     Hide,
@@ -177,14 +521,22 @@ pub enum KeyAssignment {
     ScrollByPage(isize),
     ScrollByLine(isize),
     ScrollToPrompt(isize),
+    ScrollToTop,
+    ScrollToBottom,
     ShowTabNavigator,
+    ShowPaneNavigator,
     HideApplication,
     QuitApplication,
+    ToggleSecureKeyboardEntry,
+    ShowContextMenu,
+    OpenConfigFile,
+    ShowDebugOverlay,
     SpawnCommandInNewTab(SpawnCommand),
     SpawnCommandInNewWindow(SpawnCommand),
     SplitHorizontal(SpawnCommand),
     SplitVertical(SpawnCommand),
     ShowLauncher,
+    ShowCommandHistory,
     ClearScrollback(ScrollbackEraseMode),
     Search(Pattern),
     ActivateCopyMode,
@@ -192,32 +544,86 @@ pub enum KeyAssignment {
     SelectTextAtMouseCursor(SelectionMode),
     ExtendSelectionToMouseCursor(Option<SelectionMode>),
     OpenLinkAtMouseCursor,
+    OpenWith(OpenWithCommand),
     CompleteSelection(ClipboardCopyDestination),
     CompleteSelectionOrOpenLinkAtMouseCursor(ClipboardCopyDestination),
     StartWindowDrag,
 
     AdjustPaneSize(PaneDirection, usize),
     ActivatePaneDirection(PaneDirection),
+    ActivatePaneRelativeMRU(isize),
     TogglePaneZoomState,
     CloseCurrentPane { confirm: bool },
     EmitEvent(String),
+    TogglePaneInputMode(PaneInputMode),
+    ActivateKeyTable(ActivateKeyTable),
+    CharSelect,
+    PasteFromHistory,
+    PaneSelect(PaneSelectArguments),
+    TogglePaneEscapeDebugMode,
+    TogglePauseOutput,
+    ToggleInvisibleCharacters,
+    CapturePane(CaptureArguments),
+    CaptureWindow(CaptureArguments),
+    SetWindowPosition(SetWindowPositionArguments),
+    SpawnTabLayout(String),
+    BalancePanes(SplitDirection),
+    ArrangePanes(PaneArrangement),
+    SpawnCommandInFloatingPane(SpawnCommand),
+    DismissFloatingPane,
+    RestartPane { confirm: bool },
 }
 impl_lua_conversion!(KeyAssignment);
 
+/// Produce a short human readable description of a key press,
+/// such as `CTRL-x`, for display to the user
+pub fn describe_key(key: &KeyCode, mods: Modifiers) -> String {
+    let mut result = String::new();
+    for (mask, label) in &[
+        (Modifiers::SUPER, "SUPER"),
+        (Modifiers::CTRL, "CTRL"),
+        (
+            Modifiers::ALT | Modifiers::LEFT_ALT | Modifiers::RIGHT_ALT,
+            "ALT",
+        ),
+        (Modifiers::SHIFT, "SHIFT"),
+    ] {
+        if mods.intersects(*mask) {
+            result.push_str(label);
+            result.push('-');
+        }
+    }
+    match key {
+        // Bindings are normalized so that SHIFT is implied by an
+        // uppercase letter; spell it out
+        KeyCode::Char(c) if c.is_ascii_uppercase() && !mods.contains(Modifiers::SHIFT) => {
+            result.push_str("SHIFT-");
+            result.push(*c);
+        }
+        KeyCode::Char(c) => result.push(*c),
+        key => result.push_str(&format!("{:?}", key)),
+    }
+    result
+}
+
 pub struct InputMap {
     keys: HashMap<(KeyCode, Modifiers), KeyAssignment>,
     mouse: HashMap<(MouseEventTrigger, Modifiers), KeyAssignment>,
+    gestures: HashMap<GestureTrigger, KeyAssignment>,
     leader: Option<LeaderKey>,
+    key_tables: HashMap<String, HashMap<(KeyCode, Modifiers), KeyAssignment>>,
 }
 
 impl InputMap {
     pub fn new() -> Self {
         let config = configuration();
         let mut mouse = config.mouse_bindings();
+        let mut gestures = config.gesture_bindings();
 
         let mut keys = config.key_bindings();
 
         let leader = config.leader.clone();
+        let key_tables = config.key_table_bindings();
 
         macro_rules! k {
             ($([$mod:expr, $code:expr, $action:expr]),* $(,)?) => {
@@ -233,6 +639,13 @@ impl InputMap {
                 )*
             };
         }
+        macro_rules! g {
+            ($([$code:expr, $action:expr]),* $(,)?) => {
+                $(
+                gestures.entry($code).or_insert($action);
+                )*
+            };
+        }
 
         use KeyAssignment::*;
 
@@ -346,6 +759,23 @@ impl InputMap {
                 [ctrl_shift, KeyCode::Char('7'), ActivateTab(6)],
                 [ctrl_shift, KeyCode::Char('8'), ActivateTab(7)],
                 [ctrl_shift, KeyCode::Char('9'), ActivateTab(-1)],
+            );
+
+            let tab_keys = &config.tab_activation_keys;
+            if tab_keys.mods != Modifiers::NONE {
+                for (idx, c) in "12345678".chars().enumerate() {
+                    keys.entry(KeyCode::Char(c).normalize_shift(tab_keys.mods))
+                        .or_insert(ActivateTab(idx as isize));
+                }
+                keys.entry(KeyCode::Char('9').normalize_shift(tab_keys.mods))
+                    .or_insert(ActivateTab(-1));
+            }
+            if let Some(last_tab) = &tab_keys.last_tab {
+                keys.entry(last_tab.key.normalize_shift(last_tab.mods))
+                    .or_insert(ActivateLastTab);
+            }
+
+            k!(
                 [
                     Modifiers::CTRL,
                     KeyCode::Char('W'),
@@ -375,6 +805,8 @@ impl InputMap {
                 [Modifiers::SHIFT, KeyCode::PageDown, ScrollByPage(1)],
                 [Modifiers::ALT, KeyCode::Char('9'), ShowTabNavigator],
                 [Modifiers::CTRL, KeyCode::Char('X'), ActivateCopyMode],
+                [Modifiers::CTRL, KeyCode::Char('U'), CharSelect],
+                [Modifiers::CTRL, KeyCode::Char('L'), ShowDebugOverlay],
                 [
                     Modifiers::CTRL | Modifiers::ALT | Modifiers::SHIFT,
                     KeyCode::Char('"'),
@@ -525,14 +957,6 @@ impl InputMap {
                     },
                     ExtendSelectionToMouseCursor(Some(SelectionMode::Line))
                 ],
-                [
-                    Modifiers::NONE,
-                    MouseEventTrigger::Down {
-                        streak: 1,
-                        button: MouseButton::Middle
-                    },
-                    PasteFrom(ClipboardPasteSource::PrimarySelection)
-                ],
                 [
                     Modifiers::SUPER,
                     MouseEventTrigger::Drag {
@@ -550,15 +974,61 @@ impl InputMap {
                     StartWindowDrag
                 ],
             );
+
+            if config.enable_middle_click_paste {
+                m!([
+                    Modifiers::NONE,
+                    MouseEventTrigger::Down {
+                        streak: 1,
+                        button: MouseButton::Middle
+                    },
+                    PasteFrom(config.middle_click_paste_source)
+                ]);
+            }
+
+            if config.enable_context_menu {
+                m!([
+                    Modifiers::NONE,
+                    MouseEventTrigger::Up {
+                        streak: 1,
+                        button: MouseButton::Right
+                    },
+                    ShowContextMenu
+                ]);
+            }
+        }
+
+        if !config.disable_default_gesture_bindings {
+            g!(
+                [GestureTrigger::PinchOut, IncreaseFontSize],
+                [GestureTrigger::PinchIn, DecreaseFontSize],
+                [
+                    GestureTrigger::Swipe {
+                        direction: SwipeDirection::Left,
+                        fingers: 3
+                    },
+                    ActivateTabRelative(1)
+                ],
+                [
+                    GestureTrigger::Swipe {
+                        direction: SwipeDirection::Right,
+                        fingers: 3
+                    },
+                    ActivateTabRelative(-1)
+                ],
+            );
         }
 
         keys.retain(|_, v| *v != KeyAssignment::DisableDefaultAssignment);
         mouse.retain(|_, v| *v != KeyAssignment::DisableDefaultAssignment);
+        gestures.retain(|_, v| *v != KeyAssignment::DisableDefaultAssignment);
 
         Self {
             keys,
             leader,
+            key_tables,
             mouse,
+            gestures,
         }
     }
 
@@ -583,9 +1053,40 @@ impl InputMap {
             .cloned()
     }
 
+    /// Returns a description, such as `CTRL-SHIFT-W`, of the shortest
+    /// key binding for any of `actions`, or None if none of them are
+    /// bound to a key
+    pub fn describe_key_for(&self, actions: &[KeyAssignment]) -> Option<String> {
+        self.keys
+            .iter()
+            .filter(|(_, action)| actions.contains(action))
+            .map(|((key, mods), _)| describe_key(key, *mods))
+            .min_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)))
+    }
+
+    pub fn has_key_table(&self, name: &str) -> bool {
+        self.key_tables.contains_key(name)
+    }
+
+    pub fn lookup_key_in_table(
+        &self,
+        name: &str,
+        key: &KeyCode,
+        mods: Modifiers,
+    ) -> Option<KeyAssignment> {
+        self.key_tables
+            .get(name)?
+            .get(&key.normalize_shift(Self::remove_positional_alt(mods)))
+            .cloned()
+    }
+
     pub fn lookup_mouse(&self, event: MouseEventTrigger, mods: Modifiers) -> Option<KeyAssignment> {
         self.mouse
             .get(&(event, Self::remove_positional_alt(mods)))
             .cloned()
     }
+
+    pub fn lookup_gesture(&self, event: &GestureTrigger) -> Option<KeyAssignment> {
+        self.gestures.get(event).cloned()
+    }
 }
@@ -1,4 +1,5 @@
-use crate::{KeyAssignment, MouseEventTrigger};
+use crate::keyassignment::WindowDimension;
+use crate::{GestureTrigger, KeyAssignment, MouseEventTrigger};
 use luahelper::impl_lua_conversion;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
@@ -14,6 +15,16 @@ pub struct Key {
 }
 impl_lua_conversion!(Key);
 
+/// A key press that is synthesized by the `SendKey` assignment
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct KeyNoAction {
+    #[serde(deserialize_with = "de_keycode")]
+    pub key: KeyCode,
+    #[serde(deserialize_with = "de_modifiers", default)]
+    pub mods: Modifiers,
+}
+impl_lua_conversion!(KeyNoAction);
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct LeaderKey {
     #[serde(deserialize_with = "de_keycode")]
@@ -29,6 +40,55 @@ fn default_leader_timeout() -> u64 {
     1000
 }
 
+/// Configures a window that slides down from the top of a monitor
+/// when a global hotkey is pressed, and back up when it is pressed
+/// again, in the style of Quake
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DropDownWindow {
+    #[serde(deserialize_with = "de_keycode")]
+    pub key: KeyCode,
+    #[serde(deserialize_with = "de_modifiers", default)]
+    pub mods: Modifiers,
+    /// The monitor that the window drops down from; 0 is the
+    /// primary monitor.  When not set, the monitor that contains
+    /// the mouse pointer is used.
+    #[serde(default)]
+    pub monitor: Option<usize>,
+    #[serde(default = "default_drop_down_width")]
+    pub width: WindowDimension,
+    #[serde(default = "default_drop_down_height")]
+    pub height: WindowDimension,
+    /// How long the slide animation takes; 0 disables it
+    #[serde(default = "default_drop_down_animation_duration")]
+    pub animation_duration_milliseconds: u64,
+}
+impl_lua_conversion!(DropDownWindow);
+
+fn default_drop_down_width() -> WindowDimension {
+    WindowDimension::Percent(100)
+}
+
+fn default_drop_down_height() -> WindowDimension {
+    WindowDimension::Percent(40)
+}
+
+fn default_drop_down_animation_duration() -> u64 {
+    150
+}
+
+/// Adds to the default key bindings for switching between tabs
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct TabActivationKeys {
+    /// Modifiers that activate a tab when combined with the digits
+    /// 1-8, in addition to SUPER and CTRL|SHIFT.  9 activates the
+    /// last tab.
+    #[serde(deserialize_with = "de_modifiers", default)]
+    pub mods: Modifiers,
+    /// A key that switches back to the most recently used tab
+    pub last_tab: Option<KeyNoAction>,
+}
+impl_lua_conversion!(TabActivationKeys);
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Mouse {
     pub event: MouseEventTrigger,
@@ -38,6 +98,13 @@ pub struct Mouse {
 }
 impl_lua_conversion!(Mouse);
 
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Gesture {
+    pub event: GestureTrigger,
+    pub action: KeyAssignment,
+}
+impl_lua_conversion!(Gesture);
+
 fn make_map() -> HashMap<String, KeyCode> {
     let mut map = HashMap::new();
 
@@ -1,11 +1,14 @@
 //! Configuration for the gui portion of the terminal
 
-use crate::keyassignment::{KeyAssignment, MouseEventTrigger, SpawnCommand};
+use crate::keyassignment::{
+    ClipboardPasteSource, GestureTrigger, KeyAssignment, MouseEventTrigger, PaneLayout,
+    SpawnCommand,
+};
 use anyhow::{anyhow, bail, Context, Error};
 use lazy_static::lazy_static;
 use luahelper::impl_lua_conversion;
 use mlua::Lua;
-use portable_pty::{CommandBuilder, PtySize};
+use portable_pty::{CommandBuilder, LoginShell, PtySize};
 use serde::{Deserialize, Deserializer, Serialize};
 use smol::channel::{Receiver, Sender};
 use smol::prelude::*;
@@ -34,9 +37,12 @@ pub mod keyassignment;
 mod keys;
 pub mod lua;
 mod ssh;
+mod startup;
+mod status;
 mod terminal;
 mod tls;
 mod unix;
+mod uri;
 mod version;
 
 pub use color::*;
@@ -45,9 +51,12 @@ pub use font::*;
 pub use frontend::*;
 pub use keys::*;
 pub use ssh::*;
+pub use startup::*;
+pub use status::*;
 pub use terminal::*;
 pub use tls::*;
 pub use unix::*;
+pub use uri::*;
 pub use version::*;
 
 type LuaFactory = fn(&Path) -> anyhow::Result<Lua>;
@@ -57,6 +66,7 @@ lazy_static! {
     pub static ref HOME_DIR: PathBuf = dirs_next::home_dir().expect("can't find HOME dir");
     pub static ref CONFIG_DIR: PathBuf = xdg_config_home();
     pub static ref RUNTIME_DIR: PathBuf = compute_runtime_dir().unwrap();
+    pub static ref DATA_DIR: PathBuf = compute_data_dir();
     static ref CONFIG: Configuration = Configuration::new();
     static ref CONFIG_FILE_OVERRIDE: Mutex<Option<PathBuf>> = Mutex::new(None);
     static ref CONFIG_OVERRIDES: Mutex<Vec<(String, String)>> = Mutex::new(vec![]);
@@ -65,6 +75,7 @@ lazy_static! {
         Mutex::new(Some(|e| log::error!("{}", e)));
     static ref LUA_PIPE: LuaPipe = LuaPipe::new();
     static ref COLOR_SCHEMES: HashMap<String, Palette> = build_default_schemes();
+    static ref APPEARANCE: Mutex<Appearance> = Mutex::new(Appearance::default());
 }
 
 thread_local! {
@@ -292,6 +303,31 @@ pub fn set_config_overrides(items: &[(String, String)]) {
     *CONFIG_OVERRIDES.lock().unwrap() = items.to_vec();
 }
 
+/// Returns the command line arguments that make another wezterm
+/// process, such as a mux server that we spawn on demand, use the
+/// same configuration file and overrides as this one
+pub fn config_override_args() -> Vec<OsString> {
+    let config_file = CONFIG_FILE_OVERRIDE
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|path| std::fs::canonicalize(path).unwrap_or_else(|_| path.clone()));
+    override_args(config_file.as_deref(), &CONFIG_OVERRIDES.lock().unwrap())
+}
+
+fn override_args(config_file: Option<&Path>, overrides: &[(String, String)]) -> Vec<OsString> {
+    let mut args = vec![];
+    if let Some(path) = config_file {
+        args.push("--config-file".into());
+        args.push(path.into());
+    }
+    for (name, value) in overrides {
+        args.push("--config".into());
+        args.push(format!("{}={}", name, value).into());
+    }
+    args
+}
+
 /// Discard the current configuration and replace it with
 /// the default configuration
 pub fn use_default_configuration() {
@@ -323,6 +359,26 @@ pub fn reload() {
     CONFIG.reload();
 }
 
+/// Returns the system appearance most recently reported by the gui
+pub fn appearance() -> Appearance {
+    *APPEARANCE.lock().unwrap()
+}
+
+/// Called by the gui to record the current system appearance.
+/// If it differs from the prior value, the configuration is
+/// reloaded so that appearance dependent options are re-evaluated.
+pub fn set_appearance(appearance: Appearance) {
+    let changed = {
+        let mut current = APPEARANCE.lock().unwrap();
+        let changed = *current != appearance;
+        *current = appearance;
+        changed
+    };
+    if changed {
+        reload();
+    }
+}
+
 /// If there was an error loading the preferred configuration,
 /// return it, otherwise return the current configuration
 pub fn configuration_result() -> Result<ConfigHandle, Error> {
@@ -623,6 +679,44 @@ impl Default for ExitBehavior {
     }
 }
 
+/// Who draws the title bar and borders of windows on Wayland
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum WaylandDecorations {
+    /// Use server side decorations if the compositor prefers them,
+    /// and draw them in wezterm otherwise
+    FollowServer,
+    /// Always draw the decorations in wezterm
+    ClientSide,
+    /// Ask the compositor to draw the decorations, falling back to
+    /// drawing them in wezterm if it can't
+    ServerSide,
+}
+
+/// How colors are presented on displays whose color profile
+/// is not sRGB, such as wide gamut displays
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayColorSpace {
+    /// Send colors to the display without converting them, so that
+    /// they are as saturated as the display allows
+    Native,
+    /// Treat colors as sRGB and convert them through the color
+    /// profile of the display, so that they appear as they would on
+    /// an sRGB display
+    Srgb,
+}
+
+impl Default for DisplayColorSpace {
+    fn default() -> Self {
+        DisplayColorSpace::Native
+    }
+}
+
+impl Default for WaylandDecorations {
+    fn default() -> Self {
+        WaylandDecorations::FollowServer
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
     /// The font size, measured in points
@@ -638,6 +732,26 @@ pub struct Config {
     #[serde(default)]
     pub window_decorations: WindowDecorations,
 
+    /// The window class for new windows, which is the WM_CLASS on
+    /// X11, the app_id on Wayland and the window class on Windows.
+    /// The `--class` command line option takes precedence.
+    /// The default is "org.wezfurlong.wezterm".
+    #[serde(default)]
+    pub window_class: Option<String>,
+
+    /// The instance name part of WM_CLASS for windows on X11.
+    /// The default is the same as the window class.
+    #[serde(default)]
+    pub window_instance: Option<String>,
+
+    /// When the bell rings, or a notification is shown, for a pane
+    /// in a window that isn't focused, ask the windowing system to
+    /// draw attention to the window: the urgency hint is set on X11,
+    /// the dock icon bounces on macOS and the taskbar button flashes
+    /// on Windows.
+    #[serde(default = "default_true")]
+    pub request_attention_on_alert: bool,
+
     /// When using FontKitXXX font systems, a set of directories to
     /// search ahead of the standard font locations for fonts.
     /// Relative paths are taken to be relative to the directory
@@ -679,6 +793,13 @@ pub struct Config {
     /// by the colors setting.
     pub color_scheme: Option<String>,
 
+    /// Use a different named color scheme depending on whether
+    /// the system appearance is light or dark.  When the scheme
+    /// for the current appearance is set, it takes precedence
+    /// over `color_scheme`.
+    #[serde(default)]
+    pub color_scheme_for_appearance: Option<AppearanceColorSchemes>,
+
     /// Named color schemes
     #[serde(default)]
     pub color_schemes: HashMap<String, Palette>,
@@ -723,13 +844,52 @@ pub struct Config {
     #[serde(default = "default_initial_cols")]
     pub initial_cols: u16,
 
+    /// The windows to create when the GUI is started without
+    /// specifying a program to run.  If empty, a single window
+    /// is created.
+    #[serde(default)]
+    pub startup_windows: Vec<StartupWindow>,
+
+    /// When set, the first window can be summoned and dismissed
+    /// from anywhere by pressing a global hotkey
+    #[serde(default)]
+    pub drop_down_window: Option<DropDownWindow>,
+
+    /// If true, show an icon in the system tray, or in the status
+    /// area of the menu bar on macOS, with a menu of quick actions.
+    /// This is read when the GUI starts.
+    #[serde(default)]
+    pub enable_tray_icon: bool,
+
     #[serde(default = "default_hyperlink_rules")]
     pub hyperlink_rules: Vec<hyperlink::Rule>,
 
+    /// Routes links that are opened from the terminal to handlers
+    /// based on their scheme or a regex, rather than always using
+    /// the platform opener
+    #[serde(default)]
+    pub uri_handlers: Vec<UriHandler>,
+
     /// What to set the TERM variable to
     #[serde(default = "default_term")]
     pub term: String,
 
+    /// If set, the LANG variable is set to this for spawned programs,
+    /// eg: "en_US.UTF-8".  This is useful when wezterm is launched
+    /// from a desktop environment that doesn't set the locale.
+    #[serde(default)]
+    pub locale: Option<String>,
+
+    /// How the shell is told that it is a login shell, when it is
+    /// run because `default_prog` isn't set
+    #[serde(default)]
+    pub login_shell: LoginShell,
+
+    /// The program to run in the first window that is created when
+    /// wezterm starts.  Subsequent tabs and windows use `default_prog`.
+    #[serde(default)]
+    pub initial_prog: Option<Vec<String>>,
+
     #[serde(default)]
     pub font_locator: FontLocatorSelection,
     #[serde(default)]
@@ -741,6 +901,20 @@ pub struct Config {
     #[serde(default)]
     pub font_antialias: FontAntiAliasing,
 
+    /// How many horizontal subpixel positions each glyph may be
+    /// rasterized at.  Glyphs whose shaped position falls between
+    /// pixels are drawn at the closest of these positions rather
+    /// than being snapped to the pixel grid.  Each position is a
+    /// separate entry in the glyph atlas, so 1 disables subpixel
+    /// positioning and saves texture memory.
+    #[serde(default = "default_subpixel_glyph_positions")]
+    pub subpixel_glyph_positions: u8,
+
+    /// The size of superscript and subscript text, as set by SGR 73
+    /// and 74, relative to the size of the rest of the text
+    #[serde(default = "default_superscript_subscript_scale")]
+    pub superscript_subscript_scale: f64,
+
     #[serde(default)]
     pub freetype_load_target: FreeTypeLoadTarget,
     #[serde(default, deserialize_with = "FreeTypeLoadFlags::de_string")]
@@ -791,6 +965,11 @@ pub struct Config {
     #[serde(default)]
     pub front_end: FrontEndSelection,
 
+    /// Whether colors are converted through the color profile of
+    /// the display.  This is only supported on macOS.
+    #[serde(default)]
+    pub display_color_space: DisplayColorSpace,
+
     /// The set of unix domains
     #[serde(default = "UnixDomain::default_unix_domains")]
     pub unix_domains: Vec<UnixDomain>,
@@ -807,6 +986,17 @@ pub struct Config {
     #[serde(default)]
     pub tls_clients: Vec<TlsDomainClient>,
 
+    /// When true, the mux server periodically saves the layout and
+    /// contents of its panes, and restores them when it next starts,
+    /// running fresh processes in their place
+    #[serde(default)]
+    pub mux_server_persist_scrollback: bool,
+
+    /// How often the mux server saves its panes when
+    /// `mux_server_persist_scrollback` is enabled
+    #[serde(default = "default_mux_server_persist_interval")]
+    pub mux_server_persist_interval_seconds: u64,
+
     /// Constrains the rate at which the multiplexer client will
     /// speculatively fetch line data.
     /// This helps to avoid saturating the link between the client
@@ -817,6 +1007,14 @@ pub struct Config {
 
     #[serde(default)]
     pub keys: Vec<Key>,
+
+    /// Named tables of key bindings that can be activated by the
+    /// `ActivateKeyTable` assignment.  While a table is active, only
+    /// the keys listed in that table are recognized.  An entry in a
+    /// table may itself activate another table, which allows for
+    /// multi-key sequences such as `C-x C-c`.
+    #[serde(default)]
+    pub key_tables: HashMap<String, Vec<Key>>,
     #[serde(default)]
     pub debug_key_events: bool,
 
@@ -824,11 +1022,27 @@ pub struct Config {
     pub disable_default_key_bindings: bool,
     pub leader: Option<LeaderKey>,
 
+    /// Additional default key bindings for ActivateTab and
+    /// ActivateLastTab
+    #[serde(default)]
+    pub tab_activation_keys: TabActivationKeys,
+
+    /// If true, the most recently used list is shown over the active
+    /// pane while cycling with ActivateTabRelativeMRU or
+    /// ActivatePaneRelativeMRU
+    #[serde(default = "default_true")]
+    pub show_mru_overlay: bool,
+
     #[serde(default)]
     pub mouse_bindings: Vec<Mouse>,
     #[serde(default)]
     pub disable_default_mouse_bindings: bool,
 
+    #[serde(default)]
+    pub gesture_bindings: Vec<Gesture>,
+    #[serde(default)]
+    pub disable_default_gesture_bindings: bool,
+
     #[serde(default)]
     pub daemon_options: DaemonOptions,
 
@@ -875,6 +1089,17 @@ pub struct Config {
     #[serde(default = "default_tab_max_width")]
     pub tab_max_width: usize,
 
+    /// A template for the titles in the tab bar, such as
+    /// "{index}: {process} in {cwd}".  When not set, the title of
+    /// the active pane is used, prefixed by the tab index if
+    /// show_tab_index_in_tab_bar is true.
+    #[serde(default)]
+    pub tab_title_format: Option<String>,
+
+    /// How titles that are wider than the tab are shortened
+    #[serde(default)]
+    pub tab_title_truncation: TabTitleTruncation,
+
     /// If true, hide the tab bar if the window only has a single tab.
     #[serde(default)]
     pub hide_tab_bar_if_only_one_tab: bool,
@@ -882,6 +1107,36 @@ pub struct Config {
     #[serde(default)]
     pub enable_scroll_bar: bool,
 
+    /// If true, a pane whose viewport has been scrolled back shows
+    /// a badge in its top right corner with the number of lines
+    /// that are below the viewport.
+    #[serde(default = "default_true")]
+    pub show_scroll_position_indicator: bool,
+
+    /// When new output arrives below a scrolled back viewport, the
+    /// scroll position indicator is highlighted, with the highlight
+    /// fading out over this many milliseconds.
+    #[serde(default = "default_scroll_position_indicator_flash_duration")]
+    pub scroll_position_indicator_flash_duration: u64,
+
+    /// If true, text is displayed using the Unicode bidirectional
+    /// algorithm, so that right-to-left scripts such as Arabic and
+    /// Hebrew are shown in the correct order.
+    #[serde(default)]
+    pub bidi_enabled: bool,
+
+    /// The direction of the paragraphs that are formed from each
+    /// logical line when bidi_enabled is true
+    #[serde(default)]
+    pub bidi_direction: BidiDirection,
+
+    /// If true, panes start out with invisible characters, such as
+    /// zero-width spaces and bidi controls, marked using the
+    /// `invisible_character` color.  This can be toggled for an
+    /// individual pane using the ToggleInvisibleCharacters action.
+    #[serde(default)]
+    pub show_invisible_characters: bool,
+
     /// If false, do not try to use a Wayland protocol connection
     /// when starting the gui frontend, and instead use X11.
     /// This option is only considered on X11/Wayland systems and
@@ -890,6 +1145,21 @@ pub struct Config {
     #[serde(default)]
     pub enable_wayland: bool,
 
+    /// Whether the compositor or wezterm should draw the window
+    /// decorations on Wayland.  Decorations are omitted altogether
+    /// if `window_decorations` doesn't include TITLE.
+    #[serde(default)]
+    pub wayland_decorations: WaylandDecorations,
+
+    /// Wayland compositors only tell clients about integer scale
+    /// factors; a display scaled by 1.5 is reported as 2 and the
+    /// compositor shrinks the window contents to fit.  If set, the
+    /// window is rendered, and its fonts rasterized, at this scale
+    /// instead, and presented to the compositor at its logical size
+    /// using the viewporter protocol, which avoids that resampling.
+    #[serde(default)]
+    pub wayland_fractional_scale: Option<f64>,
+
     /// Whether to prefer EGL over other GL implementations.
     /// EGL on Windows has jankier resize behavior than WGL (which
     /// is used if EGL is unavailable), but EGL survives graphics
@@ -897,6 +1167,21 @@ pub struct Config {
     #[serde(default = "default_true")]
     pub prefer_egl: bool,
 
+    /// ConPTY repaints the whole screen each time that it is resized.
+    /// On Windows, resizes that arrive in quick succession are
+    /// coalesced so that the pty is resized at most once in this
+    /// many milliseconds.  0 resizes the pty on every change.
+    /// This has no effect on other systems.
+    #[serde(default = "default_conpty_resize_coalesce_ms")]
+    pub conpty_resize_coalesce_ms: u64,
+
+    /// If true, each resize of a ConPTY first resizes it to one row
+    /// taller, which forces a full repaint from full screen programs
+    /// that don't otherwise redraw properly after a resize.
+    /// This has no effect on other systems.
+    #[serde(default)]
+    pub conpty_resize_pad_row: bool,
+
     #[serde(default = "default_true")]
     pub custom_block_glyphs: bool,
 
@@ -986,6 +1271,67 @@ pub struct Config {
     #[serde(default)]
     pub default_cursor_style: DefaultCursorStyle,
 
+    /// When true, the cursor is drawn by swapping the foreground and
+    /// background colors of the cell beneath it, rather than in the
+    /// cursor colors of the palette, including any that were set by
+    /// an application using OSC 12
+    #[serde(default)]
+    pub force_reverse_video_cursor: bool,
+
+    /// How long, in milliseconds, the text cursor takes to glide from
+    /// one cell to the next when it moves.  0 disables the animation.
+    #[serde(default)]
+    pub cursor_animation_duration: u64,
+
+    /// The easing function that is applied to the cursor animation
+    #[serde(default)]
+    pub cursor_animation_easing: EasingFunction,
+
+    /// When the cursor is animating, leave a fading trail behind it
+    #[serde(default)]
+    pub cursor_trail: bool,
+
+    /// How long, in milliseconds, the viewport takes to glide to its
+    /// new position when scrolling through the scrollback.
+    /// 0 disables the animation.
+    #[serde(default)]
+    pub scroll_animation_duration: u64,
+
+    /// The easing function that is applied to the scroll animation
+    #[serde(default)]
+    pub scroll_animation_easing: EasingFunction,
+
+    /// Disables animations that move things around the screen,
+    /// such as `cursor_animation_duration` and `scroll_animation_duration`
+    #[serde(default)]
+    pub reduce_motion: bool,
+
+    /// The maximum number of times per second that the window is
+    /// repainted in response to output
+    #[serde(default = "default_max_fps")]
+    pub max_fps: u8,
+
+    /// Adjusts rendering while the system is running on battery power
+    #[serde(default)]
+    pub battery_power_saving: BatteryPowerSaving,
+
+    /// Configures the visual bell, which briefly flashes the pane or
+    /// the window when the bell rings
+    #[serde(default)]
+    pub visual_bell: VisualBell,
+
+    /// When a search wraps around from the last match to the first,
+    /// or vice versa, flash the pane for this many milliseconds using
+    /// the `visual_bell` color, opacity and easing.
+    /// 0 disables the flash.
+    #[serde(default)]
+    pub search_wrap_flash_duration: u64,
+
+    /// Controls what happens to a window once there has been no
+    /// input or output in it for a while, to reduce power use
+    #[serde(default)]
+    pub idle: IdleConfig,
+
     /// If non-zero, specifies the period (in seconds) at which various
     /// statistics are logged.  Note that there is a minimum period of
     /// 10 seconds.
@@ -999,6 +1345,60 @@ pub struct Config {
     #[serde(default = "default_true")]
     pub scroll_to_bottom_on_input: bool,
 
+    /// When search or copy mode moves to a match or position that is
+    /// not visible, the viewport is scrolled so that this many lines
+    /// of context remain visible around it.
+    #[serde(default = "default_scroll_context_lines")]
+    pub scroll_context_lines: usize,
+
+    /// How many lines are scrolled by each click of a mouse wheel.
+    /// This applies on X11 and Wayland; on Windows and macOS the
+    /// system setting is used.
+    #[serde(default = "default_scroll_lines_per_tick")]
+    pub scroll_lines_per_tick: u16,
+
+    /// When a touchpad scroll ends while the fingers are still moving,
+    /// continue scrolling and gradually slow to a stop.  This applies
+    /// on systems that don't already do this themselves.
+    #[serde(default = "default_true")]
+    pub kinetic_scrolling: bool,
+
+    /// If true, clicking the middle mouse button pastes from
+    /// `middle_click_paste_source`.
+    #[serde(default = "default_true")]
+    pub enable_middle_click_paste: bool,
+
+    /// Which clipboard is pasted by the middle mouse button
+    #[serde(default = "default_middle_click_paste_source")]
+    pub middle_click_paste_source: ClipboardPasteSource,
+
+    /// If true, clicking the right mouse button shows a menu of
+    /// common actions
+    #[serde(default)]
+    pub enable_context_menu: bool,
+
+    /// If true, pasting text that consists of a single line followed
+    /// by a newline will paste just the line, without the newline,
+    /// so that it isn't immediately executed by the shell.
+    #[serde(default)]
+    pub strip_trailing_newline_from_single_line_paste: bool,
+
+    /// How many of the most recently copied pieces of text are
+    /// remembered for use with the PasteFromHistory overlay.
+    #[serde(default = "default_clipboard_history_size")]
+    pub clipboard_history_size: usize,
+
+    /// Controls how the paths of files that are dropped onto the
+    /// window are quoted before being pasted into the active pane.
+    #[serde(default)]
+    pub quote_dropped_files: DroppedFileQuoting,
+
+    /// When a single directory is dropped onto a pane that is idle
+    /// at a shell prompt, change to that directory rather than
+    /// pasting its path.
+    #[serde(default)]
+    pub cd_on_dropped_directory: bool,
+
     #[serde(default)]
     pub use_ime: bool,
     #[serde(default = "default_true")]
@@ -1010,6 +1410,23 @@ pub struct Config {
     #[serde(default)]
     pub launch_menu: Vec<SpawnCommand>,
 
+    /// Named layouts of panes that can be spawned into a new tab
+    /// using the `SpawnTabLayout` action, the launcher, or
+    /// `wezterm cli spawn --layout`
+    #[serde(default)]
+    pub tab_layouts: HashMap<String, PaneLayout>,
+
+    /// The size of a floating pane, as a percentage of the width
+    /// and height of the tab that it hovers above
+    #[serde(default = "default_floating_pane_size")]
+    pub floating_pane_size: u8,
+
+    /// If true, the index and title of each pane are drawn in the
+    /// border above it.  A row is reserved at the top of the window
+    /// for the titles of the panes at the top of the tab.
+    #[serde(default)]
+    pub pane_title_bar: bool,
+
     /// When true, watch the config file and reload it automatically
     /// when it is detected as changing.
     #[serde(default = "default_true")]
@@ -1018,7 +1435,7 @@ pub struct Config {
     #[serde(default = "default_true")]
     pub add_wsl_distributions_to_launch_menu: bool,
 
-    #[serde(default = "default_true")]
+    #[serde(default)]
     pub check_for_updates: bool,
     #[serde(default)]
     pub show_update_window: bool,
@@ -1026,6 +1443,12 @@ pub struct Config {
     #[serde(default = "default_update_interval")]
     pub check_for_updates_interval_seconds: u64,
 
+    /// When true, crash reports include the configuration, the command
+    /// line and the recent log messages, rather than just a hash of the
+    /// configuration
+    #[serde(default)]
+    pub crash_report_include_details: bool,
+
     /// When set to true, use the CSI-U encoding scheme as described
     /// in http://www.leonerd.org.uk/hacks/fixterms/
     /// This is off by default because @wez and @jsgf find the shift-space
@@ -1045,6 +1468,17 @@ pub struct Config {
     #[serde(default = "default_true")]
     pub adjust_window_size_when_changing_font_size: bool,
 
+    /// When true, the window size snaps to whole cells when it is
+    /// interactively resized
+    #[serde(default)]
+    pub use_resize_increments: bool,
+
+    /// When true, a change in dpi preserves the number of rows and
+    /// columns and resizes the window to match.  When false, the
+    /// window keeps its size and the rows and columns are recomputed.
+    #[serde(default = "default_true")]
+    pub window_size_is_cells: bool,
+
     #[serde(default = "default_alternate_buffer_wheel_scroll_speed")]
     pub alternate_buffer_wheel_scroll_speed: u8,
 
@@ -1056,9 +1490,39 @@ pub struct Config {
 
     #[serde(default = "default_stateless_process_list")]
     pub skip_close_confirmation_for_processes_named: Vec<String>,
+
+    /// When a command that is delimited by semantic prompt escapes
+    /// runs for at least this many seconds and then completes in a
+    /// pane that doesn't have the focus, a desktop notification is
+    /// shown.  0 disables the notification.
+    #[serde(default = "default_command_complete_notification_threshold")]
+    pub command_complete_notification_threshold_seconds: u64,
+
+    /// The names of domains whose panes should not generate
+    /// command completion notifications
+    #[serde(default)]
+    pub command_complete_notification_excluded_domains: Vec<String>,
+
+    /// Controls whether programs may read the clipboard using OSC 52
+    #[serde(default)]
+    pub clipboard_read_permission: ClipboardReadPermission,
+
+    /// The names of domains whose panes may read the clipboard using
+    /// OSC 52 without prompting, when clipboard_read_permission is Prompt
+    #[serde(default)]
+    pub clipboard_read_allowed_domains: Vec<String>,
+
+    /// External commands whose output is polled for the status area
+    /// of the tab bar
+    #[serde(default)]
+    pub status_commands: Vec<StatusCommand>,
 }
 impl_lua_conversion!(Config);
 
+fn default_command_complete_notification_threshold() -> u64 {
+    10
+}
+
 fn default_stateless_process_list() -> Vec<String> {
     ["bash", "sh", "zsh", "fish", "tmux"]
         .iter()
@@ -1066,10 +1530,22 @@ fn default_stateless_process_list() -> Vec<String> {
         .collect()
 }
 
+fn default_max_fps() -> u8 {
+    60
+}
+
 fn default_status_update_interval() -> u64 {
     1_000
 }
 
+fn default_conpty_resize_coalesce_ms() -> u64 {
+    50
+}
+
+fn default_scroll_lines_per_tick() -> u16 {
+    5
+}
+
 fn default_alternate_buffer_wheel_scroll_speed() -> u8 {
     3
 }
@@ -1078,6 +1554,14 @@ fn default_word_boundary() -> String {
     " \t\n{[}]()\"'`".to_string()
 }
 
+fn default_subpixel_glyph_positions() -> u8 {
+    4
+}
+
+fn default_superscript_subscript_scale() -> f64 {
+    0.6
+}
+
 fn default_one_point_oh_f64() -> f64 {
     1.0
 }
@@ -1135,6 +1619,135 @@ impl DefaultCursorStyle {
     }
 }
 
+/// Maps the linear progress of an animation to the proportion of
+/// the distance that has been covered
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EasingFunction {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+impl_lua_conversion!(EasingFunction);
+
+impl Default for EasingFunction {
+    fn default() -> Self {
+        EasingFunction::EaseOut
+    }
+}
+
+impl EasingFunction {
+    /// Evaluates the function for `t`, which is clamped to 0.0..=1.0
+    pub fn evaluate(self, t: f32) -> f32 {
+        let t = t.max(0.0).min(1.0);
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t * t,
+            Self::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Self::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (2.0 - 2.0 * t).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
+/// Specifies the base direction of the paragraphs that are analyzed
+/// when bidi is enabled
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BidiDirection {
+    /// Use the direction of the first strongly directional character,
+    /// or left-to-right if there isn't one
+    AutoLeftToRight,
+    /// Use the direction of the first strongly directional character,
+    /// or right-to-left if there isn't one
+    AutoRightToLeft,
+    LeftToRight,
+    RightToLeft,
+}
+impl_lua_conversion!(BidiDirection);
+
+impl Default for BidiDirection {
+    fn default() -> Self {
+        BidiDirection::AutoLeftToRight
+    }
+}
+
+/// How to quote the paths of files dropped onto the window
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DroppedFileQuoting {
+    /// Use the quoting rules of a posix shell
+    Posix,
+    /// Use the quoting rules of the Windows command line
+    Windows,
+    /// Paste the paths as-is
+    None,
+}
+impl_lua_conversion!(DroppedFileQuoting);
+
+impl Default for DroppedFileQuoting {
+    fn default() -> Self {
+        if cfg!(windows) {
+            Self::Windows
+        } else {
+            Self::Posix
+        }
+    }
+}
+
+impl DroppedFileQuoting {
+    pub fn quote(self, path: &str) -> String {
+        match self {
+            Self::None => path.to_string(),
+            Self::Posix => {
+                let is_safe = |c: char| c.is_alphanumeric() || "/._-+=:,@%".contains(c);
+                if !path.is_empty() && path.chars().all(is_safe) {
+                    path.to_string()
+                } else {
+                    format!("'{}'", path.replace('\'', "'\\''"))
+                }
+            }
+            Self::Windows => {
+                if !path.is_empty()
+                    && !path
+                        .chars()
+                        .any(|c| c.is_whitespace() || "&()[]{}^=;!'+,`~\"".contains(c))
+                {
+                    return path.to_string();
+                }
+
+                // Follow the rules used by CommandLineToArgvW: a double
+                // quote is escaped with a backslash, and so are any
+                // backslashes that precede it or the closing quote
+                let mut quoted = String::from("\"");
+                let mut backslashes = 0;
+                for c in path.chars() {
+                    match c {
+                        '\\' => {
+                            backslashes += 1;
+                            continue;
+                        }
+                        '"' => {
+                            quoted.extend(std::iter::repeat('\\').take(backslashes * 2 + 1));
+                        }
+                        _ => {
+                            quoted.extend(std::iter::repeat('\\').take(backslashes));
+                        }
+                    }
+                    quoted.push(c);
+                    backslashes = 0;
+                }
+                quoted.extend(std::iter::repeat('\\').take(backslashes * 2));
+                quoted.push('"');
+                quoted
+            }
+        }
+    }
+}
+
 #[derive(Default, Deserialize, Serialize, Clone, Copy, Debug)]
 pub struct WindowPadding {
     #[serde(default)]
@@ -1148,6 +1761,144 @@ pub struct WindowPadding {
 }
 impl_lua_conversion!(WindowPadding);
 
+#[derive(Deserialize, Serialize, Clone, Copy, Debug)]
+pub struct VisualBell {
+    /// How long the flash takes to fade out, in milliseconds.
+    /// 0 disables the visual bell.
+    #[serde(default)]
+    pub duration: u64,
+    /// The easing function that is applied to the fade out
+    #[serde(default)]
+    pub easing: EasingFunction,
+    /// The opacity of the `visual_bell` color at the start of the flash
+    #[serde(default = "default_visual_bell_opacity")]
+    pub opacity: f32,
+    #[serde(default)]
+    pub target: VisualBellTarget,
+}
+impl_lua_conversion!(VisualBell);
+
+impl Default for VisualBell {
+    fn default() -> Self {
+        Self {
+            duration: 0,
+            easing: EasingFunction::default(),
+            opacity: default_visual_bell_opacity(),
+            target: VisualBellTarget::default(),
+        }
+    }
+}
+
+fn default_visual_bell_opacity() -> f32 {
+    0.5
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug)]
+pub struct BatteryPowerSaving {
+    /// Whether to save power while running on battery
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Replaces `max_fps` while running on battery, if it is lower
+    #[serde(default = "default_battery_max_fps")]
+    pub max_fps: u8,
+    /// Stop blinking the cursor, animating images and animating
+    /// scrolling and cursor movement while running on battery
+    #[serde(default = "default_true")]
+    pub pause_animations: bool,
+    /// How often, in milliseconds, panes from multiplexer domains are
+    /// polled for changes while running on battery
+    #[serde(default = "default_battery_poll_interval")]
+    pub poll_interval: u64,
+}
+
+impl Default for BatteryPowerSaving {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_fps: default_battery_max_fps(),
+            pause_animations: true,
+            poll_interval: default_battery_poll_interval(),
+        }
+    }
+}
+
+fn default_battery_max_fps() -> u8 {
+    20
+}
+
+fn default_battery_poll_interval() -> u64 {
+    500
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug)]
+pub struct IdleConfig {
+    /// How many seconds without keyboard or mouse input, or output
+    /// in one of its visible panes, before a window becomes idle.
+    /// 0 disables idle detection.
+    #[serde(default)]
+    pub timeout_seconds: u64,
+    /// The opacity of the black layer that is drawn over an idle
+    /// window to dim it.  0 doesn't dim the window.
+    #[serde(default = "default_idle_dim_opacity")]
+    pub dim_opacity: f32,
+    /// Stop blinking the cursor and animating images while idle
+    #[serde(default = "default_true")]
+    pub pause_animations: bool,
+    /// Stop polling panes from multiplexer domains for changes while
+    /// idle.  Changes that the server pushes are still applied.
+    #[serde(default = "default_true")]
+    pub pause_remote_polling: bool,
+}
+
+impl Default for IdleConfig {
+    fn default() -> Self {
+        Self {
+            timeout_seconds: 0,
+            dim_opacity: default_idle_dim_opacity(),
+            pause_animations: true,
+            pause_remote_polling: true,
+        }
+    }
+}
+
+fn default_idle_dim_opacity() -> f32 {
+    0.3
+}
+
+/// Which part of a tab title is removed to make it fit
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TabTitleTruncation {
+    /// Keep the start of the title
+    Right,
+    /// Keep the end of the title, replacing the start with an ellipsis
+    Left,
+    /// Keep the start and the end, replacing the middle with an ellipsis
+    Middle,
+}
+impl_lua_conversion!(TabTitleTruncation);
+
+impl Default for TabTitleTruncation {
+    fn default() -> Self {
+        TabTitleTruncation::Right
+    }
+}
+
+/// What the visual bell flashes
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VisualBellTarget {
+    /// The pane in which the bell rang
+    Pane,
+    /// The whole window
+    Window,
+}
+impl_lua_conversion!(VisualBellTarget);
+
+impl Default for VisualBellTarget {
+    fn default() -> Self {
+        VisualBellTarget::Pane
+    }
+}
+
 #[derive(Deserialize, Serialize, Clone, Copy, Debug)]
 pub enum WindowCloseConfirmation {
     AlwaysPrompt,
@@ -1163,6 +1914,25 @@ impl Default for WindowCloseConfirmation {
     }
 }
 
+/// Whether programs may read the clipboard using OSC 52
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClipboardReadPermission {
+    /// Clipboard queries are ignored
+    Deny,
+    /// The first query from each pane asks whether to allow it, and
+    /// the answer is remembered for that pane
+    Prompt,
+    /// Clipboard queries are always answered
+    Allow,
+}
+impl_lua_conversion!(ClipboardReadPermission);
+
+impl Default for ClipboardReadPermission {
+    fn default() -> Self {
+        ClipboardReadPermission::Prompt
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         // Ask serde to provide the defaults based on the attributes
@@ -1178,6 +1948,18 @@ pub struct LoadedConfig {
     lua: Option<mlua::Lua>,
 }
 
+impl LoadedConfig {
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// The configuration file that was loaded, or None if there
+    /// was no configuration file and the defaults are being used
+    pub fn file_name(&self) -> Option<&Path> {
+        self.file_name.as_deref()
+    }
+}
+
 struct PathPossibility {
     path: PathBuf,
     is_required: bool,
@@ -1197,45 +1979,70 @@ impl PathPossibility {
     }
 }
 
+/// Returns the configuration files that are considered, most
+/// preferred first
+fn config_file_candidates() -> Vec<PathPossibility> {
+    // Note that the directories crate has methods for locating project
+    // specific config directories, but only returns one of them, not
+    // multiple.  In addition, it spawns a lot of subprocesses,
+    // so we do this bit "by-hand"
+
+    let mut paths = vec![
+        PathPossibility::optional(CONFIG_DIR.join("wezterm.lua")),
+        PathPossibility::optional(HOME_DIR.join(".wezterm.lua")),
+    ];
+    if cfg!(windows) {
+        // On Windows, a common use case is to maintain a thumb drive
+        // with a set of portable tools that don't need to be installed
+        // to run on a target system.  In that scenario, the user would
+        // like to run with the config from their thumbdrive because
+        // either the target system won't have any config, or will have
+        // the config of another user.
+        // So we prioritize that here: if there is a config in the same
+        // dir as the executable that will take precedence.
+        if let Ok(exe_name) = std::env::current_exe() {
+            if let Some(exe_dir) = exe_name.parent() {
+                paths.insert(0, PathPossibility::optional(exe_dir.join("wezterm.lua")));
+            }
+        }
+    }
+    if let Some(path) = std::env::var_os("WEZTERM_CONFIG_FILE") {
+        log::trace!("Note: WEZTERM_CONFIG_FILE is set in the environment");
+        paths.insert(0, PathPossibility::required(path.into()));
+    }
+
+    if let Some(path) = CONFIG_FILE_OVERRIDE.lock().unwrap().as_ref() {
+        log::trace!("Note: config file override is set");
+        paths.insert(0, PathPossibility::required(path.clone()));
+    }
+    paths
+}
+
+/// Returns the configuration file that `Config::load` uses, following
+/// the same search order, along with whether that file exists.
+/// A file that was explicitly requested, via `--config-file` or
+/// `WEZTERM_CONFIG_FILE`, is returned even if it doesn't exist.
+/// When there is no configuration file at all, the preferred location
+/// for a new one is returned.
+pub fn config_file_path() -> (PathBuf, bool) {
+    for candidate in config_file_candidates() {
+        if candidate.path.exists() {
+            return (candidate.path, true);
+        }
+        if candidate.is_required {
+            return (candidate.path, false);
+        }
+    }
+    (CONFIG_DIR.join("wezterm.lua"), false)
+}
+
 impl Config {
     pub fn load() -> Result<LoadedConfig, Error> {
         Self::load_with_overrides(&serde_json::Value::default())
     }
 
     pub fn load_with_overrides(overrides: &serde_json::Value) -> Result<LoadedConfig, Error> {
-        // Note that the directories crate has methods for locating project
-        // specific config directories, but only returns one of them, not
-        // multiple.  In addition, it spawns a lot of subprocesses,
-        // so we do this bit "by-hand"
-
-        let mut paths = vec![
-            PathPossibility::optional(CONFIG_DIR.join("wezterm.lua")),
-            PathPossibility::optional(HOME_DIR.join(".wezterm.lua")),
-        ];
-        if cfg!(windows) {
-            // On Windows, a common use case is to maintain a thumb drive
-            // with a set of portable tools that don't need to be installed
-            // to run on a target system.  In that scenario, the user would
-            // like to run with the config from their thumbdrive because
-            // either the target system won't have any config, or will have
-            // the config of another user.
-            // So we prioritize that here: if there is a config in the same
-            // dir as the executable that will take precedence.
-            if let Ok(exe_name) = std::env::current_exe() {
-                if let Some(exe_dir) = exe_name.parent() {
-                    paths.insert(0, PathPossibility::optional(exe_dir.join("wezterm.lua")));
-                }
-            }
-        }
-        if let Some(path) = std::env::var_os("WEZTERM_CONFIG_FILE") {
-            log::trace!("Note: WEZTERM_CONFIG_FILE is set in the environment");
-            paths.insert(0, PathPossibility::required(path.into()));
-        }
-
-        if let Some(path) = CONFIG_FILE_OVERRIDE.lock().unwrap().as_ref() {
-            log::trace!("Note: config file override is set");
-            paths.insert(0, PathPossibility::required(path.clone()));
-        }
+        let paths = config_file_candidates();
 
         for path_item in &paths {
             let p = path_item.path.as_path();
@@ -1346,6 +2153,23 @@ impl Config {
         map
     }
 
+    pub fn key_table_bindings(
+        &self,
+    ) -> HashMap<String, HashMap<(KeyCode, Modifiers), KeyAssignment>> {
+        let mut tables = HashMap::new();
+
+        for (name, keys) in &self.key_tables {
+            let mut map = HashMap::new();
+            for k in keys {
+                let (key, mods) = k.key.normalize_shift(k.mods);
+                map.insert((key, mods), k.action.clone());
+            }
+            tables.insert(name.clone(), map);
+        }
+
+        tables
+    }
+
     pub fn mouse_bindings(&self) -> HashMap<(MouseEventTrigger, Modifiers), KeyAssignment> {
         let mut map = HashMap::new();
 
@@ -1356,6 +2180,16 @@ impl Config {
         map
     }
 
+    pub fn gesture_bindings(&self) -> HashMap<GestureTrigger, KeyAssignment> {
+        let mut map = HashMap::new();
+
+        for g in &self.gesture_bindings {
+            map.insert(g.event.clone(), g.action.clone());
+        }
+
+        map
+    }
+
     /// In some cases we need to compute expanded values based
     /// on those provided by the user.  This is where we do that.
     pub fn compute_extra_defaults(&self, config_path: Option<&Path>) -> Self {
@@ -1416,6 +2250,14 @@ impl Config {
         cfg.load_color_schemes(&cfg.compute_color_scheme_dirs())
             .ok();
 
+        if let Some(scheme) = cfg
+            .color_scheme_for_appearance
+            .as_ref()
+            .and_then(|schemes| schemes.scheme_for(appearance()))
+        {
+            cfg.color_scheme.replace(scheme.clone());
+        }
+
         cfg.resolved_palette = cfg.colors.as_ref().cloned().unwrap_or(Default::default());
         // Color scheme overrides any manually specified palette
         if let Some(scheme) = cfg.color_scheme.as_ref() {
@@ -1501,7 +2343,13 @@ impl Config {
 
     pub fn resolve_color_scheme(&self) -> Option<&Palette> {
         let scheme_name = self.color_scheme.as_ref()?;
+        self.color_scheme_by_name(scheme_name)
+    }
 
+    /// Look up a color scheme by name, preferring schemes defined
+    /// in the `color_schemes` section of the config over the
+    /// built-in schemes.
+    pub fn color_scheme_by_name(&self, scheme_name: &str) -> Option<&Palette> {
         if let Some(palette) = self.color_schemes.get(scheme_name) {
             Some(palette)
         } else {
@@ -1552,6 +2400,15 @@ impl Config {
         Ok(cmd)
     }
 
+    /// Returns the command for the first window that is created
+    /// when wezterm starts, if `initial_prog` is set
+    pub fn build_initial_prog(&self) -> Option<CommandBuilder> {
+        let args = self.initial_prog.as_ref().filter(|args| !args.is_empty())?;
+        Some(CommandBuilder::from_argv(
+            args.iter().map(OsString::from).collect(),
+        ))
+    }
+
     pub fn apply_cmd_defaults(&self, cmd: &mut CommandBuilder) {
         // Apply `default_cwd` only if `cwd` is not already set, allows `--cwd`
         // option to take precedence
@@ -1559,13 +2416,24 @@ impl Config {
             cmd.cwd(cwd);
         }
 
-        for (k, v) in &self.set_environment_variables {
-            cmd.env(k, v);
-        }
+        // The environment set on the command itself, for example by
+        // a SpawnCommand or by the domain that it is spawned for,
+        // takes precedence over these defaults
+        apply_env_defaults(
+            cmd,
+            &self.set_environment_variables,
+            Some(&self.term),
+            self.locale.as_deref(),
+        );
 
         #[cfg(unix)]
-        cmd.umask(umask::UmaskSaver::saved_umask());
-        cmd.env("TERM", &self.term);
+        {
+            cmd.umask(umask::UmaskSaver::saved_umask());
+            cmd.login_shell(self.login_shell);
+        }
+        if cmd.get_env("COLORTERM").is_none() {
+            cmd.env("COLORTERM", "truecolor");
+        }
         // TERM_PROGRAM and TERM_PROGRAM_VERSION are an emerging
         // de-facto standard for identifying the terminal.
         cmd.env("TERM_PROGRAM", "WezTerm");
@@ -1573,10 +2441,47 @@ impl Config {
     }
 }
 
+/// Sets `TERM` and `LANG` from `term` and `locale`, and each of the
+/// variables in `env`, unless `cmd` already has a value for them.
+/// `term` and `locale` take precedence over `env`.
+pub(crate) fn apply_env_defaults(
+    cmd: &mut CommandBuilder,
+    env: &HashMap<String, String>,
+    term: Option<&str>,
+    locale: Option<&str>,
+) {
+    let vars = term
+        .map(|term| ("TERM", term))
+        .into_iter()
+        .chain(locale.map(|locale| ("LANG", locale)))
+        .chain(env.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+    for (k, v) in vars {
+        if cmd.get_env(k).is_none() {
+            cmd.env(k, v);
+        }
+    }
+}
+
 fn default_ratelimit_line_prefetches_per_second() -> u32 {
     10
 }
 
+fn default_scroll_context_lines() -> usize {
+    5
+}
+
+fn default_middle_click_paste_source() -> ClipboardPasteSource {
+    ClipboardPasteSource::PrimarySelection
+}
+
+fn default_clipboard_history_size() -> usize {
+    50
+}
+
+fn default_mux_server_persist_interval() -> u64 {
+    60
+}
+
 fn default_true() -> bool {
     true
 }
@@ -1591,10 +2496,18 @@ fn default_swap_backspace_and_delete() -> bool {
     false
 }
 
+fn default_scroll_position_indicator_flash_duration() -> u64 {
+    1500
+}
+
 fn default_scrollback_lines() -> usize {
     3500
 }
 
+fn default_floating_pane_size() -> u8 {
+    80
+}
+
 fn default_initial_rows() -> u16 {
     24
 }
@@ -1637,6 +2550,13 @@ fn compute_runtime_dir() -> Result<PathBuf, Error> {
     Ok(HOME_DIR.join(".local/share/wezterm"))
 }
 
+fn compute_data_dir() -> PathBuf {
+    match dirs_next::data_dir() {
+        Some(data) => data.join("wezterm"),
+        None => HOME_DIR.join(".local/share/wezterm"),
+    }
+}
+
 pub fn pki_dir() -> anyhow::Result<PathBuf> {
     compute_runtime_dir().map(|d| d.join("pki"))
 }
@@ -1648,3 +2568,56 @@ fn default_read_timeout() -> Duration {
 fn default_write_timeout() -> Duration {
     Duration::from_secs(60)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn quote_dropped_files_posix() {
+        let quote = |path: &str| DroppedFileQuoting::Posix.quote(path);
+        assert_eq!(quote("/tmp/file.txt"), "/tmp/file.txt");
+        assert_eq!(quote("/tmp/a file"), "'/tmp/a file'");
+        assert_eq!(quote("/tmp/it's"), "'/tmp/it'\\''s'");
+        assert_eq!(quote("$HOME"), "'$HOME'");
+        assert_eq!(quote(""), "''");
+    }
+
+    #[test]
+    fn quote_dropped_files_windows() {
+        let quote = |path: &str| DroppedFileQuoting::Windows.quote(path);
+        assert_eq!(quote(r"C:\Users\me\file.txt"), r"C:\Users\me\file.txt");
+        assert_eq!(quote(r"C:\Program Files\app"), r#""C:\Program Files\app""#);
+        assert_eq!(quote(r"C:\My Dir\"), r#""C:\My Dir\\""#);
+        assert_eq!(quote(r#"a "b" c"#), r#""a \"b\" c""#);
+        assert_eq!(quote(r#"a\"b"#), r#""a\\\"b""#);
+        assert_eq!(quote(""), r#""""#);
+    }
+
+    #[test]
+    fn override_args_round_trip() {
+        assert!(override_args(None, &[]).is_empty());
+        assert_eq!(
+            override_args(
+                Some(Path::new("/home/me/wezterm.lua")),
+                &[
+                    ("font_size".to_string(), "12".to_string()),
+                    ("term".to_string(), "\"x=y\"".to_string()),
+                ]
+            ),
+            vec![
+                "--config-file",
+                "/home/me/wezterm.lua",
+                "--config",
+                "font_size=12",
+                "--config",
+                "term=\"x=y\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn quote_dropped_files_none() {
+        assert_eq!(DroppedFileQuoting::None.quote("a b'c"), "a b'c");
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{FontAttributes, TextStyle};
+use crate::{AllowSquareGlyphOverflow, FontAttributes, TextStyle};
 use anyhow::anyhow;
 use bstr::BString;
 pub use luahelper::*;
@@ -143,6 +143,10 @@ pub fn make_lua_context(config_file: &Path) -> anyhow::Result<Lua> {
         wezterm_mod.set("format", lua.create_function(format)?)?;
         wezterm_mod.set("strftime", lua.create_function(strftime)?)?;
         wezterm_mod.set("battery_info", lua.create_function(battery_info)?)?;
+        wezterm_mod.set(
+            "gui_appearance",
+            lua.create_function(|_, ()| Ok(crate::appearance()))?,
+        )?;
 
         package.set("path", path_array.join(";"))?;
 
@@ -343,6 +347,10 @@ struct TextStyleAttributes {
     /// useful in a `[[font_rules]]` section to implement changing
     /// the text color for eg: bold text.
     pub foreground: Option<termwiz::color::RgbColor>,
+    /// If set, overrides the `allow_square_glyphs_to_overflow_width`
+    /// configuration for text rendered using this style.
+    #[serde(default)]
+    pub allow_square_glyphs_to_overflow_width: Option<AllowSquareGlyphOverflow>,
 }
 impl_lua_conversion!(TextStyleAttributes);
 
@@ -371,6 +379,7 @@ fn font<'lua>(
         is_synthetic: false,
     });
     text_style.foreground = attrs.foreground;
+    text_style.allow_square_glyphs_to_overflow_width = attrs.allow_square_glyphs_to_overflow_width;
 
     Ok(text_style)
 }
@@ -400,6 +409,7 @@ fn font_with_fallback<'lua>(
         });
     }
     text_style.foreground = attrs.foreground;
+    text_style.allow_square_glyphs_to_overflow_width = attrs.allow_square_glyphs_to_overflow_width;
 
     Ok(text_style)
 }
@@ -1,4 +1,5 @@
 use crate::*;
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::str::FromStr;
 
@@ -27,18 +28,73 @@ pub struct SshDomain {
 
     /// The path to the wezterm binary on the remote host
     pub remote_wezterm_path: Option<String>,
+
+    /// Override specific SSH configuration options, in the same way
+    /// as `wezterm ssh -oName=Value`.  These take precedence over the
+    /// values from the ssh config files.
+    #[serde(default)]
+    pub ssh_option: HashMap<String, String>,
+
+    /// If true, check the connection when the system resumes from
+    /// sleep and reconnect if it was lost.  This also allows the
+    /// domain to reconnect after other network errors, which may
+    /// prompt for authentication again, so it is off by default.
+    #[serde(default)]
+    pub reconnect_after_sleep: bool,
+
+    /// Environment variables to set for programs spawned in this
+    /// domain.  These take precedence over the configuration of the
+    /// remote mux server.
+    #[serde(default)]
+    pub set_environment_variables: HashMap<String, String>,
+
+    /// What to set the TERM variable to for programs spawned in this
+    /// domain.  If unset, the `term` of the remote mux server is used.
+    #[serde(default)]
+    pub term: Option<String>,
+
+    /// What to set the LANG variable to for programs spawned in this
+    /// domain.  If unset, the `locale` of the remote mux server is used.
+    #[serde(default)]
+    pub locale: Option<String>,
 }
 impl_lua_conversion!(SshDomain);
 
+impl SshDomain {
+    /// Returns `command` with the environment of this domain applied.
+    /// When there is environment to apply and no command was given,
+    /// a default program builder is returned to carry it; the mux
+    /// server runs its own default program in that case.
+    pub fn apply_cmd_defaults(&self, command: Option<CommandBuilder>) -> Option<CommandBuilder> {
+        if self.set_environment_variables.is_empty() && self.term.is_none() && self.locale.is_none()
+        {
+            return command;
+        }
+        let mut cmd = command.unwrap_or_else(CommandBuilder::new_default_prog);
+        crate::apply_env_defaults(
+            &mut cmd,
+            &self.set_environment_variables,
+            self.term.as_deref(),
+            self.locale.as_deref(),
+        );
+        Some(cmd)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SshParameters {
-    pub username: String,
+    /// The username, if one was specified.  Otherwise, the `User`
+    /// from the ssh config, or the local user name, is used
+    pub username: Option<String>,
     pub host_and_port: String,
 }
 
 impl Display for SshParameters {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}@{}", self.username, self.host_and_port)
+        match &self.username {
+            Some(username) => write!(f, "{}@{}", username, self.host_and_port),
+            None => write!(f, "{}", self.host_and_port),
+        }
     }
 }
 
@@ -59,12 +115,12 @@ impl FromStr for SshParameters {
 
         if parts.len() == 2 {
             Ok(Self {
-                username: parts[0].to_string(),
+                username: Some(parts[0].to_string()),
                 host_and_port: parts[1].to_string(),
             })
         } else if parts.len() == 1 {
             Ok(Self {
-                username: username_from_env()?,
+                username: None,
                 host_and_port: parts[0].to_string(),
             })
         } else {
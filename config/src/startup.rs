@@ -0,0 +1,63 @@
+use crate::*;
+use portable_pty::PtySize;
+use std::path::PathBuf;
+
+/// Describes a window that is created when the GUI starts up
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct StartupWindow {
+    /// The position of the top left corner of the window, in pixels
+    /// relative to the top left corner of `monitor`.  If unspecified,
+    /// the window system decides where to place the window.
+    #[serde(default)]
+    pub position: Option<StartupWindowPosition>,
+
+    /// The monitor on which to place the window.  0 is the
+    /// primary monitor.
+    #[serde(default)]
+    pub monitor: usize,
+
+    /// The size of the window in cells.  If unspecified, the
+    /// `initial_rows` and `initial_cols` settings are used.
+    #[serde(default)]
+    pub rows: Option<u16>,
+    #[serde(default)]
+    pub cols: Option<u16>,
+
+    /// The command to run in the window.  If unspecified,
+    /// the default program is used.
+    #[serde(default)]
+    pub args: Option<Vec<String>>,
+
+    /// The working directory for the command
+    #[serde(default)]
+    pub cwd: Option<PathBuf>,
+
+    /// A name for the window, which is shown at the start of its title
+    #[serde(default)]
+    pub name: Option<String>,
+}
+impl_lua_conversion!(StartupWindow);
+
+impl StartupWindow {
+    /// Returns the size of the window, falling back to the
+    /// initial size from the configuration
+    pub fn size(&self, config: &Config) -> PtySize {
+        let rows = self.rows.unwrap_or(config.initial_rows);
+        let cols = self.cols.unwrap_or(config.initial_cols);
+        PtySize {
+            rows,
+            cols,
+            // The same guess as Config::initial_size; the gui
+            // fills in the real pixel dimensions later
+            pixel_width: 8 * cols,
+            pixel_height: 16 * rows,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct StartupWindowPosition {
+    pub x: isize,
+    pub y: isize,
+}
+impl_lua_conversion!(StartupWindowPosition);
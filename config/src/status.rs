@@ -0,0 +1,40 @@
+use crate::*;
+use std::path::PathBuf;
+
+/// An external command that is run periodically, and whose output
+/// is shown in the tab bar and made available to lua code
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct StatusCommand {
+    /// Identifies the output of the command to lua code
+    pub name: String,
+
+    /// The program to run, followed by its arguments
+    pub args: Vec<String>,
+
+    /// The working directory for the command
+    #[serde(default)]
+    pub cwd: Option<PathBuf>,
+
+    /// How many seconds to wait between runs of the command
+    #[serde(default = "default_status_command_interval")]
+    pub interval_seconds: u64,
+
+    /// A run that takes longer than this many seconds is stopped,
+    /// and counted as having failed
+    #[serde(default = "default_status_command_timeout")]
+    pub timeout_seconds: u64,
+
+    /// Whether the first line of the output is shown at the right
+    /// of the tab bar, after any status set by lua code
+    #[serde(default = "default_true")]
+    pub show_in_tab_bar: bool,
+}
+impl_lua_conversion!(StatusCommand);
+
+fn default_status_command_interval() -> u64 {
+    5
+}
+
+fn default_status_command_timeout() -> u64 {
+    10
+}
@@ -31,6 +31,12 @@ impl wezterm_term::TerminalConfiguration for TermConfig {
         config.resolved_palette.clone().into()
     }
 
+    fn color_palette_for_scheme(&self, scheme_name: &str) -> Option<ColorPalette> {
+        configuration()
+            .color_scheme_by_name(scheme_name)
+            .map(|palette| palette.clone().into())
+    }
+
     fn alternate_buffer_wheel_scroll_speed(&self) -> u8 {
         configuration().alternate_buffer_wheel_scroll_speed
     }
@@ -22,6 +22,14 @@ pub struct TlsDomainServer {
     /// to the trust store.
     #[serde(default)]
     pub pem_root_certs: Vec<PathBuf>,
+
+    /// If set, clients must present this shared secret, in addition
+    /// to a trusted certificate, before they can access any panes
+    pub auth_token: Option<String>,
+
+    /// If set, clients that present this shared secret may view
+    /// panes, but not send input to them or spawn new ones
+    pub read_only_auth_token: Option<String>,
 }
 impl_lua_conversion!(TlsDomainServer);
 
@@ -82,6 +90,16 @@ pub struct TlsDomainClient {
 
     /// The path to the wezterm binary on the remote host
     pub remote_wezterm_path: Option<String>,
+
+    /// If true, check the connection when the system resumes from
+    /// sleep and reconnect right away if it was lost, rather than
+    /// waiting for the read timeout to expire
+    #[serde(default = "default_true")]
+    pub reconnect_after_sleep: bool,
+
+    /// The shared secret to present to a server that sets
+    /// `auth_token` or `read_only_auth_token`
+    pub auth_token: Option<String>,
 }
 impl_lua_conversion!(TlsDomainClient);
 
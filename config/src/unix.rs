@@ -38,6 +38,15 @@ pub struct UnixDomain {
     #[serde(default)]
     pub skip_permissions_check: bool,
 
+    /// Only processes running as the same user as the server may
+    /// connect to it.  The users with these uids may also connect,
+    /// but can only view panes; they cannot send input to them or
+    /// spawn new ones.  This has no effect on Windows, or when
+    /// `skip_permissions_check` is set, as the identity of the peer
+    /// is not checked in those cases.
+    #[serde(default)]
+    pub read_only_peer_uids: Vec<u32>,
+
     #[serde(default = "default_read_timeout")]
     pub read_timeout: Duration,
 
@@ -55,6 +64,7 @@ impl Default for UnixDomain {
             no_serve_automatically: false,
             serve_command: None,
             skip_permissions_check: false,
+            read_only_peer_uids: vec![],
             read_timeout: default_read_timeout(),
             write_timeout: default_write_timeout(),
         }
@@ -69,6 +79,58 @@ impl UnixDomain {
             .unwrap_or_else(|| RUNTIME_DIR.join("sock"))
     }
 
+    /// Verify that the directory containing the socket is owned by
+    /// the current user and is not writable by other users, so that
+    /// nobody else can substitute their own server in our place.
+    /// A directory that doesn't exist yet is considered to be safe;
+    /// the server will create it with appropriate permissions.
+    pub fn check_socket_permissions(&self) -> anyhow::Result<()> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+
+            if running_under_wsl() || self.skip_permissions_check {
+                return Ok(());
+            }
+
+            let sock_path = self.socket_path();
+            let sock_dir = sock_path
+                .parent()
+                .ok_or_else(|| anyhow!("sock_path {} has no parent dir", sock_path.display()))?;
+
+            let meta = match sock_dir.symlink_metadata() {
+                Ok(meta) => meta,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+                Err(err) => {
+                    return Err(err).context(format!("checking {}", sock_dir.display()));
+                }
+            };
+
+            let uid = unsafe { libc::getuid() };
+            if meta.uid() != uid {
+                anyhow::bail!(
+                    "{} is owned by uid {} rather than the current user (uid {}). \
+                     Set skip_permissions_check = true in the configuration \
+                     for the `{}` domain if this is intentional",
+                    sock_dir.display(),
+                    meta.uid(),
+                    uid,
+                    self.name
+                );
+            }
+
+            if (meta.mode() & 0o22) != 0 {
+                anyhow::bail!(
+                    "The permissions for {} are insecure and currently \
+                     allow other users to write to it (mode={:o})",
+                    sock_dir.display(),
+                    meta.mode() & 0o7777
+                );
+            }
+        }
+        Ok(())
+    }
+
     pub fn default_unix_domains() -> Vec<Self> {
         vec![UnixDomain {
             read_timeout: default_read_timeout(),
@@ -77,19 +139,61 @@ impl UnixDomain {
         }]
     }
 
+    /// Returns the command that starts the server for this domain.
+    /// The default command passes along our configuration file and
+    /// overrides, so that the server knows about this domain even if
+    /// it is only defined there.
     pub fn serve_command(&self) -> anyhow::Result<Vec<OsString>> {
         match self.serve_command.as_ref() {
             Some(cmd) => Ok(cmd.iter().map(Into::into).collect()),
-            None => Ok(vec![
-                std::env::current_exe()?
+            None => {
+                let mut argv = vec![std::env::current_exe()?
                     .with_file_name(if cfg!(windows) {
                         "wezterm-mux-server.exe"
                     } else {
                         "wezterm-mux-server"
                     })
-                    .into_os_string(),
-                OsString::from("--daemonize"),
-            ]),
+                    .into_os_string()];
+                argv.extend(config_override_args());
+                argv.push(OsString::from("--daemonize"));
+                Ok(argv)
+            }
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod test {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn socket_permissions() {
+        let dir = std::env::temp_dir().join(format!("wezterm-sock-test-{}", std::process::id()));
+        let unix_dom = UnixDomain {
+            socket_path: Some(dir.join("sock")),
+            ..Default::default()
+        };
+
+        // The server will create the directory if it is missing
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(unix_dom.check_socket_permissions().is_ok());
+
+        create_user_owned_dirs(&dir).unwrap();
+        assert!(unix_dom.check_socket_permissions().is_ok());
+
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o777)).unwrap();
+        let insecure = unix_dom.check_socket_permissions();
+        let skipped = UnixDomain {
+            skip_permissions_check: true,
+            ..unix_dom.clone()
+        }
+        .check_socket_permissions();
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        if !running_under_wsl() {
+            assert!(insecure.is_err());
         }
+        assert!(skipped.is_ok());
     }
 }
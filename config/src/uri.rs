@@ -0,0 +1,38 @@
+use crate::keyassignment::OpenWithTarget;
+use crate::*;
+
+/// Routes links that are opened from the terminal, such as by
+/// clicking on them, to a handler other than the platform opener.
+/// The handlers are tried in order and the first match wins.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct UriHandler {
+    /// Matches links whose scheme, such as `mailto`, is equal to
+    /// this, ignoring case
+    #[serde(default)]
+    pub scheme: Option<String>,
+
+    /// Matches links that match this regular expression.
+    /// If both `scheme` and `regex` are specified, then both
+    /// must match.
+    #[serde(default)]
+    pub regex: Option<String>,
+
+    pub action: UriHandlerAction,
+}
+impl_lua_conversion!(UriHandler);
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub enum UriHandlerAction {
+    /// Open the link using the platform opener
+    Default,
+    /// Run a program.  The arguments use the same template as
+    /// the `OpenWith` key assignment.
+    Run {
+        args: Vec<String>,
+        #[serde(default)]
+        target: OpenWithTarget,
+    },
+    /// Activate the pane whose id is the host portion of the link,
+    /// for example `pane://3`
+    ActivatePane,
+}
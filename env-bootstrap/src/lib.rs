@@ -1,5 +1,9 @@
 use std::path::{Path, PathBuf};
 
+mod ringlog;
+
+pub use ringlog::{log_filters, recent_log_lines};
+
 pub fn set_wezterm_executable() {
     if let Ok(exe) = std::env::current_exe() {
         if let Some(dir) = exe.parent() {
@@ -9,14 +13,50 @@ pub fn set_wezterm_executable() {
     }
 }
 
+/// Checks that `filters` uses the syntax of `WEZTERM_LOG`: a comma
+/// separated list of `module=level`, `module` or `level` directives,
+/// optionally followed by `/regex`
+fn validate_log_filters(filters: &str) -> Result<(), String> {
+    let directives = filters.split('/').next().unwrap_or("");
+    for directive in directives.split(',').map(str::trim) {
+        let mut parts = directive.splitn(2, '=');
+        let module = parts.next().unwrap_or("");
+        match parts.next() {
+            Some(level) => {
+                if module.is_empty() {
+                    return Err(format!("missing module name in `{}`", directive));
+                }
+                level
+                    .parse::<log::LevelFilter>()
+                    .map_err(|_| format!("invalid level `{}` in `{}`", level, directive))?;
+            }
+            // Either a level or a module name, both of which are valid
+            None => {}
+        }
+    }
+    Ok(())
+}
+
+/// Replaces the log filters with `filters`, which uses the same syntax
+/// as the `WEZTERM_LOG` environment variable; eg: `info` or
+/// `wezterm_gui=debug,info`
+pub fn set_log_filters(filters: &str) -> Result<(), String> {
+    validate_log_filters(filters)?;
+    let logger = pretty_env_logger::formatted_timed_builder()
+        .parse_filters(filters)
+        .build();
+    let max_level = logger.filter();
+    ringlog::set_inner(Box::new(logger), max_level, filters);
+    Ok(())
+}
+
 pub fn setup_logger() {
-    let mut builder = pretty_env_logger::formatted_timed_builder();
-    if let Ok(s) = std::env::var("WEZTERM_LOG") {
-        builder.parse_filters(&s);
-    } else {
-        builder.filter(None, log::LevelFilter::Info);
+    let filters = std::env::var("WEZTERM_LOG").unwrap_or_else(|_| "info".to_string());
+    if let Err(err) = set_log_filters(&filters) {
+        eprintln!("Ignoring WEZTERM_LOG: {}", err);
+        set_log_filters("info").ok();
     }
-    builder.init();
+    log::set_boxed_logger(Box::new(ringlog::RingLogger)).ok();
 }
 
 pub fn fixup_appimage() {
@@ -131,3 +171,34 @@ pub fn bootstrap() {
 
     setup_logger();
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn valid_log_filters() {
+        for filters in &[
+            "info",
+            "wezterm_gui",
+            "wezterm_gui=debug,info",
+            "mux=trace, wezterm_gui=off",
+            "info,",
+            "debug/some.*regex",
+        ] {
+            assert_eq!(validate_log_filters(filters), Ok(()), "{}", filters);
+        }
+    }
+
+    #[test]
+    fn invalid_log_filters() {
+        assert_eq!(
+            validate_log_filters("wezterm_gui=loud"),
+            Err("invalid level `loud` in `wezterm_gui=loud`".to_string())
+        );
+        assert_eq!(
+            validate_log_filters("info,=debug"),
+            Err("missing module name in `=debug`".to_string())
+        );
+    }
+}
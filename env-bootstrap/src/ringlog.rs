@@ -0,0 +1,93 @@
+//! The logger that is installed by `setup_logger`.  It keeps the most
+//! recent log messages in memory, so that they can be shown in the
+//! debug overlay and included in a crash report, and allows the
+//! filters to be changed while wezterm is running.
+use log::{LevelFilter, Log, Metadata, Record};
+use std::collections::VecDeque;
+use std::sync::{Mutex, RwLock};
+use std::time::Instant;
+
+/// The number of messages that are kept
+const MAX_LINES: usize = 200;
+
+lazy_static::lazy_static! {
+    static ref LINES: Mutex<VecDeque<String>> = Mutex::new(VecDeque::with_capacity(MAX_LINES));
+    /// The logger that filters and formats the messages, and the
+    /// filters that it was built from.  It is replaced when the
+    /// filters are changed.
+    static ref INNER: RwLock<Option<(Box<dyn Log>, String)>> = RwLock::new(None);
+    static ref START: Instant = Instant::now();
+}
+
+pub(crate) struct RingLogger;
+
+/// Replaces the logger that RingLogger delegates to
+pub(crate) fn set_inner(logger: Box<dyn Log>, max_level: LevelFilter, filters: &str) {
+    *INNER.write().unwrap() = Some((logger, filters.to_string()));
+    log::set_max_level(max_level);
+}
+
+/// Returns the filters that are in effect
+pub fn log_filters() -> String {
+    INNER
+        .read()
+        .unwrap()
+        .as_ref()
+        .map(|(_, filters)| filters.clone())
+        .unwrap_or_default()
+}
+
+impl Log for RingLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        match INNER.read() {
+            Ok(inner) => inner
+                .as_ref()
+                .map_or(false, |(logger, _)| logger.enabled(metadata)),
+            Err(_) => false,
+        }
+    }
+
+    fn log(&self, record: &Record) {
+        let inner = match INNER.read() {
+            Ok(inner) => inner,
+            Err(_) => return,
+        };
+        let logger = match inner.as_ref() {
+            Some((logger, _)) if logger.enabled(record.metadata()) => logger,
+            _ => return,
+        };
+        let line = format!(
+            "{:>10.3} {:<5} {} > {}",
+            START.elapsed().as_secs_f64(),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        if let Ok(mut lines) = LINES.lock() {
+            if lines.len() == MAX_LINES {
+                lines.pop_front();
+            }
+            lines.push_back(line);
+        }
+        logger.log(record);
+    }
+
+    fn flush(&self) {
+        if let Ok(inner) = INNER.read() {
+            if let Some((logger, _)) = inner.as_ref() {
+                logger.flush();
+            }
+        }
+    }
+}
+
+/// Returns the most recent log messages, oldest first, each prefixed
+/// with the number of seconds since logging started.
+/// This is called while panicking, so it gives up rather than waiting
+/// if a message is being recorded.
+pub fn recent_log_lines() -> Vec<String> {
+    match LINES.try_lock() {
+        Ok(lines) => lines.iter().cloned().collect(),
+        Err(_) => vec![],
+    }
+}
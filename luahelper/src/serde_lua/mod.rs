@@ -5,6 +5,7 @@ use serde::de::{
     IntoDeserializer, Unexpected, VariantAccess, Visitor,
 };
 use serde::{serde_if_integer128, Deserialize};
+use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::convert::TryInto;
@@ -21,6 +22,64 @@ where
     T::deserialize(ValueWrapper(value))
 }
 
+/// A field that was present in a lua table but that isn't known to
+/// the struct that the table was deserialized into
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownField {
+    pub field: String,
+    pub struct_name: &'static str,
+    /// Similarly named fields, most similar first
+    pub suggestions: Vec<&'static str>,
+}
+
+impl std::fmt::Display for UnknownField {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            fmt,
+            "unknown field `{}` in struct of type `{}`.",
+            self.field, self.struct_name
+        )?;
+        match self.suggestions.len() {
+            0 => Ok(()),
+            1 => write!(fmt, " Did you mean `{}`?", self.suggestions[0]),
+            _ => {
+                write!(fmt, " Did you mean one of ")?;
+                for (idx, candidate) in self.suggestions.iter().enumerate() {
+                    if idx > 0 {
+                        write!(fmt, ", ")?;
+                    }
+                    write!(fmt, "`{}`", candidate)?;
+                }
+                write!(fmt, "?")
+            }
+        }
+    }
+}
+
+thread_local! {
+    /// While collect_unknown_fields is running, unknown fields are
+    /// recorded here rather than being logged
+    static UNKNOWN_FIELDS: RefCell<Option<Vec<UnknownField>>> = RefCell::new(None);
+}
+
+/// Calls `func` and returns its result along with the unknown fields
+/// that were encountered by `from_lua_value` on this thread while it
+/// was running.  The unknown fields are not logged.
+pub fn collect_unknown_fields<R>(func: impl FnOnce() -> R) -> (R, Vec<UnknownField>) {
+    let prior = UNKNOWN_FIELDS.with(|fields| fields.borrow_mut().replace(vec![]));
+    let result = func();
+    let fields = UNKNOWN_FIELDS.with(|fields| {
+        let mut fields = fields.borrow_mut();
+        let collected = fields.take().unwrap_or_default();
+        if let Some(mut prior) = prior {
+            prior.extend(collected.iter().cloned());
+            fields.replace(prior);
+        }
+        collected
+    });
+    (result, fields)
+}
+
 fn unexpected<'lua>(v: &'lua Value<'lua>) -> Unexpected<'lua> {
     match v {
         Value::Nil => Unexpected::Other("lua nil"),
@@ -252,43 +311,42 @@ where
                             candidates.sort_by(|a, b| {
                                 b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal)
                             });
-                            let suggestions: Vec<&str> =
-                                candidates.into_iter().map(|(_, name)| name).collect();
+                            let unknown = UnknownField {
+                                field: pair.0.clone(),
+                                struct_name: struct_name.unwrap_or("<unknown>"),
+                                suggestions: candidates.into_iter().map(|(_, name)| name).collect(),
+                            };
+
+                            let collected =
+                                UNKNOWN_FIELDS.with(|fields| match fields.borrow_mut().as_mut() {
+                                    Some(fields) => {
+                                        fields.push(unknown.clone());
+                                        true
+                                    }
+                                    None => false,
+                                });
+                            if collected {
+                                continue;
+                            }
 
                             // Filter the suggestions out of the allowed field names
                             // and sort what remains.
                             let mut fields: Vec<&str> = allowed_fields
                                 .iter()
                                 .filter(|&name| {
-                                    !suggestions.iter().any(|candidate| candidate == name)
+                                    !unknown
+                                        .suggestions
+                                        .iter()
+                                        .any(|candidate| candidate == name)
                                 })
                                 .copied()
                                 .collect();
                             fields.sort_unstable();
 
                             let mut message = String::new();
-
-                            match suggestions.len() {
-                                0 => {}
-                                1 => {
-                                    message.push_str(&format!("Did you mean `{}`?", suggestions[0]))
-                                }
-                                _ => {
-                                    message.push_str("Did you mean one of ");
-                                    for (idx, candidate) in suggestions.iter().enumerate() {
-                                        if idx > 0 {
-                                            message.push_str(", ");
-                                        }
-                                        message.push('`');
-                                        message.push_str(candidate);
-                                        message.push('`');
-                                    }
-                                    message.push('?');
-                                }
-                            }
                             if !fields.is_empty() {
-                                if suggestions.is_empty() {
-                                    message.push_str("Possible fields are ");
+                                if unknown.suggestions.is_empty() {
+                                    message.push_str(" Possible fields are ");
                                 } else {
                                     message.push_str(" Other possible fields are ");
                                 }
@@ -302,12 +360,7 @@ where
                                 }
                                 message.push('.');
                             }
-                            log::error!(
-                                "Ignoring unknown field `{}` in struct of type `{}`. {}",
-                                pair.0,
-                                struct_name.unwrap_or("<unknown>"),
-                                message
-                            );
+                            log::error!("Ignoring {}{}", unknown, message);
 
                             continue;
                         }
@@ -971,6 +1024,40 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_unknown_fields() {
+        #[derive(Deserialize, Clone, Debug, Eq, PartialEq)]
+        struct MyStruct {
+            #[serde(default)]
+            font_size: i64,
+            #[serde(default)]
+            line_height: i64,
+        }
+
+        let lua = Lua::new();
+        let (res, unknown) = collect_unknown_fields(|| {
+            from_lua_value::<MyStruct>(lua.load("{fonts_size=2, bogus=1}").eval().unwrap())
+        });
+        assert_eq!(
+            res.unwrap(),
+            MyStruct {
+                font_size: 0,
+                line_height: 0
+            }
+        );
+
+        let mut unknown: Vec<String> = unknown.iter().map(|f| f.to_string()).collect();
+        unknown.sort();
+        assert_eq!(
+            unknown,
+            vec![
+                "unknown field `bogus` in struct of type `MyStruct`.",
+                "unknown field `fonts_size` in struct of type `MyStruct`. \
+                 Did you mean `font_size`?",
+            ]
+        );
+    }
+
     #[test]
     fn test_option_mode() {
         #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
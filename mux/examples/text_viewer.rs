@@ -0,0 +1,296 @@
+//! This example shows how to provide a custom pane to the mux.
+//! It registers a "viewer" domain whose panes display the contents
+//! of a text file, spawns one of them and prints what it shows.
+//!
+//! cargo run -p mux --example text_viewer -- README.md
+use async_trait::async_trait;
+use filedescriptor::{FileDescriptor, Pipe};
+use mux::plugin::*;
+use rangeset::RangeSet;
+use std::cell::{Cell, RefCell, RefMut};
+use std::ops::Range;
+use std::rc::Rc;
+use std::sync::Arc;
+use termwiz::cell::CellAttributes;
+use termwiz::surface::Line;
+use url::Url;
+
+struct TextViewerPane {
+    pane_id: PaneId,
+    domain_id: DomainId,
+    title: String,
+    lines: Vec<Line>,
+    size: RefCell<PtySize>,
+    top: Cell<StableRowIndex>,
+    dead: Cell<bool>,
+    /// The mux reads output from each pane on a background thread.
+    /// This pane has no output, so we hand it the read end of a pipe
+    /// that stays open for as long as the pane is alive.
+    reader: FileDescriptor,
+    writer: RefCell<FileDescriptor>,
+}
+
+impl TextViewerPane {
+    fn new(domain_id: DomainId, path: &str, size: PtySize) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        let attrs = CellAttributes::default();
+        let lines = text
+            .lines()
+            .map(|line| Line::from_text(line, &attrs))
+            .collect();
+        let pipe = Pipe::new()?;
+        Ok(Self {
+            pane_id: alloc_pane_id(),
+            domain_id,
+            title: path.to_string(),
+            lines,
+            size: RefCell::new(size),
+            top: Cell::new(0),
+            dead: Cell::new(false),
+            reader: pipe.read,
+            writer: RefCell::new(pipe.write),
+        })
+    }
+
+    fn scroll_by(&self, delta: isize) {
+        let rows = self.size.borrow().rows as isize;
+        let max_top = (self.lines.len() as isize - rows).max(0);
+        let top = (self.top.get() + delta).max(0).min(max_top);
+        self.top.set(top);
+    }
+}
+
+impl Pane for TextViewerPane {
+    fn pane_id(&self) -> PaneId {
+        self.pane_id
+    }
+
+    fn get_cursor_position(&self) -> StableCursorPosition {
+        StableCursorPosition {
+            y: self.top.get(),
+            visibility: termwiz::surface::CursorVisibility::Hidden,
+            ..Default::default()
+        }
+    }
+
+    fn get_dirty_lines(&self, lines: Range<StableRowIndex>) -> RangeSet<StableRowIndex> {
+        // Scrolling changes which lines are shown, so treat all of
+        // them as dirty; the content is small enough for that to be cheap
+        let mut set = RangeSet::new();
+        set.add_range(lines);
+        set
+    }
+
+    fn get_lines(&self, lines: Range<StableRowIndex>) -> (StableRowIndex, Vec<Line>) {
+        let cols = self.size.borrow().cols as usize;
+        let result = lines
+            .clone()
+            .map(|idx| {
+                self.lines
+                    .get(idx as usize)
+                    .cloned()
+                    .unwrap_or_else(|| Line::with_width(cols))
+            })
+            .collect();
+        (lines.start, result)
+    }
+
+    fn get_dimensions(&self) -> RenderableDimensions {
+        let size = *self.size.borrow();
+        RenderableDimensions {
+            cols: size.cols as usize,
+            viewport_rows: size.rows as usize,
+            scrollback_rows: self.lines.len().max(size.rows as usize),
+            physical_top: self.top.get(),
+            scrollback_top: 0,
+        }
+    }
+
+    fn get_title(&self) -> String {
+        self.title.clone()
+    }
+
+    fn can_close_without_prompting(&self) -> bool {
+        true
+    }
+
+    fn send_paste(&self, _text: &str) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn reader(&self) -> anyhow::Result<Box<dyn std::io::Read + Send>> {
+        Ok(Box::new(self.reader.try_clone()?))
+    }
+
+    fn writer(&self) -> RefMut<dyn std::io::Write> {
+        self.writer.borrow_mut()
+    }
+
+    fn resize(&self, size: PtySize) -> anyhow::Result<()> {
+        *self.size.borrow_mut() = size;
+        self.scroll_by(0);
+        Ok(())
+    }
+
+    fn key_down(&self, key: KeyCode, _mods: KeyModifiers) -> anyhow::Result<()> {
+        let rows = self.size.borrow().rows as isize;
+        match key {
+            KeyCode::UpArrow | KeyCode::Char('k') => self.scroll_by(-1),
+            KeyCode::DownArrow | KeyCode::Char('j') => self.scroll_by(1),
+            KeyCode::PageUp => self.scroll_by(-rows),
+            KeyCode::PageDown | KeyCode::Char(' ') => self.scroll_by(rows),
+            KeyCode::Char('q') => self.kill(),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn mouse_event(&self, _event: MouseEvent) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn is_dead(&self) -> bool {
+        self.dead.get()
+    }
+
+    fn kill(&self) {
+        self.dead.set(true);
+    }
+
+    fn palette(&self) -> ColorPalette {
+        ColorPalette::default()
+    }
+
+    fn domain_id(&self) -> DomainId {
+        self.domain_id
+    }
+
+    fn is_mouse_grabbed(&self) -> bool {
+        false
+    }
+
+    fn is_alt_screen_active(&self) -> bool {
+        false
+    }
+
+    fn get_current_working_dir(&self) -> Option<Url> {
+        None
+    }
+}
+
+struct TextViewerDomain {
+    id: DomainId,
+    path: String,
+}
+
+#[async_trait(?Send)]
+impl Domain for TextViewerDomain {
+    async fn spawn(
+        &self,
+        size: PtySize,
+        _command: Option<CommandBuilder>,
+        _command_dir: Option<String>,
+        window: WindowId,
+    ) -> anyhow::Result<Rc<Tab>> {
+        let pane: Rc<dyn Pane> = Rc::new(TextViewerPane::new(self.id, &self.path, size)?);
+        let tab = Rc::new(Tab::new(&size));
+        tab.assign_pane(&pane);
+
+        let mux = Mux::get().unwrap();
+        mux.add_tab_and_active_pane(&tab)?;
+        mux.add_tab_to_window(&tab, window)?;
+        Ok(tab)
+    }
+
+    async fn split_pane(
+        &self,
+        _command: Option<CommandBuilder>,
+        _command_dir: Option<String>,
+        tab: TabId,
+        pane_id: PaneId,
+        request: SplitRequest,
+    ) -> anyhow::Result<Rc<dyn Pane>> {
+        let mux = Mux::get().unwrap();
+        let tab = mux
+            .get_tab(tab)
+            .ok_or_else(|| anyhow::anyhow!("invalid tab id {}", tab))?;
+        let pane_index = tab
+            .iter_panes()
+            .iter()
+            .find(|p| p.pane.pane_id() == pane_id)
+            .map(|p| p.index)
+            .ok_or_else(|| anyhow::anyhow!("invalid pane id {}", pane_id))?;
+        let split_size = tab
+            .compute_split_size(pane_index, request)
+            .ok_or_else(|| anyhow::anyhow!("invalid pane index {}", pane_index))?;
+
+        let pane: Rc<dyn Pane> =
+            Rc::new(TextViewerPane::new(self.id, &self.path, split_size.second)?);
+        tab.split_and_insert(pane_index, request, Rc::clone(&pane))?;
+        mux.add_pane(&pane)?;
+        Ok(pane)
+    }
+
+    fn domain_id(&self) -> DomainId {
+        self.id
+    }
+
+    fn domain_name(&self) -> &str {
+        "viewer"
+    }
+
+    async fn attach(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn detach(&self) -> anyhow::Result<()> {
+        anyhow::bail!("detach not implemented");
+    }
+
+    fn state(&self) -> DomainState {
+        DomainState::Attached
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "README.md".to_string());
+
+    // A real plugin would do this at the top of the frontend's main
+    // function; the domain then shows up in the launcher alongside
+    // the built in domains.
+    mux::plugin::register_domain(move || {
+        let domain: Arc<dyn Domain> = Arc::new(TextViewerDomain {
+            id: alloc_domain_id(),
+            path: path.clone(),
+        });
+        Ok(domain)
+    });
+
+    let _executor = promise::spawn::SimpleExecutor::new();
+    let mux = Rc::new(Mux::new(None));
+    Mux::set_mux(&mux);
+
+    let domain = mux
+        .get_domain_by_name("viewer")
+        .ok_or_else(|| anyhow::anyhow!("viewer domain was not registered"))?;
+    let size = PtySize {
+        rows: 10,
+        cols: 80,
+        pixel_width: 0,
+        pixel_height: 0,
+    };
+    let window_id = *mux.new_empty_window();
+    let tab = promise::spawn::block_on(domain.spawn(size, None, None, window_id))?;
+    let pane = tab.get_active_pane().unwrap();
+
+    let dims = pane.get_dimensions();
+    let (_, lines) =
+        pane.get_lines(dims.physical_top..dims.physical_top + dims.viewport_rows as isize);
+    for line in lines {
+        println!("{}", line.as_str().trim_end());
+    }
+
+    Ok(())
+}
@@ -7,7 +7,8 @@
 
 use crate::localpane::LocalPane;
 use crate::pane::{alloc_pane_id, Pane, PaneId};
-use crate::tab::{SplitDirection, Tab, TabId};
+use crate::scratch::ScratchPane;
+use crate::tab::{SplitRequest, Tab, TabId};
 use crate::window::WindowId;
 use crate::Mux;
 use anyhow::{bail, Error};
@@ -47,9 +48,45 @@ pub trait Domain: Downcast {
         command_dir: Option<String>,
         tab: TabId,
         pane_id: PaneId,
-        split_direction: SplitDirection,
+        request: SplitRequest,
     ) -> anyhow::Result<Rc<dyn Pane>>;
 
+    /// Spawn a command into a floating pane that hovers above the
+    /// splits of the tab.  Not all domains support floating panes.
+    async fn spawn_floating_pane(
+        &self,
+        _command: Option<CommandBuilder>,
+        _command_dir: Option<String>,
+        _tab: TabId,
+    ) -> anyhow::Result<Rc<dyn Pane>> {
+        bail!(
+            "domain {} does not support floating panes",
+            self.domain_name()
+        );
+    }
+
+    /// Spawn a new tab holding a scratch pane, which displays content
+    /// written via `ScratchPane::write_str` rather than the output of
+    /// a command.
+    async fn spawn_view(
+        &self,
+        size: PtySize,
+        title: &str,
+        window: WindowId,
+    ) -> anyhow::Result<(Rc<Tab>, Rc<ScratchPane>)> {
+        let scratch = Rc::new(ScratchPane::new(self.domain_id(), size, title)?);
+        let pane: Rc<dyn Pane> = scratch.clone();
+
+        let tab = Rc::new(Tab::new(&size));
+        tab.assign_pane(&pane);
+
+        let mux = Mux::get().unwrap();
+        mux.add_tab_and_active_pane(&tab)?;
+        mux.add_tab_to_window(&tab, window)?;
+
+        Ok((tab, scratch))
+    }
+
     /// Returns false if the `spawn` method will never succeed.
     /// There are some internal placeholder domains that are
     /// pre-created with local UI that we do not want to allow
@@ -101,19 +138,32 @@ impl LocalDomain {
             name: name.to_string(),
         }
     }
-}
 
-#[async_trait(?Send)]
-impl Domain for LocalDomain {
-    async fn spawn(
+    /// Spawns `command`, or the default program, in a new pty of the
+    /// specified size, and returns the pane that represents it.
+    /// The caller is responsible for adding the pane to a tab and
+    /// to the mux.
+    pub(crate) fn spawn_local_pane(
         &self,
         size: PtySize,
         command: Option<CommandBuilder>,
         command_dir: Option<String>,
-        window: WindowId,
-    ) -> Result<Rc<Tab>, Error> {
+    ) -> anyhow::Result<Rc<dyn Pane>> {
         let config = configuration();
         let mut cmd = match command {
+            // A client domain sends a default program builder when it
+            // only has environment to add, in which case the configured
+            // default_prog is run with that environment
+            Some(cmd) if cmd.is_default_prog() => {
+                let mut prog = config.build_prog(None)?;
+                for (k, v) in cmd.iter_env_as_str() {
+                    prog.env(k, v);
+                }
+                if let Some(cwd) = cmd.get_cwd() {
+                    prog.cwd(cwd);
+                }
+                prog
+            }
             Some(mut cmd) => {
                 config.apply_cmd_defaults(&mut cmd);
                 cmd
@@ -133,6 +183,8 @@ impl Domain for LocalDomain {
         let pane_id = alloc_pane_id();
         cmd.env("WEZTERM_PANE", pane_id.to_string());
 
+        // Remember the command so that the pane can be restarted
+        let command = cmd.clone();
         let child = pair.slave.spawn_command(cmd)?;
         log::trace!("spawned: {:?}", child);
 
@@ -146,14 +198,43 @@ impl Domain for LocalDomain {
             Box::new(writer),
         );
 
+        let pane = LocalPane::new(pane_id, terminal, child, pair.master, self.id);
+        pane.set_command(command);
+        Ok(Rc::new(pane))
+    }
+
+    /// Spawns the command that `pane` was originally spawned with in a
+    /// new pty of the same size, and swaps it into the pane in place of
+    /// its current process.
+    /// The caller is responsible for reading the output of the new pty;
+    /// see `Mux::restart_pane`.
+    pub fn restart_pane(&self, pane: &LocalPane) -> anyhow::Result<()> {
+        let cmd = match pane.command() {
+            Some(cmd) => cmd,
+            None => bail!("pane {} has no command to restart", pane.pane_id()),
+        };
+        let size = pane.pty_size()?;
+        let pair = self.pty_system.openpty(size)?;
+        let child = pair.slave.spawn_command(cmd)?;
+        log::trace!("restarted: {:?}", child);
+
+        let writer = pair.master.try_clone_writer()?;
+        pane.replace_process(child, pair.master, Box::new(writer));
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl Domain for LocalDomain {
+    async fn spawn(
+        &self,
+        size: PtySize,
+        command: Option<CommandBuilder>,
+        command_dir: Option<String>,
+        window: WindowId,
+    ) -> Result<Rc<Tab>, Error> {
+        let pane = self.spawn_local_pane(size, command, command_dir)?;
         let mux = Mux::get().unwrap();
-        let pane: Rc<dyn Pane> = Rc::new(LocalPane::new(
-            pane_id,
-            terminal,
-            child,
-            pair.master,
-            self.id,
-        ));
 
         let tab = Rc::new(Tab::new(&size));
         tab.assign_pane(&pane);
@@ -170,7 +251,7 @@ impl Domain for LocalDomain {
         command_dir: Option<String>,
         tab: TabId,
         pane_id: PaneId,
-        direction: SplitDirection,
+        request: SplitRequest,
     ) -> anyhow::Result<Rc<dyn Pane>> {
         let mux = Mux::get().unwrap();
         let tab = match mux.get_tab(tab) {
@@ -187,54 +268,34 @@ impl Domain for LocalDomain {
             None => anyhow::bail!("invalid pane id {}", pane_id),
         };
 
-        let split_size = match tab.compute_split_size(pane_index, direction) {
+        let split_size = match tab.compute_split_size(pane_index, request) {
             Some(s) => s,
             None => anyhow::bail!("invalid pane index {}", pane_index),
         };
 
-        let config = configuration();
-        let mut cmd = match command {
-            Some(mut cmd) => {
-                config.apply_cmd_defaults(&mut cmd);
-                cmd
-            }
-            None => config.build_prog(None)?,
-        };
-        if let Some(dir) = command_dir {
-            // I'm not normally a fan of existence checking, but not checking here
-            // can be painful; in the case where a tab is local but has connected
-            // to a remote system and that remote has used OSC 7 to set a path
-            // that doesn't exist on the local system, process spawning can fail.
-            if std::path::Path::new(&dir).exists() {
-                cmd.cwd(dir);
-            }
-        }
-        let pair = self.pty_system.openpty(split_size.second)?;
-        let pane_id = alloc_pane_id();
-        cmd.env("WEZTERM_PANE", pane_id.to_string());
-        let child = pair.slave.spawn_command(cmd)?;
-        log::trace!("spawned: {:?}", child);
+        let pane = self.spawn_local_pane(split_size.second, command, command_dir)?;
 
-        let writer = pair.master.try_clone_writer()?;
+        tab.split_and_insert(pane_index, request, Rc::clone(&pane))?;
 
-        let terminal = wezterm_term::Terminal::new(
-            crate::pty_size_to_terminal_size(split_size.second),
-            std::sync::Arc::new(config::TermConfig {}),
-            "WezTerm",
-            config::wezterm_version(),
-            Box::new(writer),
-        );
+        mux.add_pane(&pane)?;
 
-        let pane: Rc<dyn Pane> = Rc::new(LocalPane::new(
-            pane_id,
-            terminal,
-            child,
-            pair.master,
-            self.id,
-        ));
+        Ok(pane)
+    }
 
-        tab.split_and_insert(pane_index, direction, Rc::clone(&pane))?;
+    async fn spawn_floating_pane(
+        &self,
+        command: Option<CommandBuilder>,
+        command_dir: Option<String>,
+        tab: TabId,
+    ) -> anyhow::Result<Rc<dyn Pane>> {
+        let mux = Mux::get().unwrap();
+        let tab = match mux.get_tab(tab) {
+            Some(t) => t,
+            None => anyhow::bail!("Invalid tab id {}", tab),
+        };
 
+        let pane = self.spawn_local_pane(tab.compute_floating_size(), command, command_dir)?;
+        tab.set_floating_pane(&pane);
         mux.add_pane(&pane)?;
 
         Ok(pane)
@@ -0,0 +1,266 @@
+//! When escape debugging is enabled for a pane, the output of the pane
+//! is displayed without being interpreted.  Instead, control codes and
+//! escape sequences are made visible, with control codes shown as
+//! reverse video mnemonics, so that broken output from an application
+//! can be examined on screen.
+//! The terminal model of the pane continues to interpret its output
+//! as usual; the visible form is rendered from a separate model, so
+//! that disabling the mode shows the real contents of the pane.
+use crate::domain::DomainId;
+use crate::pane::{Pane, PaneId};
+use crate::renderable::*;
+use portable_pty::PtySize;
+use rangeset::RangeSet;
+use std::cell::{RefCell, RefMut};
+use std::ops::Range;
+use std::rc::Rc;
+use std::sync::Arc;
+use termwiz::escape::csi::{Sgr, CSI};
+use termwiz::escape::{Action, ControlCode};
+use termwiz::surface::Line;
+use url::Url;
+use wezterm_term::color::ColorPalette;
+use wezterm_term::{KeyCode, KeyModifiers, MouseEvent, StableRowIndex, Terminal};
+
+/// Returns the short mnemonic used to display a control character
+fn mnemonic(c: char) -> String {
+    const C0: [&str; 32] = [
+        "NUL", "SOH", "STX", "ETX", "EOT", "ENQ", "ACK", "BEL", "BS", "HT", "LF", "VT", "FF", "CR",
+        "SO", "SI", "DLE", "DC1", "DC2", "DC3", "DC4", "NAK", "SYN", "ETB", "CAN", "EM", "SUB",
+        "ESC", "FS", "GS", "RS", "US",
+    ];
+    match c as u32 {
+        n @ 0..=0x1f => C0[n as usize].to_string(),
+        0x7f => "DEL".to_string(),
+        n @ 0x80..=0x9f => format!("{:02X}", n),
+        _ => c.to_string(),
+    }
+}
+
+fn is_control(c: char) -> bool {
+    let n = c as u32;
+    n < 0x20 || (0x7f..=0x9f).contains(&n)
+}
+
+fn push_mnemonic(out: &mut Vec<Action>, label: &str) {
+    out.push(Action::CSI(CSI::Sgr(Sgr::Inverse(true))));
+    out.extend(label.chars().map(Action::Print));
+    out.push(Action::CSI(CSI::Sgr(Sgr::Inverse(false))));
+}
+
+/// Prints the encoded form of a sequence, showing any control
+/// characters that it contains as mnemonics
+fn push_encoded(out: &mut Vec<Action>, encoded: &str) {
+    for c in encoded.chars() {
+        if is_control(c) {
+            push_mnemonic(out, &mnemonic(c));
+        } else {
+            out.push(Action::Print(c));
+        }
+    }
+}
+
+/// Transforms `actions` so that, rather than being interpreted, their
+/// control codes and escape sequences are displayed.
+/// Line feeds are still honored after their mnemonic, so that the
+/// output remains readable.
+pub fn visualize_actions(actions: Vec<Action>) -> Vec<Action> {
+    let mut out = Vec::with_capacity(actions.len());
+    for action in actions {
+        match action {
+            Action::Print(c) if !is_control(c) => out.push(Action::Print(c)),
+            Action::Control(ControlCode::LineFeed) => {
+                push_mnemonic(&mut out, "LF");
+                out.push(Action::Control(ControlCode::CarriageReturn));
+                out.push(Action::Control(ControlCode::LineFeed));
+            }
+            Action::Control(code) => {
+                let label = match code as u8 {
+                    0x80..=0x9f => format!("{:?}", code),
+                    n => mnemonic(n as char),
+                };
+                push_mnemonic(&mut out, &label);
+            }
+            action => push_encoded(&mut out, &action.to_string()),
+        }
+    }
+    out
+}
+
+/// Renders the visible form of the output of a pane.  It reports the
+/// id of the pane, and delegates everything other than its content to
+/// the pane, so that the GUI can render it in place of the pane.
+pub struct EscapeDebugPane {
+    delegate: Rc<dyn Pane>,
+    terminal: RefCell<Terminal>,
+}
+
+impl EscapeDebugPane {
+    pub fn new(delegate: &Rc<dyn Pane>) -> Self {
+        let dims = delegate.get_dimensions();
+        let terminal = Terminal::new(
+            crate::pty_size_to_terminal_size(PtySize {
+                rows: dims.viewport_rows as u16,
+                cols: dims.cols as u16,
+                pixel_width: 0,
+                pixel_height: 0,
+            }),
+            Arc::new(config::TermConfig {}),
+            "WezTerm",
+            config::wezterm_version(),
+            Box::new(std::io::sink()),
+        );
+        Self {
+            delegate: Rc::clone(delegate),
+            terminal: RefCell::new(terminal),
+        }
+    }
+
+    /// Appends the visible form of `actions`, which are the output
+    /// that is also being applied to the pane
+    pub fn visualize(&self, actions: Vec<Action>) {
+        let mut terminal = self.sync_size();
+        terminal.perform_actions(visualize_actions(actions));
+    }
+
+    /// Keeps the size of our model in step with that of the pane
+    fn sync_size(&self) -> RefMut<Terminal> {
+        let dims = self.delegate.get_dimensions();
+        let mut terminal = self.terminal.borrow_mut();
+        let screen = terminal.screen();
+        if screen.physical_rows != dims.viewport_rows || screen.physical_cols != dims.cols {
+            terminal.resize(dims.viewport_rows, dims.cols, 0, 0);
+        }
+        terminal
+    }
+}
+
+impl Pane for EscapeDebugPane {
+    fn pane_id(&self) -> PaneId {
+        self.delegate.pane_id()
+    }
+
+    fn get_cursor_position(&self) -> StableCursorPosition {
+        terminal_get_cursor_position(&mut self.sync_size())
+    }
+
+    fn get_dirty_lines(&self, lines: Range<StableRowIndex>) -> RangeSet<StableRowIndex> {
+        terminal_get_dirty_lines(&mut self.sync_size(), lines)
+    }
+
+    fn get_lines(&self, lines: Range<StableRowIndex>) -> (StableRowIndex, Vec<Line>) {
+        terminal_get_lines(&mut self.sync_size(), lines)
+    }
+
+    fn get_dimensions(&self) -> RenderableDimensions {
+        terminal_get_dimensions(&mut self.sync_size())
+    }
+
+    fn get_title(&self) -> String {
+        self.delegate.get_title()
+    }
+
+    fn send_paste(&self, text: &str) -> anyhow::Result<()> {
+        self.delegate.send_paste(text)
+    }
+
+    fn reader(&self) -> anyhow::Result<Box<dyn std::io::Read + Send>> {
+        self.delegate.reader()
+    }
+
+    fn writer(&self) -> RefMut<dyn std::io::Write> {
+        self.delegate.writer()
+    }
+
+    fn resize(&self, size: PtySize) -> anyhow::Result<()> {
+        self.delegate.resize(size)
+    }
+
+    fn key_down(&self, key: KeyCode, mods: KeyModifiers) -> anyhow::Result<()> {
+        self.delegate.key_down(key, mods)
+    }
+
+    fn mouse_event(&self, event: MouseEvent) -> anyhow::Result<()> {
+        self.delegate.mouse_event(event)
+    }
+
+    fn is_dead(&self) -> bool {
+        self.delegate.is_dead()
+    }
+
+    fn palette(&self) -> ColorPalette {
+        self.delegate.palette()
+    }
+
+    fn domain_id(&self) -> DomainId {
+        self.delegate.domain_id()
+    }
+
+    fn is_mouse_grabbed(&self) -> bool {
+        self.delegate.is_mouse_grabbed()
+    }
+
+    fn is_alt_screen_active(&self) -> bool {
+        false
+    }
+
+    fn get_current_working_dir(&self) -> Option<Url> {
+        self.delegate.get_current_working_dir()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn render(actions: &[Action]) -> String {
+        let mut result = String::new();
+        for action in actions {
+            match action {
+                Action::Print(c) => result.push(*c),
+                Action::Control(ControlCode::LineFeed) => result.push('\n'),
+                Action::Control(_) => {}
+                Action::CSI(CSI::Sgr(Sgr::Inverse(true))) => result.push('<'),
+                Action::CSI(CSI::Sgr(Sgr::Inverse(false))) => result.push('>'),
+                other => panic!("unexpected action {:?}", other),
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn visualize() {
+        let mut actions = vec![];
+        let mut parser = termwiz::escape::parser::Parser::new();
+        parser.parse(b"hello\x1b[1mbold\x07\r\n", |action| actions.push(action));
+
+        assert_eq!(
+            render(&visualize_actions(actions)),
+            "hello<ESC>[1mbold<BEL><CR><LF>\n"
+        );
+    }
+
+    #[test]
+    fn debug_pane_leaves_pane_alone() {
+        let size = PtySize {
+            rows: 4,
+            cols: 40,
+            pixel_width: 0,
+            pixel_height: 0,
+        };
+        let pane: Rc<dyn Pane> =
+            Rc::new(crate::scratch::ScratchPane::new(0, size, "scratch").unwrap());
+        let debug = EscapeDebugPane::new(&pane);
+        assert_eq!(debug.pane_id(), pane.pane_id());
+
+        let mut actions = vec![];
+        let mut parser = termwiz::escape::parser::Parser::new();
+        parser.parse(b"a\x1b[1mb", |action| actions.push(action));
+        debug.visualize(actions.clone());
+        pane.perform_actions(actions);
+
+        let text = |pane: &dyn Pane| pane.get_lines(0..1).1[0].as_str().trim_end().to_string();
+        assert_eq!(text(&debug), "aESC[1mb");
+        assert_eq!(text(&*pane), "ab");
+    }
+}
@@ -0,0 +1,150 @@
+//! Serializes lines from a pane as text containing the ANSI escape
+//! sequences that reproduce their colors, attributes and hyperlinks.
+use termwiz::color::ColorSpec;
+use termwiz::escape::csi::Sgr;
+use termwiz::escape::{OperatingSystemCommand, CSI};
+use wezterm_term::{
+    Blink, CellAttributes, ColorAttribute, Intensity, Line, Underline, VerticalAlign,
+};
+
+/// A line that has been trimmed to the columns that are being exported
+pub struct ExportLine {
+    pub line: Line,
+    /// true if the line continues on the next line, in which case no
+    /// newline is emitted after it
+    pub wrapped: bool,
+}
+
+fn color_spec(color: ColorAttribute) -> ColorSpec {
+    match color {
+        ColorAttribute::TrueColorWithPaletteFallback(color, _)
+        | ColorAttribute::TrueColorWithDefaultFallback(color) => ColorSpec::TrueColor(color),
+        ColorAttribute::PaletteIndex(idx) => ColorSpec::PaletteIndex(idx),
+        ColorAttribute::Default => ColorSpec::Default,
+    }
+}
+
+/// Returns the escape sequence that selects `attrs`, starting from a
+/// reset so that nothing is inherited from the prior cells
+fn sgr_for_attrs(attrs: &CellAttributes) -> String {
+    let mut sgr = vec![Sgr::Reset];
+    if attrs.intensity() != Intensity::Normal {
+        sgr.push(Sgr::Intensity(attrs.intensity()));
+    }
+    if attrs.underline() != Underline::None {
+        sgr.push(Sgr::Underline(attrs.underline()));
+    }
+    if attrs.underline_color() != ColorAttribute::Default {
+        sgr.push(Sgr::UnderlineColor(color_spec(attrs.underline_color())));
+    }
+    if attrs.blink() != Blink::None {
+        sgr.push(Sgr::Blink(attrs.blink()));
+    }
+    if attrs.italic() {
+        sgr.push(Sgr::Italic(true));
+    }
+    if attrs.reverse() {
+        sgr.push(Sgr::Inverse(true));
+    }
+    if attrs.invisible() {
+        sgr.push(Sgr::Invisible(true));
+    }
+    if attrs.strikethrough() {
+        sgr.push(Sgr::StrikeThrough(true));
+    }
+    if attrs.overline() {
+        sgr.push(Sgr::Overline(true));
+    }
+    if attrs.vertical_align() != VerticalAlign::BaseLine {
+        sgr.push(Sgr::VerticalAlign(attrs.vertical_align()));
+    }
+    if attrs.foreground != ColorAttribute::Default {
+        sgr.push(Sgr::Foreground(color_spec(attrs.foreground)));
+    }
+    if attrs.background != ColorAttribute::Default {
+        sgr.push(Sgr::Background(color_spec(attrs.background)));
+    }
+    sgr.into_iter().map(|s| CSI::Sgr(s).to_string()).collect()
+}
+
+/// Returns the text of `lines` with the escape sequences that are
+/// needed to reproduce their attributes and hyperlinks
+pub fn to_ansi(lines: &[ExportLine]) -> String {
+    let plain = sgr_for_attrs(&CellAttributes::default());
+    let mut s = String::new();
+    let mut current_sgr = plain.clone();
+    let mut current_link = None;
+
+    for (idx, export) in lines.iter().enumerate() {
+        for cluster in export.line.cluster() {
+            let sgr = sgr_for_attrs(&cluster.attrs);
+            if sgr != current_sgr {
+                s.push_str(&sgr);
+                current_sgr = sgr;
+            }
+            let link = cluster.attrs.hyperlink().cloned();
+            if link != current_link {
+                s.push_str(
+                    &OperatingSystemCommand::SetHyperlink(link.as_ref().map(|l| (**l).clone()))
+                        .to_string(),
+                );
+                current_link = link;
+            }
+            s.push_str(&cluster.text);
+        }
+        if !export.wrapped && idx + 1 < lines.len() {
+            // Don't let the background color bleed into the rest of
+            // the line when this is displayed in a terminal
+            if current_sgr != plain {
+                s.push_str(&plain);
+                current_sgr = plain.clone();
+            }
+            s.push('\n');
+        }
+    }
+
+    if current_link.is_some() {
+        s.push_str(&OperatingSystemCommand::SetHyperlink(None).to_string());
+    }
+    if current_sgr != plain {
+        s.push_str(&plain);
+    }
+    s
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Arc;
+    use termwiz::hyperlink::Hyperlink;
+
+    #[test]
+    fn ansi() {
+        let mut bold = CellAttributes::default();
+        bold.set_intensity(Intensity::Bold)
+            .set_foreground(ColorAttribute::PaletteIndex(1));
+        let mut link = CellAttributes::default();
+        link.set_hyperlink(Some(Arc::new(Hyperlink::new("https://example.com/?a&b"))));
+
+        let mut cells = Line::from_text("a<b ", &CellAttributes::default())
+            .cells()
+            .to_vec();
+        cells.extend_from_slice(Line::from_text("red", &bold).cells());
+
+        let lines = vec![
+            ExportLine {
+                line: Line::from_cells(cells),
+                wrapped: false,
+            },
+            ExportLine {
+                line: Line::from_text("link", &link),
+                wrapped: false,
+            },
+        ];
+        assert_eq!(
+            to_ansi(&lines),
+            "a<b \u{1b}[0m\u{1b}[1m\u{1b}[31mred\u{1b}[0m\n\
+             \u{1b}]8;;https://example.com/?a&b\u{1b}\\link\u{1b}]8;;\u{1b}\\"
+        );
+    }
+}
@@ -1,9 +1,11 @@
+use crate::localpane::LocalPane;
 use crate::pane::{Pane, PaneId};
 use crate::tab::{Tab, TabId};
 use crate::window::{Window, WindowId};
 use anyhow::{anyhow, Error};
+use config::keyassignment::{CaptureArguments, PaneInputMode, SetWindowPositionArguments};
 use config::{configuration, ExitBehavior};
-use domain::{Domain, DomainId};
+use domain::{Domain, DomainId, LocalDomain};
 use log::error;
 use portable_pty::ExitStatus;
 use std::cell::{Ref, RefCell, RefMut};
@@ -22,9 +24,14 @@ use thiserror::*;
 pub mod activity;
 pub mod connui;
 pub mod domain;
+pub mod escapedebug;
+pub mod export;
 pub mod localpane;
 pub mod pane;
+pub mod persist;
+pub mod plugin;
 pub mod renderable;
+pub mod scratch;
 pub mod ssh;
 pub mod tab;
 pub mod termwiztermtab;
@@ -32,6 +39,7 @@ pub mod tmux;
 pub mod window;
 
 use crate::activity::Activity;
+use crate::escapedebug::EscapeDebugPane;
 
 #[derive(Clone, Debug)]
 pub enum MuxNotification {
@@ -41,8 +49,52 @@ pub enum MuxNotification {
         pane_id: PaneId,
         alert: wezterm_term::Alert,
     },
+    /// Requests that the GUI move and/or resize the window
+    SetWindowPosition {
+        window_id: WindowId,
+        position: SetWindowPositionArguments,
+    },
+    /// Requests that the GUI save an image of the pane, or of the
+    /// whole window that contains it
+    Capture {
+        pane_id: PaneId,
+        whole_window: bool,
+        args: CaptureArguments,
+    },
+    /// The window has been removed from the mux, and any GUI
+    /// window that represents it should be closed
+    WindowRemoved(WindowId),
+    /// A different client of the mux server than before has sent
+    /// input to the pane
+    PaneWriterChanged {
+        pane_id: PaneId,
+        client_id: usize,
+        client_name: String,
+    },
 }
 
+/// How often the mux looks for dead panes to remove
+const GARBAGE_COLLECTION_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The most actions that are buffered for a pane whose output is
+/// paused.  Once this many are buffered, the pty is no longer read
+/// until output is resumed, so that the program is blocked by the
+/// pty rather than the buffer growing without limit.
+const MAX_PAUSED_ACTIONS: usize = 100_000;
+
+/// How often a reader that is blocked by a full paused output buffer
+/// checks whether output has been resumed
+const PAUSED_OUTPUT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long a pane that is closed on exit waits, once its pty has
+/// reached EOF, for the process to finish exiting so that its exit
+/// status can be collected before the pane is removed.  Removing
+/// the pane kills any process that is still running.
+const EXIT_STATUS_GRACE_PERIOD: Duration = Duration::from_secs(1);
+
+/// How often the exit status is polled during the grace period
+const EXIT_STATUS_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
 static SUB_ID: AtomicUsize = AtomicUsize::new(0);
 
 pub struct Mux {
@@ -54,6 +106,10 @@ pub struct Mux {
     domains_by_name: RefCell<HashMap<String, Arc<dyn Domain>>>,
     subscribers: RefCell<HashMap<usize, Box<dyn Fn(MuxNotification) -> bool>>>,
     banner: RefCell<Option<String>>,
+    input_modes: RefCell<HashMap<PaneId, PaneInputMode>>,
+    escape_debug_panes: RefCell<HashMap<PaneId, Rc<EscapeDebugPane>>>,
+    /// Output that arrived for panes while their output was paused
+    paused_output: RefCell<HashMap<PaneId, Vec<Action>>>,
 }
 
 /// This function bounces parsed actions over to the main thread to feed to
@@ -64,9 +120,21 @@ fn send_actions_to_mux(pane_id: PaneId, dead: &Arc<AtomicBool>, actions: Vec<Act
     promise::spawn::block_on(promise::spawn::spawn_into_main_thread({
         let dead = Arc::clone(&dead);
         async move {
+            loop {
+                let full = Mux::get().unwrap().is_paused_output_full(pane_id);
+                if !full {
+                    break;
+                }
+                smol::Timer::after(PAUSED_OUTPUT_POLL_INTERVAL).await;
+            }
+
             let mux = Mux::get().unwrap();
             if let Some(pane) = mux.get_pane(pane_id) {
-                pane.perform_actions(actions);
+                if let Some(pending) = mux.paused_output.borrow_mut().get_mut(&pane_id) {
+                    pending.extend(actions);
+                    return;
+                }
+                mux.perform_pane_actions(&pane, actions);
                 mux.notify(MuxNotification::PaneOutput(pane_id));
             } else {
                 // Something else removed the pane from
@@ -136,11 +204,26 @@ fn parse_buffered_data(pane_id: PaneId, state: &Arc<BufState>) {
     }
 }
 
+/// Returns the number of times that the process in `pane` has been
+/// restarted, which identifies the pty that is current
+fn pane_generation(pane: &dyn Pane) -> usize {
+    pane.downcast_ref::<LocalPane>()
+        .map(LocalPane::generation)
+        .unwrap_or(0)
+}
+
 /// This function is run in a separate thread; its purpose is to perform
 /// blocking reads from the pty (non-blocking reads are not portable to
 /// all platforms and pty/tty types), parse the escape sequences and
 /// relay the actions to the mux thread to apply them to the pane.
-fn read_from_pane_pty(pane_id: PaneId, banner: Option<String>, mut reader: Box<dyn std::io::Read>) {
+/// `generation` is the value of `pane_generation` for the pty that
+/// `reader` belongs to.
+fn read_from_pane_pty(
+    pane_id: PaneId,
+    generation: usize,
+    banner: Option<String>,
+    mut reader: Box<dyn std::io::Read>,
+) {
     const BUFSIZE: usize = 4 * 1024;
     let mut buf = [0; BUFSIZE];
 
@@ -156,7 +239,48 @@ fn read_from_pane_pty(pane_id: PaneId, banner: Option<String>, mut reader: Box<d
 
     std::thread::spawn({
         let state = Arc::clone(&state);
-        move || parse_buffered_data(pane_id, &state)
+        move || {
+            parse_buffered_data(pane_id, &state);
+
+            // This happens here rather than in the reader thread so that
+            // the pane is only removed after all of its output has been
+            // applied; otherwise the last few actions may be dropped.
+            // Likewise, the exit status is only shown below all of it.
+            promise::spawn::spawn_into_main_thread(async move {
+                let mux = Mux::get().unwrap();
+                match mux.get_pane(pane_id) {
+                    // The pane was restarted and this was the output of
+                    // its previous process
+                    Some(pane) if pane_generation(&*pane) != generation => return,
+                    _ => {}
+                }
+                match configuration().exit_behavior {
+                    ExitBehavior::Close => {
+                        let deadline = std::time::Instant::now() + EXIT_STATUS_GRACE_PERIOD;
+                        while let Some(pane) = mux.get_pane(pane_id) {
+                            if pane_generation(&*pane) != generation {
+                                return;
+                            }
+                            if pane.is_dead() || std::time::Instant::now() >= deadline {
+                                break;
+                            }
+                            smol::Timer::after(EXIT_STATUS_POLL_INTERVAL).await;
+                        }
+                        mux.remove_pane(pane_id);
+                    }
+                    ExitBehavior::Hold | ExitBehavior::CloseOnCleanExit => {
+                        let pane = mux.get_pane(pane_id);
+                        if let Some(local) = pane
+                            .as_ref()
+                            .and_then(|pane| pane.downcast_ref::<LocalPane>())
+                        {
+                            local.output_completed();
+                        }
+                    }
+                }
+            })
+            .detach();
+        }
     });
 
     if let Some(banner) = banner {
@@ -179,22 +303,11 @@ fn read_from_pane_pty(pane_id: PaneId, banner: Option<String>, mut reader: Box<d
         }
     }
 
-    match configuration().exit_behavior {
-        ExitBehavior::Hold | ExitBehavior::CloseOnCleanExit => {
-            // We don't know if we can unilaterally close
-            // this pane right now, so don't!
-            state.write(b"\n[Process completed]");
-        }
-        ExitBehavior::Close => {
-            promise::spawn::spawn_into_main_thread(async move {
-                let mux = Mux::get().unwrap();
-                mux.remove_pane(pane_id);
-            })
-            .detach();
-        }
-    }
-
+    // Hold the lock so that the parser thread can't miss the wakeup
+    // between checking `dead` and waiting on the condition
+    let _queue = state.queue.lock().unwrap();
     dead.store(true, Ordering::Relaxed);
+    state.cond.notify_one();
 }
 
 thread_local! {
@@ -261,7 +374,7 @@ impl Mux {
             );
         }
 
-        Self {
+        let mux = Self {
             tabs: RefCell::new(HashMap::new()),
             panes: RefCell::new(HashMap::new()),
             windows: RefCell::new(HashMap::new()),
@@ -270,7 +383,25 @@ impl Mux {
             domains: RefCell::new(domains),
             subscribers: RefCell::new(HashMap::new()),
             banner: RefCell::new(None),
+            input_modes: RefCell::new(HashMap::new()),
+            escape_debug_panes: RefCell::new(HashMap::new()),
+            paused_output: RefCell::new(HashMap::new()),
+        };
+
+        for domain in plugin::instantiate_registered_domains() {
+            if let Err(err) = plugin::check_unique_name(&mux, &domain) {
+                log::error!("{:#}", err);
+                continue;
+            }
+            mux.domains
+                .borrow_mut()
+                .insert(domain.domain_id(), Arc::clone(&domain));
+            mux.domains_by_name
+                .borrow_mut()
+                .insert(domain.domain_name().to_string(), domain);
         }
+
+        mux
     }
 
     pub fn subscribe<F>(&self, subscriber: F)
@@ -320,6 +451,15 @@ impl Mux {
             .insert(domain.domain_name().to_string(), Arc::clone(domain));
     }
 
+    /// Adds a domain that was constructed outside of the mux crate,
+    /// such as one provided by a plugin.  Unlike `add_domain`, this
+    /// refuses to replace an existing domain with the same name.
+    pub fn register_domain(&self, domain: &Arc<dyn Domain>) -> anyhow::Result<()> {
+        plugin::check_unique_name(self, domain)?;
+        self.add_domain(domain);
+        Ok(())
+    }
+
     pub fn set_mux(mux: &Rc<Mux>) {
         MUX.with(|m| {
             *m.borrow_mut() = Some(Rc::clone(mux));
@@ -354,8 +494,35 @@ impl Mux {
             .insert(pane.pane_id(), Rc::clone(pane));
         let reader = pane.reader()?;
         let pane_id = pane.pane_id();
+        let generation = pane_generation(&**pane);
         let banner = self.banner.borrow().clone();
-        thread::spawn(move || read_from_pane_pty(pane_id, banner, reader));
+        thread::spawn(move || read_from_pane_pty(pane_id, generation, banner, reader));
+        Ok(())
+    }
+
+    /// Re-spawns the command that was originally run in the pane, in
+    /// place, killing its current process if it is still running.
+    /// The pane keeps its id, position and scrollback.
+    pub fn restart_pane(&self, pane_id: PaneId) -> anyhow::Result<()> {
+        let pane = self
+            .get_pane(pane_id)
+            .ok_or_else(|| anyhow!("pane {} not found", pane_id))?;
+        let local = pane
+            .downcast_ref::<LocalPane>()
+            .ok_or_else(|| anyhow!("pane {} cannot be restarted", pane_id))?;
+        let domain = self
+            .get_domain(pane.domain_id())
+            .ok_or_else(|| anyhow!("domain for pane {} not found", pane_id))?;
+        let domain = domain
+            .downcast_ref::<LocalDomain>()
+            .ok_or_else(|| anyhow!("pane {} cannot be restarted", pane_id))?;
+
+        domain.restart_pane(local)?;
+
+        let reader = pane.reader()?;
+        let generation = local.generation();
+        thread::spawn(move || read_from_pane_pty(pane_id, generation, None, reader));
+        self.notify(MuxNotification::PaneOutput(pane_id));
         Ok(())
     }
 
@@ -373,6 +540,9 @@ impl Mux {
 
     fn remove_pane_internal(&self, pane_id: PaneId) {
         log::debug!("removing pane {}", pane_id);
+        self.input_modes.borrow_mut().remove(&pane_id);
+        self.escape_debug_panes.borrow_mut().remove(&pane_id);
+        self.paused_output.borrow_mut().remove(&pane_id);
         if let Some(pane) = self.panes.borrow_mut().remove(&pane_id) {
             log::debug!("killing pane {}", pane_id);
             pane.kill();
@@ -388,6 +558,9 @@ impl Mux {
         for pos in tab.iter_panes() {
             pane_ids.push(pos.pane.pane_id());
         }
+        if let Some(floating) = tab.get_floating_pane() {
+            pane_ids.push(floating.pane_id());
+        }
         for pane_id in pane_ids {
             self.remove_pane_internal(pane_id);
         }
@@ -402,6 +575,14 @@ impl Mux {
             for tab in window.iter() {
                 self.remove_tab_internal(tab.tab_id());
             }
+            // We may be called from within a subscriber, so defer
+            // the notification to avoid re-entering notify
+            promise::spawn::spawn_into_main_thread(async move {
+                if let Some(mux) = Mux::get() {
+                    mux.notify(MuxNotification::WindowRemoved(window_id));
+                }
+            })
+            .detach();
         }
     }
 
@@ -455,6 +636,72 @@ impl Mux {
         }
     }
 
+    /// Starts a task that periodically calls `collect_garbage`
+    /// for as long as the mux is running.
+    pub fn start_garbage_collection() {
+        promise::spawn::spawn(async {
+            loop {
+                smol::Timer::after(GARBAGE_COLLECTION_INTERVAL).await;
+                match Mux::get() {
+                    Some(mux) => mux.collect_garbage(),
+                    None => break,
+                }
+            }
+        })
+        .detach();
+    }
+
+    /// Removes dead panes from the mux and from their tabs, and then
+    /// removes any tabs and windows that were left empty as a result.
+    /// Panes are usually removed as soon as their process exits, but
+    /// this catches those that slip through, such as panes whose
+    /// remote connection was lost, or overlays that were abandoned.
+    pub fn collect_garbage(&self) {
+        if Activity::count() != 0 {
+            // Something is being spawned, and may not yet be fully
+            // attached to a tab and window; try again later
+            return;
+        }
+
+        let panes: Vec<Rc<dyn Pane>> = self.panes.borrow().values().cloned().collect();
+        let dead_panes: Vec<Rc<dyn Pane>> =
+            panes.into_iter().filter(|pane| pane.is_dead()).collect();
+
+        // Take the dead panes out of the split trees first, so that
+        // the tabs and windows are pruned based on what remains
+        let tabs: Vec<Rc<Tab>> = self.tabs.borrow().values().cloned().collect();
+        for tab in tabs {
+            tab.prune_dead_panes();
+        }
+
+        let mut dead_by_domain: HashMap<DomainId, usize> = HashMap::new();
+        for pane in &dead_panes {
+            *dead_by_domain.entry(pane.domain_id()).or_insert(0) += 1;
+            self.remove_pane_internal(pane.pane_id());
+        }
+
+        self.prune_dead_windows();
+
+        for (domain_id, count) in dead_by_domain {
+            let name = match self.get_domain(domain_id) {
+                Some(domain) => domain.domain_name().to_string(),
+                None => format!("#{}", domain_id),
+            };
+            let remaining = self
+                .panes
+                .borrow()
+                .values()
+                .filter(|pane| pane.domain_id() == domain_id)
+                .count();
+            log::info!(
+                "removed {} dead pane(s) belonging to domain {}; {} remain",
+                count,
+                name,
+                remaining
+            );
+        }
+    }
+
     pub fn kill_window(&self, window_id: WindowId) {
         self.remove_window_internal(window_id);
     }
@@ -575,6 +822,146 @@ impl Mux {
     pub fn set_banner(&self, banner: Option<String>) {
         *self.banner.borrow_mut() = banner;
     }
+
+    pub fn get_pane_input_mode(&self, pane_id: PaneId) -> PaneInputMode {
+        self.input_modes
+            .borrow()
+            .get(&pane_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn set_pane_input_mode(&self, pane_id: PaneId, mode: PaneInputMode) {
+        if mode == PaneInputMode::Normal {
+            self.input_modes.borrow_mut().remove(&pane_id);
+        } else {
+            self.input_modes.borrow_mut().insert(pane_id, mode);
+        }
+        if let Some(pane) = self.get_pane(pane_id) {
+            pane.set_input_mode(mode);
+        }
+        // Prompt the frontends to repaint so that they can
+        // reflect the new mode
+        self.notify(MuxNotification::PaneOutput(pane_id));
+    }
+
+    /// Applies output to the pane, and to its escape debugging view
+    /// if that is enabled
+    fn perform_pane_actions(&self, pane: &Rc<dyn Pane>, actions: Vec<Action>) {
+        let debug = self
+            .escape_debug_panes
+            .borrow()
+            .get(&pane.pane_id())
+            .cloned();
+        if let Some(debug) = debug {
+            debug.visualize(actions.clone());
+        }
+        pane.perform_actions(actions);
+    }
+
+    pub fn is_pane_escape_debug_enabled(&self, pane_id: PaneId) -> bool {
+        self.escape_debug_panes.borrow().contains_key(&pane_id)
+    }
+
+    /// Returns the view that displays the output of the pane as raw
+    /// control codes and escape sequences, if that is enabled.
+    /// It is rendered in place of the pane.
+    pub fn get_pane_escape_debug(&self, pane_id: PaneId) -> Option<Rc<dyn Pane>> {
+        self.escape_debug_panes
+            .borrow()
+            .get(&pane_id)
+            .map(|debug| Rc::clone(debug) as Rc<dyn Pane>)
+    }
+
+    /// When enabled, output from the pane is displayed as raw control
+    /// codes and escape sequences rather than being interpreted.
+    /// The pane itself continues to interpret its output.
+    /// See the escapedebug module for more details.
+    pub fn set_pane_escape_debug(&self, pane_id: PaneId, enable: bool) {
+        if enable {
+            if let Some(pane) = self.get_pane(pane_id) {
+                self.escape_debug_panes
+                    .borrow_mut()
+                    .insert(pane_id, Rc::new(EscapeDebugPane::new(&pane)));
+            }
+        } else {
+            self.escape_debug_panes.borrow_mut().remove(&pane_id);
+        }
+        self.notify(MuxNotification::PaneOutput(pane_id));
+    }
+
+    pub fn is_pane_output_paused(&self, pane_id: PaneId) -> bool {
+        self.paused_output.borrow().contains_key(&pane_id)
+    }
+
+    fn is_paused_output_full(&self, pane_id: PaneId) -> bool {
+        self.paused_output
+            .borrow()
+            .get(&pane_id)
+            .map_or(false, |pending| pending.len() >= MAX_PAUSED_ACTIONS)
+    }
+
+    /// While output is paused, output from the pane is buffered
+    /// rather than being applied to its terminal model, so that
+    /// the screen remains stable.  When output is resumed, the
+    /// buffered output is applied in one go.
+    pub fn set_pane_output_paused(&self, pane_id: PaneId, paused: bool) {
+        if paused {
+            self.paused_output
+                .borrow_mut()
+                .entry(pane_id)
+                .or_insert_with(Vec::new);
+        } else {
+            let pending = self.paused_output.borrow_mut().remove(&pane_id);
+            if let (Some(pending), Some(pane)) = (pending, self.get_pane(pane_id)) {
+                if !pending.is_empty() {
+                    self.perform_pane_actions(&pane, pending);
+                }
+            }
+        }
+        self.notify(MuxNotification::PaneOutput(pane_id));
+    }
+
+    /// Returns the set of panes that should receive keyboard
+    /// or paste input that is directed at pane_id, taking into
+    /// account the input mode of that pane and its siblings.
+    pub fn resolve_input_targets(&self, pane_id: PaneId) -> Vec<Rc<dyn Pane>> {
+        let mode = self.get_pane_input_mode(pane_id);
+        if mode == PaneInputMode::ReadOnly {
+            return vec![];
+        }
+        let pane = match self.get_pane(pane_id) {
+            Some(pane) => pane,
+            None => return vec![],
+        };
+        if mode == PaneInputMode::Normal || pane.input_mode_is_remote() {
+            return vec![pane];
+        }
+
+        let tab = self
+            .tabs
+            .borrow()
+            .values()
+            .find(|tab| tab.contains_pane(pane_id))
+            .map(Rc::clone);
+        let panes = match tab {
+            Some(tab) => tab.iter_panes_ignoring_zoom(),
+            None => return vec![pane],
+        };
+
+        let modes = self.input_modes.borrow();
+        panes
+            .into_iter()
+            .filter(|p| {
+                let target_mode = modes.get(&p.pane_id()).cloned().unwrap_or_default();
+                if mode == PaneInputMode::BroadcastToSelected {
+                    target_mode == PaneInputMode::BroadcastToSelected
+                } else {
+                    target_mode != PaneInputMode::ReadOnly
+                }
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Error)]
@@ -596,3 +983,146 @@ pub(crate) fn pty_size_to_terminal_size(size: portable_pty::PtySize) -> wezterm_
         pixel_height: size.pixel_height as usize,
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::scratch::ScratchPane;
+    use crate::tab::SplitDirection;
+    use portable_pty::PtySize;
+
+    fn scratch_pane(size: PtySize) -> Rc<dyn Pane> {
+        Rc::new(ScratchPane::new(0, size, "scratch").unwrap())
+    }
+
+    fn test_size() -> PtySize {
+        PtySize {
+            rows: 24,
+            cols: 80,
+            pixel_width: 800,
+            pixel_height: 600,
+        }
+    }
+
+    /// Builds a tab of `count` side by side panes and adds it to `mux`
+    fn split_tab(mux: &Mux, count: usize) -> (Rc<Tab>, Vec<Rc<dyn Pane>>) {
+        let size = test_size();
+        let tab = Rc::new(Tab::new(&size));
+        let first = scratch_pane(size);
+        tab.assign_pane(&first);
+        let mut panes = vec![first];
+        for _ in 1..count {
+            let split_size = tab
+                .compute_split_size(0, SplitDirection::Horizontal.into())
+                .unwrap();
+            let pane = scratch_pane(split_size.second);
+            tab.split_and_insert(0, SplitDirection::Horizontal.into(), Rc::clone(&pane))
+                .unwrap();
+            panes.push(pane);
+        }
+
+        // Register the panes directly, rather than via add_pane,
+        // so that no reader threads are started
+        for pane in &panes {
+            mux.panes
+                .borrow_mut()
+                .insert(pane.pane_id(), Rc::clone(pane));
+        }
+        mux.add_tab_no_panes(&tab);
+        (tab, panes)
+    }
+
+    fn target_ids(mux: &Mux, pane: &Rc<dyn Pane>) -> Vec<PaneId> {
+        let mut ids: Vec<PaneId> = mux
+            .resolve_input_targets(pane.pane_id())
+            .iter()
+            .map(|p| p.pane_id())
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn collect_garbage_prunes_split() {
+        // Pruning a tab defers a redundant removal of its dead panes
+        // to the main thread; there is no main thread here, so drop it
+        promise::spawn::set_schedulers(Box::new(drop), Box::new(drop));
+        let mux = Mux::new(None);
+
+        let (tab, panes) = split_tab(&mux, 2);
+        let (first, second) = (&panes[0], &panes[1]);
+        let mut window = Window::new();
+        window.push(&tab);
+        let window_id = window.window_id();
+        mux.windows.borrow_mut().insert(window_id, window);
+
+        second.kill();
+        mux.collect_garbage();
+
+        assert!(mux.get_pane(second.pane_id()).is_none());
+        assert!(mux.get_pane(first.pane_id()).is_some());
+        let panes = tab.iter_panes();
+        assert_eq!(panes.len(), 1);
+        assert_eq!(panes[0].pane.pane_id(), first.pane_id());
+        assert!(mux.get_tab(tab.tab_id()).is_some());
+        assert_eq!(mux.get_window(window_id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn input_targets_read_only() {
+        let mux = Mux::new(None);
+        let (_tab, panes) = split_tab(&mux, 2);
+
+        assert_eq!(target_ids(&mux, &panes[0]), vec![panes[0].pane_id()]);
+
+        mux.set_pane_input_mode(panes[0].pane_id(), PaneInputMode::ReadOnly);
+        assert!(target_ids(&mux, &panes[0]).is_empty());
+        assert_eq!(target_ids(&mux, &panes[1]), vec![panes[1].pane_id()]);
+
+        mux.set_pane_input_mode(panes[0].pane_id(), PaneInputMode::Normal);
+        assert_eq!(target_ids(&mux, &panes[0]), vec![panes[0].pane_id()]);
+    }
+
+    #[test]
+    fn input_targets_broadcast() {
+        let mux = Mux::new(None);
+        let (_tab, panes) = split_tab(&mux, 3);
+        let mut ids: Vec<PaneId> = panes.iter().map(|p| p.pane_id()).collect();
+        ids.sort();
+
+        // A broadcasting pane sends to the whole tab,
+        // except for the read only panes
+        mux.set_pane_input_mode(panes[0].pane_id(), PaneInputMode::Broadcast);
+        assert_eq!(target_ids(&mux, &panes[0]), ids);
+        assert_eq!(target_ids(&mux, &panes[1]), vec![panes[1].pane_id()]);
+
+        mux.set_pane_input_mode(panes[2].pane_id(), PaneInputMode::ReadOnly);
+        let mut expected = vec![panes[0].pane_id(), panes[1].pane_id()];
+        expected.sort();
+        assert_eq!(target_ids(&mux, &panes[0]), expected);
+        assert!(target_ids(&mux, &panes[2]).is_empty());
+    }
+
+    #[test]
+    fn input_targets_selected() {
+        let mux = Mux::new(None);
+        let (_tab, panes) = split_tab(&mux, 3);
+
+        // A lone selected pane only sends to itself
+        mux.set_pane_input_mode(panes[0].pane_id(), PaneInputMode::BroadcastToSelected);
+        assert_eq!(target_ids(&mux, &panes[0]), vec![panes[0].pane_id()]);
+
+        // Selected panes send to each other, but not to the rest
+        // of the tab, even if those are broadcasting
+        mux.set_pane_input_mode(panes[2].pane_id(), PaneInputMode::BroadcastToSelected);
+        mux.set_pane_input_mode(panes[1].pane_id(), PaneInputMode::Broadcast);
+        let mut selected = vec![panes[0].pane_id(), panes[2].pane_id()];
+        selected.sort();
+        assert_eq!(target_ids(&mux, &panes[0]), selected);
+        assert_eq!(target_ids(&mux, &panes[2]), selected);
+
+        let mut ids: Vec<PaneId> = panes.iter().map(|p| p.pane_id()).collect();
+        ids.sort();
+        assert_eq!(target_ids(&mux, &panes[1]), ids);
+    }
+}
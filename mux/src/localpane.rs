@@ -5,14 +5,15 @@ use crate::tmux::{TmuxDomain, TmuxDomainState};
 use crate::{Domain, Mux, MuxNotification};
 use anyhow::Error;
 use async_trait::async_trait;
-use config::keyassignment::ScrollbackEraseMode;
+use config::keyassignment::{InputMap, KeyAssignment, ScrollbackEraseMode};
 use config::{configuration, ExitBehavior};
-use portable_pty::{Child, MasterPty, PtySize};
+use portable_pty::{Child, CommandBuilder, ExitStatus, MasterPty, PtySize};
 use rangeset::RangeSet;
-use std::cell::{RefCell, RefMut};
+use std::cell::{Cell, RefCell, RefMut};
 use std::collections::HashSet;
 use std::ops::Range;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use termwiz::escape::DeviceControlMode;
 use termwiz::surface::Line;
 use url::Url;
@@ -35,6 +36,18 @@ enum ProcessState {
     Dead,
 }
 
+/// Tracks what is needed to write the exit status at the bottom of
+/// the pane once its process has terminated
+#[derive(Debug, Default)]
+struct ExitFooter {
+    /// Set once all of the output from the pty has been applied, so
+    /// that the footer isn't interleaved with the last of it
+    output_complete: bool,
+    /// The exit status and how long the process ran for
+    exited: Option<(ExitStatus, Duration)>,
+    written: bool,
+}
+
 pub struct LocalPane {
     pane_id: PaneId,
     terminal: RefCell<Terminal>,
@@ -42,6 +55,17 @@ pub struct LocalPane {
     pty: RefCell<Box<dyn MasterPty>>,
     domain_id: DomainId,
     tmux_domain: RefCell<Option<Arc<TmuxDomainState>>>,
+    spawned: Cell<Instant>,
+    exit_footer: RefCell<ExitFooter>,
+    /// The command that the process was spawned from, if known,
+    /// which is used to restart it
+    command: RefCell<Option<CommandBuilder>>,
+    /// Incremented each time the process is restarted
+    generation: Cell<usize>,
+    /// A size that the pty is yet to be resized to; see `resize_pty`
+    pending_pty_size: Cell<Option<PtySize>>,
+    /// How the process exited, once it has
+    exit_status: RefCell<Option<ExitStatus>>,
 }
 
 #[async_trait(?Send)]
@@ -111,6 +135,7 @@ impl Pane for LocalPane {
         match &mut *proc {
             ProcessState::Running { child, killed } => {
                 if let Ok(Some(status)) = child.try_wait() {
+                    self.exit_status.borrow_mut().replace(status.clone());
                     match (configuration().exit_behavior, status.success(), killed) {
                         (ExitBehavior::Close, _, _) => *proc = ProcessState::Dead,
                         (ExitBehavior::CloseOnCleanExit, false, false) => {
@@ -123,6 +148,10 @@ impl Pane for LocalPane {
                         (ExitBehavior::Hold, _, true) => *proc = ProcessState::Dead,
                     }
                     log::debug!("child terminated, new state is {:?}", proc);
+                    if let ProcessState::DeadPendingClose { .. } = &*proc {
+                        self.exit_footer.borrow_mut().exited =
+                            Some((status, self.spawned.get().elapsed()));
+                    }
                 }
             }
             ProcessState::DeadPendingClose { killed } => {
@@ -134,11 +163,17 @@ impl Pane for LocalPane {
             ProcessState::Dead => {}
         }
 
-        match &*proc {
+        let dead = match &*proc {
             ProcessState::Running { .. } => false,
             ProcessState::DeadPendingClose { .. } => false,
             ProcessState::Dead => true,
+        };
+        drop(proc);
+
+        if !dead {
+            self.write_exit_footer();
         }
+        dead
     }
 
     fn set_clipboard(&self, clipboard: &Arc<dyn Clipboard>) {
@@ -150,12 +185,13 @@ impl Pane for LocalPane {
     }
 
     fn mouse_event(&self, event: MouseEvent) -> Result<(), Error> {
+        self.flush_pending_resize()?;
         self.terminal.borrow_mut().mouse_event(event)
     }
 
     fn key_down(&self, key: KeyCode, mods: KeyModifiers) -> Result<(), Error> {
+        self.flush_pending_resize()?;
         if self.tmux_domain.borrow().is_some() {
-            log::error!("key: {:?}", key);
             if key == KeyCode::Char('q') {
                 self.terminal.borrow_mut().send_paste("detach\n")?;
             }
@@ -166,7 +202,7 @@ impl Pane for LocalPane {
     }
 
     fn resize(&self, size: PtySize) -> Result<(), Error> {
-        self.pty.borrow_mut().resize(size)?;
+        self.resize_pty(size)?;
         self.terminal.borrow_mut().resize(
             size.rows as usize,
             size.cols as usize,
@@ -177,6 +213,9 @@ impl Pane for LocalPane {
     }
 
     fn writer(&self) -> RefMut<dyn std::io::Write> {
+        if let Err(err) = self.flush_pending_resize() {
+            log::error!("resizing pane {}: {:#}", self.pane_id, err);
+        }
         self.pty.borrow_mut()
     }
 
@@ -185,6 +224,7 @@ impl Pane for LocalPane {
     }
 
     fn send_paste(&self, text: &str) -> Result<(), Error> {
+        self.flush_pending_resize()?;
         if self.tmux_domain.borrow().is_some() {
             Ok(())
         } else {
@@ -219,6 +259,12 @@ impl Pane for LocalPane {
         self.terminal.borrow_mut().focus_changed(focused);
     }
 
+    fn notifies_on_change(&self) -> bool {
+        // Changes arrive via the pty reader, or are applied via
+        // Mux::send_actions_to_mux, both of which notify the mux
+        true
+    }
+
     fn is_mouse_grabbed(&self) -> bool {
         if self.tmux_domain.borrow().is_some() {
             false
@@ -243,6 +289,16 @@ impl Pane for LocalPane {
             .or_else(|| self.divine_current_working_dir())
     }
 
+    fn get_foreground_process_name(&self) -> Option<String> {
+        #[cfg(unix)]
+        if let Some(pid) = self.pty.borrow().process_group_leader() {
+            return foreground_process_name(pid);
+        }
+
+        #[allow(unreachable_code)]
+        None
+    }
+
     fn can_close_without_prompting(&self) -> bool {
         let proc_list = self.divine_process_list();
         if !proc_list.is_empty() {
@@ -494,7 +550,183 @@ impl LocalPane {
             pty: RefCell::new(pty),
             domain_id,
             tmux_domain: RefCell::new(None),
+            spawned: Cell::new(Instant::now()),
+            exit_footer: RefCell::new(ExitFooter::default()),
+            command: RefCell::new(None),
+            generation: Cell::new(0),
+            pending_pty_size: Cell::new(None),
+            exit_status: RefCell::new(None),
+        }
+    }
+
+    /// Resizes the pty to `size`.
+    /// ConPTY redraws the whole screen each time that it is resized,
+    /// so on Windows a rapid series of resizes, such as those from
+    /// dragging the edge of the window, is coalesced: the pty is
+    /// resized to the most recent size at most once per
+    /// `conpty_resize_coalesce_ms`.
+    fn resize_pty(&self, size: PtySize) -> Result<(), Error> {
+        let config = configuration();
+        let interval = Duration::from_millis(config.conpty_resize_coalesce_ms);
+        if !cfg!(windows) || interval == Duration::from_millis(0) {
+            return self.apply_pty_size(size);
+        }
+
+        if self.pending_pty_size.replace(Some(size)).is_none() {
+            let pane_id = self.pane_id;
+            promise::spawn::spawn(async move {
+                smol::Timer::after(interval).await;
+                let mux = Mux::get().unwrap();
+                if let Some(pane) = mux.get_pane(pane_id) {
+                    if let Some(local) = pane.downcast_ref::<LocalPane>() {
+                        if let Err(err) = local.flush_pending_resize() {
+                            log::error!("resizing pane {}: {:#}", pane_id, err);
+                        }
+                    }
+                }
+            })
+            .detach();
+        }
+        Ok(())
+    }
+
+    /// Applies any resize that `resize_pty` is holding back.  This is
+    /// done before input is sent to the pty, so that the program sees
+    /// the size that the input was made at.
+    fn flush_pending_resize(&self) -> Result<(), Error> {
+        match self.pending_pty_size.take() {
+            Some(size) => self.apply_pty_size(size),
+            None => Ok(()),
+        }
+    }
+
+    fn apply_pty_size(&self, size: PtySize) -> Result<(), Error> {
+        let mut pty = self.pty.borrow_mut();
+        if cfg!(windows) && configuration().conpty_resize_pad_row {
+            // Some full screen programs only repaint properly when
+            // the size changes in both dimensions; passing through
+            // an extra row forces them to redraw
+            pty.resize(PtySize {
+                rows: size.rows.saturating_add(1),
+                ..size
+            })?;
+        }
+        pty.resize(size)
+    }
+
+    pub fn set_command(&self, command: CommandBuilder) {
+        self.command.borrow_mut().replace(command);
+    }
+
+    pub fn command(&self) -> Option<CommandBuilder> {
+        self.command.borrow().clone()
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation.get()
+    }
+
+    pub fn pty_size(&self) -> anyhow::Result<PtySize> {
+        self.pty.borrow().get_size()
+    }
+
+    /// Returns how the process exited, or None if it is still running
+    pub fn exit_status(&self) -> Option<ExitStatus> {
+        self.exit_status.borrow().clone()
+    }
+
+    /// Replaces the process and pty of this pane with a newly spawned
+    /// one, killing the current process if it is still running.
+    /// A divider separates the output of the new process from the
+    /// scrollback of the old one.
+    pub(crate) fn replace_process(
+        &self,
+        child: Box<dyn Child>,
+        pty: Box<dyn MasterPty>,
+        writer: Box<dyn std::io::Write + Send>,
+    ) {
+        if let ProcessState::Running { child, .. } = &mut *self.process.borrow_mut() {
+            bounded_kill_wait(child);
+        }
+        *self.process.borrow_mut() = ProcessState::Running {
+            child,
+            killed: false,
+        };
+
+        let cols = pty.get_size().map(|size| size.cols as usize).unwrap_or(80);
+        *self.pty.borrow_mut() = pty;
+        self.generation.set(self.generation.get() + 1);
+        self.spawned.set(Instant::now());
+        *self.exit_footer.borrow_mut() = ExitFooter::default();
+        self.exit_status.borrow_mut().take();
+
+        let mut terminal = self.terminal.borrow_mut();
+        terminal.set_writer(writer);
+        terminal.advance_bytes(divider(cols, " Restarted "));
+    }
+
+    /// Fills the pane with `contents`, which were saved from a process
+    /// that ran in its place before the mux server was restarted, and
+    /// separates them from the output of its own process with a divider.
+    /// This must be called before the output of the pane is read.
+    pub(crate) fn restore_contents(&self, contents: &str) {
+        let cols = self.pty_size().map(|size| size.cols as usize).unwrap_or(80);
+        let mut terminal = self.terminal.borrow_mut();
+        terminal.advance_bytes(contents);
+        terminal.advance_bytes(divider(cols, " Process restarted "));
+    }
+
+    /// Called once all of the output from the pty has been applied to
+    /// the terminal
+    pub(crate) fn output_completed(&self) {
+        self.exit_footer.borrow_mut().output_complete = true;
+        // Pick up the exit status now, rather than waiting for the
+        // next time the mux looks for dead panes
+        self.is_dead();
+    }
+
+    /// Writes the exit status of the process to the bottom of the pane
+    /// once the process has terminated and its output has been applied
+    fn write_exit_footer(&self) {
+        let mut footer = self.exit_footer.borrow_mut();
+        if footer.written || !footer.output_complete {
+            return;
         }
+        let (status, runtime) = match footer.exited.as_ref() {
+            Some(exited) => exited,
+            None => return,
+        };
+        footer.written = true;
+
+        let input_map = InputMap::new();
+        let close_key = input_map.describe_key_for(&[
+            KeyAssignment::CloseCurrentPane { confirm: false },
+            KeyAssignment::CloseCurrentPane { confirm: true },
+            KeyAssignment::CloseCurrentTab { confirm: false },
+            KeyAssignment::CloseCurrentTab { confirm: true },
+        ]);
+        let restart_key = if self.command.borrow().is_some() {
+            input_map.describe_key_for(&[
+                KeyAssignment::RestartPane { confirm: false },
+                KeyAssignment::RestartPane { confirm: true },
+            ])
+        } else {
+            None
+        };
+        self.terminal.borrow_mut().advance_bytes(exit_footer_text(
+            status,
+            *runtime,
+            close_key.as_deref(),
+            restart_key.as_deref(),
+        ));
+
+        let pane_id = self.pane_id;
+        promise::spawn::spawn_into_main_thread(async move {
+            if let Some(mux) = Mux::get() {
+                mux.notify(MuxNotification::PaneOutput(pane_id));
+            }
+        })
+        .detach();
     }
 
     #[cfg(target_os = "macos")]
@@ -628,6 +860,92 @@ impl LocalPane {
     }
 }
 
+#[cfg(target_os = "linux")]
+fn foreground_process_name(pid: libc::pid_t) -> Option<String> {
+    let path = std::fs::read_link(format!("/proc/{}/exe", pid)).ok()?;
+    Some(path.file_name()?.to_string_lossy().into_owned())
+}
+
+#[cfg(target_os = "macos")]
+fn foreground_process_name(pid: libc::pid_t) -> Option<String> {
+    let mut buffer = vec![0u8; libc::PROC_PIDPATHINFO_MAXSIZE as usize];
+    let len = unsafe {
+        libc::proc_pidpath(
+            pid,
+            buffer.as_mut_ptr() as *mut libc::c_void,
+            buffer.len() as u32,
+        )
+    };
+    if len <= 0 {
+        return None;
+    }
+    buffer.truncate(len as usize);
+    let path = std::path::PathBuf::from(String::from_utf8_lossy(&buffer).into_owned());
+    Some(path.file_name()?.to_string_lossy().into_owned())
+}
+
+#[cfg(all(unix, not(any(target_os = "linux", target_os = "macos"))))]
+fn foreground_process_name(_pid: libc::pid_t) -> Option<String> {
+    None
+}
+
+/// Formats `duration` for display, eg: `4.2s`, `3m07s` or `2h15m`
+fn format_runtime(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs < 60 {
+        format!("{:.1}s", duration.as_secs_f32())
+    } else if secs < 3600 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}h{:02}m", secs / 3600, (secs % 3600) / 60)
+    }
+}
+
+/// Returns the escape sequences and text that show the exit status of
+/// a process on a line of its own
+fn exit_footer_text(
+    status: &ExitStatus,
+    runtime: Duration,
+    close_key: Option<&str>,
+    restart_key: Option<&str>,
+) -> String {
+    // Green for success, red for failure
+    let color = if status.success() { 32 } else { 31 };
+    let mut text = format!(
+        "\x1b[0m\r\n\x1b[1;{}m[{}]\x1b[0m after {}.",
+        color,
+        status,
+        format_runtime(runtime)
+    );
+    match (close_key, restart_key) {
+        (Some(close), Some(restart)) => text.push_str(&format!(
+            " Press {} to close or {} to restart.",
+            close, restart
+        )),
+        (Some(close), None) => text.push_str(&format!(" Press {} to close.", close)),
+        (None, Some(restart)) => text.push_str(&format!(" Press {} to restart.", restart)),
+        (None, None) => {}
+    }
+    text
+}
+
+/// Returns the escape sequences and text that undo modes that the
+/// previous process may have left enabled, and draw a divider labelled
+/// with `label` above the output of a restarted process
+fn divider(cols: usize, label: &str) -> String {
+    let rule = cols.saturating_sub(label.len());
+    let left = rule.min(3);
+    format!(
+        // Leave the alternate screen, disable mouse reporting and
+        // bracketed paste, show the cursor and perform a soft reset
+        "\x1b[?1049l\x1b[?1000l\x1b[?1002l\x1b[?1003l\x1b[?1006l\x1b[?2004l\x1b[?25h\x1b[!p\
+         \x1b[0m\r\n\x1b[2m{}{}{}\x1b[0m\r\n",
+        "─".repeat(left),
+        label,
+        "─".repeat(rule - left)
+    )
+}
+
 fn bounded_kill_wait(child: &mut Box<dyn Child + 'static>) {
     for attempt in 0..5 {
         let _ = child.kill();
@@ -650,3 +968,45 @@ impl Drop for LocalPane {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn exit_footer() {
+        assert_eq!(format_runtime(Duration::from_millis(4200)), "4.2s");
+        assert_eq!(format_runtime(Duration::from_secs(187)), "3m07s");
+        assert_eq!(format_runtime(Duration::from_secs(8100)), "2h15m");
+
+        assert_eq!(
+            exit_footer_text(
+                &ExitStatus::with_exit_code(0),
+                Duration::from_secs(2),
+                Some("CTRL-SHIFT-W"),
+                None
+            ),
+            "\x1b[0m\r\n\x1b[1;32m[Exited with code 0]\x1b[0m after 2.0s. \
+             Press CTRL-SHIFT-W to close."
+        );
+        assert_eq!(
+            exit_footer_text(
+                &ExitStatus::with_signal("SIGSEGV"),
+                Duration::from_secs(2),
+                None,
+                None
+            ),
+            "\x1b[0m\r\n\x1b[1;31m[Terminated by SIGSEGV]\x1b[0m after 2.0s."
+        );
+        assert_eq!(
+            exit_footer_text(
+                &ExitStatus::with_exit_code(2),
+                Duration::from_secs(2),
+                Some("CTRL-SHIFT-W"),
+                Some("CTRL-SHIFT-R")
+            ),
+            "\x1b[0m\r\n\x1b[1;31m[Exited with code 2]\x1b[0m after 2.0s. \
+             Press CTRL-SHIFT-W to close or CTRL-SHIFT-R to restart."
+        );
+    }
+}
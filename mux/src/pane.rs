@@ -2,7 +2,7 @@ use crate::domain::DomainId;
 use crate::renderable::*;
 use crate::Mux;
 use async_trait::async_trait;
-use config::keyassignment::ScrollbackEraseMode;
+use config::keyassignment::{PaneInputMode, ScrollbackEraseMode};
 use downcast_rs::{impl_downcast, Downcast};
 use portable_pty::PtySize;
 use rangeset::RangeSet;
@@ -130,7 +130,7 @@ impl LogicalLine {
             let remainder = line.split_off(len);
             *phys = line;
             line = remainder;
-            let wrapped = idx == num_phys - 1;
+            let wrapped = idx != num_phys - 1;
             phys.set_last_cell_was_wrapped(wrapped);
         }
     }
@@ -273,6 +273,15 @@ pub trait Pane: Downcast {
     /// Called as a hint that the pane is being resized as part of
     /// a zoom-to-fill-all-the-tab-space operation.
     fn set_zoomed(&self, _zoomed: bool) {}
+    /// Called when the input mode of the pane is changed.  Panes that
+    /// represent a pane on a mux server pass the mode on to the server.
+    fn set_input_mode(&self, _mode: PaneInputMode) {}
+    /// Returns true if the input mode of the pane is applied by a mux
+    /// server, which fans out the input itself, rather than by the
+    /// local mux.
+    fn input_mode_is_remote(&self) -> bool {
+        false
+    }
     fn key_down(&self, key: KeyCode, mods: KeyModifiers) -> anyhow::Result<()>;
     fn mouse_event(&self, event: MouseEvent) -> anyhow::Result<()>;
     fn perform_actions(&self, _actions: Vec<termwiz::escape::Action>) {}
@@ -286,11 +295,30 @@ pub trait Pane: Downcast {
     /// Called to advise on whether this tab has focus
     fn focus_changed(&self, _focused: bool) {}
 
+    /// Returns true if the pane notifies the mux with
+    /// `MuxNotification::PaneOutput` whenever its content changes.
+    /// The GUI only polls panes that return false for dirty lines.
+    fn notifies_on_change(&self) -> bool {
+        false
+    }
+
     /// Certain panes are OK to be closed with impunity (no prompts)
     fn can_close_without_prompting(&self) -> bool {
         false
     }
 
+    /// Returns true if the pane can only be viewed; input sent to it
+    /// is discarded and it can't be resized
+    fn is_read_only(&self) -> bool {
+        false
+    }
+
+    /// Returns the name of another client of a shared session that
+    /// was the most recent to send input to the pane
+    fn collaborator(&self) -> Option<String> {
+        None
+    }
+
     /// Performs a search.
     /// If the result is empty then there are no matches.
     /// Otherwise, the result shall contain all possible matches.
@@ -314,6 +342,12 @@ pub trait Pane: Downcast {
 
     fn get_current_working_dir(&self) -> Option<Url>;
 
+    /// Returns the name of the program that is in the foreground
+    /// of the pane, if it can be determined
+    fn get_foreground_process_name(&self) -> Option<String> {
+        None
+    }
+
     fn trickle_paste(&self, text: String) -> anyhow::Result<()> {
         if text.len() <= PASTE_CHUNK_SIZE {
             // Send it all now
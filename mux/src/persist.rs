@@ -0,0 +1,355 @@
+//! Saves the layout and contents of the panes of the mux server so
+//! that they can be restored when it next starts.  The processes that
+//! ran in the panes can't be saved, so fresh ones are spawned in their
+//! place, below the saved contents and a divider.
+use crate::domain::LocalDomain;
+use crate::export::{to_ansi, ExportLine};
+use crate::localpane::LocalPane;
+use crate::pane::{Pane, PaneId};
+use crate::tab::{PaneEntry, PaneNode, Tab};
+use crate::Mux;
+use anyhow::{anyhow, Context};
+use portable_pty::CommandBuilder;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use wezterm_term::StableRowIndex;
+
+#[derive(Deserialize, Serialize, Debug)]
+struct SavedPane {
+    pane_id: PaneId,
+    /// The command that the pane was spawned with
+    command: Option<CommandBuilder>,
+    /// The scrollback and screen of the pane, as text with the
+    /// escape sequences that reproduce its attributes
+    contents: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct SavedTab {
+    tree: PaneNode,
+    panes: Vec<SavedPane>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct SavedSession {
+    /// The tabs of each window
+    windows: Vec<Vec<SavedTab>>,
+}
+
+/// Returns the path of the file that holds the session of the mux
+/// server for the unix domain named `domain_name`, so that servers
+/// for different domains don't overwrite each other's sessions
+fn session_file(domain_name: &str) -> PathBuf {
+    let name: String = domain_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    config::DATA_DIR.join(format!("mux-session-{}.json", name))
+}
+
+/// Returns the scrollback and screen of `pane` as text with escape
+/// sequences, omitting the blank rows at the bottom of the screen
+fn pane_contents(pane: &Rc<dyn Pane>) -> String {
+    let dims = pane.get_dimensions();
+    let (_first, lines) = pane
+        .get_lines(dims.scrollback_top..dims.physical_top + dims.viewport_rows as StableRowIndex);
+    let mut lines: Vec<ExportLine> = lines
+        .into_iter()
+        .map(|line| ExportLine {
+            wrapped: line.last_cell_was_wrapped(),
+            line,
+        })
+        .collect();
+    while lines.last().map_or(false, |l| l.line.is_whitespace()) {
+        lines.pop();
+    }
+    // The terminal that this is replayed into needs a carriage return
+    // to start each line at the left margin
+    to_ansi(&lines).replace('\n', "\r\n")
+}
+
+/// Returns the saved form of `tab`, or None if it contains panes
+/// that can't be respawned, such as those of remote domains
+fn capture_tab(tab: &Rc<Tab>) -> Option<SavedTab> {
+    let mut panes = vec![];
+    for pane in tab.iter_panes_ignoring_zoom() {
+        let local = pane.downcast_ref::<LocalPane>()?;
+        panes.push(SavedPane {
+            pane_id: pane.pane_id(),
+            command: local.command(),
+            contents: pane_contents(&pane),
+        });
+    }
+    Some(SavedTab {
+        tree: tab.codec_pane_tree(),
+        panes,
+    })
+}
+
+fn capture_session() -> SavedSession {
+    let mux = Mux::get().unwrap();
+    let mut session = SavedSession::default();
+    for window_id in mux.iter_windows() {
+        let window = match mux.get_window(window_id) {
+            Some(window) => window,
+            None => continue,
+        };
+        let tabs: Vec<SavedTab> = window.iter().filter_map(capture_tab).collect();
+        if !tabs.is_empty() {
+            session.windows.push(tabs);
+        }
+    }
+    session
+}
+
+/// Saves the layout and contents of the panes of the mux as the
+/// session of the unix domain named `domain_name`.
+/// If there are no panes to save, any prior save is removed.
+pub fn save_session(domain_name: &str) -> anyhow::Result<()> {
+    write_session(domain_name, &capture_session())
+}
+
+/// Writes `session` to the session file, or removes the file if
+/// `session` is empty.  The file holds the scrollback of the panes,
+/// which may include secrets, so only the user may read it.
+fn write_session(domain_name: &str, session: &SavedSession) -> anyhow::Result<()> {
+    let path = session_file(domain_name);
+    if session.windows.is_empty() {
+        if path.exists() {
+            std::fs::remove_file(&path).with_context(|| format!("removing {}", path.display()))?;
+        }
+        return Ok(());
+    }
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+    }
+    let data = serde_json::to_vec(session)?;
+
+    // Write to a temporary file first, so that a crash while saving
+    // doesn't lose the previous save
+    let temp = path.with_extension("json.tmp");
+    match std::fs::remove_file(&temp) {
+        Ok(()) => {}
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err).with_context(|| format!("removing {}", temp.display())),
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(&temp)
+        .and_then(|mut file| file.write_all(&data))
+        .with_context(|| format!("writing {}", temp.display()))?;
+    std::fs::rename(&temp, &path)
+        .with_context(|| format!("renaming {} to {}", temp.display(), path.display()))?;
+    Ok(())
+}
+
+fn leaf_entries<'a>(node: &'a PaneNode, entries: &mut Vec<&'a PaneEntry>) {
+    match node {
+        PaneNode::Empty => {}
+        PaneNode::Split { left, right, .. } => {
+            leaf_entries(left, entries);
+            leaf_entries(right, entries);
+        }
+        PaneNode::Leaf(entry) => entries.push(entry),
+    }
+}
+
+fn restore_tab(domain: &LocalDomain, saved: SavedTab) -> anyhow::Result<Rc<Tab>> {
+    let mux = Mux::get().unwrap();
+    let size = saved
+        .tree
+        .root_size()
+        .ok_or_else(|| anyhow!("the saved tab has no panes"))?;
+    let mut saved_panes: HashMap<PaneId, SavedPane> = saved
+        .panes
+        .into_iter()
+        .map(|pane| (pane.pane_id, pane))
+        .collect();
+
+    // Spawn all of the panes first, as building the tab from the
+    // tree cannot fail
+    let mut entries = vec![];
+    leaf_entries(&saved.tree, &mut entries);
+    let mut panes = HashMap::new();
+    for entry in entries {
+        let saved_pane = saved_panes.remove(&entry.pane_id);
+        let command = saved_pane.as_ref().and_then(|pane| pane.command.clone());
+        let cwd = entry
+            .working_dir
+            .as_ref()
+            .filter(|dir| dir.url.scheme() == "file")
+            .map(|dir| dir.url.path().to_string());
+        let pane = domain.spawn_local_pane(entry.size, command, cwd)?;
+        if let (Some(local), Some(saved_pane)) =
+            (pane.downcast_ref::<LocalPane>(), saved_pane.as_ref())
+        {
+            local.restore_contents(&saved_pane.contents);
+        }
+        panes.insert(entry.pane_id, pane);
+    }
+
+    let tab = Rc::new(Tab::new(&size));
+    tab.sync_with_pane_tree(size, saved.tree, |entry| Rc::clone(&panes[&entry.pane_id]));
+    mux.add_tab_no_panes(&tab);
+    for pane in panes.values() {
+        mux.add_pane(pane)?;
+    }
+    Ok(tab)
+}
+
+/// Recreates the windows, tabs and panes that were most recently
+/// saved for the unix domain named `domain_name`, spawning fresh
+/// processes in the panes.
+/// Returns the number of tabs that were restored.
+pub fn restore_session(domain_name: &str) -> anyhow::Result<usize> {
+    let path = session_file(domain_name);
+    let data = match std::fs::read(&path) {
+        Ok(data) => data,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(err) => return Err(err).with_context(|| format!("reading {}", path.display())),
+    };
+    let session: SavedSession =
+        serde_json::from_slice(&data).with_context(|| format!("parsing {}", path.display()))?;
+
+    let mux = Mux::get().unwrap();
+    let domain = mux.default_domain();
+    let domain = domain
+        .downcast_ref::<LocalDomain>()
+        .ok_or_else(|| anyhow!("panes can only be restored into a local domain"))?;
+
+    let mut num_tabs = 0;
+    for tabs in session.windows {
+        // The window is created once a tab has been restored into it,
+        // and is announced when the builder is dropped
+        let mut window = None;
+        for saved in tabs {
+            match restore_tab(domain, saved) {
+                Ok(tab) => {
+                    let window = window.get_or_insert_with(|| mux.new_empty_window());
+                    mux.add_tab_to_window(&tab, **window)?;
+                    num_tabs += 1;
+                }
+                Err(err) => log::error!("Unable to restore a saved tab: {:#}", err),
+            }
+        }
+    }
+    Ok(num_tabs)
+}
+
+fn periodic_save(domain_name: &str, interval: Duration) {
+    loop {
+        std::thread::sleep(interval);
+        // The panes can only be read on the main thread, but the
+        // serialization and file IO happen here so as not to hold it up
+        let session =
+            promise::spawn::block_on(promise::spawn::spawn_into_main_thread(async move {
+                capture_session()
+            }));
+        if let Err(err) = write_session(domain_name, &session) {
+            log::error!("Unable to save the mux session: {:#}", err);
+        }
+    }
+}
+
+/// Starts saving the panes as the session of the unix domain named
+/// `domain_name` every `interval`, if that isn't already happening
+pub fn start_periodic_save(domain_name: &str, interval: Duration) {
+    static SAVE_STARTED: AtomicBool = AtomicBool::new(false);
+    if let Ok(false) =
+        SAVE_STARTED.compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed)
+    {
+        let domain_name = domain_name.to_string();
+        std::thread::Builder::new()
+            .name("mux_session_save".into())
+            .spawn(move || periodic_save(&domain_name, interval))
+            .expect("failed to spawn mux session save thread");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::scratch::ScratchPane;
+    use portable_pty::PtySize;
+    use termwiz::cell::CellAttributes;
+
+    fn scratch_pane() -> Rc<dyn Pane> {
+        let size = PtySize {
+            rows: 4,
+            cols: 10,
+            pixel_width: 0,
+            pixel_height: 0,
+        };
+        Rc::new(ScratchPane::new(0, size, "test").unwrap())
+    }
+
+    /// Summarizes the rows of `pane` as their text, whether they wrap
+    /// and the attributes of their cells, up to the last non-blank row
+    fn summarize(pane: &Rc<dyn Pane>) -> Vec<(String, bool, Vec<CellAttributes>)> {
+        let dims = pane.get_dimensions();
+        let (_first, lines) = pane.get_lines(
+            dims.scrollback_top..dims.physical_top + dims.viewport_rows as StableRowIndex,
+        );
+        let mut rows: Vec<_> = lines
+            .iter()
+            .map(|line| {
+                let text = line.as_str().trim_end().to_string();
+                let attrs = line
+                    .cells()
+                    .iter()
+                    .take(text.chars().count())
+                    .map(|cell| cell.attrs().clone())
+                    .collect();
+                (text, line.last_cell_was_wrapped(), attrs)
+            })
+            .collect();
+        while rows.last().map_or(false, |(text, ..)| text.is_empty()) {
+            rows.pop();
+        }
+        rows
+    }
+
+    #[test]
+    fn contents_round_trip() {
+        let original = scratch_pane();
+        original.downcast_ref::<ScratchPane>().unwrap().write_str(
+            "plain\n\x1b[1;31mbold red\x1b[0m\nthis line wraps around\none\ntwo\nthree\n",
+        );
+
+        let restored = scratch_pane();
+        restored
+            .downcast_ref::<ScratchPane>()
+            .unwrap()
+            .write_str(&pane_contents(&original));
+
+        let rows = summarize(&original);
+        // Some of the rows have gone to the scrollback, and one wraps
+        assert_eq!(rows.len(), 8);
+        assert!(rows[2].1);
+        assert_eq!(summarize(&restored), rows);
+    }
+
+    #[test]
+    fn session_file_names() {
+        assert!(session_file("unix").ends_with("mux-session-unix.json"));
+        assert!(session_file("../my domain").ends_with("mux-session-___my_domain.json"));
+    }
+}
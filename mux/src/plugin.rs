@@ -0,0 +1,76 @@
+//! This module is the supported surface for crates that want to
+//! provide their own kinds of pane to the multiplexer; for example,
+//! an embedded file viewer or a pane that renders a chart.
+//!
+//! A plugin implements `Pane` for its content and `Domain` to produce
+//! tabs and splits holding those panes.  The domain is then made known
+//! to the mux, either by calling `register_domain` before the mux is
+//! created, or by calling `Mux::register_domain` on a running mux.
+//!
+//! The types re-exported here are the ones that the `Pane` and `Domain`
+//! traits reference; plugins should import them from this module rather
+//! than reaching into the individual mux modules, as those may be
+//! reorganized between releases.
+//!
+//! See `mux/examples/text_viewer.rs` for a complete example.
+use anyhow::anyhow;
+use std::sync::{Arc, Mutex};
+
+pub use crate::domain::{alloc_domain_id, Domain, DomainId, DomainState};
+pub use crate::pane::{alloc_pane_id, Pane, PaneId, Pattern, SearchResult};
+pub use crate::renderable::{RenderableDimensions, StableCursorPosition};
+pub use crate::scratch::ScratchPane;
+pub use crate::tab::{SplitDirection, SplitRequest, Tab, TabId};
+pub use crate::window::WindowId;
+pub use crate::Mux;
+pub use portable_pty::{CommandBuilder, PtySize};
+pub use wezterm_term::color::ColorPalette;
+pub use wezterm_term::{KeyCode, KeyModifiers, MouseEvent, StableRowIndex};
+
+/// Produces a domain when the mux is created.
+/// Domains are bound to the thread that owns the mux, so rather than
+/// registering a domain instance, plugins register a function that
+/// is called on that thread to construct it.
+pub type DomainFactory = Box<dyn Fn() -> anyhow::Result<Arc<dyn Domain>> + Send + Sync>;
+
+lazy_static::lazy_static! {
+    static ref FACTORIES: Mutex<Vec<DomainFactory>> = Mutex::new(vec![]);
+}
+
+/// Registers a domain to be added to every mux created after this call.
+/// This is intended to be called early in `main`, before the frontend
+/// or mux server has been started.
+pub fn register_domain<F>(factory: F)
+where
+    F: Fn() -> anyhow::Result<Arc<dyn Domain>> + Send + Sync + 'static,
+{
+    FACTORIES.lock().unwrap().push(Box::new(factory));
+}
+
+/// Constructs the domains that were passed to `register_domain`.
+/// A factory that fails is logged and skipped so that a broken
+/// plugin doesn't prevent the mux from starting.
+pub(crate) fn instantiate_registered_domains() -> Vec<Arc<dyn Domain>> {
+    FACTORIES
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|factory| match factory() {
+            Ok(domain) => Some(domain),
+            Err(err) => {
+                log::error!("failed to create plugin domain: {:#}", err);
+                None
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn check_unique_name(mux: &Mux, domain: &Arc<dyn Domain>) -> anyhow::Result<()> {
+    match mux.get_domain_by_name(domain.domain_name()) {
+        Some(_) => Err(anyhow!(
+            "a domain named {} is already registered",
+            domain.domain_name()
+        )),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,206 @@
+//! A scratch pane is a pane that is not attached to a pty.
+//! Its content is produced by calling `ScratchPane::write_str`,
+//! which makes it suitable for help screens, error reports and
+//! connection banners.  The content is held by a regular terminal
+//! model, so escape sequences, scrollback, search and copy mode
+//! all work the same way that they do for any other pane.
+use crate::domain::DomainId;
+use crate::pane::{alloc_pane_id, Pane, PaneId};
+use crate::renderable::*;
+use crate::{Mux, MuxNotification};
+use config::keyassignment::ScrollbackEraseMode;
+use filedescriptor::{FileDescriptor, Pipe};
+use portable_pty::PtySize;
+use rangeset::RangeSet;
+use std::cell::{RefCell, RefMut};
+use std::io::Sink;
+use std::ops::Range;
+use termwiz::surface::Line;
+use url::Url;
+use wezterm_term::color::ColorPalette;
+use wezterm_term::{KeyCode, KeyModifiers, MouseEvent, StableRowIndex, Terminal};
+
+pub struct ScratchPane {
+    pane_id: PaneId,
+    domain_id: DomainId,
+    title: RefCell<String>,
+    terminal: RefCell<Terminal>,
+    sink: RefCell<Sink>,
+    /// The mux runs a reader thread for every pane, and treats EOF
+    /// as the pane having exited.  Content is written directly to the
+    /// terminal model, so the reader is given a pipe that stays silent
+    /// until the write end is dropped when the pane is killed.
+    reader: FileDescriptor,
+    keep_alive: RefCell<Option<FileDescriptor>>,
+}
+
+impl ScratchPane {
+    pub fn new(domain_id: DomainId, size: PtySize, title: &str) -> anyhow::Result<Self> {
+        let terminal = Terminal::new(
+            crate::pty_size_to_terminal_size(size),
+            std::sync::Arc::new(config::TermConfig {}),
+            "WezTerm",
+            config::wezterm_version(),
+            Box::new(std::io::sink()),
+        );
+        let pipe = Pipe::new()?;
+
+        Ok(Self {
+            pane_id: alloc_pane_id(),
+            domain_id,
+            title: RefCell::new(title.to_string()),
+            terminal: RefCell::new(terminal),
+            sink: RefCell::new(std::io::sink()),
+            reader: pipe.read,
+            keep_alive: RefCell::new(Some(pipe.write)),
+        })
+    }
+
+    /// Appends `text` to the pane.  `text` may contain escape sequences.
+    /// Newlines are translated to CRLF, as a tty would do.
+    pub fn write_str(&self, text: &str) {
+        let text = text.replace("\r\n", "\n").replace('\n', "\r\n");
+        self.terminal.borrow_mut().advance_bytes(text);
+        if let Some(mux) = Mux::get() {
+            mux.notify(MuxNotification::PaneOutput(self.pane_id));
+        }
+    }
+
+    pub fn set_title(&self, title: &str) {
+        *self.title.borrow_mut() = title.to_string();
+    }
+}
+
+impl Pane for ScratchPane {
+    fn pane_id(&self) -> PaneId {
+        self.pane_id
+    }
+
+    fn get_cursor_position(&self) -> StableCursorPosition {
+        terminal_get_cursor_position(&mut self.terminal.borrow_mut())
+    }
+
+    fn get_dirty_lines(&self, lines: Range<StableRowIndex>) -> RangeSet<StableRowIndex> {
+        terminal_get_dirty_lines(&mut self.terminal.borrow_mut(), lines)
+    }
+
+    fn get_lines(&self, lines: Range<StableRowIndex>) -> (StableRowIndex, Vec<Line>) {
+        terminal_get_lines(&mut self.terminal.borrow_mut(), lines)
+    }
+
+    fn get_dimensions(&self) -> RenderableDimensions {
+        terminal_get_dimensions(&mut self.terminal.borrow_mut())
+    }
+
+    fn get_title(&self) -> String {
+        self.title.borrow().clone()
+    }
+
+    fn can_close_without_prompting(&self) -> bool {
+        true
+    }
+
+    fn send_paste(&self, _text: &str) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn reader(&self) -> anyhow::Result<Box<dyn std::io::Read + Send>> {
+        Ok(Box::new(self.reader.try_clone()?))
+    }
+
+    fn writer(&self) -> RefMut<dyn std::io::Write> {
+        self.sink.borrow_mut()
+    }
+
+    fn resize(&self, size: PtySize) -> anyhow::Result<()> {
+        self.terminal.borrow_mut().resize(
+            size.rows as usize,
+            size.cols as usize,
+            size.pixel_width as usize,
+            size.pixel_height as usize,
+        );
+        Ok(())
+    }
+
+    fn key_down(&self, _key: KeyCode, _mods: KeyModifiers) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn mouse_event(&self, event: MouseEvent) -> anyhow::Result<()> {
+        self.terminal.borrow_mut().mouse_event(event)
+    }
+
+    fn perform_actions(&self, actions: Vec<termwiz::escape::Action>) {
+        self.terminal.borrow_mut().perform_actions(actions)
+    }
+
+    fn is_dead(&self) -> bool {
+        self.keep_alive.borrow().is_none()
+    }
+
+    fn kill(&self) {
+        self.keep_alive.borrow_mut().take();
+    }
+
+    fn palette(&self) -> ColorPalette {
+        self.terminal.borrow().palette()
+    }
+
+    fn domain_id(&self) -> DomainId {
+        self.domain_id
+    }
+
+    fn erase_scrollback(&self, erase_mode: ScrollbackEraseMode) {
+        match erase_mode {
+            ScrollbackEraseMode::ScrollbackOnly => {
+                self.terminal.borrow_mut().erase_scrollback();
+            }
+            ScrollbackEraseMode::ScrollbackAndViewport => {
+                self.terminal.borrow_mut().erase_scrollback_and_viewport();
+            }
+        }
+    }
+
+    fn is_mouse_grabbed(&self) -> bool {
+        false
+    }
+
+    fn is_alt_screen_active(&self) -> bool {
+        false
+    }
+
+    fn get_current_working_dir(&self) -> Option<Url> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn write_str() {
+        let size = PtySize {
+            rows: 4,
+            cols: 20,
+            pixel_width: 0,
+            pixel_height: 0,
+        };
+        let pane = ScratchPane::new(0, size, "scratch").unwrap();
+        pane.write_str("hello\nworld\n1\n2\n3");
+
+        let dims = pane.get_dimensions();
+        assert_eq!(dims.scrollback_rows, 5);
+
+        let (_, lines) = pane.get_lines(0..5);
+        let text: Vec<String> = lines
+            .iter()
+            .map(|line| line.as_str().trim_end().to_string())
+            .collect();
+        assert_eq!(text, vec!["hello", "world", "1", "2", "3"]);
+
+        assert!(!pane.is_dead());
+        pane.kill();
+        assert!(pane.is_dead());
+    }
+}
@@ -2,7 +2,7 @@ use crate::connui::ConnectionUI;
 use crate::domain::{alloc_domain_id, Domain, DomainId, DomainState};
 use crate::localpane::LocalPane;
 use crate::pane::{alloc_pane_id, Pane, PaneId};
-use crate::tab::{SplitDirection, Tab, TabId};
+use crate::tab::{SplitRequest, Tab, TabId};
 use crate::window::WindowId;
 use crate::Mux;
 use anyhow::{anyhow, bail, Context, Error};
@@ -53,7 +53,8 @@ impl LineEditorHost for PasswordPromptHost {
 
 pub fn ssh_connect_with_ui(
     remote_address: &str,
-    username: &str,
+    username: Option<&str>,
+    ssh_option: &HashMap<String, String>,
     ui: &mut ConnectionUI,
 ) -> anyhow::Result<Session> {
     let cloned_ui = ui.clone();
@@ -71,17 +72,24 @@ pub fn ssh_connect_with_ui(
             }
         };
 
-        let mut ssh_config = ssh_config.for_host(&remote_host_name);
-        ssh_config.insert("user".to_string(), username.to_string());
+        if let Some(username) = username {
+            ssh_config.set_option("user", username);
+        }
         if let Some(port) = port {
-            ssh_config.insert("port".to_string(), port.to_string());
+            ssh_config.set_option("port", port.to_string());
+        }
+        for (k, v) in ssh_option {
+            ssh_config.set_option(k, v);
         }
+        let ssh_config = ssh_config.for_host(&remote_host_name);
 
-        ui.output_str(&format!("Connecting to {} using SSH\n", remote_address));
         let (session, events) = Session::connect(ssh_config.clone())?;
 
         while let Ok(event) = smol::block_on(events.recv()) {
             match event {
+                SessionEvent::Progress(message) => {
+                    ui.output_str(&format!("{}\n", message));
+                }
                 SessionEvent::Banner(banner) => {
                     if let Some(banner) = banner {
                         ui.output_str(&format!("{}\n", banner));
@@ -309,6 +317,9 @@ fn connect_ssh_session(
     // Process authentication related events
     while let Ok(event) = smol::block_on(events.recv()) {
         match event {
+            SessionEvent::Progress(message) => {
+                shim.output_line(&message)?;
+            }
             SessionEvent::Banner(banner) => {
                 if let Some(banner) = banner {
                     shim.output_line(&banner)?;
@@ -553,7 +564,7 @@ impl Domain for RemoteSshDomain {
         _command_dir: Option<String>,
         tab: TabId,
         pane_id: PaneId,
-        direction: SplitDirection,
+        request: SplitRequest,
     ) -> anyhow::Result<Rc<dyn Pane>> {
         let mux = Mux::get().unwrap();
         let tab = match mux.get_tab(tab) {
@@ -570,7 +581,7 @@ impl Domain for RemoteSshDomain {
             None => anyhow::bail!("invalid pane id {}", pane_id),
         };
 
-        let split_size = match tab.compute_split_size(pane_index, direction) {
+        let split_size = match tab.compute_split_size(pane_index, request) {
             Some(s) => s,
             None => anyhow::bail!("invalid pane index {}", pane_index),
         };
@@ -624,7 +635,7 @@ impl Domain for RemoteSshDomain {
             self.id,
         ));
 
-        tab.split_and_insert(pane_index, direction, Rc::clone(&pane))?;
+        tab.split_and_insert(pane_index, request, Rc::clone(&pane))?;
 
         mux.add_pane(&pane)?;
 
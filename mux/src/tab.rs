@@ -2,7 +2,9 @@ use crate::domain::DomainId;
 use crate::pane::*;
 use crate::{Mux, WindowId};
 use bintree::PathBranch;
-use config::keyassignment::PaneDirection;
+use config::configuration;
+use config::keyassignment::{PaneArrangement, PaneDirection};
+pub use config::keyassignment::{SplitDirection, SplitSize};
 use portable_pty::PtySize;
 use rangeset::range_intersection;
 use serde::{Deserialize, Serialize};
@@ -23,7 +25,10 @@ pub struct Tab {
     pane: RefCell<Option<Tree>>,
     size: RefCell<PtySize>,
     active: RefCell<usize>,
+    /// The panes that were previously active, most recently used first
+    mru: RefCell<Vec<PaneId>>,
     zoomed: RefCell<Option<Rc<dyn Pane>>>,
+    floating: RefCell<Option<Rc<dyn Pane>>>,
 }
 
 #[derive(Clone)]
@@ -64,10 +69,21 @@ impl std::fmt::Debug for PositionedPane {
     }
 }
 
+/// Describes how a pane should be split
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
-pub enum SplitDirection {
-    Horizontal,
-    Vertical,
+pub struct SplitRequest {
+    pub direction: SplitDirection,
+    /// The size of the right/bottom pane that is created by the split
+    pub size: SplitSize,
+}
+
+impl From<SplitDirection> for SplitRequest {
+    fn from(direction: SplitDirection) -> Self {
+        Self {
+            direction,
+            size: SplitSize::default(),
+        }
+    }
 }
 
 /// The size is of the (first, second) child of the split
@@ -400,6 +416,109 @@ fn cell_dimensions(size: &PtySize) -> PtySize {
     }
 }
 
+/// Returns the number of panes that are placed side by side along
+/// `direction` in this portion of the tree
+fn count_along(tree: &Tree, direction: SplitDirection) -> usize {
+    match tree {
+        Tree::Node {
+            left,
+            right,
+            data: Some(data),
+        } if data.direction == direction => {
+            count_along(&*left, direction) + count_along(&*right, direction)
+        }
+        _ => 1,
+    }
+}
+
+/// Recomputes the sizes recorded in the splits of `tree`, which occupies
+/// `size`.  Splits in `direction`, or all splits if `direction` is None,
+/// are positioned so that the panes that are placed side by side along
+/// that axis are of equal size.  Other splits retain their position
+/// where the space allows.
+fn balance_splits(
+    tree: &mut Tree,
+    size: PtySize,
+    direction: Option<SplitDirection>,
+    cell_dimensions: &PtySize,
+) {
+    if let Tree::Node {
+        left,
+        right,
+        data: Some(data),
+    } = tree
+    {
+        let (total, current) = match data.direction {
+            SplitDirection::Horizontal => (size.cols, data.first.cols),
+            SplitDirection::Vertical => (size.rows, data.first.rows),
+        };
+        let total = total as usize;
+
+        let first = if direction.map_or(true, |d| d == data.direction) {
+            let left_count = count_along(&*left, data.direction);
+            let count = left_count + count_along(&*right, data.direction);
+            // Share out the cells that are not used to render the splits
+            let available = total.saturating_sub(count - 1);
+            available * left_count / count + left_count - 1
+        } else {
+            current as usize
+        };
+        let first = first.min(total.saturating_sub(2)).max(1);
+        let second = total.saturating_sub(first + 1);
+
+        let sized = |cols: usize, rows: usize| PtySize {
+            rows: rows as u16,
+            cols: cols as u16,
+            pixel_width: cell_dimensions.pixel_width * cols as u16,
+            pixel_height: cell_dimensions.pixel_height * rows as u16,
+        };
+        let rows = size.rows as usize;
+        let cols = size.cols as usize;
+        match data.direction {
+            SplitDirection::Horizontal => {
+                data.first = sized(first, rows);
+                data.second = sized(second, rows);
+            }
+            SplitDirection::Vertical => {
+                data.first = sized(cols, first);
+                data.second = sized(cols, second);
+            }
+        }
+
+        balance_splits(&mut *left, data.first, direction, cell_dimensions);
+        balance_splits(&mut *right, data.second, direction, cell_dimensions);
+    }
+}
+
+/// Builds a tree that places `trees` side by side along `direction`.
+/// The sizes of the splits are placeholders to be filled in by
+/// `balance_splits`.
+fn chain_trees(mut trees: Vec<Tree>, direction: SplitDirection) -> Tree {
+    let last = trees.pop().unwrap_or(Tree::Empty);
+    trees
+        .into_iter()
+        .rev()
+        .fold(last, |right, left| Tree::Node {
+            left: Box::new(left),
+            right: Box::new(right),
+            data: Some(SplitDirectionAndSize {
+                direction,
+                first: PtySize::default(),
+                second: PtySize::default(),
+            }),
+        })
+}
+
+fn chain_panes(panes: &[Rc<dyn Pane>], direction: SplitDirection) -> Tree {
+    chain_trees(
+        panes
+            .iter()
+            .map(|pane| Tree::Leaf(Rc::clone(pane)))
+            .collect(),
+        direction,
+    )
+}
+
 impl Tab {
     pub fn new(size: &PtySize) -> Self {
         Self {
@@ -407,7 +526,9 @@ impl Tab {
             pane: RefCell::new(Some(Tree::new())),
             size: RefCell::new(*size),
             active: RefCell::new(0),
+            mru: RefCell::new(vec![]),
             zoomed: RefCell::new(None),
+            floating: RefCell::new(None),
         }
     }
 
@@ -512,6 +633,10 @@ impl Tab {
         }
     }
 
+    pub fn is_zoomed(&self) -> bool {
+        self.zoomed.borrow().is_some()
+    }
+
     pub fn set_zoomed(&self, zoomed: bool) {
         if self.zoomed.borrow().is_some() == zoomed {
             // Current zoom state matches intended zoom state,
@@ -551,12 +676,39 @@ impl Tab {
                 Tree::Leaf(p) => p.pane_id() == pane,
             }
         }
+        if let Some(floating) = self.floating.borrow().as_ref() {
+            if floating.pane_id() == pane {
+                return true;
+            }
+        }
         match &*self.pane.borrow() {
             Some(root) => contains(root, pane),
             None => false,
         }
     }
 
+    /// Returns the panes contained in this tab in topological order.
+    /// Unlike `iter_panes`, all panes are returned even when the
+    /// tab is zoomed.
+    pub fn iter_panes_ignoring_zoom(&self) -> Vec<Rc<dyn Pane>> {
+        let mut panes = vec![];
+        let mut root = self.pane.borrow_mut();
+        let mut cursor = root.take().unwrap().cursor();
+
+        loop {
+            if let Some(pane) = cursor.leaf_mut() {
+                panes.push(Rc::clone(pane));
+            }
+            match cursor.preorder_next() {
+                Ok(c) => cursor = c,
+                Err(c) => {
+                    root.replace(c.tree());
+                    return panes;
+                }
+            }
+        }
+    }
+
     /// Walks the pane tree to produce the topologically ordered flattened
     /// list of PositionedPane instances along with their positioning information.
     pub fn iter_panes(&self) -> Vec<PositionedPane> {
@@ -752,6 +904,10 @@ impl Tab {
 
         // And finally restore the zoom, if appropriate
         self.set_zoomed(was_zoomed);
+
+        if let Some(floating) = self.get_floating_pane() {
+            floating.resize(self.compute_floating_size()).ok();
+        }
     }
 
     fn apply_pane_size(&self, pane_size: PtySize, cursor: &mut Cursor) {
@@ -1198,9 +1354,23 @@ impl Tab {
                 }
             }
             *self.active.borrow_mut() = active_idx;
+
+            let floating_is_dead = self
+                .floating
+                .borrow()
+                .as_ref()
+                .map_or(false, |pane| f(pane_index, pane));
+            if floating_is_dead {
+                if let Some(pane) = self.floating.borrow_mut().take() {
+                    dead_panes.push(pane.pane_id());
+                }
+            }
         }
 
         if !dead_panes.is_empty() {
+            self.mru
+                .borrow_mut()
+                .retain(|pane_id| !dead_panes.contains(pane_id));
             promise::spawn::spawn_into_main_thread(async move {
                 let mux = Mux::get().unwrap();
                 for pane_id in dead_panes.into_iter() {
@@ -1246,6 +1416,72 @@ impl Tab {
             .map(|p| Rc::clone(&p.pane))
     }
 
+    /// Returns the floating pane that hovers above the splits in this
+    /// tab, if any
+    pub fn get_floating_pane(&self) -> Option<Rc<dyn Pane>> {
+        self.floating.borrow().as_ref().map(Rc::clone)
+    }
+
+    /// Makes `pane` the floating pane of this tab, resizing it to fit.
+    /// Any prior floating pane is removed from the mux.
+    pub fn set_floating_pane(&self, pane: &Rc<dyn Pane>) {
+        pane.resize(self.compute_floating_size()).ok();
+        if let Some(prior) = self.floating.borrow_mut().replace(Rc::clone(pane)) {
+            let pane_id = prior.pane_id();
+            promise::spawn::spawn_into_main_thread(async move {
+                Mux::get().unwrap().remove_pane(pane_id);
+            })
+            .detach();
+        }
+    }
+
+    /// Computes the size of a floating pane in this tab
+    pub fn compute_floating_size(&self) -> PtySize {
+        self.compute_floating_position().2
+    }
+
+    /// Returns the position of the floating pane, if any.
+    /// It is numbered after the panes in the splits.
+    pub fn floating_pane_position(&self) -> Option<PositionedPane> {
+        let pane = self.get_floating_pane()?;
+        let (left, top, size) = self.compute_floating_position();
+        Some(PositionedPane {
+            index: self.count_panes(),
+            is_active: true,
+            is_zoomed: false,
+            left,
+            top,
+            width: size.cols as usize,
+            pixel_width: size.pixel_width as usize,
+            height: size.rows as usize,
+            pixel_height: size.pixel_height as usize,
+            pane,
+        })
+    }
+
+    /// Computes the (left, top) offset and the size of a floating pane,
+    /// which is centered in the tab and sized per `floating_pane_size`.
+    /// Room is left around it for its border.
+    fn compute_floating_position(&self) -> (usize, usize, PtySize) {
+        let size = *self.size.borrow();
+        let dims = cell_dimensions(&size);
+        let percent = configuration().floating_pane_size.min(100) as usize;
+        let cols = size.cols as usize;
+        let rows = size.rows as usize;
+        let width = (cols * percent / 100).min(cols.saturating_sub(2)).max(1);
+        let height = (rows * percent / 100).min(rows.saturating_sub(2)).max(1);
+        (
+            cols.saturating_sub(width) / 2,
+            rows.saturating_sub(height) / 2,
+            PtySize {
+                rows: height as u16,
+                cols: width as u16,
+                pixel_width: dims.pixel_width * width as u16,
+                pixel_height: dims.pixel_height * height as u16,
+            },
+        )
+    }
+
     #[allow(unused)]
     pub fn get_active_idx(&self) -> usize {
         *self.active.borrow()
@@ -1257,12 +1493,188 @@ impl Tab {
             .iter()
             .find(|p| p.pane.pane_id() == pane.pane_id())
         {
-            *self.active.borrow_mut() = item.index;
+            self.set_active_idx(item.index);
         }
     }
 
+    /// If `pane_index` is different from the current active pane,
+    /// save the current pane id and then make `pane_index` the
+    /// active pane.
     pub fn set_active_idx(&self, pane_index: usize) {
+        if pane_index != *self.active.borrow() {
+            self.save_last_active();
+        }
+        self.set_active_idx_without_saving(pane_index);
+    }
+
+    /// Make `pane_index` the active pane.
+    /// The most recently used list is not changed.
+    pub fn set_active_idx_without_saving(&self, pane_index: usize) {
+        *self.active.borrow_mut() = pane_index;
+    }
+
+    /// Moves the active pane to the front of the most recently used list
+    fn save_last_active(&self) {
+        if let Some(pane) = self.get_active_pane() {
+            self.mark_used(pane.pane_id());
+        }
+    }
+
+    /// Moves `pane_id` to the front of the most recently used list
+    pub fn mark_used(&self, pane_id: PaneId) {
+        let mut mru = self.mru.borrow_mut();
+        mru.retain(|&id| id != pane_id);
+        mru.insert(0, pane_id);
+    }
+
+    /// Returns the panes in most recently used order, starting with
+    /// the active pane.  Panes that have never been active follow in
+    /// their topological order.
+    pub fn iter_panes_by_recent_use(&self) -> Vec<PositionedPane> {
+        let mut panes = self.iter_panes();
+        let mru = self.mru.borrow();
+        panes.sort_by_key(|pos| {
+            if pos.is_active {
+                0
+            } else {
+                mru.iter()
+                    .position(|&id| id == pos.pane.pane_id())
+                    .map_or(usize::max_value(), |idx| idx + 1)
+            }
+        });
+        panes
+    }
+
+    /// Swaps the position of the active pane with the pane at `pane_index`.
+    /// The active pane remains active in its new position.
+    /// Returns None if the tab is zoomed or if `pane_index` is invalid.
+    pub fn swap_active_with_index(&self, pane_index: usize) -> Option<()> {
+        if self.zoomed.borrow().is_some() {
+            return None;
+        }
+
+        let active_idx = *self.active.borrow();
+        let panes = self.iter_panes();
+        let active = Rc::clone(&panes.get(active_idx)?.pane);
+        let other = Rc::clone(&panes.get(pane_index)?.pane);
+        if active_idx == pane_index {
+            return Some(());
+        }
+
+        {
+            let mut root = self.pane.borrow_mut();
+            let mut cursor = root.take().unwrap().cursor();
+            let mut index = 0;
+
+            loop {
+                if let Some(pane) = cursor.leaf_mut() {
+                    if index == active_idx {
+                        *pane = Rc::clone(&other);
+                    } else if index == pane_index {
+                        *pane = Rc::clone(&active);
+                    }
+                    index += 1;
+                }
+                match cursor.preorder_next() {
+                    Ok(c) => cursor = c,
+                    Err(c) => {
+                        root.replace(c.tree());
+                        break;
+                    }
+                }
+            }
+        }
+
+        // The panes have traded places, and likely sizes too
+        for pos in self.iter_panes() {
+            if pos.index == active_idx || pos.index == pane_index {
+                pos.pane
+                    .resize(PtySize {
+                        rows: pos.height as u16,
+                        cols: pos.width as u16,
+                        pixel_width: pos.pixel_width as u16,
+                        pixel_height: pos.pixel_height as u16,
+                    })
+                    .ok();
+            }
+        }
+
         *self.active.borrow_mut() = pane_index;
+        Some(())
+    }
+
+    /// Resizes the splits along `direction` so that the panes that
+    /// are placed side by side along that axis are of equal size.
+    /// Does nothing if the tab is zoomed.
+    pub fn balance_panes(&self, direction: SplitDirection) {
+        if self.zoomed.borrow().is_some() {
+            return;
+        }
+        let size = *self.size.borrow();
+        let mut root = self.pane.borrow_mut();
+        if let Some(root) = root.as_mut() {
+            balance_splits(root, size, Some(direction), &cell_dimensions(&size));
+            apply_sizes_from_splits(root, &size);
+        }
+    }
+
+    /// Replaces the splits in the tab with those of `arrangement`,
+    /// and resizes the panes to fit.  The active pane is used as the
+    /// main pane of the arrangements that have one, and remains active.
+    /// Returns None if the tab is zoomed, or if it is too small to
+    /// hold the panes in that arrangement.
+    pub fn arrange_panes(&self, arrangement: PaneArrangement) -> Option<()> {
+        if self.zoomed.borrow().is_some() {
+            return None;
+        }
+
+        let mut panes = self.iter_panes_ignoring_zoom();
+        if panes.len() < 2 {
+            return Some(());
+        }
+        let active_idx = *self.active.borrow();
+        if active_idx >= panes.len() {
+            return None;
+        }
+
+        let (mut tree, active_idx) = match arrangement {
+            PaneArrangement::MainVertical | PaneArrangement::MainHorizontal => {
+                let main = panes.remove(active_idx);
+                let (direction, others) = match arrangement {
+                    PaneArrangement::MainVertical => {
+                        (SplitDirection::Horizontal, SplitDirection::Vertical)
+                    }
+                    _ => (SplitDirection::Vertical, SplitDirection::Horizontal),
+                };
+                (
+                    chain_trees(
+                        vec![Tree::Leaf(main), chain_panes(&panes, others)],
+                        direction,
+                    ),
+                    0,
+                )
+            }
+            PaneArrangement::Tiled => {
+                let columns = (panes.len() as f64).sqrt().ceil() as usize;
+                let rows = panes
+                    .chunks(columns)
+                    .map(|row| chain_panes(row, SplitDirection::Horizontal))
+                    .collect();
+                (chain_trees(rows, SplitDirection::Vertical), active_idx)
+            }
+        };
+
+        let size = *self.size.borrow();
+        balance_splits(&mut tree, size, None, &cell_dimensions(&size));
+        let (min_x, min_y) = compute_min_size(&mut tree);
+        if min_x > size.cols as usize || min_y > size.rows as usize {
+            return None;
+        }
+
+        apply_sizes_from_splits(&tree, &size);
+        self.pane.borrow_mut().replace(tree);
+        *self.active.borrow_mut() = active_idx;
+        Some(())
     }
 
     /// Assigns the root pane.
@@ -1287,32 +1699,31 @@ impl Tab {
     pub fn compute_split_size(
         &self,
         pane_index: usize,
-        direction: SplitDirection,
+        request: SplitRequest,
     ) -> Option<SplitDirectionAndSize> {
         let cell_dims = self.cell_dimensions();
 
         self.iter_panes().iter().nth(pane_index).map(|pos| {
-            fn split_dimension(dim: usize) -> (usize, usize) {
-                let halved = dim / 2;
-                if halved * 2 == dim {
-                    // Was an even size; we need to allow 1 cell to render
-                    // the split UI, so make the newly created leaf slightly
-                    // smaller
-                    (halved, halved.saturating_sub(1))
-                } else {
-                    (halved, halved)
-                }
+            fn split_dimension(dim: usize, size: SplitSize) -> (usize, usize) {
+                // We need to allow 1 cell to render the split UI; an
+                // even split leaves the newly created leaf slightly smaller
+                let second = size.evaluate(dim);
+                (dim.saturating_sub(second + 1), second)
             }
 
-            let ((width1, width2), (height1, height2)) = match direction {
-                SplitDirection::Horizontal => {
-                    (split_dimension(pos.width), (pos.height, pos.height))
-                }
-                SplitDirection::Vertical => ((pos.width, pos.width), split_dimension(pos.height)),
+            let ((width1, width2), (height1, height2)) = match request.direction {
+                SplitDirection::Horizontal => (
+                    split_dimension(pos.width, request.size),
+                    (pos.height, pos.height),
+                ),
+                SplitDirection::Vertical => (
+                    (pos.width, pos.width),
+                    split_dimension(pos.height, request.size),
+                ),
             };
 
             SplitDirectionAndSize {
-                direction,
+                direction: request.direction,
                 first: PtySize {
                     rows: height1 as _,
                     cols: width1 as _,
@@ -1329,14 +1740,14 @@ impl Tab {
         })
     }
 
-    /// Split the pane that has pane_index in the given direction and assign
+    /// Split the pane that has pane_index as described by `request` and assign
     /// the right/bottom pane of the newly created split to the provided Pane
     /// instance.  Returns the resultant index of the newly inserted pane.
     /// Both the split and the inserted pane will be resized.
     pub fn split_and_insert(
         &self,
         pane_index: usize,
-        direction: SplitDirection,
+        request: SplitRequest,
         pane: Rc<dyn Pane>,
     ) -> anyhow::Result<usize> {
         if self.zoomed.borrow().is_some() {
@@ -1345,7 +1756,7 @@ impl Tab {
 
         {
             let split_info = self
-                .compute_split_size(pane_index, direction)
+                .compute_split_size(pane_index, request)
                 .ok_or_else(|| {
                     anyhow::anyhow!("invalid pane_index {}; cannot split!", pane_index)
                 })?;
@@ -1369,6 +1780,9 @@ impl Tab {
                 anyhow::bail!("No space for split!");
             }
 
+            // The new pane becomes active
+            self.save_last_active();
+
             let mut root = self.pane.borrow_mut();
             let mut cursor = root.take().unwrap().cursor();
 
@@ -1589,6 +2003,239 @@ mod test {
         }
     }
 
+    #[test]
+    fn swap_panes() {
+        let size = PtySize {
+            rows: 24,
+            cols: 80,
+            pixel_width: 800,
+            pixel_height: 600,
+        };
+
+        let tab = Tab::new(&size);
+        tab.assign_pane(&FakePane::new(1, size));
+
+        let horz_size = tab
+            .compute_split_size(0, SplitDirection::Horizontal.into())
+            .unwrap();
+        tab.split_and_insert(
+            0,
+            SplitDirection::Horizontal.into(),
+            FakePane::new(2, horz_size.second),
+        )
+        .unwrap();
+        assert_eq!(1, tab.get_active_idx());
+
+        assert!(tab.swap_active_with_index(2).is_none());
+        tab.swap_active_with_index(0).unwrap();
+
+        let panes = tab.iter_panes();
+        assert_eq!(2, panes[0].pane.pane_id());
+        assert_eq!(true, panes[0].is_active);
+        assert_eq!(1, panes[1].pane.pane_id());
+        assert_eq!(false, panes[1].is_active);
+        assert_eq!(41, panes[1].left);
+    }
+
+    #[test]
+    fn sized_splits() {
+        let size = PtySize {
+            rows: 24,
+            cols: 80,
+            pixel_width: 800,
+            pixel_height: 600,
+        };
+
+        let tab = Tab::new(&size);
+        tab.assign_pane(&FakePane::new(1, size));
+
+        let horz_size = tab
+            .compute_split_size(
+                0,
+                SplitRequest {
+                    direction: SplitDirection::Horizontal,
+                    size: SplitSize::Percent(25),
+                },
+            )
+            .unwrap();
+        assert_eq!(horz_size.first.cols, 60);
+        assert_eq!(horz_size.second.cols, 19);
+        assert_eq!(horz_size.second.rows, 24);
+
+        let vert_size = tab
+            .compute_split_size(
+                0,
+                SplitRequest {
+                    direction: SplitDirection::Vertical,
+                    size: SplitSize::Cells(5),
+                },
+            )
+            .unwrap();
+        assert_eq!(vert_size.first.rows, 18);
+        assert_eq!(vert_size.second.rows, 5);
+        assert_eq!(vert_size.second.pixel_height, 125);
+
+        let too_big = SplitRequest {
+            direction: SplitDirection::Vertical,
+            size: SplitSize::Cells(30),
+        };
+        assert!(tab
+            .split_and_insert(0, too_big, FakePane::new(2, size))
+            .is_err());
+        assert_eq!(1, tab.iter_panes().len());
+    }
+
+    #[test]
+    fn balance_and_arrange() {
+        let size = PtySize {
+            rows: 24,
+            cols: 80,
+            pixel_width: 800,
+            pixel_height: 600,
+        };
+
+        let tab = Tab::new(&size);
+        tab.assign_pane(&FakePane::new(1, size));
+        let quarter = SplitRequest {
+            direction: SplitDirection::Horizontal,
+            size: SplitSize::Percent(25),
+        };
+        let split = tab.compute_split_size(0, quarter).unwrap();
+        tab.split_and_insert(0, quarter, FakePane::new(2, split.second))
+            .unwrap();
+        let split = tab
+            .compute_split_size(1, SplitDirection::Horizontal.into())
+            .unwrap();
+        tab.split_and_insert(
+            1,
+            SplitDirection::Horizontal.into(),
+            FakePane::new(3, split.second),
+        )
+        .unwrap();
+
+        let widths =
+            |tab: &Tab| -> Vec<usize> { tab.iter_panes().iter().map(|p| p.width).collect() };
+        assert_eq!(widths(&tab), vec![60, 9, 9]);
+
+        // Balancing the other axis leaves things alone
+        tab.balance_panes(SplitDirection::Vertical);
+        assert_eq!(widths(&tab), vec![60, 9, 9]);
+
+        tab.balance_panes(SplitDirection::Horizontal);
+        let panes = tab.iter_panes();
+        assert_eq!(widths(&tab), vec![26, 26, 26]);
+        assert_eq!(panes[2].left, 54);
+        assert_eq!(panes[2].pixel_width, 260);
+
+        // The new pane is active, and becomes the main pane
+        assert_eq!(tab.get_active_idx(), 2);
+        tab.arrange_panes(PaneArrangement::MainVertical).unwrap();
+        let panes = tab.iter_panes();
+        let ids: Vec<PaneId> = panes.iter().map(|p| p.pane.pane_id()).collect();
+        assert_eq!(ids, vec![3, 1, 2]);
+        assert!(panes[0].is_active);
+        assert_eq!((panes[0].width, panes[0].height), (39, 24));
+        assert_eq!(
+            (panes[1].left, panes[1].width, panes[1].height),
+            (40, 40, 11)
+        );
+        assert_eq!(
+            (panes[2].top, panes[2].width, panes[2].height),
+            (12, 40, 12)
+        );
+
+        tab.arrange_panes(PaneArrangement::Tiled).unwrap();
+        let panes = tab.iter_panes();
+        assert!(panes[0].is_active);
+        assert_eq!((panes[0].width, panes[0].height), (39, 11));
+        assert_eq!(
+            (panes[1].left, panes[1].width, panes[1].height),
+            (40, 40, 11)
+        );
+        assert_eq!(
+            (panes[2].top, panes[2].width, panes[2].height),
+            (12, 80, 12)
+        );
+    }
+
+    #[test]
+    fn most_recently_used_panes() {
+        let size = PtySize {
+            rows: 24,
+            cols: 80,
+            pixel_width: 800,
+            pixel_height: 600,
+        };
+
+        let tab = Tab::new(&size);
+        tab.assign_pane(&FakePane::new(1, size));
+        for pane_id in 2..=3 {
+            let index = tab.get_active_idx();
+            let split = tab
+                .compute_split_size(index, SplitDirection::Horizontal.into())
+                .unwrap();
+            tab.split_and_insert(
+                index,
+                SplitDirection::Horizontal.into(),
+                FakePane::new(pane_id, split.second),
+            )
+            .unwrap();
+        }
+
+        let order = |tab: &Tab| -> Vec<PaneId> {
+            tab.iter_panes_by_recent_use()
+                .iter()
+                .map(|p| p.pane.pane_id())
+                .collect()
+        };
+        assert_eq!(order(&tab), vec![3, 2, 1]);
+
+        tab.set_active_idx(0);
+        assert_eq!(order(&tab), vec![1, 3, 2]);
+
+        // Cycling through the list leaves the order alone until
+        // the selection is committed
+        tab.set_active_idx_without_saving(2);
+        assert_eq!(order(&tab), vec![3, 2, 1]);
+        tab.mark_used(1);
+        assert_eq!(order(&tab), vec![3, 1, 2]);
+    }
+
+    #[test]
+    fn floating_pane() {
+        let size = PtySize {
+            rows: 24,
+            cols: 80,
+            pixel_width: 800,
+            pixel_height: 600,
+        };
+
+        let tab = Tab::new(&size);
+        tab.assign_pane(&FakePane::new(1, size));
+        assert!(tab.floating_pane_position().is_none());
+
+        tab.set_floating_pane(&FakePane::new(2, size));
+        assert!(tab.contains_pane(2));
+        assert_eq!(tab.iter_panes().len(), 1);
+
+        let pos = tab.floating_pane_position().unwrap();
+        assert_eq!(pos.pane.pane_id(), 2);
+        assert_eq!(pos.index, 1);
+        assert_eq!((pos.left, pos.top), (8, 2));
+        assert_eq!((pos.width, pos.height), (64, 19));
+        assert_eq!((pos.pixel_width, pos.pixel_height), (640, 475));
+
+        tab.resize(PtySize {
+            rows: 10,
+            cols: 20,
+            pixel_width: 200,
+            pixel_height: 250,
+        });
+        let pos = tab.floating_pane_position().unwrap();
+        assert_eq!((pos.left, pos.top), (2, 1));
+        assert_eq!((pos.width, pos.height), (16, 8));
+    }
+
     #[test]
     fn tab_splitting() {
         let size = PtySize {
@@ -1611,11 +2258,11 @@ mod test {
         assert_eq!(24, panes[0].height);
 
         assert!(tab
-            .compute_split_size(1, SplitDirection::Horizontal)
+            .compute_split_size(1, SplitDirection::Horizontal.into())
             .is_none());
 
         let horz_size = tab
-            .compute_split_size(0, SplitDirection::Horizontal)
+            .compute_split_size(0, SplitDirection::Horizontal.into())
             .unwrap();
         assert_eq!(
             horz_size,
@@ -1636,7 +2283,9 @@ mod test {
             }
         );
 
-        let vert_size = tab.compute_split_size(0, SplitDirection::Vertical).unwrap();
+        let vert_size = tab
+            .compute_split_size(0, SplitDirection::Vertical.into())
+            .unwrap();
         assert_eq!(
             vert_size,
             SplitDirectionAndSize {
@@ -1659,7 +2308,7 @@ mod test {
         let new_index = tab
             .split_and_insert(
                 0,
-                SplitDirection::Horizontal,
+                SplitDirection::Horizontal.into(),
                 FakePane::new(2, horz_size.second),
             )
             .unwrap();
@@ -1688,11 +2337,13 @@ mod test {
         assert_eq!(600, panes[1].pixel_height);
         assert_eq!(2, panes[1].pane.pane_id());
 
-        let vert_size = tab.compute_split_size(0, SplitDirection::Vertical).unwrap();
+        let vert_size = tab
+            .compute_split_size(0, SplitDirection::Vertical.into())
+            .unwrap();
         let new_index = tab
             .split_and_insert(
                 0,
-                SplitDirection::Vertical,
+                SplitDirection::Vertical.into(),
                 FakePane::new(3, vert_size.second),
             )
             .unwrap();
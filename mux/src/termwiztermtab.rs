@@ -6,7 +6,7 @@
 use crate::domain::{alloc_domain_id, Domain, DomainId, DomainState};
 use crate::pane::{alloc_pane_id, Pane, PaneId};
 use crate::renderable::*;
-use crate::tab::{SplitDirection, Tab, TabId};
+use crate::tab::{SplitRequest, Tab, TabId};
 use crate::window::WindowId;
 use crate::Mux;
 use anyhow::bail;
@@ -63,7 +63,7 @@ impl Domain for TermWizTerminalDomain {
         _command_dir: Option<String>,
         _tab: TabId,
         _pane_id: PaneId,
-        _split_direction: SplitDirection,
+        _request: SplitRequest,
     ) -> anyhow::Result<Rc<dyn Pane>> {
         bail!("cannot spawn panes in a TermWizTerminalPane");
     }
@@ -1,6 +1,6 @@
 use crate::domain::{alloc_domain_id, Domain, DomainId, DomainState};
 use crate::pane::{Pane, PaneId};
-use crate::tab::{SplitDirection, Tab, TabId};
+use crate::tab::{SplitRequest, Tab, TabId};
 use crate::window::WindowId;
 use crate::Mux;
 use anyhow::anyhow;
@@ -220,7 +220,7 @@ impl Domain for TmuxDomain {
         _command_dir: Option<String>,
         _tab: TabId,
         _pane_id: PaneId,
-        _direction: SplitDirection,
+        _request: SplitRequest,
     ) -> anyhow::Result<Rc<dyn Pane>> {
         anyhow::bail!("split_pane not yet implemented for TmuxDomain");
     }
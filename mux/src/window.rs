@@ -10,9 +10,12 @@ pub struct Window {
     id: WindowId,
     tabs: Vec<Rc<Tab>>,
     active: usize,
-    last_active: Option<TabId>,
+    /// The tabs that were previously active, most recently used first
+    mru: Vec<TabId>,
     clipboard: Option<Arc<dyn Clipboard>>,
     invalidated: bool,
+    /// A name given to the window, which is shown in its title
+    name: Option<String>,
 }
 
 impl Window {
@@ -21,9 +24,10 @@ impl Window {
             id: WIN_ID.fetch_add(1, ::std::sync::atomic::Ordering::Relaxed),
             tabs: vec![],
             active: 0,
-            last_active: None,
+            mru: vec![],
             clipboard: None,
             invalidated: false,
+            name: None,
         }
     }
 
@@ -35,6 +39,15 @@ impl Window {
         self.id
     }
 
+    pub fn set_name(&mut self, name: Option<String>) {
+        self.name = name;
+        self.invalidated = true;
+    }
+
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
     fn check_that_tab_isnt_already_in_window(&self, tab: &Rc<Tab>) {
         for t in &self.tabs {
             assert_ne!(t.tab_id(), tab.tab_id(), "tab already added to this window");
@@ -99,6 +112,7 @@ impl Window {
     }
 
     pub fn remove_by_id(&mut self, id: TabId) -> bool {
+        self.mru.retain(|&tab_id| tab_id != id);
         if let Some(idx) = self.idx_by_id(id) {
             self.tabs.remove(idx);
             let len = self.tabs.len();
@@ -126,17 +140,45 @@ impl Window {
         self.active
     }
 
+    /// Moves the active tab to the front of the most recently used list
     pub fn save_last_active(&mut self) {
-        self.last_active = self.get_by_idx(self.active).map(|tab| tab.tab_id());
+        if let Some(tab_id) = self.get_by_idx(self.active).map(|tab| tab.tab_id()) {
+            self.mark_used(tab_id);
+        }
     }
 
-    #[inline]
+    /// Moves `tab_id` to the front of the most recently used list
+    pub fn mark_used(&mut self, tab_id: TabId) {
+        self.mru.retain(|&id| id != tab_id);
+        self.mru.insert(0, tab_id);
+    }
+
+    /// Returns the positions of the tabs in most recently used order,
+    /// starting with the active tab.  Tabs that have never been active
+    /// follow in the order that they appear in the window.
+    pub fn iter_by_recent_use(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.tabs.len()).collect();
+        order.sort_by_key(|&idx| {
+            if idx == self.active {
+                0
+            } else {
+                let tab_id = self.tabs[idx].tab_id();
+                self.mru
+                    .iter()
+                    .position(|&id| id == tab_id)
+                    .map_or(usize::max_value(), |pos| pos + 1)
+            }
+        });
+        order
+    }
+
+    /// Returns the position of the most recently used tab, other than
+    /// the active tab, that is still in this window
     pub fn get_last_active_idx(&self) -> Option<usize> {
-        if let Some(tab_id) = self.last_active {
-            self.idx_by_id(tab_id)
-        } else {
-            None
-        }
+        self.mru
+            .iter()
+            .filter_map(|&tab_id| self.idx_by_id(tab_id))
+            .find(|&idx| idx != self.active)
     }
 
     /// If `idx` is different from the current active tab,
@@ -207,3 +249,38 @@ impl Window {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use portable_pty::PtySize;
+
+    #[test]
+    fn most_recently_used() {
+        let size = PtySize::default();
+        let tabs: Vec<Rc<Tab>> = (0..4).map(|_| Rc::new(Tab::new(&size))).collect();
+        let mut window = Window::new();
+        for tab in &tabs {
+            window.push(tab);
+        }
+        assert_eq!(window.get_last_active_idx(), None);
+
+        window.save_and_then_set_active(2);
+        window.save_and_then_set_active(3);
+        assert_eq!(window.get_last_active_idx(), Some(2));
+
+        // Toggling between the two most recent tabs
+        window.save_and_then_set_active(2);
+        assert_eq!(window.get_last_active_idx(), Some(3));
+
+        // Closing the previous tab falls back to the one before it
+        window.remove_by_id(tabs[3].tab_id());
+        assert_eq!(window.get_last_active_idx(), Some(0));
+        assert_eq!(window.iter_by_recent_use(), vec![2, 0, 1]);
+
+        window.set_active_without_saving(1);
+        assert_eq!(window.iter_by_recent_use(), vec![1, 2, 0]);
+        window.mark_used(tabs[0].tab_id());
+        assert_eq!(window.iter_by_recent_use(), vec![1, 0, 2]);
+    }
+}
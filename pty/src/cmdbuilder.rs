@@ -6,9 +6,30 @@ use std::ffi::{OsStr, OsString};
 #[cfg(windows)]
 use std::os::windows::ffi::OsStrExt;
 
+/// How the default program, the user's shell, is told that it is
+/// a login shell.  This only has an effect on unix systems.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub enum LoginShell {
+    /// Pass `-l` to the shell.  bash, zsh, fish and tcsh understand
+    /// this, but some other shells do not.
+    Flag,
+    /// Prefix argv[0] with a `-`, in the same way as `login(1)`.
+    /// This is understood by all common shells.
+    Argv0,
+    /// Run the shell as an ordinary, non-login, shell
+    Never,
+}
+
+impl Default for LoginShell {
+    fn default() -> Self {
+        Self::Flag
+    }
+}
+
 /// `CommandBuilder` is used to prepare a command to be spawned into a pty.
 /// The interface is intentionally similar to that of `std::process::Command`.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
 pub struct CommandBuilder {
     args: Vec<OsString>,
@@ -16,6 +37,11 @@ pub struct CommandBuilder {
     cwd: Option<OsString>,
     #[cfg(unix)]
     pub(crate) umask: Option<libc::mode_t>,
+    /// This is a property of the host that the command is spawned on,
+    /// so it isn't sent along with the command
+    #[cfg(unix)]
+    #[cfg_attr(feature = "serde_support", serde(skip))]
+    login_shell: LoginShell,
 }
 
 impl CommandBuilder {
@@ -28,6 +54,8 @@ impl CommandBuilder {
             cwd: None,
             #[cfg(unix)]
             umask: None,
+            #[cfg(unix)]
+            login_shell: LoginShell::default(),
         }
     }
 
@@ -39,6 +67,8 @@ impl CommandBuilder {
             cwd: None,
             #[cfg(unix)]
             umask: None,
+            #[cfg(unix)]
+            login_shell: LoginShell::default(),
         }
     }
 
@@ -51,6 +81,8 @@ impl CommandBuilder {
             cwd: None,
             #[cfg(unix)]
             umask: None,
+            #[cfg(unix)]
+            login_shell: LoginShell::default(),
         }
     }
 
@@ -89,6 +121,20 @@ impl CommandBuilder {
             .push((key.as_ref().to_owned(), val.as_ref().to_owned()));
     }
 
+    /// Returns the value that will be set for the environment variable
+    /// `key`, if it has been set on this builder
+    pub fn get_env<K>(&self, key: K) -> Option<&OsStr>
+    where
+        K: AsRef<OsStr>,
+    {
+        let key = key.as_ref();
+        self.envs
+            .iter()
+            .rev()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_os_str())
+    }
+
     pub fn cwd<D>(&mut self, dir: D)
     where
         D: AsRef<OsStr>,
@@ -129,16 +175,31 @@ impl CommandBuilder {
         self.umask = mask;
     }
 
+    /// Specify how the shell is run when this builder was created
+    /// via `new_default_prog`
+    pub fn login_shell(&mut self, login_shell: LoginShell) {
+        self.login_shell = login_shell;
+    }
+
     /// Convert the CommandBuilder to a `std::process::Command` instance.
     pub(crate) fn as_command(&self) -> anyhow::Result<std::process::Command> {
         let mut cmd = if self.is_default_prog() {
-            let mut cmd = std::process::Command::new(&Self::get_shell()?);
-            // Run the shell as a login shell.  This is a little shaky; it just
-            // happens to be the case that bash, zsh, fish and tcsh use -l
-            // to indicate that they are login shells.  Ideally we'd just
-            // tell the command builder to prefix argv[0] with a `-`, but
-            // Rust doesn't support that.
-            cmd.arg("-l");
+            let shell = Self::get_shell()?;
+            let mut cmd = std::process::Command::new(&shell);
+            match self.login_shell {
+                LoginShell::Flag => {
+                    cmd.arg("-l");
+                }
+                LoginShell::Argv0 => {
+                    use std::os::unix::process::CommandExt;
+                    let name = std::path::Path::new(&shell)
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_else(|| shell.clone());
+                    cmd.arg0(format!("-{}", name));
+                }
+                LoginShell::Never => {}
+            }
             let home = Self::get_home_dir()?;
             let dir: &OsStr = self
                 .cwd
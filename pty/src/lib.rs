@@ -51,7 +51,7 @@ use serde_derive::*;
 use std::io::Result as IoResult;
 
 pub mod cmdbuilder;
-pub use cmdbuilder::CommandBuilder;
+pub use cmdbuilder::{CommandBuilder, LoginShell};
 
 #[cfg(unix)]
 pub mod unix;
@@ -138,30 +138,90 @@ pub trait SlavePty {
 }
 
 /// Represents the exit status of a child process.
-/// This is rather anemic in the current version of this crate,
-/// holding only an indicator of success or failure.
 #[derive(Debug, Clone)]
 pub struct ExitStatus {
-    successful: bool,
+    code: u32,
+    signal: Option<String>,
 }
 
 impl ExitStatus {
     /// Construct an ExitStatus from a process return code
     pub fn with_exit_code(code: u32) -> Self {
+        Self { code, signal: None }
+    }
+
+    /// Construct an ExitStatus for a process that was terminated
+    /// by the named signal, such as `SIGTERM`
+    pub fn with_signal(signal: &str) -> Self {
         Self {
-            successful: code == 0,
+            code: 1,
+            signal: Some(signal.to_string()),
         }
     }
 
     pub fn success(&self) -> bool {
-        self.successful
+        self.signal.is_none() && self.code == 0
+    }
+
+    /// Returns the exit code of the process.  This is non-zero
+    /// if the process was terminated by a signal.
+    pub fn exit_code(&self) -> u32 {
+        self.code
+    }
+
+    /// Returns the name of the signal that terminated the process, if any
+    pub fn signal(&self) -> Option<&str> {
+        self.signal.as_deref()
+    }
+}
+
+impl std::fmt::Display for ExitStatus {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match &self.signal {
+            Some(signal) => write!(fmt, "Terminated by {}", signal),
+            None => write!(fmt, "Exited with code {}", self.code),
+        }
     }
 }
 
+#[cfg(unix)]
+fn signal_name(signal: i32) -> String {
+    let name = match signal {
+        libc::SIGHUP => "SIGHUP",
+        libc::SIGINT => "SIGINT",
+        libc::SIGQUIT => "SIGQUIT",
+        libc::SIGILL => "SIGILL",
+        libc::SIGTRAP => "SIGTRAP",
+        libc::SIGABRT => "SIGABRT",
+        libc::SIGBUS => "SIGBUS",
+        libc::SIGFPE => "SIGFPE",
+        libc::SIGKILL => "SIGKILL",
+        libc::SIGUSR1 => "SIGUSR1",
+        libc::SIGSEGV => "SIGSEGV",
+        libc::SIGUSR2 => "SIGUSR2",
+        libc::SIGPIPE => "SIGPIPE",
+        libc::SIGALRM => "SIGALRM",
+        libc::SIGTERM => "SIGTERM",
+        libc::SIGXCPU => "SIGXCPU",
+        libc::SIGXFSZ => "SIGXFSZ",
+        signal => return format!("signal {}", signal),
+    };
+    name.to_string()
+}
+
 impl From<std::process::ExitStatus> for ExitStatus {
     fn from(status: std::process::ExitStatus) -> ExitStatus {
-        ExitStatus {
-            successful: status.success(),
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+            if let Some(signal) = status.signal() {
+                return ExitStatus::with_signal(&signal_name(signal));
+            }
+        }
+
+        match status.code() {
+            Some(code) => ExitStatus::with_exit_code(code as u32),
+            None => ExitStatus::with_exit_code(1),
         }
     }
 }
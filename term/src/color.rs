@@ -4,7 +4,7 @@ use std::fmt;
 use std::result::Result;
 pub use termwiz::color::{AnsiColor, ColorAttribute, RgbColor, RgbaTuple};
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct Palette256(pub [RgbColor; 256]);
 
 impl std::iter::FromIterator<RgbColor> for Palette256 {
@@ -17,7 +17,7 @@ impl std::iter::FromIterator<RgbColor> for Palette256 {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ColorPalette {
     pub colors: Palette256,
     pub foreground: RgbColor,
@@ -25,10 +25,14 @@ pub struct ColorPalette {
     pub cursor_fg: RgbColor,
     pub cursor_bg: RgbColor,
     pub cursor_border: RgbColor,
-    pub selection_fg: RgbColor,
+    /// When None, selected text keeps its own color
+    pub selection_fg: Option<RgbColor>,
     pub selection_bg: RgbColor,
     pub scrollbar_thumb: RgbColor,
     pub split: RgbColor,
+    pub active_split: RgbColor,
+    pub invisible_character: RgbColor,
+    pub visual_bell: RgbColor,
 }
 
 /// Adjust the color to make it appear disabled.
@@ -86,10 +90,13 @@ impl ColorPalette {
             cursor_fg: grey_out(self.cursor_fg),
             cursor_bg: grey_out(self.cursor_bg),
             cursor_border: grey_out(self.cursor_border),
-            selection_fg: grey_out(self.selection_fg),
+            selection_fg: self.selection_fg.map(grey_out),
             selection_bg: grey_out(self.selection_bg),
             scrollbar_thumb: grey_out(self.scrollbar_thumb),
             split: grey_out(self.split),
+            active_split: grey_out(self.active_split),
+            invisible_character: grey_out(self.invisible_character),
+            visual_bell: grey_out(self.visual_bell),
         }
     }
 }
@@ -242,11 +249,12 @@ impl ColorPalette {
         let cursor_border = RgbColor::new(0x52, 0xad, 0x70);
         let cursor_fg = colors[AnsiColor::Black as usize];
 
-        let selection_fg = colors[AnsiColor::Black as usize];
+        let selection_fg = Some(colors[AnsiColor::Black as usize]);
         let selection_bg = RgbColor::new(0xff, 0xfa, 0xcd);
 
         let scrollbar_thumb = RgbColor::new(0x22, 0x22, 0x22);
         let split = RgbColor::new(0x44, 0x44, 0x44);
+        let invisible_character = RgbColor::new(0x80, 0x30, 0x70);
 
         ColorPalette {
             colors: Palette256(colors),
@@ -259,6 +267,9 @@ impl ColorPalette {
             selection_bg,
             scrollbar_thumb,
             split,
+            active_split: split,
+            invisible_character,
+            visual_bell: foreground,
         }
     }
 }
@@ -41,6 +41,14 @@ pub trait TerminalConfiguration: std::fmt::Debug {
     /// defines the initial palette.
     fn color_palette(&self) -> ColorPalette;
 
+    /// Returns the palette for the named color scheme, if known.
+    /// This is used to allow the application running in the terminal
+    /// to select an entirely different scheme for the terminal instance
+    /// at runtime by setting the `WEZTERM_COLOR_SCHEME` user var.
+    fn color_palette_for_scheme(&self, _scheme_name: &str) -> Option<ColorPalette> {
+        None
+    }
+
     /// Return true if a resize operation should consider rows that have
     /// made it to scrollback as being immutable.
     /// When immutable, the resize operation will pad out the screen height
@@ -22,8 +22,10 @@ pub struct Screen {
 
     /// Whenever we scroll a line off the top of the scrollback, we
     /// increment this.  We use this offset to translate between
-    /// PhysRowIndex and StableRowIndex.
-    stable_row_index_offset: usize,
+    /// PhysRowIndex and StableRowIndex.  Rewrapping on resize adjusts
+    /// it to keep the cursor row's StableRowIndex, which can make it
+    /// negative.
+    stable_row_index_offset: isize,
 
     /// config so we can access Maximum number of lines of scrollback
     config: Arc<dyn TerminalConfiguration>,
@@ -90,6 +92,7 @@ impl Screen {
         let mut logical_line: Option<Line> = None;
         let mut logical_cursor_x: Option<usize> = None;
         let mut adjusted_cursor = (cursor_y, cursor_y);
+        let cursor_stable_row = self.phys_to_stable_row_index(cursor_y);
 
         for (phys_idx, mut line) in self.lines.drain(..).enumerate() {
             line.invalidate_implicit_hyperlinks();
@@ -149,6 +152,24 @@ impl Screen {
             self.lines.pop_back();
         }
 
+        // If there are still too many lines, discard the excess from
+        // the top of the scrollback, just as scrolling would
+        let excess = self.lines.len().saturating_sub(capacity);
+        if excess > 0 {
+            self.lines.drain(0..excess);
+            adjusted_cursor.1 = adjusted_cursor.1.saturating_sub(excess);
+        }
+
+        // Rewrapping changes the number of physical lines that precede
+        // any given line, so adjust the offset such that the cursor row
+        // retains its StableRowIndex.  That keeps the stable rows in the
+        // vicinity of the cursor, and the viewport and selections that
+        // reference them, pointing at the same content after the resize.
+        // The offset isn't clamped: when rewrapping adds rows above the
+        // cursor near the top of the scrollback, the top row may have a
+        // negative index, and consumers clamp to `scrollback_top` instead.
+        self.stable_row_index_offset = cursor_stable_row - adjusted_cursor.1 as StableRowIndex;
+
         adjusted_cursor
     }
 
@@ -388,12 +409,12 @@ impl Screen {
 
     #[inline]
     pub fn phys_to_stable_row_index(&self, phys: PhysRowIndex) -> StableRowIndex {
-        (phys + self.stable_row_index_offset) as StableRowIndex
+        phys as StableRowIndex + self.stable_row_index_offset
     }
 
     #[inline]
     pub fn stable_row_to_phys(&self, stable: StableRowIndex) -> Option<PhysRowIndex> {
-        let idx = stable - self.stable_row_index_offset as isize;
+        let idx = stable - self.stable_row_index_offset;
         if idx < 0 || idx >= self.lines.len() as isize {
             // Index is no longer valid
             None
@@ -557,7 +578,7 @@ impl Screen {
         }
 
         if remove_idx == 0 {
-            self.stable_row_index_offset += lines_removed;
+            self.stable_row_index_offset += lines_removed as isize;
         }
 
         if scroll_region.end as usize == self.physical_rows {
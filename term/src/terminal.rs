@@ -47,6 +47,26 @@ pub enum Alert {
         /// window/tab/pane that generated it
         focus: bool,
     },
+    /// A command, delimited by OSC 133 semantic prompt escapes,
+    /// has finished running
+    CommandComplete {
+        /// The text of the command, if it could be determined
+        command: Option<String>,
+        /// The exit status reported by the shell
+        status: i32,
+        /// How long the command took to run
+        duration: std::time::Duration,
+    },
+    /// A program has used OSC 52 to ask for the contents of the
+    /// clipboard.  Since that could expose sensitive data to remote
+    /// programs, the terminal doesn't respond by itself; the embedding
+    /// application decides whether to allow it, and if so, writes the
+    /// OSC 52 response to the pane.
+    ClipboardQuery(ClipboardSelection),
+    /// A program has set the `WEZTERM_BACKGROUND` user var to ask for
+    /// the image at the given path to be used as the window background.
+    /// `None` reverts to the background from the configuration.
+    SetBackgroundImage(Option<String>),
 }
 
 pub trait AlertHandler {
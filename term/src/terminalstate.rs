@@ -3,6 +3,7 @@
 #![cfg_attr(feature = "cargo-clippy", allow(clippy::range_plus_one))]
 use super::*;
 use crate::color::{ColorPalette, RgbColor};
+use crate::terminfo::Capability;
 use anyhow::bail;
 use image::imageops::FilterType;
 use image::ImageFormat;
@@ -13,6 +14,7 @@ use std::collections::HashMap;
 use std::fmt::Write;
 use std::sync::mpsc::{channel, Sender};
 use std::sync::Arc;
+use std::time::Instant;
 use termwiz::escape::csi::{
     Cursor, CursorStyle, DecPrivateMode, DecPrivateModeCode, Device, Edit, EraseInDisplay,
     EraseInLine, Mode, Sgr, TabulationClear, TerminalMode, TerminalModeCode, Window, XtSmGraphics,
@@ -97,6 +99,26 @@ impl TabStop {
     }
 }
 
+/// The maximum depth of the title and SGR stacks; this is the
+/// same limit that xterm uses.  Pushes beyond this are ignored.
+const MAX_SAVED_STACK_DEPTH: usize = 10;
+
+/// An entry in the title stack (CSI 22 t).  The fields are None
+/// for the titles that were not selected by the push.
+#[derive(Debug, Clone)]
+struct SavedTitle {
+    title: Option<String>,
+    icon_title: Option<Option<String>>,
+}
+
+/// An entry in the SGR stack (XTPUSHSGR)
+#[derive(Debug, Clone)]
+struct SavedSgr {
+    pen: CellAttributes,
+    /// The attributes to restore; all of them if empty
+    selection: Vec<i64>,
+}
+
 #[derive(Debug, Clone)]
 struct SavedCursor {
     position: CursorPosition,
@@ -268,6 +290,9 @@ pub struct TerminalState {
     /// Movement events enabled
     any_event_mouse: bool,
     focus_tracking: bool,
+    /// When set, wheel events in the alternate screen are sent as
+    /// cursor keys if mouse reporting is not enabled
+    alternate_scroll: bool,
     /// SGR style mouse tracking and reporting is enabled
     sgr_mouse: bool,
     mouse_tracking: bool,
@@ -283,6 +308,10 @@ pub struct TerminalState {
     title: String,
     /// The icon title string (OSC 1)
     icon_title: Option<String>,
+    /// Titles saved by CSI 22 t
+    title_stack: Vec<SavedTitle>,
+    /// Graphic renditions saved by XTPUSHSGR
+    sgr_stack: Vec<SavedSgr>,
 
     palette: Option<ColorPalette>,
 
@@ -292,6 +321,9 @@ pub struct TerminalState {
     clipboard: Option<Arc<dyn Clipboard>>,
     device_control_handler: Option<Box<dyn DeviceControlHandler>>,
     alert_handler: Option<Box<dyn AlertHandler>>,
+    /// When the current command started running, and its text,
+    /// as marked up by OSC 133 semantic prompt escapes
+    command_started: Option<(Instant, Option<String>)>,
 
     current_dir: Option<Url>,
 
@@ -468,6 +500,7 @@ impl TerminalState {
             application_keypad: false,
             bracketed_paste: false,
             focus_tracking: false,
+            alternate_scroll: true,
             sgr_mouse: false,
             any_event_mouse: false,
             button_event_mouse: false,
@@ -478,12 +511,15 @@ impl TerminalState {
             tabs: TabStop::new(size.physical_cols, 8),
             title: "wezterm".to_string(),
             icon_title: None,
+            title_stack: vec![],
+            sgr_stack: vec![],
             palette: None,
             pixel_height: size.pixel_height,
             pixel_width: size.pixel_width,
             clipboard: None,
             device_control_handler: None,
             alert_handler: None,
+            command_started: None,
             current_dir: None,
             term_program: term_program.to_string(),
             term_version: term_version.to_string(),
@@ -504,6 +540,12 @@ impl TerminalState {
         self.alert_handler.replace(handler);
     }
 
+    /// Replaces the writer that sends data to the input of the pty,
+    /// such as when the process in the pty has been restarted
+    pub fn set_writer(&mut self, writer: Box<dyn std::io::Write + Send>) {
+        self.writer = Box::new(std::io::BufWriter::new(ThreadedWriter::new(writer)));
+    }
+
     /// Returns the title text associated with the terminal session.
     /// The title can be changed by the application using a number
     /// of escape sequences:
@@ -556,6 +598,26 @@ impl TerminalState {
         self.palette.as_mut().unwrap()
     }
 
+    /// Replace the palette for this terminal instance with that of the
+    /// named color scheme.  An empty name reverts to the palette from
+    /// the configuration.  Any prior dynamic color changes are discarded.
+    pub fn set_color_scheme(&mut self, scheme_name: &str) {
+        if scheme_name.is_empty() {
+            self.palette.take();
+        } else {
+            match self.config.color_palette_for_scheme(scheme_name) {
+                Some(palette) => {
+                    self.palette.replace(palette);
+                }
+                None => {
+                    log::warn!("WEZTERM_COLOR_SCHEME: unknown scheme {}", scheme_name);
+                    return;
+                }
+            }
+        }
+        self.make_all_lines_dirty();
+    }
+
     /// Returns a reference to the active screen (either the primary or
     /// the alternate screen).
     pub fn screen(&self) -> &Screen {
@@ -640,7 +702,7 @@ impl TerminalState {
                 Self::legacy_mouse_coord(event.y),
             )?;
             self.writer.flush()?;
-        } else if self.screen.is_alt_screen_active() {
+        } else if self.screen.is_alt_screen_active() && self.alternate_scroll {
             // Send cursor keys instead (equivalent to xterm's alternateScroll mode)
             for _ in 0..self.config.alternate_buffer_wheel_scroll_speed() {
                 self.key_down(
@@ -2045,6 +2107,15 @@ impl TerminalState {
                 self.focus_tracking = false;
             }
 
+            Mode::SetDecPrivateMode(DecPrivateMode::Code(DecPrivateModeCode::AlternateScroll)) => {
+                self.alternate_scroll = true;
+            }
+            Mode::ResetDecPrivateMode(DecPrivateMode::Code(
+                DecPrivateModeCode::AlternateScroll,
+            )) => {
+                self.alternate_scroll = false;
+            }
+
             Mode::SetDecPrivateMode(DecPrivateMode::Code(DecPrivateModeCode::SGRMouse)) => {
                 self.sgr_mouse = true;
             }
@@ -2208,17 +2279,46 @@ impl TerminalState {
                 // up to the user!
             }
             Window::Iconify | Window::DeIconify => {}
-            Window::PopIconAndWindowTitle
-            | Window::PopWindowTitle
-            | Window::PopIconTitle
-            | Window::PushIconAndWindowTitle
-            | Window::PushIconTitle
-            | Window::PushWindowTitle => {}
+            Window::PushIconAndWindowTitle => self.push_title(true, true),
+            Window::PushIconTitle => self.push_title(true, false),
+            Window::PushWindowTitle => self.push_title(false, true),
+            Window::PopIconAndWindowTitle => self.pop_title(true, true),
+            Window::PopIconTitle => self.pop_title(true, false),
+            Window::PopWindowTitle => self.pop_title(false, true),
 
             _ => log::warn!("unhandled Window CSI {:?}", window),
         }
     }
 
+    fn push_title(&mut self, icon: bool, window: bool) {
+        if self.title_stack.len() >= MAX_SAVED_STACK_DEPTH {
+            return;
+        }
+        self.title_stack.push(SavedTitle {
+            title: if window {
+                Some(self.title.clone())
+            } else {
+                None
+            },
+            icon_title: if icon {
+                Some(self.icon_title.clone())
+            } else {
+                None
+            },
+        });
+    }
+
+    fn pop_title(&mut self, icon: bool, window: bool) {
+        if let Some(saved) = self.title_stack.pop() {
+            if let (true, Some(title)) = (window, saved.title) {
+                self.title = title;
+            }
+            if let (true, Some(icon_title)) = (icon, saved.icon_title) {
+                self.icon_title = icon_title;
+            }
+        }
+    }
+
     fn erase_in_display(&mut self, erase: EraseInDisplay) {
         let cy = self.cursor.y;
         let pen = self.pen.clone_sgr_only();
@@ -2390,6 +2490,115 @@ impl TerminalState {
                 self.cursor.x = x;
                 self.cursor.y = y;
             }
+            Edit::CopyRectangularArea {
+                top,
+                left,
+                bottom,
+                right,
+                dest_top,
+                dest_left,
+                ..
+            } => {
+                let (rows, cols) = match self.rectangular_area(top, left, bottom, right) {
+                    Some(area) => area,
+                    None => return,
+                };
+                let far_edge = OneBased::new(u32::max_value());
+                let (dest_rows, dest_cols) =
+                    match self.rectangular_area(dest_top, dest_left, far_edge, far_edge) {
+                        Some(area) => area,
+                        None => return,
+                    };
+
+                let screen = self.screen_mut();
+                // Take a copy of the source first, so that the result is
+                // correct when the source and destination overlap.
+                let mut source = vec![];
+                for y in rows {
+                    let line_idx = screen.phys_row(y);
+                    let line = screen.line_mut(line_idx);
+                    let cells: Vec<Cell> = cols
+                        .clone()
+                        .map(|x| line.cells().get(x).cloned().unwrap_or_default())
+                        .collect();
+                    source.push(cells);
+                }
+                for (y, cells) in dest_rows.zip(source) {
+                    for (x, cell) in dest_cols.clone().zip(cells) {
+                        screen.set_cell(x, y, &cell);
+                    }
+                }
+            }
+            Edit::FillRectangularArea {
+                character,
+                top,
+                left,
+                bottom,
+                right,
+            } => {
+                // DEC only permits printable characters from GL or GR
+                let code = character as u32;
+                if !((32..=126).contains(&code) || (160..=255).contains(&code)) {
+                    return;
+                }
+                if let Some((rows, cols)) = self.rectangular_area(top, left, bottom, right) {
+                    let cell = Cell::new(character, self.pen.clone_sgr_only());
+                    let screen = self.screen_mut();
+                    for y in rows {
+                        for x in cols.clone() {
+                            screen.set_cell(x, y, &cell);
+                        }
+                    }
+                }
+            }
+            Edit::EraseRectangularArea {
+                top,
+                left,
+                bottom,
+                right,
+            } => {
+                if let Some((rows, cols)) = self.rectangular_area(top, left, bottom, right) {
+                    let pen = self.pen.clone_sgr_only();
+                    let screen = self.screen_mut();
+                    for y in rows {
+                        screen.clear_line(y, cols.clone(), &pen);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Resolves the 1-based, inclusive bounds of a rectangular area
+    /// operation into ranges of visible rows and columns.  The bounds are
+    /// relative to the margins when origin mode is enabled, and are clipped
+    /// to the screen.  Returns None if the resulting area is empty.
+    fn rectangular_area(
+        &self,
+        top: OneBased,
+        left: OneBased,
+        bottom: OneBased,
+        right: OneBased,
+    ) -> Option<(Range<VisibleRowIndex>, Range<usize>)> {
+        let (y_origin, x_origin) = if self.dec_origin_mode {
+            (
+                self.top_and_bottom_margins.start,
+                self.left_and_right_margins.start,
+            )
+        } else {
+            (0, 0)
+        };
+        let rows = self.screen().physical_rows as VisibleRowIndex;
+        let cols = self.screen().physical_cols;
+
+        let top = (y_origin + VisibleRowIndex::from(top.as_zero_based())).min(rows);
+        let bottom = (y_origin + VisibleRowIndex::from(bottom.as_zero_based())).min(rows - 1) + 1;
+        let left = (x_origin + left.as_zero_based() as usize).min(cols);
+        let right = (x_origin + right.as_zero_based() as usize).min(cols - 1) + 1;
+
+        if top >= bottom || left >= right {
+            None
+        } else {
+            Some((top..bottom, left..right))
         }
     }
 
@@ -2398,9 +2607,8 @@ impl TerminalState {
         // The terminal only recognizes this control function if vertical split
         // screen mode (DECLRMM) is set.
         if self.left_and_right_margin_mode {
-            let rows = self.screen().physical_rows as u32;
             let cols = self.screen().physical_cols as u32;
-            let left = left.as_zero_based().min(rows - 1).max(0) as usize;
+            let left = left.as_zero_based().min(cols - 1).max(0) as usize;
             let right = right.as_zero_based().min(cols - 1).max(0) as usize;
 
             // The value of the left margin (Pl) must be less than the right margin (Pr).
@@ -2769,6 +2977,9 @@ impl TerminalState {
             Sgr::Overline(overline) => {
                 self.pen.set_overline(overline);
             }
+            Sgr::VerticalAlign(align) => {
+                self.pen.set_vertical_align(align);
+            }
             Sgr::Blink(blink) => {
                 self.pen.set_blink(blink);
             }
@@ -2794,6 +3005,98 @@ impl TerminalState {
                 self.pen.set_underline_color(col);
             }
             Sgr::Font(_) => {}
+            Sgr::Push(selection) => {
+                if self.sgr_stack.len() < MAX_SAVED_STACK_DEPTH {
+                    self.sgr_stack.push(SavedSgr {
+                        pen: self.pen.clone(),
+                        selection,
+                    });
+                }
+            }
+            Sgr::Pop => {
+                if let Some(saved) = self.sgr_stack.pop() {
+                    self.restore_sgr(saved);
+                }
+            }
+        }
+    }
+
+    fn restore_sgr(&mut self, saved: SavedSgr) {
+        let SavedSgr { pen, selection } = saved;
+        if selection.is_empty() {
+            let link = self.pen.hyperlink().map(Arc::clone);
+            let semantic_type = self.pen.semantic_type();
+            self.pen = pen;
+            self.pen.set_hyperlink(link);
+            self.pen.set_semantic_type(semantic_type);
+            return;
+        }
+        for code in selection {
+            match code {
+                1 | 2 => {
+                    self.pen.set_intensity(pen.intensity());
+                }
+                3 => {
+                    self.pen.set_italic(pen.italic());
+                }
+                4 | 21 => {
+                    self.pen.set_underline(pen.underline());
+                }
+                5 => {
+                    self.pen.set_blink(pen.blink());
+                }
+                7 => {
+                    self.pen.set_reverse(pen.reverse());
+                }
+                8 => {
+                    self.pen.set_invisible(pen.invisible());
+                }
+                9 => {
+                    self.pen.set_strikethrough(pen.strikethrough());
+                }
+                30 => {
+                    self.pen.set_foreground(pen.foreground);
+                }
+                31 => {
+                    self.pen.set_background(pen.background);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns the text of the command that was most recently entered,
+    /// by collecting the Input cells on the lines between the cursor
+    /// and the preceding prompt.
+    fn current_command_text(&self) -> Option<String> {
+        let screen = self.screen();
+        let cursor_row = screen.phys_row(self.cursor.y);
+        let mut lines = vec![];
+
+        for line in screen.lines.iter().take(cursor_row + 1).rev() {
+            let mut text = String::new();
+            let mut has_prompt = false;
+            for (_, cell) in line.visible_cells() {
+                match cell.attrs().semantic_type() {
+                    SemanticType::Input => text.push_str(cell.str()),
+                    SemanticType::Prompt => has_prompt = true,
+                    SemanticType::Output => {}
+                }
+            }
+            let text = text.trim();
+            if !text.is_empty() {
+                lines.push(text.to_string());
+            }
+            if has_prompt {
+                break;
+            }
+        }
+
+        if lines.is_empty() {
+            None
+        } else {
+            lines.reverse();
+            Some(lines.join(" "))
         }
     }
 
@@ -2907,6 +3210,23 @@ fn selection_to_selection(sel: Selection) -> ClipboardSelection {
     }
 }
 
+fn hex_encode(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+fn hex_decode(data: &[u8]) -> Option<Vec<u8>> {
+    if data.len() % 2 != 0 {
+        return None;
+    }
+    data.chunks(2)
+        .map(|pair| {
+            std::str::from_utf8(pair)
+                .ok()
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+        })
+        .collect()
+}
+
 impl<'a> Performer<'a> {
     pub fn new(state: &'a mut TerminalState) -> Self {
         Self { state, print: None }
@@ -2944,7 +3264,14 @@ impl<'a> Performer<'a> {
 
             let x = self.cursor.x;
             let y = self.cursor.y;
-            let width = self.left_and_right_margins.end;
+            // When the cursor has been placed to the right of the right
+            // margin, text wraps at the edge of the page rather than at
+            // the margin.
+            let width = if x < self.left_and_right_margins.end {
+                self.left_and_right_margins.end
+            } else {
+                self.screen().physical_cols
+            };
 
             let mut pen = self.pen.clone();
             // the max(1) here is to ensure that we advance to the next cell
@@ -2961,10 +3288,9 @@ impl<'a> Performer<'a> {
             let cell = Cell::new_grapheme(g, pen);
 
             if self.insert {
-                let margin = self.left_and_right_margins.end;
                 let screen = self.screen_mut();
                 for _ in x..x + print_width as usize {
-                    screen.insert_cell(x, y, margin);
+                    screen.insert_cell(x, y, width);
                 }
             }
 
@@ -2994,6 +3320,36 @@ impl<'a> Performer<'a> {
         }
     }
 
+    /// XTGETTCAP - Request Termcap/Terminfo String.
+    /// The request is a list of hex encoded capability names separated
+    /// by semicolons.  Each name is answered separately using the
+    /// capabilities of the wezterm terminfo entry.
+    fn xt_get_tcap(&mut self, data: &[u8]) {
+        for encoded in data.split(|&b| b == b';') {
+            let name = String::from_utf8_lossy(encoded);
+            let cap = hex_decode(encoded)
+                .and_then(|name| String::from_utf8(name).ok())
+                .and_then(|name| crate::terminfo::lookup(&name));
+            match cap {
+                Some(Capability::Boolean) => {
+                    write!(self.writer, "{}1+r{}{}", DCS, name, ST).ok();
+                }
+                Some(Capability::Number(n)) => {
+                    let value = hex_encode(n.to_string().as_bytes());
+                    write!(self.writer, "{}1+r{}={}{}", DCS, name, value, ST).ok();
+                }
+                Some(Capability::String(s)) => {
+                    let value = hex_encode(&s);
+                    write!(self.writer, "{}1+r{}={}{}", DCS, name, value, ST).ok();
+                }
+                None => {
+                    write!(self.writer, "{}0+r{}{}", DCS, name, ST).ok();
+                }
+            }
+        }
+        self.writer.flush().ok();
+    }
+
     fn device_control(&mut self, ctrl: DeviceControlMode) {
         match &ctrl {
             DeviceControlMode::ShortDeviceControl(s) => {
@@ -3048,6 +3404,7 @@ impl<'a> Performer<'a> {
                             }
                         }
                     }
+                    (b'q', &[b'+']) => self.xt_get_tcap(&s.data),
                     _ => log::warn!("unhandled {:?}", s),
                 }
             }
@@ -3211,6 +3568,7 @@ impl<'a> Performer<'a> {
 
                 self.top_and_bottom_margins = 0..self.screen().physical_rows as VisibleRowIndex;
                 self.left_and_right_margins = 0..self.screen().physical_cols;
+                self.dec_origin_mode = false;
                 self.cursor = Default::default();
             }
 
@@ -3220,6 +3578,8 @@ impl<'a> Performer<'a> {
             // character position of first line.
             Esc::Code(EscCode::FullReset) => {
                 self.pen = Default::default();
+                self.sgr_stack.clear();
+                self.title_stack.clear();
                 self.cursor = Default::default();
                 self.wrap_next = false;
                 self.insert = false;
@@ -3234,6 +3594,7 @@ impl<'a> Performer<'a> {
                 self.application_keypad = false;
                 self.bracketed_paste = false;
                 self.focus_tracking = false;
+                self.alternate_scroll = true;
                 self.sgr_mouse = false;
                 self.any_event_mouse = false;
                 self.button_event_mouse = false;
@@ -3290,7 +3651,13 @@ impl<'a> Performer<'a> {
                 let selection = selection_to_selection(selection);
                 self.set_clipboard_contents(selection, None).ok();
             }
-            OperatingSystemCommand::QuerySelection(_) => {}
+            OperatingSystemCommand::QuerySelection(selection) => {
+                let selection = selection_to_selection(selection);
+                match self.alert_handler.as_mut() {
+                    Some(handler) => handler.alert(Alert::ClipboardQuery(selection)),
+                    None => log::warn!("ignoring OSC 52 clipboard query"),
+                }
+            }
             OperatingSystemCommand::SetSelection(selection, selection_data) => {
                 let selection = selection_to_selection(selection);
                 match self.set_clipboard_contents(selection, Some(selection_data)) {
@@ -3300,6 +3667,16 @@ impl<'a> Performer<'a> {
             }
             OperatingSystemCommand::ITermProprietary(iterm) => match iterm {
                 ITermProprietary::File(image) => self.set_image(*image),
+                ITermProprietary::SetUserVar { name, value } if name == "WEZTERM_COLOR_SCHEME" => {
+                    self.set_color_scheme(&value)
+                }
+                ITermProprietary::SetUserVar { name, value } if name == "WEZTERM_BACKGROUND" => {
+                    let path = if value.is_empty() { None } else { Some(value) };
+                    match self.alert_handler.as_mut() {
+                        Some(handler) => handler.alert(Alert::SetBackgroundImage(path)),
+                        None => log::warn!("ignoring WEZTERM_BACKGROUND user var"),
+                    }
+                }
                 _ => log::warn!("unhandled iterm2: {:?}", iterm),
             },
 
@@ -3332,11 +3709,22 @@ impl<'a> Performer<'a> {
                 FinalTermSemanticPrompt::MarkEndOfInputAndStartOfOutput { .. },
             ) => {
                 self.pen.set_semantic_type(SemanticType::Output);
+                self.command_started = Some((Instant::now(), self.current_command_text()));
             }
 
             OperatingSystemCommand::FinalTermSemanticPrompt(
-                FinalTermSemanticPrompt::CommandStatus { .. },
-            ) => {}
+                FinalTermSemanticPrompt::CommandStatus { status, .. },
+            ) => {
+                if let Some((started, command)) = self.command_started.take() {
+                    if let Some(handler) = self.alert_handler.as_mut() {
+                        handler.alert(Alert::CommandComplete {
+                            command,
+                            status,
+                            duration: started.elapsed(),
+                        });
+                    }
+                }
+            }
 
             OperatingSystemCommand::FinalTermSemanticPrompt(ft) => {
                 log::warn!("unhandled: {:?}", ft);
@@ -3427,17 +3815,22 @@ impl<'a> Performer<'a> {
                     let which_color: Option<DynamicColorNumber> = FromPrimitive::from_u8(idx);
                     log::trace!("ChangeDynamicColors item: {:?}", which_color);
                     if let Some(which_color) = which_color {
+                        macro_rules! respond {
+                            ($color:expr) => {
+                                let response = OperatingSystemCommand::ChangeDynamicColors(
+                                    which_color,
+                                    vec![ColorOrQuery::Color($color)],
+                                );
+                                log::trace!("Color Query response {:?}", response);
+                                write!(self.writer, "{}", response).ok();
+                                self.writer.flush().ok();
+                            };
+                        }
                         macro_rules! set_or_query {
                             ($name:ident) => {
                                 match color {
                                     ColorOrQuery::Query => {
-                                        let response = OperatingSystemCommand::ChangeDynamicColors(
-                                            which_color,
-                                            vec![ColorOrQuery::Color(self.palette().$name)],
-                                        );
-                                        log::trace!("Color Query response {:?}", response);
-                                        write!(self.writer, "{}", response).ok();
-                                        self.writer.flush().ok();
+                                        respond!(self.palette().$name);
                                     }
                                     ColorOrQuery::Color(c) => self.palette_mut().$name = c,
                                 }
@@ -3455,9 +3848,15 @@ impl<'a> Performer<'a> {
                                 }
                                 set_or_query!(cursor_bg)
                             }
-                            DynamicColorNumber::HighlightForegroundColor => {
-                                set_or_query!(selection_fg)
-                            }
+                            DynamicColorNumber::HighlightForegroundColor => match color {
+                                ColorOrQuery::Query => {
+                                    // Selected text that keeps its own color
+                                    // is reported as the default foreground
+                                    let palette = self.palette();
+                                    respond!(palette.selection_fg.unwrap_or(palette.foreground));
+                                }
+                                ColorOrQuery::Color(c) => self.palette_mut().selection_fg = Some(c),
+                            },
                             DynamicColorNumber::HighlightBackgroundColor => {
                                 set_or_query!(selection_bg)
                             }
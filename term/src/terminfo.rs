@@ -0,0 +1,167 @@
+//! Provides access to the capabilities described by the wezterm terminfo
+//! entry.  This is used to answer XTGETTCAP queries so that applications
+//! can discover capabilities without the entry being installed locally,
+//! and the source is made available so that it can be installed with `tic`.
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+
+/// The terminfo source for the `wezterm` entry.  It uses extended
+/// capabilities so it must be compiled using `tic -x`.
+pub const WEZTERM_TERMINFO: &str = include_str!("../../termwiz/data/wezterm.terminfo");
+
+/// The name of the terminfo entry described by `WEZTERM_TERMINFO`
+pub const WEZTERM_TERMINFO_NAME: &str = "wezterm";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Capability {
+    Boolean,
+    Number(u32),
+    String(Vec<u8>),
+}
+
+lazy_static! {
+    static ref CAPABILITIES: HashMap<String, Capability> = parse_terminfo(WEZTERM_TERMINFO);
+}
+
+/// Returns the capability of the wezterm terminfo entry with the
+/// specified name.  In addition to the terminfo names, the `TN` and
+/// `Co` names that xterm recognizes for XTGETTCAP are understood.
+pub fn lookup(name: &str) -> Option<Capability> {
+    match name {
+        "TN" | "name" => Some(Capability::String(
+            WEZTERM_TERMINFO_NAME.as_bytes().to_vec(),
+        )),
+        "Co" => CAPABILITIES.get("colors").cloned(),
+        _ => CAPABILITIES.get(name).cloned(),
+    }
+}
+
+/// Splits the source into comma separated fields, honoring escaped commas
+fn split_fields(source: &str) -> Vec<String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut chars = source.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                field.push(c);
+                if let Some(next) = chars.next() {
+                    field.push(next);
+                }
+            }
+            ',' => {
+                fields.push(field.trim().to_string());
+                field.clear();
+            }
+            c => field.push(c),
+        }
+    }
+    fields
+}
+
+fn parse_terminfo(source: &str) -> HashMap<String, Capability> {
+    let source: String = source
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut caps = HashMap::new();
+    // The first field is the list of names for the entry
+    for field in split_fields(&source).into_iter().skip(1) {
+        if field.is_empty() || field.ends_with('@') {
+            continue;
+        }
+        if let Some(eq) = field.find('=') {
+            caps.insert(
+                field[..eq].to_string(),
+                Capability::String(unescape(&field[eq + 1..])),
+            );
+        } else if let Some(hash) = field.find('#') {
+            let value = &field[hash + 1..];
+            let number = if let Some(hex) = value.strip_prefix("0x") {
+                u32::from_str_radix(hex, 16)
+            } else if value.len() > 1 && value.starts_with('0') {
+                u32::from_str_radix(&value[1..], 8)
+            } else {
+                value.parse()
+            };
+            if let Ok(number) = number {
+                caps.insert(field[..hash].to_string(), Capability::Number(number));
+            }
+        } else {
+            caps.insert(field, Capability::Boolean);
+        }
+    }
+    caps
+}
+
+/// Expands the escapes that may appear in a terminfo string capability
+fn unescape(value: &str) -> Vec<u8> {
+    let mut result = vec![];
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('E') | Some('e') => result.push(0x1b),
+                Some('n') | Some('l') => result.push(b'\n'),
+                Some('r') => result.push(b'\r'),
+                Some('t') => result.push(b'\t'),
+                Some('b') => result.push(0x08),
+                Some('f') => result.push(0x0c),
+                Some('s') => result.push(b' '),
+                Some(d) if d.is_digit(8) => {
+                    let mut octal = d.to_digit(8).unwrap();
+                    for _ in 0..2 {
+                        match chars.peek().and_then(|c| c.to_digit(8)) {
+                            Some(digit) => {
+                                octal = octal * 8 + digit;
+                                chars.next();
+                            }
+                            None => break,
+                        }
+                    }
+                    // \0 is an encoding of NUL that doesn't terminate
+                    // the string
+                    result.push(if octal == 0 { 0x80 } else { octal as u8 });
+                }
+                Some(other) => {
+                    let mut buf = [0u8; 4];
+                    result.extend_from_slice(other.encode_utf8(&mut buf).as_bytes());
+                }
+                None => result.push(b'\\'),
+            },
+            '^' => match chars.next() {
+                Some('?') => result.push(0x7f),
+                Some(ctrl) => result.push((ctrl.to_ascii_uppercase() as u8) & 0x1f),
+                None => result.push(b'^'),
+            },
+            c => {
+                let mut buf = [0u8; 4];
+                result.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn wezterm_entry() {
+        assert_eq!(lookup("Tc"), Some(Capability::Boolean));
+        assert_eq!(lookup("colors"), Some(Capability::Number(256)));
+        assert_eq!(lookup("Co"), Some(Capability::Number(256)));
+        assert_eq!(lookup("it"), Some(Capability::Number(8)));
+        assert_eq!(lookup("bel"), Some(Capability::String(vec![0x07])));
+        assert_eq!(
+            lookup("Smulx"),
+            Some(Capability::String(b"\x1b[4:%p1%dm".to_vec()))
+        );
+        assert_eq!(lookup("Se"), Some(Capability::String(b"\x1b[2 q".to_vec())));
+        assert_eq!(lookup("TN"), Some(Capability::String(b"wezterm".to_vec())));
+        assert_eq!(lookup("bogus"), None);
+    }
+}
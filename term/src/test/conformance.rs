@@ -0,0 +1,444 @@
+//! A table of escape sequence conformance checks in the style of
+//! esctest.  Each case feeds some input to a fresh terminal and then
+//! checks the cursor position, the screen content or the attributes
+//! of a cell.  All of the cases are run and a pass/fail matrix is
+//! reported by category, so that a single regression doesn't hide
+//! others that share its root cause.
+use super::*;
+use termwiz::color::{ColorAttribute, RgbColor};
+
+enum Expect {
+    /// The 0-based cursor position
+    Cursor(usize, i64),
+    /// The visible lines, with trailing spaces trimmed
+    Screen(&'static [&'static str]),
+    /// A predicate on the attributes of the cell at x, y
+    Attr(usize, usize, fn(&CellAttributes) -> bool),
+}
+
+struct Case {
+    category: &'static str,
+    name: &'static str,
+    rows: usize,
+    cols: usize,
+    input: &'static str,
+    expect: Expect,
+}
+
+const CASES: &[Case] = &[
+    Case {
+        category: "cursor",
+        name: "CUP",
+        rows: 10,
+        cols: 10,
+        input: "\x1b[3;5H",
+        expect: Expect::Cursor(4, 2),
+    },
+    Case {
+        category: "cursor",
+        name: "CUP defaults to home",
+        rows: 10,
+        cols: 10,
+        input: "\x1b[5;5H\x1b[H",
+        expect: Expect::Cursor(0, 0),
+    },
+    Case {
+        category: "cursor",
+        name: "CUU stops at top",
+        rows: 10,
+        cols: 10,
+        input: "\x1b[2;1H\x1b[5A",
+        expect: Expect::Cursor(0, 0),
+    },
+    Case {
+        category: "cursor",
+        name: "CUD stops at bottom",
+        rows: 5,
+        cols: 10,
+        input: "\x1b[10B",
+        expect: Expect::Cursor(0, 4),
+    },
+    Case {
+        category: "cursor",
+        name: "CUF stops at right edge",
+        rows: 5,
+        cols: 10,
+        input: "\x1b[20C",
+        expect: Expect::Cursor(9, 0),
+    },
+    Case {
+        category: "cursor",
+        name: "CUB",
+        rows: 5,
+        cols: 10,
+        input: "\x1b[1;5H\x1b[2D",
+        expect: Expect::Cursor(2, 0),
+    },
+    Case {
+        category: "cursor",
+        name: "CHA",
+        rows: 5,
+        cols: 10,
+        input: "\x1b[7G",
+        expect: Expect::Cursor(6, 0),
+    },
+    Case {
+        category: "cursor",
+        name: "VPA",
+        rows: 5,
+        cols: 10,
+        input: "\x1b[4d",
+        expect: Expect::Cursor(0, 3),
+    },
+    Case {
+        category: "cursor",
+        name: "CNL",
+        rows: 5,
+        cols: 10,
+        input: "\x1b[1;5H\x1b[2E",
+        expect: Expect::Cursor(0, 2),
+    },
+    Case {
+        category: "cursor",
+        name: "HT to default tab stop",
+        rows: 5,
+        cols: 20,
+        input: "\t",
+        expect: Expect::Cursor(8, 0),
+    },
+    Case {
+        category: "cursor",
+        name: "DECSC/DECRC",
+        rows: 5,
+        cols: 10,
+        input: "\x1b[3;3H\x1b7\x1b[H\x1b8",
+        expect: Expect::Cursor(2, 2),
+    },
+    Case {
+        category: "margins",
+        name: "DECSTBM homes the cursor",
+        rows: 5,
+        cols: 10,
+        input: "\x1b[3;3H\x1b[2;4r",
+        expect: Expect::Cursor(0, 0),
+    },
+    Case {
+        category: "margins",
+        name: "LF at bottom margin scrolls the region",
+        rows: 5,
+        cols: 10,
+        input: concat!("1\r\n2\r\n3\r\n4\r\n5", "\x1b[2;4r\x1b[4;1H\n"),
+        expect: Expect::Screen(&["1", "3", "4", "", "5"]),
+    },
+    Case {
+        category: "margins",
+        name: "RI at top margin scrolls the region down",
+        rows: 5,
+        cols: 10,
+        input: concat!("1\r\n2\r\n3\r\n4\r\n5", "\x1b[2;4r\x1b[2;1H\x1bM"),
+        expect: Expect::Screen(&["1", "", "2", "3", "5"]),
+    },
+    Case {
+        category: "margins",
+        name: "DL within the region",
+        rows: 5,
+        cols: 10,
+        input: concat!("1\r\n2\r\n3\r\n4\r\n5", "\x1b[2;4r\x1b[2;1H\x1b[M"),
+        expect: Expect::Screen(&["1", "3", "4", "", "5"]),
+    },
+    Case {
+        category: "margins",
+        name: "IL within the region",
+        rows: 5,
+        cols: 10,
+        input: concat!("1\r\n2\r\n3\r\n4\r\n5", "\x1b[2;4r\x1b[2;1H\x1b[L"),
+        expect: Expect::Screen(&["1", "", "2", "3", "5"]),
+    },
+    Case {
+        category: "margins",
+        name: "CUB stops at a left margin beyond the row count",
+        rows: 5,
+        cols: 20,
+        input: "\x1b[?69h\x1b[12;16s\x1b[1;14H\x1b[9D",
+        expect: Expect::Cursor(11, 0),
+    },
+    Case {
+        category: "margins",
+        name: "CUF stops at the right margin",
+        rows: 5,
+        cols: 20,
+        input: "\x1b[?69h\x1b[3;8s\x1b[1;4H\x1b[20C",
+        expect: Expect::Cursor(7, 0),
+    },
+    Case {
+        category: "margins",
+        name: "text wraps at the page edge right of the right margin",
+        rows: 3,
+        cols: 10,
+        input: "\x1b[?69h\x1b[2;5s\x1b[1;8Habc",
+        expect: Expect::Screen(&["       abc", "", ""]),
+    },
+    Case {
+        category: "origin",
+        name: "DECOM homes to the margins",
+        rows: 10,
+        cols: 20,
+        input: "\x1b[3;8r\x1b[?6h",
+        expect: Expect::Cursor(0, 2),
+    },
+    Case {
+        category: "origin",
+        name: "CUP is relative to the margins",
+        rows: 10,
+        cols: 20,
+        input: "\x1b[3;8r\x1b[?69h\x1b[5;15s\x1b[?6h\x1b[2;3H",
+        expect: Expect::Cursor(6, 3),
+    },
+    Case {
+        category: "origin",
+        name: "CUD stops at the bottom margin",
+        rows: 10,
+        cols: 20,
+        input: "\x1b[3;8r\x1b[?6h\x1b[20B",
+        expect: Expect::Cursor(0, 7),
+    },
+    Case {
+        category: "origin",
+        name: "DECRC restores origin mode",
+        rows: 10,
+        cols: 20,
+        input: "\x1b[3;8r\x1b[?6h\x1b7\x1b[?6l\x1b8\x1b[H",
+        expect: Expect::Cursor(0, 2),
+    },
+    Case {
+        category: "origin",
+        name: "DECALN resets origin mode",
+        rows: 10,
+        cols: 20,
+        input: "\x1b[3;8r\x1b[?6h\x1b#8\x1b[H",
+        expect: Expect::Cursor(0, 0),
+    },
+    Case {
+        category: "edit",
+        name: "EL to end of line",
+        rows: 5,
+        cols: 10,
+        input: "abcdef\x1b[1;3H\x1b[K",
+        expect: Expect::Screen(&["ab", "", "", "", ""]),
+    },
+    Case {
+        category: "edit",
+        name: "EL to start of line",
+        rows: 5,
+        cols: 10,
+        input: "abcdef\x1b[1;3H\x1b[1K",
+        expect: Expect::Screen(&["   def", "", "", "", ""]),
+    },
+    Case {
+        category: "edit",
+        name: "ECH",
+        rows: 5,
+        cols: 10,
+        input: "abcdef\x1b[1;2H\x1b[2X",
+        expect: Expect::Screen(&["a  def", "", "", "", ""]),
+    },
+    Case {
+        category: "edit",
+        name: "ICH",
+        rows: 5,
+        cols: 10,
+        input: "abcdef\x1b[1;2H\x1b[2@",
+        expect: Expect::Screen(&["a  bcdef", "", "", "", ""]),
+    },
+    Case {
+        category: "edit",
+        name: "DCH",
+        rows: 5,
+        cols: 10,
+        input: "abcdef\x1b[1;2H\x1b[2P",
+        expect: Expect::Screen(&["adef", "", "", "", ""]),
+    },
+    Case {
+        category: "edit",
+        name: "ED clears the display",
+        rows: 5,
+        cols: 10,
+        input: concat!("1\r\n2\r\n3\r\n4\r\n5", "\x1b[2J"),
+        expect: Expect::Screen(&["", "", "", "", ""]),
+    },
+    Case {
+        category: "sgr",
+        name: "bold",
+        rows: 2,
+        cols: 10,
+        input: "\x1b[1mX",
+        expect: Expect::Attr(0, 0, |a| a.intensity() == Intensity::Bold),
+    },
+    Case {
+        category: "sgr",
+        name: "SGR 22 resets bold",
+        rows: 2,
+        cols: 10,
+        input: "\x1b[1m\x1b[22mX",
+        expect: Expect::Attr(0, 0, |a| a.intensity() == Intensity::Normal),
+    },
+    Case {
+        category: "sgr",
+        name: "SGR 0 resets everything",
+        rows: 2,
+        cols: 10,
+        input: "\x1b[1;3;4;7;31m\x1b[0mX",
+        expect: Expect::Attr(0, 0, |a| {
+            a.intensity() == Intensity::Normal
+                && !a.italic()
+                && a.underline() == Underline::None
+                && !a.reverse()
+                && a.foreground == ColorAttribute::Default
+        }),
+    },
+    Case {
+        category: "sgr",
+        name: "italic",
+        rows: 2,
+        cols: 10,
+        input: "\x1b[3mX",
+        expect: Expect::Attr(0, 0, |a| a.italic()),
+    },
+    Case {
+        category: "sgr",
+        name: "underline",
+        rows: 2,
+        cols: 10,
+        input: "\x1b[4mX",
+        expect: Expect::Attr(0, 0, |a| a.underline() == Underline::Single),
+    },
+    Case {
+        category: "sgr",
+        name: "reverse",
+        rows: 2,
+        cols: 10,
+        input: "\x1b[7mX",
+        expect: Expect::Attr(0, 0, |a| a.reverse()),
+    },
+    Case {
+        category: "sgr",
+        name: "ANSI foreground",
+        rows: 2,
+        cols: 10,
+        input: "\x1b[31mX",
+        expect: Expect::Attr(0, 0, |a| a.foreground == ColorAttribute::PaletteIndex(1)),
+    },
+    Case {
+        category: "sgr",
+        name: "ANSI background",
+        rows: 2,
+        cols: 10,
+        input: "\x1b[42mX",
+        expect: Expect::Attr(0, 0, |a| a.background == ColorAttribute::PaletteIndex(2)),
+    },
+    Case {
+        category: "sgr",
+        name: "256 color foreground",
+        rows: 2,
+        cols: 10,
+        input: "\x1b[38;5;100mX",
+        expect: Expect::Attr(0, 0, |a| a.foreground == ColorAttribute::PaletteIndex(100)),
+    },
+    Case {
+        category: "sgr",
+        name: "true color foreground",
+        rows: 2,
+        cols: 10,
+        input: "\x1b[38;2;10;20;30mX",
+        expect: Expect::Attr(0, 0, |a| {
+            a.foreground == ColorAttribute::TrueColorWithDefaultFallback(RgbColor::new(10, 20, 30))
+        }),
+    },
+    Case {
+        category: "sgr",
+        name: "attributes don't apply to earlier cells",
+        rows: 2,
+        cols: 10,
+        input: "a\x1b[1mX",
+        expect: Expect::Attr(0, 0, |a| a.intensity() == Intensity::Normal),
+    },
+];
+
+fn run_case(case: &Case) -> Result<(), String> {
+    let mut term = TestTerm::new(case.rows, case.cols, 0);
+    term.print(case.input);
+
+    match &case.expect {
+        Expect::Cursor(x, y) => {
+            let cursor = term.cursor_pos();
+            if cursor.x == *x && cursor.y == *y {
+                Ok(())
+            } else {
+                Err(format!(
+                    "cursor at ({}, {}), expected ({}, {})",
+                    cursor.x, cursor.y, x, y
+                ))
+            }
+        }
+        Expect::Screen(expect) => {
+            let actual: Vec<String> = term
+                .screen()
+                .visible_lines()
+                .iter()
+                .map(|line| line.as_str().trim_end().to_string())
+                .collect();
+            if actual == *expect {
+                Ok(())
+            } else {
+                Err(format!("screen is {:?}, expected {:?}", actual, expect))
+            }
+        }
+        Expect::Attr(x, y, check) => {
+            let lines = term.screen().visible_lines();
+            let attrs = lines
+                .get(*y)
+                .and_then(|line| line.cells().get(*x))
+                .map(|cell| cell.attrs().clone())
+                .ok_or_else(|| format!("no cell at ({}, {})", x, y))?;
+            if check(&attrs) {
+                Ok(())
+            } else {
+                Err(format!("unexpected attributes {:?}", attrs))
+            }
+        }
+    }
+}
+
+#[test]
+fn conformance_matrix() {
+    let mut categories: Vec<&str> = vec![];
+    for case in CASES {
+        if !categories.contains(&case.category) {
+            categories.push(case.category);
+        }
+    }
+
+    let mut failures = vec![];
+    println!("{:<10} {:>6} {:>6}", "category", "pass", "total");
+    for category in categories {
+        let cases: Vec<&Case> = CASES.iter().filter(|c| c.category == category).collect();
+        let mut passed = 0;
+        for case in &cases {
+            match run_case(case) {
+                Ok(()) => passed += 1,
+                Err(err) => failures.push(format!("{}: {}: {}", category, case.name, err)),
+            }
+        }
+        println!("{:<10} {:>6} {:>6}", category, passed, cases.len());
+    }
+
+    for failure in &failures {
+        println!("FAIL {}", failure);
+    }
+    assert!(
+        failures.is_empty(),
+        "{} conformance case(s) failed:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
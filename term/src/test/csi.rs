@@ -192,3 +192,112 @@ fn test_ed_erase_scrollback() {
     term.print("b");
     assert_all_contents(&term, file!(), line!(), &["111", "222", "ab "]);
 }
+
+#[test]
+fn test_decfra() {
+    let mut term = TestTerm::new(4, 5, 0);
+    term.clean_dirty_lines();
+    term.print("\x1b[88;2;2;3;4$x");
+    assert_visible_contents(
+        &term,
+        file!(),
+        line!(),
+        &["     ", " XXX ", " XXX ", "     "],
+    );
+    term.assert_dirty_lines(&[1, 2], None);
+
+    // Coordinates are relative to the margins in origin mode
+    term.print("\x1b[2;3r\x1b[?6h\x1b[66;2;1;2;1$x");
+    assert_visible_contents(
+        &term,
+        file!(),
+        line!(),
+        &["     ", " XXX ", "BXXX ", "     "],
+    );
+}
+
+#[test]
+fn test_decera() {
+    let mut term = TestTerm::new(4, 5, 0);
+    term.print("abcde\r\nabcde\r\nabcde\r\nabcde");
+    term.clean_dirty_lines();
+    term.print("\x1b[2;2;3;4$z");
+    assert_visible_contents(
+        &term,
+        file!(),
+        line!(),
+        &["abcde", "a   e", "a   e", "abcde"],
+    );
+    term.assert_dirty_lines(&[1, 2], None);
+
+    // Omitted bottom and right extend to the edges of the screen
+    term.print("\x1b[4;4$z");
+    assert_visible_contents(
+        &term,
+        file!(),
+        line!(),
+        &["abcde", "a   e", "a   e", "abc  "],
+    );
+}
+
+#[test]
+fn test_deccra() {
+    let mut term = TestTerm::new(3, 6, 0);
+    term.print("abc\r\ndef");
+    term.clean_dirty_lines();
+
+    // Copy to an overlapping destination one row down and one column right
+    term.print("\x1b[1;1;2;3;1;2;2;1$v");
+    assert_visible_contents(&term, file!(), line!(), &["abc   ", "dabc  ", " def  "]);
+    term.assert_dirty_lines(&[1, 2], None);
+
+    // The copy is clipped to the screen
+    term.print("\x1b[1;1;1;3;1;3;5;1$v");
+    assert_visible_contents(&term, file!(), line!(), &["abc   ", "dabc  ", " defab"]);
+}
+
+#[test]
+fn test_title_stack() {
+    let mut term = TestTerm::new(2, 10, 0);
+    term.print("\x1b]2;one\x07\x1b[22t\x1b]2;two\x07");
+    assert_eq!(term.get_title(), "two");
+    term.print("\x1b[23t");
+    assert_eq!(term.get_title(), "one");
+
+    // Popping the window title leaves the icon title alone
+    term.print("\x1b]1;icon\x07\x1b[22;2t\x1b]2;three\x07\x1b]1;\x07");
+    assert_eq!(term.get_title(), "three");
+    term.print("\x1b[23;2t");
+    assert_eq!(term.get_title(), "one");
+
+    // Popping an empty stack is ignored
+    term.print("\x1b[23t");
+    assert_eq!(term.get_title(), "one");
+}
+
+#[test]
+fn test_sgr_stack() {
+    let mut term = TestTerm::new(2, 10, 0);
+    term.print("\x1b[1;31m\x1b[#{\x1b[0;3mA\x1b[#}B");
+
+    // Only restore the foreground color
+    term.print("\x1b[30#{\x1b[32;3mC\x1b[#}D");
+
+    let attrs: Vec<CellAttributes> = term.screen().visible_lines()[0]
+        .cells()
+        .iter()
+        .take(4)
+        .map(|cell| cell.attrs().clone())
+        .collect();
+    let red = color::ColorAttribute::PaletteIndex(1);
+
+    assert_eq!(attrs[0].intensity(), Intensity::Normal);
+    assert!(attrs[0].italic());
+    assert_eq!(attrs[1].intensity(), Intensity::Bold);
+    assert!(!attrs[1].italic());
+    assert_eq!(attrs[1].foreground, red);
+    assert_eq!(attrs[2].foreground, color::ColorAttribute::PaletteIndex(2));
+    assert_eq!(attrs[3].foreground, red);
+    assert!(attrs[3].italic());
+    assert_eq!(attrs[3].intensity(), Intensity::Bold);
+}
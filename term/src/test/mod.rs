@@ -5,11 +5,13 @@ use super::*;
 mod c0;
 use bitflags::bitflags;
 mod c1;
+mod conformance;
 mod csi;
 // mod selection; FIXME: port to render layer
 use crate::color::ColorPalette;
 use pretty_assertions::assert_eq;
 use std::cell::RefCell;
+use std::rc::Rc;
 use std::sync::Arc;
 use termwiz::escape::csi::{Edit, EraseInDisplay, EraseInLine};
 use termwiz::escape::{OneBased, OperatingSystemCommand, CSI};
@@ -470,6 +472,131 @@ fn test_semantic() {
     );
 }
 
+#[test]
+fn test_command_complete_alert() {
+    use termwiz::escape::osc::FinalTermSemanticPrompt;
+
+    struct Alerts(Rc<RefCell<Vec<Alert>>>);
+    impl AlertHandler for Alerts {
+        fn alert(&mut self, alert: Alert) {
+            self.0.borrow_mut().push(alert);
+        }
+    }
+
+    fn osc(prompt: FinalTermSemanticPrompt) -> String {
+        format!(
+            "{}",
+            OperatingSystemCommand::FinalTermSemanticPrompt(prompt)
+        )
+    }
+
+    let alerts = Rc::new(RefCell::new(vec![]));
+    let mut term = TestTerm::new(5, 10, 0);
+    term.term
+        .set_notification_handler(Box::new(Alerts(Rc::clone(&alerts))));
+
+    term.print(osc(FinalTermSemanticPrompt::FreshLineAndStartPrompt {
+        aid: None,
+        cl: None,
+    }));
+    term.print("> ");
+    term.print(osc(
+        FinalTermSemanticPrompt::MarkEndOfPromptAndStartOfInputUntilNextMarker,
+    ));
+    term.print("make\r\n");
+    term.print(osc(
+        FinalTermSemanticPrompt::MarkEndOfInputAndStartOfOutput { aid: None },
+    ));
+    term.print("done\r\n");
+    assert!(alerts.borrow().is_empty());
+
+    term.print(osc(FinalTermSemanticPrompt::CommandStatus {
+        status: 2,
+        aid: None,
+    }));
+
+    let alerts = alerts.borrow();
+    assert_eq!(alerts.len(), 1);
+    match &alerts[0] {
+        Alert::CommandComplete {
+            command, status, ..
+        } => {
+            assert_eq!(command.as_deref(), Some("make"));
+            assert_eq!(*status, 2);
+        }
+        other => panic!("unexpected alert {:?}", other),
+    }
+}
+
+#[test]
+fn test_clipboard_query_alert() {
+    use termwiz::escape::osc::Selection;
+
+    struct Alerts(Rc<RefCell<Vec<Alert>>>);
+    impl AlertHandler for Alerts {
+        fn alert(&mut self, alert: Alert) {
+            self.0.borrow_mut().push(alert);
+        }
+    }
+
+    let alerts = Rc::new(RefCell::new(vec![]));
+    let mut term = TestTerm::new(5, 10, 0);
+    term.term
+        .set_notification_handler(Box::new(Alerts(Rc::clone(&alerts))));
+
+    term.print(format!(
+        "{}",
+        OperatingSystemCommand::QuerySelection(Selection::CLIPBOARD)
+    ));
+    term.print(format!(
+        "{}",
+        OperatingSystemCommand::QuerySelection(Selection::PRIMARY)
+    ));
+
+    assert_eq!(
+        *alerts.borrow(),
+        vec![
+            Alert::ClipboardQuery(ClipboardSelection::Clipboard),
+            Alert::ClipboardQuery(ClipboardSelection::PrimarySelection),
+        ]
+    );
+}
+
+#[test]
+fn test_set_background_alert() {
+    use termwiz::escape::osc::ITermProprietary;
+
+    struct Alerts(Rc<RefCell<Vec<Alert>>>);
+    impl AlertHandler for Alerts {
+        fn alert(&mut self, alert: Alert) {
+            self.0.borrow_mut().push(alert);
+        }
+    }
+
+    let alerts = Rc::new(RefCell::new(vec![]));
+    let mut term = TestTerm::new(5, 10, 0);
+    term.term
+        .set_notification_handler(Box::new(Alerts(Rc::clone(&alerts))));
+
+    for value in &["/tmp/bg.png", ""] {
+        term.print(format!(
+            "{}",
+            OperatingSystemCommand::ITermProprietary(ITermProprietary::SetUserVar {
+                name: "WEZTERM_BACKGROUND".to_string(),
+                value: value.to_string(),
+            })
+        ));
+    }
+
+    assert_eq!(
+        *alerts.borrow(),
+        vec![
+            Alert::SetBackgroundImage(Some("/tmp/bg.png".to_string())),
+            Alert::SetBackgroundImage(None),
+        ]
+    );
+}
+
 #[test]
 fn basic_output() {
     let mut term = TestTerm::new(5, 10, 0);
@@ -736,6 +863,78 @@ fn test_resize_wrap() {
     );
 }
 
+/// Test that rewrapping the scrollback on resize preserves the
+/// StableRowIndex of the cursor row.
+#[test]
+fn test_resize_wrap_stable_rows() {
+    let mut term = TestTerm::new(3, 4, 10);
+    term.print("1111aa\r\n2222bb\r\n333");
+    assert_all_contents(
+        &term,
+        file!(),
+        line!(),
+        &["1111", "aa  ", "2222", "bb  ", "333 "],
+    );
+    let cursor_row = term.screen().visible_row_to_stable_row(term.cursor_pos().y);
+    assert_eq!(cursor_row, 4);
+
+    term.resize(3, 6, 0, 0);
+    assert_all_contents(&term, file!(), line!(), &["1111aa", "2222bb", "333 "]);
+    assert_eq!(
+        term.screen().visible_row_to_stable_row(term.cursor_pos().y),
+        cursor_row
+    );
+
+    term.resize(3, 4, 0, 0);
+    assert_all_contents(
+        &term,
+        file!(),
+        line!(),
+        &["1111", "aa", "2222", "bb", "333 "],
+    );
+    assert_eq!(
+        term.screen().visible_row_to_stable_row(term.cursor_pos().y),
+        cursor_row
+    );
+}
+
+/// Test that the cursor row keeps its StableRowIndex when rewrapping
+/// adds rows above it while the scrollback is still empty, rather than
+/// the whole screen shifting to keep the top row at index 0.
+#[test]
+fn test_resize_wrap_stable_rows_at_scrollback_top() {
+    fn text(term: &TestTerm) -> Vec<String> {
+        term.screen()
+            .all_lines()
+            .iter()
+            .map(|line| line.as_str().trim_end().to_string())
+            .collect()
+    }
+
+    let mut term = TestTerm::new(3, 6, 10);
+    term.print("1111aa\r\n2222bb\r\n333");
+    assert_eq!(term.screen().phys_to_stable_row_index(0), 0);
+    let cursor_row = term.screen().visible_row_to_stable_row(term.cursor_pos().y);
+    assert_eq!(cursor_row, 2);
+
+    term.resize(3, 4, 0, 0);
+    assert_eq!(text(&term), vec!["1111", "aa", "2222", "bb", "333"]);
+    assert_eq!(
+        term.screen().visible_row_to_stable_row(term.cursor_pos().y),
+        cursor_row
+    );
+    // The rows that rewrapping added are above index 0
+    assert_eq!(term.screen().phys_to_stable_row_index(0), -2);
+
+    term.resize(3, 6, 0, 0);
+    assert_eq!(text(&term), vec!["1111aa", "2222bb", "333"]);
+    assert_eq!(
+        term.screen().visible_row_to_stable_row(term.cursor_pos().y),
+        cursor_row
+    );
+    assert_eq!(term.screen().phys_to_stable_row_index(0), 0);
+}
+
 #[test]
 fn test_scrollup() {
     let mut term = TestTerm::new(2, 1, 4);
@@ -800,6 +999,20 @@ fn test_scroll_margins() {
     );
 }
 
+#[test]
+fn test_resize_resets_margins() {
+    let mut term = TestTerm::new(5, 10, 0);
+    term.print("\x1b[2;4r\x1b[?69h\x1b[3;6s\x1b[?6h");
+    term.assert_cursor_pos(2, 1, Some("origin mode homes to the margins"));
+
+    term.resize(6, 12, 0, 0);
+    term.print("\x1b[H");
+    term.assert_cursor_pos(0, 0, Some("margins were reset by the resize"));
+
+    term.print("\x1b[20B\x1b[20C");
+    term.assert_cursor_pos(11, 5, Some("cursor can reach the new bottom right"));
+}
+
 #[test]
 fn test_emoji_with_modifier() {
     let waving_hand = "\u{1f44b}";
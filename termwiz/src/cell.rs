@@ -16,7 +16,7 @@ use unicode_width::UnicodeWidthStr;
 #[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
 #[derive(Default, Clone, Eq, PartialEq)]
 pub struct CellAttributes {
-    attributes: u16,
+    attributes: u32,
     /// The foreground color
     pub foreground: ColorAttribute,
     /// The background color
@@ -41,6 +41,7 @@ impl std::fmt::Debug for CellAttributes {
             .field("wrapped", &self.wrapped())
             .field("overline", &self.overline())
             .field("semantic_type", &self.semantic_type())
+            .field("vertical_align", &self.vertical_align())
             .field("foreground", &self.foreground)
             .field("background", &self.background)
             .field("fat", &self.fat)
@@ -85,13 +86,13 @@ macro_rules! bitfield {
     ($getter:ident, $setter:ident, $bitmask:expr, $bitshift:expr) => {
         #[inline]
         pub fn $getter(&self) -> u16 {
-            (self.attributes >> $bitshift) & $bitmask
+            ((self.attributes >> $bitshift) & $bitmask) as u16
         }
 
         #[inline]
         pub fn $setter(&mut self, value: u16) -> &mut Self {
             let clear = !($bitmask << $bitshift);
-            let attr_value = (value & $bitmask) << $bitshift;
+            let attr_value = (value as u32 & $bitmask) << $bitshift;
             self.attributes = (self.attributes & clear) | attr_value;
             self
         }
@@ -105,7 +106,7 @@ macro_rules! bitfield {
 
         #[inline]
         pub fn $setter(&mut self, value: $enum) -> &mut Self {
-            let value = value as u16;
+            let value = value as u32;
             let clear = !($bitmask << $bitshift);
             let attr_value = (value & $bitmask) << $bitshift;
             self.attributes = (self.attributes & clear) | attr_value;
@@ -134,6 +135,23 @@ impl Default for SemanticType {
     }
 }
 
+/// Whether the text in a cell is drawn normally, or as a superscript
+/// or subscript.  These are set by SGR 73, 74 and 75, as in mintty.
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum VerticalAlign {
+    BaseLine = 0,
+    SuperScript = 1,
+    SubScript = 2,
+}
+
+impl Default for VerticalAlign {
+    fn default() -> Self {
+        Self::BaseLine
+    }
+}
+
 /// The `Intensity` of a cell describes its boldness.  Most terminals
 /// implement `Intensity::Bold` by either using a bold font or by simply
 /// using an alternative color.  Some terminals implement `Intensity::Half`
@@ -217,6 +235,7 @@ impl CellAttributes {
     bitfield!(wrapped, set_wrapped, 11);
     bitfield!(overline, set_overline, 12);
     bitfield!(semantic_type, set_semantic_type, SemanticType, 0b11, 13);
+    bitfield!(vertical_align, set_vertical_align, VerticalAlign, 0b11, 15);
 
     /// Returns true if the attribute bits in both objects are equal.
     /// This can be used to cheaply test whether the styles of the two
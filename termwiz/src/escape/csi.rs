@@ -1,5 +1,5 @@
 use super::OneBased;
-use crate::cell::{Blink, Intensity, Underline};
+use crate::cell::{Blink, Intensity, Underline, VerticalAlign};
 use crate::color::{AnsiColor, ColorSpec, RgbColor};
 use crate::input::{Modifiers, MouseButtons};
 use num_derive::*;
@@ -683,6 +683,9 @@ pub enum DecPrivateModeCode {
     /// enable mouse reporting itself, it just controls how reports
     /// will be encoded.
     SGRMouse = 1006,
+    /// When the alternate screen is active and mouse reporting is
+    /// disabled, translate mouse wheel events into cursor key presses
+    AlternateScroll = 1007,
     /// Save cursor as in DECSC
     SaveCursor = 1048,
     ClearAndEnableAlternateScreen = 1049,
@@ -956,6 +959,45 @@ pub enum Edit {
 
     /// REP - Repeat the preceding character n times
     Repeat(u32),
+
+    /// DECCRA - Copy Rectangular Area
+    /// Copies the characters and attributes in the rectangle bounded by
+    /// top, left, bottom and right (inclusive) so that its top left corner
+    /// is placed at dest_top, dest_left.  The source and destination may
+    /// overlap.  Omitted bottom and right values refer to the bottom and
+    /// right of the page.
+    /// https://vt100.net/docs/vt510-rm/DECCRA.html
+    CopyRectangularArea {
+        top: OneBased,
+        left: OneBased,
+        bottom: OneBased,
+        right: OneBased,
+        source_page: OneBased,
+        dest_top: OneBased,
+        dest_left: OneBased,
+        dest_page: OneBased,
+    },
+
+    /// DECFRA - Fill Rectangular Area
+    /// Fills the rectangle with the specified character, using the
+    /// current graphic rendition.
+    /// https://vt100.net/docs/vt510-rm/DECFRA.html
+    FillRectangularArea {
+        character: char,
+        top: OneBased,
+        left: OneBased,
+        bottom: OneBased,
+        right: OneBased,
+    },
+
+    /// DECERA - Erase Rectangular Area
+    /// https://vt100.net/docs/vt510-rm/DECERA.html
+    EraseRectangularArea {
+        top: OneBased,
+        left: OneBased,
+        bottom: OneBased,
+        right: OneBased,
+    },
 }
 
 trait EncodeCSIParam {
@@ -1006,6 +1048,37 @@ impl Display for Edit {
             Edit::ScrollUp(n) => n.write_csi(f, "S")?,
             Edit::EraseInDisplay(n) => n.write_csi(f, "J")?,
             Edit::Repeat(n) => n.write_csi(f, "b")?,
+            Edit::CopyRectangularArea {
+                top,
+                left,
+                bottom,
+                right,
+                source_page,
+                dest_top,
+                dest_left,
+                dest_page,
+            } => write!(
+                f,
+                "{};{};{};{};{};{};{};{}$v",
+                top, left, bottom, right, source_page, dest_top, dest_left, dest_page
+            )?,
+            Edit::FillRectangularArea {
+                character,
+                top,
+                left,
+                bottom,
+                right,
+            } => write!(
+                f,
+                "{};{};{};{};{}$x",
+                *character as u32, top, left, bottom, right
+            )?,
+            Edit::EraseRectangularArea {
+                top,
+                left,
+                bottom,
+                right,
+            } => write!(f, "{};{};{};{}$z", top, left, bottom, right)?,
         }
         Ok(())
     }
@@ -1221,6 +1294,16 @@ pub enum Sgr {
     Foreground(ColorSpec),
     Background(ColorSpec),
     Overline(bool),
+    VerticalAlign(VerticalAlign),
+    /// XTPUSHSGR - save the current graphic rendition on a stack.
+    /// If the list is empty then all attributes are saved, otherwise
+    /// only the attributes selected by these codes are saved:
+    /// 1 bold, 2 faint, 3 italic, 4 underline, 5 blink, 7 inverse,
+    /// 8 invisible, 9 strikethrough, 21 double underline,
+    /// 30 foreground color and 31 background color.
+    Push(Vec<i64>),
+    /// XTPOPSGR - restore the attributes saved by the most recent Push
+    Pop,
 }
 
 impl Display for Sgr {
@@ -1267,6 +1350,9 @@ impl Display for Sgr {
             Sgr::StrikeThrough(false) => code!(StrikeThroughOff),
             Sgr::Overline(true) => code!(OverlineOn),
             Sgr::Overline(false) => code!(OverlineOff),
+            Sgr::VerticalAlign(VerticalAlign::SuperScript) => code!(SuperScript),
+            Sgr::VerticalAlign(VerticalAlign::SubScript) => code!(SubScript),
+            Sgr::VerticalAlign(VerticalAlign::BaseLine) => code!(BaseLine),
             Sgr::Font(Font::Default) => code!(DefaultFont),
             Sgr::Font(Font::Alternate(1)) => code!(AltFont1),
             Sgr::Font(Font::Alternate(2)) => code!(AltFont2),
@@ -1354,6 +1440,16 @@ impl Display for Sgr {
             Sgr::UnderlineColor(ColorSpec::PaletteIndex(idx)) => {
                 write!(f, "{}:5:{}m", SgrCode::UnderlineColor as i64, *idx)?
             }
+            Sgr::Push(selection) => {
+                for (idx, code) in selection.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ";")?;
+                    }
+                    write!(f, "{}", code)?;
+                }
+                write!(f, "#{{")?
+            }
+            Sgr::Pop => write!(f, "#}}")?,
         }
         Ok(())
     }
@@ -1535,6 +1631,18 @@ impl<'a> CSIParser<'a> {
                 }))
             }
 
+            ('v', &[b'$']) => self.deccra(params),
+            ('x', &[b'$']) => self.decfra(params),
+            ('z', &[b'$']) => self.decera(params),
+
+            ('{', &[b'#']) | ('p', &[b'#']) => Ok(CSI::Sgr(Sgr::Push(
+                params
+                    .iter()
+                    .map(|p| p.as_integer().ok_or(()))
+                    .collect::<Result<Vec<_>, ()>>()?,
+            ))),
+            ('}', &[b'#']) | ('q', &[b'#']) => noparams!(Sgr, Pop, params),
+
             ('p', &[b'!']) => Ok(CSI::Device(Box::new(Device::SoftReset))),
 
             ('h', &[b'?']) => self
@@ -1605,6 +1713,74 @@ impl<'a> CSIParser<'a> {
         }
     }
 
+    /// Parses the top, left, bottom and right parameters of a rectangular
+    /// area operation, starting at `idx`.  Omitted top and left values
+    /// default to 1, and omitted bottom and right values to the bottom and
+    /// right of the page.
+    fn rectangle(
+        params: &[CsiParam],
+        idx: usize,
+    ) -> Result<(OneBased, OneBased, OneBased, OneBased), ()> {
+        let far_edge = |i: usize| match params.get(i) {
+            Some(p) => OneBased::from_esc_param_with_big_default(p),
+            None => Ok(OneBased::new(u32::max_value())),
+        };
+        Ok((
+            OneBased::from_optional_esc_param(params.get(idx))?,
+            OneBased::from_optional_esc_param(params.get(idx + 1))?,
+            far_edge(idx + 2)?,
+            far_edge(idx + 3)?,
+        ))
+    }
+
+    fn deccra(&mut self, params: &'a [CsiParam]) -> Result<CSI, ()> {
+        if params.len() > 8 {
+            return Err(());
+        }
+        let (top, left, bottom, right) = Self::rectangle(params, 0)?;
+        Ok(CSI::Edit(Edit::CopyRectangularArea {
+            top,
+            left,
+            bottom,
+            right,
+            source_page: OneBased::from_optional_esc_param(params.get(4))?,
+            dest_top: OneBased::from_optional_esc_param(params.get(5))?,
+            dest_left: OneBased::from_optional_esc_param(params.get(6))?,
+            dest_page: OneBased::from_optional_esc_param(params.get(7))?,
+        }))
+    }
+
+    fn decfra(&mut self, params: &'a [CsiParam]) -> Result<CSI, ()> {
+        if params.is_empty() || params.len() > 5 {
+            return Err(());
+        }
+        let character = params[0]
+            .as_integer()
+            .and_then(|c| std::char::from_u32(c as u32))
+            .ok_or(())?;
+        let (top, left, bottom, right) = Self::rectangle(params, 1)?;
+        Ok(CSI::Edit(Edit::FillRectangularArea {
+            character,
+            top,
+            left,
+            bottom,
+            right,
+        }))
+    }
+
+    fn decera(&mut self, params: &'a [CsiParam]) -> Result<CSI, ()> {
+        if params.len() > 4 {
+            return Err(());
+        }
+        let (top, left, bottom, right) = Self::rectangle(params, 0)?;
+        Ok(CSI::Edit(Edit::EraseRectangularArea {
+            top,
+            left,
+            bottom,
+            right,
+        }))
+    }
+
     fn decstbm(&mut self, params: &'a [CsiParam]) -> Result<CSI, ()> {
         if params.is_empty() {
             Ok(CSI::Cursor(Cursor::SetTopAndBottomMargins {
@@ -1937,13 +2113,13 @@ impl<'a> CSIParser<'a> {
                     20 => Ok(Window::ReportIconLabel),
                     21 => Ok(Window::ReportWindowTitle),
                     22 => match arg1 {
-                        Some(0) => Ok(Window::PushIconAndWindowTitle),
+                        Some(0) | None => Ok(Window::PushIconAndWindowTitle),
                         Some(1) => Ok(Window::PushIconTitle),
                         Some(2) => Ok(Window::PushWindowTitle),
                         _ => Err(()),
                     },
                     23 => match arg1 {
-                        Some(0) => Ok(Window::PopIconAndWindowTitle),
+                        Some(0) | None => Ok(Window::PopIconAndWindowTitle),
                         Some(1) => Ok(Window::PopIconTitle),
                         Some(2) => Ok(Window::PopWindowTitle),
                         _ => Err(()),
@@ -2074,6 +2250,11 @@ impl<'a> CSIParser<'a> {
                         SgrCode::StrikeThroughOff => one!(Sgr::StrikeThrough(false)),
                         SgrCode::OverlineOn => one!(Sgr::Overline(true)),
                         SgrCode::OverlineOff => one!(Sgr::Overline(false)),
+                        SgrCode::SuperScript => {
+                            one!(Sgr::VerticalAlign(VerticalAlign::SuperScript))
+                        }
+                        SgrCode::SubScript => one!(Sgr::VerticalAlign(VerticalAlign::SubScript)),
+                        SgrCode::BaseLine => one!(Sgr::VerticalAlign(VerticalAlign::BaseLine)),
                         SgrCode::DefaultFont => one!(Sgr::Font(Font::Default)),
                         SgrCode::AltFont1 => one!(Sgr::Font(Font::Alternate(1))),
                         SgrCode::AltFont2 => one!(Sgr::Font(Font::Alternate(2))),
@@ -2182,6 +2363,11 @@ pub enum SgrCode {
     UnderlineColor = 58,
     ResetUnderlineColor = 59,
 
+    SuperScript = 73,
+    SubScript = 74,
+    /// Neither superscript nor subscript
+    BaseLine = 75,
+
     ForegroundBrightBlack = 90,
     ForegroundBrightRed = 91,
     ForegroundBrightGreen = 92,
@@ -2327,6 +2513,22 @@ mod test {
         );
     }
 
+    #[test]
+    fn vertical_align() {
+        assert_eq!(
+            parse('m', &[73], "\x1b[73m"),
+            vec![CSI::Sgr(Sgr::VerticalAlign(VerticalAlign::SuperScript))]
+        );
+        assert_eq!(
+            parse('m', &[74], "\x1b[74m"),
+            vec![CSI::Sgr(Sgr::VerticalAlign(VerticalAlign::SubScript))]
+        );
+        assert_eq!(
+            parse('m', &[75], "\x1b[75m"),
+            vec![CSI::Sgr(Sgr::VerticalAlign(VerticalAlign::BaseLine))]
+        );
+    }
+
     #[test]
     fn underline_color() {
         assert_eq!(
@@ -2415,6 +2617,10 @@ mod test {
 
     #[test]
     fn window() {
+        assert_eq!(
+            parse('t', &[22], "\x1b[22;0t"),
+            vec![CSI::Window(Window::PushIconAndWindowTitle)]
+        );
         assert_eq!(
             parse('t', &[6], "\x1b[6t"),
             vec![CSI::Window(Window::LowerWindow)]
@@ -2428,6 +2634,77 @@ mod test {
         );
     }
 
+    #[test]
+    fn sgr_stack() {
+        assert_eq!(
+            parse_int('{', &[], b'#', "\x1b[#{"),
+            vec![CSI::Sgr(Sgr::Push(vec![]))]
+        );
+        assert_eq!(
+            parse_int('{', &[30, 31], b'#', "\x1b[30;31#{"),
+            vec![CSI::Sgr(Sgr::Push(vec![30, 31]))]
+        );
+        assert_eq!(
+            parse_int('p', &[1], b'#', "\x1b[1#{"),
+            vec![CSI::Sgr(Sgr::Push(vec![1]))]
+        );
+        assert_eq!(
+            parse_int('}', &[], b'#', "\x1b[#}"),
+            vec![CSI::Sgr(Sgr::Pop)]
+        );
+        assert_eq!(
+            parse_int('q', &[], b'#', "\x1b[#}"),
+            vec![CSI::Sgr(Sgr::Pop)]
+        );
+    }
+
+    #[test]
+    fn rectangular_area() {
+        assert_eq!(
+            parse_int('z', &[2, 3, 4, 5], b'$', "\x1b[2;3;4;5$z"),
+            vec![CSI::Edit(Edit::EraseRectangularArea {
+                top: OneBased::new(2),
+                left: OneBased::new(3),
+                bottom: OneBased::new(4),
+                right: OneBased::new(5),
+            })]
+        );
+        // Omitted bottom and right refer to the far edges of the page
+        assert_eq!(
+            parse_int(
+                'x',
+                &[65, 2, 3],
+                b'$',
+                "\x1b[65;2;3;4294967295;4294967295$x"
+            ),
+            vec![CSI::Edit(Edit::FillRectangularArea {
+                character: 'A',
+                top: OneBased::new(2),
+                left: OneBased::new(3),
+                bottom: OneBased::new(u32::max_value()),
+                right: OneBased::new(u32::max_value()),
+            })]
+        );
+        assert_eq!(
+            parse_int(
+                'v',
+                &[1, 2, 3, 4, 1, 5, 6, 1],
+                b'$',
+                "\x1b[1;2;3;4;1;5;6;1$v"
+            ),
+            vec![CSI::Edit(Edit::CopyRectangularArea {
+                top: OneBased::new(1),
+                left: OneBased::new(2),
+                bottom: OneBased::new(3),
+                right: OneBased::new(4),
+                source_page: OneBased::new(1),
+                dest_top: OneBased::new(5),
+                dest_left: OneBased::new(6),
+                dest_page: OneBased::new(1),
+            })]
+        );
+    }
+
     #[test]
     fn cursor() {
         assert_eq!(
@@ -135,6 +135,9 @@ fn is_short_dcs(intermediates: &[u8], byte: u8) -> bool {
     if intermediates == &[b'$'] && byte == b'q' {
         // DECRQSS
         true
+    } else if intermediates == &[b'+'] && byte == b'q' {
+        // XTGETTCAP
+        true
     } else {
         false
     }
@@ -46,6 +46,18 @@ impl Line {
         Self { bits, cells }
     }
 
+    /// Construct a line from a pre-built sequence of cells.
+    /// The cells are used as-is; the caller is responsible for ensuring
+    /// that double-width cells are followed by the appropriate number
+    /// of spacer cells.
+    pub fn from_cells(cells: Vec<Cell>) -> Self {
+        let mut bits = LineBits::DIRTY;
+        if cells.iter().any(|c| c.attrs().hyperlink().is_some()) {
+            bits |= LineBits::HAS_HYPERLINK;
+        }
+        Self { bits, cells }
+    }
+
     pub fn from_text(s: &str, attrs: &CellAttributes) -> Line {
         let mut cells = Vec::new();
 
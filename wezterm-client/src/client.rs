@@ -24,6 +24,10 @@ use std::marker::Unpin;
 use std::net::TcpStream;
 use std::path::Path;
 use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context as TaskContext, Poll};
 use std::thread;
 use std::time::Duration;
 use thiserror::Error;
@@ -34,6 +38,10 @@ enum ReaderMessage {
         promise: Sender<anyhow::Result<Pdu>>,
     },
     Readable,
+    /// Drop the connection and establish a new one
+    Reconnect {
+        reason: String,
+    },
 }
 
 #[derive(Clone)]
@@ -41,6 +49,10 @@ pub struct Client {
     sender: Sender<ReaderMessage>,
     local_domain_id: DomainId,
     pub is_reconnectable: bool,
+    /// The optional protocol features advertised by the server
+    peer_capabilities: Arc<Mutex<Vec<String>>>,
+    /// Whether the server only permits us to view its panes
+    read_only: Arc<AtomicBool>,
 }
 
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
@@ -211,7 +223,10 @@ async fn client_thread_async(
         map: HashMap::new(),
     };
 
-    let mut stream = reconnectable.take_stream().unwrap();
+    let mut stream = MeteredStream {
+        inner: reconnectable.take_stream().unwrap(),
+        domain_name: reconnectable.config.name().to_string(),
+    };
 
     loop {
         let rx_msg = rx.recv();
@@ -256,6 +271,10 @@ async fn client_thread_async(
                     return Err(err).context("Error while decoding response pdu");
                 }
             },
+            Ok(ReaderMessage::Reconnect { reason }) => {
+                promises.fail_all(&reason);
+                bail!("{}", reason);
+            }
             Err(_) => {
                 return Err(NotReconnectableError::ClientWasDestroyed.into());
             }
@@ -304,6 +323,65 @@ where
     }
 }
 
+/// Tallies the bytes flowing to and from the server so that the
+/// bandwidth used by each domain can be reported in the metrics.
+#[derive(Debug)]
+struct MeteredStream {
+    inner: Box<dyn AsyncReadAndWrite>,
+    domain_name: String,
+}
+
+impl AsyncRead for MeteredStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let result = Pin::new(&mut self.inner).poll_read(cx, buf);
+        if let Poll::Ready(Ok(len)) = &result {
+            metrics::counter!(
+                "mux.client.bytes.received",
+                *len as u64,
+                "domain" => self.domain_name.clone()
+            );
+        }
+        result
+    }
+}
+
+impl AsyncWrite for MeteredStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let result = Pin::new(&mut self.inner).poll_write(cx, buf);
+        if let Poll::Ready(Ok(len)) = &result {
+            metrics::counter!(
+                "mux.client.bytes.sent",
+                *len as u64,
+                "domain" => self.domain_name.clone()
+            );
+        }
+        result
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_close(cx)
+    }
+}
+
+#[async_trait(?Send)]
+impl AsyncReadAndWrite for MeteredStream {
+    async fn wait_for_readable(&self) -> anyhow::Result<()> {
+        self.inner.wait_for_readable().await
+    }
+}
+
 #[derive(Debug)]
 struct Reconnectable {
     config: ClientDomainConfig,
@@ -390,8 +468,9 @@ impl Reconnectable {
             // need to grow some smarts about whether the disconnect was because
             // we sent CTRL-D to close the last session, or whether it was a network
             // level disconnect, because we will otherwise throw up authentication
-            // dialogs that would be annoying
-            ClientDomainConfig::Ssh(_) => false,
+            // dialogs that would be annoying.  Until then, only do so for
+            // domains that have opted in.
+            ClientDomainConfig::Ssh(ssh) => ssh.reconnect_after_sleep,
         }
     }
 
@@ -425,7 +504,16 @@ impl Reconnectable {
         initial: bool,
         ui: &mut ConnectionUI,
     ) -> anyhow::Result<()> {
-        let sess = ssh_connect_with_ui(&ssh_dom.remote_address, &ssh_dom.username, ui)?;
+        let mut ssh_option = ssh_dom.ssh_option.clone();
+        if ssh_dom.no_agent_auth {
+            ssh_option.insert("IdentitiesOnly".to_string(), "yes".to_string());
+        }
+        let sess = ssh_connect_with_ui(
+            &ssh_dom.remote_address,
+            Some(&ssh_dom.username),
+            &ssh_option,
+            ui,
+        )?;
         let proxy_bin = Self::wezterm_bin_path(&ssh_dom.remote_wezterm_path);
 
         let cmd = if initial {
@@ -470,6 +558,10 @@ impl Reconnectable {
         ui.output_str(&format!("Connect to {}\n", sock_path.display()));
         log::trace!("connect to {}", sock_path.display());
 
+        // Refuse to talk to a server that someone else may have
+        // put in place of ours
+        unix_dom.check_socket_permissions()?;
+
         let stream = match unix_connect_with_retry(&sock_path, false) {
             Ok(stream) => stream,
             Err(e) => {
@@ -577,8 +669,12 @@ impl Reconnectable {
         if let Some(Ok(ssh_params)) = tls_client.ssh_parameters() {
             if self.tls_creds.is_none() {
                 // We need to bootstrap via an ssh session
-                let sess =
-                    ssh_connect_with_ui(&ssh_params.host_and_port, &ssh_params.username, ui)?;
+                let sess = ssh_connect_with_ui(
+                    &ssh_params.host_and_port,
+                    ssh_params.username.as_deref(),
+                    &HashMap::new(),
+                    ui,
+                )?;
 
                 let creds = ui.run_and_log_error(|| {
                     // The `tlscreds` command will start the server if needed and then
@@ -827,12 +923,68 @@ impl Client {
             sender,
             local_domain_id,
             is_reconnectable,
+            peer_capabilities: Arc::new(Mutex::new(vec![])),
+            read_only: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    /// Returns true if the server only permits this client to view
+    /// its panes, either because of how it authenticated or because
+    /// it asked to be read-only
+    pub fn is_read_only(&self) -> bool {
+        self.read_only.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn set_is_read_only(&self, read_only: bool) {
+        self.read_only.store(read_only, Ordering::Relaxed);
+    }
+
+    /// Returns true if both the server and this client support
+    /// the named optional protocol feature
+    pub fn peer_has_capability(&self, capability: &str) -> bool {
+        CODEC_CAPABILITIES.contains(&capability)
+            && self
+                .peer_capabilities
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|c| c == capability)
+    }
+
     pub async fn verify_version_compat(&self, ui: &ConnectionUI) -> anyhow::Result<()> {
+        match self.handshake(Handshake::local()).await {
+            Ok(info) if info.is_compatible() => {
+                log::trace!(
+                    "Server version is {} (codec version {}, capabilities {:?})",
+                    info.version_string,
+                    info.codec_vers,
+                    info.capabilities
+                );
+                *self.peer_capabilities.lock().unwrap() = info.capabilities;
+                return Ok(());
+            }
+            Ok(info) => {
+                let err = IncompatibleVersionError {
+                    version: info.version_string,
+                    codec_vers: info.codec_vers,
+                };
+                ui.output_str(&err.to_string());
+                log::error!("{:?}", err);
+                return Err(err.into());
+            }
+            Err(err) => {
+                // Either the server predates the handshake, in which case
+                // we fall back to the legacy version check below, or it
+                // rejected our version and the error explains why.
+                log::debug!("Handshake failed: {:#}; trying GetCodecVersion", err);
+            }
+        }
+
         match self.get_codec_version(GetCodecVersion {}).await {
-            Ok(info) if info.codec_vers == CODEC_VERSION => {
+            // Servers that predate the handshake have no way to tell us
+            // how old a client they accept, so assume that they can only
+            // talk to their own version
+            Ok(info) if codec_version_is_compatible(info.codec_vers, info.codec_vers) => {
                 log::trace!(
                     "Server version is {} (codec version {})",
                     info.version_string,
@@ -925,6 +1077,20 @@ impl Client {
         Ok(Self::new(local_domain_id, reconnectable))
     }
 
+    /// Asks the client thread to drop the current connection and
+    /// establish a new one, failing any requests that are in flight.
+    /// This has no effect if the connection is not reconnectable.
+    pub fn request_reconnect(&self, reason: &str) {
+        if !self.is_reconnectable {
+            return;
+        }
+        self.sender
+            .try_send(ReaderMessage::Reconnect {
+                reason: reason.to_string(),
+            })
+            .ok();
+    }
+
     pub async fn send_pdu(&self, pdu: Pdu) -> anyhow::Result<Pdu> {
         let (promise, rx) = bounded(1);
         self.sender
@@ -944,6 +1110,9 @@ impl Client {
     rpc!(mouse_event, SendMouseEvent, UnitResponse);
     rpc!(resize, Resize, UnitResponse);
     rpc!(set_zoomed, SetPaneZoomed, UnitResponse);
+    rpc!(set_input_mode, SetPaneInputMode, UnitResponse);
+    rpc!(set_window_position, SetWindowPosition, UnitResponse);
+    rpc!(capture_pane, CapturePane, UnitResponse);
     rpc!(
         get_tab_render_changes,
         GetPaneRenderChanges,
@@ -951,6 +1120,11 @@ impl Client {
     );
     rpc!(get_lines, GetLines, GetLinesResponse);
     rpc!(get_codec_version, GetCodecVersion, GetCodecVersionResponse);
+    rpc!(handshake, Handshake, HandshakeResponse);
+    rpc!(authenticate, Authenticate, AuthenticateResponse);
+    rpc!(set_read_only, SetReadOnly = (), UnitResponse);
+    rpc!(set_client_name, SetClientName, UnitResponse);
+    rpc!(set_log_filters, SetLogFilters, UnitResponse);
     rpc!(get_tls_creds, GetTlsCreds = (), GetTlsCredsResponse);
     rpc!(
         search_scrollback,
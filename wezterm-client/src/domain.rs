@@ -2,13 +2,13 @@ use crate::client::Client;
 use crate::pane::ClientPane;
 use anyhow::{anyhow, bail};
 use async_trait::async_trait;
-use codec::{ListPanesResponse, Spawn, SplitPane};
+use codec::{Authenticate, ListPanesResponse, SetClientName, Spawn, SplitPane};
 use config::keyassignment::SpawnTabDomain;
 use config::{SshDomain, TlsDomainClient, UnixDomain};
 use mux::connui::ConnectionUI;
 use mux::domain::{alloc_domain_id, Domain, DomainId, DomainState};
 use mux::pane::{Pane, PaneId};
-use mux::tab::{SplitDirection, Tab, TabId};
+use mux::tab::{SplitRequest, Tab, TabId};
 use mux::window::WindowId;
 use mux::Mux;
 use portable_pty::{CommandBuilder, PtySize};
@@ -17,6 +17,11 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How long to wait for the server to respond when checking the
+/// connection after the system resumes from sleep
+const RESUME_HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
 
 pub struct ClientInner {
     pub client: Client,
@@ -141,6 +146,23 @@ impl ClientDomainConfig {
             ClientDomainConfig::Ssh(ssh) => ssh.connect_automatically,
         }
     }
+
+    /// The shared secret to present to the server, if any
+    pub fn auth_token(&self) -> Option<&str> {
+        match self {
+            ClientDomainConfig::Unix(_) | ClientDomainConfig::Ssh(_) => None,
+            ClientDomainConfig::Tls(tls) => tls.auth_token.as_deref(),
+        }
+    }
+
+    pub fn reconnect_after_sleep(&self) -> bool {
+        match self {
+            // The socket is local, so it survives the system sleeping
+            ClientDomainConfig::Unix(_) => false,
+            ClientDomainConfig::Tls(tls) => tls.reconnect_after_sleep,
+            ClientDomainConfig::Ssh(ssh) => ssh.reconnect_after_sleep,
+        }
+    }
 }
 
 impl ClientInner {
@@ -166,6 +188,8 @@ pub struct ClientDomain {
     label: String,
     inner: RefCell<Option<Arc<ClientInner>>>,
     local_domain_id: DomainId,
+    /// If true, ask the server to only let us view its panes
+    read_only: bool,
 }
 
 impl ClientDomain {
@@ -177,6 +201,37 @@ impl ClientDomain {
             label,
             inner: RefCell::new(None),
             local_domain_id,
+            read_only: false,
+        }
+    }
+
+    /// Attach as a viewer, which can watch the panes of the server
+    /// but not send input to them or spawn new ones
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+        self.label = if read_only {
+            format!("{} (read-only)", self.config.label())
+        } else {
+            self.config.label()
+        };
+    }
+
+    /// Returns an error if the client may only view panes
+    fn check_not_read_only(inner: &ClientInner) -> anyhow::Result<()> {
+        if inner.client.is_read_only() {
+            bail!(
+                "domain {} is attached read-only and cannot spawn panes",
+                inner.local_domain_id
+            );
+        }
+        Ok(())
+    }
+
+    /// Adds the environment configured for ssh domains to `command`
+    fn apply_cmd_defaults(&self, command: Option<CommandBuilder>) -> Option<CommandBuilder> {
+        match &self.config {
+            ClientDomainConfig::Ssh(ssh) => ssh.apply_cmd_defaults(command),
+            _ => command,
         }
     }
 
@@ -219,6 +274,18 @@ impl ClientDomain {
     pub async fn reattach(domain_id: DomainId, ui: ConnectionUI) -> anyhow::Result<()> {
         let inner = Self::get_client_inner_for_domain(domain_id)?;
 
+        let config = Mux::get()
+            .unwrap()
+            .get_domain(domain_id)
+            .and_then(|domain| {
+                domain
+                    .downcast_ref::<Self>()
+                    .map(|d| (d.config.clone(), d.read_only))
+            });
+        if let Some((config, read_only)) = config {
+            Self::establish_access(&inner.client, &config, read_only, &ui).await?;
+        }
+
         let panes = inner.client.list_panes().await?;
         Self::process_pane_list(inner, panes)?;
 
@@ -226,6 +293,46 @@ impl ClientDomain {
         Ok(())
     }
 
+    /// Called when the system resumes from sleep.  The connection may
+    /// have been dropped while we were asleep without us noticing, so
+    /// check that the server still responds and otherwise reconnect
+    /// right away, rather than leaving the panes unresponsive until
+    /// the read timeout expires.
+    pub fn check_connection_after_resume(&self) {
+        if !self.config.reconnect_after_sleep() {
+            return;
+        }
+        let inner = match self.inner() {
+            Some(inner) => inner,
+            None => return,
+        };
+        let name = self.config.name().to_string();
+        promise::spawn::spawn(async move {
+            let ping = async { inner.client.ping().await.map(|_| ()) };
+            let timeout = async {
+                smol::Timer::after(RESUME_HEALTH_CHECK_TIMEOUT).await;
+                Err(anyhow!(
+                    "no response within {:?}",
+                    RESUME_HEALTH_CHECK_TIMEOUT
+                ))
+            };
+            match smol::future::or(ping, timeout).await {
+                Ok(()) => log::info!("domain {} is still connected after resume", name),
+                Err(err) => {
+                    log::warn!(
+                        "domain {} connection check after resume failed: {:#}; reconnecting",
+                        name,
+                        err
+                    );
+                    inner
+                        .client
+                        .request_reconnect("connection was lost while the system was asleep");
+                }
+            }
+        })
+        .detach();
+    }
+
     pub async fn resync(&self) -> anyhow::Result<()> {
         if let Some(inner) = self.inner.borrow().as_ref() {
             let panes = inner.client.list_panes().await?;
@@ -234,6 +341,53 @@ impl ClientDomain {
         Ok(())
     }
 
+    /// Presents the configured token, if any, to a server that has
+    /// just been connected to, and then asks to be read-only if
+    /// `read_only` is true
+    async fn establish_access(
+        client: &Client,
+        config: &ClientDomainConfig,
+        read_only: bool,
+        ui: &ConnectionUI,
+    ) -> anyhow::Result<()> {
+        let mut granted_read_only = false;
+        if let Some(token) = config.auth_token() {
+            ui.output_str("Authenticating\n");
+            let response = client
+                .authenticate(Authenticate {
+                    token: token.to_string(),
+                })
+                .await?;
+            if response.read_only {
+                ui.output_str("The server only permits viewing its panes\n");
+            }
+            granted_read_only = response.read_only;
+        }
+        if read_only && !granted_read_only {
+            ui.output_str("Attaching read-only\n");
+            client.set_read_only().await?;
+        }
+        client.set_is_read_only(read_only || granted_read_only);
+        if client.peer_has_capability("pane_writer") {
+            client
+                .set_client_name(SetClientName {
+                    name: Self::client_name(),
+                })
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Returns the name by which the other clients of the server
+    /// will know this one when it sends input to a shared pane
+    fn client_name() -> String {
+        let user = config::username_from_env().unwrap_or_else(|_| "unknown".to_string());
+        match hostname::get() {
+            Ok(host) => format!("{}@{}", user, host.to_string_lossy()),
+            Err(_) => user,
+        }
+    }
+
     fn process_pane_list(inner: Arc<ClientInner>, panes: ListPanesResponse) -> anyhow::Result<()> {
         let mux = Mux::get().expect("to be called on main thread");
         log::debug!("ListPanes result {:#?}", panes);
@@ -338,6 +492,7 @@ impl ClientDomain {
 
         let inner = Arc::new(ClientInner::new(domain_id, client));
         *domain.inner.borrow_mut() = Some(Arc::clone(&inner));
+        crate::resume::start_resume_monitor();
 
         Self::process_pane_list(inner, panes)?;
 
@@ -369,6 +524,8 @@ impl Domain for ClientDomain {
         let inner = self
             .inner()
             .ok_or_else(|| anyhow!("domain is not attached"))?;
+        Self::check_not_read_only(&inner)?;
+        let command = self.apply_cmd_defaults(command);
         let result = inner
             .client
             .spawn(Spawn {
@@ -405,11 +562,12 @@ impl Domain for ClientDomain {
         command_dir: Option<String>,
         tab_id: TabId,
         pane_id: PaneId,
-        direction: SplitDirection,
+        request: SplitRequest,
     ) -> anyhow::Result<Rc<dyn Pane>> {
         let inner = self
             .inner()
             .ok_or_else(|| anyhow!("domain is not attached"))?;
+        Self::check_not_read_only(&inner)?;
 
         let mux = Mux::get().unwrap();
 
@@ -423,12 +581,13 @@ impl Domain for ClientDomain {
             .downcast_ref::<ClientPane>()
             .ok_or_else(|| anyhow!("pane_id {} is not a ClientPane", pane_id))?;
 
+        let command = self.apply_cmd_defaults(command);
         let result = inner
             .client
             .split_pane(SplitPane {
                 domain: SpawnTabDomain::CurrentPaneDomain,
                 pane_id: pane.remote_tab_id,
-                direction,
+                request,
                 command,
                 command_dir,
             })
@@ -451,7 +610,7 @@ impl Domain for ClientDomain {
             None => anyhow::bail!("invalid pane id {}", pane_id),
         };
 
-        tab.split_and_insert(pane_index, direction, Rc::clone(&pane))
+        tab.split_and_insert(pane_index, request, Rc::clone(&pane))
             .ok();
 
         mux.add_pane(&pane)?;
@@ -462,6 +621,8 @@ impl Domain for ClientDomain {
     async fn attach(&self) -> anyhow::Result<()> {
         let domain_id = self.local_domain_id;
         let config = self.config.clone();
+        let auth_config = self.config.clone();
+        let read_only = self.read_only;
 
         let activity = mux::activity::Activity::new();
         let ui = ConnectionUI::new();
@@ -483,6 +644,7 @@ impl Domain for ClientDomain {
 
                 ui.output_str("Checking server version\n");
                 client.verify_version_compat(&ui).await?;
+                Self::establish_access(&client, &auth_config, read_only, &ui).await?;
 
                 ui.output_str("Version check OK!  Requesting pane list...\n");
                 let panes = client.list_panes().await?;
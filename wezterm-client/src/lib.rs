@@ -6,3 +6,4 @@ use uds_windows::UnixStream;
 pub mod client;
 pub mod domain;
 pub mod pane;
+mod resume;
@@ -5,11 +5,13 @@ use anyhow::bail;
 use async_trait::async_trait;
 use codec::*;
 use config::configuration;
+use config::keyassignment::PaneInputMode;
 use filedescriptor::Pipe;
 use mux::domain::DomainId;
 use mux::pane::{alloc_pane_id, Pane, PaneId, Pattern, SearchResult};
 use mux::renderable::{RenderableDimensions, StableCursorPosition};
 use mux::tab::TabId;
+use mux::{Mux, MuxNotification};
 use portable_pty::PtySize;
 use rangeset::RangeSet;
 use ratelim::RateLimiter;
@@ -34,6 +36,8 @@ pub struct ClientPane {
     mouse: Rc<RefCell<MouseState>>,
     clipboard: RefCell<Option<Arc<dyn Clipboard>>>,
     mouse_grabbed: RefCell<bool>,
+    /// The other client that most recently sent input to this pane
+    collaborator: RefCell<Option<String>>,
 }
 
 impl ClientPane {
@@ -88,6 +92,7 @@ impl ClientPane {
             reader,
             clipboard: RefCell::new(None),
             mouse_grabbed: RefCell::new(false),
+            collaborator: RefCell::new(None),
         }
     }
 
@@ -113,6 +118,13 @@ impl ClientPane {
                     log::error!("ClientPane: Ignoring SetClipboard request {:?}", clipboard);
                 }
             },
+            Pdu::PaneWriterChanged(PaneWriterChanged { writer, .. }) => {
+                *self.collaborator.borrow_mut() = writer;
+                // Repaint so that the change is visible right away
+                if let Some(mux) = Mux::get() {
+                    mux.notify(MuxNotification::PaneOutput(self.local_pane_id));
+                }
+            }
             _ => bail!("unhandled unilateral pdu: {:?}", pdu),
         };
         Ok(())
@@ -155,6 +167,9 @@ impl Pane for ClientPane {
     }
 
     fn send_paste(&self, text: &str) -> anyhow::Result<()> {
+        if self.is_read_only() {
+            return Ok(());
+        }
         let client = Arc::clone(&self.client);
         let remote_pane_id = self.remote_pane_id;
         self.renderable
@@ -192,6 +207,9 @@ impl Pane for ClientPane {
     }
 
     fn set_zoomed(&self, zoomed: bool) {
+        if self.is_read_only() {
+            return;
+        }
         let render = self.renderable.borrow();
         let mut inner = render.inner.borrow_mut();
         let client = Arc::clone(&self.client);
@@ -213,7 +231,34 @@ impl Pane for ClientPane {
         inner.update_last_send();
     }
 
+    fn set_input_mode(&self, mode: PaneInputMode) {
+        if self.is_read_only() {
+            return;
+        }
+        let client = Arc::clone(&self.client);
+        let remote_pane_id = self.remote_pane_id;
+        promise::spawn::spawn(async move {
+            client
+                .client
+                .set_input_mode(SetPaneInputMode {
+                    pane_id: remote_pane_id,
+                    mode,
+                })
+                .await
+        })
+        .detach();
+    }
+
+    fn input_mode_is_remote(&self) -> bool {
+        true
+    }
+
     fn resize(&self, size: PtySize) -> anyhow::Result<()> {
+        // The size is decided by the clients that are allowed to
+        // interact with the pane
+        if self.is_read_only() {
+            return Ok(());
+        }
         let render = self.renderable.borrow();
         let mut inner = render.inner.borrow_mut();
 
@@ -262,6 +307,9 @@ impl Pane for ClientPane {
     }
 
     fn key_down(&self, key: KeyCode, mods: KeyModifiers) -> anyhow::Result<()> {
+        if self.is_read_only() {
+            return Ok(());
+        }
         let input_serial;
         {
             let renderable = self.renderable.borrow();
@@ -295,6 +343,10 @@ impl Pane for ClientPane {
     }
 
     fn kill(&self) {
+        // Closing the pane locally just stops viewing it
+        if self.is_read_only() {
+            return;
+        }
         let client = Arc::clone(&self.client);
         let remote_pane_id = self.remote_pane_id;
         promise::spawn::spawn(async move {
@@ -309,6 +361,9 @@ impl Pane for ClientPane {
     }
 
     fn mouse_event(&self, event: MouseEvent) -> anyhow::Result<()> {
+        if self.is_read_only() {
+            return Ok(());
+        }
         self.mouse.borrow_mut().append(event);
         if MouseState::next(Rc::clone(&self.mouse)) {
             self.renderable
@@ -324,11 +379,29 @@ impl Pane for ClientPane {
         self.renderable.borrow().inner.borrow().dead
     }
 
+    fn is_read_only(&self) -> bool {
+        self.client.client.is_read_only()
+    }
+
+    fn collaborator(&self) -> Option<String> {
+        self.collaborator.borrow().clone()
+    }
+
     fn palette(&self) -> ColorPalette {
-        let tardy = self.renderable.borrow().inner.borrow().is_tardy();
+        let (tardy, cursor_color) = {
+            let renderable = self.renderable.borrow();
+            let inner = renderable.inner.borrow();
+            (inner.is_tardy(), inner.cursor_color)
+        };
 
         let config = configuration();
-        let palette: ColorPalette = config.resolved_palette.clone().into();
+        let mut palette: ColorPalette = config.resolved_palette.clone().into();
+        if let Some(color) = cursor_color {
+            // Matches the handling of OSC 12 by the terminal, which
+            // sets the border to the same color
+            palette.cursor_bg = color;
+            palette.cursor_border = color;
+        }
 
         if tardy {
             palette.grey_out()
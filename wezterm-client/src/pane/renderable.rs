@@ -16,7 +16,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use termwiz::cell::{Cell, CellAttributes, Underline};
-use termwiz::color::AnsiColor;
+use termwiz::color::{AnsiColor, RgbColor};
 use url::Url;
 use wezterm_term::{KeyCode, KeyModifiers};
 use wezterm_term::{Line, StableRowIndex};
@@ -53,6 +53,36 @@ impl LineEntry {
     }
 }
 
+/// Returns the rows of scrollback, a page either side of `lines`, that
+/// are worth fetching along with them when the user scrolls back into
+/// history, or None if `lines` are not in the scrollback.
+fn prefetch_range(
+    lines: &Range<StableRowIndex>,
+    dims: &RenderableDimensions,
+) -> Option<Range<StableRowIndex>> {
+    if lines.start >= dims.physical_top {
+        // Rows in the physical screen are pushed to us as they change
+        return None;
+    }
+    let page = dims.viewport_rows as StableRowIndex;
+    let start = (lines.start - page).max(dims.scrollback_top);
+    let end = (lines.end + page).min(dims.physical_top);
+    Some(start..end)
+}
+
+/// Removes the cached rows that precede `first_row`, which have been
+/// trimmed from the scrollback by the server
+fn discard_rows_before(lines: &mut LruCache<StableRowIndex, LineEntry>, first_row: StableRowIndex) {
+    let discard: Vec<StableRowIndex> = lines
+        .iter()
+        .map(|(&stable_row, _)| stable_row)
+        .filter(|&stable_row| stable_row < first_row)
+        .collect();
+    for stable_row in discard {
+        lines.pop(&stable_row);
+    }
+}
+
 pub struct RenderableInner {
     client: Arc<ClientInner>,
     remote_pane_id: TabId,
@@ -68,6 +98,10 @@ pub struct RenderableInner {
     lines: LruCache<StableRowIndex, LineEntry>,
     pub title: String,
     pub working_dir: Option<Url>,
+    /// The cursor color that the remote application selected
+    pub cursor_color: Option<RgbColor>,
+    /// The bonus lines most recently received from the server
+    bonus_lines: LineDeltaBase,
 
     fetch_limiter: RateLimiter,
 
@@ -107,6 +141,8 @@ impl RenderableInner {
             lines: LruCache::new(configuration().scrollback_lines),
             title: title.to_string(),
             working_dir: None,
+            cursor_color: None,
+            bonus_lines: LineDeltaBase::default(),
             fetch_limiter,
             last_send_time: now,
             last_recv_time: now,
@@ -308,7 +344,8 @@ impl RenderableInner {
         for r in delta.dirty_lines {
             dirty.add_range(r.clone());
         }
-        if delta.cursor_position != self.cursor_position {
+        if delta.cursor_position != self.cursor_position || delta.cursor_color != self.cursor_color
+        {
             dirty.add(self.cursor_position.y);
             // But note that the server may have sent this in bonus_lines;
             // we'll address that below
@@ -337,14 +374,30 @@ impl RenderableInner {
         {
             self.cursor_position = delta.cursor_position;
         }
+        if delta.dimensions.cols != self.dimensions.cols {
+            // Another client resized the pane, so the server has
+            // rewrapped the lines that we have cached
+            self.make_all_stale();
+        }
+        if delta.dimensions.scrollback_top > self.dimensions.scrollback_top {
+            discard_rows_before(&mut self.lines, delta.dimensions.scrollback_top);
+        }
         self.dimensions = delta.dimensions;
         self.title = delta.title;
         self.working_dir = delta.working_dir.map(Into::into);
+        self.cursor_color = delta.cursor_color;
 
         let config = configuration();
-        for (stable_row, line) in delta.bonus_lines.lines() {
-            self.put_line(stable_row, line, &config, None);
-            dirty.remove(stable_row);
+        for (stable_row, line) in delta.bonus_lines.lines_with_deltas(&mut self.bonus_lines) {
+            match line {
+                Some(line) => {
+                    self.put_line(stable_row, line, &config, None);
+                    dirty.remove(stable_row);
+                }
+                // We don't have the line that this was a delta of,
+                // so fetch the whole line instead
+                None => dirty.add(stable_row),
+            }
         }
 
         if !dirty.is_empty() {
@@ -473,6 +526,29 @@ impl RenderableInner {
         self.lines.put(stable_row, entry);
     }
 
+    /// When the user scrolls back into history that we haven't fetched yet,
+    /// also fetch a page of scrollback either side of the rows that were
+    /// requested.  That way the next scroll is satisfied from the cache
+    /// rather than waiting on a round trip to the server.
+    fn prefetch_adjacent_scrollback(
+        &mut self,
+        lines: Range<StableRowIndex>,
+        to_fetch: &mut RangeSet<StableRowIndex>,
+        now: Instant,
+    ) {
+        let range = match prefetch_range(&lines, &self.dimensions) {
+            Some(range) => range,
+            None => return,
+        };
+
+        for idx in range {
+            if !self.lines.contains(&idx) {
+                to_fetch.add(idx);
+                self.lines.put(idx, LineEntry::Fetching(now));
+            }
+        }
+    }
+
     fn schedule_fetch_lines(&mut self, to_fetch: RangeSet<StableRowIndex>, now: Instant) {
         if to_fetch.is_empty() {
             return;
@@ -672,6 +748,9 @@ impl RenderableState {
             inner.lines.put(idx, entry);
         }
 
+        if !to_fetch.is_empty() {
+            inner.prefetch_adjacent_scrollback(lines.clone(), &mut to_fetch, now);
+        }
         inner.schedule_fetch_lines(to_fetch, now);
         (lines.start, result)
     }
@@ -721,3 +800,41 @@ impl RenderableState {
         self.inner.borrow().dimensions
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn dims() -> RenderableDimensions {
+        RenderableDimensions {
+            cols: 80,
+            viewport_rows: 10,
+            scrollback_rows: 110,
+            physical_top: 100,
+            scrollback_top: 0,
+        }
+    }
+
+    #[test]
+    fn prefetch_ranges() {
+        let dims = dims();
+        // The physical screen is never prefetched
+        assert_eq!(prefetch_range(&(100..110), &dims), None);
+        assert_eq!(prefetch_range(&(50..60), &dims), Some(40..70));
+        // Clamped to the scrollback
+        assert_eq!(prefetch_range(&(5..15), &dims), Some(0..25));
+        assert_eq!(prefetch_range(&(95..105), &dims), Some(85..100));
+    }
+
+    #[test]
+    fn discard_trimmed_rows() {
+        let mut lines = LruCache::new(100);
+        for stable_row in 0..10 {
+            lines.put(stable_row, LineEntry::Line(Line::with_width(80)));
+        }
+        discard_rows_before(&mut lines, 4);
+        let mut remaining: Vec<StableRowIndex> = lines.iter().map(|(&row, _)| row).collect();
+        remaining.sort();
+        assert_eq!(remaining, (4..10).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,73 @@
+//! Detects that the system has resumed from sleep, so that the
+//! connections to mux servers can be checked right away rather than
+//! leaving their panes unresponsive until the connection times out.
+//! There is no portable notification for this, but the monitoring
+//! thread is suspended along with everything else, so a resume shows
+//! up as the wall clock having advanced much further than expected
+//! between two of its samples.
+use crate::domain::ClientDomain;
+use mux::Mux;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
+
+/// How often the wall clock is sampled
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How much later than expected a sample must be taken for the
+/// system to be considered to have been asleep
+const SLEEP_THRESHOLD: Duration = Duration::from_secs(30);
+
+/// Returns true if `elapsed` wall clock time between samples implies
+/// that the system was asleep
+fn slept_between_samples(elapsed: Duration) -> bool {
+    elapsed > SAMPLE_INTERVAL + SLEEP_THRESHOLD
+}
+
+fn check_domains() {
+    let mux = match Mux::get() {
+        Some(mux) => mux,
+        None => return,
+    };
+    for domain in mux.iter_domains() {
+        if let Some(client_domain) = domain.downcast_ref::<ClientDomain>() {
+            client_domain.check_connection_after_resume();
+        }
+    }
+}
+
+fn resume_monitor() {
+    let mut last_sample = SystemTime::now();
+    loop {
+        std::thread::sleep(SAMPLE_INTERVAL);
+        let now = SystemTime::now();
+        // The clock may have been set backwards, in which case
+        // there is nothing to learn from this sample
+        let elapsed = now.duration_since(last_sample).unwrap_or_default();
+        last_sample = now;
+
+        if slept_between_samples(elapsed) {
+            log::info!(
+                "system appears to have resumed after {:?}; checking mux connections",
+                elapsed
+            );
+            promise::spawn::spawn_into_main_thread(async move {
+                check_domains();
+            })
+            .detach();
+        }
+    }
+}
+
+/// Starts watching for the system resuming from sleep, if that
+/// isn't already happening
+pub fn start_resume_monitor() {
+    static MONITOR_STARTED: AtomicBool = AtomicBool::new(false);
+    if let Ok(false) =
+        MONITOR_STARTED.compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed)
+    {
+        std::thread::Builder::new()
+            .name("resume_monitor".into())
+            .spawn(resume_monitor)
+            .expect("failed to spawn resume monitor thread");
+    }
+}
@@ -202,6 +202,18 @@ impl Face {
         ft_result(unsafe { FT_Select_Size(self.face, idx as i32) }, ()).context("FT_Select_Size")
     }
 
+    /// Shifts the glyphs that are subsequently loaded `x` pixels to
+    /// the right.  This has no effect on bitmap glyphs.
+    pub fn set_subpixel_offset(&mut self, x: f64) {
+        let mut delta = FT_Vector {
+            x: (x * 64.0).round() as FT_Pos,
+            y: 0,
+        };
+        unsafe {
+            FT_Set_Transform(self.face, ptr::null_mut(), &mut delta);
+        }
+    }
+
     pub fn load_and_render_glyph(
         &mut self,
         glyph_index: FT_UInt,
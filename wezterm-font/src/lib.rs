@@ -8,6 +8,7 @@ use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::rc::{Rc, Weak};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use wezterm_term::CellAttributes;
 use window::default_dpi;
@@ -26,12 +27,75 @@ pub mod units;
 pub mod fcwrap;
 
 pub use crate::rasterizer::RasterizedGlyph;
-pub use crate::shaper::{FallbackIdx, FontMetrics, GlyphInfo};
+pub use crate::shaper::{FallbackIdx, FontMetrics, GlyphInfo, MISSING_GLYPH};
 
 #[derive(Debug, Error)]
 #[error("Font fallback recalculated")]
 pub struct ClearShapeCache {}
 
+/// The minimum amount of time between warnings about missing glyphs
+const MISSING_GLYPH_WARNING_INTERVAL: Duration = Duration::from_secs(5);
+
+lazy_static::lazy_static! {
+    static ref MISSING_GLYPH_WARNINGS: Mutex<MissingGlyphWarnings> =
+        Mutex::new(MissingGlyphWarnings::default());
+}
+
+/// Tracks which codepoints we've already complained about, so that
+/// text that is repeatedly reshaped doesn't flood the log
+#[derive(Default)]
+struct MissingGlyphWarnings {
+    reported: HashSet<char>,
+    pending: Vec<char>,
+    last_warning: Option<Instant>,
+}
+
+/// Log a warning that none of `handles` has a glyph for `codepoints`.
+/// Each codepoint is reported once, and warnings are logged no more
+/// often than `MISSING_GLYPH_WARNING_INTERVAL`; codepoints that are
+/// held back are included in the next warning.
+fn warn_missing_glyphs(codepoints: &[char], handles: &[FontDataHandle]) {
+    let mut warnings = MISSING_GLYPH_WARNINGS.lock().unwrap();
+    for c in codepoints {
+        if !warnings.reported.contains(c) && !warnings.pending.contains(c) {
+            warnings.pending.push(*c);
+        }
+    }
+    if warnings.pending.is_empty() {
+        return;
+    }
+
+    let now = Instant::now();
+    if let Some(last) = warnings.last_warning {
+        if now.duration_since(last) < MISSING_GLYPH_WARNING_INTERVAL {
+            return;
+        }
+    }
+    warnings.last_warning.replace(now);
+
+    let missing = warnings.pending.split_off(0);
+    warnings.reported.extend(missing.iter().copied());
+
+    let codepoints = missing
+        .iter()
+        .map(|c| format!("U+{:04X}", *c as u32))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let fonts = handles
+        .iter()
+        .map(|handle| handle.name_or_path_str().into_owned())
+        .collect::<Vec<_>>()
+        .join(", ");
+    log::warn!(
+        "No font has a glyph for {}, so a placeholder will be shown instead. \
+         Fonts searched: {}. \
+         Adding a font that covers these codepoints to your font \
+         configuration will resolve this.",
+        codepoints,
+        fonts
+    );
+}
+
 pub struct LoadedFont {
     rasterizers: RefCell<HashMap<FallbackIdx, Box<dyn FontRasterizer>>>,
     handles: RefCell<Vec<FontDataHandle>>,
@@ -48,6 +112,12 @@ impl LoadedFont {
         self.metrics
     }
 
+    /// Returns the fonts that make up the fallback sequence, in the
+    /// order that they are searched for glyphs
+    pub fn clone_handles(&self) -> Vec<FontDataHandle> {
+        self.handles.borrow().clone()
+    }
+
     fn insert_fallback_handles(&self, extra_handles: Vec<FontDataHandle>) -> anyhow::Result<bool> {
         let mut loaded = false;
         {
@@ -98,10 +168,24 @@ impl LoadedFont {
             .borrow()
             .shape(text, self.font_size, self.dpi, &mut no_glyphs);
 
+        let mut missing = vec![];
+        if let Ok(glyphs) = &result {
+            for glyph in glyphs {
+                if glyph.glyph_pos == MISSING_GLYPH {
+                    let text = text.get(glyph.cluster as usize..).unwrap_or("");
+                    if let Some(c) = text.chars().next() {
+                        missing.push(c);
+                    }
+                }
+            }
+        }
+
         if !no_glyphs.is_empty() {
             if let Some(font_config) = self.font_config.upgrade() {
                 font_config.schedule_fallback_resolve(
                     no_glyphs,
+                    missing,
+                    self.handles.borrow().clone(),
                     &self.pending_fallback,
                     completion,
                 );
@@ -117,14 +201,16 @@ impl LoadedFont {
             .metrics_for_idx(font_idx, self.font_size, self.dpi)
     }
 
+    /// Rasterizes the glyph, shifted `subpixel_x` pixels to the right
     pub fn rasterize_glyph(
         &self,
         glyph_pos: u32,
         fallback: FallbackIdx,
+        subpixel_x: f64,
     ) -> anyhow::Result<RasterizedGlyph> {
         let mut rasterizers = self.rasterizers.borrow_mut();
         if let Some(raster) = rasterizers.get(&fallback) {
-            raster.rasterize_glyph(glyph_pos, self.font_size, self.dpi)
+            raster.rasterize_glyph(glyph_pos, self.font_size, self.dpi, subpixel_x)
         } else {
             let raster_selection = self
                 .font_config
@@ -133,7 +219,7 @@ impl LoadedFont {
                     c.config.borrow().font_rasterizer
                 });
             let raster = new_rasterizer(raster_selection, &(self.handles.borrow())[fallback])?;
-            let result = raster.rasterize_glyph(glyph_pos, self.font_size, self.dpi);
+            let result = raster.rasterize_glyph(glyph_pos, self.font_size, self.dpi, subpixel_x);
             rasterizers.insert(fallback, raster);
             result
         }
@@ -186,13 +272,28 @@ impl FontConfigInner {
         Ok(())
     }
 
+    /// Search for fonts that have glyphs for `no_glyphs` in the background.
+    /// `missing` holds the subset of those that couldn't be rendered by any
+    /// of `handles`; a warning is logged for those that remain missing
+    /// once the search is complete.
     fn schedule_fallback_resolve<F: FnOnce() + Send + Sync + 'static>(
         &self,
         mut no_glyphs: Vec<char>,
+        missing: Vec<char>,
+        handles: Vec<FontDataHandle>,
         pending: &Arc<Mutex<Vec<FontDataHandle>>>,
         completion: F,
     ) {
         let mut ng = self.no_glyphs.borrow_mut();
+
+        // We already searched for these and didn't find anything
+        // that helps, so there's no sense in waiting to report them
+        let (searched, missing): (Vec<char>, Vec<char>) =
+            missing.into_iter().partition(|c| ng.contains(c));
+        if !searched.is_empty() {
+            warn_missing_glyphs(&searched, &handles);
+        }
+
         no_glyphs.retain(|c| !ng.contains(c));
         for c in &no_glyphs {
             ng.insert(*c);
@@ -240,6 +341,8 @@ impl FontConfigInner {
                 let mut pending = pending.lock().unwrap();
                 pending.append(&mut extra_handles);
                 completion();
+            } else if !missing.is_empty() {
+                warn_missing_glyphs(&missing, &handles);
             }
         });
     }
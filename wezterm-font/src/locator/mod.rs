@@ -30,7 +30,7 @@ pub enum FontDataHandle {
 }
 
 impl FontDataHandle {
-    fn name_or_path_str(&self) -> Cow<str> {
+    pub(crate) fn name_or_path_str(&self) -> Cow<str> {
         match self {
             Self::OnDisk { path, .. } => path.to_string_lossy(),
             Self::Memory { name, .. } => Cow::Borrowed(name),
@@ -42,6 +42,15 @@ impl FontDataHandle {
             Self::Memory { index, .. } => *index,
         }
     }
+
+    /// Returns a human readable description of where the font data
+    /// resides, for use in diagnostic output
+    pub fn diagnostic_string(&self) -> String {
+        match self {
+            Self::OnDisk { path, index } => format!("{}, index={}", path.display(), index),
+            Self::Memory { name, index, .. } => format!("{} (in memory), index={}", name, index),
+        }
+    }
 }
 
 impl Eq for FontDataHandle {}
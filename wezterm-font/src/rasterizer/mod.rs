@@ -17,13 +17,16 @@ pub struct RasterizedGlyph {
 }
 
 /// Rasterizes the specified glyph index in the associated font
-/// and returns the generated bitmap
+/// and returns the generated bitmap.  The glyph is shifted `subpixel_x`
+/// pixels to the right, which is reflected in the bitmap and, once
+/// the shift crosses a pixel boundary, in `bearing_x`.
 pub trait FontRasterizer {
     fn rasterize_glyph(
         &self,
         glyph_pos: u32,
         size: f64,
         dpi: u32,
+        subpixel_x: f64,
     ) -> anyhow::Result<RasterizedGlyph>;
 }
 
@@ -1,6 +1,6 @@
 use crate::locator::FontDataHandle;
 use crate::parser::*;
-use crate::shaper::{FallbackIdx, FontMetrics, FontShaper, GlyphInfo};
+use crate::shaper::{make_missing_glyphs, FallbackIdx, FontMetrics, FontShaper, GlyphInfo};
 use crate::units::*;
 use allsorts::binary::read::{ReadScope, ReadScopeOwned};
 use allsorts::font_data_impl::read_cmap_subtable;
@@ -502,30 +502,14 @@ impl AllsortsShaper {
                 );
             }
             None => {
-                // Note: since we added a last resort font, this case shouldn't
-                // ever get hit in practice.
-                // We ran out of fallback fonts, so use a replacement
-                // character that is likely to be in one of those fonts.
-                let mut alt_text = String::new();
+                // We ran out of fallback fonts, so leave it to the
+                // renderer to draw a placeholder for this text
                 for c in s.chars() {
                     no_glyphs.push(c);
-                    alt_text.push('?');
                 }
-                if alt_text == s {
-                    // We already tried to fallback to this and failed
-                    return Err(anyhow!("could not fallback to ? character"));
-                }
-                return self.shape_into(
-                    0,
-                    &alt_text,
-                    slice_index,
-                    script,
-                    lang,
-                    font_size,
-                    dpi,
-                    results,
-                    no_glyphs,
-                );
+                let metrics = self.metrics_for_idx(0, font_size, dpi)?;
+                results.append(&mut make_missing_glyphs(s, slice_index, &metrics));
+                return Ok(());
             }
         };
 
@@ -1,7 +1,7 @@
 use crate::ftwrap;
 use crate::hbwrap as harfbuzz;
 use crate::locator::FontDataHandle;
-use crate::shaper::{FallbackIdx, FontMetrics, FontShaper, GlyphInfo};
+use crate::shaper::{make_missing_glyphs, FallbackIdx, FontMetrics, FontShaper, GlyphInfo};
 use crate::units::*;
 use anyhow::anyhow;
 use config::ConfigHandle;
@@ -274,6 +274,12 @@ impl HarfbuzzShaper {
                 let mut shape = match self.do_shape(font_idx + 1, substr, font_size, dpi, no_glyphs)
                 {
                     Ok(shape) => Ok(shape),
+                    Err(e) if e.downcast_ref::<NoMoreFallbacksError>().is_some() => {
+                        // None of the fonts have a glyph for this text,
+                        // so leave it to the renderer to draw a placeholder
+                        let metrics = self.metrics_for_idx(0, font_size, dpi)?;
+                        Ok(make_missing_glyphs(substr, 0, &metrics))
+                    }
                     Err(e) => {
                         error!("{:?} for {:?}", e, substr);
                         self.do_shape(0, &make_question_string(substr), font_size, dpi, no_glyphs)
@@ -636,4 +642,42 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn missing_glyphs() {
+        let db = FontDatabase::with_built_in().unwrap();
+        let handle = db
+            .resolve(&FontAttributes {
+                family: "JetBrains Mono".into(),
+                bold: false,
+                is_fallback: false,
+                is_synthetic: false,
+                italic: false,
+            })
+            .unwrap()
+            .clone();
+
+        let config = config::configuration();
+
+        let shaper = HarfbuzzShaper::new(&config, &[handle]).unwrap();
+        let mut no_glyphs = vec![];
+        let info = shaper.shape("\u{10fffd}", 10., 72, &mut no_glyphs).unwrap();
+        assert_eq!(no_glyphs, vec!['\u{10fffd}']);
+        assert_eq!(
+            info,
+            vec![GlyphInfo {
+                cluster: 0,
+                is_space: false,
+                font_idx: 0,
+                glyph_pos: crate::shaper::MISSING_GLYPH,
+                num_cells: 1,
+                #[cfg(debug_assertions)]
+                text: "\u{10fffd}".into(),
+                x_advance: PixelLength::new(6.),
+                x_offset: PixelLength::new(0.),
+                y_advance: PixelLength::new(0.),
+                y_offset: PixelLength::new(0.),
+            }]
+        );
+    }
 }
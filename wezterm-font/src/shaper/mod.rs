@@ -1,5 +1,7 @@
 use crate::locator::FontDataHandle;
 use crate::units::PixelLength;
+use termwiz::cell::unicode_column_width;
+use unicode_segmentation::UnicodeSegmentation;
 
 pub mod allsorts;
 pub mod harfbuzz;
@@ -29,6 +31,40 @@ pub struct GlyphInfo {
     pub y_offset: PixelLength,
 }
 
+/// The glyph index of the `.notdef` glyph.  The shapers report this
+/// for text that none of the fonts in the fallback sequence can render,
+/// so that the renderer can draw a placeholder in its place.
+pub const MISSING_GLYPH: u32 = 0;
+
+/// Produce a `MISSING_GLYPH` for each grapheme in `text`, which
+/// starts at byte offset `cluster` of the text being shaped.
+/// Each placeholder advances by the number of cells that the
+/// grapheme would otherwise occupy.
+pub(crate) fn make_missing_glyphs(
+    text: &str,
+    cluster: usize,
+    metrics: &FontMetrics,
+) -> Vec<GlyphInfo> {
+    text.grapheme_indices(true)
+        .map(|(idx, grapheme)| {
+            let num_cells = unicode_column_width(grapheme).max(1) as u8;
+            GlyphInfo {
+                #[cfg(debug_assertions)]
+                text: grapheme.into(),
+                is_space: false,
+                cluster: (cluster + idx) as u32,
+                num_cells,
+                font_idx: 0,
+                glyph_pos: MISSING_GLYPH,
+                x_advance: metrics.cell_width * f64::from(num_cells),
+                y_advance: PixelLength::new(0.),
+                x_offset: PixelLength::new(0.),
+                y_offset: PixelLength::new(0.),
+            }
+        })
+        .collect()
+}
+
 /// Represents a numbered index in the fallback sequence for a `NamedFont`.
 /// 0 is the first, best match.  If a glyph isn't present then we will
 /// want to search for a fallback in later indices.
@@ -33,7 +33,8 @@ pub struct StartCommand {
     pub cwd: Option<OsString>,
 
     /// Override the default windowing system class.
-    /// The default is "org.wezfurlong.wezterm".
+    /// The default is the `window_class` from the configuration,
+    /// or "org.wezfurlong.wezterm" if that isn't set.
     /// Under X11 and Windows this changes the window class.
     /// Under Wayland this changes the app_id.
     /// This changes the class for all windows spawned by this
@@ -53,10 +54,12 @@ pub struct StartCommand {
 pub struct SshCommand {
     /// Specifies the remote system using the form:
     /// `[username@]host[:port]`.
-    /// If `username@` is omitted, then your local $USER is used
-    /// instead.
-    /// If `:port` is omitted, then the standard ssh port (22) is
-    /// used instead.
+    /// `host` may be an alias defined by a `Host` entry in your
+    /// `~/.ssh/config`.
+    /// If `username@` is omitted, then the `User` from your ssh
+    /// config, or your local $USER, is used instead.
+    /// If `:port` is omitted, then the `Port` from your ssh config,
+    /// or the standard ssh port (22), is used instead.
     pub user_at_host_and_port: SshParameters,
 
     /// Override specific SSH configuration options.
@@ -102,9 +105,60 @@ pub struct ConnectCommand {
     /// to which you'd like to connect
     pub domain_name: String,
 
+    /// Attach as a viewer: the panes are displayed, but input is
+    /// not sent to them, and no new panes can be spawned
+    #[structopt(long = "read-only")]
+    pub read_only: bool,
+
     /// Instead of executing your shell, run PROG.
     /// For example: `wezterm start -- bash -l` will spawn bash
     /// as if it were a login shell.
     #[structopt(parse(from_os_str))]
     pub prog: Vec<OsString>,
 }
+
+/// How a subcommand presents its results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human readable text
+    Table,
+    /// JSON, for consumption by scripts
+    Json,
+}
+
+impl Default for OutputFormat {
+    fn default() -> Self {
+        Self::Table
+    }
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "table" => Ok(Self::Table),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("Expected one of table or json, but got {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, StructOpt, Clone)]
+pub struct LsFontsCommand {
+    /// Explain which fonts are used to render the supplied text string
+    #[structopt(long = "text")]
+    pub text: Option<String>,
+
+    /// How to present the results; one of `table` or `json`
+    #[structopt(long = "format", default_value = "table")]
+    pub format: OutputFormat,
+}
+
+#[derive(Debug, StructOpt, Clone)]
+pub struct VersionCommand {
+    /// Check whether a newer release is available.  The exit status
+    /// is 0 when this is the latest release, 2 when a newer release
+    /// is available and 1 if the check failed.
+    #[structopt(long = "check")]
+    pub check: bool,
+}
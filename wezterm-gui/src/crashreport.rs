@@ -0,0 +1,194 @@
+//! Writes a report when wezterm panics, and mentions it the next time
+//! that wezterm starts.  The report describes the build and the
+//! renderer, and identifies the configuration by a hash.  It never
+//! includes the contents of any pane.
+//! The configuration itself, the command line and the most recent log
+//! messages are included only when `crash_report_include_details` is
+//! set; log messages can quote terminal output.
+use backtrace::Backtrace;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::panic::PanicInfo;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use wezterm_toast_notification::persistent_toast_notification;
+
+lazy_static::lazy_static! {
+    static ref RENDERER: Mutex<Option<String>> = Mutex::new(None);
+}
+
+/// Records a description of the renderer, for inclusion in reports
+pub fn set_renderer_info(info: String) {
+    RENDERER.lock().unwrap().replace(info);
+}
+
+fn crash_dir() -> PathBuf {
+    config::DATA_DIR.join("crash")
+}
+
+/// Holds the path of the most recent report until it is mentioned
+fn pending_file() -> PathBuf {
+    crash_dir().join("pending")
+}
+
+pub fn panic_message(info: &PanicInfo) -> String {
+    if let Some(s) = info.payload().downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = info.payload().downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "Box<Any>".to_string()
+    }
+}
+
+struct CrashReport {
+    message: String,
+    location: String,
+    thread: String,
+    renderer: String,
+    /// The debug representation of the configuration
+    config: String,
+    command_line: Vec<String>,
+    include_details: bool,
+    backtrace: String,
+    log_lines: Vec<String>,
+}
+
+impl CrashReport {
+    fn config_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.config.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn to_text(&self) -> String {
+        let mut text = format!(
+            "wezterm crash report\n\
+             Version: {}\n\
+             OS: {} {}\n\
+             Thread: {}\n\
+             Panic: {}\n\
+             Location: {}\n\
+             Renderer: {}\n\
+             Config hash: {:016x}\n",
+            config::wezterm_version(),
+            std::env::consts::OS,
+            std::env::consts::ARCH,
+            self.thread,
+            self.message,
+            self.location,
+            self.renderer,
+            self.config_hash(),
+        );
+
+        if self.include_details {
+            text.push_str(&format!(
+                "Command line: {:?}\n\nConfiguration:\n{}\n",
+                self.command_line, self.config
+            ));
+        }
+
+        text.push_str(&format!("\nBacktrace:\n{}\n", self.backtrace));
+
+        if self.include_details {
+            text.push_str("\nRecent log messages:\n");
+            for line in &self.log_lines {
+                text.push_str(line);
+                text.push('\n');
+            }
+        }
+        text
+    }
+}
+
+/// Writes a report about the panic described by `info`, returning
+/// the path to the report if it was written
+pub fn write_crash_report(info: &PanicInfo) -> Option<PathBuf> {
+    let config = config::configuration();
+    let report = CrashReport {
+        message: panic_message(info),
+        location: info
+            .location()
+            .map(|l| format!("{}:{}", l.file(), l.line()))
+            .unwrap_or_default(),
+        thread: std::thread::current()
+            .name()
+            .unwrap_or("<unnamed>")
+            .to_string(),
+        renderer: RENDERER
+            .try_lock()
+            .ok()
+            .and_then(|r| r.clone())
+            .unwrap_or_else(|| "not initialized".to_string()),
+        config: format!("{:#?}", *config),
+        command_line: std::env::args().collect(),
+        include_details: config.crash_report_include_details,
+        backtrace: format!("{:?}", Backtrace::new()),
+        log_lines: env_bootstrap::recent_log_lines(),
+    };
+
+    let dir = crash_dir();
+    config::create_user_owned_dirs(&dir).ok()?;
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let path = dir.join(format!("wezterm-crash-{}-{}.txt", secs, std::process::id()));
+    std::fs::write(&path, report.to_text()).ok()?;
+    std::fs::write(pending_file(), path.to_string_lossy().as_bytes()).ok();
+    Some(path)
+}
+
+/// Mentions the report of a crash that happened since the previous
+/// time that this was called
+pub fn announce_previous_crash() {
+    let pending = pending_file();
+    let path = match std::fs::read_to_string(&pending) {
+        Ok(path) => path,
+        Err(_) => return,
+    };
+    std::fs::remove_file(&pending).ok();
+
+    log::warn!(
+        "wezterm crashed the last time that it ran; a report was written to {}",
+        path
+    );
+    persistent_toast_notification(
+        "WezTerm crashed",
+        &format!("A crash report was written to {}", path),
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn details_are_opt_in() {
+        let mut report = CrashReport {
+            message: "oops".to_string(),
+            location: "src/main.rs:1".to_string(),
+            thread: "main".to_string(),
+            renderer: "not initialized".to_string(),
+            config: "Config { font_size: 12.0 }".to_string(),
+            command_line: vec!["wezterm".to_string(), "start".to_string()],
+            include_details: false,
+            backtrace: String::new(),
+            log_lines: vec!["0.000 INFO  wezterm_gui > hello".to_string()],
+        };
+        let hash = format!("{:016x}", report.config_hash());
+
+        let text = report.to_text();
+        assert!(text.contains(&hash));
+        assert!(!text.contains("wezterm_gui > hello"));
+        assert!(!text.contains("font_size"));
+        assert!(!text.contains("\"start\""));
+
+        report.include_details = true;
+        let text = report.to_text();
+        assert!(text.contains("wezterm_gui > hello"));
+        assert!(text.contains("font_size"));
+        assert!(text.contains("\"start\""));
+    }
+}
@@ -61,6 +61,16 @@ vec4 apply_hsv(vec4 c, vec3 transform)
   return vec4(hsv2rgb(hsv).rgb, c.a);
 }
 
+// The colors of the quads are sRGB encoded; these convert them
+// to linear light and back again.
+vec3 srgb_to_linear(vec3 c) {
+  return mix(c / 12.92, pow((c + 0.055) / 1.055, vec3(2.4)), step(0.04045, c));
+}
+
+vec3 linear_to_srgb(vec3 c) {
+  return mix(c * 12.92, 1.055 * pow(c, vec3(1.0 / 2.4)) - 0.055, step(0.0031308, c));
+}
+
 // Given glyph, the greyscale rgba value computed by freetype,
 // and color, the desired color, compute the resultant pixel
 // value for rendering over the top of the given background
@@ -78,14 +88,20 @@ vec4 apply_hsv(vec4 c, vec3 transform)
 // computing the blend, but does include that value for the returned
 // alpha value.
 //
+// The glyph values are coverage, which is linear, so the blend is
+// computed in linear light.  Blending the sRGB encoded values
+// directly makes the edges of glyphs too dark, which causes dark
+// text on a light background to appear too heavy and light text on
+// a dark background to appear too thin, and gives colored fringes
+// with subpixel anti-aliasing.
+//
 // See also: https://www.puredevsoftware.com/blog/2019/01/22/sub-pixel-gamma-correct-font-rendering/
 vec4 colorize(vec4 glyph, vec4 color, vec4 background) {
-  float r = glyph.r * color.r + (1.0 - glyph.r) * background.r;
-  float g = glyph.g * color.g + (1.0 - glyph.g) * background.g;
-  float b = glyph.b * color.b + (1.0 - glyph.b) * background.b;
+  vec3 fg = srgb_to_linear(color.rgb);
+  vec3 bg = srgb_to_linear(background.rgb);
+  vec3 blended = glyph.rgb * fg + (1.0 - glyph.rgb) * bg;
 
-  return vec4(r, g, b, glyph.a);
-//  return vec4(glyph.rgb * color.rgb, glyph.a);
+  return vec4(linear_to_srgb(blended), glyph.a);
 }
 
 vec4 from_linear(vec4 v) {
@@ -1,15 +1,23 @@
 use crate::TermWindow;
 use ::window::*;
 use anyhow::Error;
+use config::keyassignment::CaptureArguments;
 pub use config::FrontEndSelection;
+use mux::pane::PaneId;
+use mux::window::WindowId as MuxWindowId;
 use mux::{Mux, MuxNotification};
 use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
 use std::rc::Rc;
-use wezterm_term::Alert;
+use std::time::Duration;
+use wezterm_term::{Alert, ClipboardSelection};
 use wezterm_toast_notification::*;
 
 pub struct GuiFrontEnd {
     connection: Rc<Connection>,
+    known_windows: RefCell<BTreeMap<MuxWindowId, Window>>,
+    /// Positions requested for windows that are about to be created
+    startup_positions: RefCell<HashMap<MuxWindowId, ScreenPoint>>,
 }
 
 impl Drop for GuiFrontEnd {
@@ -21,11 +29,17 @@ impl Drop for GuiFrontEnd {
 impl GuiFrontEnd {
     pub fn try_new() -> anyhow::Result<Rc<GuiFrontEnd>> {
         let connection = Connection::init()?;
-        let front_end = Rc::new(GuiFrontEnd { connection });
+        config::set_appearance(connection.get_appearance());
+        let front_end = Rc::new(GuiFrontEnd {
+            connection,
+            known_windows: RefCell::new(BTreeMap::new()),
+            startup_positions: RefCell::new(HashMap::new()),
+        });
         let mux = Mux::get().expect("mux started and running on main thread");
+        Mux::start_garbage_collection();
         let fe = Rc::downgrade(&front_end);
         mux.subscribe(move |n| {
-            if let Some(_fe) = fe.upgrade() {
+            if let Some(fe) = fe.upgrade() {
                 match n {
                     MuxNotification::WindowCreated(mux_window_id) => {
                         if let Err(err) = TermWindow::new_window(mux_window_id) {
@@ -35,8 +49,28 @@ impl GuiFrontEnd {
                         }
                     }
                     MuxNotification::PaneOutput(_) => {}
+                    MuxNotification::PaneWriterChanged { .. } => {}
+                    MuxNotification::WindowRemoved(mux_window_id) => {
+                        let window = fe.known_windows.borrow().get(&mux_window_id).cloned();
+                        if let Some(window) = window {
+                            window.close();
+                        }
+                    }
+                    MuxNotification::SetWindowPosition {
+                        window_id,
+                        position,
+                    } => {
+                        if let Some(window) = fe.known_windows.borrow().get(&window_id) {
+                            window.apply(move |tw, _ops| {
+                                if let Some(term_window) = tw.downcast_mut::<TermWindow>() {
+                                    term_window.set_window_position(&position);
+                                }
+                                Ok(())
+                            });
+                        }
+                    }
                     MuxNotification::Alert {
-                        pane_id: _,
+                        pane_id,
                         alert:
                             Alert::ToastNotification {
                                 title,
@@ -50,13 +84,45 @@ impl GuiFrontEnd {
                         // something here to arrange to focus pane_id when the
                         // notification is clicked
                         persistent_toast_notification(title, message);
+                        fe.request_attention(pane_id);
+                    }
+                    MuxNotification::Alert {
+                        pane_id,
+                        alert:
+                            Alert::CommandComplete {
+                                command,
+                                status,
+                                duration,
+                            },
+                    } => {
+                        fe.command_complete(pane_id, command, status, duration);
                     }
                     MuxNotification::Alert {
-                        pane_id: _,
+                        pane_id,
                         alert: Alert::Bell,
                     } => {
                         // persistent_toast_notification("Ding!", "This is the bell");
                         log::info!("Ding! (this is the bell)");
+                        fe.bell(pane_id);
+                    }
+                    MuxNotification::Alert {
+                        pane_id,
+                        alert: Alert::ClipboardQuery(selection),
+                    } => {
+                        fe.clipboard_query(pane_id, selection);
+                    }
+                    MuxNotification::Alert {
+                        pane_id,
+                        alert: Alert::SetBackgroundImage(path),
+                    } => {
+                        fe.set_background_image(pane_id, path);
+                    }
+                    MuxNotification::Capture {
+                        pane_id,
+                        whole_window,
+                        args,
+                    } => {
+                        fe.capture(pane_id, whole_window, args);
                     }
                 }
                 true
@@ -64,9 +130,155 @@ impl GuiFrontEnd {
                 false
             }
         });
+
+        if let Some(drop_down) = config::configuration().drop_down_window.clone() {
+            let fe = Rc::downgrade(&front_end);
+            if let Err(err) = front_end.connection.register_global_hotkey(
+                drop_down.key,
+                drop_down.mods,
+                move || {
+                    if let Some(fe) = fe.upgrade() {
+                        fe.toggle_drop_down();
+                    }
+                },
+            ) {
+                log::error!("Unable to register the drop down window hotkey: {:#}", err);
+            }
+        }
+
+        crate::crashreport::announce_previous_crash();
+
+        if config::configuration().enable_tray_icon {
+            if let Err(err) = crate::tray::add_tray_icon(&front_end.connection) {
+                log::error!("Unable to add the tray icon: {:#}", err);
+            }
+        }
+
         Ok(front_end)
     }
 
+    /// Shows a notification for a long running command that finished
+    /// in `pane_id`, unless it is excluded by the configuration or the
+    /// pane has the focus
+    fn command_complete(
+        &self,
+        pane_id: PaneId,
+        mut command: Option<String>,
+        status: i32,
+        duration: Duration,
+    ) {
+        let config = config::configuration();
+        let threshold = config.command_complete_notification_threshold_seconds;
+        if threshold == 0 || duration < Duration::from_secs(threshold) {
+            return;
+        }
+
+        let mux = Mux::get().expect("mux started and running on main thread");
+        let pane = match mux.get_pane(pane_id) {
+            Some(pane) => pane,
+            None => return,
+        };
+        if let Some(domain) = mux.get_domain(pane.domain_id()) {
+            if config
+                .command_complete_notification_excluded_domains
+                .iter()
+                .any(|name| name == domain.domain_name())
+            {
+                return;
+            }
+        }
+
+        if let Some(window) = self.window_for_pane(pane_id) {
+            window.apply(move |tw, _ops| {
+                if let Some(term_window) = tw.downcast_mut::<TermWindow>() {
+                    term_window.command_complete(pane_id, command.take(), status, duration);
+                }
+                Ok(())
+            });
+        }
+    }
+
+    /// Returns the window that contains `pane_id`
+    fn window_for_pane(&self, pane_id: PaneId) -> Option<Window> {
+        let mux = Mux::get().expect("mux started and running on main thread");
+        let (_domain_id, window_id, _tab_id) = mux.resolve_pane_id(pane_id)?;
+        self.known_windows.borrow().get(&window_id).cloned()
+    }
+
+    /// Lets the window that contains `pane_id` show the visual bell
+    fn bell(&self, pane_id: PaneId) {
+        if let Some(window) = self.window_for_pane(pane_id) {
+            window.apply(move |tw, _ops| {
+                if let Some(term_window) = tw.downcast_mut::<TermWindow>() {
+                    term_window.bell(pane_id);
+                }
+                Ok(())
+            });
+        }
+    }
+
+    /// Draws attention to the window that contains `pane_id`, if it
+    /// isn't focused
+    fn request_attention(&self, pane_id: PaneId) {
+        if let Some(window) = self.window_for_pane(pane_id) {
+            window.apply(move |tw, _ops| {
+                if let Some(term_window) = tw.downcast_mut::<TermWindow>() {
+                    term_window.request_attention();
+                }
+                Ok(())
+            });
+        }
+    }
+
+    /// Asks the window that contains `pane_id` to save an image of the
+    /// pane, or of the whole window
+    fn capture(&self, pane_id: PaneId, whole_window: bool, args: CaptureArguments) {
+        if let Some(window) = self.window_for_pane(pane_id) {
+            window.apply(move |tw, _ops| {
+                if let Some(term_window) = tw.downcast_mut::<TermWindow>() {
+                    term_window.capture(pane_id, whole_window, &args);
+                }
+                Ok(())
+            });
+        }
+    }
+
+    /// Lets the window that contains `pane_id` decide whether to answer
+    /// an OSC 52 clipboard query
+    fn clipboard_query(&self, pane_id: PaneId, selection: ClipboardSelection) {
+        if let Some(window) = self.window_for_pane(pane_id) {
+            window.apply(move |tw, _ops| {
+                if let Some(term_window) = tw.downcast_mut::<TermWindow>() {
+                    term_window.clipboard_query(pane_id, selection);
+                }
+                Ok(())
+            });
+        }
+    }
+
+    fn set_background_image(&self, pane_id: PaneId, path: Option<String>) {
+        if let Some(window) = self.window_for_pane(pane_id) {
+            window.apply(move |tw, _ops| {
+                if let Some(term_window) = tw.downcast_mut::<TermWindow>() {
+                    term_window.set_background_image(path.clone());
+                }
+                Ok(())
+            });
+        }
+    }
+
+    /// The first window is the drop down window
+    pub fn toggle_drop_down(&self) {
+        if let Some(window) = self.known_windows.borrow().values().next() {
+            window.apply(|tw, _ops| {
+                if let Some(term_window) = tw.downcast_mut::<TermWindow>() {
+                    term_window.toggle_drop_down();
+                }
+                Ok(())
+            });
+        }
+    }
+
     pub fn run_forever(&self) -> anyhow::Result<()> {
         self.connection
             .schedule_timer(std::time::Duration::from_millis(200), move || {
@@ -81,6 +293,57 @@ impl GuiFrontEnd {
 
         self.connection.run_message_loop()
     }
+
+    pub fn record_known_window(&self, window: Window, mux_window_id: MuxWindowId) {
+        self.known_windows
+            .borrow_mut()
+            .insert(mux_window_id, window);
+    }
+
+    pub fn forget_known_window(&self, mux_window_id: MuxWindowId) {
+        self.known_windows.borrow_mut().remove(&mux_window_id);
+    }
+
+    /// Arrange for the GUI window that is created for mux_window_id
+    /// to be placed at `position`, relative to the top left corner
+    /// of the specified monitor.
+    pub fn set_startup_position(
+        &self,
+        mux_window_id: MuxWindowId,
+        monitor: usize,
+        position: config::StartupWindowPosition,
+    ) {
+        let origin = match self.connection.screens().get(monitor) {
+            Some(screen) => screen.origin,
+            None => {
+                if monitor != 0 {
+                    log::warn!(
+                        "startup window requested monitor {}, which is not available",
+                        monitor
+                    );
+                }
+                ScreenPoint::new(0, 0)
+            }
+        };
+        self.startup_positions.borrow_mut().insert(
+            mux_window_id,
+            ScreenPoint::new(origin.x + position.x, origin.y + position.y),
+        );
+    }
+
+    pub fn take_startup_position(&self, mux_window_id: MuxWindowId) -> Option<ScreenPoint> {
+        self.startup_positions.borrow_mut().remove(&mux_window_id)
+    }
+
+    /// Returns the GUI windows ordered by their mux window id,
+    /// which is also the order in which they were created
+    pub fn gui_windows(&self) -> Vec<(MuxWindowId, Window)> {
+        self.known_windows
+            .borrow()
+            .iter()
+            .map(|(id, window)| (*id, window.clone()))
+            .collect()
+    }
 }
 
 thread_local! {
@@ -7,7 +7,7 @@ use ::window::color::{LinearRgba, SrgbaPixel};
 use ::window::glium;
 use ::window::glium::backend::Context as GliumContext;
 use ::window::glium::texture::SrgbTexture2d;
-use ::window::{Point, Rect};
+use ::window::{Point, Rect, Size};
 use anyhow::{anyhow, Context};
 use config::{configuration, AllowSquareGlyphOverflow, TextStyle};
 use euclid::num::Zero;
@@ -20,7 +20,7 @@ use std::time::{Duration, Instant};
 use termwiz::image::ImageData;
 use wezterm_font::units::*;
 use wezterm_font::{FontConfiguration, GlyphInfo};
-use wezterm_term::Underline;
+use wezterm_term::{Underline, VerticalAlign};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GlyphKey {
@@ -28,6 +28,10 @@ pub struct GlyphKey {
     pub glyph_pos: u32,
     pub style: TextStyle,
     pub followed_by_space: bool,
+    /// Which of the horizontal subpixel positions the glyph is
+    /// rasterized at; see `subpixel_phase`
+    pub subpixel_phase: u8,
+    pub vertical_align: VerticalAlign,
 }
 
 /// We'd like to avoid allocating when resolving from the cache
@@ -41,6 +45,8 @@ pub struct BorrowedGlyphKey<'a> {
     pub glyph_pos: u32,
     pub style: &'a TextStyle,
     pub followed_by_space: bool,
+    pub subpixel_phase: u8,
+    pub vertical_align: VerticalAlign,
 }
 
 impl<'a> BorrowedGlyphKey<'a> {
@@ -50,6 +56,8 @@ impl<'a> BorrowedGlyphKey<'a> {
             glyph_pos: self.glyph_pos,
             style: self.style.clone(),
             followed_by_space: self.followed_by_space,
+            subpixel_phase: self.subpixel_phase,
+            vertical_align: self.vertical_align,
         }
     }
 }
@@ -65,6 +73,8 @@ impl GlyphKeyTrait for GlyphKey {
             glyph_pos: self.glyph_pos,
             style: &self.style,
             followed_by_space: self.followed_by_space,
+            subpixel_phase: self.subpixel_phase,
+            vertical_align: self.vertical_align,
         }
     }
 }
@@ -105,6 +115,10 @@ pub struct CachedGlyph<T: Texture2d> {
     pub bearing_y: PixelLength,
     pub texture: Option<Sprite<T>>,
     pub scale: f64,
+    /// How far to the right of its position the glyph was shifted
+    /// when it was rasterized.  This is subtracted from the position
+    /// that the glyph is drawn at.
+    pub subpixel_shift: PixelLength,
 }
 
 impl<T: Texture2d> std::fmt::Debug for CachedGlyph<T> {
@@ -116,11 +130,68 @@ impl<T: Texture2d> std::fmt::Debug for CachedGlyph<T> {
             .field("bearing_x", &self.bearing_x)
             .field("bearing_y", &self.bearing_y)
             .field("scale", &self.scale)
+            .field("subpixel_shift", &self.subpixel_shift)
             .field("texture", &self.texture)
             .finish()
     }
 }
 
+/// Returns which of `positions` evenly spaced horizontal subpixel
+/// positions is closest to the fractional part of `x`.
+/// Glyphs are rasterized at that position, so that the shape of a
+/// glyph that is drawn at a fractional offset, such as a combining
+/// mark, isn't distorted by snapping it to a whole pixel.
+pub fn subpixel_phase(x: f64, positions: u8) -> u8 {
+    if positions <= 1 {
+        return 0;
+    }
+    let fraction = x - x.floor();
+    // A fraction that rounds up to the next whole pixel is drawn at
+    // phase 0 of that pixel
+    ((fraction * positions as f64).round() as u8) % positions
+}
+
+/// Returns whether a square or wide glyph rendered using `style` may
+/// overflow the width of its cell(s) rather than being scaled down to
+/// fit.  The policy set on the style, if any, takes precedence over
+/// the `configured` one.
+fn allow_square_glyph_overflow(
+    style: &TextStyle,
+    configured: AllowSquareGlyphOverflow,
+    followed_by_space: bool,
+) -> bool {
+    match style
+        .allow_square_glyphs_to_overflow_width
+        .unwrap_or(configured)
+    {
+        AllowSquareGlyphOverflow::Never => false,
+        AllowSquareGlyphOverflow::Always => true,
+        AllowSquareGlyphOverflow::WhenFollowedBySpace => followed_by_space,
+    }
+}
+
+/// 3x5 pixel bitmaps for the hexadecimal digits, used to draw the
+/// placeholder for missing glyphs.  Each row holds three bits, with
+/// the most significant bit being the leftmost pixel.
+const HEX_DIGIT_BITMAPS: [[u8; 5]; 16] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b010, 0b010],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+    [0b010, 0b101, 0b111, 0b101, 0b101],
+    [0b110, 0b101, 0b110, 0b101, 0b110],
+    [0b011, 0b100, 0b100, 0b100, 0b011],
+    [0b110, 0b101, 0b101, 0b101, 0b110],
+    [0b111, 0b100, 0b110, 0b100, 0b111],
+    [0b111, 0b100, 0b110, 0b100, 0b100],
+];
+
 #[derive(Copy, Clone, PartialEq, Eq, Hash)]
 struct LineKey {
     strike_through: bool,
@@ -324,7 +395,11 @@ pub struct GlyphCache<T: Texture2d> {
     frame_cache: HashMap<(usize, usize), Sprite<T>>,
     line_glyphs: HashMap<LineKey, Sprite<T>>,
     block_glyphs: HashMap<BlockKey, Sprite<T>>,
+    missing_glyphs: HashMap<(char, u8), Rc<CachedGlyph<T>>>,
     metrics: RenderMetrics,
+    /// The number of horizontal subpixel positions that glyphs are
+    /// rasterized at
+    subpixel_positions: u8,
 }
 
 #[cfg(test)]
@@ -346,6 +421,8 @@ impl GlyphCache<ImageTexture> {
             metrics: metrics.clone(),
             line_glyphs: HashMap::new(),
             block_glyphs: HashMap::new(),
+            missing_glyphs: HashMap::new(),
+            subpixel_positions: configuration().subpixel_glyph_positions,
         })
     }
 }
@@ -375,6 +452,8 @@ impl GlyphCache<SrgbTexture2d> {
             metrics: metrics.clone(),
             line_glyphs: HashMap::new(),
             block_glyphs: HashMap::new(),
+            missing_glyphs: HashMap::new(),
+            subpixel_positions: configuration().subpixel_glyph_positions,
         })
     }
 
@@ -385,6 +464,7 @@ impl GlyphCache<SrgbTexture2d> {
         self.glyph_cache.clear();
         self.line_glyphs.clear();
         self.block_glyphs.clear();
+        self.missing_glyphs.clear();
     }
 }
 
@@ -396,12 +476,15 @@ impl<T: Texture2d> GlyphCache<T> {
         info: &GlyphInfo,
         style: &TextStyle,
         followed_by_space: bool,
+        vertical_align: VerticalAlign,
     ) -> anyhow::Result<Rc<CachedGlyph<T>>> {
         let key = BorrowedGlyphKey {
             font_idx: info.font_idx,
             glyph_pos: info.glyph_pos,
             style,
             followed_by_space,
+            subpixel_phase: subpixel_phase(info.x_offset.get(), self.subpixel_positions),
+            vertical_align,
         };
 
         if let Some(entry) = self.glyph_cache.get(&key as &dyn GlyphKeyTrait) {
@@ -409,7 +492,13 @@ impl<T: Texture2d> GlyphCache<T> {
         }
 
         let glyph = self
-            .load_glyph(info, style, followed_by_space)
+            .load_glyph(
+                info,
+                style,
+                followed_by_space,
+                key.subpixel_phase,
+                vertical_align,
+            )
             .with_context(|| anyhow!("load_glyph {:?} {:?}", info, style))?;
         self.glyph_cache.insert(key.to_owned(), Rc::clone(&glyph));
         Ok(glyph)
@@ -422,18 +511,19 @@ impl<T: Texture2d> GlyphCache<T> {
         info: &GlyphInfo,
         style: &TextStyle,
         followed_by_space: bool,
+        subpixel_phase: u8,
+        vertical_align: VerticalAlign,
     ) -> anyhow::Result<Rc<CachedGlyph<T>>> {
-        let base_metrics;
-        let idx_metrics;
-        let glyph;
+        let mut subpixel_shift = if subpixel_phase == 0 {
+            0.
+        } else {
+            subpixel_phase as f64 / self.subpixel_positions as f64
+        };
 
-        {
-            let font = self.fonts.resolve_font(style)?;
-            base_metrics = font.metrics();
-            glyph = font.rasterize_glyph(info.glyph_pos, info.font_idx)?;
-
-            idx_metrics = font.metrics_for_idx(info.font_idx)?;
-        }
+        let font = self.fonts.resolve_font(style)?;
+        let base_metrics = font.metrics();
+        let mut glyph = font.rasterize_glyph(info.glyph_pos, info.font_idx, subpixel_shift)?;
+        let idx_metrics = font.metrics_for_idx(info.font_idx)?;
 
         let y_scale = base_metrics.cell_height.get() / idx_metrics.cell_height.get();
         let x_scale =
@@ -442,15 +532,12 @@ impl<T: Texture2d> GlyphCache<T> {
         let aspect = (idx_metrics.cell_height / idx_metrics.cell_width).get();
         let is_square_or_wide = aspect >= 0.9;
 
-        let allow_width_overflow = if is_square_or_wide {
-            match configuration().allow_square_glyphs_to_overflow_width {
-                AllowSquareGlyphOverflow::Never => false,
-                AllowSquareGlyphOverflow::Always => true,
-                AllowSquareGlyphOverflow::WhenFollowedBySpace => followed_by_space,
-            }
-        } else {
-            false
-        };
+        let allow_width_overflow = is_square_or_wide
+            && allow_square_glyph_overflow(
+                style,
+                configuration().allow_square_glyphs_to_overflow_width,
+                followed_by_space,
+            );
 
         let scale = if !allow_width_overflow
             && y_scale * glyph.width as f64 > base_metrics.cell_width.get() * info.num_cells as f64
@@ -461,6 +548,33 @@ impl<T: Texture2d> GlyphCache<T> {
             y_scale
         };
 
+        // Superscripts are shrunk towards the top of the cell, and
+        // subscripts towards the bottom, by moving the baseline
+        let ascender = base_metrics.cell_height + base_metrics.descender;
+        let (scale, baseline_shift) = match vertical_align {
+            VerticalAlign::BaseLine => (scale, PixelLength::zero()),
+            VerticalAlign::SuperScript => {
+                let script_scale = configuration().superscript_subscript_scale;
+                (scale * script_scale, ascender * (1. - script_scale))
+            }
+            VerticalAlign::SubScript => {
+                let script_scale = configuration().superscript_subscript_scale;
+                (
+                    scale * script_scale,
+                    base_metrics.descender * (1. - script_scale),
+                )
+            }
+        };
+
+        if scale != 1.0 && subpixel_shift != 0. {
+            // Scaling the bitmap blurs it by more than the shift would
+            // gain, so use the whole pixel rendition instead
+            glyph = font.rasterize_glyph(info.glyph_pos, info.font_idx, 0.)?;
+            subpixel_shift = 0.;
+        }
+        drop(font);
+        let subpixel_shift = PixelLength::new(subpixel_shift);
+
         let (cell_width, cell_height) = (base_metrics.cell_width, base_metrics.cell_height);
 
         let glyph = if glyph.width == 0 || glyph.height == 0 {
@@ -469,10 +583,11 @@ impl<T: Texture2d> GlyphCache<T> {
                 has_color: glyph.has_color,
                 texture: None,
                 x_offset: info.x_offset * scale,
-                y_offset: info.y_offset * scale,
+                y_offset: info.y_offset * scale + baseline_shift,
                 bearing_x: PixelLength::zero(),
                 bearing_y: PixelLength::zero(),
                 scale,
+                subpixel_shift,
             }
         } else {
             let raw_im = Image::with_rgba32(
@@ -485,7 +600,7 @@ impl<T: Texture2d> GlyphCache<T> {
             let bearing_x = glyph.bearing_x * scale;
             let bearing_y = glyph.bearing_y * scale;
             let x_offset = info.x_offset * scale;
-            let y_offset = info.y_offset * scale;
+            let y_offset = info.y_offset * scale + baseline_shift;
 
             let (scale, raw_im) = if scale != 1.0 {
                 log::trace!(
@@ -513,6 +628,7 @@ impl<T: Texture2d> GlyphCache<T> {
                 bearing_x,
                 bearing_y,
                 scale,
+                subpixel_shift,
             };
 
             if info.font_idx != 0 {
@@ -745,183 +861,106 @@ impl<T: Texture2d> GlyphCache<T> {
         self.block_sprite(block)
     }
 
-    fn line_sprite(&mut self, key: LineKey) -> anyhow::Result<Sprite<T>> {
-        let mut buffer = Image::new(
-            self.metrics.cell_size.width as usize,
-            self.metrics.cell_size.height as usize,
-        );
+    /// Render a placeholder for `c`, which none of the fonts have a glyph
+    /// for.  The placeholder is a box holding the hexadecimal value of the
+    /// codepoint, spanning `num_cells` cells.
+    fn missing_glyph(&mut self, c: char, num_cells: u8) -> anyhow::Result<Rc<CachedGlyph<T>>> {
+        let width = self.metrics.cell_size.width as usize * usize::from(num_cells.max(1));
+        let height = self.metrics.cell_size.height as usize;
+        let mut buffer = Image::new(width, height);
         let black = SrgbaPixel::rgba(0, 0, 0, 0);
         let white = SrgbaPixel::rgba(0xff, 0xff, 0xff, 0xff);
 
-        let cell_rect = Rect::new(Point::new(0, 0), self.metrics.cell_size);
-
-        let draw_single = |buffer: &mut Image| {
-            for row in 0..self.metrics.underline_height {
-                buffer.draw_line(
-                    Point::new(
-                        cell_rect.origin.x,
-                        cell_rect.origin.y + self.metrics.descender_row + row,
-                    ),
-                    Point::new(
-                        cell_rect.origin.x + self.metrics.cell_size.width,
-                        cell_rect.origin.y + self.metrics.descender_row + row,
-                    ),
-                    white,
-                );
-            }
-        };
-
-        let draw_dotted = |buffer: &mut Image| {
-            for row in 0..self.metrics.underline_height {
-                let y = (cell_rect.origin.y + self.metrics.descender_row + row) as usize;
-                if y >= self.metrics.cell_size.height as usize {
-                    break;
-                }
+        buffer.clear_rect(
+            Rect::new(Point::new(0, 0), Size::new(width as isize, height as isize)),
+            black,
+        );
 
-                let mut color = white;
-                let segment_length = (self.metrics.cell_size.width / 4) as usize;
-                let mut count = segment_length;
-                let range =
-                    buffer.horizontal_pixel_range_mut(0, self.metrics.cell_size.width as usize, y);
-                for c in range.iter_mut() {
-                    *c = color.as_srgba32();
-                    count -= 1;
-                    if count == 0 {
-                        color = if color == white { black } else { white };
-                        count = segment_length;
-                    }
-                }
-            }
+        // The outline of the box, inset by a pixel so that adjacent
+        // placeholders remain distinct
+        let (left, top) = (1, 1);
+        let (right, bottom) = (width as isize - 2, height as isize - 2);
+        buffer.draw_line(Point::new(left, top), Point::new(right, top), white);
+        buffer.draw_line(Point::new(left, bottom), Point::new(right, bottom), white);
+        buffer.draw_line(Point::new(left, top), Point::new(left, bottom), white);
+        buffer.draw_line(Point::new(right, top), Point::new(right, bottom), white);
+
+        // The digits are laid out over two rows, as is done by the
+        // Unicode Last Resort font.  Each digit is drawn from a 3x5
+        // pixel bitmap, scaled up by the largest whole number that
+        // lets the digits and a pixel of space around them fit inside
+        // the box.  If even that doesn't fit, then we show the box alone.
+        let digits = if (c as u32) > 0xffff {
+            format!("{:06X}", c as u32)
+        } else {
+            format!("{:04X}", c as u32)
         };
-
-        let draw_dashed = |buffer: &mut Image| {
-            for row in 0..self.metrics.underline_height {
-                let y = (cell_rect.origin.y + self.metrics.descender_row + row) as usize;
-                if y >= self.metrics.cell_size.height as usize {
-                    break;
-                }
-                let mut color = white;
-                let third = (self.metrics.cell_size.width / 3) as usize + 1;
-                let mut count = third;
-                let range =
-                    buffer.horizontal_pixel_range_mut(0, self.metrics.cell_size.width as usize, y);
-                for c in range.iter_mut() {
-                    *c = color.as_srgba32();
-                    count -= 1;
-                    if count == 0 {
-                        color = if color == white { black } else { white };
-                        count = third;
+        let digits: Vec<u32> = digits.chars().filter_map(|d| d.to_digit(16)).collect();
+        let cols = digits.len() / 2;
+        let inner_width = width.saturating_sub(4);
+        let inner_height = height.saturating_sub(4);
+        let scale = ((inner_width + 1) / (cols * 4)).min((inner_height + 1) / (2 * 6));
+
+        if scale > 0 {
+            let block_width = cols * 4 * scale - scale;
+            let block_height = 2 * 6 * scale - scale;
+            let x0 = (width - block_width) / 2;
+            let y0 = (height - block_height) / 2;
+
+            for (idx, digit) in digits.iter().enumerate() {
+                let x = x0 + (idx % cols) * 4 * scale;
+                let y = y0 + (idx / cols) * 6 * scale;
+                for (row, bits) in HEX_DIGIT_BITMAPS[*digit as usize].iter().enumerate() {
+                    for col in 0..3 {
+                        if bits & (0b100 >> col) != 0 {
+                            buffer.clear_rect(
+                                Rect::new(
+                                    Point::new(
+                                        (x + col * scale) as isize,
+                                        (y + row * scale) as isize,
+                                    ),
+                                    Size::new(scale as isize, scale as isize),
+                                ),
+                                white,
+                            );
+                        }
                     }
                 }
             }
-        };
-
-        let draw_curly = |buffer: &mut Image| {
-            let max_y = self.metrics.cell_size.height as usize - 1;
-            let x_factor = (2. * std::f32::consts::PI) / self.metrics.cell_size.width as f32;
-
-            // Have the wave go from the descender to the bottom of the cell
-            let wave_height =
-                self.metrics.cell_size.height - (cell_rect.origin.y + self.metrics.descender_row);
-
-            let half_height = (wave_height as f32 / 2.).max(1.);
-            let y =
-                (cell_rect.origin.y + self.metrics.descender_row) as usize - half_height as usize;
-
-            fn add(x: usize, y: usize, val: u8, max_y: usize, buffer: &mut Image) {
-                let y = y.min(max_y);
-                let pixel = buffer.pixel_mut(x, y);
-                let (current, _, _, _) = SrgbaPixel::with_srgba_u32(*pixel).as_rgba();
-                let value = current.saturating_add(val);
-                *pixel = SrgbaPixel::rgba(value, value, value, 0xff).as_srgba32();
-            }
-
-            for x in 0..self.metrics.cell_size.width as usize {
-                let vertical = wave_height as f32 * (x as f32 * x_factor).cos();
-                let v1 = vertical.floor();
-                let v2 = vertical.ceil();
-
-                for row in 0..self.metrics.underline_height as usize {
-                    let value = (255. * (vertical - v1).abs()) as u8;
-                    add(x, row + y + v1 as usize, 255 - value, max_y, buffer);
-                    add(x, row + y + v2 as usize, value, max_y, buffer);
-                }
-            }
-        };
-
-        let draw_double = |buffer: &mut Image| {
-            let first_line = self
-                .metrics
-                .descender_row
-                .min(self.metrics.descender_plus_two - 2 * self.metrics.underline_height);
-
-            for row in 0..self.metrics.underline_height {
-                buffer.draw_line(
-                    Point::new(cell_rect.origin.x, cell_rect.origin.y + first_line + row),
-                    Point::new(
-                        cell_rect.origin.x + self.metrics.cell_size.width,
-                        cell_rect.origin.y + first_line + row,
-                    ),
-                    white,
-                );
-                buffer.draw_line(
-                    Point::new(
-                        cell_rect.origin.x,
-                        cell_rect.origin.y + self.metrics.descender_plus_two + row,
-                    ),
-                    Point::new(
-                        cell_rect.origin.x + self.metrics.cell_size.width,
-                        cell_rect.origin.y + self.metrics.descender_plus_two + row,
-                    ),
-                    white,
-                );
-            }
-        };
-
-        let draw_strike = |buffer: &mut Image| {
-            for row in 0..self.metrics.underline_height {
-                buffer.draw_line(
-                    Point::new(
-                        cell_rect.origin.x,
-                        cell_rect.origin.y + self.metrics.strike_row + row,
-                    ),
-                    Point::new(
-                        cell_rect.origin.x + self.metrics.cell_size.width,
-                        cell_rect.origin.y + self.metrics.strike_row + row,
-                    ),
-                    white,
-                );
-            }
-        };
+        }
 
-        let draw_overline = |buffer: &mut Image| {
-            for row in 0..self.metrics.underline_height {
-                buffer.draw_line(
-                    Point::new(cell_rect.origin.x, cell_rect.origin.y + row),
-                    Point::new(
-                        cell_rect.origin.x + self.metrics.cell_size.width,
-                        cell_rect.origin.y + row,
-                    ),
-                    white,
-                );
-            }
-        };
+        let sprite = self.atlas.allocate(&buffer)?;
+        Ok(Rc::new(CachedGlyph {
+            has_color: false,
+            texture: Some(sprite),
+            x_offset: PixelLength::zero(),
+            y_offset: PixelLength::zero(),
+            bearing_x: PixelLength::zero(),
+            // Place the top of the sprite at the top of the cell
+            bearing_y: PixelLength::new(height as f64) + self.metrics.descender,
+            scale: 1.0,
+            subpixel_shift: PixelLength::zero(),
+        }))
+    }
 
-        buffer.clear_rect(cell_rect, black);
-        if key.overline {
-            draw_overline(&mut buffer);
-        }
-        match key.underline {
-            Underline::None => {}
-            Underline::Single => draw_single(&mut buffer),
-            Underline::Curly => draw_curly(&mut buffer),
-            Underline::Dashed => draw_dashed(&mut buffer),
-            Underline::Dotted => draw_dotted(&mut buffer),
-            Underline::Double => draw_double(&mut buffer),
-        }
-        if key.strike_through {
-            draw_strike(&mut buffer);
+    /// Resolve the placeholder for a codepoint that none of the fonts
+    /// can render, rendering it on-demand if required
+    pub fn cached_missing_glyph(
+        &mut self,
+        c: char,
+        num_cells: u8,
+    ) -> anyhow::Result<Rc<CachedGlyph<T>>> {
+        if let Some(glyph) = self.missing_glyphs.get(&(c, num_cells)) {
+            return Ok(Rc::clone(glyph));
         }
+        let glyph = self.missing_glyph(c, num_cells)?;
+        self.missing_glyphs
+            .insert((c, num_cells), Rc::clone(&glyph));
+        Ok(glyph)
+    }
+
+    fn line_sprite(&mut self, key: LineKey) -> anyhow::Result<Sprite<T>> {
+        let buffer = render_line(&self.metrics, key);
         let sprite = self.atlas.allocate(&buffer)?;
         self.line_glyphs.insert(key, sprite.clone());
         Ok(sprite)
@@ -957,3 +996,332 @@ impl<T: Texture2d> GlyphCache<T> {
         self.line_sprite(key)
     }
 }
+
+/// Draws the underline, strikethrough and overline decorations
+/// described by `key` into a cell sized image
+fn render_line(metrics: &RenderMetrics, key: LineKey) -> Image {
+    let mut buffer = Image::new(
+        metrics.cell_size.width as usize,
+        metrics.cell_size.height as usize,
+    );
+    let black = SrgbaPixel::rgba(0, 0, 0, 0);
+    let white = SrgbaPixel::rgba(0xff, 0xff, 0xff, 0xff);
+
+    let cell_rect = Rect::new(Point::new(0, 0), metrics.cell_size);
+
+    let draw_single = |buffer: &mut Image| {
+        for row in 0..metrics.underline_height {
+            buffer.draw_line(
+                Point::new(
+                    cell_rect.origin.x,
+                    cell_rect.origin.y + metrics.descender_row + row,
+                ),
+                Point::new(
+                    cell_rect.origin.x + metrics.cell_size.width,
+                    cell_rect.origin.y + metrics.descender_row + row,
+                ),
+                white,
+            );
+        }
+    };
+
+    let draw_dotted = |buffer: &mut Image| {
+        for row in 0..metrics.underline_height {
+            let y = (cell_rect.origin.y + metrics.descender_row + row) as usize;
+            if y >= metrics.cell_size.height as usize {
+                break;
+            }
+
+            let mut color = white;
+            let segment_length = (metrics.cell_size.width / 4) as usize;
+            let mut count = segment_length;
+            let range = buffer.horizontal_pixel_range_mut(0, metrics.cell_size.width as usize, y);
+            for c in range.iter_mut() {
+                *c = color.as_srgba32();
+                count -= 1;
+                if count == 0 {
+                    color = if color == white { black } else { white };
+                    count = segment_length;
+                }
+            }
+        }
+    };
+
+    let draw_dashed = |buffer: &mut Image| {
+        for row in 0..metrics.underline_height {
+            let y = (cell_rect.origin.y + metrics.descender_row + row) as usize;
+            if y >= metrics.cell_size.height as usize {
+                break;
+            }
+            let mut color = white;
+            let third = (metrics.cell_size.width / 3) as usize + 1;
+            let mut count = third;
+            let range = buffer.horizontal_pixel_range_mut(0, metrics.cell_size.width as usize, y);
+            for c in range.iter_mut() {
+                *c = color.as_srgba32();
+                count -= 1;
+                if count == 0 {
+                    color = if color == white { black } else { white };
+                    count = third;
+                }
+            }
+        }
+    };
+
+    let draw_curly = |buffer: &mut Image| {
+        let max_y = metrics.cell_size.height as usize - 1;
+        let x_factor = (2. * std::f32::consts::PI) / metrics.cell_size.width as f32;
+
+        // Have the wave go from the descender to the bottom of the cell
+        let wave_height = metrics.cell_size.height - (cell_rect.origin.y + metrics.descender_row);
+
+        let half_height = (wave_height as f32 / 2.).max(1.);
+        let y = (cell_rect.origin.y + metrics.descender_row) as usize - half_height as usize;
+
+        fn add(x: usize, y: usize, val: u8, max_y: usize, buffer: &mut Image) {
+            let y = y.min(max_y);
+            let pixel = buffer.pixel_mut(x, y);
+            let (current, _, _, _) = SrgbaPixel::with_srgba_u32(*pixel).as_rgba();
+            let value = current.saturating_add(val);
+            *pixel = SrgbaPixel::rgba(value, value, value, 0xff).as_srgba32();
+        }
+
+        for x in 0..metrics.cell_size.width as usize {
+            let vertical = wave_height as f32 * (x as f32 * x_factor).cos();
+            let v1 = vertical.floor();
+            let v2 = vertical.ceil();
+
+            for row in 0..metrics.underline_height as usize {
+                let value = (255. * (vertical - v1).abs()) as u8;
+                add(x, row + y + v1 as usize, 255 - value, max_y, buffer);
+                add(x, row + y + v2 as usize, value, max_y, buffer);
+            }
+        }
+    };
+
+    let draw_double = |buffer: &mut Image| {
+        let first_line = metrics
+            .descender_row
+            .min(metrics.descender_plus_two - 2 * metrics.underline_height);
+
+        for row in 0..metrics.underline_height {
+            buffer.draw_line(
+                Point::new(cell_rect.origin.x, cell_rect.origin.y + first_line + row),
+                Point::new(
+                    cell_rect.origin.x + metrics.cell_size.width,
+                    cell_rect.origin.y + first_line + row,
+                ),
+                white,
+            );
+            buffer.draw_line(
+                Point::new(
+                    cell_rect.origin.x,
+                    cell_rect.origin.y + metrics.descender_plus_two + row,
+                ),
+                Point::new(
+                    cell_rect.origin.x + metrics.cell_size.width,
+                    cell_rect.origin.y + metrics.descender_plus_two + row,
+                ),
+                white,
+            );
+        }
+    };
+
+    let draw_strike = |buffer: &mut Image| {
+        for row in 0..metrics.underline_height {
+            buffer.draw_line(
+                Point::new(
+                    cell_rect.origin.x,
+                    cell_rect.origin.y + metrics.strike_row + row,
+                ),
+                Point::new(
+                    cell_rect.origin.x + metrics.cell_size.width,
+                    cell_rect.origin.y + metrics.strike_row + row,
+                ),
+                white,
+            );
+        }
+    };
+
+    let draw_overline = |buffer: &mut Image| {
+        for row in 0..metrics.underline_height {
+            buffer.draw_line(
+                Point::new(cell_rect.origin.x, cell_rect.origin.y + row),
+                Point::new(
+                    cell_rect.origin.x + metrics.cell_size.width,
+                    cell_rect.origin.y + row,
+                ),
+                white,
+            );
+        }
+    };
+
+    buffer.clear_rect(cell_rect, black);
+    if key.overline {
+        draw_overline(&mut buffer);
+    }
+    match key.underline {
+        Underline::None => {}
+        Underline::Single => draw_single(&mut buffer),
+        Underline::Curly => draw_curly(&mut buffer),
+        Underline::Dashed => draw_dashed(&mut buffer),
+        Underline::Dotted => draw_dotted(&mut buffer),
+        Underline::Double => draw_double(&mut buffer),
+    }
+    if key.strike_through {
+        draw_strike(&mut buffer);
+    }
+    buffer
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn subpixel_phases() {
+        assert_eq!(subpixel_phase(0.3, 1), 0);
+        assert_eq!(subpixel_phase(0.0, 4), 0);
+        assert_eq!(subpixel_phase(2.25, 4), 1);
+        assert_eq!(subpixel_phase(0.6, 4), 2);
+        assert_eq!(subpixel_phase(-0.25, 4), 3);
+        // Close enough to the next pixel to be drawn there
+        assert_eq!(subpixel_phase(0.9, 4), 0);
+    }
+
+    #[test]
+    fn square_glyph_overflow_policy() {
+        use AllowSquareGlyphOverflow::*;
+
+        let unset = TextStyle::default();
+        assert!(!allow_square_glyph_overflow(&unset, Never, true));
+        assert!(allow_square_glyph_overflow(&unset, Always, false));
+        assert!(allow_square_glyph_overflow(
+            &unset,
+            WhenFollowedBySpace,
+            true
+        ));
+        assert!(!allow_square_glyph_overflow(
+            &unset,
+            WhenFollowedBySpace,
+            false
+        ));
+
+        // A policy on the style overrides the configured one
+        let always = TextStyle {
+            allow_square_glyphs_to_overflow_width: Some(Always),
+            ..TextStyle::default()
+        };
+        assert!(allow_square_glyph_overflow(&always, Never, false));
+        let never = TextStyle {
+            allow_square_glyphs_to_overflow_width: Some(Never),
+            ..TextStyle::default()
+        };
+        assert!(!allow_square_glyph_overflow(&never, Always, true));
+        let when_followed_by_space = TextStyle {
+            allow_square_glyphs_to_overflow_width: Some(WhenFollowedBySpace),
+            ..TextStyle::default()
+        };
+        assert!(!allow_square_glyph_overflow(
+            &when_followed_by_space,
+            Always,
+            false
+        ));
+        // Synthesized styles keep the policy
+        assert!(allow_square_glyph_overflow(
+            &always.make_bold(),
+            Never,
+            false
+        ));
+    }
+
+    fn metrics(underline_height: IntPixelLength) -> RenderMetrics {
+        RenderMetrics {
+            descender: PixelLength::new(-3.0),
+            descender_row: 13,
+            descender_plus_two: 15,
+            underline_height,
+            strike_row: 8,
+            cell_size: Size::new(8, 16),
+        }
+    }
+
+    fn line(underline: Underline, strike_through: bool, overline: bool) -> LineKey {
+        LineKey {
+            strike_through,
+            underline,
+            overline,
+        }
+    }
+
+    /// Whether the pixel is visible; the antialiased line drawing
+    /// can leave fully transparent, but otherwise colored, pixels
+    /// alongside the line
+    fn is_lit(image: &Image, x: usize, y: usize) -> bool {
+        let (_, _, _, alpha) = SrgbaPixel::with_srgba_u32(*image.pixel(x, y)).as_rgba();
+        alpha != 0
+    }
+
+    /// Returns the rows of the rendered line sprite that have
+    /// any visible pixels
+    fn lit_rows(metrics: &RenderMetrics, key: LineKey) -> Vec<usize> {
+        let image = render_line(metrics, key);
+        (0..metrics.cell_size.height as usize)
+            .filter(|&y| (0..metrics.cell_size.width as usize).any(|x| is_lit(&image, x, y)))
+            .collect()
+    }
+
+    #[test]
+    fn line_placement() {
+        let m = metrics(1);
+        assert!(lit_rows(&m, line(Underline::None, false, false)).is_empty());
+        assert_eq!(
+            lit_rows(&m, line(Underline::Single, false, false)),
+            vec![13]
+        );
+        assert_eq!(
+            lit_rows(&m, line(Underline::Double, false, false)),
+            vec![13, 15]
+        );
+        assert_eq!(lit_rows(&m, line(Underline::None, true, false)), vec![8]);
+        assert_eq!(lit_rows(&m, line(Underline::None, false, true)), vec![0]);
+        assert_eq!(
+            lit_rows(&m, line(Underline::Single, true, true)),
+            vec![0, 8, 13]
+        );
+
+        // Thicker lines grow downwards from the same starting row
+        let m = metrics(2);
+        assert_eq!(
+            lit_rows(&m, line(Underline::Single, false, false)),
+            vec![13, 14]
+        );
+        assert_eq!(
+            lit_rows(&m, line(Underline::None, true, true)),
+            vec![0, 1, 8, 9]
+        );
+        // The first line of a double underline moves up when there
+        // isn't room for both of them above the second line
+        let m = RenderMetrics {
+            descender_plus_two: 14,
+            ..metrics(2)
+        };
+        assert_eq!(
+            lit_rows(&m, line(Underline::Double, false, false)),
+            vec![10, 11, 14, 15]
+        );
+    }
+
+    #[test]
+    fn dotted_and_dashed_lines() {
+        let m = metrics(1);
+        let lit = |key| {
+            let image = render_line(&m, key);
+            (0..8)
+                .map(|x| if is_lit(&image, x, 13) { '#' } else { '.' })
+                .collect::<String>()
+        };
+        assert_eq!(lit(line(Underline::Dotted, false, false)), "##..##..");
+        assert_eq!(lit(line(Underline::Dashed, false, false)), "###...##");
+    }
+}
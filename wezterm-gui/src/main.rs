@@ -9,19 +9,25 @@ use mux::domain::{Domain, LocalDomain};
 use mux::Mux;
 use portable_pty::cmdbuilder::CommandBuilder;
 use promise::spawn::block_on;
+use serde::Serialize;
 use std::ffi::OsString;
 use std::rc::Rc;
 use std::sync::Arc;
 use structopt::StructOpt;
+use termwiz::cell::CellAttributes;
+use termwiz::surface::Line;
 use wezterm_client::domain::{ClientDomain, ClientDomainConfig};
+use wezterm_font::{ClearShapeCache, FontConfiguration, GlyphInfo, LoadedFont, MISSING_GLYPH};
 use wezterm_gui_subcommands::*;
 use wezterm_ssh::*;
 use wezterm_toast_notification::*;
 
+mod crashreport;
 mod frontend;
 mod glyphcache;
 mod markdown;
 mod overlay;
+mod power;
 mod quad;
 mod renderstate;
 mod scripting;
@@ -29,8 +35,10 @@ mod scrollbar;
 mod selection;
 mod shapecache;
 mod stats;
+mod statuscmd;
 mod tabbar;
 mod termwindow;
+mod tray;
 mod update;
 mod utilsprites;
 
@@ -84,6 +92,18 @@ enum SubCommand {
 
     #[structopt(name = "connect", about = "Connect to wezterm multiplexer")]
     Connect(ConnectCommand),
+
+    #[structopt(
+        name = "ls-fonts",
+        about = "Display information about fonts and how they are resolved"
+    )]
+    LsFonts(LsFontsCommand),
+
+    #[structopt(
+        name = "version",
+        about = "Print the version, and optionally check for a newer release"
+    )]
+    Version(VersionCommand),
 }
 
 async fn async_run_ssh(opts: SshCommand) -> anyhow::Result<()> {
@@ -96,17 +116,19 @@ async fn async_run_ssh(opts: SshCommand) -> anyhow::Result<()> {
         .ok_or_else(|| anyhow::anyhow!("no host component somehow"))?;
     let port = fields.next();
 
-    let mut ssh_config = ssh_config.for_host(host);
-    ssh_config.insert(
-        "user".to_string(),
-        opts.user_at_host_and_port.username.to_string(),
-    );
+    // Options from the command line take precedence over those from
+    // the config files, and are taken into account when expanding the
+    // tokens in options such as ProxyCommand
+    if let Some(username) = &opts.user_at_host_and_port.username {
+        ssh_config.set_option("user", username);
+    }
     if let Some(port) = port {
-        ssh_config.insert("port".to_string(), port.to_string());
+        ssh_config.set_option("port", port);
     }
-    for (k, v) in opts.config_override {
-        ssh_config.insert(k.to_lowercase().to_string(), v);
+    for (k, v) in &opts.config_override {
+        ssh_config.set_option(k, v);
     }
+    let ssh_config = ssh_config.for_host(host);
 
     let _gui = front_end().unwrap();
 
@@ -215,7 +237,9 @@ fn run_mux_client(config: config::ConfigHandle, opts: &ConnectCommand) -> anyhow
             )
         })?;
 
-    let domain: Arc<dyn Domain> = Arc::new(ClientDomain::new(client_config));
+    let mut client_domain = ClientDomain::new(client_config);
+    client_domain.set_read_only(opts.read_only);
+    let domain: Arc<dyn Domain> = Arc::new(client_domain);
     let mux = Rc::new(mux::Mux::new(Some(domain.clone())));
     Mux::set_mux(&mux);
     crate::update::load_last_release_info_and_set_banner();
@@ -266,6 +290,11 @@ async fn spawn_tab_in_default_domain_if_mux_is_empty(
     }
 
     let config = config::configuration();
+    if cmd.is_none() && !config.startup_windows.is_empty() {
+        return spawn_startup_windows(&config, &domain).await;
+    }
+
+    let cmd = cmd.or_else(|| config.build_initial_prog());
     let window_id = mux.new_empty_window();
     let _tab = mux
         .default_domain()
@@ -274,6 +303,43 @@ async fn spawn_tab_in_default_domain_if_mux_is_empty(
     Ok(())
 }
 
+/// Creates the windows described by the `startup_windows` configuration
+async fn spawn_startup_windows(
+    config: &config::ConfigHandle,
+    domain: &Arc<dyn Domain>,
+) -> anyhow::Result<()> {
+    let mux = Mux::get().unwrap();
+
+    for startup in &config.startup_windows {
+        let cmd = if startup.args.is_some() || startup.cwd.is_some() {
+            let mut builder = match &startup.args {
+                Some(args) => CommandBuilder::from_argv(args.iter().map(OsString::from).collect()),
+                None => CommandBuilder::new_default_prog(),
+            };
+            if let Some(cwd) = &startup.cwd {
+                builder.cwd(cwd);
+            }
+            Some(builder)
+        } else {
+            None
+        };
+
+        let window_id = mux.new_empty_window();
+        if let Some(name) = &startup.name {
+            if let Some(mut window) = mux.get_window_mut(*window_id) {
+                window.set_name(Some(name.clone()));
+            }
+        }
+        if let (Some(position), Some(fe)) = (startup.position, front_end()) {
+            fe.set_startup_position(*window_id, startup.monitor, position);
+        }
+        domain
+            .spawn(startup.size(config), cmd, None, *window_id)
+            .await?;
+    }
+    Ok(())
+}
+
 async fn async_run_terminal_gui(
     cmd: Option<CommandBuilder>,
     do_auto_connect: bool,
@@ -365,6 +431,238 @@ fn run_terminal_gui(opts: StartCommand) -> anyhow::Result<()> {
     res
 }
 
+/// Describes the cell attributes that must be present for `rule` to apply
+fn describe_style_rule(rule: &config::StyleRule) -> String {
+    let mut matchers = vec![];
+    if let Some(intensity) = rule.intensity {
+        matchers.push(format!("intensity={:?}", intensity));
+    }
+    if let Some(underline) = rule.underline {
+        matchers.push(format!("underline={:?}", underline));
+    }
+    if let Some(italic) = rule.italic {
+        matchers.push(format!("italic={}", italic));
+    }
+    if let Some(blink) = rule.blink {
+        matchers.push(format!("blink={:?}", blink));
+    }
+    if let Some(reverse) = rule.reverse {
+        matchers.push(format!("reverse={}", reverse));
+    }
+    if let Some(strikethrough) = rule.strikethrough {
+        matchers.push(format!("strikethrough={}", strikethrough));
+    }
+    if let Some(invisible) = rule.invisible {
+        matchers.push(format!("invisible={}", invisible));
+    }
+    if matchers.is_empty() {
+        "any text".to_string()
+    } else {
+        matchers.join(", ")
+    }
+}
+
+/// The fonts requested for some text, and the fallback sequence of
+/// fonts that they were resolved to.  This is part of the output of
+/// `ls-fonts --format json`, so fields should only ever be added.
+#[derive(Debug, Serialize)]
+struct FontChain {
+    /// Describes the text that the fonts apply to; `None` for the
+    /// primary font
+    when: Option<String>,
+    requested: Vec<String>,
+    resolved: Vec<String>,
+}
+
+impl FontChain {
+    fn new(
+        fonts: &FontConfiguration,
+        when: Option<String>,
+        style: &config::TextStyle,
+    ) -> anyhow::Result<Self> {
+        let font = fonts.resolve_font(style)?;
+        Ok(Self {
+            when,
+            requested: style.font.iter().map(|attr| attr.to_string()).collect(),
+            resolved: font
+                .clone_handles()
+                .iter()
+                .map(|handle| handle.diagnostic_string())
+                .collect(),
+        })
+    }
+
+    fn print(&self) {
+        match &self.when {
+            Some(when) => println!("When {}:", when),
+            None => println!("Primary font:"),
+        }
+        for attr in &self.requested {
+            println!("  requested: {}", attr);
+        }
+        for (idx, font) in self.resolved.iter().enumerate() {
+            println!("  {}: {}", idx, font);
+        }
+    }
+}
+
+/// A glyph produced by shaping the `--text` passed to `ls-fonts`
+#[derive(Debug, Serialize)]
+struct ShapedGlyph {
+    text: String,
+    /// The glyph index, or `None` if no font has a glyph for the text
+    glyph: Option<u32>,
+    /// The font that the glyph is taken from
+    font: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ShapedCluster {
+    text: String,
+    glyphs: Vec<ShapedGlyph>,
+}
+
+impl ShapedCluster {
+    fn print(&self) {
+        println!("Shaping {:?}:", self.text);
+        for glyph in &self.glyphs {
+            match (glyph.glyph, &glyph.font) {
+                (Some(idx), Some(font)) => {
+                    println!("  {:?}: glyph {} from {}", glyph.text, idx, font)
+                }
+                _ => println!("  {:?}: no font has a glyph for this text", glyph.text),
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct LsFontsOutput {
+    fonts: Vec<FontChain>,
+    shaped: Vec<ShapedCluster>,
+}
+
+/// Shapes `text` in the same way as the terminal would, waiting for
+/// any fallback fonts that need to be located along the way
+fn shape_with_fallback(font: &Rc<LoadedFont>, text: &str) -> anyhow::Result<Vec<GlyphInfo>> {
+    loop {
+        // The completion is only called if fallback fonts were found;
+        // otherwise it is dropped and `recv` returns an error
+        let (tx, rx) = std::sync::mpsc::sync_channel(1);
+        match font.shape(text, move || {
+            tx.send(()).ok();
+        }) {
+            Ok(infos) => {
+                if rx.recv().is_err() {
+                    return Ok(infos);
+                }
+            }
+            Err(err) if err.downcast_ref::<ClearShapeCache>().is_some() => {}
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+fn run_version(cmd: &VersionCommand) -> anyhow::Result<()> {
+    println!("wezterm {}", config::wezterm_version());
+    if !cmd.check {
+        return Ok(());
+    }
+
+    let latest = crate::update::get_latest_release_info()?;
+    if crate::update::is_newer_than_current(&latest) {
+        println!(
+            "{} is available: {}",
+            latest.tag_name,
+            crate::update::changelog_url(&latest)
+        );
+        std::process::exit(2);
+    }
+    println!("This is the latest release");
+    Ok(())
+}
+
+fn run_ls_fonts(config: config::ConfigHandle, cmd: &LsFontsCommand) -> anyhow::Result<()> {
+    let fonts = FontConfiguration::new(Some(config.clone()))?;
+
+    let mut output = LsFontsOutput {
+        fonts: vec![FontChain::new(&fonts, None, &config.font)?],
+        shaped: vec![],
+    };
+    for rule in &config.font_rules {
+        output.fonts.push(FontChain::new(
+            &fonts,
+            Some(describe_style_rule(rule)),
+            &rule.font,
+        )?);
+    }
+
+    if let Some(text) = &cmd.text {
+        let line = Line::from_text(text, &CellAttributes::default());
+        for cluster in line.cluster() {
+            let style = fonts.match_style(&config, &cluster.attrs);
+            let font = fonts.resolve_font(style)?;
+            let handles = font.clone_handles();
+            let infos = shape_with_fallback(&font, &cluster.text)?;
+
+            let mut shaped = ShapedCluster {
+                text: cluster.text.clone(),
+                glyphs: vec![],
+            };
+            for info in &infos {
+                let end = infos
+                    .iter()
+                    .map(|other| other.cluster as usize)
+                    .filter(|&start| start > info.cluster as usize)
+                    .min()
+                    .unwrap_or(cluster.text.len());
+                let text = cluster
+                    .text
+                    .get(info.cluster as usize..end)
+                    .unwrap_or("")
+                    .to_string();
+                shaped.glyphs.push(if info.glyph_pos == MISSING_GLYPH {
+                    ShapedGlyph {
+                        text,
+                        glyph: None,
+                        font: None,
+                    }
+                } else {
+                    ShapedGlyph {
+                        text,
+                        glyph: Some(info.glyph_pos),
+                        font: Some(
+                            handles
+                                .get(info.font_idx)
+                                .map(|handle| handle.diagnostic_string())
+                                .unwrap_or_else(|| format!("font_idx {}", info.font_idx)),
+                        ),
+                    }
+                });
+            }
+            output.shaped.push(shaped);
+        }
+    }
+
+    match cmd.format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&output)?),
+        OutputFormat::Table => {
+            for (idx, chain) in output.fonts.iter().enumerate() {
+                if idx > 0 {
+                    println!();
+                }
+                chain.print();
+            }
+            for shaped in &output.shaped {
+                println!();
+                shaped.print();
+            }
+        }
+    }
+
+    Ok(())
+}
+
 fn fatal_toast_notification(title: &str, message: &str) {
     persistent_toast_notification(title, message);
     // We need a short delay otherwise the notification
@@ -376,9 +674,14 @@ fn fatal_toast_notification(title: &str, message: &str) {
 fn notify_on_panic() {
     let default_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |info| {
-        if let Some(s) = info.payload().downcast_ref::<&str>() {
-            fatal_toast_notification("Wezterm panic", s);
+        let mut message = crashreport::panic_message(info);
+        if let Some(path) = crashreport::write_crash_report(info) {
+            message.push_str(&format!(
+                "\nA crash report was written to {}",
+                path.display()
+            ));
         }
+        fatal_toast_notification("Wezterm panic", &message);
         default_hook(info);
     }));
 }
@@ -485,5 +788,7 @@ fn run() -> anyhow::Result<()> {
         SubCommand::Ssh(ssh) => run_ssh(ssh),
         SubCommand::Serial(serial) => run_serial(config, &serial),
         SubCommand::Connect(connect) => run_mux_client(config, &connect),
+        SubCommand::LsFonts(cmd) => run_ls_fonts(config, &cmd),
+        SubCommand::Version(cmd) => run_version(&cmd),
     }
 }
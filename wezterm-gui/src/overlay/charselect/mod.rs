@@ -0,0 +1,620 @@
+//! The character selector is an overlay that allows searching for
+//! a character by name, or browsing the characters by category,
+//! and then sends the chosen character to the pane.
+//! The names are compiled in; see ci/generate-charselect-names.py.
+use lazy_static::lazy_static;
+use mux::pane::PaneId;
+use mux::termwiztermtab::TermWizTerminal;
+use mux::Mux;
+use std::io::Write;
+use std::sync::Mutex;
+use termwiz::cell::{AttributeChange, CellAttributes};
+use termwiz::color::ColorAttribute;
+use termwiz::input::{InputEvent, KeyCode, KeyEvent, Modifiers, MouseButtons, MouseEvent};
+use termwiz::surface::{Change, Position};
+use termwiz::terminal::Terminal;
+use unicode_width::UnicodeWidthChar;
+
+#[rustfmt::skip]
+mod names;
+
+/// How many characters are remembered in the recently used group
+const MAX_RECENT: usize = 32;
+
+/// The number of rows used by the header that precedes the list
+const HEADER_ROWS: usize = 2;
+
+lazy_static! {
+    /// The characters that were most recently chosen, most recent first.
+    /// This is shared by all windows for the lifetime of the process.
+    static ref RECENT: Mutex<Vec<char>> = Mutex::new(vec![]);
+}
+
+/// A selection of commonly used glyphs from the Nerd Fonts patched
+/// fonts (powerline and Font Awesome).  These live in the private use
+/// area, so they render only if such a font is in the fallback list.
+const NERD_FONTS: &[(char, &str)] = &[
+    ('\u{E0A0}', "nf-pl-branch"),
+    ('\u{E0A1}', "nf-pl-line_number"),
+    ('\u{E0A2}', "nf-pl-hostname"),
+    ('\u{E0B0}', "nf-pl-left_hard_divider"),
+    ('\u{E0B1}', "nf-pl-left_soft_divider"),
+    ('\u{E0B2}', "nf-pl-right_hard_divider"),
+    ('\u{E0B3}', "nf-pl-right_soft_divider"),
+    ('\u{E0B4}', "nf-ple-right_half_circle_thick"),
+    ('\u{E0B6}', "nf-ple-left_half_circle_thick"),
+    ('\u{E0C0}', "nf-ple-flame_thick"),
+    ('\u{F000}', "nf-fa-glass"),
+    ('\u{F001}', "nf-fa-music"),
+    ('\u{F002}', "nf-fa-search"),
+    ('\u{F004}', "nf-fa-heart"),
+    ('\u{F005}', "nf-fa-star"),
+    ('\u{F006}', "nf-fa-star_o"),
+    ('\u{F007}', "nf-fa-user"),
+    ('\u{F008}', "nf-fa-film"),
+    ('\u{F00C}', "nf-fa-check"),
+    ('\u{F00D}', "nf-fa-times"),
+    ('\u{F011}', "nf-fa-power_off"),
+    ('\u{F012}', "nf-fa-signal"),
+    ('\u{F013}', "nf-fa-cog"),
+    ('\u{F014}', "nf-fa-trash_o"),
+    ('\u{F015}', "nf-fa-home"),
+    ('\u{F016}', "nf-fa-file_o"),
+    ('\u{F017}', "nf-fa-clock_o"),
+    ('\u{F019}', "nf-fa-download"),
+    ('\u{F01C}', "nf-fa-inbox"),
+    ('\u{F021}', "nf-fa-refresh"),
+    ('\u{F023}', "nf-fa-lock"),
+    ('\u{F024}', "nf-fa-flag"),
+    ('\u{F025}', "nf-fa-headphones"),
+    ('\u{F028}', "nf-fa-volume_up"),
+    ('\u{F02B}', "nf-fa-tag"),
+    ('\u{F02D}', "nf-fa-book"),
+    ('\u{F02E}', "nf-fa-bookmark"),
+    ('\u{F02F}', "nf-fa-print"),
+    ('\u{F030}', "nf-fa-camera"),
+    ('\u{F040}', "nf-fa-pencil"),
+    ('\u{F041}', "nf-fa-map_marker"),
+    ('\u{F044}', "nf-fa-edit"),
+    ('\u{F04B}', "nf-fa-play"),
+    ('\u{F04C}', "nf-fa-pause"),
+    ('\u{F04D}', "nf-fa-stop"),
+    ('\u{F053}', "nf-fa-chevron_left"),
+    ('\u{F054}', "nf-fa-chevron_right"),
+    ('\u{F055}', "nf-fa-plus_circle"),
+    ('\u{F056}', "nf-fa-minus_circle"),
+    ('\u{F057}', "nf-fa-times_circle"),
+    ('\u{F058}', "nf-fa-check_circle"),
+    ('\u{F059}', "nf-fa-question_circle"),
+    ('\u{F05A}', "nf-fa-info_circle"),
+    ('\u{F05E}', "nf-fa-ban"),
+    ('\u{F060}', "nf-fa-arrow_left"),
+    ('\u{F061}', "nf-fa-arrow_right"),
+    ('\u{F062}', "nf-fa-arrow_up"),
+    ('\u{F063}', "nf-fa-arrow_down"),
+    ('\u{F067}', "nf-fa-plus"),
+    ('\u{F068}', "nf-fa-minus"),
+    ('\u{F06A}', "nf-fa-exclamation_circle"),
+    ('\u{F06E}', "nf-fa-eye"),
+    ('\u{F071}', "nf-fa-warning"),
+    ('\u{F073}', "nf-fa-calendar"),
+    ('\u{F075}', "nf-fa-comment"),
+    ('\u{F07B}', "nf-fa-folder"),
+    ('\u{F07C}', "nf-fa-folder_open"),
+    ('\u{F084}', "nf-fa-key"),
+    ('\u{F085}', "nf-fa-cogs"),
+    ('\u{F09B}', "nf-fa-github"),
+    ('\u{F09C}', "nf-fa-unlock"),
+    ('\u{F0AC}', "nf-fa-globe"),
+    ('\u{F0AD}', "nf-fa-wrench"),
+    ('\u{F0B0}', "nf-fa-filter"),
+    ('\u{F0B1}', "nf-fa-briefcase"),
+    ('\u{F0C2}', "nf-fa-cloud"),
+    ('\u{F0C7}', "nf-fa-floppy_o"),
+    ('\u{F0C9}', "nf-fa-bars"),
+    ('\u{F0E0}', "nf-fa-envelope"),
+    ('\u{F0E7}', "nf-fa-bolt"),
+    ('\u{F0E8}', "nf-fa-sitemap"),
+    ('\u{F0F3}', "nf-fa-bell"),
+    ('\u{F0F4}', "nf-fa-coffee"),
+    ('\u{F120}', "nf-fa-terminal"),
+    ('\u{F121}', "nf-fa-code"),
+    ('\u{F126}', "nf-fa-code_fork"),
+    ('\u{F15B}', "nf-fa-file"),
+    ('\u{F179}', "nf-fa-apple"),
+    ('\u{F17A}', "nf-fa-windows"),
+    ('\u{F17B}', "nf-fa-android"),
+    ('\u{F17C}', "nf-fa-linux"),
+    ('\u{F188}', "nf-fa-bug"),
+    ('\u{F1C0}', "nf-fa-database"),
+    ('\u{F1D3}', "nf-fa-git"),
+    ('\u{F1EB}', "nf-fa-wifi"),
+    ('\u{F233}', "nf-fa-server"),
+    ('\u{F240}', "nf-fa-battery_full"),
+    ('\u{F244}', "nf-fa-battery_empty"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharSelectGroup {
+    RecentlyUsed,
+    Emoticons,
+    Pictographs,
+    TransportAndMap,
+    SymbolsAndDingbats,
+    Arrows,
+    Mathematical,
+    Technical,
+    BoxDrawing,
+    GeometricShapes,
+    PunctuationAndCurrency,
+    Latin,
+    Greek,
+    NerdFonts,
+}
+
+/// The groups in the order that they are cycled through
+const GROUPS: &[CharSelectGroup] = &[
+    CharSelectGroup::RecentlyUsed,
+    CharSelectGroup::Emoticons,
+    CharSelectGroup::Pictographs,
+    CharSelectGroup::TransportAndMap,
+    CharSelectGroup::SymbolsAndDingbats,
+    CharSelectGroup::Arrows,
+    CharSelectGroup::Mathematical,
+    CharSelectGroup::Technical,
+    CharSelectGroup::BoxDrawing,
+    CharSelectGroup::GeometricShapes,
+    CharSelectGroup::PunctuationAndCurrency,
+    CharSelectGroup::Latin,
+    CharSelectGroup::Greek,
+    CharSelectGroup::NerdFonts,
+];
+
+impl CharSelectGroup {
+    fn label(self) -> &'static str {
+        match self {
+            Self::RecentlyUsed => "Recently Used",
+            Self::Emoticons => "Emoticons",
+            Self::Pictographs => "Pictographs",
+            Self::TransportAndMap => "Transport & Map",
+            Self::SymbolsAndDingbats => "Symbols & Dingbats",
+            Self::Arrows => "Arrows",
+            Self::Mathematical => "Mathematical",
+            Self::Technical => "Technical",
+            Self::BoxDrawing => "Box Drawing",
+            Self::GeometricShapes => "Geometric Shapes",
+            Self::PunctuationAndCurrency => "Punctuation & Currency",
+            Self::Latin => "Latin",
+            Self::Greek => "Greek",
+            Self::NerdFonts => "Nerd Fonts",
+        }
+    }
+
+    /// The compiled-in table for this group.
+    /// The recently used group is dynamic and has no table.
+    fn table(self) -> &'static [(char, &'static str)] {
+        match self {
+            Self::RecentlyUsed => &[],
+            Self::Emoticons => names::EMOTICONS,
+            Self::Pictographs => names::PICTOGRAPHS,
+            Self::TransportAndMap => names::TRANSPORT_AND_MAP,
+            Self::SymbolsAndDingbats => names::SYMBOLS_AND_DINGBATS,
+            Self::Arrows => names::ARROWS,
+            Self::Mathematical => names::MATHEMATICAL,
+            Self::Technical => names::TECHNICAL,
+            Self::BoxDrawing => names::BOX_DRAWING,
+            Self::GeometricShapes => names::GEOMETRIC_SHAPES,
+            Self::PunctuationAndCurrency => names::PUNCTUATION_AND_CURRENCY,
+            Self::Latin => names::LATIN,
+            Self::Greek => names::GREEK,
+            Self::NerdFonts => NERD_FONTS,
+        }
+    }
+
+    fn entries(self) -> Vec<Entry> {
+        match self {
+            Self::RecentlyUsed => RECENT
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|&c| Entry {
+                    c,
+                    name: name_of_char(c),
+                })
+                .collect(),
+            group => group
+                .table()
+                .iter()
+                .map(|&(c, name)| Entry { c, name })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Entry {
+    c: char,
+    name: &'static str,
+}
+
+fn name_of_char(c: char) -> &'static str {
+    GROUPS
+        .iter()
+        .flat_map(|group| group.table().iter())
+        .find(|(candidate, _)| *candidate == c)
+        .map(|(_, name)| *name)
+        .unwrap_or("")
+}
+
+/// Record `c` as the most recently used character
+fn remember(c: char) {
+    let mut recent = RECENT.lock().unwrap();
+    recent.retain(|&r| r != c);
+    recent.insert(0, c);
+    recent.truncate(MAX_RECENT);
+}
+
+/// Computes a fuzzy match score for `query` against `name`.
+/// Each character of the query must be present in the name, in order,
+/// but not necessarily adjacent.  Consecutive matches and matches at
+/// the start of a word score more highly.
+/// Returns None if the name doesn't match.
+pub(super) fn fuzzy_score(query: &str, name: &str) -> Option<i64> {
+    let mut score = 0;
+    let mut prev_matched = false;
+    let mut prev_char = ' ';
+    let mut query_chars = query.chars().filter(|c| !c.is_whitespace()).peekable();
+
+    for c in name.chars() {
+        let want = match query_chars.peek() {
+            Some(want) => *want,
+            None => break,
+        };
+        if c.eq_ignore_ascii_case(&want) {
+            query_chars.next();
+            score += 1;
+            if prev_matched {
+                score += 5;
+            }
+            if !prev_char.is_alphanumeric() {
+                score += 8;
+            }
+            prev_matched = true;
+        } else {
+            prev_matched = false;
+        }
+        prev_char = c;
+    }
+
+    if query_chars.peek().is_some() {
+        None
+    } else {
+        Some(score)
+    }
+}
+
+/// Returns the entries matching `query`, best match first.
+/// A query of the form `U+XXXX` selects a character by codepoint.
+fn search(query: &str) -> Vec<Entry> {
+    let query = query.trim();
+
+    if let Some(hex) = query
+        .strip_prefix("U+")
+        .or_else(|| query.strip_prefix("u+"))
+    {
+        return u32::from_str_radix(hex, 16)
+            .ok()
+            .and_then(std::char::from_u32)
+            .map(|c| {
+                vec![Entry {
+                    c,
+                    name: name_of_char(c),
+                }]
+            })
+            .unwrap_or_else(Vec::new);
+    }
+
+    let mut matches: Vec<(i64, Entry)> = GROUPS
+        .iter()
+        .flat_map(|group| group.table().iter())
+        .filter_map(|&(c, name)| fuzzy_score(query, name).map(|score| (score, Entry { c, name })))
+        .collect();
+    matches.sort_by(|(a_score, a), (b_score, b)| {
+        b_score
+            .cmp(a_score)
+            .then_with(|| a.name.len().cmp(&b.name.len()))
+    });
+    matches.into_iter().map(|(_, entry)| entry).collect()
+}
+
+struct CharSelector {
+    group_idx: usize,
+    query: String,
+    entries: Vec<Entry>,
+    active_idx: usize,
+    top_row: usize,
+}
+
+impl CharSelector {
+    fn new() -> Self {
+        let mut selector = Self {
+            group_idx: 0,
+            query: String::new(),
+            entries: vec![],
+            active_idx: 0,
+            top_row: 0,
+        };
+        selector.update_entries();
+        if selector.entries.is_empty() {
+            // Nothing has been used yet, so start with something
+            // more interesting than an empty list
+            selector.group_idx = 1;
+            selector.update_entries();
+        }
+        selector
+    }
+
+    fn update_entries(&mut self) {
+        self.entries = if self.query.is_empty() {
+            GROUPS[self.group_idx].entries()
+        } else {
+            search(&self.query)
+        };
+        self.active_idx = 0;
+        self.top_row = 0;
+    }
+
+    fn cycle_group(&mut self, delta: isize) {
+        let num_groups = GROUPS.len() as isize;
+        self.group_idx = (self.group_idx as isize + delta).rem_euclid(num_groups) as usize;
+        self.query.clear();
+        self.update_entries();
+    }
+
+    fn move_by(&mut self, delta: isize, visible_rows: usize) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let max_idx = self.entries.len() as isize - 1;
+        self.active_idx = (self.active_idx as isize + delta).max(0).min(max_idx) as usize;
+
+        // Keep the active entry in view
+        if self.active_idx < self.top_row {
+            self.top_row = self.active_idx;
+        } else if visible_rows > 0 && self.active_idx >= self.top_row + visible_rows {
+            self.top_row = self.active_idx + 1 - visible_rows;
+        }
+    }
+
+    fn render(&self, term: &mut TermWizTerminal) -> termwiz::Result<()> {
+        let size = term.get_screen_size()?;
+        let visible_rows = size.rows.saturating_sub(HEADER_ROWS);
+
+        let status = if self.query.is_empty() {
+            format!(
+                "{}: Tab/Shift-Tab to change category, type to search, \
+                 Enter to send, Escape to cancel",
+                GROUPS[self.group_idx].label()
+            )
+        } else {
+            format!(
+                "{} matches: Enter to send, Escape to cancel",
+                self.entries.len()
+            )
+        };
+
+        let mut changes = vec![
+            Change::ClearScreen(ColorAttribute::Default),
+            Change::CursorPosition {
+                x: Position::Absolute(0),
+                y: Position::Absolute(1),
+            },
+            Change::Text(truncate(&status, size.cols)),
+            Change::CursorPosition {
+                x: Position::Absolute(0),
+                y: Position::Absolute(HEADER_ROWS),
+            },
+            Change::AllAttributes(CellAttributes::default()),
+        ];
+
+        for (idx, entry) in self
+            .entries
+            .iter()
+            .enumerate()
+            .skip(self.top_row)
+            .take(visible_rows)
+        {
+            if idx == self.active_idx {
+                changes.push(AttributeChange::Reverse(true).into());
+            }
+
+            let line = format!(" {}  {} (U+{:X})", entry.c, entry.name, entry.c as u32);
+            changes.push(Change::Text(format!("{}\r\n", truncate(&line, size.cols))));
+
+            if idx == self.active_idx {
+                changes.push(AttributeChange::Reverse(false).into());
+            }
+        }
+
+        // Leave the cursor at the end of the search text
+        let prompt = format!("Search: {}", self.query);
+        changes.push(Change::CursorPosition {
+            x: Position::Absolute(0),
+            y: Position::Absolute(0),
+        });
+        changes.push(Change::Text(prompt));
+
+        term.render(&changes)?;
+        term.flush()
+    }
+}
+
+/// Shorten `text` so that it fits within `cols` columns.
+/// Wide characters are assumed to take two cells.
+pub(super) fn truncate(text: &str, cols: usize) -> String {
+    let mut width = 0;
+    text.chars()
+        .take_while(|c| {
+            width += c.width().unwrap_or(0);
+            width < cols
+        })
+        .collect()
+}
+
+fn send_char(pane_id: PaneId, c: char) {
+    remember(c);
+    promise::spawn::spawn_into_main_thread(async move {
+        let mux = Mux::get().unwrap();
+        // Respect the read-only and broadcast input modes of the pane
+        let mut buf = [0u8; 4];
+        let encoded = c.encode_utf8(&mut buf);
+        for pane in mux.resolve_input_targets(pane_id) {
+            pane.writer().write_all(encoded.as_bytes()).ok();
+        }
+    })
+    .detach();
+}
+
+pub fn char_select(pane_id: PaneId, mut term: TermWizTerminal) -> anyhow::Result<()> {
+    let mut selector = CharSelector::new();
+
+    term.set_raw_mode()?;
+    term.render(&[Change::Title("Character Selector".to_string())])?;
+    selector.render(&mut term)?;
+
+    while let Ok(Some(event)) = term.poll_input(None) {
+        let visible_rows = term
+            .get_screen_size()
+            .map(|size| size.rows.saturating_sub(HEADER_ROWS))
+            .unwrap_or(0);
+
+        match event {
+            InputEvent::Key(KeyEvent {
+                key: KeyCode::Escape,
+                ..
+            }) => {
+                break;
+            }
+            InputEvent::Key(KeyEvent {
+                key: KeyCode::Enter,
+                ..
+            }) => {
+                if let Some(entry) = selector.entries.get(selector.active_idx) {
+                    send_char(pane_id, entry.c);
+                }
+                break;
+            }
+            InputEvent::Key(KeyEvent {
+                key: KeyCode::UpArrow,
+                ..
+            }) => {
+                selector.move_by(-1, visible_rows);
+            }
+            InputEvent::Key(KeyEvent {
+                key: KeyCode::DownArrow,
+                ..
+            }) => {
+                selector.move_by(1, visible_rows);
+            }
+            InputEvent::Key(KeyEvent {
+                key: KeyCode::PageUp,
+                ..
+            }) => {
+                selector.move_by(-(visible_rows as isize), visible_rows);
+            }
+            InputEvent::Key(KeyEvent {
+                key: KeyCode::PageDown,
+                ..
+            }) => {
+                selector.move_by(visible_rows as isize, visible_rows);
+            }
+            InputEvent::Key(KeyEvent {
+                key: KeyCode::Tab,
+                modifiers,
+            }) => {
+                selector.cycle_group(if modifiers.contains(Modifiers::SHIFT) {
+                    -1
+                } else {
+                    1
+                });
+            }
+            InputEvent::Key(KeyEvent {
+                key: KeyCode::Backspace,
+                ..
+            }) => {
+                selector.query.pop();
+                selector.update_entries();
+            }
+            InputEvent::Key(KeyEvent {
+                key: KeyCode::Char('u'),
+                modifiers,
+            }) if modifiers == Modifiers::CTRL => {
+                selector.query.clear();
+                selector.update_entries();
+            }
+            InputEvent::Key(KeyEvent {
+                key: KeyCode::Char(c),
+                modifiers,
+            }) if !modifiers.intersects(Modifiers::CTRL | Modifiers::ALT | Modifiers::SUPER) => {
+                selector.query.push(c);
+                selector.update_entries();
+            }
+            InputEvent::Mouse(MouseEvent {
+                y, mouse_buttons, ..
+            }) => {
+                let row = y as usize;
+                if row >= HEADER_ROWS {
+                    let idx = selector.top_row + row - HEADER_ROWS;
+                    if idx < selector.entries.len() {
+                        selector.active_idx = idx;
+
+                        if mouse_buttons == MouseButtons::LEFT {
+                            send_char(pane_id, selector.entries[idx].c);
+                            break;
+                        }
+                    }
+                }
+                if mouse_buttons != MouseButtons::NONE && mouse_buttons != MouseButtons::LEFT {
+                    // Treat any other mouse button as cancel
+                    break;
+                }
+            }
+            _ => {}
+        }
+        selector.render(&mut term)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fuzzy() {
+        assert_eq!(fuzzy_score("xyz", "grinning face"), None);
+        assert!(fuzzy_score("grin", "grinning face").is_some());
+        assert!(fuzzy_score("gf", "grinning face").is_some());
+        assert!(fuzzy_score("fg", "grinning face").is_none());
+
+        // Matching at word boundaries and consecutive letters
+        // is preferred over scattered letters
+        assert!(fuzzy_score("face", "grinning face") > fuzzy_score("face", "fast acorn eater"));
+    }
+
+    #[test]
+    fn search_by_name_and_codepoint() {
+        let results = search("grinning face");
+        assert_eq!(results[0].c, '\u{1F600}');
+
+        let results = search("U+2603");
+        assert_eq!(
+            results,
+            vec![Entry {
+                c: '\u{2603}',
+                name: "snowman"
+            }]
+        );
+    }
+}
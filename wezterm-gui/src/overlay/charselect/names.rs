@@ -0,0 +1,3849 @@
+//! This file was generated by ci/generate-charselect-names.py
+//! using unicode 14.0.0; do not edit it by hand.
+
+pub const EMOTICONS: &[(char, &str)] = &[
+    ('\u{1F600}', "grinning face"),
+    ('\u{1F601}', "grinning face with smiling eyes"),
+    ('\u{1F602}', "face with tears of joy"),
+    ('\u{1F603}', "smiling face with open mouth"),
+    ('\u{1F604}', "smiling face with open mouth and smiling eyes"),
+    ('\u{1F605}', "smiling face with open mouth and cold sweat"),
+    ('\u{1F606}', "smiling face with open mouth and tightly-closed eyes"),
+    ('\u{1F607}', "smiling face with halo"),
+    ('\u{1F608}', "smiling face with horns"),
+    ('\u{1F609}', "winking face"),
+    ('\u{1F60A}', "smiling face with smiling eyes"),
+    ('\u{1F60B}', "face savouring delicious food"),
+    ('\u{1F60C}', "relieved face"),
+    ('\u{1F60D}', "smiling face with heart-shaped eyes"),
+    ('\u{1F60E}', "smiling face with sunglasses"),
+    ('\u{1F60F}', "smirking face"),
+    ('\u{1F610}', "neutral face"),
+    ('\u{1F611}', "expressionless face"),
+    ('\u{1F612}', "unamused face"),
+    ('\u{1F613}', "face with cold sweat"),
+    ('\u{1F614}', "pensive face"),
+    ('\u{1F615}', "confused face"),
+    ('\u{1F616}', "confounded face"),
+    ('\u{1F617}', "kissing face"),
+    ('\u{1F618}', "face throwing a kiss"),
+    ('\u{1F619}', "kissing face with smiling eyes"),
+    ('\u{1F61A}', "kissing face with closed eyes"),
+    ('\u{1F61B}', "face with stuck-out tongue"),
+    ('\u{1F61C}', "face with stuck-out tongue and winking eye"),
+    ('\u{1F61D}', "face with stuck-out tongue and tightly-closed eyes"),
+    ('\u{1F61E}', "disappointed face"),
+    ('\u{1F61F}', "worried face"),
+    ('\u{1F620}', "angry face"),
+    ('\u{1F621}', "pouting face"),
+    ('\u{1F622}', "crying face"),
+    ('\u{1F623}', "persevering face"),
+    ('\u{1F624}', "face with look of triumph"),
+    ('\u{1F625}', "disappointed but relieved face"),
+    ('\u{1F626}', "frowning face with open mouth"),
+    ('\u{1F627}', "anguished face"),
+    ('\u{1F628}', "fearful face"),
+    ('\u{1F629}', "weary face"),
+    ('\u{1F62A}', "sleepy face"),
+    ('\u{1F62B}', "tired face"),
+    ('\u{1F62C}', "grimacing face"),
+    ('\u{1F62D}', "loudly crying face"),
+    ('\u{1F62E}', "face with open mouth"),
+    ('\u{1F62F}', "hushed face"),
+    ('\u{1F630}', "face with open mouth and cold sweat"),
+    ('\u{1F631}', "face screaming in fear"),
+    ('\u{1F632}', "astonished face"),
+    ('\u{1F633}', "flushed face"),
+    ('\u{1F634}', "sleeping face"),
+    ('\u{1F635}', "dizzy face"),
+    ('\u{1F636}', "face without mouth"),
+    ('\u{1F637}', "face with medical mask"),
+    ('\u{1F638}', "grinning cat face with smiling eyes"),
+    ('\u{1F639}', "cat face with tears of joy"),
+    ('\u{1F63A}', "smiling cat face with open mouth"),
+    ('\u{1F63B}', "smiling cat face with heart-shaped eyes"),
+    ('\u{1F63C}', "cat face with wry smile"),
+    ('\u{1F63D}', "kissing cat face with closed eyes"),
+    ('\u{1F63E}', "pouting cat face"),
+    ('\u{1F63F}', "crying cat face"),
+    ('\u{1F640}', "weary cat face"),
+    ('\u{1F641}', "slightly frowning face"),
+    ('\u{1F642}', "slightly smiling face"),
+    ('\u{1F643}', "upside-down face"),
+    ('\u{1F644}', "face with rolling eyes"),
+    ('\u{1F645}', "face with no good gesture"),
+    ('\u{1F646}', "face with ok gesture"),
+    ('\u{1F647}', "person bowing deeply"),
+    ('\u{1F648}', "see-no-evil monkey"),
+    ('\u{1F649}', "hear-no-evil monkey"),
+    ('\u{1F64A}', "speak-no-evil monkey"),
+    ('\u{1F64B}', "happy person raising one hand"),
+    ('\u{1F64C}', "person raising both hands in celebration"),
+    ('\u{1F64D}', "person frowning"),
+    ('\u{1F64E}', "person with pouting face"),
+    ('\u{1F64F}', "person with folded hands"),
+];
+
+pub const PICTOGRAPHS: &[(char, &str)] = &[
+    ('\u{1F300}', "cyclone"),
+    ('\u{1F301}', "foggy"),
+    ('\u{1F302}', "closed umbrella"),
+    ('\u{1F303}', "night with stars"),
+    ('\u{1F304}', "sunrise over mountains"),
+    ('\u{1F305}', "sunrise"),
+    ('\u{1F306}', "cityscape at dusk"),
+    ('\u{1F307}', "sunset over buildings"),
+    ('\u{1F308}', "rainbow"),
+    ('\u{1F309}', "bridge at night"),
+    ('\u{1F30A}', "water wave"),
+    ('\u{1F30B}', "volcano"),
+    ('\u{1F30C}', "milky way"),
+    ('\u{1F30D}', "earth globe europe-africa"),
+    ('\u{1F30E}', "earth globe americas"),
+    ('\u{1F30F}', "earth globe asia-australia"),
+    ('\u{1F310}', "globe with meridians"),
+    ('\u{1F311}', "new moon symbol"),
+    ('\u{1F312}', "waxing crescent moon symbol"),
+    ('\u{1F313}', "first quarter moon symbol"),
+    ('\u{1F314}', "waxing gibbous moon symbol"),
+    ('\u{1F315}', "full moon symbol"),
+    ('\u{1F316}', "waning gibbous moon symbol"),
+    ('\u{1F317}', "last quarter moon symbol"),
+    ('\u{1F318}', "waning crescent moon symbol"),
+    ('\u{1F319}', "crescent moon"),
+    ('\u{1F31A}', "new moon with face"),
+    ('\u{1F31B}', "first quarter moon with face"),
+    ('\u{1F31C}', "last quarter moon with face"),
+    ('\u{1F31D}', "full moon with face"),
+    ('\u{1F31E}', "sun with face"),
+    ('\u{1F31F}', "glowing star"),
+    ('\u{1F320}', "shooting star"),
+    ('\u{1F321}', "thermometer"),
+    ('\u{1F322}', "black droplet"),
+    ('\u{1F323}', "white sun"),
+    ('\u{1F324}', "white sun with small cloud"),
+    ('\u{1F325}', "white sun behind cloud"),
+    ('\u{1F326}', "white sun behind cloud with rain"),
+    ('\u{1F327}', "cloud with rain"),
+    ('\u{1F328}', "cloud with snow"),
+    ('\u{1F329}', "cloud with lightning"),
+    ('\u{1F32A}', "cloud with tornado"),
+    ('\u{1F32B}', "fog"),
+    ('\u{1F32C}', "wind blowing face"),
+    ('\u{1F32D}', "hot dog"),
+    ('\u{1F32E}', "taco"),
+    ('\u{1F32F}', "burrito"),
+    ('\u{1F330}', "chestnut"),
+    ('\u{1F331}', "seedling"),
+    ('\u{1F332}', "evergreen tree"),
+    ('\u{1F333}', "deciduous tree"),
+    ('\u{1F334}', "palm tree"),
+    ('\u{1F335}', "cactus"),
+    ('\u{1F336}', "hot pepper"),
+    ('\u{1F337}', "tulip"),
+    ('\u{1F338}', "cherry blossom"),
+    ('\u{1F339}', "rose"),
+    ('\u{1F33A}', "hibiscus"),
+    ('\u{1F33B}', "sunflower"),
+    ('\u{1F33C}', "blossom"),
+    ('\u{1F33D}', "ear of maize"),
+    ('\u{1F33E}', "ear of rice"),
+    ('\u{1F33F}', "herb"),
+    ('\u{1F340}', "four leaf clover"),
+    ('\u{1F341}', "maple leaf"),
+    ('\u{1F342}', "fallen leaf"),
+    ('\u{1F343}', "leaf fluttering in wind"),
+    ('\u{1F344}', "mushroom"),
+    ('\u{1F345}', "tomato"),
+    ('\u{1F346}', "aubergine"),
+    ('\u{1F347}', "grapes"),
+    ('\u{1F348}', "melon"),
+    ('\u{1F349}', "watermelon"),
+    ('\u{1F34A}', "tangerine"),
+    ('\u{1F34B}', "lemon"),
+    ('\u{1F34C}', "banana"),
+    ('\u{1F34D}', "pineapple"),
+    ('\u{1F34E}', "red apple"),
+    ('\u{1F34F}', "green apple"),
+    ('\u{1F350}', "pear"),
+    ('\u{1F351}', "peach"),
+    ('\u{1F352}', "cherries"),
+    ('\u{1F353}', "strawberry"),
+    ('\u{1F354}', "hamburger"),
+    ('\u{1F355}', "slice of pizza"),
+    ('\u{1F356}', "meat on bone"),
+    ('\u{1F357}', "poultry leg"),
+    ('\u{1F358}', "rice cracker"),
+    ('\u{1F359}', "rice ball"),
+    ('\u{1F35A}', "cooked rice"),
+    ('\u{1F35B}', "curry and rice"),
+    ('\u{1F35C}', "steaming bowl"),
+    ('\u{1F35D}', "spaghetti"),
+    ('\u{1F35E}', "bread"),
+    ('\u{1F35F}', "french fries"),
+    ('\u{1F360}', "roasted sweet potato"),
+    ('\u{1F361}', "dango"),
+    ('\u{1F362}', "oden"),
+    ('\u{1F363}', "sushi"),
+    ('\u{1F364}', "fried shrimp"),
+    ('\u{1F365}', "fish cake with swirl design"),
+    ('\u{1F366}', "soft ice cream"),
+    ('\u{1F367}', "shaved ice"),
+    ('\u{1F368}', "ice cream"),
+    ('\u{1F369}', "doughnut"),
+    ('\u{1F36A}', "cookie"),
+    ('\u{1F36B}', "chocolate bar"),
+    ('\u{1F36C}', "candy"),
+    ('\u{1F36D}', "lollipop"),
+    ('\u{1F36E}', "custard"),
+    ('\u{1F36F}', "honey pot"),
+    ('\u{1F370}', "shortcake"),
+    ('\u{1F371}', "bento box"),
+    ('\u{1F372}', "pot of food"),
+    ('\u{1F373}', "cooking"),
+    ('\u{1F374}', "fork and knife"),
+    ('\u{1F375}', "teacup without handle"),
+    ('\u{1F376}', "sake bottle and cup"),
+    ('\u{1F377}', "wine glass"),
+    ('\u{1F378}', "cocktail glass"),
+    ('\u{1F379}', "tropical drink"),
+    ('\u{1F37A}', "beer mug"),
+    ('\u{1F37B}', "clinking beer mugs"),
+    ('\u{1F37C}', "baby bottle"),
+    ('\u{1F37D}', "fork and knife with plate"),
+    ('\u{1F37E}', "bottle with popping cork"),
+    ('\u{1F37F}', "popcorn"),
+    ('\u{1F380}', "ribbon"),
+    ('\u{1F381}', "wrapped present"),
+    ('\u{1F382}', "birthday cake"),
+    ('\u{1F383}', "jack-o-lantern"),
+    ('\u{1F384}', "christmas tree"),
+    ('\u{1F385}', "father christmas"),
+    ('\u{1F386}', "fireworks"),
+    ('\u{1F387}', "firework sparkler"),
+    ('\u{1F388}', "balloon"),
+    ('\u{1F389}', "party popper"),
+    ('\u{1F38A}', "confetti ball"),
+    ('\u{1F38B}', "tanabata tree"),
+    ('\u{1F38C}', "crossed flags"),
+    ('\u{1F38D}', "pine decoration"),
+    ('\u{1F38E}', "japanese dolls"),
+    ('\u{1F38F}', "carp streamer"),
+    ('\u{1F390}', "wind chime"),
+    ('\u{1F391}', "moon viewing ceremony"),
+    ('\u{1F392}', "school satchel"),
+    ('\u{1F393}', "graduation cap"),
+    ('\u{1F394}', "heart with tip on the left"),
+    ('\u{1F395}', "bouquet of flowers"),
+    ('\u{1F396}', "military medal"),
+    ('\u{1F397}', "reminder ribbon"),
+    ('\u{1F398}', "musical keyboard with jacks"),
+    ('\u{1F399}', "studio microphone"),
+    ('\u{1F39A}', "level slider"),
+    ('\u{1F39B}', "control knobs"),
+    ('\u{1F39C}', "beamed ascending musical notes"),
+    ('\u{1F39D}', "beamed descending musical notes"),
+    ('\u{1F39E}', "film frames"),
+    ('\u{1F39F}', "admission tickets"),
+    ('\u{1F3A0}', "carousel horse"),
+    ('\u{1F3A1}', "ferris wheel"),
+    ('\u{1F3A2}', "roller coaster"),
+    ('\u{1F3A3}', "fishing pole and fish"),
+    ('\u{1F3A4}', "microphone"),
+    ('\u{1F3A5}', "movie camera"),
+    ('\u{1F3A6}', "cinema"),
+    ('\u{1F3A7}', "headphone"),
+    ('\u{1F3A8}', "artist palette"),
+    ('\u{1F3A9}', "top hat"),
+    ('\u{1F3AA}', "circus tent"),
+    ('\u{1F3AB}', "ticket"),
+    ('\u{1F3AC}', "clapper board"),
+    ('\u{1F3AD}', "performing arts"),
+    ('\u{1F3AE}', "video game"),
+    ('\u{1F3AF}', "direct hit"),
+    ('\u{1F3B0}', "slot machine"),
+    ('\u{1F3B1}', "billiards"),
+    ('\u{1F3B2}', "game die"),
+    ('\u{1F3B3}', "bowling"),
+    ('\u{1F3B4}', "flower playing cards"),
+    ('\u{1F3B5}', "musical note"),
+    ('\u{1F3B6}', "multiple musical notes"),
+    ('\u{1F3B7}', "saxophone"),
+    ('\u{1F3B8}', "guitar"),
+    ('\u{1F3B9}', "musical keyboard"),
+    ('\u{1F3BA}', "trumpet"),
+    ('\u{1F3BB}', "violin"),
+    ('\u{1F3BC}', "musical score"),
+    ('\u{1F3BD}', "running shirt with sash"),
+    ('\u{1F3BE}', "tennis racquet and ball"),
+    ('\u{1F3BF}', "ski and ski boot"),
+    ('\u{1F3C0}', "basketball and hoop"),
+    ('\u{1F3C1}', "chequered flag"),
+    ('\u{1F3C2}', "snowboarder"),
+    ('\u{1F3C3}', "runner"),
+    ('\u{1F3C4}', "surfer"),
+    ('\u{1F3C5}', "sports medal"),
+    ('\u{1F3C6}', "trophy"),
+    ('\u{1F3C7}', "horse racing"),
+    ('\u{1F3C8}', "american football"),
+    ('\u{1F3C9}', "rugby football"),
+    ('\u{1F3CA}', "swimmer"),
+    ('\u{1F3CB}', "weight lifter"),
+    ('\u{1F3CC}', "golfer"),
+    ('\u{1F3CD}', "racing motorcycle"),
+    ('\u{1F3CE}', "racing car"),
+    ('\u{1F3CF}', "cricket bat and ball"),
+    ('\u{1F3D0}', "volleyball"),
+    ('\u{1F3D1}', "field hockey stick and ball"),
+    ('\u{1F3D2}', "ice hockey stick and puck"),
+    ('\u{1F3D3}', "table tennis paddle and ball"),
+    ('\u{1F3D4}', "snow capped mountain"),
+    ('\u{1F3D5}', "camping"),
+    ('\u{1F3D6}', "beach with umbrella"),
+    ('\u{1F3D7}', "building construction"),
+    ('\u{1F3D8}', "house buildings"),
+    ('\u{1F3D9}', "cityscape"),
+    ('\u{1F3DA}', "derelict house building"),
+    ('\u{1F3DB}', "classical building"),
+    ('\u{1F3DC}', "desert"),
+    ('\u{1F3DD}', "desert island"),
+    ('\u{1F3DE}', "national park"),
+    ('\u{1F3DF}', "stadium"),
+    ('\u{1F3E0}', "house building"),
+    ('\u{1F3E1}', "house with garden"),
+    ('\u{1F3E2}', "office building"),
+    ('\u{1F3E3}', "japanese post office"),
+    ('\u{1F3E4}', "european post office"),
+    ('\u{1F3E5}', "hospital"),
+    ('\u{1F3E6}', "bank"),
+    ('\u{1F3E7}', "automated teller machine"),
+    ('\u{1F3E8}', "hotel"),
+    ('\u{1F3E9}', "love hotel"),
+    ('\u{1F3EA}', "convenience store"),
+    ('\u{1F3EB}', "school"),
+    ('\u{1F3EC}', "department store"),
+    ('\u{1F3ED}', "factory"),
+    ('\u{1F3EE}', "izakaya lantern"),
+    ('\u{1F3EF}', "japanese castle"),
+    ('\u{1F3F0}', "european castle"),
+    ('\u{1F3F1}', "white pennant"),
+    ('\u{1F3F2}', "black pennant"),
+    ('\u{1F3F3}', "waving white flag"),
+    ('\u{1F3F4}', "waving black flag"),
+    ('\u{1F3F5}', "rosette"),
+    ('\u{1F3F6}', "black rosette"),
+    ('\u{1F3F7}', "label"),
+    ('\u{1F3F8}', "badminton racquet and shuttlecock"),
+    ('\u{1F3F9}', "bow and arrow"),
+    ('\u{1F3FA}', "amphora"),
+    ('\u{1F3FB}', "emoji modifier fitzpatrick type-1-2"),
+    ('\u{1F3FC}', "emoji modifier fitzpatrick type-3"),
+    ('\u{1F3FD}', "emoji modifier fitzpatrick type-4"),
+    ('\u{1F3FE}', "emoji modifier fitzpatrick type-5"),
+    ('\u{1F3FF}', "emoji modifier fitzpatrick type-6"),
+    ('\u{1F400}', "rat"),
+    ('\u{1F401}', "mouse"),
+    ('\u{1F402}', "ox"),
+    ('\u{1F403}', "water buffalo"),
+    ('\u{1F404}', "cow"),
+    ('\u{1F405}', "tiger"),
+    ('\u{1F406}', "leopard"),
+    ('\u{1F407}', "rabbit"),
+    ('\u{1F408}', "cat"),
+    ('\u{1F409}', "dragon"),
+    ('\u{1F40A}', "crocodile"),
+    ('\u{1F40B}', "whale"),
+    ('\u{1F40C}', "snail"),
+    ('\u{1F40D}', "snake"),
+    ('\u{1F40E}', "horse"),
+    ('\u{1F40F}', "ram"),
+    ('\u{1F410}', "goat"),
+    ('\u{1F411}', "sheep"),
+    ('\u{1F412}', "monkey"),
+    ('\u{1F413}', "rooster"),
+    ('\u{1F414}', "chicken"),
+    ('\u{1F415}', "dog"),
+    ('\u{1F416}', "pig"),
+    ('\u{1F417}', "boar"),
+    ('\u{1F418}', "elephant"),
+    ('\u{1F419}', "octopus"),
+    ('\u{1F41A}', "spiral shell"),
+    ('\u{1F41B}', "bug"),
+    ('\u{1F41C}', "ant"),
+    ('\u{1F41D}', "honeybee"),
+    ('\u{1F41E}', "lady beetle"),
+    ('\u{1F41F}', "fish"),
+    ('\u{1F420}', "tropical fish"),
+    ('\u{1F421}', "blowfish"),
+    ('\u{1F422}', "turtle"),
+    ('\u{1F423}', "hatching chick"),
+    ('\u{1F424}', "baby chick"),
+    ('\u{1F425}', "front-facing baby chick"),
+    ('\u{1F426}', "bird"),
+    ('\u{1F427}', "penguin"),
+    ('\u{1F428}', "koala"),
+    ('\u{1F429}', "poodle"),
+    ('\u{1F42A}', "dromedary camel"),
+    ('\u{1F42B}', "bactrian camel"),
+    ('\u{1F42C}', "dolphin"),
+    ('\u{1F42D}', "mouse face"),
+    ('\u{1F42E}', "cow face"),
+    ('\u{1F42F}', "tiger face"),
+    ('\u{1F430}', "rabbit face"),
+    ('\u{1F431}', "cat face"),
+    ('\u{1F432}', "dragon face"),
+    ('\u{1F433}', "spouting whale"),
+    ('\u{1F434}', "horse face"),
+    ('\u{1F435}', "monkey face"),
+    ('\u{1F436}', "dog face"),
+    ('\u{1F437}', "pig face"),
+    ('\u{1F438}', "frog face"),
+    ('\u{1F439}', "hamster face"),
+    ('\u{1F43A}', "wolf face"),
+    ('\u{1F43B}', "bear face"),
+    ('\u{1F43C}', "panda face"),
+    ('\u{1F43D}', "pig nose"),
+    ('\u{1F43E}', "paw prints"),
+    ('\u{1F43F}', "chipmunk"),
+    ('\u{1F440}', "eyes"),
+    ('\u{1F441}', "eye"),
+    ('\u{1F442}', "ear"),
+    ('\u{1F443}', "nose"),
+    ('\u{1F444}', "mouth"),
+    ('\u{1F445}', "tongue"),
+    ('\u{1F446}', "white up pointing backhand index"),
+    ('\u{1F447}', "white down pointing backhand index"),
+    ('\u{1F448}', "white left pointing backhand index"),
+    ('\u{1F449}', "white right pointing backhand index"),
+    ('\u{1F44A}', "fisted hand sign"),
+    ('\u{1F44B}', "waving hand sign"),
+    ('\u{1F44C}', "ok hand sign"),
+    ('\u{1F44D}', "thumbs up sign"),
+    ('\u{1F44E}', "thumbs down sign"),
+    ('\u{1F44F}', "clapping hands sign"),
+    ('\u{1F450}', "open hands sign"),
+    ('\u{1F451}', "crown"),
+    ('\u{1F452}', "womans hat"),
+    ('\u{1F453}', "eyeglasses"),
+    ('\u{1F454}', "necktie"),
+    ('\u{1F455}', "t-shirt"),
+    ('\u{1F456}', "jeans"),
+    ('\u{1F457}', "dress"),
+    ('\u{1F458}', "kimono"),
+    ('\u{1F459}', "bikini"),
+    ('\u{1F45A}', "womans clothes"),
+    ('\u{1F45B}', "purse"),
+    ('\u{1F45C}', "handbag"),
+    ('\u{1F45D}', "pouch"),
+    ('\u{1F45E}', "mans shoe"),
+    ('\u{1F45F}', "athletic shoe"),
+    ('\u{1F460}', "high-heeled shoe"),
+    ('\u{1F461}', "womans sandal"),
+    ('\u{1F462}', "womans boots"),
+    ('\u{1F463}', "footprints"),
+    ('\u{1F464}', "bust in silhouette"),
+    ('\u{1F465}', "busts in silhouette"),
+    ('\u{1F466}', "boy"),
+    ('\u{1F467}', "girl"),
+    ('\u{1F468}', "man"),
+    ('\u{1F469}', "woman"),
+    ('\u{1F46A}', "family"),
+    ('\u{1F46B}', "man and woman holding hands"),
+    ('\u{1F46C}', "two men holding hands"),
+    ('\u{1F46D}', "two women holding hands"),
+    ('\u{1F46E}', "police officer"),
+    ('\u{1F46F}', "woman with bunny ears"),
+    ('\u{1F470}', "bride with veil"),
+    ('\u{1F471}', "person with blond hair"),
+    ('\u{1F472}', "man with gua pi mao"),
+    ('\u{1F473}', "man with turban"),
+    ('\u{1F474}', "older man"),
+    ('\u{1F475}', "older woman"),
+    ('\u{1F476}', "baby"),
+    ('\u{1F477}', "construction worker"),
+    ('\u{1F478}', "princess"),
+    ('\u{1F479}', "japanese ogre"),
+    ('\u{1F47A}', "japanese goblin"),
+    ('\u{1F47B}', "ghost"),
+    ('\u{1F47C}', "baby angel"),
+    ('\u{1F47D}', "extraterrestrial alien"),
+    ('\u{1F47E}', "alien monster"),
+    ('\u{1F47F}', "imp"),
+    ('\u{1F480}', "skull"),
+    ('\u{1F481}', "information desk person"),
+    ('\u{1F482}', "guardsman"),
+    ('\u{1F483}', "dancer"),
+    ('\u{1F484}', "lipstick"),
+    ('\u{1F485}', "nail polish"),
+    ('\u{1F486}', "face massage"),
+    ('\u{1F487}', "haircut"),
+    ('\u{1F488}', "barber pole"),
+    ('\u{1F489}', "syringe"),
+    ('\u{1F48A}', "pill"),
+    ('\u{1F48B}', "kiss mark"),
+    ('\u{1F48C}', "love letter"),
+    ('\u{1F48D}', "ring"),
+    ('\u{1F48E}', "gem stone"),
+    ('\u{1F48F}', "kiss"),
+    ('\u{1F490}', "bouquet"),
+    ('\u{1F491}', "couple with heart"),
+    ('\u{1F492}', "wedding"),
+    ('\u{1F493}', "beating heart"),
+    ('\u{1F494}', "broken heart"),
+    ('\u{1F495}', "two hearts"),
+    ('\u{1F496}', "sparkling heart"),
+    ('\u{1F497}', "growing heart"),
+    ('\u{1F498}', "heart with arrow"),
+    ('\u{1F499}', "blue heart"),
+    ('\u{1F49A}', "green heart"),
+    ('\u{1F49B}', "yellow heart"),
+    ('\u{1F49C}', "purple heart"),
+    ('\u{1F49D}', "heart with ribbon"),
+    ('\u{1F49E}', "revolving hearts"),
+    ('\u{1F49F}', "heart decoration"),
+    ('\u{1F4A0}', "diamond shape with a dot inside"),
+    ('\u{1F4A1}', "electric light bulb"),
+    ('\u{1F4A2}', "anger symbol"),
+    ('\u{1F4A3}', "bomb"),
+    ('\u{1F4A4}', "sleeping symbol"),
+    ('\u{1F4A5}', "collision symbol"),
+    ('\u{1F4A6}', "splashing sweat symbol"),
+    ('\u{1F4A7}', "droplet"),
+    ('\u{1F4A8}', "dash symbol"),
+    ('\u{1F4A9}', "pile of poo"),
+    ('\u{1F4AA}', "flexed biceps"),
+    ('\u{1F4AB}', "dizzy symbol"),
+    ('\u{1F4AC}', "speech balloon"),
+    ('\u{1F4AD}', "thought balloon"),
+    ('\u{1F4AE}', "white flower"),
+    ('\u{1F4AF}', "hundred points symbol"),
+    ('\u{1F4B0}', "money bag"),
+    ('\u{1F4B1}', "currency exchange"),
+    ('\u{1F4B2}', "heavy dollar sign"),
+    ('\u{1F4B3}', "credit card"),
+    ('\u{1F4B4}', "banknote with yen sign"),
+    ('\u{1F4B5}', "banknote with dollar sign"),
+    ('\u{1F4B6}', "banknote with euro sign"),
+    ('\u{1F4B7}', "banknote with pound sign"),
+    ('\u{1F4B8}', "money with wings"),
+    ('\u{1F4B9}', "chart with upwards trend and yen sign"),
+    ('\u{1F4BA}', "seat"),
+    ('\u{1F4BB}', "personal computer"),
+    ('\u{1F4BC}', "briefcase"),
+    ('\u{1F4BD}', "minidisc"),
+    ('\u{1F4BE}', "floppy disk"),
+    ('\u{1F4BF}', "optical disc"),
+    ('\u{1F4C0}', "dvd"),
+    ('\u{1F4C1}', "file folder"),
+    ('\u{1F4C2}', "open file folder"),
+    ('\u{1F4C3}', "page with curl"),
+    ('\u{1F4C4}', "page facing up"),
+    ('\u{1F4C5}', "calendar"),
+    ('\u{1F4C6}', "tear-off calendar"),
+    ('\u{1F4C7}', "card index"),
+    ('\u{1F4C8}', "chart with upwards trend"),
+    ('\u{1F4C9}', "chart with downwards trend"),
+    ('\u{1F4CA}', "bar chart"),
+    ('\u{1F4CB}', "clipboard"),
+    ('\u{1F4CC}', "pushpin"),
+    ('\u{1F4CD}', "round pushpin"),
+    ('\u{1F4CE}', "paperclip"),
+    ('\u{1F4CF}', "straight ruler"),
+    ('\u{1F4D0}', "triangular ruler"),
+    ('\u{1F4D1}', "bookmark tabs"),
+    ('\u{1F4D2}', "ledger"),
+    ('\u{1F4D3}', "notebook"),
+    ('\u{1F4D4}', "notebook with decorative cover"),
+    ('\u{1F4D5}', "closed book"),
+    ('\u{1F4D6}', "open book"),
+    ('\u{1F4D7}', "green book"),
+    ('\u{1F4D8}', "blue book"),
+    ('\u{1F4D9}', "orange book"),
+    ('\u{1F4DA}', "books"),
+    ('\u{1F4DB}', "name badge"),
+    ('\u{1F4DC}', "scroll"),
+    ('\u{1F4DD}', "memo"),
+    ('\u{1F4DE}', "telephone receiver"),
+    ('\u{1F4DF}', "pager"),
+    ('\u{1F4E0}', "fax machine"),
+    ('\u{1F4E1}', "satellite antenna"),
+    ('\u{1F4E2}', "public address loudspeaker"),
+    ('\u{1F4E3}', "cheering megaphone"),
+    ('\u{1F4E4}', "outbox tray"),
+    ('\u{1F4E5}', "inbox tray"),
+    ('\u{1F4E6}', "package"),
+    ('\u{1F4E7}', "e-mail symbol"),
+    ('\u{1F4E8}', "incoming envelope"),
+    ('\u{1F4E9}', "envelope with downwards arrow above"),
+    ('\u{1F4EA}', "closed mailbox with lowered flag"),
+    ('\u{1F4EB}', "closed mailbox with raised flag"),
+    ('\u{1F4EC}', "open mailbox with raised flag"),
+    ('\u{1F4ED}', "open mailbox with lowered flag"),
+    ('\u{1F4EE}', "postbox"),
+    ('\u{1F4EF}', "postal horn"),
+    ('\u{1F4F0}', "newspaper"),
+    ('\u{1F4F1}', "mobile phone"),
+    ('\u{1F4F2}', "mobile phone with rightwards arrow at left"),
+    ('\u{1F4F3}', "vibration mode"),
+    ('\u{1F4F4}', "mobile phone off"),
+    ('\u{1F4F5}', "no mobile phones"),
+    ('\u{1F4F6}', "antenna with bars"),
+    ('\u{1F4F7}', "camera"),
+    ('\u{1F4F8}', "camera with flash"),
+    ('\u{1F4F9}', "video camera"),
+    ('\u{1F4FA}', "television"),
+    ('\u{1F4FB}', "radio"),
+    ('\u{1F4FC}', "videocassette"),
+    ('\u{1F4FD}', "film projector"),
+    ('\u{1F4FE}', "portable stereo"),
+    ('\u{1F4FF}', "prayer beads"),
+    ('\u{1F500}', "twisted rightwards arrows"),
+    ('\u{1F501}', "clockwise rightwards and leftwards open circle arrows"),
+    ('\u{1F502}', "clockwise rightwards and leftwards open circle arrows with circled one overlay"),
+    ('\u{1F503}', "clockwise downwards and upwards open circle arrows"),
+    ('\u{1F504}', "anticlockwise downwards and upwards open circle arrows"),
+    ('\u{1F505}', "low brightness symbol"),
+    ('\u{1F506}', "high brightness symbol"),
+    ('\u{1F507}', "speaker with cancellation stroke"),
+    ('\u{1F508}', "speaker"),
+    ('\u{1F509}', "speaker with one sound wave"),
+    ('\u{1F50A}', "speaker with three sound waves"),
+    ('\u{1F50B}', "battery"),
+    ('\u{1F50C}', "electric plug"),
+    ('\u{1F50D}', "left-pointing magnifying glass"),
+    ('\u{1F50E}', "right-pointing magnifying glass"),
+    ('\u{1F50F}', "lock with ink pen"),
+    ('\u{1F510}', "closed lock with key"),
+    ('\u{1F511}', "key"),
+    ('\u{1F512}', "lock"),
+    ('\u{1F513}', "open lock"),
+    ('\u{1F514}', "bell"),
+    ('\u{1F515}', "bell with cancellation stroke"),
+    ('\u{1F516}', "bookmark"),
+    ('\u{1F517}', "link symbol"),
+    ('\u{1F518}', "radio button"),
+    ('\u{1F519}', "back with leftwards arrow above"),
+    ('\u{1F51A}', "end with leftwards arrow above"),
+    ('\u{1F51B}', "on with exclamation mark with left right arrow above"),
+    ('\u{1F51C}', "soon with rightwards arrow above"),
+    ('\u{1F51D}', "top with upwards arrow above"),
+    ('\u{1F51E}', "no one under eighteen symbol"),
+    ('\u{1F51F}', "keycap ten"),
+    ('\u{1F520}', "input symbol for latin capital letters"),
+    ('\u{1F521}', "input symbol for latin small letters"),
+    ('\u{1F522}', "input symbol for numbers"),
+    ('\u{1F523}', "input symbol for symbols"),
+    ('\u{1F524}', "input symbol for latin letters"),
+    ('\u{1F525}', "fire"),
+    ('\u{1F526}', "electric torch"),
+    ('\u{1F527}', "wrench"),
+    ('\u{1F528}', "hammer"),
+    ('\u{1F529}', "nut and bolt"),
+    ('\u{1F52A}', "hocho"),
+    ('\u{1F52B}', "pistol"),
+    ('\u{1F52C}', "microscope"),
+    ('\u{1F52D}', "telescope"),
+    ('\u{1F52E}', "crystal ball"),
+    ('\u{1F52F}', "six pointed star with middle dot"),
+    ('\u{1F530}', "japanese symbol for beginner"),
+    ('\u{1F531}', "trident emblem"),
+    ('\u{1F532}', "black square button"),
+    ('\u{1F533}', "white square button"),
+    ('\u{1F534}', "large red circle"),
+    ('\u{1F535}', "large blue circle"),
+    ('\u{1F536}', "large orange diamond"),
+    ('\u{1F537}', "large blue diamond"),
+    ('\u{1F538}', "small orange diamond"),
+    ('\u{1F539}', "small blue diamond"),
+    ('\u{1F53A}', "up-pointing red triangle"),
+    ('\u{1F53B}', "down-pointing red triangle"),
+    ('\u{1F53C}', "up-pointing small red triangle"),
+    ('\u{1F53D}', "down-pointing small red triangle"),
+    ('\u{1F53E}', "lower right shadowed white circle"),
+    ('\u{1F53F}', "upper right shadowed white circle"),
+    ('\u{1F540}', "circled cross pommee"),
+    ('\u{1F541}', "cross pommee with half-circle below"),
+    ('\u{1F542}', "cross pommee"),
+    ('\u{1F543}', "notched left semicircle with three dots"),
+    ('\u{1F544}', "notched right semicircle with three dots"),
+    ('\u{1F545}', "symbol for marks chapter"),
+    ('\u{1F546}', "white latin cross"),
+    ('\u{1F547}', "heavy latin cross"),
+    ('\u{1F548}', "celtic cross"),
+    ('\u{1F549}', "om symbol"),
+    ('\u{1F54A}', "dove of peace"),
+    ('\u{1F54B}', "kaaba"),
+    ('\u{1F54C}', "mosque"),
+    ('\u{1F54D}', "synagogue"),
+    ('\u{1F54E}', "menorah with nine branches"),
+    ('\u{1F54F}', "bowl of hygieia"),
+    ('\u{1F550}', "clock face one oclock"),
+    ('\u{1F551}', "clock face two oclock"),
+    ('\u{1F552}', "clock face three oclock"),
+    ('\u{1F553}', "clock face four oclock"),
+    ('\u{1F554}', "clock face five oclock"),
+    ('\u{1F555}', "clock face six oclock"),
+    ('\u{1F556}', "clock face seven oclock"),
+    ('\u{1F557}', "clock face eight oclock"),
+    ('\u{1F558}', "clock face nine oclock"),
+    ('\u{1F559}', "clock face ten oclock"),
+    ('\u{1F55A}', "clock face eleven oclock"),
+    ('\u{1F55B}', "clock face twelve oclock"),
+    ('\u{1F55C}', "clock face one-thirty"),
+    ('\u{1F55D}', "clock face two-thirty"),
+    ('\u{1F55E}', "clock face three-thirty"),
+    ('\u{1F55F}', "clock face four-thirty"),
+    ('\u{1F560}', "clock face five-thirty"),
+    ('\u{1F561}', "clock face six-thirty"),
+    ('\u{1F562}', "clock face seven-thirty"),
+    ('\u{1F563}', "clock face eight-thirty"),
+    ('\u{1F564}', "clock face nine-thirty"),
+    ('\u{1F565}', "clock face ten-thirty"),
+    ('\u{1F566}', "clock face eleven-thirty"),
+    ('\u{1F567}', "clock face twelve-thirty"),
+    ('\u{1F568}', "right speaker"),
+    ('\u{1F569}', "right speaker with one sound wave"),
+    ('\u{1F56A}', "right speaker with three sound waves"),
+    ('\u{1F56B}', "bullhorn"),
+    ('\u{1F56C}', "bullhorn with sound waves"),
+    ('\u{1F56D}', "ringing bell"),
+    ('\u{1F56E}', "book"),
+    ('\u{1F56F}', "candle"),
+    ('\u{1F570}', "mantelpiece clock"),
+    ('\u{1F571}', "black skull and crossbones"),
+    ('\u{1F572}', "no piracy"),
+    ('\u{1F573}', "hole"),
+    ('\u{1F574}', "man in business suit levitating"),
+    ('\u{1F575}', "sleuth or spy"),
+    ('\u{1F576}', "dark sunglasses"),
+    ('\u{1F577}', "spider"),
+    ('\u{1F578}', "spider web"),
+    ('\u{1F579}', "joystick"),
+    ('\u{1F57A}', "man dancing"),
+    ('\u{1F57B}', "left hand telephone receiver"),
+    ('\u{1F57C}', "telephone receiver with page"),
+    ('\u{1F57D}', "right hand telephone receiver"),
+    ('\u{1F57E}', "white touchtone telephone"),
+    ('\u{1F57F}', "black touchtone telephone"),
+    ('\u{1F580}', "telephone on top of modem"),
+    ('\u{1F581}', "clamshell mobile phone"),
+    ('\u{1F582}', "back of envelope"),
+    ('\u{1F583}', "stamped envelope"),
+    ('\u{1F584}', "envelope with lightning"),
+    ('\u{1F585}', "flying envelope"),
+    ('\u{1F586}', "pen over stamped envelope"),
+    ('\u{1F587}', "linked paperclips"),
+    ('\u{1F588}', "black pushpin"),
+    ('\u{1F589}', "lower left pencil"),
+    ('\u{1F58A}', "lower left ballpoint pen"),
+    ('\u{1F58B}', "lower left fountain pen"),
+    ('\u{1F58C}', "lower left paintbrush"),
+    ('\u{1F58D}', "lower left crayon"),
+    ('\u{1F58E}', "left writing hand"),
+    ('\u{1F58F}', "turned ok hand sign"),
+    ('\u{1F590}', "raised hand with fingers splayed"),
+    ('\u{1F591}', "reversed raised hand with fingers splayed"),
+    ('\u{1F592}', "reversed thumbs up sign"),
+    ('\u{1F593}', "reversed thumbs down sign"),
+    ('\u{1F594}', "reversed victory hand"),
+    ('\u{1F595}', "reversed hand with middle finger extended"),
+    ('\u{1F596}', "raised hand with part between middle and ring fingers"),
+    ('\u{1F597}', "white down pointing left hand index"),
+    ('\u{1F598}', "sideways white left pointing index"),
+    ('\u{1F599}', "sideways white right pointing index"),
+    ('\u{1F59A}', "sideways black left pointing index"),
+    ('\u{1F59B}', "sideways black right pointing index"),
+    ('\u{1F59C}', "black left pointing backhand index"),
+    ('\u{1F59D}', "black right pointing backhand index"),
+    ('\u{1F59E}', "sideways white up pointing index"),
+    ('\u{1F59F}', "sideways white down pointing index"),
+    ('\u{1F5A0}', "sideways black up pointing index"),
+    ('\u{1F5A1}', "sideways black down pointing index"),
+    ('\u{1F5A2}', "black up pointing backhand index"),
+    ('\u{1F5A3}', "black down pointing backhand index"),
+    ('\u{1F5A4}', "black heart"),
+    ('\u{1F5A5}', "desktop computer"),
+    ('\u{1F5A6}', "keyboard and mouse"),
+    ('\u{1F5A7}', "three networked computers"),
+    ('\u{1F5A8}', "printer"),
+    ('\u{1F5A9}', "pocket calculator"),
+    ('\u{1F5AA}', "black hard shell floppy disk"),
+    ('\u{1F5AB}', "white hard shell floppy disk"),
+    ('\u{1F5AC}', "soft shell floppy disk"),
+    ('\u{1F5AD}', "tape cartridge"),
+    ('\u{1F5AE}', "wired keyboard"),
+    ('\u{1F5AF}', "one button mouse"),
+    ('\u{1F5B0}', "two button mouse"),
+    ('\u{1F5B1}', "three button mouse"),
+    ('\u{1F5B2}', "trackball"),
+    ('\u{1F5B3}', "old personal computer"),
+    ('\u{1F5B4}', "hard disk"),
+    ('\u{1F5B5}', "screen"),
+    ('\u{1F5B6}', "printer icon"),
+    ('\u{1F5B7}', "fax icon"),
+    ('\u{1F5B8}', "optical disc icon"),
+    ('\u{1F5B9}', "document with text"),
+    ('\u{1F5BA}', "document with text and picture"),
+    ('\u{1F5BB}', "document with picture"),
+    ('\u{1F5BC}', "frame with picture"),
+    ('\u{1F5BD}', "frame with tiles"),
+    ('\u{1F5BE}', "frame with an x"),
+    ('\u{1F5BF}', "black folder"),
+    ('\u{1F5C0}', "folder"),
+    ('\u{1F5C1}', "open folder"),
+    ('\u{1F5C2}', "card index dividers"),
+    ('\u{1F5C3}', "card file box"),
+    ('\u{1F5C4}', "file cabinet"),
+    ('\u{1F5C5}', "empty note"),
+    ('\u{1F5C6}', "empty note page"),
+    ('\u{1F5C7}', "empty note pad"),
+    ('\u{1F5C8}', "note"),
+    ('\u{1F5C9}', "note page"),
+    ('\u{1F5CA}', "note pad"),
+    ('\u{1F5CB}', "empty document"),
+    ('\u{1F5CC}', "empty page"),
+    ('\u{1F5CD}', "empty pages"),
+    ('\u{1F5CE}', "document"),
+    ('\u{1F5CF}', "page"),
+    ('\u{1F5D0}', "pages"),
+    ('\u{1F5D1}', "wastebasket"),
+    ('\u{1F5D2}', "spiral note pad"),
+    ('\u{1F5D3}', "spiral calendar pad"),
+    ('\u{1F5D4}', "desktop window"),
+    ('\u{1F5D5}', "minimize"),
+    ('\u{1F5D6}', "maximize"),
+    ('\u{1F5D7}', "overlap"),
+    ('\u{1F5D8}', "clockwise right and left semicircle arrows"),
+    ('\u{1F5D9}', "cancellation x"),
+    ('\u{1F5DA}', "increase font size symbol"),
+    ('\u{1F5DB}', "decrease font size symbol"),
+    ('\u{1F5DC}', "compression"),
+    ('\u{1F5DD}', "old key"),
+    ('\u{1F5DE}', "rolled-up newspaper"),
+    ('\u{1F5DF}', "page with circled text"),
+    ('\u{1F5E0}', "stock chart"),
+    ('\u{1F5E1}', "dagger knife"),
+    ('\u{1F5E2}', "lips"),
+    ('\u{1F5E3}', "speaking head in silhouette"),
+    ('\u{1F5E4}', "three rays above"),
+    ('\u{1F5E5}', "three rays below"),
+    ('\u{1F5E6}', "three rays left"),
+    ('\u{1F5E7}', "three rays right"),
+    ('\u{1F5E8}', "left speech bubble"),
+    ('\u{1F5E9}', "right speech bubble"),
+    ('\u{1F5EA}', "two speech bubbles"),
+    ('\u{1F5EB}', "three speech bubbles"),
+    ('\u{1F5EC}', "left thought bubble"),
+    ('\u{1F5ED}', "right thought bubble"),
+    ('\u{1F5EE}', "left anger bubble"),
+    ('\u{1F5EF}', "right anger bubble"),
+    ('\u{1F5F0}', "mood bubble"),
+    ('\u{1F5F1}', "lightning mood bubble"),
+    ('\u{1F5F2}', "lightning mood"),
+    ('\u{1F5F3}', "ballot box with ballot"),
+    ('\u{1F5F4}', "ballot script x"),
+    ('\u{1F5F5}', "ballot box with script x"),
+    ('\u{1F5F6}', "ballot bold script x"),
+    ('\u{1F5F7}', "ballot box with bold script x"),
+    ('\u{1F5F8}', "light check mark"),
+    ('\u{1F5F9}', "ballot box with bold check"),
+    ('\u{1F5FA}', "world map"),
+    ('\u{1F5FB}', "mount fuji"),
+    ('\u{1F5FC}', "tokyo tower"),
+    ('\u{1F5FD}', "statue of liberty"),
+    ('\u{1F5FE}', "silhouette of japan"),
+    ('\u{1F5FF}', "moyai"),
+    ('\u{1F900}', "circled cross formee with four dots"),
+    ('\u{1F901}', "circled cross formee with two dots"),
+    ('\u{1F902}', "circled cross formee"),
+    ('\u{1F903}', "left half circle with four dots"),
+    ('\u{1F904}', "left half circle with three dots"),
+    ('\u{1F905}', "left half circle with two dots"),
+    ('\u{1F906}', "left half circle with dot"),
+    ('\u{1F907}', "left half circle"),
+    ('\u{1F908}', "downward facing hook"),
+    ('\u{1F909}', "downward facing notched hook"),
+    ('\u{1F90A}', "downward facing hook with dot"),
+    ('\u{1F90B}', "downward facing notched hook with dot"),
+    ('\u{1F90C}', "pinched fingers"),
+    ('\u{1F90D}', "white heart"),
+    ('\u{1F90E}', "brown heart"),
+    ('\u{1F90F}', "pinching hand"),
+    ('\u{1F910}', "zipper-mouth face"),
+    ('\u{1F911}', "money-mouth face"),
+    ('\u{1F912}', "face with thermometer"),
+    ('\u{1F913}', "nerd face"),
+    ('\u{1F914}', "thinking face"),
+    ('\u{1F915}', "face with head-bandage"),
+    ('\u{1F916}', "robot face"),
+    ('\u{1F917}', "hugging face"),
+    ('\u{1F918}', "sign of the horns"),
+    ('\u{1F919}', "call me hand"),
+    ('\u{1F91A}', "raised back of hand"),
+    ('\u{1F91B}', "left-facing fist"),
+    ('\u{1F91C}', "right-facing fist"),
+    ('\u{1F91D}', "handshake"),
+    ('\u{1F91E}', "hand with index and middle fingers crossed"),
+    ('\u{1F91F}', "i love you hand sign"),
+    ('\u{1F920}', "face with cowboy hat"),
+    ('\u{1F921}', "clown face"),
+    ('\u{1F922}', "nauseated face"),
+    ('\u{1F923}', "rolling on the floor laughing"),
+    ('\u{1F924}', "drooling face"),
+    ('\u{1F925}', "lying face"),
+    ('\u{1F926}', "face palm"),
+    ('\u{1F927}', "sneezing face"),
+    ('\u{1F928}', "face with one eyebrow raised"),
+    ('\u{1F929}', "grinning face with star eyes"),
+    ('\u{1F92A}', "grinning face with one large and one small eye"),
+    ('\u{1F92B}', "face with finger covering closed lips"),
+    ('\u{1F92C}', "serious face with symbols covering mouth"),
+    ('\u{1F92D}', "smiling face with smiling eyes and hand covering mouth"),
+    ('\u{1F92E}', "face with open mouth vomiting"),
+    ('\u{1F92F}', "shocked face with exploding head"),
+    ('\u{1F930}', "pregnant woman"),
+    ('\u{1F931}', "breast-feeding"),
+    ('\u{1F932}', "palms up together"),
+    ('\u{1F933}', "selfie"),
+    ('\u{1F934}', "prince"),
+    ('\u{1F935}', "man in tuxedo"),
+    ('\u{1F936}', "mother christmas"),
+    ('\u{1F937}', "shrug"),
+    ('\u{1F938}', "person doing cartwheel"),
+    ('\u{1F939}', "juggling"),
+    ('\u{1F93A}', "fencer"),
+    ('\u{1F93B}', "modern pentathlon"),
+    ('\u{1F93C}', "wrestlers"),
+    ('\u{1F93D}', "water polo"),
+    ('\u{1F93E}', "handball"),
+    ('\u{1F93F}', "diving mask"),
+    ('\u{1F940}', "wilted flower"),
+    ('\u{1F941}', "drum with drumsticks"),
+    ('\u{1F942}', "clinking glasses"),
+    ('\u{1F943}', "tumbler glass"),
+    ('\u{1F944}', "spoon"),
+    ('\u{1F945}', "goal net"),
+    ('\u{1F946}', "rifle"),
+    ('\u{1F947}', "first place medal"),
+    ('\u{1F948}', "second place medal"),
+    ('\u{1F949}', "third place medal"),
+    ('\u{1F94A}', "boxing glove"),
+    ('\u{1F94B}', "martial arts uniform"),
+    ('\u{1F94C}', "curling stone"),
+    ('\u{1F94D}', "lacrosse stick and ball"),
+    ('\u{1F94E}', "softball"),
+    ('\u{1F94F}', "flying disc"),
+    ('\u{1F950}', "croissant"),
+    ('\u{1F951}', "avocado"),
+    ('\u{1F952}', "cucumber"),
+    ('\u{1F953}', "bacon"),
+    ('\u{1F954}', "potato"),
+    ('\u{1F955}', "carrot"),
+    ('\u{1F956}', "baguette bread"),
+    ('\u{1F957}', "green salad"),
+    ('\u{1F958}', "shallow pan of food"),
+    ('\u{1F959}', "stuffed flatbread"),
+    ('\u{1F95A}', "egg"),
+    ('\u{1F95B}', "glass of milk"),
+    ('\u{1F95C}', "peanuts"),
+    ('\u{1F95D}', "kiwifruit"),
+    ('\u{1F95E}', "pancakes"),
+    ('\u{1F95F}', "dumpling"),
+    ('\u{1F960}', "fortune cookie"),
+    ('\u{1F961}', "takeout box"),
+    ('\u{1F962}', "chopsticks"),
+    ('\u{1F963}', "bowl with spoon"),
+    ('\u{1F964}', "cup with straw"),
+    ('\u{1F965}', "coconut"),
+    ('\u{1F966}', "broccoli"),
+    ('\u{1F967}', "pie"),
+    ('\u{1F968}', "pretzel"),
+    ('\u{1F969}', "cut of meat"),
+    ('\u{1F96A}', "sandwich"),
+    ('\u{1F96B}', "canned food"),
+    ('\u{1F96C}', "leafy green"),
+    ('\u{1F96D}', "mango"),
+    ('\u{1F96E}', "moon cake"),
+    ('\u{1F96F}', "bagel"),
+    ('\u{1F970}', "smiling face with smiling eyes and three hearts"),
+    ('\u{1F971}', "yawning face"),
+    ('\u{1F972}', "smiling face with tear"),
+    ('\u{1F973}', "face with party horn and party hat"),
+    ('\u{1F974}', "face with uneven eyes and wavy mouth"),
+    ('\u{1F975}', "overheated face"),
+    ('\u{1F976}', "freezing face"),
+    ('\u{1F977}', "ninja"),
+    ('\u{1F978}', "disguised face"),
+    ('\u{1F979}', "face holding back tears"),
+    ('\u{1F97A}', "face with pleading eyes"),
+    ('\u{1F97B}', "sari"),
+    ('\u{1F97C}', "lab coat"),
+    ('\u{1F97D}', "goggles"),
+    ('\u{1F97E}', "hiking boot"),
+    ('\u{1F97F}', "flat shoe"),
+    ('\u{1F980}', "crab"),
+    ('\u{1F981}', "lion face"),
+    ('\u{1F982}', "scorpion"),
+    ('\u{1F983}', "turkey"),
+    ('\u{1F984}', "unicorn face"),
+    ('\u{1F985}', "eagle"),
+    ('\u{1F986}', "duck"),
+    ('\u{1F987}', "bat"),
+    ('\u{1F988}', "shark"),
+    ('\u{1F989}', "owl"),
+    ('\u{1F98A}', "fox face"),
+    ('\u{1F98B}', "butterfly"),
+    ('\u{1F98C}', "deer"),
+    ('\u{1F98D}', "gorilla"),
+    ('\u{1F98E}', "lizard"),
+    ('\u{1F98F}', "rhinoceros"),
+    ('\u{1F990}', "shrimp"),
+    ('\u{1F991}', "squid"),
+    ('\u{1F992}', "giraffe face"),
+    ('\u{1F993}', "zebra face"),
+    ('\u{1F994}', "hedgehog"),
+    ('\u{1F995}', "sauropod"),
+    ('\u{1F996}', "t-rex"),
+    ('\u{1F997}', "cricket"),
+    ('\u{1F998}', "kangaroo"),
+    ('\u{1F999}', "llama"),
+    ('\u{1F99A}', "peacock"),
+    ('\u{1F99B}', "hippopotamus"),
+    ('\u{1F99C}', "parrot"),
+    ('\u{1F99D}', "raccoon"),
+    ('\u{1F99E}', "lobster"),
+    ('\u{1F99F}', "mosquito"),
+    ('\u{1F9A0}', "microbe"),
+    ('\u{1F9A1}', "badger"),
+    ('\u{1F9A2}', "swan"),
+    ('\u{1F9A3}', "mammoth"),
+    ('\u{1F9A4}', "dodo"),
+    ('\u{1F9A5}', "sloth"),
+    ('\u{1F9A6}', "otter"),
+    ('\u{1F9A7}', "orangutan"),
+    ('\u{1F9A8}', "skunk"),
+    ('\u{1F9A9}', "flamingo"),
+    ('\u{1F9AA}', "oyster"),
+    ('\u{1F9AB}', "beaver"),
+    ('\u{1F9AC}', "bison"),
+    ('\u{1F9AD}', "seal"),
+    ('\u{1F9AE}', "guide dog"),
+    ('\u{1F9AF}', "probing cane"),
+    ('\u{1F9B0}', "emoji component red hair"),
+    ('\u{1F9B1}', "emoji component curly hair"),
+    ('\u{1F9B2}', "emoji component bald"),
+    ('\u{1F9B3}', "emoji component white hair"),
+    ('\u{1F9B4}', "bone"),
+    ('\u{1F9B5}', "leg"),
+    ('\u{1F9B6}', "foot"),
+    ('\u{1F9B7}', "tooth"),
+    ('\u{1F9B8}', "superhero"),
+    ('\u{1F9B9}', "supervillain"),
+    ('\u{1F9BA}', "safety vest"),
+    ('\u{1F9BB}', "ear with hearing aid"),
+    ('\u{1F9BC}', "motorized wheelchair"),
+    ('\u{1F9BD}', "manual wheelchair"),
+    ('\u{1F9BE}', "mechanical arm"),
+    ('\u{1F9BF}', "mechanical leg"),
+    ('\u{1F9C0}', "cheese wedge"),
+    ('\u{1F9C1}', "cupcake"),
+    ('\u{1F9C2}', "salt shaker"),
+    ('\u{1F9C3}', "beverage box"),
+    ('\u{1F9C4}', "garlic"),
+    ('\u{1F9C5}', "onion"),
+    ('\u{1F9C6}', "falafel"),
+    ('\u{1F9C7}', "waffle"),
+    ('\u{1F9C8}', "butter"),
+    ('\u{1F9C9}', "mate drink"),
+    ('\u{1F9CA}', "ice cube"),
+    ('\u{1F9CB}', "bubble tea"),
+    ('\u{1F9CC}', "troll"),
+    ('\u{1F9CD}', "standing person"),
+    ('\u{1F9CE}', "kneeling person"),
+    ('\u{1F9CF}', "deaf person"),
+    ('\u{1F9D0}', "face with monocle"),
+    ('\u{1F9D1}', "adult"),
+    ('\u{1F9D2}', "child"),
+    ('\u{1F9D3}', "older adult"),
+    ('\u{1F9D4}', "bearded person"),
+    ('\u{1F9D5}', "person with headscarf"),
+    ('\u{1F9D6}', "person in steamy room"),
+    ('\u{1F9D7}', "person climbing"),
+    ('\u{1F9D8}', "person in lotus position"),
+    ('\u{1F9D9}', "mage"),
+    ('\u{1F9DA}', "fairy"),
+    ('\u{1F9DB}', "vampire"),
+    ('\u{1F9DC}', "merperson"),
+    ('\u{1F9DD}', "elf"),
+    ('\u{1F9DE}', "genie"),
+    ('\u{1F9DF}', "zombie"),
+    ('\u{1F9E0}', "brain"),
+    ('\u{1F9E1}', "orange heart"),
+    ('\u{1F9E2}', "billed cap"),
+    ('\u{1F9E3}', "scarf"),
+    ('\u{1F9E4}', "gloves"),
+    ('\u{1F9E5}', "coat"),
+    ('\u{1F9E6}', "socks"),
+    ('\u{1F9E7}', "red gift envelope"),
+    ('\u{1F9E8}', "firecracker"),
+    ('\u{1F9E9}', "jigsaw puzzle piece"),
+    ('\u{1F9EA}', "test tube"),
+    ('\u{1F9EB}', "petri dish"),
+    ('\u{1F9EC}', "dna double helix"),
+    ('\u{1F9ED}', "compass"),
+    ('\u{1F9EE}', "abacus"),
+    ('\u{1F9EF}', "fire extinguisher"),
+    ('\u{1F9F0}', "toolbox"),
+    ('\u{1F9F1}', "brick"),
+    ('\u{1F9F2}', "magnet"),
+    ('\u{1F9F3}', "luggage"),
+    ('\u{1F9F4}', "lotion bottle"),
+    ('\u{1F9F5}', "spool of thread"),
+    ('\u{1F9F6}', "ball of yarn"),
+    ('\u{1F9F7}', "safety pin"),
+    ('\u{1F9F8}', "teddy bear"),
+    ('\u{1F9F9}', "broom"),
+    ('\u{1F9FA}', "basket"),
+    ('\u{1F9FB}', "roll of paper"),
+    ('\u{1F9FC}', "bar of soap"),
+    ('\u{1F9FD}', "sponge"),
+    ('\u{1F9FE}', "receipt"),
+    ('\u{1F9FF}', "nazar amulet"),
+    ('\u{1FA70}', "ballet shoes"),
+    ('\u{1FA71}', "one-piece swimsuit"),
+    ('\u{1FA72}', "briefs"),
+    ('\u{1FA73}', "shorts"),
+    ('\u{1FA74}', "thong sandal"),
+    ('\u{1FA78}', "drop of blood"),
+    ('\u{1FA79}', "adhesive bandage"),
+    ('\u{1FA7A}', "stethoscope"),
+    ('\u{1FA7B}', "x-ray"),
+    ('\u{1FA7C}', "crutch"),
+    ('\u{1FA80}', "yo-yo"),
+    ('\u{1FA81}', "kite"),
+    ('\u{1FA82}', "parachute"),
+    ('\u{1FA83}', "boomerang"),
+    ('\u{1FA84}', "magic wand"),
+    ('\u{1FA85}', "pinata"),
+    ('\u{1FA86}', "nesting dolls"),
+    ('\u{1FA90}', "ringed planet"),
+    ('\u{1FA91}', "chair"),
+    ('\u{1FA92}', "razor"),
+    ('\u{1FA93}', "axe"),
+    ('\u{1FA94}', "diya lamp"),
+    ('\u{1FA95}', "banjo"),
+    ('\u{1FA96}', "military helmet"),
+    ('\u{1FA97}', "accordion"),
+    ('\u{1FA98}', "long drum"),
+    ('\u{1FA99}', "coin"),
+    ('\u{1FA9A}', "carpentry saw"),
+    ('\u{1FA9B}', "screwdriver"),
+    ('\u{1FA9C}', "ladder"),
+    ('\u{1FA9D}', "hook"),
+    ('\u{1FA9E}', "mirror"),
+    ('\u{1FA9F}', "window"),
+    ('\u{1FAA0}', "plunger"),
+    ('\u{1FAA1}', "sewing needle"),
+    ('\u{1FAA2}', "knot"),
+    ('\u{1FAA3}', "bucket"),
+    ('\u{1FAA4}', "mouse trap"),
+    ('\u{1FAA5}', "toothbrush"),
+    ('\u{1FAA6}', "headstone"),
+    ('\u{1FAA7}', "placard"),
+    ('\u{1FAA8}', "rock"),
+    ('\u{1FAA9}', "mirror ball"),
+    ('\u{1FAAA}', "identification card"),
+    ('\u{1FAAB}', "low battery"),
+    ('\u{1FAAC}', "hamsa"),
+    ('\u{1FAB0}', "fly"),
+    ('\u{1FAB1}', "worm"),
+    ('\u{1FAB2}', "beetle"),
+    ('\u{1FAB3}', "cockroach"),
+    ('\u{1FAB4}', "potted plant"),
+    ('\u{1FAB5}', "wood"),
+    ('\u{1FAB6}', "feather"),
+    ('\u{1FAB7}', "lotus"),
+    ('\u{1FAB8}', "coral"),
+    ('\u{1FAB9}', "empty nest"),
+    ('\u{1FABA}', "nest with eggs"),
+    ('\u{1FAC0}', "anatomical heart"),
+    ('\u{1FAC1}', "lungs"),
+    ('\u{1FAC2}', "people hugging"),
+    ('\u{1FAC3}', "pregnant man"),
+    ('\u{1FAC4}', "pregnant person"),
+    ('\u{1FAC5}', "person with crown"),
+    ('\u{1FAD0}', "blueberries"),
+    ('\u{1FAD1}', "bell pepper"),
+    ('\u{1FAD2}', "olive"),
+    ('\u{1FAD3}', "flatbread"),
+    ('\u{1FAD4}', "tamale"),
+    ('\u{1FAD5}', "fondue"),
+    ('\u{1FAD6}', "teapot"),
+    ('\u{1FAD7}', "pouring liquid"),
+    ('\u{1FAD8}', "beans"),
+    ('\u{1FAD9}', "jar"),
+    ('\u{1FAE0}', "melting face"),
+    ('\u{1FAE1}', "saluting face"),
+    ('\u{1FAE2}', "face with open eyes and hand over mouth"),
+    ('\u{1FAE3}', "face with peeking eye"),
+    ('\u{1FAE4}', "face with diagonal mouth"),
+    ('\u{1FAE5}', "dotted line face"),
+    ('\u{1FAE6}', "biting lip"),
+    ('\u{1FAE7}', "bubbles"),
+    ('\u{1FAF0}', "hand with index finger and thumb crossed"),
+    ('\u{1FAF1}', "rightwards hand"),
+    ('\u{1FAF2}', "leftwards hand"),
+    ('\u{1FAF3}', "palm down hand"),
+    ('\u{1FAF4}', "palm up hand"),
+    ('\u{1FAF5}', "index pointing at the viewer"),
+    ('\u{1FAF6}', "heart hands"),
+];
+
+pub const TRANSPORT_AND_MAP: &[(char, &str)] = &[
+    ('\u{1F680}', "rocket"),
+    ('\u{1F681}', "helicopter"),
+    ('\u{1F682}', "steam locomotive"),
+    ('\u{1F683}', "railway car"),
+    ('\u{1F684}', "high-speed train"),
+    ('\u{1F685}', "high-speed train with bullet nose"),
+    ('\u{1F686}', "train"),
+    ('\u{1F687}', "metro"),
+    ('\u{1F688}', "light rail"),
+    ('\u{1F689}', "station"),
+    ('\u{1F68A}', "tram"),
+    ('\u{1F68B}', "tram car"),
+    ('\u{1F68C}', "bus"),
+    ('\u{1F68D}', "oncoming bus"),
+    ('\u{1F68E}', "trolleybus"),
+    ('\u{1F68F}', "bus stop"),
+    ('\u{1F690}', "minibus"),
+    ('\u{1F691}', "ambulance"),
+    ('\u{1F692}', "fire engine"),
+    ('\u{1F693}', "police car"),
+    ('\u{1F694}', "oncoming police car"),
+    ('\u{1F695}', "taxi"),
+    ('\u{1F696}', "oncoming taxi"),
+    ('\u{1F697}', "automobile"),
+    ('\u{1F698}', "oncoming automobile"),
+    ('\u{1F699}', "recreational vehicle"),
+    ('\u{1F69A}', "delivery truck"),
+    ('\u{1F69B}', "articulated lorry"),
+    ('\u{1F69C}', "tractor"),
+    ('\u{1F69D}', "monorail"),
+    ('\u{1F69E}', "mountain railway"),
+    ('\u{1F69F}', "suspension railway"),
+    ('\u{1F6A0}', "mountain cableway"),
+    ('\u{1F6A1}', "aerial tramway"),
+    ('\u{1F6A2}', "ship"),
+    ('\u{1F6A3}', "rowboat"),
+    ('\u{1F6A4}', "speedboat"),
+    ('\u{1F6A5}', "horizontal traffic light"),
+    ('\u{1F6A6}', "vertical traffic light"),
+    ('\u{1F6A7}', "construction sign"),
+    ('\u{1F6A8}', "police cars revolving light"),
+    ('\u{1F6A9}', "triangular flag on post"),
+    ('\u{1F6AA}', "door"),
+    ('\u{1F6AB}', "no entry sign"),
+    ('\u{1F6AC}', "smoking symbol"),
+    ('\u{1F6AD}', "no smoking symbol"),
+    ('\u{1F6AE}', "put litter in its place symbol"),
+    ('\u{1F6AF}', "do not litter symbol"),
+    ('\u{1F6B0}', "potable water symbol"),
+    ('\u{1F6B1}', "non-potable water symbol"),
+    ('\u{1F6B2}', "bicycle"),
+    ('\u{1F6B3}', "no bicycles"),
+    ('\u{1F6B4}', "bicyclist"),
+    ('\u{1F6B5}', "mountain bicyclist"),
+    ('\u{1F6B6}', "pedestrian"),
+    ('\u{1F6B7}', "no pedestrians"),
+    ('\u{1F6B8}', "children crossing"),
+    ('\u{1F6B9}', "mens symbol"),
+    ('\u{1F6BA}', "womens symbol"),
+    ('\u{1F6BB}', "restroom"),
+    ('\u{1F6BC}', "baby symbol"),
+    ('\u{1F6BD}', "toilet"),
+    ('\u{1F6BE}', "water closet"),
+    ('\u{1F6BF}', "shower"),
+    ('\u{1F6C0}', "bath"),
+    ('\u{1F6C1}', "bathtub"),
+    ('\u{1F6C2}', "passport control"),
+    ('\u{1F6C3}', "customs"),
+    ('\u{1F6C4}', "baggage claim"),
+    ('\u{1F6C5}', "left luggage"),
+    ('\u{1F6C6}', "triangle with rounded corners"),
+    ('\u{1F6C7}', "prohibited sign"),
+    ('\u{1F6C8}', "circled information source"),
+    ('\u{1F6C9}', "boys symbol"),
+    ('\u{1F6CA}', "girls symbol"),
+    ('\u{1F6CB}', "couch and lamp"),
+    ('\u{1F6CC}', "sleeping accommodation"),
+    ('\u{1F6CD}', "shopping bags"),
+    ('\u{1F6CE}', "bellhop bell"),
+    ('\u{1F6CF}', "bed"),
+    ('\u{1F6D0}', "place of worship"),
+    ('\u{1F6D1}', "octagonal sign"),
+    ('\u{1F6D2}', "shopping trolley"),
+    ('\u{1F6D3}', "stupa"),
+    ('\u{1F6D4}', "pagoda"),
+    ('\u{1F6D5}', "hindu temple"),
+    ('\u{1F6D6}', "hut"),
+    ('\u{1F6D7}', "elevator"),
+    ('\u{1F6DD}', "playground slide"),
+    ('\u{1F6DE}', "wheel"),
+    ('\u{1F6DF}', "ring buoy"),
+    ('\u{1F6E0}', "hammer and wrench"),
+    ('\u{1F6E1}', "shield"),
+    ('\u{1F6E2}', "oil drum"),
+    ('\u{1F6E3}', "motorway"),
+    ('\u{1F6E4}', "railway track"),
+    ('\u{1F6E5}', "motor boat"),
+    ('\u{1F6E6}', "up-pointing military airplane"),
+    ('\u{1F6E7}', "up-pointing airplane"),
+    ('\u{1F6E8}', "up-pointing small airplane"),
+    ('\u{1F6E9}', "small airplane"),
+    ('\u{1F6EA}', "northeast-pointing airplane"),
+    ('\u{1F6EB}', "airplane departure"),
+    ('\u{1F6EC}', "airplane arriving"),
+    ('\u{1F6F0}', "satellite"),
+    ('\u{1F6F1}', "oncoming fire engine"),
+    ('\u{1F6F2}', "diesel locomotive"),
+    ('\u{1F6F3}', "passenger ship"),
+    ('\u{1F6F4}', "scooter"),
+    ('\u{1F6F5}', "motor scooter"),
+    ('\u{1F6F6}', "canoe"),
+    ('\u{1F6F7}', "sled"),
+    ('\u{1F6F8}', "flying saucer"),
+    ('\u{1F6F9}', "skateboard"),
+    ('\u{1F6FA}', "auto rickshaw"),
+    ('\u{1F6FB}', "pickup truck"),
+    ('\u{1F6FC}', "roller skate"),
+];
+
+pub const SYMBOLS_AND_DINGBATS: &[(char, &str)] = &[
+    ('\u{2600}', "black sun with rays"),
+    ('\u{2601}', "cloud"),
+    ('\u{2602}', "umbrella"),
+    ('\u{2603}', "snowman"),
+    ('\u{2604}', "comet"),
+    ('\u{2605}', "black star"),
+    ('\u{2606}', "white star"),
+    ('\u{2607}', "lightning"),
+    ('\u{2608}', "thunderstorm"),
+    ('\u{2609}', "sun"),
+    ('\u{260A}', "ascending node"),
+    ('\u{260B}', "descending node"),
+    ('\u{260C}', "conjunction"),
+    ('\u{260D}', "opposition"),
+    ('\u{260E}', "black telephone"),
+    ('\u{260F}', "white telephone"),
+    ('\u{2610}', "ballot box"),
+    ('\u{2611}', "ballot box with check"),
+    ('\u{2612}', "ballot box with x"),
+    ('\u{2613}', "saltire"),
+    ('\u{2614}', "umbrella with rain drops"),
+    ('\u{2615}', "hot beverage"),
+    ('\u{2616}', "white shogi piece"),
+    ('\u{2617}', "black shogi piece"),
+    ('\u{2618}', "shamrock"),
+    ('\u{2619}', "reversed rotated floral heart bullet"),
+    ('\u{261A}', "black left pointing index"),
+    ('\u{261B}', "black right pointing index"),
+    ('\u{261C}', "white left pointing index"),
+    ('\u{261D}', "white up pointing index"),
+    ('\u{261E}', "white right pointing index"),
+    ('\u{261F}', "white down pointing index"),
+    ('\u{2620}', "skull and crossbones"),
+    ('\u{2621}', "caution sign"),
+    ('\u{2622}', "radioactive sign"),
+    ('\u{2623}', "biohazard sign"),
+    ('\u{2624}', "caduceus"),
+    ('\u{2625}', "ankh"),
+    ('\u{2626}', "orthodox cross"),
+    ('\u{2627}', "chi rho"),
+    ('\u{2628}', "cross of lorraine"),
+    ('\u{2629}', "cross of jerusalem"),
+    ('\u{262A}', "star and crescent"),
+    ('\u{262B}', "farsi symbol"),
+    ('\u{262C}', "adi shakti"),
+    ('\u{262D}', "hammer and sickle"),
+    ('\u{262E}', "peace symbol"),
+    ('\u{262F}', "yin yang"),
+    ('\u{2630}', "trigram for heaven"),
+    ('\u{2631}', "trigram for lake"),
+    ('\u{2632}', "trigram for fire"),
+    ('\u{2633}', "trigram for thunder"),
+    ('\u{2634}', "trigram for wind"),
+    ('\u{2635}', "trigram for water"),
+    ('\u{2636}', "trigram for mountain"),
+    ('\u{2637}', "trigram for earth"),
+    ('\u{2638}', "wheel of dharma"),
+    ('\u{2639}', "white frowning face"),
+    ('\u{263A}', "white smiling face"),
+    ('\u{263B}', "black smiling face"),
+    ('\u{263C}', "white sun with rays"),
+    ('\u{263D}', "first quarter moon"),
+    ('\u{263E}', "last quarter moon"),
+    ('\u{263F}', "mercury"),
+    ('\u{2640}', "female sign"),
+    ('\u{2641}', "earth"),
+    ('\u{2642}', "male sign"),
+    ('\u{2643}', "jupiter"),
+    ('\u{2644}', "saturn"),
+    ('\u{2645}', "uranus"),
+    ('\u{2646}', "neptune"),
+    ('\u{2647}', "pluto"),
+    ('\u{2648}', "aries"),
+    ('\u{2649}', "taurus"),
+    ('\u{264A}', "gemini"),
+    ('\u{264B}', "cancer"),
+    ('\u{264C}', "leo"),
+    ('\u{264D}', "virgo"),
+    ('\u{264E}', "libra"),
+    ('\u{264F}', "scorpius"),
+    ('\u{2650}', "sagittarius"),
+    ('\u{2651}', "capricorn"),
+    ('\u{2652}', "aquarius"),
+    ('\u{2653}', "pisces"),
+    ('\u{2654}', "white chess king"),
+    ('\u{2655}', "white chess queen"),
+    ('\u{2656}', "white chess rook"),
+    ('\u{2657}', "white chess bishop"),
+    ('\u{2658}', "white chess knight"),
+    ('\u{2659}', "white chess pawn"),
+    ('\u{265A}', "black chess king"),
+    ('\u{265B}', "black chess queen"),
+    ('\u{265C}', "black chess rook"),
+    ('\u{265D}', "black chess bishop"),
+    ('\u{265E}', "black chess knight"),
+    ('\u{265F}', "black chess pawn"),
+    ('\u{2660}', "black spade suit"),
+    ('\u{2661}', "white heart suit"),
+    ('\u{2662}', "white diamond suit"),
+    ('\u{2663}', "black club suit"),
+    ('\u{2664}', "white spade suit"),
+    ('\u{2665}', "black heart suit"),
+    ('\u{2666}', "black diamond suit"),
+    ('\u{2667}', "white club suit"),
+    ('\u{2668}', "hot springs"),
+    ('\u{2669}', "quarter note"),
+    ('\u{266A}', "eighth note"),
+    ('\u{266B}', "beamed eighth notes"),
+    ('\u{266C}', "beamed sixteenth notes"),
+    ('\u{266D}', "music flat sign"),
+    ('\u{266E}', "music natural sign"),
+    ('\u{266F}', "music sharp sign"),
+    ('\u{2670}', "west syriac cross"),
+    ('\u{2671}', "east syriac cross"),
+    ('\u{2672}', "universal recycling symbol"),
+    ('\u{2673}', "recycling symbol for type-1 plastics"),
+    ('\u{2674}', "recycling symbol for type-2 plastics"),
+    ('\u{2675}', "recycling symbol for type-3 plastics"),
+    ('\u{2676}', "recycling symbol for type-4 plastics"),
+    ('\u{2677}', "recycling symbol for type-5 plastics"),
+    ('\u{2678}', "recycling symbol for type-6 plastics"),
+    ('\u{2679}', "recycling symbol for type-7 plastics"),
+    ('\u{267A}', "recycling symbol for generic materials"),
+    ('\u{267B}', "black universal recycling symbol"),
+    ('\u{267C}', "recycled paper symbol"),
+    ('\u{267D}', "partially-recycled paper symbol"),
+    ('\u{267E}', "permanent paper sign"),
+    ('\u{267F}', "wheelchair symbol"),
+    ('\u{2680}', "die face-1"),
+    ('\u{2681}', "die face-2"),
+    ('\u{2682}', "die face-3"),
+    ('\u{2683}', "die face-4"),
+    ('\u{2684}', "die face-5"),
+    ('\u{2685}', "die face-6"),
+    ('\u{2686}', "white circle with dot right"),
+    ('\u{2687}', "white circle with two dots"),
+    ('\u{2688}', "black circle with white dot right"),
+    ('\u{2689}', "black circle with two white dots"),
+    ('\u{268A}', "monogram for yang"),
+    ('\u{268B}', "monogram for yin"),
+    ('\u{268C}', "digram for greater yang"),
+    ('\u{268D}', "digram for lesser yin"),
+    ('\u{268E}', "digram for lesser yang"),
+    ('\u{268F}', "digram for greater yin"),
+    ('\u{2690}', "white flag"),
+    ('\u{2691}', "black flag"),
+    ('\u{2692}', "hammer and pick"),
+    ('\u{2693}', "anchor"),
+    ('\u{2694}', "crossed swords"),
+    ('\u{2695}', "staff of aesculapius"),
+    ('\u{2696}', "scales"),
+    ('\u{2697}', "alembic"),
+    ('\u{2698}', "flower"),
+    ('\u{2699}', "gear"),
+    ('\u{269A}', "staff of hermes"),
+    ('\u{269B}', "atom symbol"),
+    ('\u{269C}', "fleur-de-lis"),
+    ('\u{269D}', "outlined white star"),
+    ('\u{269E}', "three lines converging right"),
+    ('\u{269F}', "three lines converging left"),
+    ('\u{26A0}', "warning sign"),
+    ('\u{26A1}', "high voltage sign"),
+    ('\u{26A2}', "doubled female sign"),
+    ('\u{26A3}', "doubled male sign"),
+    ('\u{26A4}', "interlocked female and male sign"),
+    ('\u{26A5}', "male and female sign"),
+    ('\u{26A6}', "male with stroke sign"),
+    ('\u{26A7}', "male with stroke and male and female sign"),
+    ('\u{26A8}', "vertical male with stroke sign"),
+    ('\u{26A9}', "horizontal male with stroke sign"),
+    ('\u{26AA}', "medium white circle"),
+    ('\u{26AB}', "medium black circle"),
+    ('\u{26AC}', "medium small white circle"),
+    ('\u{26AD}', "marriage symbol"),
+    ('\u{26AE}', "divorce symbol"),
+    ('\u{26AF}', "unmarried partnership symbol"),
+    ('\u{26B0}', "coffin"),
+    ('\u{26B1}', "funeral urn"),
+    ('\u{26B2}', "neuter"),
+    ('\u{26B3}', "ceres"),
+    ('\u{26B4}', "pallas"),
+    ('\u{26B5}', "juno"),
+    ('\u{26B6}', "vesta"),
+    ('\u{26B7}', "chiron"),
+    ('\u{26B8}', "black moon lilith"),
+    ('\u{26B9}', "sextile"),
+    ('\u{26BA}', "semisextile"),
+    ('\u{26BB}', "quincunx"),
+    ('\u{26BC}', "sesquiquadrate"),
+    ('\u{26BD}', "soccer ball"),
+    ('\u{26BE}', "baseball"),
+    ('\u{26BF}', "squared key"),
+    ('\u{26C0}', "white draughts man"),
+    ('\u{26C1}', "white draughts king"),
+    ('\u{26C2}', "black draughts man"),
+    ('\u{26C3}', "black draughts king"),
+    ('\u{26C4}', "snowman without snow"),
+    ('\u{26C5}', "sun behind cloud"),
+    ('\u{26C6}', "rain"),
+    ('\u{26C7}', "black snowman"),
+    ('\u{26C8}', "thunder cloud and rain"),
+    ('\u{26C9}', "turned white shogi piece"),
+    ('\u{26CA}', "turned black shogi piece"),
+    ('\u{26CB}', "white diamond in square"),
+    ('\u{26CC}', "crossing lanes"),
+    ('\u{26CD}', "disabled car"),
+    ('\u{26CE}', "ophiuchus"),
+    ('\u{26CF}', "pick"),
+    ('\u{26D0}', "car sliding"),
+    ('\u{26D1}', "helmet with white cross"),
+    ('\u{26D2}', "circled crossing lanes"),
+    ('\u{26D3}', "chains"),
+    ('\u{26D4}', "no entry"),
+    ('\u{26D5}', "alternate one-way left way traffic"),
+    ('\u{26D6}', "black two-way left way traffic"),
+    ('\u{26D7}', "white two-way left way traffic"),
+    ('\u{26D8}', "black left lane merge"),
+    ('\u{26D9}', "white left lane merge"),
+    ('\u{26DA}', "drive slow sign"),
+    ('\u{26DB}', "heavy white down-pointing triangle"),
+    ('\u{26DC}', "left closed entry"),
+    ('\u{26DD}', "squared saltire"),
+    ('\u{26DE}', "falling diagonal in white circle in black square"),
+    ('\u{26DF}', "black truck"),
+    ('\u{26E0}', "restricted left entry-1"),
+    ('\u{26E1}', "restricted left entry-2"),
+    ('\u{26E2}', "astronomical symbol for uranus"),
+    ('\u{26E3}', "heavy circle with stroke and two dots above"),
+    ('\u{26E4}', "pentagram"),
+    ('\u{26E5}', "right-handed interlaced pentagram"),
+    ('\u{26E6}', "left-handed interlaced pentagram"),
+    ('\u{26E7}', "inverted pentagram"),
+    ('\u{26E8}', "black cross on shield"),
+    ('\u{26E9}', "shinto shrine"),
+    ('\u{26EA}', "church"),
+    ('\u{26EB}', "castle"),
+    ('\u{26EC}', "historic site"),
+    ('\u{26ED}', "gear without hub"),
+    ('\u{26EE}', "gear with handles"),
+    ('\u{26EF}', "map symbol for lighthouse"),
+    ('\u{26F0}', "mountain"),
+    ('\u{26F1}', "umbrella on ground"),
+    ('\u{26F2}', "fountain"),
+    ('\u{26F3}', "flag in hole"),
+    ('\u{26F4}', "ferry"),
+    ('\u{26F5}', "sailboat"),
+    ('\u{26F6}', "square four corners"),
+    ('\u{26F7}', "skier"),
+    ('\u{26F8}', "ice skate"),
+    ('\u{26F9}', "person with ball"),
+    ('\u{26FA}', "tent"),
+    ('\u{26FB}', "japanese bank symbol"),
+    ('\u{26FC}', "headstone graveyard symbol"),
+    ('\u{26FD}', "fuel pump"),
+    ('\u{26FE}', "cup on black square"),
+    ('\u{26FF}', "white flag with horizontal middle black stripe"),
+    ('\u{2700}', "black safety scissors"),
+    ('\u{2701}', "upper blade scissors"),
+    ('\u{2702}', "black scissors"),
+    ('\u{2703}', "lower blade scissors"),
+    ('\u{2704}', "white scissors"),
+    ('\u{2705}', "white heavy check mark"),
+    ('\u{2706}', "telephone location sign"),
+    ('\u{2707}', "tape drive"),
+    ('\u{2708}', "airplane"),
+    ('\u{2709}', "envelope"),
+    ('\u{270A}', "raised fist"),
+    ('\u{270B}', "raised hand"),
+    ('\u{270C}', "victory hand"),
+    ('\u{270D}', "writing hand"),
+    ('\u{270E}', "lower right pencil"),
+    ('\u{270F}', "pencil"),
+    ('\u{2710}', "upper right pencil"),
+    ('\u{2711}', "white nib"),
+    ('\u{2712}', "black nib"),
+    ('\u{2713}', "check mark"),
+    ('\u{2714}', "heavy check mark"),
+    ('\u{2715}', "multiplication x"),
+    ('\u{2716}', "heavy multiplication x"),
+    ('\u{2717}', "ballot x"),
+    ('\u{2718}', "heavy ballot x"),
+    ('\u{2719}', "outlined greek cross"),
+    ('\u{271A}', "heavy greek cross"),
+    ('\u{271B}', "open centre cross"),
+    ('\u{271C}', "heavy open centre cross"),
+    ('\u{271D}', "latin cross"),
+    ('\u{271E}', "shadowed white latin cross"),
+    ('\u{271F}', "outlined latin cross"),
+    ('\u{2720}', "maltese cross"),
+    ('\u{2721}', "star of david"),
+    ('\u{2722}', "four teardrop-spoked asterisk"),
+    ('\u{2723}', "four balloon-spoked asterisk"),
+    ('\u{2724}', "heavy four balloon-spoked asterisk"),
+    ('\u{2725}', "four club-spoked asterisk"),
+    ('\u{2726}', "black four pointed star"),
+    ('\u{2727}', "white four pointed star"),
+    ('\u{2728}', "sparkles"),
+    ('\u{2729}', "stress outlined white star"),
+    ('\u{272A}', "circled white star"),
+    ('\u{272B}', "open centre black star"),
+    ('\u{272C}', "black centre white star"),
+    ('\u{272D}', "outlined black star"),
+    ('\u{272E}', "heavy outlined black star"),
+    ('\u{272F}', "pinwheel star"),
+    ('\u{2730}', "shadowed white star"),
+    ('\u{2731}', "heavy asterisk"),
+    ('\u{2732}', "open centre asterisk"),
+    ('\u{2733}', "eight spoked asterisk"),
+    ('\u{2734}', "eight pointed black star"),
+    ('\u{2735}', "eight pointed pinwheel star"),
+    ('\u{2736}', "six pointed black star"),
+    ('\u{2737}', "eight pointed rectilinear black star"),
+    ('\u{2738}', "heavy eight pointed rectilinear black star"),
+    ('\u{2739}', "twelve pointed black star"),
+    ('\u{273A}', "sixteen pointed asterisk"),
+    ('\u{273B}', "teardrop-spoked asterisk"),
+    ('\u{273C}', "open centre teardrop-spoked asterisk"),
+    ('\u{273D}', "heavy teardrop-spoked asterisk"),
+    ('\u{273E}', "six petalled black and white florette"),
+    ('\u{273F}', "black florette"),
+    ('\u{2740}', "white florette"),
+    ('\u{2741}', "eight petalled outlined black florette"),
+    ('\u{2742}', "circled open centre eight pointed star"),
+    ('\u{2743}', "heavy teardrop-spoked pinwheel asterisk"),
+    ('\u{2744}', "snowflake"),
+    ('\u{2745}', "tight trifoliate snowflake"),
+    ('\u{2746}', "heavy chevron snowflake"),
+    ('\u{2747}', "sparkle"),
+    ('\u{2748}', "heavy sparkle"),
+    ('\u{2749}', "balloon-spoked asterisk"),
+    ('\u{274A}', "eight teardrop-spoked propeller asterisk"),
+    ('\u{274B}', "heavy eight teardrop-spoked propeller asterisk"),
+    ('\u{274C}', "cross mark"),
+    ('\u{274D}', "shadowed white circle"),
+    ('\u{274E}', "negative squared cross mark"),
+    ('\u{274F}', "lower right drop-shadowed white square"),
+    ('\u{2750}', "upper right drop-shadowed white square"),
+    ('\u{2751}', "lower right shadowed white square"),
+    ('\u{2752}', "upper right shadowed white square"),
+    ('\u{2753}', "black question mark ornament"),
+    ('\u{2754}', "white question mark ornament"),
+    ('\u{2755}', "white exclamation mark ornament"),
+    ('\u{2756}', "black diamond minus white x"),
+    ('\u{2757}', "heavy exclamation mark symbol"),
+    ('\u{2758}', "light vertical bar"),
+    ('\u{2759}', "medium vertical bar"),
+    ('\u{275A}', "heavy vertical bar"),
+    ('\u{275B}', "heavy single turned comma quotation mark ornament"),
+    ('\u{275C}', "heavy single comma quotation mark ornament"),
+    ('\u{275D}', "heavy double turned comma quotation mark ornament"),
+    ('\u{275E}', "heavy double comma quotation mark ornament"),
+    ('\u{275F}', "heavy low single comma quotation mark ornament"),
+    ('\u{2760}', "heavy low double comma quotation mark ornament"),
+    ('\u{2761}', "curved stem paragraph sign ornament"),
+    ('\u{2762}', "heavy exclamation mark ornament"),
+    ('\u{2763}', "heavy heart exclamation mark ornament"),
+    ('\u{2764}', "heavy black heart"),
+    ('\u{2765}', "rotated heavy black heart bullet"),
+    ('\u{2766}', "floral heart"),
+    ('\u{2767}', "rotated floral heart bullet"),
+    ('\u{2768}', "medium left parenthesis ornament"),
+    ('\u{2769}', "medium right parenthesis ornament"),
+    ('\u{276A}', "medium flattened left parenthesis ornament"),
+    ('\u{276B}', "medium flattened right parenthesis ornament"),
+    ('\u{276C}', "medium left-pointing angle bracket ornament"),
+    ('\u{276D}', "medium right-pointing angle bracket ornament"),
+    ('\u{276E}', "heavy left-pointing angle quotation mark ornament"),
+    ('\u{276F}', "heavy right-pointing angle quotation mark ornament"),
+    ('\u{2770}', "heavy left-pointing angle bracket ornament"),
+    ('\u{2771}', "heavy right-pointing angle bracket ornament"),
+    ('\u{2772}', "light left tortoise shell bracket ornament"),
+    ('\u{2773}', "light right tortoise shell bracket ornament"),
+    ('\u{2774}', "medium left curly bracket ornament"),
+    ('\u{2775}', "medium right curly bracket ornament"),
+    ('\u{2776}', "dingbat negative circled digit one"),
+    ('\u{2777}', "dingbat negative circled digit two"),
+    ('\u{2778}', "dingbat negative circled digit three"),
+    ('\u{2779}', "dingbat negative circled digit four"),
+    ('\u{277A}', "dingbat negative circled digit five"),
+    ('\u{277B}', "dingbat negative circled digit six"),
+    ('\u{277C}', "dingbat negative circled digit seven"),
+    ('\u{277D}', "dingbat negative circled digit eight"),
+    ('\u{277E}', "dingbat negative circled digit nine"),
+    ('\u{277F}', "dingbat negative circled number ten"),
+    ('\u{2780}', "dingbat circled sans-serif digit one"),
+    ('\u{2781}', "dingbat circled sans-serif digit two"),
+    ('\u{2782}', "dingbat circled sans-serif digit three"),
+    ('\u{2783}', "dingbat circled sans-serif digit four"),
+    ('\u{2784}', "dingbat circled sans-serif digit five"),
+    ('\u{2785}', "dingbat circled sans-serif digit six"),
+    ('\u{2786}', "dingbat circled sans-serif digit seven"),
+    ('\u{2787}', "dingbat circled sans-serif digit eight"),
+    ('\u{2788}', "dingbat circled sans-serif digit nine"),
+    ('\u{2789}', "dingbat circled sans-serif number ten"),
+    ('\u{278A}', "dingbat negative circled sans-serif digit one"),
+    ('\u{278B}', "dingbat negative circled sans-serif digit two"),
+    ('\u{278C}', "dingbat negative circled sans-serif digit three"),
+    ('\u{278D}', "dingbat negative circled sans-serif digit four"),
+    ('\u{278E}', "dingbat negative circled sans-serif digit five"),
+    ('\u{278F}', "dingbat negative circled sans-serif digit six"),
+    ('\u{2790}', "dingbat negative circled sans-serif digit seven"),
+    ('\u{2791}', "dingbat negative circled sans-serif digit eight"),
+    ('\u{2792}', "dingbat negative circled sans-serif digit nine"),
+    ('\u{2793}', "dingbat negative circled sans-serif number ten"),
+    ('\u{2794}', "heavy wide-headed rightwards arrow"),
+    ('\u{2795}', "heavy plus sign"),
+    ('\u{2796}', "heavy minus sign"),
+    ('\u{2797}', "heavy division sign"),
+    ('\u{2798}', "heavy south east arrow"),
+    ('\u{2799}', "heavy rightwards arrow"),
+    ('\u{279A}', "heavy north east arrow"),
+    ('\u{279B}', "drafting point rightwards arrow"),
+    ('\u{279C}', "heavy round-tipped rightwards arrow"),
+    ('\u{279D}', "triangle-headed rightwards arrow"),
+    ('\u{279E}', "heavy triangle-headed rightwards arrow"),
+    ('\u{279F}', "dashed triangle-headed rightwards arrow"),
+    ('\u{27A0}', "heavy dashed triangle-headed rightwards arrow"),
+    ('\u{27A1}', "black rightwards arrow"),
+    ('\u{27A2}', "three-d top-lighted rightwards arrowhead"),
+    ('\u{27A3}', "three-d bottom-lighted rightwards arrowhead"),
+    ('\u{27A4}', "black rightwards arrowhead"),
+    ('\u{27A5}', "heavy black curved downwards and rightwards arrow"),
+    ('\u{27A6}', "heavy black curved upwards and rightwards arrow"),
+    ('\u{27A7}', "squat black rightwards arrow"),
+    ('\u{27A8}', "heavy concave-pointed black rightwards arrow"),
+    ('\u{27A9}', "right-shaded white rightwards arrow"),
+    ('\u{27AA}', "left-shaded white rightwards arrow"),
+    ('\u{27AB}', "back-tilted shadowed white rightwards arrow"),
+    ('\u{27AC}', "front-tilted shadowed white rightwards arrow"),
+    ('\u{27AD}', "heavy lower right-shadowed white rightwards arrow"),
+    ('\u{27AE}', "heavy upper right-shadowed white rightwards arrow"),
+    ('\u{27AF}', "notched lower right-shadowed white rightwards arrow"),
+    ('\u{27B0}', "curly loop"),
+    ('\u{27B1}', "notched upper right-shadowed white rightwards arrow"),
+    ('\u{27B2}', "circled heavy white rightwards arrow"),
+    ('\u{27B3}', "white-feathered rightwards arrow"),
+    ('\u{27B4}', "black-feathered south east arrow"),
+    ('\u{27B5}', "black-feathered rightwards arrow"),
+    ('\u{27B6}', "black-feathered north east arrow"),
+    ('\u{27B7}', "heavy black-feathered south east arrow"),
+    ('\u{27B8}', "heavy black-feathered rightwards arrow"),
+    ('\u{27B9}', "heavy black-feathered north east arrow"),
+    ('\u{27BA}', "teardrop-barbed rightwards arrow"),
+    ('\u{27BB}', "heavy teardrop-shanked rightwards arrow"),
+    ('\u{27BC}', "wedge-tailed rightwards arrow"),
+    ('\u{27BD}', "heavy wedge-tailed rightwards arrow"),
+    ('\u{27BE}', "open-outlined rightwards arrow"),
+    ('\u{27BF}', "double curly loop"),
+];
+
+pub const ARROWS: &[(char, &str)] = &[
+    ('\u{2190}', "leftwards arrow"),
+    ('\u{2191}', "upwards arrow"),
+    ('\u{2192}', "rightwards arrow"),
+    ('\u{2193}', "downwards arrow"),
+    ('\u{2194}', "left right arrow"),
+    ('\u{2195}', "up down arrow"),
+    ('\u{2196}', "north west arrow"),
+    ('\u{2197}', "north east arrow"),
+    ('\u{2198}', "south east arrow"),
+    ('\u{2199}', "south west arrow"),
+    ('\u{219A}', "leftwards arrow with stroke"),
+    ('\u{219B}', "rightwards arrow with stroke"),
+    ('\u{219C}', "leftwards wave arrow"),
+    ('\u{219D}', "rightwards wave arrow"),
+    ('\u{219E}', "leftwards two headed arrow"),
+    ('\u{219F}', "upwards two headed arrow"),
+    ('\u{21A0}', "rightwards two headed arrow"),
+    ('\u{21A1}', "downwards two headed arrow"),
+    ('\u{21A2}', "leftwards arrow with tail"),
+    ('\u{21A3}', "rightwards arrow with tail"),
+    ('\u{21A4}', "leftwards arrow from bar"),
+    ('\u{21A5}', "upwards arrow from bar"),
+    ('\u{21A6}', "rightwards arrow from bar"),
+    ('\u{21A7}', "downwards arrow from bar"),
+    ('\u{21A8}', "up down arrow with base"),
+    ('\u{21A9}', "leftwards arrow with hook"),
+    ('\u{21AA}', "rightwards arrow with hook"),
+    ('\u{21AB}', "leftwards arrow with loop"),
+    ('\u{21AC}', "rightwards arrow with loop"),
+    ('\u{21AD}', "left right wave arrow"),
+    ('\u{21AE}', "left right arrow with stroke"),
+    ('\u{21AF}', "downwards zigzag arrow"),
+    ('\u{21B0}', "upwards arrow with tip leftwards"),
+    ('\u{21B1}', "upwards arrow with tip rightwards"),
+    ('\u{21B2}', "downwards arrow with tip leftwards"),
+    ('\u{21B3}', "downwards arrow with tip rightwards"),
+    ('\u{21B4}', "rightwards arrow with corner downwards"),
+    ('\u{21B5}', "downwards arrow with corner leftwards"),
+    ('\u{21B6}', "anticlockwise top semicircle arrow"),
+    ('\u{21B7}', "clockwise top semicircle arrow"),
+    ('\u{21B8}', "north west arrow to long bar"),
+    ('\u{21B9}', "leftwards arrow to bar over rightwards arrow to bar"),
+    ('\u{21BA}', "anticlockwise open circle arrow"),
+    ('\u{21BB}', "clockwise open circle arrow"),
+    ('\u{21BC}', "leftwards harpoon with barb upwards"),
+    ('\u{21BD}', "leftwards harpoon with barb downwards"),
+    ('\u{21BE}', "upwards harpoon with barb rightwards"),
+    ('\u{21BF}', "upwards harpoon with barb leftwards"),
+    ('\u{21C0}', "rightwards harpoon with barb upwards"),
+    ('\u{21C1}', "rightwards harpoon with barb downwards"),
+    ('\u{21C2}', "downwards harpoon with barb rightwards"),
+    ('\u{21C3}', "downwards harpoon with barb leftwards"),
+    ('\u{21C4}', "rightwards arrow over leftwards arrow"),
+    ('\u{21C5}', "upwards arrow leftwards of downwards arrow"),
+    ('\u{21C6}', "leftwards arrow over rightwards arrow"),
+    ('\u{21C7}', "leftwards paired arrows"),
+    ('\u{21C8}', "upwards paired arrows"),
+    ('\u{21C9}', "rightwards paired arrows"),
+    ('\u{21CA}', "downwards paired arrows"),
+    ('\u{21CB}', "leftwards harpoon over rightwards harpoon"),
+    ('\u{21CC}', "rightwards harpoon over leftwards harpoon"),
+    ('\u{21CD}', "leftwards double arrow with stroke"),
+    ('\u{21CE}', "left right double arrow with stroke"),
+    ('\u{21CF}', "rightwards double arrow with stroke"),
+    ('\u{21D0}', "leftwards double arrow"),
+    ('\u{21D1}', "upwards double arrow"),
+    ('\u{21D2}', "rightwards double arrow"),
+    ('\u{21D3}', "downwards double arrow"),
+    ('\u{21D4}', "left right double arrow"),
+    ('\u{21D5}', "up down double arrow"),
+    ('\u{21D6}', "north west double arrow"),
+    ('\u{21D7}', "north east double arrow"),
+    ('\u{21D8}', "south east double arrow"),
+    ('\u{21D9}', "south west double arrow"),
+    ('\u{21DA}', "leftwards triple arrow"),
+    ('\u{21DB}', "rightwards triple arrow"),
+    ('\u{21DC}', "leftwards squiggle arrow"),
+    ('\u{21DD}', "rightwards squiggle arrow"),
+    ('\u{21DE}', "upwards arrow with double stroke"),
+    ('\u{21DF}', "downwards arrow with double stroke"),
+    ('\u{21E0}', "leftwards dashed arrow"),
+    ('\u{21E1}', "upwards dashed arrow"),
+    ('\u{21E2}', "rightwards dashed arrow"),
+    ('\u{21E3}', "downwards dashed arrow"),
+    ('\u{21E4}', "leftwards arrow to bar"),
+    ('\u{21E5}', "rightwards arrow to bar"),
+    ('\u{21E6}', "leftwards white arrow"),
+    ('\u{21E7}', "upwards white arrow"),
+    ('\u{21E8}', "rightwards white arrow"),
+    ('\u{21E9}', "downwards white arrow"),
+    ('\u{21EA}', "upwards white arrow from bar"),
+    ('\u{21EB}', "upwards white arrow on pedestal"),
+    ('\u{21EC}', "upwards white arrow on pedestal with horizontal bar"),
+    ('\u{21ED}', "upwards white arrow on pedestal with vertical bar"),
+    ('\u{21EE}', "upwards white double arrow"),
+    ('\u{21EF}', "upwards white double arrow on pedestal"),
+    ('\u{21F0}', "rightwards white arrow from wall"),
+    ('\u{21F1}', "north west arrow to corner"),
+    ('\u{21F2}', "south east arrow to corner"),
+    ('\u{21F3}', "up down white arrow"),
+    ('\u{21F4}', "right arrow with small circle"),
+    ('\u{21F5}', "downwards arrow leftwards of upwards arrow"),
+    ('\u{21F6}', "three rightwards arrows"),
+    ('\u{21F7}', "leftwards arrow with vertical stroke"),
+    ('\u{21F8}', "rightwards arrow with vertical stroke"),
+    ('\u{21F9}', "left right arrow with vertical stroke"),
+    ('\u{21FA}', "leftwards arrow with double vertical stroke"),
+    ('\u{21FB}', "rightwards arrow with double vertical stroke"),
+    ('\u{21FC}', "left right arrow with double vertical stroke"),
+    ('\u{21FD}', "leftwards open-headed arrow"),
+    ('\u{21FE}', "rightwards open-headed arrow"),
+    ('\u{21FF}', "left right open-headed arrow"),
+    ('\u{27F0}', "upwards quadruple arrow"),
+    ('\u{27F1}', "downwards quadruple arrow"),
+    ('\u{27F2}', "anticlockwise gapped circle arrow"),
+    ('\u{27F3}', "clockwise gapped circle arrow"),
+    ('\u{27F4}', "right arrow with circled plus"),
+    ('\u{27F5}', "long leftwards arrow"),
+    ('\u{27F6}', "long rightwards arrow"),
+    ('\u{27F7}', "long left right arrow"),
+    ('\u{27F8}', "long leftwards double arrow"),
+    ('\u{27F9}', "long rightwards double arrow"),
+    ('\u{27FA}', "long left right double arrow"),
+    ('\u{27FB}', "long leftwards arrow from bar"),
+    ('\u{27FC}', "long rightwards arrow from bar"),
+    ('\u{27FD}', "long leftwards double arrow from bar"),
+    ('\u{27FE}', "long rightwards double arrow from bar"),
+    ('\u{27FF}', "long rightwards squiggle arrow"),
+    ('\u{2900}', "rightwards two-headed arrow with vertical stroke"),
+    ('\u{2901}', "rightwards two-headed arrow with double vertical stroke"),
+    ('\u{2902}', "leftwards double arrow with vertical stroke"),
+    ('\u{2903}', "rightwards double arrow with vertical stroke"),
+    ('\u{2904}', "left right double arrow with vertical stroke"),
+    ('\u{2905}', "rightwards two-headed arrow from bar"),
+    ('\u{2906}', "leftwards double arrow from bar"),
+    ('\u{2907}', "rightwards double arrow from bar"),
+    ('\u{2908}', "downwards arrow with horizontal stroke"),
+    ('\u{2909}', "upwards arrow with horizontal stroke"),
+    ('\u{290A}', "upwards triple arrow"),
+    ('\u{290B}', "downwards triple arrow"),
+    ('\u{290C}', "leftwards double dash arrow"),
+    ('\u{290D}', "rightwards double dash arrow"),
+    ('\u{290E}', "leftwards triple dash arrow"),
+    ('\u{290F}', "rightwards triple dash arrow"),
+    ('\u{2910}', "rightwards two-headed triple dash arrow"),
+    ('\u{2911}', "rightwards arrow with dotted stem"),
+    ('\u{2912}', "upwards arrow to bar"),
+    ('\u{2913}', "downwards arrow to bar"),
+    ('\u{2914}', "rightwards arrow with tail with vertical stroke"),
+    ('\u{2915}', "rightwards arrow with tail with double vertical stroke"),
+    ('\u{2916}', "rightwards two-headed arrow with tail"),
+    ('\u{2917}', "rightwards two-headed arrow with tail with vertical stroke"),
+    ('\u{2918}', "rightwards two-headed arrow with tail with double vertical stroke"),
+    ('\u{2919}', "leftwards arrow-tail"),
+    ('\u{291A}', "rightwards arrow-tail"),
+    ('\u{291B}', "leftwards double arrow-tail"),
+    ('\u{291C}', "rightwards double arrow-tail"),
+    ('\u{291D}', "leftwards arrow to black diamond"),
+    ('\u{291E}', "rightwards arrow to black diamond"),
+    ('\u{291F}', "leftwards arrow from bar to black diamond"),
+    ('\u{2920}', "rightwards arrow from bar to black diamond"),
+    ('\u{2921}', "north west and south east arrow"),
+    ('\u{2922}', "north east and south west arrow"),
+    ('\u{2923}', "north west arrow with hook"),
+    ('\u{2924}', "north east arrow with hook"),
+    ('\u{2925}', "south east arrow with hook"),
+    ('\u{2926}', "south west arrow with hook"),
+    ('\u{2927}', "north west arrow and north east arrow"),
+    ('\u{2928}', "north east arrow and south east arrow"),
+    ('\u{2929}', "south east arrow and south west arrow"),
+    ('\u{292A}', "south west arrow and north west arrow"),
+    ('\u{292B}', "rising diagonal crossing falling diagonal"),
+    ('\u{292C}', "falling diagonal crossing rising diagonal"),
+    ('\u{292D}', "south east arrow crossing north east arrow"),
+    ('\u{292E}', "north east arrow crossing south east arrow"),
+    ('\u{292F}', "falling diagonal crossing north east arrow"),
+    ('\u{2930}', "rising diagonal crossing south east arrow"),
+    ('\u{2931}', "north east arrow crossing north west arrow"),
+    ('\u{2932}', "north west arrow crossing north east arrow"),
+    ('\u{2933}', "wave arrow pointing directly right"),
+    ('\u{2934}', "arrow pointing rightwards then curving upwards"),
+    ('\u{2935}', "arrow pointing rightwards then curving downwards"),
+    ('\u{2936}', "arrow pointing downwards then curving leftwards"),
+    ('\u{2937}', "arrow pointing downwards then curving rightwards"),
+    ('\u{2938}', "right-side arc clockwise arrow"),
+    ('\u{2939}', "left-side arc anticlockwise arrow"),
+    ('\u{293A}', "top arc anticlockwise arrow"),
+    ('\u{293B}', "bottom arc anticlockwise arrow"),
+    ('\u{293C}', "top arc clockwise arrow with minus"),
+    ('\u{293D}', "top arc anticlockwise arrow with plus"),
+    ('\u{293E}', "lower right semicircular clockwise arrow"),
+    ('\u{293F}', "lower left semicircular anticlockwise arrow"),
+    ('\u{2940}', "anticlockwise closed circle arrow"),
+    ('\u{2941}', "clockwise closed circle arrow"),
+    ('\u{2942}', "rightwards arrow above short leftwards arrow"),
+    ('\u{2943}', "leftwards arrow above short rightwards arrow"),
+    ('\u{2944}', "short rightwards arrow above leftwards arrow"),
+    ('\u{2945}', "rightwards arrow with plus below"),
+    ('\u{2946}', "leftwards arrow with plus below"),
+    ('\u{2947}', "rightwards arrow through x"),
+    ('\u{2948}', "left right arrow through small circle"),
+    ('\u{2949}', "upwards two-headed arrow from small circle"),
+    ('\u{294A}', "left barb up right barb down harpoon"),
+    ('\u{294B}', "left barb down right barb up harpoon"),
+    ('\u{294C}', "up barb right down barb left harpoon"),
+    ('\u{294D}', "up barb left down barb right harpoon"),
+    ('\u{294E}', "left barb up right barb up harpoon"),
+    ('\u{294F}', "up barb right down barb right harpoon"),
+    ('\u{2950}', "left barb down right barb down harpoon"),
+    ('\u{2951}', "up barb left down barb left harpoon"),
+    ('\u{2952}', "leftwards harpoon with barb up to bar"),
+    ('\u{2953}', "rightwards harpoon with barb up to bar"),
+    ('\u{2954}', "upwards harpoon with barb right to bar"),
+    ('\u{2955}', "downwards harpoon with barb right to bar"),
+    ('\u{2956}', "leftwards harpoon with barb down to bar"),
+    ('\u{2957}', "rightwards harpoon with barb down to bar"),
+    ('\u{2958}', "upwards harpoon with barb left to bar"),
+    ('\u{2959}', "downwards harpoon with barb left to bar"),
+    ('\u{295A}', "leftwards harpoon with barb up from bar"),
+    ('\u{295B}', "rightwards harpoon with barb up from bar"),
+    ('\u{295C}', "upwards harpoon with barb right from bar"),
+    ('\u{295D}', "downwards harpoon with barb right from bar"),
+    ('\u{295E}', "leftwards harpoon with barb down from bar"),
+    ('\u{295F}', "rightwards harpoon with barb down from bar"),
+    ('\u{2960}', "upwards harpoon with barb left from bar"),
+    ('\u{2961}', "downwards harpoon with barb left from bar"),
+    ('\u{2962}', "leftwards harpoon with barb up above leftwards harpoon with barb down"),
+    ('\u{2963}', "upwards harpoon with barb left beside upwards harpoon with barb right"),
+    ('\u{2964}', "rightwards harpoon with barb up above rightwards harpoon with barb down"),
+    ('\u{2965}', "downwards harpoon with barb left beside downwards harpoon with barb right"),
+    ('\u{2966}', "leftwards harpoon with barb up above rightwards harpoon with barb up"),
+    ('\u{2967}', "leftwards harpoon with barb down above rightwards harpoon with barb down"),
+    ('\u{2968}', "rightwards harpoon with barb up above leftwards harpoon with barb up"),
+    ('\u{2969}', "rightwards harpoon with barb down above leftwards harpoon with barb down"),
+    ('\u{296A}', "leftwards harpoon with barb up above long dash"),
+    ('\u{296B}', "leftwards harpoon with barb down below long dash"),
+    ('\u{296C}', "rightwards harpoon with barb up above long dash"),
+    ('\u{296D}', "rightwards harpoon with barb down below long dash"),
+    ('\u{296E}', "upwards harpoon with barb left beside downwards harpoon with barb right"),
+    ('\u{296F}', "downwards harpoon with barb left beside upwards harpoon with barb right"),
+    ('\u{2970}', "right double arrow with rounded head"),
+    ('\u{2971}', "equals sign above rightwards arrow"),
+    ('\u{2972}', "tilde operator above rightwards arrow"),
+    ('\u{2973}', "leftwards arrow above tilde operator"),
+    ('\u{2974}', "rightwards arrow above tilde operator"),
+    ('\u{2975}', "rightwards arrow above almost equal to"),
+    ('\u{2976}', "less-than above leftwards arrow"),
+    ('\u{2977}', "leftwards arrow through less-than"),
+    ('\u{2978}', "greater-than above rightwards arrow"),
+    ('\u{2979}', "subset above rightwards arrow"),
+    ('\u{297A}', "leftwards arrow through subset"),
+    ('\u{297B}', "superset above leftwards arrow"),
+    ('\u{297C}', "left fish tail"),
+    ('\u{297D}', "right fish tail"),
+    ('\u{297E}', "up fish tail"),
+    ('\u{297F}', "down fish tail"),
+];
+
+pub const MATHEMATICAL: &[(char, &str)] = &[
+    ('\u{2200}', "for all"),
+    ('\u{2201}', "complement"),
+    ('\u{2202}', "partial differential"),
+    ('\u{2203}', "there exists"),
+    ('\u{2204}', "there does not exist"),
+    ('\u{2205}', "empty set"),
+    ('\u{2206}', "increment"),
+    ('\u{2207}', "nabla"),
+    ('\u{2208}', "element of"),
+    ('\u{2209}', "not an element of"),
+    ('\u{220A}', "small element of"),
+    ('\u{220B}', "contains as member"),
+    ('\u{220C}', "does not contain as member"),
+    ('\u{220D}', "small contains as member"),
+    ('\u{220E}', "end of proof"),
+    ('\u{220F}', "n-ary product"),
+    ('\u{2210}', "n-ary coproduct"),
+    ('\u{2211}', "n-ary summation"),
+    ('\u{2212}', "minus sign"),
+    ('\u{2213}', "minus-or-plus sign"),
+    ('\u{2214}', "dot plus"),
+    ('\u{2215}', "division slash"),
+    ('\u{2216}', "set minus"),
+    ('\u{2217}', "asterisk operator"),
+    ('\u{2218}', "ring operator"),
+    ('\u{2219}', "bullet operator"),
+    ('\u{221A}', "square root"),
+    ('\u{221B}', "cube root"),
+    ('\u{221C}', "fourth root"),
+    ('\u{221D}', "proportional to"),
+    ('\u{221E}', "infinity"),
+    ('\u{221F}', "right angle"),
+    ('\u{2220}', "angle"),
+    ('\u{2221}', "measured angle"),
+    ('\u{2222}', "spherical angle"),
+    ('\u{2223}', "divides"),
+    ('\u{2224}', "does not divide"),
+    ('\u{2225}', "parallel to"),
+    ('\u{2226}', "not parallel to"),
+    ('\u{2227}', "logical and"),
+    ('\u{2228}', "logical or"),
+    ('\u{2229}', "intersection"),
+    ('\u{222A}', "union"),
+    ('\u{222B}', "integral"),
+    ('\u{222C}', "double integral"),
+    ('\u{222D}', "triple integral"),
+    ('\u{222E}', "contour integral"),
+    ('\u{222F}', "surface integral"),
+    ('\u{2230}', "volume integral"),
+    ('\u{2231}', "clockwise integral"),
+    ('\u{2232}', "clockwise contour integral"),
+    ('\u{2233}', "anticlockwise contour integral"),
+    ('\u{2234}', "therefore"),
+    ('\u{2235}', "because"),
+    ('\u{2236}', "ratio"),
+    ('\u{2237}', "proportion"),
+    ('\u{2238}', "dot minus"),
+    ('\u{2239}', "excess"),
+    ('\u{223A}', "geometric proportion"),
+    ('\u{223B}', "homothetic"),
+    ('\u{223C}', "tilde operator"),
+    ('\u{223D}', "reversed tilde"),
+    ('\u{223E}', "inverted lazy s"),
+    ('\u{223F}', "sine wave"),
+    ('\u{2240}', "wreath product"),
+    ('\u{2241}', "not tilde"),
+    ('\u{2242}', "minus tilde"),
+    ('\u{2243}', "asymptotically equal to"),
+    ('\u{2244}', "not asymptotically equal to"),
+    ('\u{2245}', "approximately equal to"),
+    ('\u{2246}', "approximately but not actually equal to"),
+    ('\u{2247}', "neither approximately nor actually equal to"),
+    ('\u{2248}', "almost equal to"),
+    ('\u{2249}', "not almost equal to"),
+    ('\u{224A}', "almost equal or equal to"),
+    ('\u{224B}', "triple tilde"),
+    ('\u{224C}', "all equal to"),
+    ('\u{224D}', "equivalent to"),
+    ('\u{224E}', "geometrically equivalent to"),
+    ('\u{224F}', "difference between"),
+    ('\u{2250}', "approaches the limit"),
+    ('\u{2251}', "geometrically equal to"),
+    ('\u{2252}', "approximately equal to or the image of"),
+    ('\u{2253}', "image of or approximately equal to"),
+    ('\u{2254}', "colon equals"),
+    ('\u{2255}', "equals colon"),
+    ('\u{2256}', "ring in equal to"),
+    ('\u{2257}', "ring equal to"),
+    ('\u{2258}', "corresponds to"),
+    ('\u{2259}', "estimates"),
+    ('\u{225A}', "equiangular to"),
+    ('\u{225B}', "star equals"),
+    ('\u{225C}', "delta equal to"),
+    ('\u{225D}', "equal to by definition"),
+    ('\u{225E}', "measured by"),
+    ('\u{225F}', "questioned equal to"),
+    ('\u{2260}', "not equal to"),
+    ('\u{2261}', "identical to"),
+    ('\u{2262}', "not identical to"),
+    ('\u{2263}', "strictly equivalent to"),
+    ('\u{2264}', "less-than or equal to"),
+    ('\u{2265}', "greater-than or equal to"),
+    ('\u{2266}', "less-than over equal to"),
+    ('\u{2267}', "greater-than over equal to"),
+    ('\u{2268}', "less-than but not equal to"),
+    ('\u{2269}', "greater-than but not equal to"),
+    ('\u{226A}', "much less-than"),
+    ('\u{226B}', "much greater-than"),
+    ('\u{226C}', "between"),
+    ('\u{226D}', "not equivalent to"),
+    ('\u{226E}', "not less-than"),
+    ('\u{226F}', "not greater-than"),
+    ('\u{2270}', "neither less-than nor equal to"),
+    ('\u{2271}', "neither greater-than nor equal to"),
+    ('\u{2272}', "less-than or equivalent to"),
+    ('\u{2273}', "greater-than or equivalent to"),
+    ('\u{2274}', "neither less-than nor equivalent to"),
+    ('\u{2275}', "neither greater-than nor equivalent to"),
+    ('\u{2276}', "less-than or greater-than"),
+    ('\u{2277}', "greater-than or less-than"),
+    ('\u{2278}', "neither less-than nor greater-than"),
+    ('\u{2279}', "neither greater-than nor less-than"),
+    ('\u{227A}', "precedes"),
+    ('\u{227B}', "succeeds"),
+    ('\u{227C}', "precedes or equal to"),
+    ('\u{227D}', "succeeds or equal to"),
+    ('\u{227E}', "precedes or equivalent to"),
+    ('\u{227F}', "succeeds or equivalent to"),
+    ('\u{2280}', "does not precede"),
+    ('\u{2281}', "does not succeed"),
+    ('\u{2282}', "subset of"),
+    ('\u{2283}', "superset of"),
+    ('\u{2284}', "not a subset of"),
+    ('\u{2285}', "not a superset of"),
+    ('\u{2286}', "subset of or equal to"),
+    ('\u{2287}', "superset of or equal to"),
+    ('\u{2288}', "neither a subset of nor equal to"),
+    ('\u{2289}', "neither a superset of nor equal to"),
+    ('\u{228A}', "subset of with not equal to"),
+    ('\u{228B}', "superset of with not equal to"),
+    ('\u{228C}', "multiset"),
+    ('\u{228D}', "multiset multiplication"),
+    ('\u{228E}', "multiset union"),
+    ('\u{228F}', "square image of"),
+    ('\u{2290}', "square original of"),
+    ('\u{2291}', "square image of or equal to"),
+    ('\u{2292}', "square original of or equal to"),
+    ('\u{2293}', "square cap"),
+    ('\u{2294}', "square cup"),
+    ('\u{2295}', "circled plus"),
+    ('\u{2296}', "circled minus"),
+    ('\u{2297}', "circled times"),
+    ('\u{2298}', "circled division slash"),
+    ('\u{2299}', "circled dot operator"),
+    ('\u{229A}', "circled ring operator"),
+    ('\u{229B}', "circled asterisk operator"),
+    ('\u{229C}', "circled equals"),
+    ('\u{229D}', "circled dash"),
+    ('\u{229E}', "squared plus"),
+    ('\u{229F}', "squared minus"),
+    ('\u{22A0}', "squared times"),
+    ('\u{22A1}', "squared dot operator"),
+    ('\u{22A2}', "right tack"),
+    ('\u{22A3}', "left tack"),
+    ('\u{22A4}', "down tack"),
+    ('\u{22A5}', "up tack"),
+    ('\u{22A6}', "assertion"),
+    ('\u{22A7}', "models"),
+    ('\u{22A8}', "true"),
+    ('\u{22A9}', "forces"),
+    ('\u{22AA}', "triple vertical bar right turnstile"),
+    ('\u{22AB}', "double vertical bar double right turnstile"),
+    ('\u{22AC}', "does not prove"),
+    ('\u{22AD}', "not true"),
+    ('\u{22AE}', "does not force"),
+    ('\u{22AF}', "negated double vertical bar double right turnstile"),
+    ('\u{22B0}', "precedes under relation"),
+    ('\u{22B1}', "succeeds under relation"),
+    ('\u{22B2}', "normal subgroup of"),
+    ('\u{22B3}', "contains as normal subgroup"),
+    ('\u{22B4}', "normal subgroup of or equal to"),
+    ('\u{22B5}', "contains as normal subgroup or equal to"),
+    ('\u{22B6}', "original of"),
+    ('\u{22B7}', "image of"),
+    ('\u{22B8}', "multimap"),
+    ('\u{22B9}', "hermitian conjugate matrix"),
+    ('\u{22BA}', "intercalate"),
+    ('\u{22BB}', "xor"),
+    ('\u{22BC}', "nand"),
+    ('\u{22BD}', "nor"),
+    ('\u{22BE}', "right angle with arc"),
+    ('\u{22BF}', "right triangle"),
+    ('\u{22C0}', "n-ary logical and"),
+    ('\u{22C1}', "n-ary logical or"),
+    ('\u{22C2}', "n-ary intersection"),
+    ('\u{22C3}', "n-ary union"),
+    ('\u{22C4}', "diamond operator"),
+    ('\u{22C5}', "dot operator"),
+    ('\u{22C6}', "star operator"),
+    ('\u{22C7}', "division times"),
+    ('\u{22C8}', "bowtie"),
+    ('\u{22C9}', "left normal factor semidirect product"),
+    ('\u{22CA}', "right normal factor semidirect product"),
+    ('\u{22CB}', "left semidirect product"),
+    ('\u{22CC}', "right semidirect product"),
+    ('\u{22CD}', "reversed tilde equals"),
+    ('\u{22CE}', "curly logical or"),
+    ('\u{22CF}', "curly logical and"),
+    ('\u{22D0}', "double subset"),
+    ('\u{22D1}', "double superset"),
+    ('\u{22D2}', "double intersection"),
+    ('\u{22D3}', "double union"),
+    ('\u{22D4}', "pitchfork"),
+    ('\u{22D5}', "equal and parallel to"),
+    ('\u{22D6}', "less-than with dot"),
+    ('\u{22D7}', "greater-than with dot"),
+    ('\u{22D8}', "very much less-than"),
+    ('\u{22D9}', "very much greater-than"),
+    ('\u{22DA}', "less-than equal to or greater-than"),
+    ('\u{22DB}', "greater-than equal to or less-than"),
+    ('\u{22DC}', "equal to or less-than"),
+    ('\u{22DD}', "equal to or greater-than"),
+    ('\u{22DE}', "equal to or precedes"),
+    ('\u{22DF}', "equal to or succeeds"),
+    ('\u{22E0}', "does not precede or equal"),
+    ('\u{22E1}', "does not succeed or equal"),
+    ('\u{22E2}', "not square image of or equal to"),
+    ('\u{22E3}', "not square original of or equal to"),
+    ('\u{22E4}', "square image of or not equal to"),
+    ('\u{22E5}', "square original of or not equal to"),
+    ('\u{22E6}', "less-than but not equivalent to"),
+    ('\u{22E7}', "greater-than but not equivalent to"),
+    ('\u{22E8}', "precedes but not equivalent to"),
+    ('\u{22E9}', "succeeds but not equivalent to"),
+    ('\u{22EA}', "not normal subgroup of"),
+    ('\u{22EB}', "does not contain as normal subgroup"),
+    ('\u{22EC}', "not normal subgroup of or equal to"),
+    ('\u{22ED}', "does not contain as normal subgroup or equal"),
+    ('\u{22EE}', "vertical ellipsis"),
+    ('\u{22EF}', "midline horizontal ellipsis"),
+    ('\u{22F0}', "up right diagonal ellipsis"),
+    ('\u{22F1}', "down right diagonal ellipsis"),
+    ('\u{22F2}', "element of with long horizontal stroke"),
+    ('\u{22F3}', "element of with vertical bar at end of horizontal stroke"),
+    ('\u{22F4}', "small element of with vertical bar at end of horizontal stroke"),
+    ('\u{22F5}', "element of with dot above"),
+    ('\u{22F6}', "element of with overbar"),
+    ('\u{22F7}', "small element of with overbar"),
+    ('\u{22F8}', "element of with underbar"),
+    ('\u{22F9}', "element of with two horizontal strokes"),
+    ('\u{22FA}', "contains with long horizontal stroke"),
+    ('\u{22FB}', "contains with vertical bar at end of horizontal stroke"),
+    ('\u{22FC}', "small contains with vertical bar at end of horizontal stroke"),
+    ('\u{22FD}', "contains with overbar"),
+    ('\u{22FE}', "small contains with overbar"),
+    ('\u{22FF}', "z notation bag membership"),
+    ('\u{27C0}', "three dimensional angle"),
+    ('\u{27C1}', "white triangle containing small white triangle"),
+    ('\u{27C2}', "perpendicular"),
+    ('\u{27C3}', "open subset"),
+    ('\u{27C4}', "open superset"),
+    ('\u{27C5}', "left s-shaped bag delimiter"),
+    ('\u{27C6}', "right s-shaped bag delimiter"),
+    ('\u{27C7}', "or with dot inside"),
+    ('\u{27C8}', "reverse solidus preceding subset"),
+    ('\u{27C9}', "superset preceding solidus"),
+    ('\u{27CA}', "vertical bar with horizontal stroke"),
+    ('\u{27CB}', "mathematical rising diagonal"),
+    ('\u{27CC}', "long division"),
+    ('\u{27CD}', "mathematical falling diagonal"),
+    ('\u{27CE}', "squared logical and"),
+    ('\u{27CF}', "squared logical or"),
+    ('\u{27D0}', "white diamond with centred dot"),
+    ('\u{27D1}', "and with dot"),
+    ('\u{27D2}', "element of opening upwards"),
+    ('\u{27D3}', "lower right corner with dot"),
+    ('\u{27D4}', "upper left corner with dot"),
+    ('\u{27D5}', "left outer join"),
+    ('\u{27D6}', "right outer join"),
+    ('\u{27D7}', "full outer join"),
+    ('\u{27D8}', "large up tack"),
+    ('\u{27D9}', "large down tack"),
+    ('\u{27DA}', "left and right double turnstile"),
+    ('\u{27DB}', "left and right tack"),
+    ('\u{27DC}', "left multimap"),
+    ('\u{27DD}', "long right tack"),
+    ('\u{27DE}', "long left tack"),
+    ('\u{27DF}', "up tack with circle above"),
+    ('\u{27E0}', "lozenge divided by horizontal rule"),
+    ('\u{27E1}', "white concave-sided diamond"),
+    ('\u{27E2}', "white concave-sided diamond with leftwards tick"),
+    ('\u{27E3}', "white concave-sided diamond with rightwards tick"),
+    ('\u{27E4}', "white square with leftwards tick"),
+    ('\u{27E5}', "white square with rightwards tick"),
+    ('\u{27E6}', "mathematical left white square bracket"),
+    ('\u{27E7}', "mathematical right white square bracket"),
+    ('\u{27E8}', "mathematical left angle bracket"),
+    ('\u{27E9}', "mathematical right angle bracket"),
+    ('\u{27EA}', "mathematical left double angle bracket"),
+    ('\u{27EB}', "mathematical right double angle bracket"),
+    ('\u{27EC}', "mathematical left white tortoise shell bracket"),
+    ('\u{27ED}', "mathematical right white tortoise shell bracket"),
+    ('\u{27EE}', "mathematical left flattened parenthesis"),
+    ('\u{27EF}', "mathematical right flattened parenthesis"),
+    ('\u{2980}', "triple vertical bar delimiter"),
+    ('\u{2981}', "z notation spot"),
+    ('\u{2982}', "z notation type colon"),
+    ('\u{2983}', "left white curly bracket"),
+    ('\u{2984}', "right white curly bracket"),
+    ('\u{2985}', "left white parenthesis"),
+    ('\u{2986}', "right white parenthesis"),
+    ('\u{2987}', "z notation left image bracket"),
+    ('\u{2988}', "z notation right image bracket"),
+    ('\u{2989}', "z notation left binding bracket"),
+    ('\u{298A}', "z notation right binding bracket"),
+    ('\u{298B}', "left square bracket with underbar"),
+    ('\u{298C}', "right square bracket with underbar"),
+    ('\u{298D}', "left square bracket with tick in top corner"),
+    ('\u{298E}', "right square bracket with tick in bottom corner"),
+    ('\u{298F}', "left square bracket with tick in bottom corner"),
+    ('\u{2990}', "right square bracket with tick in top corner"),
+    ('\u{2991}', "left angle bracket with dot"),
+    ('\u{2992}', "right angle bracket with dot"),
+    ('\u{2993}', "left arc less-than bracket"),
+    ('\u{2994}', "right arc greater-than bracket"),
+    ('\u{2995}', "double left arc greater-than bracket"),
+    ('\u{2996}', "double right arc less-than bracket"),
+    ('\u{2997}', "left black tortoise shell bracket"),
+    ('\u{2998}', "right black tortoise shell bracket"),
+    ('\u{2999}', "dotted fence"),
+    ('\u{299A}', "vertical zigzag line"),
+    ('\u{299B}', "measured angle opening left"),
+    ('\u{299C}', "right angle variant with square"),
+    ('\u{299D}', "measured right angle with dot"),
+    ('\u{299E}', "angle with s inside"),
+    ('\u{299F}', "acute angle"),
+    ('\u{29A0}', "spherical angle opening left"),
+    ('\u{29A1}', "spherical angle opening up"),
+    ('\u{29A2}', "turned angle"),
+    ('\u{29A3}', "reversed angle"),
+    ('\u{29A4}', "angle with underbar"),
+    ('\u{29A5}', "reversed angle with underbar"),
+    ('\u{29A6}', "oblique angle opening up"),
+    ('\u{29A7}', "oblique angle opening down"),
+    ('\u{29A8}', "measured angle with open arm ending in arrow pointing up and right"),
+    ('\u{29A9}', "measured angle with open arm ending in arrow pointing up and left"),
+    ('\u{29AA}', "measured angle with open arm ending in arrow pointing down and right"),
+    ('\u{29AB}', "measured angle with open arm ending in arrow pointing down and left"),
+    ('\u{29AC}', "measured angle with open arm ending in arrow pointing right and up"),
+    ('\u{29AD}', "measured angle with open arm ending in arrow pointing left and up"),
+    ('\u{29AE}', "measured angle with open arm ending in arrow pointing right and down"),
+    ('\u{29AF}', "measured angle with open arm ending in arrow pointing left and down"),
+    ('\u{29B0}', "reversed empty set"),
+    ('\u{29B1}', "empty set with overbar"),
+    ('\u{29B2}', "empty set with small circle above"),
+    ('\u{29B3}', "empty set with right arrow above"),
+    ('\u{29B4}', "empty set with left arrow above"),
+    ('\u{29B5}', "circle with horizontal bar"),
+    ('\u{29B6}', "circled vertical bar"),
+    ('\u{29B7}', "circled parallel"),
+    ('\u{29B8}', "circled reverse solidus"),
+    ('\u{29B9}', "circled perpendicular"),
+    ('\u{29BA}', "circle divided by horizontal bar and top half divided by vertical bar"),
+    ('\u{29BB}', "circle with superimposed x"),
+    ('\u{29BC}', "circled anticlockwise-rotated division sign"),
+    ('\u{29BD}', "up arrow through circle"),
+    ('\u{29BE}', "circled white bullet"),
+    ('\u{29BF}', "circled bullet"),
+    ('\u{29C0}', "circled less-than"),
+    ('\u{29C1}', "circled greater-than"),
+    ('\u{29C2}', "circle with small circle to the right"),
+    ('\u{29C3}', "circle with two horizontal strokes to the right"),
+    ('\u{29C4}', "squared rising diagonal slash"),
+    ('\u{29C5}', "squared falling diagonal slash"),
+    ('\u{29C6}', "squared asterisk"),
+    ('\u{29C7}', "squared small circle"),
+    ('\u{29C8}', "squared square"),
+    ('\u{29C9}', "two joined squares"),
+    ('\u{29CA}', "triangle with dot above"),
+    ('\u{29CB}', "triangle with underbar"),
+    ('\u{29CC}', "s in triangle"),
+    ('\u{29CD}', "triangle with serifs at bottom"),
+    ('\u{29CE}', "right triangle above left triangle"),
+    ('\u{29CF}', "left triangle beside vertical bar"),
+    ('\u{29D0}', "vertical bar beside right triangle"),
+    ('\u{29D1}', "bowtie with left half black"),
+    ('\u{29D2}', "bowtie with right half black"),
+    ('\u{29D3}', "black bowtie"),
+    ('\u{29D4}', "times with left half black"),
+    ('\u{29D5}', "times with right half black"),
+    ('\u{29D6}', "white hourglass"),
+    ('\u{29D7}', "black hourglass"),
+    ('\u{29D8}', "left wiggly fence"),
+    ('\u{29D9}', "right wiggly fence"),
+    ('\u{29DA}', "left double wiggly fence"),
+    ('\u{29DB}', "right double wiggly fence"),
+    ('\u{29DC}', "incomplete infinity"),
+    ('\u{29DD}', "tie over infinity"),
+    ('\u{29DE}', "infinity negated with vertical bar"),
+    ('\u{29DF}', "double-ended multimap"),
+    ('\u{29E0}', "square with contoured outline"),
+    ('\u{29E1}', "increases as"),
+    ('\u{29E2}', "shuffle product"),
+    ('\u{29E3}', "equals sign and slanted parallel"),
+    ('\u{29E4}', "equals sign and slanted parallel with tilde above"),
+    ('\u{29E5}', "identical to and slanted parallel"),
+    ('\u{29E6}', "gleich stark"),
+    ('\u{29E7}', "thermodynamic"),
+    ('\u{29E8}', "down-pointing triangle with left half black"),
+    ('\u{29E9}', "down-pointing triangle with right half black"),
+    ('\u{29EA}', "black diamond with down arrow"),
+    ('\u{29EB}', "black lozenge"),
+    ('\u{29EC}', "white circle with down arrow"),
+    ('\u{29ED}', "black circle with down arrow"),
+    ('\u{29EE}', "error-barred white square"),
+    ('\u{29EF}', "error-barred black square"),
+    ('\u{29F0}', "error-barred white diamond"),
+    ('\u{29F1}', "error-barred black diamond"),
+    ('\u{29F2}', "error-barred white circle"),
+    ('\u{29F3}', "error-barred black circle"),
+    ('\u{29F4}', "rule-delayed"),
+    ('\u{29F5}', "reverse solidus operator"),
+    ('\u{29F6}', "solidus with overbar"),
+    ('\u{29F7}', "reverse solidus with horizontal stroke"),
+    ('\u{29F8}', "big solidus"),
+    ('\u{29F9}', "big reverse solidus"),
+    ('\u{29FA}', "double plus"),
+    ('\u{29FB}', "triple plus"),
+    ('\u{29FC}', "left-pointing curved angle bracket"),
+    ('\u{29FD}', "right-pointing curved angle bracket"),
+    ('\u{29FE}', "tiny"),
+    ('\u{29FF}', "miny"),
+];
+
+pub const TECHNICAL: &[(char, &str)] = &[
+    ('\u{2300}', "diameter sign"),
+    ('\u{2301}', "electric arrow"),
+    ('\u{2302}', "house"),
+    ('\u{2303}', "up arrowhead"),
+    ('\u{2304}', "down arrowhead"),
+    ('\u{2305}', "projective"),
+    ('\u{2306}', "perspective"),
+    ('\u{2307}', "wavy line"),
+    ('\u{2308}', "left ceiling"),
+    ('\u{2309}', "right ceiling"),
+    ('\u{230A}', "left floor"),
+    ('\u{230B}', "right floor"),
+    ('\u{230C}', "bottom right crop"),
+    ('\u{230D}', "bottom left crop"),
+    ('\u{230E}', "top right crop"),
+    ('\u{230F}', "top left crop"),
+    ('\u{2310}', "reversed not sign"),
+    ('\u{2311}', "square lozenge"),
+    ('\u{2312}', "arc"),
+    ('\u{2313}', "segment"),
+    ('\u{2314}', "sector"),
+    ('\u{2315}', "telephone recorder"),
+    ('\u{2316}', "position indicator"),
+    ('\u{2317}', "viewdata square"),
+    ('\u{2318}', "place of interest sign"),
+    ('\u{2319}', "turned not sign"),
+    ('\u{231A}', "watch"),
+    ('\u{231B}', "hourglass"),
+    ('\u{231C}', "top left corner"),
+    ('\u{231D}', "top right corner"),
+    ('\u{231E}', "bottom left corner"),
+    ('\u{231F}', "bottom right corner"),
+    ('\u{2320}', "top half integral"),
+    ('\u{2321}', "bottom half integral"),
+    ('\u{2322}', "frown"),
+    ('\u{2323}', "smile"),
+    ('\u{2324}', "up arrowhead between two horizontal bars"),
+    ('\u{2325}', "option key"),
+    ('\u{2326}', "erase to the right"),
+    ('\u{2327}', "x in a rectangle box"),
+    ('\u{2328}', "keyboard"),
+    ('\u{2329}', "left-pointing angle bracket"),
+    ('\u{232A}', "right-pointing angle bracket"),
+    ('\u{232B}', "erase to the left"),
+    ('\u{232C}', "benzene ring"),
+    ('\u{232D}', "cylindricity"),
+    ('\u{232E}', "all around-profile"),
+    ('\u{232F}', "symmetry"),
+    ('\u{2330}', "total runout"),
+    ('\u{2331}', "dimension origin"),
+    ('\u{2332}', "conical taper"),
+    ('\u{2333}', "slope"),
+    ('\u{2334}', "counterbore"),
+    ('\u{2335}', "countersink"),
+    ('\u{2336}', "apl functional symbol i-beam"),
+    ('\u{2337}', "apl functional symbol squish quad"),
+    ('\u{2338}', "apl functional symbol quad equal"),
+    ('\u{2339}', "apl functional symbol quad divide"),
+    ('\u{233A}', "apl functional symbol quad diamond"),
+    ('\u{233B}', "apl functional symbol quad jot"),
+    ('\u{233C}', "apl functional symbol quad circle"),
+    ('\u{233D}', "apl functional symbol circle stile"),
+    ('\u{233E}', "apl functional symbol circle jot"),
+    ('\u{233F}', "apl functional symbol slash bar"),
+    ('\u{2340}', "apl functional symbol backslash bar"),
+    ('\u{2341}', "apl functional symbol quad slash"),
+    ('\u{2342}', "apl functional symbol quad backslash"),
+    ('\u{2343}', "apl functional symbol quad less-than"),
+    ('\u{2344}', "apl functional symbol quad greater-than"),
+    ('\u{2345}', "apl functional symbol leftwards vane"),
+    ('\u{2346}', "apl functional symbol rightwards vane"),
+    ('\u{2347}', "apl functional symbol quad leftwards arrow"),
+    ('\u{2348}', "apl functional symbol quad rightwards arrow"),
+    ('\u{2349}', "apl functional symbol circle backslash"),
+    ('\u{234A}', "apl functional symbol down tack underbar"),
+    ('\u{234B}', "apl functional symbol delta stile"),
+    ('\u{234C}', "apl functional symbol quad down caret"),
+    ('\u{234D}', "apl functional symbol quad delta"),
+    ('\u{234E}', "apl functional symbol down tack jot"),
+    ('\u{234F}', "apl functional symbol upwards vane"),
+    ('\u{2350}', "apl functional symbol quad upwards arrow"),
+    ('\u{2351}', "apl functional symbol up tack overbar"),
+    ('\u{2352}', "apl functional symbol del stile"),
+    ('\u{2353}', "apl functional symbol quad up caret"),
+    ('\u{2354}', "apl functional symbol quad del"),
+    ('\u{2355}', "apl functional symbol up tack jot"),
+    ('\u{2356}', "apl functional symbol downwards vane"),
+    ('\u{2357}', "apl functional symbol quad downwards arrow"),
+    ('\u{2358}', "apl functional symbol quote underbar"),
+    ('\u{2359}', "apl functional symbol delta underbar"),
+    ('\u{235A}', "apl functional symbol diamond underbar"),
+    ('\u{235B}', "apl functional symbol jot underbar"),
+    ('\u{235C}', "apl functional symbol circle underbar"),
+    ('\u{235D}', "apl functional symbol up shoe jot"),
+    ('\u{235E}', "apl functional symbol quote quad"),
+    ('\u{235F}', "apl functional symbol circle star"),
+    ('\u{2360}', "apl functional symbol quad colon"),
+    ('\u{2361}', "apl functional symbol up tack diaeresis"),
+    ('\u{2362}', "apl functional symbol del diaeresis"),
+    ('\u{2363}', "apl functional symbol star diaeresis"),
+    ('\u{2364}', "apl functional symbol jot diaeresis"),
+    ('\u{2365}', "apl functional symbol circle diaeresis"),
+    ('\u{2366}', "apl functional symbol down shoe stile"),
+    ('\u{2367}', "apl functional symbol left shoe stile"),
+    ('\u{2368}', "apl functional symbol tilde diaeresis"),
+    ('\u{2369}', "apl functional symbol greater-than diaeresis"),
+    ('\u{236A}', "apl functional symbol comma bar"),
+    ('\u{236B}', "apl functional symbol del tilde"),
+    ('\u{236C}', "apl functional symbol zilde"),
+    ('\u{236D}', "apl functional symbol stile tilde"),
+    ('\u{236E}', "apl functional symbol semicolon underbar"),
+    ('\u{236F}', "apl functional symbol quad not equal"),
+    ('\u{2370}', "apl functional symbol quad question"),
+    ('\u{2371}', "apl functional symbol down caret tilde"),
+    ('\u{2372}', "apl functional symbol up caret tilde"),
+    ('\u{2373}', "apl functional symbol iota"),
+    ('\u{2374}', "apl functional symbol rho"),
+    ('\u{2375}', "apl functional symbol omega"),
+    ('\u{2376}', "apl functional symbol alpha underbar"),
+    ('\u{2377}', "apl functional symbol epsilon underbar"),
+    ('\u{2378}', "apl functional symbol iota underbar"),
+    ('\u{2379}', "apl functional symbol omega underbar"),
+    ('\u{237A}', "apl functional symbol alpha"),
+    ('\u{237B}', "not check mark"),
+    ('\u{237C}', "right angle with downwards zigzag arrow"),
+    ('\u{237D}', "shouldered open box"),
+    ('\u{237E}', "bell symbol"),
+    ('\u{237F}', "vertical line with middle dot"),
+    ('\u{2380}', "insertion symbol"),
+    ('\u{2381}', "continuous underline symbol"),
+    ('\u{2382}', "discontinuous underline symbol"),
+    ('\u{2383}', "emphasis symbol"),
+    ('\u{2384}', "composition symbol"),
+    ('\u{2385}', "white square with centre vertical line"),
+    ('\u{2386}', "enter symbol"),
+    ('\u{2387}', "alternative key symbol"),
+    ('\u{2388}', "helm symbol"),
+    ('\u{2389}', "circled horizontal bar with notch"),
+    ('\u{238A}', "circled triangle down"),
+    ('\u{238B}', "broken circle with northwest arrow"),
+    ('\u{238C}', "undo symbol"),
+    ('\u{238D}', "monostable symbol"),
+    ('\u{238E}', "hysteresis symbol"),
+    ('\u{238F}', "open-circuit-output h-type symbol"),
+    ('\u{2390}', "open-circuit-output l-type symbol"),
+    ('\u{2391}', "passive-pull-down-output symbol"),
+    ('\u{2392}', "passive-pull-up-output symbol"),
+    ('\u{2393}', "direct current symbol form two"),
+    ('\u{2394}', "software-function symbol"),
+    ('\u{2395}', "apl functional symbol quad"),
+    ('\u{2396}', "decimal separator key symbol"),
+    ('\u{2397}', "previous page"),
+    ('\u{2398}', "next page"),
+    ('\u{2399}', "print screen symbol"),
+    ('\u{239A}', "clear screen symbol"),
+    ('\u{239B}', "left parenthesis upper hook"),
+    ('\u{239C}', "left parenthesis extension"),
+    ('\u{239D}', "left parenthesis lower hook"),
+    ('\u{239E}', "right parenthesis upper hook"),
+    ('\u{239F}', "right parenthesis extension"),
+    ('\u{23A0}', "right parenthesis lower hook"),
+    ('\u{23A1}', "left square bracket upper corner"),
+    ('\u{23A2}', "left square bracket extension"),
+    ('\u{23A3}', "left square bracket lower corner"),
+    ('\u{23A4}', "right square bracket upper corner"),
+    ('\u{23A5}', "right square bracket extension"),
+    ('\u{23A6}', "right square bracket lower corner"),
+    ('\u{23A7}', "left curly bracket upper hook"),
+    ('\u{23A8}', "left curly bracket middle piece"),
+    ('\u{23A9}', "left curly bracket lower hook"),
+    ('\u{23AA}', "curly bracket extension"),
+    ('\u{23AB}', "right curly bracket upper hook"),
+    ('\u{23AC}', "right curly bracket middle piece"),
+    ('\u{23AD}', "right curly bracket lower hook"),
+    ('\u{23AE}', "integral extension"),
+    ('\u{23AF}', "horizontal line extension"),
+    ('\u{23B0}', "upper left or lower right curly bracket section"),
+    ('\u{23B1}', "upper right or lower left curly bracket section"),
+    ('\u{23B2}', "summation top"),
+    ('\u{23B3}', "summation bottom"),
+    ('\u{23B4}', "top square bracket"),
+    ('\u{23B5}', "bottom square bracket"),
+    ('\u{23B6}', "bottom square bracket over top square bracket"),
+    ('\u{23B7}', "radical symbol bottom"),
+    ('\u{23B8}', "left vertical box line"),
+    ('\u{23B9}', "right vertical box line"),
+    ('\u{23BA}', "horizontal scan line-1"),
+    ('\u{23BB}', "horizontal scan line-3"),
+    ('\u{23BC}', "horizontal scan line-7"),
+    ('\u{23BD}', "horizontal scan line-9"),
+    ('\u{23BE}', "dentistry symbol light vertical and top right"),
+    ('\u{23BF}', "dentistry symbol light vertical and bottom right"),
+    ('\u{23C0}', "dentistry symbol light vertical with circle"),
+    ('\u{23C1}', "dentistry symbol light down and horizontal with circle"),
+    ('\u{23C2}', "dentistry symbol light up and horizontal with circle"),
+    ('\u{23C3}', "dentistry symbol light vertical with triangle"),
+    ('\u{23C4}', "dentistry symbol light down and horizontal with triangle"),
+    ('\u{23C5}', "dentistry symbol light up and horizontal with triangle"),
+    ('\u{23C6}', "dentistry symbol light vertical and wave"),
+    ('\u{23C7}', "dentistry symbol light down and horizontal with wave"),
+    ('\u{23C8}', "dentistry symbol light up and horizontal with wave"),
+    ('\u{23C9}', "dentistry symbol light down and horizontal"),
+    ('\u{23CA}', "dentistry symbol light up and horizontal"),
+    ('\u{23CB}', "dentistry symbol light vertical and top left"),
+    ('\u{23CC}', "dentistry symbol light vertical and bottom left"),
+    ('\u{23CD}', "square foot"),
+    ('\u{23CE}', "return symbol"),
+    ('\u{23CF}', "eject symbol"),
+    ('\u{23D0}', "vertical line extension"),
+    ('\u{23D1}', "metrical breve"),
+    ('\u{23D2}', "metrical long over short"),
+    ('\u{23D3}', "metrical short over long"),
+    ('\u{23D4}', "metrical long over two shorts"),
+    ('\u{23D5}', "metrical two shorts over long"),
+    ('\u{23D6}', "metrical two shorts joined"),
+    ('\u{23D7}', "metrical triseme"),
+    ('\u{23D8}', "metrical tetraseme"),
+    ('\u{23D9}', "metrical pentaseme"),
+    ('\u{23DA}', "earth ground"),
+    ('\u{23DB}', "fuse"),
+    ('\u{23DC}', "top parenthesis"),
+    ('\u{23DD}', "bottom parenthesis"),
+    ('\u{23DE}', "top curly bracket"),
+    ('\u{23DF}', "bottom curly bracket"),
+    ('\u{23E0}', "top tortoise shell bracket"),
+    ('\u{23E1}', "bottom tortoise shell bracket"),
+    ('\u{23E2}', "white trapezium"),
+    ('\u{23E3}', "benzene ring with circle"),
+    ('\u{23E4}', "straightness"),
+    ('\u{23E5}', "flatness"),
+    ('\u{23E6}', "ac current"),
+    ('\u{23E7}', "electrical intersection"),
+    ('\u{23E8}', "decimal exponent symbol"),
+    ('\u{23E9}', "black right-pointing double triangle"),
+    ('\u{23EA}', "black left-pointing double triangle"),
+    ('\u{23EB}', "black up-pointing double triangle"),
+    ('\u{23EC}', "black down-pointing double triangle"),
+    ('\u{23ED}', "black right-pointing double triangle with vertical bar"),
+    ('\u{23EE}', "black left-pointing double triangle with vertical bar"),
+    ('\u{23EF}', "black right-pointing triangle with double vertical bar"),
+    ('\u{23F0}', "alarm clock"),
+    ('\u{23F1}', "stopwatch"),
+    ('\u{23F2}', "timer clock"),
+    ('\u{23F3}', "hourglass with flowing sand"),
+    ('\u{23F4}', "black medium left-pointing triangle"),
+    ('\u{23F5}', "black medium right-pointing triangle"),
+    ('\u{23F6}', "black medium up-pointing triangle"),
+    ('\u{23F7}', "black medium down-pointing triangle"),
+    ('\u{23F8}', "double vertical bar"),
+    ('\u{23F9}', "black square for stop"),
+    ('\u{23FA}', "black circle for record"),
+    ('\u{23FB}', "power symbol"),
+    ('\u{23FC}', "power on-off symbol"),
+    ('\u{23FD}', "power on symbol"),
+    ('\u{23FE}', "power sleep symbol"),
+    ('\u{23FF}', "observer eye symbol"),
+];
+
+pub const BOX_DRAWING: &[(char, &str)] = &[
+    ('\u{2500}', "box drawings light horizontal"),
+    ('\u{2501}', "box drawings heavy horizontal"),
+    ('\u{2502}', "box drawings light vertical"),
+    ('\u{2503}', "box drawings heavy vertical"),
+    ('\u{2504}', "box drawings light triple dash horizontal"),
+    ('\u{2505}', "box drawings heavy triple dash horizontal"),
+    ('\u{2506}', "box drawings light triple dash vertical"),
+    ('\u{2507}', "box drawings heavy triple dash vertical"),
+    ('\u{2508}', "box drawings light quadruple dash horizontal"),
+    ('\u{2509}', "box drawings heavy quadruple dash horizontal"),
+    ('\u{250A}', "box drawings light quadruple dash vertical"),
+    ('\u{250B}', "box drawings heavy quadruple dash vertical"),
+    ('\u{250C}', "box drawings light down and right"),
+    ('\u{250D}', "box drawings down light and right heavy"),
+    ('\u{250E}', "box drawings down heavy and right light"),
+    ('\u{250F}', "box drawings heavy down and right"),
+    ('\u{2510}', "box drawings light down and left"),
+    ('\u{2511}', "box drawings down light and left heavy"),
+    ('\u{2512}', "box drawings down heavy and left light"),
+    ('\u{2513}', "box drawings heavy down and left"),
+    ('\u{2514}', "box drawings light up and right"),
+    ('\u{2515}', "box drawings up light and right heavy"),
+    ('\u{2516}', "box drawings up heavy and right light"),
+    ('\u{2517}', "box drawings heavy up and right"),
+    ('\u{2518}', "box drawings light up and left"),
+    ('\u{2519}', "box drawings up light and left heavy"),
+    ('\u{251A}', "box drawings up heavy and left light"),
+    ('\u{251B}', "box drawings heavy up and left"),
+    ('\u{251C}', "box drawings light vertical and right"),
+    ('\u{251D}', "box drawings vertical light and right heavy"),
+    ('\u{251E}', "box drawings up heavy and right down light"),
+    ('\u{251F}', "box drawings down heavy and right up light"),
+    ('\u{2520}', "box drawings vertical heavy and right light"),
+    ('\u{2521}', "box drawings down light and right up heavy"),
+    ('\u{2522}', "box drawings up light and right down heavy"),
+    ('\u{2523}', "box drawings heavy vertical and right"),
+    ('\u{2524}', "box drawings light vertical and left"),
+    ('\u{2525}', "box drawings vertical light and left heavy"),
+    ('\u{2526}', "box drawings up heavy and left down light"),
+    ('\u{2527}', "box drawings down heavy and left up light"),
+    ('\u{2528}', "box drawings vertical heavy and left light"),
+    ('\u{2529}', "box drawings down light and left up heavy"),
+    ('\u{252A}', "box drawings up light and left down heavy"),
+    ('\u{252B}', "box drawings heavy vertical and left"),
+    ('\u{252C}', "box drawings light down and horizontal"),
+    ('\u{252D}', "box drawings left heavy and right down light"),
+    ('\u{252E}', "box drawings right heavy and left down light"),
+    ('\u{252F}', "box drawings down light and horizontal heavy"),
+    ('\u{2530}', "box drawings down heavy and horizontal light"),
+    ('\u{2531}', "box drawings right light and left down heavy"),
+    ('\u{2532}', "box drawings left light and right down heavy"),
+    ('\u{2533}', "box drawings heavy down and horizontal"),
+    ('\u{2534}', "box drawings light up and horizontal"),
+    ('\u{2535}', "box drawings left heavy and right up light"),
+    ('\u{2536}', "box drawings right heavy and left up light"),
+    ('\u{2537}', "box drawings up light and horizontal heavy"),
+    ('\u{2538}', "box drawings up heavy and horizontal light"),
+    ('\u{2539}', "box drawings right light and left up heavy"),
+    ('\u{253A}', "box drawings left light and right up heavy"),
+    ('\u{253B}', "box drawings heavy up and horizontal"),
+    ('\u{253C}', "box drawings light vertical and horizontal"),
+    ('\u{253D}', "box drawings left heavy and right vertical light"),
+    ('\u{253E}', "box drawings right heavy and left vertical light"),
+    ('\u{253F}', "box drawings vertical light and horizontal heavy"),
+    ('\u{2540}', "box drawings up heavy and down horizontal light"),
+    ('\u{2541}', "box drawings down heavy and up horizontal light"),
+    ('\u{2542}', "box drawings vertical heavy and horizontal light"),
+    ('\u{2543}', "box drawings left up heavy and right down light"),
+    ('\u{2544}', "box drawings right up heavy and left down light"),
+    ('\u{2545}', "box drawings left down heavy and right up light"),
+    ('\u{2546}', "box drawings right down heavy and left up light"),
+    ('\u{2547}', "box drawings down light and up horizontal heavy"),
+    ('\u{2548}', "box drawings up light and down horizontal heavy"),
+    ('\u{2549}', "box drawings right light and left vertical heavy"),
+    ('\u{254A}', "box drawings left light and right vertical heavy"),
+    ('\u{254B}', "box drawings heavy vertical and horizontal"),
+    ('\u{254C}', "box drawings light double dash horizontal"),
+    ('\u{254D}', "box drawings heavy double dash horizontal"),
+    ('\u{254E}', "box drawings light double dash vertical"),
+    ('\u{254F}', "box drawings heavy double dash vertical"),
+    ('\u{2550}', "box drawings double horizontal"),
+    ('\u{2551}', "box drawings double vertical"),
+    ('\u{2552}', "box drawings down single and right double"),
+    ('\u{2553}', "box drawings down double and right single"),
+    ('\u{2554}', "box drawings double down and right"),
+    ('\u{2555}', "box drawings down single and left double"),
+    ('\u{2556}', "box drawings down double and left single"),
+    ('\u{2557}', "box drawings double down and left"),
+    ('\u{2558}', "box drawings up single and right double"),
+    ('\u{2559}', "box drawings up double and right single"),
+    ('\u{255A}', "box drawings double up and right"),
+    ('\u{255B}', "box drawings up single and left double"),
+    ('\u{255C}', "box drawings up double and left single"),
+    ('\u{255D}', "box drawings double up and left"),
+    ('\u{255E}', "box drawings vertical single and right double"),
+    ('\u{255F}', "box drawings vertical double and right single"),
+    ('\u{2560}', "box drawings double vertical and right"),
+    ('\u{2561}', "box drawings vertical single and left double"),
+    ('\u{2562}', "box drawings vertical double and left single"),
+    ('\u{2563}', "box drawings double vertical and left"),
+    ('\u{2564}', "box drawings down single and horizontal double"),
+    ('\u{2565}', "box drawings down double and horizontal single"),
+    ('\u{2566}', "box drawings double down and horizontal"),
+    ('\u{2567}', "box drawings up single and horizontal double"),
+    ('\u{2568}', "box drawings up double and horizontal single"),
+    ('\u{2569}', "box drawings double up and horizontal"),
+    ('\u{256A}', "box drawings vertical single and horizontal double"),
+    ('\u{256B}', "box drawings vertical double and horizontal single"),
+    ('\u{256C}', "box drawings double vertical and horizontal"),
+    ('\u{256D}', "box drawings light arc down and right"),
+    ('\u{256E}', "box drawings light arc down and left"),
+    ('\u{256F}', "box drawings light arc up and left"),
+    ('\u{2570}', "box drawings light arc up and right"),
+    ('\u{2571}', "box drawings light diagonal upper right to lower left"),
+    ('\u{2572}', "box drawings light diagonal upper left to lower right"),
+    ('\u{2573}', "box drawings light diagonal cross"),
+    ('\u{2574}', "box drawings light left"),
+    ('\u{2575}', "box drawings light up"),
+    ('\u{2576}', "box drawings light right"),
+    ('\u{2577}', "box drawings light down"),
+    ('\u{2578}', "box drawings heavy left"),
+    ('\u{2579}', "box drawings heavy up"),
+    ('\u{257A}', "box drawings heavy right"),
+    ('\u{257B}', "box drawings heavy down"),
+    ('\u{257C}', "box drawings light left and heavy right"),
+    ('\u{257D}', "box drawings light up and heavy down"),
+    ('\u{257E}', "box drawings heavy left and light right"),
+    ('\u{257F}', "box drawings heavy up and light down"),
+    ('\u{2580}', "upper half block"),
+    ('\u{2581}', "lower one eighth block"),
+    ('\u{2582}', "lower one quarter block"),
+    ('\u{2583}', "lower three eighths block"),
+    ('\u{2584}', "lower half block"),
+    ('\u{2585}', "lower five eighths block"),
+    ('\u{2586}', "lower three quarters block"),
+    ('\u{2587}', "lower seven eighths block"),
+    ('\u{2588}', "full block"),
+    ('\u{2589}', "left seven eighths block"),
+    ('\u{258A}', "left three quarters block"),
+    ('\u{258B}', "left five eighths block"),
+    ('\u{258C}', "left half block"),
+    ('\u{258D}', "left three eighths block"),
+    ('\u{258E}', "left one quarter block"),
+    ('\u{258F}', "left one eighth block"),
+    ('\u{2590}', "right half block"),
+    ('\u{2591}', "light shade"),
+    ('\u{2592}', "medium shade"),
+    ('\u{2593}', "dark shade"),
+    ('\u{2594}', "upper one eighth block"),
+    ('\u{2595}', "right one eighth block"),
+    ('\u{2596}', "quadrant lower left"),
+    ('\u{2597}', "quadrant lower right"),
+    ('\u{2598}', "quadrant upper left"),
+    ('\u{2599}', "quadrant upper left and lower left and lower right"),
+    ('\u{259A}', "quadrant upper left and lower right"),
+    ('\u{259B}', "quadrant upper left and upper right and lower left"),
+    ('\u{259C}', "quadrant upper left and upper right and lower right"),
+    ('\u{259D}', "quadrant upper right"),
+    ('\u{259E}', "quadrant upper right and lower left"),
+    ('\u{259F}', "quadrant upper right and lower left and lower right"),
+];
+
+pub const GEOMETRIC_SHAPES: &[(char, &str)] = &[
+    ('\u{25A0}', "black square"),
+    ('\u{25A1}', "white square"),
+    ('\u{25A2}', "white square with rounded corners"),
+    ('\u{25A3}', "white square containing black small square"),
+    ('\u{25A4}', "square with horizontal fill"),
+    ('\u{25A5}', "square with vertical fill"),
+    ('\u{25A6}', "square with orthogonal crosshatch fill"),
+    ('\u{25A7}', "square with upper left to lower right fill"),
+    ('\u{25A8}', "square with upper right to lower left fill"),
+    ('\u{25A9}', "square with diagonal crosshatch fill"),
+    ('\u{25AA}', "black small square"),
+    ('\u{25AB}', "white small square"),
+    ('\u{25AC}', "black rectangle"),
+    ('\u{25AD}', "white rectangle"),
+    ('\u{25AE}', "black vertical rectangle"),
+    ('\u{25AF}', "white vertical rectangle"),
+    ('\u{25B0}', "black parallelogram"),
+    ('\u{25B1}', "white parallelogram"),
+    ('\u{25B2}', "black up-pointing triangle"),
+    ('\u{25B3}', "white up-pointing triangle"),
+    ('\u{25B4}', "black up-pointing small triangle"),
+    ('\u{25B5}', "white up-pointing small triangle"),
+    ('\u{25B6}', "black right-pointing triangle"),
+    ('\u{25B7}', "white right-pointing triangle"),
+    ('\u{25B8}', "black right-pointing small triangle"),
+    ('\u{25B9}', "white right-pointing small triangle"),
+    ('\u{25BA}', "black right-pointing pointer"),
+    ('\u{25BB}', "white right-pointing pointer"),
+    ('\u{25BC}', "black down-pointing triangle"),
+    ('\u{25BD}', "white down-pointing triangle"),
+    ('\u{25BE}', "black down-pointing small triangle"),
+    ('\u{25BF}', "white down-pointing small triangle"),
+    ('\u{25C0}', "black left-pointing triangle"),
+    ('\u{25C1}', "white left-pointing triangle"),
+    ('\u{25C2}', "black left-pointing small triangle"),
+    ('\u{25C3}', "white left-pointing small triangle"),
+    ('\u{25C4}', "black left-pointing pointer"),
+    ('\u{25C5}', "white left-pointing pointer"),
+    ('\u{25C6}', "black diamond"),
+    ('\u{25C7}', "white diamond"),
+    ('\u{25C8}', "white diamond containing black small diamond"),
+    ('\u{25C9}', "fisheye"),
+    ('\u{25CA}', "lozenge"),
+    ('\u{25CB}', "white circle"),
+    ('\u{25CC}', "dotted circle"),
+    ('\u{25CD}', "circle with vertical fill"),
+    ('\u{25CE}', "bullseye"),
+    ('\u{25CF}', "black circle"),
+    ('\u{25D0}', "circle with left half black"),
+    ('\u{25D1}', "circle with right half black"),
+    ('\u{25D2}', "circle with lower half black"),
+    ('\u{25D3}', "circle with upper half black"),
+    ('\u{25D4}', "circle with upper right quadrant black"),
+    ('\u{25D5}', "circle with all but upper left quadrant black"),
+    ('\u{25D6}', "left half black circle"),
+    ('\u{25D7}', "right half black circle"),
+    ('\u{25D8}', "inverse bullet"),
+    ('\u{25D9}', "inverse white circle"),
+    ('\u{25DA}', "upper half inverse white circle"),
+    ('\u{25DB}', "lower half inverse white circle"),
+    ('\u{25DC}', "upper left quadrant circular arc"),
+    ('\u{25DD}', "upper right quadrant circular arc"),
+    ('\u{25DE}', "lower right quadrant circular arc"),
+    ('\u{25DF}', "lower left quadrant circular arc"),
+    ('\u{25E0}', "upper half circle"),
+    ('\u{25E1}', "lower half circle"),
+    ('\u{25E2}', "black lower right triangle"),
+    ('\u{25E3}', "black lower left triangle"),
+    ('\u{25E4}', "black upper left triangle"),
+    ('\u{25E5}', "black upper right triangle"),
+    ('\u{25E6}', "white bullet"),
+    ('\u{25E7}', "square with left half black"),
+    ('\u{25E8}', "square with right half black"),
+    ('\u{25E9}', "square with upper left diagonal half black"),
+    ('\u{25EA}', "square with lower right diagonal half black"),
+    ('\u{25EB}', "white square with vertical bisecting line"),
+    ('\u{25EC}', "white up-pointing triangle with dot"),
+    ('\u{25ED}', "up-pointing triangle with left half black"),
+    ('\u{25EE}', "up-pointing triangle with right half black"),
+    ('\u{25EF}', "large circle"),
+    ('\u{25F0}', "white square with upper left quadrant"),
+    ('\u{25F1}', "white square with lower left quadrant"),
+    ('\u{25F2}', "white square with lower right quadrant"),
+    ('\u{25F3}', "white square with upper right quadrant"),
+    ('\u{25F4}', "white circle with upper left quadrant"),
+    ('\u{25F5}', "white circle with lower left quadrant"),
+    ('\u{25F6}', "white circle with lower right quadrant"),
+    ('\u{25F7}', "white circle with upper right quadrant"),
+    ('\u{25F8}', "upper left triangle"),
+    ('\u{25F9}', "upper right triangle"),
+    ('\u{25FA}', "lower left triangle"),
+    ('\u{25FB}', "white medium square"),
+    ('\u{25FC}', "black medium square"),
+    ('\u{25FD}', "white medium small square"),
+    ('\u{25FE}', "black medium small square"),
+    ('\u{25FF}', "lower right triangle"),
+    ('\u{1F780}', "black left-pointing isosceles right triangle"),
+    ('\u{1F781}', "black up-pointing isosceles right triangle"),
+    ('\u{1F782}', "black right-pointing isosceles right triangle"),
+    ('\u{1F783}', "black down-pointing isosceles right triangle"),
+    ('\u{1F784}', "black slightly small circle"),
+    ('\u{1F785}', "medium bold white circle"),
+    ('\u{1F786}', "bold white circle"),
+    ('\u{1F787}', "heavy white circle"),
+    ('\u{1F788}', "very heavy white circle"),
+    ('\u{1F789}', "extremely heavy white circle"),
+    ('\u{1F78A}', "white circle containing black small circle"),
+    ('\u{1F78B}', "round target"),
+    ('\u{1F78C}', "black tiny square"),
+    ('\u{1F78D}', "black slightly small square"),
+    ('\u{1F78E}', "light white square"),
+    ('\u{1F78F}', "medium white square"),
+    ('\u{1F790}', "bold white square"),
+    ('\u{1F791}', "heavy white square"),
+    ('\u{1F792}', "very heavy white square"),
+    ('\u{1F793}', "extremely heavy white square"),
+    ('\u{1F794}', "white square containing black very small square"),
+    ('\u{1F795}', "white square containing black medium square"),
+    ('\u{1F796}', "square target"),
+    ('\u{1F797}', "black tiny diamond"),
+    ('\u{1F798}', "black very small diamond"),
+    ('\u{1F799}', "black medium small diamond"),
+    ('\u{1F79A}', "white diamond containing black very small diamond"),
+    ('\u{1F79B}', "white diamond containing black medium diamond"),
+    ('\u{1F79C}', "diamond target"),
+    ('\u{1F79D}', "black tiny lozenge"),
+    ('\u{1F79E}', "black very small lozenge"),
+    ('\u{1F79F}', "black medium small lozenge"),
+    ('\u{1F7A0}', "white lozenge containing black small lozenge"),
+    ('\u{1F7A1}', "thin greek cross"),
+    ('\u{1F7A2}', "light greek cross"),
+    ('\u{1F7A3}', "medium greek cross"),
+    ('\u{1F7A4}', "bold greek cross"),
+    ('\u{1F7A5}', "very bold greek cross"),
+    ('\u{1F7A6}', "very heavy greek cross"),
+    ('\u{1F7A7}', "extremely heavy greek cross"),
+    ('\u{1F7A8}', "thin saltire"),
+    ('\u{1F7A9}', "light saltire"),
+    ('\u{1F7AA}', "medium saltire"),
+    ('\u{1F7AB}', "bold saltire"),
+    ('\u{1F7AC}', "heavy saltire"),
+    ('\u{1F7AD}', "very heavy saltire"),
+    ('\u{1F7AE}', "extremely heavy saltire"),
+    ('\u{1F7AF}', "light five spoked asterisk"),
+    ('\u{1F7B0}', "medium five spoked asterisk"),
+    ('\u{1F7B1}', "bold five spoked asterisk"),
+    ('\u{1F7B2}', "heavy five spoked asterisk"),
+    ('\u{1F7B3}', "very heavy five spoked asterisk"),
+    ('\u{1F7B4}', "extremely heavy five spoked asterisk"),
+    ('\u{1F7B5}', "light six spoked asterisk"),
+    ('\u{1F7B6}', "medium six spoked asterisk"),
+    ('\u{1F7B7}', "bold six spoked asterisk"),
+    ('\u{1F7B8}', "heavy six spoked asterisk"),
+    ('\u{1F7B9}', "very heavy six spoked asterisk"),
+    ('\u{1F7BA}', "extremely heavy six spoked asterisk"),
+    ('\u{1F7BB}', "light eight spoked asterisk"),
+    ('\u{1F7BC}', "medium eight spoked asterisk"),
+    ('\u{1F7BD}', "bold eight spoked asterisk"),
+    ('\u{1F7BE}', "heavy eight spoked asterisk"),
+    ('\u{1F7BF}', "very heavy eight spoked asterisk"),
+    ('\u{1F7C0}', "light three pointed black star"),
+    ('\u{1F7C1}', "medium three pointed black star"),
+    ('\u{1F7C2}', "three pointed black star"),
+    ('\u{1F7C3}', "medium three pointed pinwheel star"),
+    ('\u{1F7C4}', "light four pointed black star"),
+    ('\u{1F7C5}', "medium four pointed black star"),
+    ('\u{1F7C6}', "four pointed black star"),
+    ('\u{1F7C7}', "medium four pointed pinwheel star"),
+    ('\u{1F7C8}', "reverse light four pointed pinwheel star"),
+    ('\u{1F7C9}', "light five pointed black star"),
+    ('\u{1F7CA}', "heavy five pointed black star"),
+    ('\u{1F7CB}', "medium six pointed black star"),
+    ('\u{1F7CC}', "heavy six pointed black star"),
+    ('\u{1F7CD}', "six pointed pinwheel star"),
+    ('\u{1F7CE}', "medium eight pointed black star"),
+    ('\u{1F7CF}', "heavy eight pointed black star"),
+    ('\u{1F7D0}', "very heavy eight pointed black star"),
+    ('\u{1F7D1}', "heavy eight pointed pinwheel star"),
+    ('\u{1F7D2}', "light twelve pointed black star"),
+    ('\u{1F7D3}', "heavy twelve pointed black star"),
+    ('\u{1F7D4}', "heavy twelve pointed pinwheel star"),
+    ('\u{1F7D5}', "circled triangle"),
+    ('\u{1F7D6}', "negative circled triangle"),
+    ('\u{1F7D7}', "circled square"),
+    ('\u{1F7D8}', "negative circled square"),
+    ('\u{1F7E0}', "large orange circle"),
+    ('\u{1F7E1}', "large yellow circle"),
+    ('\u{1F7E2}', "large green circle"),
+    ('\u{1F7E3}', "large purple circle"),
+    ('\u{1F7E4}', "large brown circle"),
+    ('\u{1F7E5}', "large red square"),
+    ('\u{1F7E6}', "large blue square"),
+    ('\u{1F7E7}', "large orange square"),
+    ('\u{1F7E8}', "large yellow square"),
+    ('\u{1F7E9}', "large green square"),
+    ('\u{1F7EA}', "large purple square"),
+    ('\u{1F7EB}', "large brown square"),
+    ('\u{1F7F0}', "heavy equals sign"),
+];
+
+pub const PUNCTUATION_AND_CURRENCY: &[(char, &str)] = &[
+    ('\u{2010}', "hyphen"),
+    ('\u{2011}', "non-breaking hyphen"),
+    ('\u{2012}', "figure dash"),
+    ('\u{2013}', "en dash"),
+    ('\u{2014}', "em dash"),
+    ('\u{2015}', "horizontal bar"),
+    ('\u{2016}', "double vertical line"),
+    ('\u{2017}', "double low line"),
+    ('\u{2018}', "left single quotation mark"),
+    ('\u{2019}', "right single quotation mark"),
+    ('\u{201A}', "single low-9 quotation mark"),
+    ('\u{201B}', "single high-reversed-9 quotation mark"),
+    ('\u{201C}', "left double quotation mark"),
+    ('\u{201D}', "right double quotation mark"),
+    ('\u{201E}', "double low-9 quotation mark"),
+    ('\u{201F}', "double high-reversed-9 quotation mark"),
+    ('\u{2020}', "dagger"),
+    ('\u{2021}', "double dagger"),
+    ('\u{2022}', "bullet"),
+    ('\u{2023}', "triangular bullet"),
+    ('\u{2024}', "one dot leader"),
+    ('\u{2025}', "two dot leader"),
+    ('\u{2026}', "horizontal ellipsis"),
+    ('\u{2027}', "hyphenation point"),
+    ('\u{2028}', "line separator"),
+    ('\u{2029}', "paragraph separator"),
+    ('\u{202F}', "narrow no-break space"),
+    ('\u{2030}', "per mille sign"),
+    ('\u{2031}', "per ten thousand sign"),
+    ('\u{2032}', "prime"),
+    ('\u{2033}', "double prime"),
+    ('\u{2034}', "triple prime"),
+    ('\u{2035}', "reversed prime"),
+    ('\u{2036}', "reversed double prime"),
+    ('\u{2037}', "reversed triple prime"),
+    ('\u{2038}', "caret"),
+    ('\u{2039}', "single left-pointing angle quotation mark"),
+    ('\u{203A}', "single right-pointing angle quotation mark"),
+    ('\u{203B}', "reference mark"),
+    ('\u{203C}', "double exclamation mark"),
+    ('\u{203D}', "interrobang"),
+    ('\u{203E}', "overline"),
+    ('\u{203F}', "undertie"),
+    ('\u{2040}', "character tie"),
+    ('\u{2041}', "caret insertion point"),
+    ('\u{2042}', "asterism"),
+    ('\u{2043}', "hyphen bullet"),
+    ('\u{2044}', "fraction slash"),
+    ('\u{2045}', "left square bracket with quill"),
+    ('\u{2046}', "right square bracket with quill"),
+    ('\u{2047}', "double question mark"),
+    ('\u{2048}', "question exclamation mark"),
+    ('\u{2049}', "exclamation question mark"),
+    ('\u{204A}', "tironian sign et"),
+    ('\u{204B}', "reversed pilcrow sign"),
+    ('\u{204C}', "black leftwards bullet"),
+    ('\u{204D}', "black rightwards bullet"),
+    ('\u{204E}', "low asterisk"),
+    ('\u{204F}', "reversed semicolon"),
+    ('\u{2050}', "close up"),
+    ('\u{2051}', "two asterisks aligned vertically"),
+    ('\u{2052}', "commercial minus sign"),
+    ('\u{2053}', "swung dash"),
+    ('\u{2054}', "inverted undertie"),
+    ('\u{2055}', "flower punctuation mark"),
+    ('\u{2056}', "three dot punctuation"),
+    ('\u{2057}', "quadruple prime"),
+    ('\u{2058}', "four dot punctuation"),
+    ('\u{2059}', "five dot punctuation"),
+    ('\u{205A}', "two dot punctuation"),
+    ('\u{205B}', "four dot mark"),
+    ('\u{205C}', "dotted cross"),
+    ('\u{205D}', "tricolon"),
+    ('\u{205E}', "vertical four dots"),
+    ('\u{20A0}', "euro-currency sign"),
+    ('\u{20A1}', "colon sign"),
+    ('\u{20A2}', "cruzeiro sign"),
+    ('\u{20A3}', "french franc sign"),
+    ('\u{20A4}', "lira sign"),
+    ('\u{20A5}', "mill sign"),
+    ('\u{20A6}', "naira sign"),
+    ('\u{20A7}', "peseta sign"),
+    ('\u{20A8}', "rupee sign"),
+    ('\u{20A9}', "won sign"),
+    ('\u{20AA}', "new sheqel sign"),
+    ('\u{20AB}', "dong sign"),
+    ('\u{20AC}', "euro sign"),
+    ('\u{20AD}', "kip sign"),
+    ('\u{20AE}', "tugrik sign"),
+    ('\u{20AF}', "drachma sign"),
+    ('\u{20B0}', "german penny sign"),
+    ('\u{20B1}', "peso sign"),
+    ('\u{20B2}', "guarani sign"),
+    ('\u{20B3}', "austral sign"),
+    ('\u{20B4}', "hryvnia sign"),
+    ('\u{20B5}', "cedi sign"),
+    ('\u{20B6}', "livre tournois sign"),
+    ('\u{20B7}', "spesmilo sign"),
+    ('\u{20B8}', "tenge sign"),
+    ('\u{20B9}', "indian rupee sign"),
+    ('\u{20BA}', "turkish lira sign"),
+    ('\u{20BB}', "nordic mark sign"),
+    ('\u{20BC}', "manat sign"),
+    ('\u{20BD}', "ruble sign"),
+    ('\u{20BE}', "lari sign"),
+    ('\u{20BF}', "bitcoin sign"),
+    ('\u{20C0}', "som sign"),
+    ('\u{2100}', "account of"),
+    ('\u{2101}', "addressed to the subject"),
+    ('\u{2102}', "double-struck capital c"),
+    ('\u{2103}', "degree celsius"),
+    ('\u{2104}', "centre line symbol"),
+    ('\u{2105}', "care of"),
+    ('\u{2106}', "cada una"),
+    ('\u{2107}', "euler constant"),
+    ('\u{2108}', "scruple"),
+    ('\u{2109}', "degree fahrenheit"),
+    ('\u{210A}', "script small g"),
+    ('\u{210B}', "script capital h"),
+    ('\u{210C}', "black-letter capital h"),
+    ('\u{210D}', "double-struck capital h"),
+    ('\u{210E}', "planck constant"),
+    ('\u{210F}', "planck constant over two pi"),
+    ('\u{2110}', "script capital i"),
+    ('\u{2111}', "black-letter capital i"),
+    ('\u{2112}', "script capital l"),
+    ('\u{2113}', "script small l"),
+    ('\u{2114}', "l b bar symbol"),
+    ('\u{2115}', "double-struck capital n"),
+    ('\u{2116}', "numero sign"),
+    ('\u{2117}', "sound recording copyright"),
+    ('\u{2118}', "script capital p"),
+    ('\u{2119}', "double-struck capital p"),
+    ('\u{211A}', "double-struck capital q"),
+    ('\u{211B}', "script capital r"),
+    ('\u{211C}', "black-letter capital r"),
+    ('\u{211D}', "double-struck capital r"),
+    ('\u{211E}', "prescription take"),
+    ('\u{211F}', "response"),
+    ('\u{2120}', "service mark"),
+    ('\u{2121}', "telephone sign"),
+    ('\u{2122}', "trade mark sign"),
+    ('\u{2123}', "versicle"),
+    ('\u{2124}', "double-struck capital z"),
+    ('\u{2125}', "ounce sign"),
+    ('\u{2126}', "ohm sign"),
+    ('\u{2127}', "inverted ohm sign"),
+    ('\u{2128}', "black-letter capital z"),
+    ('\u{2129}', "turned greek small letter iota"),
+    ('\u{212A}', "kelvin sign"),
+    ('\u{212B}', "angstrom sign"),
+    ('\u{212C}', "script capital b"),
+    ('\u{212D}', "black-letter capital c"),
+    ('\u{212E}', "estimated symbol"),
+    ('\u{212F}', "script small e"),
+    ('\u{2130}', "script capital e"),
+    ('\u{2131}', "script capital f"),
+    ('\u{2132}', "turned capital f"),
+    ('\u{2133}', "script capital m"),
+    ('\u{2134}', "script small o"),
+    ('\u{2135}', "alef symbol"),
+    ('\u{2136}', "bet symbol"),
+    ('\u{2137}', "gimel symbol"),
+    ('\u{2138}', "dalet symbol"),
+    ('\u{2139}', "information source"),
+    ('\u{213A}', "rotated capital q"),
+    ('\u{213B}', "facsimile sign"),
+    ('\u{213C}', "double-struck small pi"),
+    ('\u{213D}', "double-struck small gamma"),
+    ('\u{213E}', "double-struck capital gamma"),
+    ('\u{213F}', "double-struck capital pi"),
+    ('\u{2140}', "double-struck n-ary summation"),
+    ('\u{2141}', "turned sans-serif capital g"),
+    ('\u{2142}', "turned sans-serif capital l"),
+    ('\u{2143}', "reversed sans-serif capital l"),
+    ('\u{2144}', "turned sans-serif capital y"),
+    ('\u{2145}', "double-struck italic capital d"),
+    ('\u{2146}', "double-struck italic small d"),
+    ('\u{2147}', "double-struck italic small e"),
+    ('\u{2148}', "double-struck italic small i"),
+    ('\u{2149}', "double-struck italic small j"),
+    ('\u{214A}', "property line"),
+    ('\u{214B}', "turned ampersand"),
+    ('\u{214C}', "per sign"),
+    ('\u{214D}', "aktieselskab"),
+    ('\u{214E}', "turned small f"),
+    ('\u{214F}', "symbol for samaritan source"),
+];
+
+pub const LATIN: &[(char, &str)] = &[
+    ('\u{A1}', "inverted exclamation mark"),
+    ('\u{A2}', "cent sign"),
+    ('\u{A3}', "pound sign"),
+    ('\u{A4}', "currency sign"),
+    ('\u{A5}', "yen sign"),
+    ('\u{A6}', "broken bar"),
+    ('\u{A7}', "section sign"),
+    ('\u{A8}', "diaeresis"),
+    ('\u{A9}', "copyright sign"),
+    ('\u{AA}', "feminine ordinal indicator"),
+    ('\u{AB}', "left-pointing double angle quotation mark"),
+    ('\u{AC}', "not sign"),
+    ('\u{AE}', "registered sign"),
+    ('\u{AF}', "macron"),
+    ('\u{B0}', "degree sign"),
+    ('\u{B1}', "plus-minus sign"),
+    ('\u{B2}', "superscript two"),
+    ('\u{B3}', "superscript three"),
+    ('\u{B4}', "acute accent"),
+    ('\u{B5}', "micro sign"),
+    ('\u{B6}', "pilcrow sign"),
+    ('\u{B7}', "middle dot"),
+    ('\u{B8}', "cedilla"),
+    ('\u{B9}', "superscript one"),
+    ('\u{BA}', "masculine ordinal indicator"),
+    ('\u{BB}', "right-pointing double angle quotation mark"),
+    ('\u{BC}', "vulgar fraction one quarter"),
+    ('\u{BD}', "vulgar fraction one half"),
+    ('\u{BE}', "vulgar fraction three quarters"),
+    ('\u{BF}', "inverted question mark"),
+    ('\u{C0}', "latin capital letter a with grave"),
+    ('\u{C1}', "latin capital letter a with acute"),
+    ('\u{C2}', "latin capital letter a with circumflex"),
+    ('\u{C3}', "latin capital letter a with tilde"),
+    ('\u{C4}', "latin capital letter a with diaeresis"),
+    ('\u{C5}', "latin capital letter a with ring above"),
+    ('\u{C6}', "latin capital letter ae"),
+    ('\u{C7}', "latin capital letter c with cedilla"),
+    ('\u{C8}', "latin capital letter e with grave"),
+    ('\u{C9}', "latin capital letter e with acute"),
+    ('\u{CA}', "latin capital letter e with circumflex"),
+    ('\u{CB}', "latin capital letter e with diaeresis"),
+    ('\u{CC}', "latin capital letter i with grave"),
+    ('\u{CD}', "latin capital letter i with acute"),
+    ('\u{CE}', "latin capital letter i with circumflex"),
+    ('\u{CF}', "latin capital letter i with diaeresis"),
+    ('\u{D0}', "latin capital letter eth"),
+    ('\u{D1}', "latin capital letter n with tilde"),
+    ('\u{D2}', "latin capital letter o with grave"),
+    ('\u{D3}', "latin capital letter o with acute"),
+    ('\u{D4}', "latin capital letter o with circumflex"),
+    ('\u{D5}', "latin capital letter o with tilde"),
+    ('\u{D6}', "latin capital letter o with diaeresis"),
+    ('\u{D7}', "multiplication sign"),
+    ('\u{D8}', "latin capital letter o with stroke"),
+    ('\u{D9}', "latin capital letter u with grave"),
+    ('\u{DA}', "latin capital letter u with acute"),
+    ('\u{DB}', "latin capital letter u with circumflex"),
+    ('\u{DC}', "latin capital letter u with diaeresis"),
+    ('\u{DD}', "latin capital letter y with acute"),
+    ('\u{DE}', "latin capital letter thorn"),
+    ('\u{DF}', "latin small letter sharp s"),
+    ('\u{E0}', "latin small letter a with grave"),
+    ('\u{E1}', "latin small letter a with acute"),
+    ('\u{E2}', "latin small letter a with circumflex"),
+    ('\u{E3}', "latin small letter a with tilde"),
+    ('\u{E4}', "latin small letter a with diaeresis"),
+    ('\u{E5}', "latin small letter a with ring above"),
+    ('\u{E6}', "latin small letter ae"),
+    ('\u{E7}', "latin small letter c with cedilla"),
+    ('\u{E8}', "latin small letter e with grave"),
+    ('\u{E9}', "latin small letter e with acute"),
+    ('\u{EA}', "latin small letter e with circumflex"),
+    ('\u{EB}', "latin small letter e with diaeresis"),
+    ('\u{EC}', "latin small letter i with grave"),
+    ('\u{ED}', "latin small letter i with acute"),
+    ('\u{EE}', "latin small letter i with circumflex"),
+    ('\u{EF}', "latin small letter i with diaeresis"),
+    ('\u{F0}', "latin small letter eth"),
+    ('\u{F1}', "latin small letter n with tilde"),
+    ('\u{F2}', "latin small letter o with grave"),
+    ('\u{F3}', "latin small letter o with acute"),
+    ('\u{F4}', "latin small letter o with circumflex"),
+    ('\u{F5}', "latin small letter o with tilde"),
+    ('\u{F6}', "latin small letter o with diaeresis"),
+    ('\u{F7}', "division sign"),
+    ('\u{F8}', "latin small letter o with stroke"),
+    ('\u{F9}', "latin small letter u with grave"),
+    ('\u{FA}', "latin small letter u with acute"),
+    ('\u{FB}', "latin small letter u with circumflex"),
+    ('\u{FC}', "latin small letter u with diaeresis"),
+    ('\u{FD}', "latin small letter y with acute"),
+    ('\u{FE}', "latin small letter thorn"),
+    ('\u{FF}', "latin small letter y with diaeresis"),
+    ('\u{100}', "latin capital letter a with macron"),
+    ('\u{101}', "latin small letter a with macron"),
+    ('\u{102}', "latin capital letter a with breve"),
+    ('\u{103}', "latin small letter a with breve"),
+    ('\u{104}', "latin capital letter a with ogonek"),
+    ('\u{105}', "latin small letter a with ogonek"),
+    ('\u{106}', "latin capital letter c with acute"),
+    ('\u{107}', "latin small letter c with acute"),
+    ('\u{108}', "latin capital letter c with circumflex"),
+    ('\u{109}', "latin small letter c with circumflex"),
+    ('\u{10A}', "latin capital letter c with dot above"),
+    ('\u{10B}', "latin small letter c with dot above"),
+    ('\u{10C}', "latin capital letter c with caron"),
+    ('\u{10D}', "latin small letter c with caron"),
+    ('\u{10E}', "latin capital letter d with caron"),
+    ('\u{10F}', "latin small letter d with caron"),
+    ('\u{110}', "latin capital letter d with stroke"),
+    ('\u{111}', "latin small letter d with stroke"),
+    ('\u{112}', "latin capital letter e with macron"),
+    ('\u{113}', "latin small letter e with macron"),
+    ('\u{114}', "latin capital letter e with breve"),
+    ('\u{115}', "latin small letter e with breve"),
+    ('\u{116}', "latin capital letter e with dot above"),
+    ('\u{117}', "latin small letter e with dot above"),
+    ('\u{118}', "latin capital letter e with ogonek"),
+    ('\u{119}', "latin small letter e with ogonek"),
+    ('\u{11A}', "latin capital letter e with caron"),
+    ('\u{11B}', "latin small letter e with caron"),
+    ('\u{11C}', "latin capital letter g with circumflex"),
+    ('\u{11D}', "latin small letter g with circumflex"),
+    ('\u{11E}', "latin capital letter g with breve"),
+    ('\u{11F}', "latin small letter g with breve"),
+    ('\u{120}', "latin capital letter g with dot above"),
+    ('\u{121}', "latin small letter g with dot above"),
+    ('\u{122}', "latin capital letter g with cedilla"),
+    ('\u{123}', "latin small letter g with cedilla"),
+    ('\u{124}', "latin capital letter h with circumflex"),
+    ('\u{125}', "latin small letter h with circumflex"),
+    ('\u{126}', "latin capital letter h with stroke"),
+    ('\u{127}', "latin small letter h with stroke"),
+    ('\u{128}', "latin capital letter i with tilde"),
+    ('\u{129}', "latin small letter i with tilde"),
+    ('\u{12A}', "latin capital letter i with macron"),
+    ('\u{12B}', "latin small letter i with macron"),
+    ('\u{12C}', "latin capital letter i with breve"),
+    ('\u{12D}', "latin small letter i with breve"),
+    ('\u{12E}', "latin capital letter i with ogonek"),
+    ('\u{12F}', "latin small letter i with ogonek"),
+    ('\u{130}', "latin capital letter i with dot above"),
+    ('\u{131}', "latin small letter dotless i"),
+    ('\u{132}', "latin capital ligature ij"),
+    ('\u{133}', "latin small ligature ij"),
+    ('\u{134}', "latin capital letter j with circumflex"),
+    ('\u{135}', "latin small letter j with circumflex"),
+    ('\u{136}', "latin capital letter k with cedilla"),
+    ('\u{137}', "latin small letter k with cedilla"),
+    ('\u{138}', "latin small letter kra"),
+    ('\u{139}', "latin capital letter l with acute"),
+    ('\u{13A}', "latin small letter l with acute"),
+    ('\u{13B}', "latin capital letter l with cedilla"),
+    ('\u{13C}', "latin small letter l with cedilla"),
+    ('\u{13D}', "latin capital letter l with caron"),
+    ('\u{13E}', "latin small letter l with caron"),
+    ('\u{13F}', "latin capital letter l with middle dot"),
+    ('\u{140}', "latin small letter l with middle dot"),
+    ('\u{141}', "latin capital letter l with stroke"),
+    ('\u{142}', "latin small letter l with stroke"),
+    ('\u{143}', "latin capital letter n with acute"),
+    ('\u{144}', "latin small letter n with acute"),
+    ('\u{145}', "latin capital letter n with cedilla"),
+    ('\u{146}', "latin small letter n with cedilla"),
+    ('\u{147}', "latin capital letter n with caron"),
+    ('\u{148}', "latin small letter n with caron"),
+    ('\u{149}', "latin small letter n preceded by apostrophe"),
+    ('\u{14A}', "latin capital letter eng"),
+    ('\u{14B}', "latin small letter eng"),
+    ('\u{14C}', "latin capital letter o with macron"),
+    ('\u{14D}', "latin small letter o with macron"),
+    ('\u{14E}', "latin capital letter o with breve"),
+    ('\u{14F}', "latin small letter o with breve"),
+    ('\u{150}', "latin capital letter o with double acute"),
+    ('\u{151}', "latin small letter o with double acute"),
+    ('\u{152}', "latin capital ligature oe"),
+    ('\u{153}', "latin small ligature oe"),
+    ('\u{154}', "latin capital letter r with acute"),
+    ('\u{155}', "latin small letter r with acute"),
+    ('\u{156}', "latin capital letter r with cedilla"),
+    ('\u{157}', "latin small letter r with cedilla"),
+    ('\u{158}', "latin capital letter r with caron"),
+    ('\u{159}', "latin small letter r with caron"),
+    ('\u{15A}', "latin capital letter s with acute"),
+    ('\u{15B}', "latin small letter s with acute"),
+    ('\u{15C}', "latin capital letter s with circumflex"),
+    ('\u{15D}', "latin small letter s with circumflex"),
+    ('\u{15E}', "latin capital letter s with cedilla"),
+    ('\u{15F}', "latin small letter s with cedilla"),
+    ('\u{160}', "latin capital letter s with caron"),
+    ('\u{161}', "latin small letter s with caron"),
+    ('\u{162}', "latin capital letter t with cedilla"),
+    ('\u{163}', "latin small letter t with cedilla"),
+    ('\u{164}', "latin capital letter t with caron"),
+    ('\u{165}', "latin small letter t with caron"),
+    ('\u{166}', "latin capital letter t with stroke"),
+    ('\u{167}', "latin small letter t with stroke"),
+    ('\u{168}', "latin capital letter u with tilde"),
+    ('\u{169}', "latin small letter u with tilde"),
+    ('\u{16A}', "latin capital letter u with macron"),
+    ('\u{16B}', "latin small letter u with macron"),
+    ('\u{16C}', "latin capital letter u with breve"),
+    ('\u{16D}', "latin small letter u with breve"),
+    ('\u{16E}', "latin capital letter u with ring above"),
+    ('\u{16F}', "latin small letter u with ring above"),
+    ('\u{170}', "latin capital letter u with double acute"),
+    ('\u{171}', "latin small letter u with double acute"),
+    ('\u{172}', "latin capital letter u with ogonek"),
+    ('\u{173}', "latin small letter u with ogonek"),
+    ('\u{174}', "latin capital letter w with circumflex"),
+    ('\u{175}', "latin small letter w with circumflex"),
+    ('\u{176}', "latin capital letter y with circumflex"),
+    ('\u{177}', "latin small letter y with circumflex"),
+    ('\u{178}', "latin capital letter y with diaeresis"),
+    ('\u{179}', "latin capital letter z with acute"),
+    ('\u{17A}', "latin small letter z with acute"),
+    ('\u{17B}', "latin capital letter z with dot above"),
+    ('\u{17C}', "latin small letter z with dot above"),
+    ('\u{17D}', "latin capital letter z with caron"),
+    ('\u{17E}', "latin small letter z with caron"),
+    ('\u{17F}', "latin small letter long s"),
+    ('\u{180}', "latin small letter b with stroke"),
+    ('\u{181}', "latin capital letter b with hook"),
+    ('\u{182}', "latin capital letter b with topbar"),
+    ('\u{183}', "latin small letter b with topbar"),
+    ('\u{184}', "latin capital letter tone six"),
+    ('\u{185}', "latin small letter tone six"),
+    ('\u{186}', "latin capital letter open o"),
+    ('\u{187}', "latin capital letter c with hook"),
+    ('\u{188}', "latin small letter c with hook"),
+    ('\u{189}', "latin capital letter african d"),
+    ('\u{18A}', "latin capital letter d with hook"),
+    ('\u{18B}', "latin capital letter d with topbar"),
+    ('\u{18C}', "latin small letter d with topbar"),
+    ('\u{18D}', "latin small letter turned delta"),
+    ('\u{18E}', "latin capital letter reversed e"),
+    ('\u{18F}', "latin capital letter schwa"),
+    ('\u{190}', "latin capital letter open e"),
+    ('\u{191}', "latin capital letter f with hook"),
+    ('\u{192}', "latin small letter f with hook"),
+    ('\u{193}', "latin capital letter g with hook"),
+    ('\u{194}', "latin capital letter gamma"),
+    ('\u{195}', "latin small letter hv"),
+    ('\u{196}', "latin capital letter iota"),
+    ('\u{197}', "latin capital letter i with stroke"),
+    ('\u{198}', "latin capital letter k with hook"),
+    ('\u{199}', "latin small letter k with hook"),
+    ('\u{19A}', "latin small letter l with bar"),
+    ('\u{19B}', "latin small letter lambda with stroke"),
+    ('\u{19C}', "latin capital letter turned m"),
+    ('\u{19D}', "latin capital letter n with left hook"),
+    ('\u{19E}', "latin small letter n with long right leg"),
+    ('\u{19F}', "latin capital letter o with middle tilde"),
+    ('\u{1A0}', "latin capital letter o with horn"),
+    ('\u{1A1}', "latin small letter o with horn"),
+    ('\u{1A2}', "latin capital letter oi"),
+    ('\u{1A3}', "latin small letter oi"),
+    ('\u{1A4}', "latin capital letter p with hook"),
+    ('\u{1A5}', "latin small letter p with hook"),
+    ('\u{1A6}', "latin letter yr"),
+    ('\u{1A7}', "latin capital letter tone two"),
+    ('\u{1A8}', "latin small letter tone two"),
+    ('\u{1A9}', "latin capital letter esh"),
+    ('\u{1AA}', "latin letter reversed esh loop"),
+    ('\u{1AB}', "latin small letter t with palatal hook"),
+    ('\u{1AC}', "latin capital letter t with hook"),
+    ('\u{1AD}', "latin small letter t with hook"),
+    ('\u{1AE}', "latin capital letter t with retroflex hook"),
+    ('\u{1AF}', "latin capital letter u with horn"),
+    ('\u{1B0}', "latin small letter u with horn"),
+    ('\u{1B1}', "latin capital letter upsilon"),
+    ('\u{1B2}', "latin capital letter v with hook"),
+    ('\u{1B3}', "latin capital letter y with hook"),
+    ('\u{1B4}', "latin small letter y with hook"),
+    ('\u{1B5}', "latin capital letter z with stroke"),
+    ('\u{1B6}', "latin small letter z with stroke"),
+    ('\u{1B7}', "latin capital letter ezh"),
+    ('\u{1B8}', "latin capital letter ezh reversed"),
+    ('\u{1B9}', "latin small letter ezh reversed"),
+    ('\u{1BA}', "latin small letter ezh with tail"),
+    ('\u{1BB}', "latin letter two with stroke"),
+    ('\u{1BC}', "latin capital letter tone five"),
+    ('\u{1BD}', "latin small letter tone five"),
+    ('\u{1BE}', "latin letter inverted glottal stop with stroke"),
+    ('\u{1BF}', "latin letter wynn"),
+    ('\u{1C0}', "latin letter dental click"),
+    ('\u{1C1}', "latin letter lateral click"),
+    ('\u{1C2}', "latin letter alveolar click"),
+    ('\u{1C3}', "latin letter retroflex click"),
+    ('\u{1C4}', "latin capital letter dz with caron"),
+    ('\u{1C5}', "latin capital letter d with small letter z with caron"),
+    ('\u{1C6}', "latin small letter dz with caron"),
+    ('\u{1C7}', "latin capital letter lj"),
+    ('\u{1C8}', "latin capital letter l with small letter j"),
+    ('\u{1C9}', "latin small letter lj"),
+    ('\u{1CA}', "latin capital letter nj"),
+    ('\u{1CB}', "latin capital letter n with small letter j"),
+    ('\u{1CC}', "latin small letter nj"),
+    ('\u{1CD}', "latin capital letter a with caron"),
+    ('\u{1CE}', "latin small letter a with caron"),
+    ('\u{1CF}', "latin capital letter i with caron"),
+    ('\u{1D0}', "latin small letter i with caron"),
+    ('\u{1D1}', "latin capital letter o with caron"),
+    ('\u{1D2}', "latin small letter o with caron"),
+    ('\u{1D3}', "latin capital letter u with caron"),
+    ('\u{1D4}', "latin small letter u with caron"),
+    ('\u{1D5}', "latin capital letter u with diaeresis and macron"),
+    ('\u{1D6}', "latin small letter u with diaeresis and macron"),
+    ('\u{1D7}', "latin capital letter u with diaeresis and acute"),
+    ('\u{1D8}', "latin small letter u with diaeresis and acute"),
+    ('\u{1D9}', "latin capital letter u with diaeresis and caron"),
+    ('\u{1DA}', "latin small letter u with diaeresis and caron"),
+    ('\u{1DB}', "latin capital letter u with diaeresis and grave"),
+    ('\u{1DC}', "latin small letter u with diaeresis and grave"),
+    ('\u{1DD}', "latin small letter turned e"),
+    ('\u{1DE}', "latin capital letter a with diaeresis and macron"),
+    ('\u{1DF}', "latin small letter a with diaeresis and macron"),
+    ('\u{1E0}', "latin capital letter a with dot above and macron"),
+    ('\u{1E1}', "latin small letter a with dot above and macron"),
+    ('\u{1E2}', "latin capital letter ae with macron"),
+    ('\u{1E3}', "latin small letter ae with macron"),
+    ('\u{1E4}', "latin capital letter g with stroke"),
+    ('\u{1E5}', "latin small letter g with stroke"),
+    ('\u{1E6}', "latin capital letter g with caron"),
+    ('\u{1E7}', "latin small letter g with caron"),
+    ('\u{1E8}', "latin capital letter k with caron"),
+    ('\u{1E9}', "latin small letter k with caron"),
+    ('\u{1EA}', "latin capital letter o with ogonek"),
+    ('\u{1EB}', "latin small letter o with ogonek"),
+    ('\u{1EC}', "latin capital letter o with ogonek and macron"),
+    ('\u{1ED}', "latin small letter o with ogonek and macron"),
+    ('\u{1EE}', "latin capital letter ezh with caron"),
+    ('\u{1EF}', "latin small letter ezh with caron"),
+    ('\u{1F0}', "latin small letter j with caron"),
+    ('\u{1F1}', "latin capital letter dz"),
+    ('\u{1F2}', "latin capital letter d with small letter z"),
+    ('\u{1F3}', "latin small letter dz"),
+    ('\u{1F4}', "latin capital letter g with acute"),
+    ('\u{1F5}', "latin small letter g with acute"),
+    ('\u{1F6}', "latin capital letter hwair"),
+    ('\u{1F7}', "latin capital letter wynn"),
+    ('\u{1F8}', "latin capital letter n with grave"),
+    ('\u{1F9}', "latin small letter n with grave"),
+    ('\u{1FA}', "latin capital letter a with ring above and acute"),
+    ('\u{1FB}', "latin small letter a with ring above and acute"),
+    ('\u{1FC}', "latin capital letter ae with acute"),
+    ('\u{1FD}', "latin small letter ae with acute"),
+    ('\u{1FE}', "latin capital letter o with stroke and acute"),
+    ('\u{1FF}', "latin small letter o with stroke and acute"),
+    ('\u{200}', "latin capital letter a with double grave"),
+    ('\u{201}', "latin small letter a with double grave"),
+    ('\u{202}', "latin capital letter a with inverted breve"),
+    ('\u{203}', "latin small letter a with inverted breve"),
+    ('\u{204}', "latin capital letter e with double grave"),
+    ('\u{205}', "latin small letter e with double grave"),
+    ('\u{206}', "latin capital letter e with inverted breve"),
+    ('\u{207}', "latin small letter e with inverted breve"),
+    ('\u{208}', "latin capital letter i with double grave"),
+    ('\u{209}', "latin small letter i with double grave"),
+    ('\u{20A}', "latin capital letter i with inverted breve"),
+    ('\u{20B}', "latin small letter i with inverted breve"),
+    ('\u{20C}', "latin capital letter o with double grave"),
+    ('\u{20D}', "latin small letter o with double grave"),
+    ('\u{20E}', "latin capital letter o with inverted breve"),
+    ('\u{20F}', "latin small letter o with inverted breve"),
+    ('\u{210}', "latin capital letter r with double grave"),
+    ('\u{211}', "latin small letter r with double grave"),
+    ('\u{212}', "latin capital letter r with inverted breve"),
+    ('\u{213}', "latin small letter r with inverted breve"),
+    ('\u{214}', "latin capital letter u with double grave"),
+    ('\u{215}', "latin small letter u with double grave"),
+    ('\u{216}', "latin capital letter u with inverted breve"),
+    ('\u{217}', "latin small letter u with inverted breve"),
+    ('\u{218}', "latin capital letter s with comma below"),
+    ('\u{219}', "latin small letter s with comma below"),
+    ('\u{21A}', "latin capital letter t with comma below"),
+    ('\u{21B}', "latin small letter t with comma below"),
+    ('\u{21C}', "latin capital letter yogh"),
+    ('\u{21D}', "latin small letter yogh"),
+    ('\u{21E}', "latin capital letter h with caron"),
+    ('\u{21F}', "latin small letter h with caron"),
+    ('\u{220}', "latin capital letter n with long right leg"),
+    ('\u{221}', "latin small letter d with curl"),
+    ('\u{222}', "latin capital letter ou"),
+    ('\u{223}', "latin small letter ou"),
+    ('\u{224}', "latin capital letter z with hook"),
+    ('\u{225}', "latin small letter z with hook"),
+    ('\u{226}', "latin capital letter a with dot above"),
+    ('\u{227}', "latin small letter a with dot above"),
+    ('\u{228}', "latin capital letter e with cedilla"),
+    ('\u{229}', "latin small letter e with cedilla"),
+    ('\u{22A}', "latin capital letter o with diaeresis and macron"),
+    ('\u{22B}', "latin small letter o with diaeresis and macron"),
+    ('\u{22C}', "latin capital letter o with tilde and macron"),
+    ('\u{22D}', "latin small letter o with tilde and macron"),
+    ('\u{22E}', "latin capital letter o with dot above"),
+    ('\u{22F}', "latin small letter o with dot above"),
+    ('\u{230}', "latin capital letter o with dot above and macron"),
+    ('\u{231}', "latin small letter o with dot above and macron"),
+    ('\u{232}', "latin capital letter y with macron"),
+    ('\u{233}', "latin small letter y with macron"),
+    ('\u{234}', "latin small letter l with curl"),
+    ('\u{235}', "latin small letter n with curl"),
+    ('\u{236}', "latin small letter t with curl"),
+    ('\u{237}', "latin small letter dotless j"),
+    ('\u{238}', "latin small letter db digraph"),
+    ('\u{239}', "latin small letter qp digraph"),
+    ('\u{23A}', "latin capital letter a with stroke"),
+    ('\u{23B}', "latin capital letter c with stroke"),
+    ('\u{23C}', "latin small letter c with stroke"),
+    ('\u{23D}', "latin capital letter l with bar"),
+    ('\u{23E}', "latin capital letter t with diagonal stroke"),
+    ('\u{23F}', "latin small letter s with swash tail"),
+    ('\u{240}', "latin small letter z with swash tail"),
+    ('\u{241}', "latin capital letter glottal stop"),
+    ('\u{242}', "latin small letter glottal stop"),
+    ('\u{243}', "latin capital letter b with stroke"),
+    ('\u{244}', "latin capital letter u bar"),
+    ('\u{245}', "latin capital letter turned v"),
+    ('\u{246}', "latin capital letter e with stroke"),
+    ('\u{247}', "latin small letter e with stroke"),
+    ('\u{248}', "latin capital letter j with stroke"),
+    ('\u{249}', "latin small letter j with stroke"),
+    ('\u{24A}', "latin capital letter small q with hook tail"),
+    ('\u{24B}', "latin small letter q with hook tail"),
+    ('\u{24C}', "latin capital letter r with stroke"),
+    ('\u{24D}', "latin small letter r with stroke"),
+    ('\u{24E}', "latin capital letter y with stroke"),
+    ('\u{24F}', "latin small letter y with stroke"),
+];
+
+pub const GREEK: &[(char, &str)] = &[
+    ('\u{370}', "greek capital letter heta"),
+    ('\u{371}', "greek small letter heta"),
+    ('\u{372}', "greek capital letter archaic sampi"),
+    ('\u{373}', "greek small letter archaic sampi"),
+    ('\u{374}', "greek numeral sign"),
+    ('\u{375}', "greek lower numeral sign"),
+    ('\u{376}', "greek capital letter pamphylian digamma"),
+    ('\u{377}', "greek small letter pamphylian digamma"),
+    ('\u{37A}', "greek ypogegrammeni"),
+    ('\u{37B}', "greek small reversed lunate sigma symbol"),
+    ('\u{37C}', "greek small dotted lunate sigma symbol"),
+    ('\u{37D}', "greek small reversed dotted lunate sigma symbol"),
+    ('\u{37E}', "greek question mark"),
+    ('\u{37F}', "greek capital letter yot"),
+    ('\u{384}', "greek tonos"),
+    ('\u{385}', "greek dialytika tonos"),
+    ('\u{386}', "greek capital letter alpha with tonos"),
+    ('\u{387}', "greek ano teleia"),
+    ('\u{388}', "greek capital letter epsilon with tonos"),
+    ('\u{389}', "greek capital letter eta with tonos"),
+    ('\u{38A}', "greek capital letter iota with tonos"),
+    ('\u{38C}', "greek capital letter omicron with tonos"),
+    ('\u{38E}', "greek capital letter upsilon with tonos"),
+    ('\u{38F}', "greek capital letter omega with tonos"),
+    ('\u{390}', "greek small letter iota with dialytika and tonos"),
+    ('\u{391}', "greek capital letter alpha"),
+    ('\u{392}', "greek capital letter beta"),
+    ('\u{393}', "greek capital letter gamma"),
+    ('\u{394}', "greek capital letter delta"),
+    ('\u{395}', "greek capital letter epsilon"),
+    ('\u{396}', "greek capital letter zeta"),
+    ('\u{397}', "greek capital letter eta"),
+    ('\u{398}', "greek capital letter theta"),
+    ('\u{399}', "greek capital letter iota"),
+    ('\u{39A}', "greek capital letter kappa"),
+    ('\u{39B}', "greek capital letter lamda"),
+    ('\u{39C}', "greek capital letter mu"),
+    ('\u{39D}', "greek capital letter nu"),
+    ('\u{39E}', "greek capital letter xi"),
+    ('\u{39F}', "greek capital letter omicron"),
+    ('\u{3A0}', "greek capital letter pi"),
+    ('\u{3A1}', "greek capital letter rho"),
+    ('\u{3A3}', "greek capital letter sigma"),
+    ('\u{3A4}', "greek capital letter tau"),
+    ('\u{3A5}', "greek capital letter upsilon"),
+    ('\u{3A6}', "greek capital letter phi"),
+    ('\u{3A7}', "greek capital letter chi"),
+    ('\u{3A8}', "greek capital letter psi"),
+    ('\u{3A9}', "greek capital letter omega"),
+    ('\u{3AA}', "greek capital letter iota with dialytika"),
+    ('\u{3AB}', "greek capital letter upsilon with dialytika"),
+    ('\u{3AC}', "greek small letter alpha with tonos"),
+    ('\u{3AD}', "greek small letter epsilon with tonos"),
+    ('\u{3AE}', "greek small letter eta with tonos"),
+    ('\u{3AF}', "greek small letter iota with tonos"),
+    ('\u{3B0}', "greek small letter upsilon with dialytika and tonos"),
+    ('\u{3B1}', "greek small letter alpha"),
+    ('\u{3B2}', "greek small letter beta"),
+    ('\u{3B3}', "greek small letter gamma"),
+    ('\u{3B4}', "greek small letter delta"),
+    ('\u{3B5}', "greek small letter epsilon"),
+    ('\u{3B6}', "greek small letter zeta"),
+    ('\u{3B7}', "greek small letter eta"),
+    ('\u{3B8}', "greek small letter theta"),
+    ('\u{3B9}', "greek small letter iota"),
+    ('\u{3BA}', "greek small letter kappa"),
+    ('\u{3BB}', "greek small letter lamda"),
+    ('\u{3BC}', "greek small letter mu"),
+    ('\u{3BD}', "greek small letter nu"),
+    ('\u{3BE}', "greek small letter xi"),
+    ('\u{3BF}', "greek small letter omicron"),
+    ('\u{3C0}', "greek small letter pi"),
+    ('\u{3C1}', "greek small letter rho"),
+    ('\u{3C2}', "greek small letter final sigma"),
+    ('\u{3C3}', "greek small letter sigma"),
+    ('\u{3C4}', "greek small letter tau"),
+    ('\u{3C5}', "greek small letter upsilon"),
+    ('\u{3C6}', "greek small letter phi"),
+    ('\u{3C7}', "greek small letter chi"),
+    ('\u{3C8}', "greek small letter psi"),
+    ('\u{3C9}', "greek small letter omega"),
+    ('\u{3CA}', "greek small letter iota with dialytika"),
+    ('\u{3CB}', "greek small letter upsilon with dialytika"),
+    ('\u{3CC}', "greek small letter omicron with tonos"),
+    ('\u{3CD}', "greek small letter upsilon with tonos"),
+    ('\u{3CE}', "greek small letter omega with tonos"),
+    ('\u{3CF}', "greek capital kai symbol"),
+    ('\u{3D0}', "greek beta symbol"),
+    ('\u{3D1}', "greek theta symbol"),
+    ('\u{3D2}', "greek upsilon with hook symbol"),
+    ('\u{3D3}', "greek upsilon with acute and hook symbol"),
+    ('\u{3D4}', "greek upsilon with diaeresis and hook symbol"),
+    ('\u{3D5}', "greek phi symbol"),
+    ('\u{3D6}', "greek pi symbol"),
+    ('\u{3D7}', "greek kai symbol"),
+    ('\u{3D8}', "greek letter archaic koppa"),
+    ('\u{3D9}', "greek small letter archaic koppa"),
+    ('\u{3DA}', "greek letter stigma"),
+    ('\u{3DB}', "greek small letter stigma"),
+    ('\u{3DC}', "greek letter digamma"),
+    ('\u{3DD}', "greek small letter digamma"),
+    ('\u{3DE}', "greek letter koppa"),
+    ('\u{3DF}', "greek small letter koppa"),
+    ('\u{3E0}', "greek letter sampi"),
+    ('\u{3E1}', "greek small letter sampi"),
+    ('\u{3E2}', "coptic capital letter shei"),
+    ('\u{3E3}', "coptic small letter shei"),
+    ('\u{3E4}', "coptic capital letter fei"),
+    ('\u{3E5}', "coptic small letter fei"),
+    ('\u{3E6}', "coptic capital letter khei"),
+    ('\u{3E7}', "coptic small letter khei"),
+    ('\u{3E8}', "coptic capital letter hori"),
+    ('\u{3E9}', "coptic small letter hori"),
+    ('\u{3EA}', "coptic capital letter gangia"),
+    ('\u{3EB}', "coptic small letter gangia"),
+    ('\u{3EC}', "coptic capital letter shima"),
+    ('\u{3ED}', "coptic small letter shima"),
+    ('\u{3EE}', "coptic capital letter dei"),
+    ('\u{3EF}', "coptic small letter dei"),
+    ('\u{3F0}', "greek kappa symbol"),
+    ('\u{3F1}', "greek rho symbol"),
+    ('\u{3F2}', "greek lunate sigma symbol"),
+    ('\u{3F3}', "greek letter yot"),
+    ('\u{3F4}', "greek capital theta symbol"),
+    ('\u{3F5}', "greek lunate epsilon symbol"),
+    ('\u{3F6}', "greek reversed lunate epsilon symbol"),
+    ('\u{3F7}', "greek capital letter sho"),
+    ('\u{3F8}', "greek small letter sho"),
+    ('\u{3F9}', "greek capital lunate sigma symbol"),
+    ('\u{3FA}', "greek capital letter san"),
+    ('\u{3FB}', "greek small letter san"),
+    ('\u{3FC}', "greek rho with stroke symbol"),
+    ('\u{3FD}', "greek capital reversed lunate sigma symbol"),
+    ('\u{3FE}', "greek capital dotted lunate sigma symbol"),
+    ('\u{3FF}', "greek capital reversed dotted lunate sigma symbol"),
+];
@@ -0,0 +1,81 @@
+//! The command history overlay lists the commands that were run in
+//! a pane, as recorded by the shell integration (OSC 133) Input zones.
+//! Choosing a command scrolls the viewport to its output, or the
+//! command can be typed into the shell again.
+use super::selector::{Choice, FuzzySelector, SelectorText};
+use crate::termwindow::TermWindow;
+use mux::pane::PaneId;
+use mux::termwiztermtab::TermWizTerminal;
+use wezterm_term::StableRowIndex;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandHistoryEntry {
+    /// The text of the command
+    pub command: String,
+    /// The first row of the output of the command, or the row of
+    /// the command itself if it didn't produce any output
+    pub output_row: StableRowIndex,
+}
+
+pub fn command_history(
+    pane_id: PaneId,
+    mut term: TermWizTerminal,
+    commands: Vec<CommandHistoryEntry>,
+    window: ::window::Window,
+) -> anyhow::Result<()> {
+    let selector = FuzzySelector::new(
+        commands,
+        SelectorText {
+            title: "Command History",
+            empty: "No commands were found; this requires shell integration: Escape to cancel",
+            noun: "commands",
+            help: "Enter to scroll to output, Tab to type the command",
+        },
+        |entry: &CommandHistoryEntry| entry.command.clone(),
+        |entry: &CommandHistoryEntry| format!(" {}", entry.command.replace('\n', "⏎")),
+    );
+
+    selector.run(&mut term, |entry, choice| {
+        match choice {
+            Choice::Activate => {
+                TermWindow::schedule_scroll_to_row(window.clone(), pane_id, entry.output_row);
+            }
+            Choice::Alternate => {
+                TermWindow::schedule_paste_to_pane(window.clone(), pane_id, entry.command.clone());
+            }
+        }
+        true
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::overlay::selector::fuzzy_search;
+
+    fn entry(command: &str, output_row: StableRowIndex) -> CommandHistoryEntry {
+        CommandHistoryEntry {
+            command: command.to_string(),
+            output_row,
+        }
+    }
+
+    #[test]
+    fn search_commands() {
+        let commands = vec![
+            entry("git status", 20),
+            entry("cargo build --release", 10),
+            entry("git status", 2),
+        ];
+        let search = |query: &str| {
+            fuzzy_search(&commands, query, |entry: &CommandHistoryEntry| {
+                entry.command.clone()
+            })
+        };
+        assert_eq!(search(""), vec![0, 1, 2]);
+        assert_eq!(search("cbr"), vec![1]);
+        // Repeated commands are listed separately, most recent first
+        assert_eq!(search("gst"), vec![0, 2]);
+        assert!(search("xyz").is_empty());
+    }
+}
@@ -9,6 +9,7 @@ use termwiz::color::ColorAttribute;
 use termwiz::input::{InputEvent, KeyCode, KeyEvent, MouseButtons, MouseEvent};
 use termwiz::surface::{Change, CursorVisibility, Position};
 use termwiz::terminal::Terminal;
+use wezterm_term::ClipboardSelection;
 
 fn run_confirmation_app(message: &str, term: &mut TermWizTerminal) -> anyhow::Result<bool> {
     term.set_raw_mode()?;
@@ -183,6 +184,28 @@ pub fn confirm_close_pane(
     Ok(())
 }
 
+pub fn confirm_restart_pane(
+    pane_id: PaneId,
+    mut term: TermWizTerminal,
+    window: ::window::Window,
+) -> anyhow::Result<()> {
+    if run_confirmation_app(
+        "🔁 Really kill and restart the command in this pane?",
+        &mut term,
+    )? {
+        promise::spawn::spawn_into_main_thread(async move {
+            let mux = Mux::get().unwrap();
+            if let Err(err) = mux.restart_pane(pane_id) {
+                log::error!("Failed to restart pane {}: {:#}", pane_id, err);
+            }
+        })
+        .detach();
+    }
+    TermWindow::schedule_cancel_overlay_for_pane(window, pane_id);
+
+    Ok(())
+}
+
 pub fn confirm_close_tab(
     tab_id: TabId,
     mut term: TermWizTerminal,
@@ -242,3 +265,25 @@ pub fn confirm_quit_program(
 
     Ok(())
 }
+
+pub fn confirm_clipboard_read(
+    pane_id: PaneId,
+    mut term: TermWizTerminal,
+    window: ::window::Window,
+    selection: ClipboardSelection,
+) -> anyhow::Result<()> {
+    let allow = run_confirmation_app(
+        "📋 A program running in this pane wants to read the contents of the clipboard. \
+         Allow programs in this pane to read the clipboard for the rest of this session?",
+        &mut term,
+    )?;
+    window.apply(move |term_window, _window| {
+        if let Some(term_window) = term_window.downcast_mut::<TermWindow>() {
+            term_window.clipboard_read_answered(pane_id, allow, selection);
+        }
+        Ok(())
+    });
+    TermWindow::schedule_cancel_overlay_for_pane(window, pane_id);
+
+    Ok(())
+}
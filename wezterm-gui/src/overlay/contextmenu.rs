@@ -0,0 +1,138 @@
+use mux::termwiztermtab::TermWizTerminal;
+use termwiz::cell::{AttributeChange, CellAttributes};
+use termwiz::color::ColorAttribute;
+use termwiz::input::{InputEvent, KeyCode, KeyEvent, MouseButtons, MouseEvent};
+use termwiz::surface::{Change, Position};
+use termwiz::terminal::Terminal;
+use window::MenuEntry;
+
+/// Shows `entries` as a list on systems that have no native context
+/// menus, and returns the id of the item that was chosen, if any
+pub fn context_menu(
+    mut term: TermWizTerminal,
+    entries: Vec<MenuEntry>,
+) -> anyhow::Result<Option<usize>> {
+    // Separators are shown as blank rows, which can't be chosen
+    let selectable: Vec<usize> = entries
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, entry)| match entry {
+            MenuEntry::Item { .. } => Some(idx),
+            MenuEntry::Separator => None,
+        })
+        .collect();
+    if selectable.is_empty() {
+        return Ok(None);
+    }
+    let mut active = 0;
+
+    term.set_raw_mode()?;
+    term.render(&[Change::Title("Menu".to_string())])?;
+
+    fn render(
+        active_row: usize,
+        entries: &[MenuEntry],
+        term: &mut TermWizTerminal,
+    ) -> termwiz::Result<()> {
+        let mut changes = vec![
+            Change::ClearScreen(ColorAttribute::Default),
+            Change::CursorPosition {
+                x: Position::Absolute(0),
+                y: Position::Absolute(0),
+            },
+            Change::Text(
+                "Select an action and press Enter to perform it.  Press Escape to cancel\r\n"
+                    .to_string(),
+            ),
+            Change::AllAttributes(CellAttributes::default()),
+        ];
+
+        for (idx, entry) in entries.iter().enumerate() {
+            match entry {
+                MenuEntry::Item {
+                    label, key_hint, ..
+                } => {
+                    if idx == active_row {
+                        changes.push(AttributeChange::Reverse(true).into());
+                    }
+                    let text = match key_hint {
+                        Some(hint) => format!(" {}  ({})\r\n", label, hint),
+                        None => format!(" {}\r\n", label),
+                    };
+                    changes.push(Change::Text(text));
+                    if idx == active_row {
+                        changes.push(AttributeChange::Reverse(false).into());
+                    }
+                }
+                MenuEntry::Separator => changes.push(Change::Text("\r\n".to_string())),
+            }
+        }
+
+        term.render(&changes)?;
+        term.flush()
+    }
+
+    let id_of = |row: usize| match &entries[row] {
+        MenuEntry::Item { id, .. } => Some(*id),
+        MenuEntry::Separator => None,
+    };
+
+    render(selectable[active], &entries, &mut term)?;
+
+    while let Ok(Some(event)) = term.poll_input(None) {
+        match event {
+            InputEvent::Key(KeyEvent {
+                key: KeyCode::Char('k'),
+                ..
+            })
+            | InputEvent::Key(KeyEvent {
+                key: KeyCode::UpArrow,
+                ..
+            }) => {
+                active = active.saturating_sub(1);
+            }
+            InputEvent::Key(KeyEvent {
+                key: KeyCode::Char('j'),
+                ..
+            })
+            | InputEvent::Key(KeyEvent {
+                key: KeyCode::DownArrow,
+                ..
+            }) => {
+                active = (active + 1).min(selectable.len() - 1);
+            }
+            InputEvent::Key(KeyEvent {
+                key: KeyCode::Escape,
+                ..
+            }) => {
+                break;
+            }
+            InputEvent::Key(KeyEvent {
+                key: KeyCode::Enter,
+                ..
+            }) => {
+                return Ok(id_of(selectable[active]));
+            }
+            InputEvent::Mouse(MouseEvent {
+                y, mouse_buttons, ..
+            }) => {
+                // The first row holds the instructions
+                let row = (y as usize).wrapping_sub(1);
+                if let Some(pos) = selectable.iter().position(|&idx| idx == row) {
+                    active = pos;
+                    if mouse_buttons == MouseButtons::LEFT {
+                        return Ok(id_of(row));
+                    }
+                }
+                if mouse_buttons != MouseButtons::NONE && mouse_buttons != MouseButtons::LEFT {
+                    // Treat any other mouse button as cancel
+                    break;
+                }
+            }
+            _ => {}
+        }
+        render(selectable[active], &entries, &mut term)?;
+    }
+
+    Ok(None)
+}
@@ -1,15 +1,21 @@
+use crate::overlay::PaneOverlay;
 use crate::selection::{SelectionCoordinate, SelectionRange};
 use crate::termwindow::TermWindow;
+use config::configuration;
 use config::keyassignment::ScrollbackEraseMode;
+use lazy_static::lazy_static;
 use mux::domain::DomainId;
 use mux::pane::{Pane, PaneId};
 use mux::renderable::*;
+use mux::Mux;
 use portable_pty::PtySize;
 use rangeset::RangeSet;
 use std::cell::{RefCell, RefMut};
 use std::ops::Range;
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
+use termwiz::hyperlink::{Hyperlink, Rule};
 use unicode_segmentation::*;
 use url::Url;
 use wezterm_term::color::ColorPalette;
@@ -18,6 +24,18 @@ use wezterm_term::{
 };
 use window::WindowOps;
 
+lazy_static! {
+    /// Matches file system paths that contain at least one slash, such
+    /// as `/etc/hosts`, `~/.bashrc` and `src/main.rs`.  This is used
+    /// alongside `hyperlink_rules` when jumping between links.
+    static ref PATH_RULE: Rule =
+        Rule::new(r"(?:~|\.{1,2}|[\w.@+-]+)?(?:/[\w.@%+~-]+)+/?", "$0")
+            .expect("builtin path rule regex to be valid");
+}
+
+/// How many rows are examined at a time when searching for a link
+const LINK_SEARCH_CHUNK: StableRowIndex = 100;
+
 pub struct CopyOverlay {
     delegate: Rc<dyn Pane>,
     render: RefCell<CopyRenderable>,
@@ -39,7 +57,7 @@ struct Dimensions {
 }
 
 impl CopyOverlay {
-    pub fn with_pane(term_window: &TermWindow, pane: &Rc<dyn Pane>) -> Rc<dyn Pane> {
+    pub fn with_pane(term_window: &TermWindow, pane: &Rc<dyn Pane>) -> Rc<CopyOverlay> {
         let mut cursor = pane.get_cursor_position();
         cursor.shape = termwiz::surface::CursorShape::SteadyBlock;
 
@@ -57,10 +75,37 @@ impl CopyOverlay {
         })
     }
 
-    pub fn viewport_changed(&self, viewport: Option<StableRowIndex>) {
+    /// Move the copy mode cursor to the specified position, clamped
+    /// to the scrollback, extending the selection to it if one has
+    /// been started.  The viewport is scrolled to keep the cursor
+    /// visible.
+    pub fn move_cursor_to(&self, x: usize, y: StableRowIndex) {
+        let mut r = self.render.borrow_mut();
+        r.cursor.x = x;
+        r.cursor.y = y;
+        r.select_to_cursor_pos();
+    }
+}
+
+impl PaneOverlay for CopyOverlay {
+    fn viewport_changed(&self, viewport: Option<StableRowIndex>) {
         let mut r = self.render.borrow_mut();
         r.viewport = viewport;
     }
+
+    fn restore_viewport_on_dismiss(&self) -> bool {
+        true
+    }
+
+    fn rows_evicted(&self, first_row: StableRowIndex) {
+        let mut r = self.render.borrow_mut();
+        if r.start.map(|start| start.y < first_row).unwrap_or(false) {
+            r.start.take();
+        }
+        if r.cursor.y < first_row {
+            r.clamp_cursor_to_scrollback();
+        }
+    }
 }
 
 impl CopyRenderable {
@@ -114,12 +159,14 @@ impl CopyRenderable {
     }
 
     fn dimensions(&self) -> Dimensions {
-        const VERTICAL_GAP: isize = 5;
         let dims = self.delegate.get_dimensions();
-        let vertical_gap = if dims.physical_top <= VERTICAL_GAP {
+        let context = configuration().scroll_context_lines as isize;
+        // Don't let the context consume the whole viewport
+        let context = context.min((dims.viewport_rows as isize - 1) / 2);
+        let vertical_gap = if dims.physical_top <= context {
             1
         } else {
-            VERTICAL_GAP
+            context.max(1)
         };
         let top = self.viewport.unwrap_or_else(|| dims.physical_top);
         Dimensions {
@@ -163,7 +210,6 @@ impl CopyRenderable {
     }
 
     fn close(&self) {
-        self.set_viewport(None);
         TermWindow::schedule_cancel_overlay_for_pane(self.window.clone(), self.delegate.pane_id());
     }
 
@@ -364,6 +410,94 @@ impl CopyRenderable {
         self.select_to_cursor_pos();
     }
 
+    /// Returns the links that start in `rows`, in order, along with the
+    /// row and the range of columns that they occupy on it.
+    fn links_in_rows(
+        &self,
+        rows: Range<StableRowIndex>,
+    ) -> Vec<(StableRowIndex, Range<usize>, Arc<Hyperlink>)> {
+        let rules = link_rules();
+
+        // Fetch the row above too, so that wrapped links can be recognized
+        let (top, lines) = self
+            .delegate
+            .get_lines_with_hyperlinks_applied(rows.start.saturating_sub(1)..rows.end, &rules);
+        links_starting_in(rows, top, &lines)
+    }
+
+    /// Moves the cursor to the start of the next link or path
+    fn move_to_next_link(&mut self) {
+        let dims = self.delegate.get_dimensions();
+        let max_row = dims.scrollback_top + dims.scrollback_rows as StableRowIndex;
+        let cursor = self.cursor;
+        let mut start = cursor.y;
+        while start < max_row {
+            let end = (start + LINK_SEARCH_CHUNK).min(max_row);
+            let next = self
+                .links_in_rows(start..end)
+                .into_iter()
+                .find(|(y, range, _)| *y > cursor.y || range.start > cursor.x);
+            if let Some((y, range, _)) = next {
+                self.cursor.y = y;
+                self.cursor.x = range.start;
+                self.select_to_cursor_pos();
+                return;
+            }
+            start = end;
+        }
+    }
+
+    /// Moves the cursor to the start of the prior link or path
+    fn move_to_prior_link(&mut self) {
+        let dims = self.delegate.get_dimensions();
+        let cursor = self.cursor;
+        let mut end = cursor.y + 1;
+        while end > dims.scrollback_top {
+            let start = (end - LINK_SEARCH_CHUNK).max(dims.scrollback_top);
+            let prior = self
+                .links_in_rows(start..end)
+                .into_iter()
+                .rev()
+                .find(|(y, range, _)| *y < cursor.y || range.start < cursor.x);
+            if let Some((y, range, _)) = prior {
+                self.cursor.y = y;
+                self.cursor.x = range.start;
+                self.select_to_cursor_pos();
+                return;
+            }
+            end = start;
+        }
+    }
+
+    /// Opens the link or path under the cursor
+    fn open_link_at_cursor(&self) {
+        let rules = link_rules();
+        let y = self.cursor.y;
+        let (_top, lines) = self
+            .delegate
+            .get_lines_with_hyperlinks_applied(y..y + 1, &rules);
+        let link = match lines.get(0).and_then(|line| {
+            line.cells()
+                .get(self.cursor.x)
+                .and_then(|cell| cell.attrs().hyperlink().cloned())
+        }) {
+            Some(link) => link,
+            None => return,
+        };
+
+        let uri = resolve_link(link.uri(), self.delegate.get_current_working_dir());
+        let pane_id = self.delegate.pane_id();
+        self.window.apply(move |term_window, _window| {
+            if let Some(term_window) = term_window.downcast_mut::<TermWindow>() {
+                let mux = Mux::get().unwrap();
+                if let Some(pane) = mux.get_pane(pane_id) {
+                    term_window.open_link(&pane, &uri);
+                }
+            }
+            Ok(())
+        });
+    }
+
     fn toggle_selection_by_cell(&mut self) {
         if self.start.take().is_none() {
             let coord = SelectionCoordinate {
@@ -471,6 +605,15 @@ impl Pane for CopyOverlay {
             (KeyCode::Char('L'), KeyModifiers::NONE) => {
                 self.render.borrow_mut().move_to_viewport_bottom();
             }
+            (KeyCode::Char(']'), KeyModifiers::NONE) => {
+                self.render.borrow_mut().move_to_next_link();
+            }
+            (KeyCode::Char('['), KeyModifiers::NONE) => {
+                self.render.borrow_mut().move_to_prior_link();
+            }
+            (KeyCode::Char('o'), KeyModifiers::NONE) => {
+                self.render.borrow().open_link_at_cursor();
+            }
             (KeyCode::PageUp, KeyModifiers::NONE) | (KeyCode::Char('b'), KeyModifiers::CTRL) => self.render.borrow_mut().page_up(),
             (KeyCode::PageDown, KeyModifiers::NONE) | (KeyCode::Char('f'), KeyModifiers::CTRL) => self.render.borrow_mut().page_down(),
             _ => {}
@@ -536,6 +679,89 @@ impl Pane for CopyOverlay {
     }
 }
 
+/// The rules that are used to find links and paths in copy mode
+fn link_rules() -> Vec<Rule> {
+    let mut rules = configuration().hyperlink_rules.clone();
+    rules.push(PATH_RULE.clone());
+    rules
+}
+
+/// Returns the range of columns occupied by each of the links on `line`
+fn links_in_line(line: &Line) -> Vec<(Range<usize>, Arc<Hyperlink>)> {
+    let mut links: Vec<(Range<usize>, Arc<Hyperlink>)> = vec![];
+    let cells = line.cells();
+    let mut x = 0;
+    while x < cells.len() {
+        let cell = &cells[x];
+        let width = cell.width().max(1);
+        if let Some(link) = cell.attrs().hyperlink() {
+            match links.last_mut() {
+                Some((range, last)) if range.end == x && Arc::ptr_eq(last, link) => {
+                    range.end = x + width;
+                }
+                _ => links.push((x..x + width, Arc::clone(link))),
+            }
+        }
+        x += width;
+    }
+    links
+}
+
+/// Returns the links that start in `rows`, in order, along with the
+/// row and the range of columns that they occupy on it.  `lines` are
+/// the lines that have had the hyperlink rules applied, starting with
+/// row `top`.  The parts of links that have wrapped from the row above
+/// are not included.
+fn links_starting_in(
+    rows: Range<StableRowIndex>,
+    top: StableRowIndex,
+    lines: &[Line],
+) -> Vec<(StableRowIndex, Range<usize>, Arc<Hyperlink>)> {
+    let mut links = vec![];
+    let mut prior: Option<(Range<usize>, Arc<Hyperlink>)> = None;
+    for (idx, line) in lines.iter().enumerate() {
+        let y = top + idx as StableRowIndex;
+        let width = line.cells().len();
+        let line_links = links_in_line(line);
+        for (range, link) in &line_links {
+            let wrapped = range.start == 0
+                && prior
+                    .as_ref()
+                    .map(|(_, prior_link)| Arc::ptr_eq(prior_link, link))
+                    .unwrap_or(false);
+            if !wrapped && rows.contains(&y) {
+                links.push((y, range.clone(), Arc::clone(link)));
+            }
+        }
+        prior = line_links
+            .last()
+            .filter(|(range, _)| line.last_cell_was_wrapped() && range.end >= width)
+            .cloned();
+    }
+    links
+}
+
+/// Links that were matched by PATH_RULE, or by a hyperlink rule that
+/// doesn't produce a URL, are treated as file system paths and turned
+/// into file URLs.  Relative paths are relative to `cwd`, the working
+/// directory of the pane.
+fn resolve_link(link: &str, cwd: Option<Url>) -> String {
+    if Url::parse(link).is_ok() {
+        return link.to_string();
+    }
+    let path = match link.strip_prefix("~/") {
+        Some(rest) => config::HOME_DIR.join(rest),
+        None => PathBuf::from(link),
+    };
+    let path = match cwd.and_then(|cwd| cwd.to_file_path().ok()) {
+        Some(cwd) if path.is_relative() => cwd.join(path),
+        _ => path,
+    };
+    Url::from_file_path(&path)
+        .map(|url| url.to_string())
+        .unwrap_or_else(|_| link.to_string())
+}
+
 fn is_whitespace_word(word: &str) -> bool {
     if let Some(c) = word.chars().next() {
         c.is_whitespace()
@@ -543,3 +769,75 @@ fn is_whitespace_word(word: &str) -> bool {
         false
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use mux::pane::LogicalLine;
+
+    /// Builds the physical lines for a single logical line that has
+    /// been wrapped into `rows`, and applies the path rule to them the
+    /// same way that `get_lines_with_hyperlinks_applied` does
+    fn wrapped_lines(rows: &[&str]) -> Vec<Line> {
+        let attrs = Default::default();
+        let mut physical_lines: Vec<Line> = rows
+            .iter()
+            .map(|text| Line::from_text(text, &attrs))
+            .collect();
+        let num_rows = physical_lines.len();
+        for line in &mut physical_lines[..num_rows - 1] {
+            line.set_last_cell_was_wrapped(true);
+        }
+        let mut logical = LogicalLine {
+            physical_lines,
+            logical: Line::from_text(&rows.concat(), &attrs),
+            first_row: 0,
+        };
+        logical.apply_hyperlink_rules(&[PATH_RULE.clone()]);
+        logical.physical_lines
+    }
+
+    fn summarize(
+        links: Vec<(StableRowIndex, Range<usize>, Arc<Hyperlink>)>,
+    ) -> Vec<(StableRowIndex, Range<usize>, String)> {
+        links
+            .into_iter()
+            .map(|(y, range, link)| (y, range, link.uri().to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn path_forms() {
+        let lines = wrapped_lines(&["cat /etc/hosts ~/.bashrc src/main.rs README"]);
+        assert_eq!(
+            summarize(links_starting_in(0..1, 0, &lines)),
+            vec![
+                (0, 4..14, "/etc/hosts".to_string()),
+                (0, 15..24, "~/.bashrc".to_string()),
+                (0, 25..36, "src/main.rs".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn link_wrapped_across_rows() {
+        let mut lines = wrapped_lines(&["ls /usr/lo", "cal/bin ok"]);
+        lines.extend(wrapped_lines(&["x ./run.sh"]));
+
+        // The wrapped link is only reported on the row where it starts
+        assert_eq!(
+            summarize(links_starting_in(0..3, 0, &lines)),
+            vec![
+                (0, 3..10, "/usr/local/bin".to_string()),
+                (2, 2..10, "./run.sh".to_string()),
+            ]
+        );
+
+        // and the remainder isn't mistaken for a link of its own when
+        // the search begins on the row below
+        assert_eq!(
+            summarize(links_starting_in(1..3, 0, &lines)),
+            vec![(2, 2..10, "./run.sh".to_string())]
+        );
+    }
+}
@@ -0,0 +1,71 @@
+//! The debug overlay shows the most recent log messages, and follows
+//! new ones as they are logged.  The messages are those that pass the
+//! current log filters, which can be changed while wezterm is running
+//! with `wezterm cli set-log-level`.
+use mux::termwiztermtab::TermWizTerminal;
+use std::time::Duration;
+use termwiz::color::ColorAttribute;
+use termwiz::input::{InputEvent, KeyCode, KeyEvent};
+use termwiz::surface::{Change, Position};
+use termwiz::terminal::Terminal;
+
+/// How often to check for new messages
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+fn render(term: &mut TermWizTerminal, lines: &[String]) -> termwiz::Result<()> {
+    let size = term.get_screen_size()?;
+
+    let mut text = format!(
+        "Log filters: `{}`.  Press Escape to close",
+        env_bootstrap::log_filters()
+    );
+    // Show as many of the most recent messages as fit below the header
+    let available = size.rows.saturating_sub(1);
+    for line in lines.iter().skip(lines.len().saturating_sub(available)) {
+        text.push_str("\r\n");
+        text.extend(line.chars().take(size.cols));
+    }
+
+    term.render(&[
+        Change::ClearScreen(ColorAttribute::Default),
+        Change::CursorPosition {
+            x: Position::Absolute(0),
+            y: Position::Absolute(0),
+        },
+        Change::Text(text),
+    ])?;
+    term.flush()
+}
+
+pub fn debug_overlay(mut term: TermWizTerminal) -> anyhow::Result<()> {
+    term.set_raw_mode()?;
+    term.render(&[Change::Title("Debug".to_string())])?;
+
+    let mut shown: Option<Vec<String>> = None;
+    loop {
+        let lines = env_bootstrap::recent_log_lines();
+        if shown.as_ref() != Some(&lines) {
+            render(&mut term, &lines)?;
+            shown.replace(lines);
+        }
+
+        match term.poll_input(Some(REFRESH_INTERVAL)) {
+            Ok(Some(InputEvent::Key(KeyEvent {
+                key: KeyCode::Escape,
+                ..
+            })))
+            | Ok(Some(InputEvent::Key(KeyEvent {
+                key: KeyCode::Char('q'),
+                ..
+            }))) => break,
+            Ok(Some(InputEvent::Resized { .. })) => {
+                shown.take();
+            }
+            Ok(_) => {}
+            // The overlay was closed
+            Err(_) => break,
+        }
+    }
+
+    Ok(())
+}
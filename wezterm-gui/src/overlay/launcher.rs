@@ -10,7 +10,7 @@ use crate::termwindow::spawn::SpawnWhere;
 use crate::termwindow::TermWindow;
 use anyhow::anyhow;
 use config::configuration;
-use config::keyassignment::{SpawnCommand, SpawnTabDomain};
+use config::keyassignment::{PaneLayout, SpawnCommand, SpawnTabDomain};
 use mux::domain::{DomainId, DomainState};
 use mux::tab::TabId;
 use mux::termwiztermtab::TermWizTerminal;
@@ -34,6 +34,10 @@ enum Entry {
         label: String,
         domain: DomainId,
     },
+    Layout {
+        label: String,
+        layout: PaneLayout,
+    },
 }
 
 impl Entry {
@@ -41,6 +45,7 @@ impl Entry {
         match self {
             Entry::Spawn { label, .. } => label,
             Entry::Attach { label, .. } => label,
+            Entry::Layout { label, .. } => label,
         }
     }
 }
@@ -130,6 +135,15 @@ pub fn launcher(
         });
     }
 
+    let mut layout_names: Vec<&String> = config.tab_layouts.keys().collect();
+    layout_names.sort();
+    for name in layout_names {
+        entries.push(Entry::Layout {
+            label: format!("New Tab (layout: {})", name),
+            layout: config.tab_layouts[name].clone(),
+        });
+    }
+
     #[cfg(windows)]
     {
         if config.add_wsl_distributions_to_launch_menu {
@@ -223,6 +237,12 @@ pub fn launcher(
                 })
                 .detach();
             }
+            Entry::Layout { layout, .. } => {
+                promise::spawn::spawn_into_main_thread(async move {
+                    TermWindow::spawn_layout_impl(&layout, size, mux_window_id, clipboard);
+                })
+                .detach();
+            }
             Entry::Attach { domain, .. } => {
                 promise::spawn::spawn_into_main_thread(async move {
                     // We can't inline do_domain_attach here directly
@@ -5,22 +5,122 @@ use mux::termwiztermtab::{allocate, TermWizTerminal};
 use portable_pty::PtySize;
 use std::pin::Pin;
 use std::rc::Rc;
+use wezterm_term::StableRowIndex;
 
+mod charselect;
+mod commandhistory;
 mod confirm_close_pane;
+mod contextmenu;
 mod copy;
+mod debug;
 mod launcher;
+mod panenavigator;
+mod pastehistory;
 mod search;
+mod selector;
 mod tabnavigator;
 
+pub use charselect::char_select;
+pub use commandhistory::{command_history, CommandHistoryEntry};
+pub use confirm_close_pane::confirm_clipboard_read;
 pub use confirm_close_pane::confirm_close_pane;
 pub use confirm_close_pane::confirm_close_tab;
 pub use confirm_close_pane::confirm_close_window;
 pub use confirm_close_pane::confirm_quit_program;
+pub use confirm_close_pane::confirm_restart_pane;
+pub use contextmenu::context_menu;
 pub use copy::CopyOverlay;
+pub use debug::debug_overlay;
 pub use launcher::launcher;
+pub use panenavigator::{list_panes, pane_navigator};
+pub use pastehistory::{paste_from_history, remember_clipboard_text};
 pub use search::SearchOverlay;
 pub use tabnavigator::tab_navigator;
 
+/// Implemented by overlays that are layered over an existing pane
+/// and delegate to it for their content, such as search and copy mode.
+/// This allows the TermWindow to manage their lifecycle without
+/// needing to know their concrete type.
+pub trait PaneOverlay {
+    /// Called when the viewport of the underlying pane is changed
+    fn viewport_changed(&self, _viewport: Option<StableRowIndex>) {}
+
+    /// If true, when the overlay is dismissed the viewport of the
+    /// underlying pane is restored to the position that it had
+    /// when the overlay was assigned.
+    fn restore_viewport_on_dismiss(&self) -> bool {
+        false
+    }
+
+    /// Called when rows before `first_row` have been evicted from
+    /// the scrollback of the underlying pane, so that the overlay
+    /// can drop any state that refers to them.
+    fn rows_evicted(&self, _first_row: StableRowIndex) {}
+}
+
+/// Tracks an overlay that has been assigned to a pane.
+/// While assigned, the overlay renders in place of the pane
+/// and receives its input.
+#[derive(Clone)]
+pub struct OverlayState {
+    pub pane: Rc<dyn Pane>,
+    hooks: Option<Rc<dyn PaneOverlay>>,
+    saved_viewport: Option<StableRowIndex>,
+}
+
+impl OverlayState {
+    /// An overlay, such as a termwiz based confirmation prompt,
+    /// that is independent of the pane that it is layered over
+    pub fn new(pane: Rc<dyn Pane>) -> Self {
+        Self {
+            pane,
+            hooks: None,
+            saved_viewport: None,
+        }
+    }
+
+    /// An overlay that wants to be kept informed about the pane
+    /// that it is layered over
+    pub fn with_hooks<T: Pane + PaneOverlay + 'static>(overlay: Rc<T>) -> Self {
+        let hooks: Rc<dyn PaneOverlay> = overlay.clone();
+        Self {
+            pane: overlay,
+            hooks: Some(hooks),
+            saved_viewport: None,
+        }
+    }
+
+    pub(crate) fn saved_viewport(&self) -> Option<StableRowIndex> {
+        self.saved_viewport
+    }
+
+    pub(crate) fn set_saved_viewport(&mut self, viewport: Option<StableRowIndex>) {
+        self.saved_viewport = viewport;
+    }
+
+    pub fn viewport_changed(&self, viewport: Option<StableRowIndex>) {
+        if let Some(hooks) = self.hooks.as_ref() {
+            hooks.viewport_changed(viewport);
+        }
+    }
+
+    pub fn rows_evicted(&self, first_row: StableRowIndex) {
+        if let Some(hooks) = self.hooks.as_ref() {
+            hooks.rows_evicted(first_row);
+        }
+    }
+
+    /// Returns the viewport that should be applied to the underlying
+    /// pane when this overlay is dismissed, or None if the viewport
+    /// should be left alone.
+    pub fn viewport_to_restore(&self) -> Option<Option<StableRowIndex>> {
+        match self.hooks.as_ref() {
+            Some(hooks) if hooks.restore_viewport_on_dismiss() => Some(self.saved_viewport),
+            _ => None,
+        }
+    }
+}
+
 pub fn start_overlay<T, F>(
     term_window: &TermWindow,
     tab: &Rc<Tab>,
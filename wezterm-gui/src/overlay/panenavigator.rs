@@ -0,0 +1,219 @@
+//! The pane navigator overlay lists every pane in every window,
+//! allows searching them, and activates the chosen pane, bringing
+//! its window to the front.
+use super::selector::{Choice, FuzzySelector, SelectorText};
+use crate::frontend::front_end;
+use crate::termwindow::TermWindow;
+use anyhow::anyhow;
+use mux::pane::{Pane, PaneId};
+use mux::tab::TabId;
+use mux::termwiztermtab::TermWizTerminal;
+use mux::window::WindowId;
+use mux::Mux;
+use std::rc::Rc;
+
+/// Describes a pane for the list.  The overlay runs in a different
+/// thread from the mux, so this is captured up front.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaneEntry {
+    pub pane_id: PaneId,
+    pub tab_id: TabId,
+    pub window_id: WindowId,
+    pub title: String,
+    pub cwd: Option<String>,
+    pub domain: String,
+    /// The last line of output that is visible in the pane
+    pub snippet: String,
+}
+
+impl PaneEntry {
+    fn new(pane: &Rc<dyn Pane>, tab_id: TabId, window_id: WindowId) -> Self {
+        let mux = Mux::get().unwrap();
+        let domain = mux
+            .get_domain(pane.domain_id())
+            .map(|domain| domain.domain_name().to_string())
+            .unwrap_or_default();
+        let cwd = pane
+            .get_current_working_dir()
+            .map(|url| match url.scheme() {
+                "file" => url.path().to_string(),
+                _ => url.to_string(),
+            });
+        Self {
+            pane_id: pane.pane_id(),
+            tab_id,
+            window_id,
+            title: pane.get_title(),
+            cwd,
+            domain,
+            snippet: last_output(pane),
+        }
+    }
+
+    /// The text that the search is matched against
+    fn search_text(&self) -> String {
+        format!(
+            "{} {} {} {}",
+            self.title,
+            self.cwd.as_deref().unwrap_or(""),
+            self.domain,
+            self.snippet
+        )
+    }
+
+    fn label(&self) -> String {
+        let mut label = format!(" {} [{}]", self.title, self.domain);
+        if let Some(cwd) = &self.cwd {
+            label.push_str(&format!(" {}", cwd));
+        }
+        if !self.snippet.is_empty() {
+            label.push_str(&format!(" \u{2502} {}", self.snippet));
+        }
+        label
+    }
+}
+
+/// Returns the bottom-most non-blank line of the viewport of `pane`
+fn last_output(pane: &Rc<dyn Pane>) -> String {
+    let dims = pane.get_dimensions();
+    let (_first, lines) =
+        pane.get_lines(dims.physical_top..dims.physical_top + dims.viewport_rows as isize);
+    lines
+        .iter()
+        .rev()
+        .map(|line| line.as_str().trim().to_string())
+        .find(|text| !text.is_empty())
+        .unwrap_or_default()
+}
+
+/// Returns a description of each pane in each window, in the order
+/// of the windows and then of the tabs within them
+pub fn list_panes() -> Vec<PaneEntry> {
+    let mux = Mux::get().unwrap();
+    let mut entries = vec![];
+    for window_id in mux.iter_windows() {
+        let window = match mux.get_window(window_id) {
+            Some(window) => window,
+            None => continue,
+        };
+        for tab in window.iter() {
+            for pane in tab.iter_panes_ignoring_zoom() {
+                entries.push(PaneEntry::new(&pane, tab.tab_id(), window_id));
+            }
+        }
+    }
+    entries
+}
+
+/// Makes the pane described by `entry` the active pane of its tab and
+/// window, and brings that window to the front.  If the mux window
+/// has no GUI window, for example because it belongs to a domain that
+/// was attached without one, a GUI window is created for it.
+fn activate_pane(entry: &PaneEntry) {
+    let (pane_id, tab_id, window_id) = (entry.pane_id, entry.tab_id, entry.window_id);
+    promise::spawn::spawn_into_main_thread(async move {
+        let mux = Mux::get().unwrap();
+        let tab = mux
+            .get_tab(tab_id)
+            .ok_or_else(|| anyhow!("no such tab {}", tab_id))?;
+        let pane = mux
+            .get_pane(pane_id)
+            .ok_or_else(|| anyhow!("no such pane {}", pane_id))?;
+        {
+            let mut window = mux
+                .get_window_mut(window_id)
+                .ok_or_else(|| anyhow!("no such window {}", window_id))?;
+            let tab_idx = window
+                .idx_by_id(tab_id)
+                .ok_or_else(|| anyhow!("tab {} is no longer in window {}", tab_id, window_id))?;
+            window.save_and_then_set_active(tab_idx);
+        }
+        if tab.is_zoomed() && tab.get_active_pane().map(|p| p.pane_id()) != Some(pane_id) {
+            tab.set_zoomed(false);
+        }
+        tab.set_active_pane(&pane);
+
+        let gui_window = front_end()
+            .and_then(|fe| {
+                fe.gui_windows()
+                    .into_iter()
+                    .find(|(id, _)| *id == window_id)
+            })
+            .map(|(_, window)| window);
+        match gui_window {
+            Some(window) => window.focus(),
+            None => TermWindow::new_window(window_id)?,
+        }
+        anyhow::Result::<()>::Ok(())
+    })
+    .detach();
+}
+
+pub fn pane_navigator(
+    pane_id: PaneId,
+    mut term: TermWizTerminal,
+    panes: Vec<PaneEntry>,
+) -> anyhow::Result<()> {
+    let mut selector = FuzzySelector::new(
+        panes,
+        SelectorText {
+            title: "Pane Navigator",
+            empty: "There are no panes: Escape to cancel",
+            noun: "panes",
+            help: "Enter to activate",
+        },
+        PaneEntry::search_text,
+        PaneEntry::label,
+    );
+    selector.select_first(|entry| entry.pane_id == pane_id);
+
+    selector.run(&mut term, |entry, choice| match choice {
+        Choice::Activate => {
+            activate_pane(entry);
+            true
+        }
+        Choice::Alternate => false,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::overlay::selector::fuzzy_search;
+
+    fn entry(pane_id: PaneId, title: &str, cwd: Option<&str>, domain: &str) -> PaneEntry {
+        PaneEntry {
+            pane_id,
+            tab_id: 0,
+            window_id: 0,
+            title: title.to_string(),
+            cwd: cwd.map(str::to_string),
+            domain: domain.to_string(),
+            snippet: String::new(),
+        }
+    }
+
+    #[test]
+    fn search_panes() {
+        let panes = vec![
+            entry(1, "vim", Some("/home/wez/src/wezterm"), "local"),
+            entry(2, "htop", None, "devbox"),
+            entry(3, "bash", Some("/tmp"), "devbox"),
+        ];
+        let search = |query: &str| fuzzy_search(&panes, query, PaneEntry::search_text);
+        assert_eq!(search(""), vec![0, 1, 2]);
+        assert_eq!(search("wezterm"), vec![0]);
+        assert_eq!(search("devbox"), vec![1, 2]);
+        assert_eq!(search("bash tmp"), vec![2]);
+        assert!(search("xyz").is_empty());
+    }
+
+    #[test]
+    fn label() {
+        let mut pane = entry(1, "vim", Some("/tmp"), "local");
+        assert_eq!(pane.label(), " vim [local] /tmp");
+        pane.snippet = "-- INSERT --".to_string();
+        pane.cwd = None;
+        assert_eq!(pane.label(), " vim [local] \u{2502} -- INSERT --");
+    }
+}
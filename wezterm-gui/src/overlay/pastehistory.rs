@@ -0,0 +1,119 @@
+//! The paste history overlay lists the text that was recently copied
+//! to the clipboard from wezterm, allows searching it, and pastes the
+//! chosen entry into the pane.
+use super::selector::{Choice, FuzzySelector, SelectorText};
+use crate::termwindow::TermWindow;
+use lazy_static::lazy_static;
+use mux::pane::PaneId;
+use mux::termwiztermtab::TermWizTerminal;
+use std::sync::Mutex;
+
+lazy_static! {
+    /// The text that was most recently copied, most recent first.
+    /// This is shared by all windows for the lifetime of the process.
+    static ref HISTORY: Mutex<Vec<String>> = Mutex::new(vec![]);
+}
+
+/// Record `text` as the most recently copied text, retaining at
+/// most `max_entries` entries.  Copying text that is already present
+/// moves it to the front rather than adding a duplicate.
+pub fn remember_clipboard_text(text: &str, max_entries: usize) {
+    let mut history = HISTORY.lock().unwrap();
+    remember(&mut history, text, max_entries);
+}
+
+fn remember(history: &mut Vec<String>, text: &str, max_entries: usize) {
+    if text.trim().is_empty() {
+        return;
+    }
+    history.retain(|entry| entry != text);
+    history.insert(0, text.to_string());
+    history.truncate(max_entries);
+}
+
+/// Produces a single line representation of `text` for the list.
+/// Newlines and tabs are shown as visible symbols, and the number
+/// of lines is shown for multi-line entries.
+fn summarize(text: &str) -> String {
+    let num_lines = text.trim_end_matches('\n').lines().count();
+    let mut summary: String = text
+        .chars()
+        .map(|c| match c {
+            '\n' => '⏎',
+            '\t' => '→',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect();
+    if num_lines > 1 {
+        summary = format!("({} lines) {}", num_lines, summary);
+    }
+    summary
+}
+
+fn paste_entry(window: &::window::Window, pane_id: PaneId, text: &str) {
+    {
+        // Move it to the front, but don't trim the history here; that
+        // happens the next time something is copied
+        let mut history = HISTORY.lock().unwrap();
+        let max_entries = history.len();
+        remember(&mut history, text, max_entries);
+    }
+    TermWindow::schedule_paste_to_pane(window.clone(), pane_id, text.to_string());
+}
+
+pub fn paste_from_history(
+    pane_id: PaneId,
+    mut term: TermWizTerminal,
+    window: ::window::Window,
+) -> anyhow::Result<()> {
+    let history = HISTORY.lock().unwrap().clone();
+    let selector = FuzzySelector::new(
+        history,
+        SelectorText {
+            title: "Paste From History",
+            empty: "Nothing has been copied yet: Escape to cancel",
+            noun: "entries",
+            help: "Enter to paste",
+        },
+        String::clone,
+        |text| format!(" {}", summarize(text)),
+    );
+
+    selector.run(&mut term, |text, choice| match choice {
+        Choice::Activate => {
+            paste_entry(&window, pane_id, text);
+            true
+        }
+        Choice::Alternate => false,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn history_is_most_recent_first_without_duplicates() {
+        let mut history = vec![];
+        remember(&mut history, "one", 3);
+        remember(&mut history, "two", 3);
+        remember(&mut history, "  \n", 3);
+        remember(&mut history, "one", 3);
+        assert_eq!(history, vec!["one", "two"]);
+
+        remember(&mut history, "three", 3);
+        remember(&mut history, "four", 3);
+        assert_eq!(history, vec!["four", "three", "one"]);
+
+        remember(&mut history, "five", 0);
+        assert!(history.is_empty());
+    }
+
+    #[test]
+    fn summary() {
+        assert_eq!(summarize("ls -l"), "ls -l");
+        assert_eq!(summarize("ls\tfoo\n"), "ls→foo⏎");
+        assert_eq!(summarize("a\nb\n"), "(2 lines) a⏎b⏎");
+    }
+}
@@ -1,5 +1,7 @@
+use crate::overlay::PaneOverlay;
 use crate::selection::{SelectionCoordinate, SelectionRange};
 use crate::termwindow::TermWindow;
+use config::configuration;
 use config::keyassignment::ScrollbackEraseMode;
 use mux::domain::DomainId;
 use mux::pane::{Pane, PaneId, Pattern, SearchResult};
@@ -18,6 +20,14 @@ use wezterm_term::color::ColorPalette;
 use wezterm_term::{Clipboard, KeyCode, KeyModifiers, Line, MouseEvent, StableRowIndex};
 use window::WindowOps;
 
+/// Orders the matches from the top of the scrollback to the bottom,
+/// and left to right within a row, which is the order that
+/// `SearchOverlay::results` reports them in
+fn sort_results(mut results: Vec<SearchResult>) -> Vec<SearchResult> {
+    results.sort();
+    results
+}
+
 pub struct SearchOverlay {
     renderer: RefCell<SearchRenderable>,
     delegate: Rc<dyn Pane>,
@@ -54,7 +64,7 @@ impl SearchOverlay {
         term_window: &TermWindow,
         pane: &Rc<dyn Pane>,
         pattern: Pattern,
-    ) -> Rc<dyn Pane> {
+    ) -> Rc<SearchOverlay> {
         let viewport = term_window.get_viewport(pane.pane_id());
         let dims = pane.get_dimensions();
 
@@ -83,7 +93,21 @@ impl SearchOverlay {
         })
     }
 
-    pub fn viewport_changed(&self, viewport: Option<StableRowIndex>) {
+    /// Returns the matches for the current pattern, ordered from the
+    /// top of the scrollback to the bottom
+    pub fn results(&self) -> Vec<SearchResult> {
+        self.renderer.borrow().results.clone()
+    }
+
+    /// Returns the currently highlighted match, if any
+    pub fn current_result(&self) -> Option<SearchResult> {
+        let r = self.renderer.borrow();
+        r.result_pos.and_then(|idx| r.results.get(idx).cloned())
+    }
+}
+
+impl PaneOverlay for SearchOverlay {
+    fn viewport_changed(&self, viewport: Option<StableRowIndex>) {
         let mut render = self.renderer.borrow_mut();
         if render.viewport != viewport {
             if let Some(last) = render.last_bar_pos.take() {
@@ -95,6 +119,10 @@ impl SearchOverlay {
             render.viewport = viewport;
         }
     }
+
+    fn rows_evicted(&self, first_row: StableRowIndex) {
+        self.renderer.borrow_mut().evict_rows_before(first_row);
+    }
 }
 
 impl Pane for SearchOverlay {
@@ -138,11 +166,27 @@ impl Pane for SearchOverlay {
                     let prior = if *cur > 0 {
                         cur - 1
                     } else {
+                        r.search_wrapped();
                         r.results.len() - 1
                     };
                     r.activate_match_number(prior);
                 }
             }
+            (KeyCode::Home, KeyModifiers::NONE) => {
+                // Move to the first match
+                let mut r = self.renderer.borrow_mut();
+                if !r.results.is_empty() {
+                    r.activate_match_number(0);
+                }
+            }
+            (KeyCode::End, KeyModifiers::NONE) => {
+                // Move to the last match
+                let mut r = self.renderer.borrow_mut();
+                if !r.results.is_empty() {
+                    let last = r.results.len() - 1;
+                    r.activate_match_number(last);
+                }
+            }
             (KeyCode::PageUp, KeyModifiers::NONE) => {
                 // Skip this page of matches and move up to the first match from
                 // the prior page.
@@ -183,6 +227,7 @@ impl Pane for SearchOverlay {
                 let mut r = self.renderer.borrow_mut();
                 if let Some(cur) = r.result_pos.as_ref() {
                     let next = if *cur + 1 >= r.results.len() {
+                        r.search_wrapped();
                         0
                     } else {
                         *cur + 1
@@ -304,15 +349,14 @@ impl Pane for SearchOverlay {
                     Pattern::CaseInSensitiveString(_) => "ignore-case",
                     Pattern::Regex(_) => "regex",
                 };
+                let status = match renderer.result_pos {
+                    Some(pos) => format!("match {} of {}", pos + 1, renderer.results.len()),
+                    None if renderer.pattern.is_empty() => "no pattern".to_string(),
+                    None => "no matches".to_string(),
+                };
                 line.overlay_text_with_attribute(
                     0,
-                    &format!(
-                        "Search: {} ({}/{} matches. {})",
-                        *renderer.pattern,
-                        renderer.result_pos.map(|x| x + 1).unwrap_or(0),
-                        renderer.results.len(),
-                        mode
-                    ),
+                    &format!("Search: {} ({}. {})", *renderer.pattern, status, mode),
                     rev,
                 );
                 renderer.last_bar_pos = Some(search_row);
@@ -414,6 +458,27 @@ impl SearchRenderable {
         }
     }
 
+    /// Drops the results that start in rows that have been evicted
+    /// from the scrollback, keeping the current result if it survived
+    fn evict_rows_before(&mut self, first_row: StableRowIndex) {
+        let current = self
+            .result_pos
+            .and_then(|idx| self.results.get(idx).cloned());
+        let num_results = self.results.len();
+        self.results.retain(|res| res.start_y >= first_row);
+        if self.results.len() == num_results {
+            return;
+        }
+
+        self.result_pos =
+            current.and_then(|current| self.results.iter().position(|res| *res == current));
+        for idx in self.by_line.keys() {
+            self.dirty_results.add(*idx);
+        }
+        self.by_line.clear();
+        self.recompute_results();
+    }
+
     fn update_search(&mut self) {
         for idx in self.by_line.keys() {
             self.dirty_results.add(*idx);
@@ -434,8 +499,7 @@ impl SearchRenderable {
             let window = self.window.clone();
             let pattern = self.pattern.clone();
             promise::spawn::spawn(async move {
-                let mut results = pane.search(pattern).await?;
-                results.sort();
+                let results = sort_results(pane.search(pattern).await?);
 
                 let pane_id = pane.pane_id();
                 let mut results = Some(results);
@@ -445,7 +509,7 @@ impl SearchRenderable {
                         .expect("to be TermWindow");
                     let state = term_window.pane_state(pane_id);
                     if let Some(overlay) = state.overlay.as_ref() {
-                        if let Some(search_overlay) = overlay.downcast_ref::<SearchOverlay>() {
+                        if let Some(search_overlay) = overlay.pane.downcast_ref::<SearchOverlay>() {
                             let mut r = search_overlay.renderer.borrow_mut();
                             r.results = results.take().unwrap();
                             r.recompute_results();
@@ -508,6 +572,70 @@ impl SearchRenderable {
             Ok(())
         });
 
-        self.set_viewport(Some(result.start_y));
+        self.scroll_to_result(&result);
+    }
+
+    /// Lets the window flash the pane to show that moving to the
+    /// next or prior match has wrapped around
+    fn search_wrapped(&self) {
+        let pane_id = self.delegate.pane_id();
+        self.window.apply(move |term_window, _window| {
+            if let Some(term_window) = term_window.downcast_mut::<TermWindow>() {
+                term_window.search_wrapped(pane_id);
+            }
+            Ok(())
+        });
+    }
+
+    /// Adjust the viewport so that the result is visible, along with
+    /// scroll_context_lines of context above and below it.
+    /// The viewport is left alone if that is already the case.
+    fn scroll_to_result(&self, result: &SearchResult) {
+        let dims = self.delegate.get_dimensions();
+        let rows = dims.viewport_rows as StableRowIndex;
+        let context = configuration()
+            .scroll_context_lines
+            .min(dims.viewport_rows.saturating_sub(1) / 2) as StableRowIndex;
+        let top = self.viewport.unwrap_or(dims.physical_top);
+        let bottom = top + rows - 1;
+
+        if result.start_y < top + context {
+            self.set_viewport(Some(result.start_y - context));
+        } else if result.end_y > bottom - context {
+            self.set_viewport(Some(result.end_y + context + 1 - rows));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn result(start_y: StableRowIndex, start_x: usize, end_x: usize) -> SearchResult {
+        SearchResult {
+            start_y,
+            start_x,
+            end_y: start_y,
+            end_x,
+        }
+    }
+
+    #[test]
+    fn results_are_ordered_top_to_bottom() {
+        let results = sort_results(vec![
+            result(10, 4, 8),
+            result(-3, 0, 2),
+            result(10, 0, 3),
+            result(2, 5, 9),
+        ]);
+        assert_eq!(
+            results,
+            vec![
+                result(-3, 0, 2),
+                result(2, 5, 9),
+                result(10, 0, 3),
+                result(10, 4, 8),
+            ]
+        );
     }
 }
@@ -0,0 +1,377 @@
+//! A list of entries that can be searched by typing, and from which
+//! an entry can be chosen using the keyboard or the mouse.  This is
+//! the common part of the paste history, command history and pane
+//! navigator overlays.
+use super::charselect::{fuzzy_score, truncate};
+use mux::termwiztermtab::TermWizTerminal;
+use termwiz::cell::{AttributeChange, CellAttributes};
+use termwiz::color::ColorAttribute;
+use termwiz::input::{InputEvent, KeyCode, KeyEvent, Modifiers, MouseButtons, MouseEvent};
+use termwiz::surface::{Change, Position};
+use termwiz::terminal::Terminal;
+
+/// The number of rows used by the header that precedes the list
+const HEADER_ROWS: usize = 2;
+
+/// How an entry was chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Choice {
+    /// Enter was pressed, or the entry was clicked
+    Activate,
+    /// Tab was pressed
+    Alternate,
+}
+
+/// The text shown in the header of a selector
+pub struct SelectorText {
+    /// The title of the overlay
+    pub title: &'static str,
+    /// Shown in place of the status when there are no entries at all
+    pub empty: &'static str,
+    /// What the entries are called, eg: "entries"
+    pub noun: &'static str,
+    /// The keys that choose an entry and what they do, eg: "Enter to paste"
+    pub help: &'static str,
+}
+
+pub struct FuzzySelector<T> {
+    items: Vec<T>,
+    text: SelectorText,
+    search_text: Box<dyn Fn(&T) -> String>,
+    label: Box<dyn Fn(&T) -> String>,
+    query: String,
+    /// Indices into `items` of the entries that match `query`
+    matches: Vec<usize>,
+    active_idx: usize,
+    top_row: usize,
+}
+
+/// Returns the indices of the items matching `query`, best match first.
+/// Items that score the same remain in their original order.
+pub fn fuzzy_search<T, F>(items: &[T], query: &str, search_text: F) -> Vec<usize>
+where
+    F: Fn(&T) -> String,
+{
+    let query = query.trim();
+    if query.is_empty() {
+        return (0..items.len()).collect();
+    }
+    let mut matches: Vec<(i64, usize)> = items
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, item)| fuzzy_score(query, &search_text(item)).map(|s| (s, idx)))
+        .collect();
+    matches.sort_by(|(a, _), (b, _)| b.cmp(a));
+    matches.into_iter().map(|(_, idx)| idx).collect()
+}
+
+impl<T> FuzzySelector<T> {
+    /// Creates a selector for `items`.  `search_text` returns the text
+    /// that the query is matched against, and `label` the text that
+    /// is shown in the list, for an item.
+    pub fn new<S, L>(items: Vec<T>, text: SelectorText, search_text: S, label: L) -> Self
+    where
+        S: Fn(&T) -> String + 'static,
+        L: Fn(&T) -> String + 'static,
+    {
+        let matches = (0..items.len()).collect();
+        Self {
+            items,
+            text,
+            search_text: Box::new(search_text),
+            label: Box::new(label),
+            query: String::new(),
+            matches,
+            active_idx: 0,
+            top_row: 0,
+        }
+    }
+
+    /// Makes the first item for which `f` returns true the active entry
+    pub fn select_first<F: Fn(&T) -> bool>(&mut self, f: F) {
+        if let Some(idx) = self.matches.iter().position(|&idx| f(&self.items[idx])) {
+            self.active_idx = idx;
+        }
+    }
+
+    fn update_matches(&mut self) {
+        self.matches = fuzzy_search(&self.items, &self.query, &self.search_text);
+        self.active_idx = 0;
+        self.top_row = 0;
+    }
+
+    fn selected(&self) -> Option<&T> {
+        self.matches
+            .get(self.active_idx)
+            .map(|&idx| &self.items[idx])
+    }
+
+    fn move_by(&mut self, delta: isize, visible_rows: usize) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let max_idx = self.matches.len() as isize - 1;
+        self.active_idx = (self.active_idx as isize + delta).max(0).min(max_idx) as usize;
+
+        // Keep the active entry in view
+        if self.active_idx < self.top_row {
+            self.top_row = self.active_idx;
+        } else if visible_rows > 0 && self.active_idx >= self.top_row + visible_rows {
+            self.top_row = self.active_idx + 1 - visible_rows;
+        }
+    }
+
+    fn status(&self) -> String {
+        if self.items.is_empty() {
+            self.text.empty.to_string()
+        } else if self.query.is_empty() {
+            format!(
+                "{} {}: type to search, {}, Escape to cancel",
+                self.matches.len(),
+                self.text.noun,
+                self.text.help
+            )
+        } else {
+            format!(
+                "{} matches: {}, Escape to cancel",
+                self.matches.len(),
+                self.text.help
+            )
+        }
+    }
+
+    fn render(&self, term: &mut TermWizTerminal) -> termwiz::Result<()> {
+        let size = term.get_screen_size()?;
+        let visible_rows = size.rows.saturating_sub(HEADER_ROWS);
+
+        let mut changes = vec![
+            Change::ClearScreen(ColorAttribute::Default),
+            Change::CursorPosition {
+                x: Position::Absolute(0),
+                y: Position::Absolute(1),
+            },
+            Change::Text(truncate(&self.status(), size.cols)),
+            Change::CursorPosition {
+                x: Position::Absolute(0),
+                y: Position::Absolute(HEADER_ROWS),
+            },
+            Change::AllAttributes(CellAttributes::default()),
+        ];
+
+        for (idx, &item) in self
+            .matches
+            .iter()
+            .enumerate()
+            .skip(self.top_row)
+            .take(visible_rows)
+        {
+            if idx == self.active_idx {
+                changes.push(AttributeChange::Reverse(true).into());
+            }
+
+            let line = (self.label)(&self.items[item]);
+            changes.push(Change::Text(format!("{}\r\n", truncate(&line, size.cols))));
+
+            if idx == self.active_idx {
+                changes.push(AttributeChange::Reverse(false).into());
+            }
+        }
+
+        // Leave the cursor at the end of the search text
+        let prompt = format!("Search: {}", self.query);
+        changes.push(Change::CursorPosition {
+            x: Position::Absolute(0),
+            y: Position::Absolute(0),
+        });
+        changes.push(Change::Text(prompt));
+
+        term.render(&changes)?;
+        term.flush()
+    }
+
+    /// Runs the selector until it is cancelled, or until an entry
+    /// is chosen.  When an entry is chosen, `on_choose` is called
+    /// with it.  The selector always closes after `Choice::Activate`;
+    /// after `Choice::Alternate` it closes if `on_choose` returns true.
+    pub fn run<F>(mut self, term: &mut TermWizTerminal, mut on_choose: F) -> anyhow::Result<()>
+    where
+        F: FnMut(&T, Choice) -> bool,
+    {
+        term.set_raw_mode()?;
+        term.render(&[Change::Title(self.text.title.to_string())])?;
+        let visible_rows = term
+            .get_screen_size()
+            .map(|size| size.rows.saturating_sub(HEADER_ROWS))
+            .unwrap_or(0);
+        self.move_by(0, visible_rows);
+        self.render(term)?;
+
+        while let Ok(Some(event)) = term.poll_input(None) {
+            let visible_rows = term
+                .get_screen_size()
+                .map(|size| size.rows.saturating_sub(HEADER_ROWS))
+                .unwrap_or(0);
+
+            match event {
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::Escape,
+                    ..
+                }) => {
+                    break;
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::Enter,
+                    ..
+                }) => {
+                    if let Some(item) = self.selected() {
+                        on_choose(item, Choice::Activate);
+                    }
+                    break;
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::Tab, ..
+                }) => {
+                    if let Some(item) = self.selected() {
+                        if on_choose(item, Choice::Alternate) {
+                            break;
+                        }
+                    }
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::UpArrow,
+                    ..
+                }) => {
+                    self.move_by(-1, visible_rows);
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::DownArrow,
+                    ..
+                }) => {
+                    self.move_by(1, visible_rows);
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::PageUp,
+                    ..
+                }) => {
+                    self.move_by(-(visible_rows as isize), visible_rows);
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::PageDown,
+                    ..
+                }) => {
+                    self.move_by(visible_rows as isize, visible_rows);
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::Backspace,
+                    ..
+                }) => {
+                    self.query.pop();
+                    self.update_matches();
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::Char('u'),
+                    modifiers,
+                }) if modifiers == Modifiers::CTRL => {
+                    self.query.clear();
+                    self.update_matches();
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::Char(c),
+                    modifiers,
+                }) if !modifiers
+                    .intersects(Modifiers::CTRL | Modifiers::ALT | Modifiers::SUPER) =>
+                {
+                    self.query.push(c);
+                    self.update_matches();
+                }
+                InputEvent::Mouse(MouseEvent {
+                    y, mouse_buttons, ..
+                }) => {
+                    let row = y as usize;
+                    if row >= HEADER_ROWS {
+                        let idx = self.top_row + row - HEADER_ROWS;
+                        if idx < self.matches.len() {
+                            self.active_idx = idx;
+
+                            if mouse_buttons == MouseButtons::LEFT {
+                                if let Some(item) = self.selected() {
+                                    on_choose(item, Choice::Activate);
+                                }
+                                break;
+                            }
+                        }
+                    }
+                    if mouse_buttons != MouseButtons::NONE && mouse_buttons != MouseButtons::LEFT {
+                        // Treat any other mouse button as cancel
+                        break;
+                    }
+                }
+                _ => {}
+            }
+            self.render(term)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn selector(items: &[&str]) -> FuzzySelector<String> {
+        FuzzySelector::new(
+            items.iter().map(|s| s.to_string()).collect(),
+            SelectorText {
+                title: "Test",
+                empty: "Nothing here",
+                noun: "entries",
+                help: "Enter to choose",
+            },
+            String::clone,
+            |s| format!(" {}", s),
+        )
+    }
+
+    #[test]
+    fn search() {
+        let items = vec!["git status", "cargo build --release", "git stash pop"];
+        let text = |s: &&str| s.to_string();
+        assert_eq!(fuzzy_search(&items, "", text), vec![0, 1, 2]);
+        assert_eq!(fuzzy_search(&items, " cbr ", text), vec![1]);
+        assert_eq!(fuzzy_search(&items, "git st", text), vec![0, 2]);
+        assert!(fuzzy_search(&items, "xyz", text).is_empty());
+    }
+
+    #[test]
+    fn movement_keeps_active_entry_in_view() {
+        let mut sel = selector(&["a", "b", "c", "d", "e"]);
+        sel.move_by(3, 2);
+        assert_eq!((sel.active_idx, sel.top_row), (3, 2));
+        sel.move_by(10, 2);
+        assert_eq!((sel.active_idx, sel.top_row), (4, 3));
+        sel.move_by(-2, 2);
+        assert_eq!((sel.active_idx, sel.top_row), (2, 2));
+        sel.move_by(-10, 2);
+        assert_eq!((sel.active_idx, sel.top_row), (0, 0));
+        assert_eq!(sel.selected().map(String::as_str), Some("a"));
+    }
+
+    #[test]
+    fn query_and_status() {
+        let mut sel = selector(&["alpha", "beta", "gamma"]);
+        sel.select_first(|s| s == "gamma");
+        assert_eq!(sel.selected().map(String::as_str), Some("gamma"));
+        assert_eq!(
+            sel.status(),
+            "3 entries: type to search, Enter to choose, Escape to cancel"
+        );
+
+        sel.query.push_str("bt");
+        sel.update_matches();
+        assert_eq!(sel.selected().map(String::as_str), Some("beta"));
+        assert_eq!(sel.status(), "1 matches: Enter to choose, Escape to cancel");
+
+        assert_eq!(selector(&[]).status(), "Nothing here");
+    }
+}
@@ -0,0 +1,57 @@
+//! Keeps track of whether the system is running on battery power, so
+//! that windows can render less often to save power.
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// How often the state of the batteries is sampled
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(15);
+
+static ON_BATTERY: AtomicBool = AtomicBool::new(false);
+
+/// Returns true if the system was running on battery power when
+/// it was most recently sampled
+pub fn on_battery_power() -> bool {
+    ON_BATTERY.load(Ordering::Relaxed)
+}
+
+fn sample() -> anyhow::Result<bool> {
+    let manager = battery::Manager::new()?;
+    let mut discharging = false;
+    for bat in manager.batteries()? {
+        if bat?.state() == battery::State::Discharging {
+            discharging = true;
+        }
+    }
+    Ok(discharging)
+}
+
+fn power_monitor() {
+    loop {
+        match sample() {
+            Ok(on_battery) => {
+                if ON_BATTERY.swap(on_battery, Ordering::Relaxed) != on_battery {
+                    log::info!("running on battery power: {}", on_battery);
+                }
+            }
+            Err(err) => {
+                log::warn!("Unable to query the battery state: {:#}", err);
+                return;
+            }
+        }
+        std::thread::sleep(SAMPLE_INTERVAL);
+    }
+}
+
+/// Starts sampling the battery state in the background, as querying
+/// it can block
+pub fn start_power_monitor() {
+    static MONITOR_STARTED: AtomicBool = AtomicBool::new(false);
+    if let Ok(false) =
+        MONITOR_STARTED.compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed)
+    {
+        std::thread::Builder::new()
+            .name("power_monitor".into())
+            .spawn(power_monitor)
+            .expect("failed to spawn power monitor thread");
+    }
+}
@@ -7,6 +7,7 @@ use ::window::bitmaps::TextureRect;
 use ::window::color::LinearRgba;
 use ::window::glium::buffer::Mapping;
 use std::cell::RefMut;
+use std::ops::Range;
 
 /// Each cell is composed of two triangles built from 4 vertices.
 /// The buffer is organized row by row.
@@ -16,6 +17,19 @@ pub const V_TOP_RIGHT: usize = 1;
 pub const V_BOT_LEFT: usize = 2;
 pub const V_BOT_RIGHT: usize = 3;
 
+/// How many rows of quads are reserved for panes that are scrolled
+/// part way through a row; beyond this many panes scrolling at once,
+/// the partially visible line at the bottom of a pane is left blank
+pub const NUM_SPARE_ROWS: usize = 8;
+
+/// How many quads are reserved for the fading trail that can follow
+/// the animated cursor
+pub const CURSOR_TRAIL_QUADS: usize = 8;
+
+/// How many quads are reserved for visual effects, such as flashes,
+/// that are drawn over the top of everything else
+pub const EFFECT_QUADS: usize = 8;
+
 #[derive(Copy, Clone, Default)]
 pub struct Vertex {
     // Physical position of the corner of the character cell
@@ -70,9 +84,19 @@ pub struct Quads {
     pub cols: usize,
     /// row number to vertex index for the first vertex on that row
     pub row_starts: Vec<usize>,
+    /// The rows whose quads are positioned explicitly rather than
+    /// being fixed to the grid; they are addressed via `cell` like the
+    /// other rows.  Each pane that needs one during a frame takes the
+    /// next unused row, so that panes don't overwrite each other's.
+    pub spare_rows: Range<usize>,
     /// The vertex index for the first vertex of the scroll bar thumb
     pub scroll_thumb: usize,
     pub background_image: usize,
+    /// The vertex indices for the animated cursor.  The trail quads
+    /// precede the cursor itself so that it is drawn on top of them.
+    pub animated_cursor: Vec<usize>,
+    /// The vertex indices for the visual effects
+    pub effects: Vec<usize>,
 }
 
 pub struct MappedQuads<'a> {
@@ -111,6 +135,25 @@ impl<'a> MappedQuads<'a> {
             vert: &mut self.mapping[start..start + VERTICES_PER_CELL],
         }
     }
+
+    /// Returns the animated cursor quad with the specified index;
+    /// indices less than CURSOR_TRAIL_QUADS are used for the trail
+    /// and CURSOR_TRAIL_QUADS is used for the cursor itself.
+    pub fn animated_cursor<'b>(&'b mut self, idx: usize) -> Quad<'b> {
+        let start = self.quads.animated_cursor[idx];
+        Quad {
+            vert: &mut self.mapping[start..start + VERTICES_PER_CELL],
+        }
+    }
+
+    /// Returns the effect quad with the specified index, which must
+    /// be less than EFFECT_QUADS
+    pub fn effect<'b>(&'b mut self, idx: usize) -> Quad<'b> {
+        let start = self.quads.effects[idx];
+        Quad {
+            vert: &mut self.mapping[start..start + VERTICES_PER_CELL],
+        }
+    }
 }
 
 impl Quads {
@@ -232,4 +275,87 @@ impl<'a> Quad<'a> {
         self.vert[V_BOT_LEFT].position = (left, bottom);
         self.vert[V_BOT_RIGHT].position = (right, bottom);
     }
+
+    /// Like `set_position`, but only the part of the quad that lies
+    /// between `min_y` and `max_y` is drawn; the textures are trimmed
+    /// to match.  This must be called after the textures and the
+    /// bearing adjustment have been assigned.
+    pub fn set_position_clipped(
+        &mut self,
+        left: f32,
+        top: f32,
+        right: f32,
+        bottom: f32,
+        min_y: f32,
+        max_y: f32,
+    ) {
+        let clip = |y: f32| y.max(min_y).min(max_y);
+        // The proportion of the way from `from` to `to` that `y` lies
+        let fraction = |y: f32, from: f32, to: f32| {
+            if to > from {
+                (y - from) / (to - from)
+            } else {
+                0.
+            }
+        };
+
+        // The backgrounds, underlines and cursor fill the cell, but the
+        // glyph is drawn at the position offset by its bearing
+        let (clipped_top, clipped_bottom) = (clip(top), clip(bottom));
+        let cell = (
+            fraction(clipped_top, top, bottom),
+            fraction(clipped_bottom, top, bottom),
+        );
+        let glyph_top = top + self.vert[V_TOP_LEFT].adjust.1;
+        let glyph_bottom = bottom + self.vert[V_BOT_LEFT].adjust.1;
+        let (clipped_glyph_top, clipped_glyph_bottom) = (clip(glyph_top), clip(glyph_bottom));
+        let glyph = (
+            fraction(clipped_glyph_top, glyph_top, glyph_bottom),
+            fraction(clipped_glyph_bottom, glyph_top, glyph_bottom),
+        );
+
+        let trim = |(from, to): (f32, f32), (start, end): (f32, f32)| {
+            (from + (to - from) * start, from + (to - from) * end)
+        };
+        let tex = trim(
+            (self.vert[V_TOP_LEFT].tex.1, self.vert[V_BOT_LEFT].tex.1),
+            glyph,
+        );
+        let underline = trim(
+            (
+                self.vert[V_TOP_LEFT].underline.1,
+                self.vert[V_BOT_LEFT].underline.1,
+            ),
+            cell,
+        );
+        let cursor = trim(
+            (
+                self.vert[V_TOP_LEFT].cursor.1,
+                self.vert[V_BOT_LEFT].cursor.1,
+            ),
+            cell,
+        );
+
+        for (idx, v) in self.vert.iter_mut().enumerate() {
+            let is_top = idx == V_TOP_LEFT || idx == V_TOP_RIGHT;
+            let x = if idx == V_TOP_LEFT || idx == V_BOT_LEFT {
+                left
+            } else {
+                right
+            };
+            if is_top {
+                v.position = (x, clipped_top);
+                v.adjust.1 = clipped_glyph_top - clipped_top;
+                v.tex.1 = tex.0;
+                v.underline.1 = underline.0;
+                v.cursor.1 = cursor.0;
+            } else {
+                v.position = (x, clipped_bottom);
+                v.adjust.1 = clipped_glyph_bottom - clipped_bottom;
+                v.tex.1 = tex.1;
+                v.underline.1 = underline.1;
+                v.cursor.1 = cursor.1;
+            }
+        }
+    }
 }
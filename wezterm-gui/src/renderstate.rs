@@ -287,9 +287,37 @@ impl RenderState {
             }
         }
 
+        // And some rows that have no fixed position; while a pane is
+        // scrolled part way through a row, one of them holds the line
+        // that is partially visible at the bottom of the pane
+        let first_spare_row = quads.row_starts.len();
+        for _ in 0..NUM_SPARE_ROWS {
+            for x in 0..num_cols {
+                let idx = define_quad(0.0, 0.0, 0.0, 0.0);
+                if x == 0 {
+                    quads.row_starts.push(idx as usize);
+                }
+            }
+        }
+        quads.spare_rows = first_spare_row..quads.row_starts.len();
+
         // And a quad for the scrollbar thumb
         quads.scroll_thumb = define_quad(0.0, 0.0, 0.0, 0.0) as usize;
 
+        // And the animated cursor and its trail, which are positioned
+        // as it moves
+        for _ in 0..=CURSOR_TRAIL_QUADS {
+            quads
+                .animated_cursor
+                .push(define_quad(0.0, 0.0, 0.0, 0.0) as usize);
+        }
+
+        // And the visual effects, which are positioned over the window
+        // or a pane while they are visible
+        for _ in 0..EFFECT_QUADS {
+            quads.effects.push(define_quad(0.0, 0.0, 0.0, 0.0) as usize);
+        }
+
         let buffer = TripleVertexBuffer {
             index: 0,
             bufs: [
@@ -48,6 +48,9 @@ impl GuiWin {
 impl UserData for GuiWin {
     fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
         methods.add_method("window_id", |_, this, _: ()| Ok(this.mux_window_id));
+        methods.add_method("status_command_output", |_, _this, name: String| {
+            Ok(crate::statuscmd::status_command_output(&name))
+        });
         methods.add_async_method("set_right_status", |_, this, status: String| async move {
             this.with_term_window(move |term_window, _ops| {
                 if status != term_window.right_status {
@@ -79,6 +82,24 @@ impl UserData for GuiWin {
             })
             .await
         });
+        methods.add_async_method("get_power_state", |_, this, _: ()| async move {
+            this.with_term_window(move |term_window, _ops| {
+                #[derive(Serialize, Deserialize)]
+                struct PowerState {
+                    on_battery: bool,
+                    power_saving: bool,
+                    idle: bool,
+                }
+                impl_lua_conversion!(PowerState);
+
+                Ok(PowerState {
+                    on_battery: crate::power::on_battery_power(),
+                    power_saving: term_window.saving_battery_power(),
+                    idle: term_window.is_idle(),
+                })
+            })
+            .await
+        });
         methods.add_async_method(
             "get_selection_text_for_pane",
             |_, this, pane: PaneObject| async move {
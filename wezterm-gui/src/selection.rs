@@ -19,7 +19,6 @@ pub struct Selection {
 pub use config::keyassignment::SelectionMode;
 
 impl Selection {
-    #[allow(dead_code)]
     pub fn clear(&mut self) {
         self.range = None;
         self.start = None;
@@ -34,6 +33,24 @@ impl Selection {
     pub fn is_empty(&self) -> bool {
         self.range.is_none()
     }
+
+    /// Rows before `first_row` have been evicted from the scrollback.
+    /// If the selection references any of them then it is cleared,
+    /// rather than being left to refer to text that no longer exists.
+    /// Returns true if the selection was cleared.
+    pub fn evict_rows_before(&mut self, first_row: StableRowIndex) -> bool {
+        let start_evicted = self.start.map(|s| s.y < first_row).unwrap_or(false);
+        let range_evicted = self
+            .range
+            .map(|r| r.normalize().start.y < first_row)
+            .unwrap_or(false);
+        if start_evicted || range_evicted {
+            self.clear();
+            true
+        } else {
+            false
+        }
+    }
 }
 
 /// The x,y coordinates of either the start or end of a selection region
@@ -232,3 +249,29 @@ impl SelectionRange {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn coord(x: usize, y: StableRowIndex) -> SelectionCoordinate {
+        SelectionCoordinate { x, y }
+    }
+
+    #[test]
+    fn evict_rows() {
+        let mut selection = Selection::default();
+        selection.begin(coord(0, 10));
+        selection.range = Some(SelectionRange {
+            start: coord(0, 10),
+            end: coord(4, 5),
+        });
+
+        assert!(!selection.evict_rows_before(5));
+        assert!(selection.range.is_some());
+
+        assert!(selection.evict_rows_before(6));
+        assert!(selection.range.is_none());
+        assert!(selection.start.is_none());
+    }
+}
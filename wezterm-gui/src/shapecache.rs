@@ -3,6 +3,7 @@ use crate::utilsprites::RenderMetrics;
 use ::window::bitmaps::Texture2d;
 use config::TextStyle;
 use std::rc::Rc;
+use termwiz::cell::VerticalAlign;
 use termwiz::cellcluster::CellCluster;
 use wezterm_font::shaper::GlyphInfo;
 use wezterm_font::units::*;
@@ -11,6 +12,7 @@ use wezterm_font::units::*;
 pub struct ShapeCacheKey {
     pub style: TextStyle,
     pub text: String,
+    pub vertical_align: VerticalAlign,
 }
 
 #[derive(Debug, PartialEq)]
@@ -68,7 +70,7 @@ where
                             .map_or(0, |t| t.coords.width() as u32),
                         cluster: info.cluster,
                         num_cells: info.num_cells,
-                        x_offset: info.x_offset,
+                        x_offset: info.x_offset - glyph.subpixel_shift,
                         bearing_x: glyph.bearing_x.get() as f32,
                     },
                     glyph: Rc::clone(glyph),
@@ -159,7 +161,7 @@ where
                         glyph_idx: info.glyph_pos,
                         cluster,
                         num_cells,
-                        x_offset: info.x_offset,
+                        x_offset: info.x_offset - glyph.subpixel_shift,
                         bearing_x: bearing_x as f32,
                         bitmap_pixel_width,
                     },
@@ -174,7 +176,7 @@ where
                             .map_or(0, |t| t.coords.width() as u32),
                         cluster: info.cluster,
                         num_cells: info.num_cells,
-                        x_offset: info.x_offset,
+                        x_offset: info.x_offset - glyph.subpixel_shift,
                         bearing_x: glyph.bearing_x.get() as f32,
                     },
                     glyph: Rc::clone(glyph),
@@ -198,6 +200,7 @@ where
 pub struct BorrowedShapeCacheKey<'a> {
     pub style: &'a TextStyle,
     pub text: &'a str,
+    pub vertical_align: VerticalAlign,
 }
 
 impl<'a> BorrowedShapeCacheKey<'a> {
@@ -205,6 +208,7 @@ impl<'a> BorrowedShapeCacheKey<'a> {
         ShapeCacheKey {
             style: self.style.clone(),
             text: self.text.to_owned(),
+            vertical_align: self.vertical_align,
         }
     }
 }
@@ -218,6 +222,7 @@ impl ShapeCacheKeyTrait for ShapeCacheKey {
         BorrowedShapeCacheKey {
             style: &self.style,
             text: &self.text,
+            vertical_align: self.vertical_align,
         }
     }
 }
@@ -298,7 +303,7 @@ mod test {
                 };
 
                 glyph_cache
-                    .cached_glyph(info, &style, followed_by_space)
+                    .cached_glyph(info, &style, followed_by_space, VerticalAlign::BaseLine)
                     .unwrap()
             })
             .collect::<Vec<_>>();
@@ -328,6 +333,7 @@ mod test {
         config.font = TextStyle {
             font: vec![FontAttributes::new("Fira Code")],
             foreground: None,
+            allow_square_glyphs_to_overflow_width: None,
         };
         config.font_rules.clear();
         config.compute_extra_defaults(None);
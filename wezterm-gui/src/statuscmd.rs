@@ -0,0 +1,242 @@
+//! Runs the commands from the `status_commands` configuration at their
+//! configured intervals, and keeps the most recent output of each so
+//! that it can be shown in the tab bar and read by lua code.
+//! The commands run on the executor of the main thread, rather than
+//! each needing a thread of its own.
+use crate::frontend::front_end;
+use crate::TermWindow;
+use anyhow::{anyhow, ensure};
+use config::StatusCommand;
+use smol::process::{Command, Stdio};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How often the commands are checked to see whether any are due
+const TICK: Duration = Duration::from_secs(1);
+
+/// The longest time that a failing command is left before it is
+/// retried
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+struct CommandState {
+    /// The definition that the state belongs to.  If the definition
+    /// is changed by reloading the configuration, the state is
+    /// discarded and the command starts afresh.
+    command: StatusCommand,
+    output: Option<String>,
+    next_run: Instant,
+    running: bool,
+    /// The number of consecutive runs that have failed
+    failures: u32,
+}
+
+lazy_static::lazy_static! {
+    static ref STATES: Mutex<HashMap<String, CommandState>> = Mutex::new(HashMap::new());
+}
+
+/// Returns the most recent output of the status command named `name`,
+/// with trailing whitespace removed.  The output of a command that
+/// fails is kept until it next succeeds.
+pub fn status_command_output(name: &str) -> Option<String> {
+    STATES
+        .lock()
+        .unwrap()
+        .get(name)
+        .and_then(|state| state.output.clone())
+}
+
+/// Returns the text that the status commands contribute to the right
+/// of the tab bar: the first line of the output of each, in the
+/// order in which they are configured
+pub fn tab_bar_text() -> String {
+    let config = config::configuration();
+    let states = STATES.lock().unwrap();
+    config
+        .status_commands
+        .iter()
+        .filter(|command| command.show_in_tab_bar)
+        .filter_map(|command| states.get(&command.name)?.output.as_ref()?.lines().next())
+        .filter(|line| !line.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+/// Returns how long to wait before running a command again after it
+/// has failed `failures` times in a row
+fn backoff(interval: Duration, failures: u32) -> Duration {
+    interval
+        .checked_mul(2u32.saturating_pow(failures))
+        .unwrap_or(MAX_BACKOFF)
+        .min(MAX_BACKOFF)
+        .max(interval)
+}
+
+fn interval(command: &StatusCommand) -> Duration {
+    Duration::from_secs(command.interval_seconds.max(1))
+}
+
+async fn run(command: &StatusCommand) -> anyhow::Result<String> {
+    let (program, args) = command
+        .args
+        .split_first()
+        .ok_or_else(|| anyhow!("args is empty"))?;
+    let mut cmd = Command::new(program);
+    cmd.args(args).stdin(Stdio::null()).kill_on_drop(true);
+    if let Some(cwd) = &command.cwd {
+        cmd.current_dir(cwd);
+    }
+
+    #[cfg(windows)]
+    {
+        use smol::process::windows::CommandExt;
+        cmd.creation_flags(winapi::um::winbase::CREATE_NO_WINDOW);
+    }
+
+    let timeout = Duration::from_secs(command.timeout_seconds.max(1));
+    // Dropping the command's future when the timer wins kills it
+    let output = smol::future::or(async { Some(cmd.output().await) }, async {
+        smol::Timer::after(timeout).await;
+        None
+    })
+    .await
+    .ok_or_else(|| anyhow!("timed out after {:?}", timeout))??;
+
+    ensure!(
+        output.status.success(),
+        "{}: {}",
+        output.status,
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .trim_end()
+        .to_string())
+}
+
+/// Updates the tab bar of each window to show the current output
+fn refresh_windows() {
+    if let Some(fe) = front_end() {
+        for (_, window) in fe.gui_windows() {
+            window.apply(|tw, _ops| {
+                if let Some(term_window) = tw.downcast_mut::<TermWindow>() {
+                    term_window.update_title_post_status();
+                }
+                Ok(())
+            });
+        }
+    }
+}
+
+async fn run_and_record(command: StatusCommand) {
+    let result = run(&command).await;
+
+    let mut states = STATES.lock().unwrap();
+    let state = match states.get_mut(&command.name) {
+        Some(state) if state.command == command => state,
+        // The configuration changed while the command was running
+        _ => return,
+    };
+    state.running = false;
+
+    let changed = match result {
+        Ok(output) => {
+            state.failures = 0;
+            state.next_run = Instant::now() + interval(&command);
+            if state.output.as_ref() != Some(&output) {
+                state.output.replace(output);
+                true
+            } else {
+                false
+            }
+        }
+        Err(err) => {
+            // Only the first of a run of failures is logged, as the
+            // command is likely to keep failing in the same way
+            if state.failures == 0 {
+                log::warn!("status command {} failed: {:#}", command.name, err);
+            }
+            state.failures += 1;
+            state.next_run = Instant::now() + backoff(interval(&command), state.failures);
+            false
+        }
+    };
+    drop(states);
+
+    if changed {
+        refresh_windows();
+    }
+}
+
+/// Starts any commands that are due to run, and forgets about those
+/// that have been removed from the configuration
+fn run_due_commands() {
+    let config = config::configuration();
+    let now = Instant::now();
+
+    let mut states = STATES.lock().unwrap();
+    let num_states = states.len();
+    states.retain(|name, state| {
+        config
+            .status_commands
+            .iter()
+            .any(|command| command.name == *name && *command == state.command)
+    });
+    let removed = states.len() != num_states;
+
+    for command in &config.status_commands {
+        let state = states
+            .entry(command.name.clone())
+            .or_insert_with(|| CommandState {
+                command: command.clone(),
+                output: None,
+                next_run: now,
+                running: false,
+                failures: 0,
+            });
+        if !state.running && state.next_run <= now {
+            state.running = true;
+            promise::spawn::spawn(run_and_record(command.clone())).detach();
+        }
+    }
+    drop(states);
+
+    if removed {
+        refresh_windows();
+    }
+}
+
+/// Starts polling the status commands, if that isn't already
+/// happening.  Changes to the configuration are picked up as it is
+/// reloaded.
+pub fn start_status_commands() {
+    static STARTED: AtomicBool = AtomicBool::new(false);
+    if let Ok(false) = STARTED.compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed) {
+        promise::spawn::spawn(async {
+            loop {
+                run_due_commands();
+                smol::Timer::after(TICK).await;
+            }
+        })
+        .detach();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn backoff_is_bounded() {
+        let interval = Duration::from_secs(5);
+        assert_eq!(backoff(interval, 0), interval);
+        assert_eq!(backoff(interval, 1), Duration::from_secs(10));
+        assert_eq!(backoff(interval, 3), Duration::from_secs(40));
+        assert_eq!(backoff(interval, 10), MAX_BACKOFF);
+        assert_eq!(backoff(interval, u32::MAX), MAX_BACKOFF);
+        // A command that runs less often than the cap is retried at
+        // its usual interval
+        let hourly = Duration::from_secs(3600);
+        assert_eq!(backoff(hourly, 2), hourly);
+    }
+}
@@ -1,6 +1,11 @@
-use config::{ConfigHandle, TabBarColors};
+use config::{ConfigHandle, TabBarColors, TabTitleTruncation};
+use mux::pane::Pane;
+use mux::tab::{Tab, TabId};
 use mux::window::Window as MuxWindow;
+use mux::Mux;
 use std::cell::Ref;
+use std::collections::HashMap;
+use std::rc::Rc;
 use termwiz::cell::unicode_column_width;
 use termwiz::cell::{Cell, CellAttributes};
 use termwiz::color::ColorSpec;
@@ -30,6 +35,125 @@ struct TabEntry {
     width: usize,
 }
 
+/// Things that have happened in a tab since it was last active,
+/// which are shown by the `{flags}` tab title variable
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TabActivity {
+    pub output: bool,
+    pub bell: bool,
+}
+
+impl TabActivity {
+    fn flags(&self, zoomed: bool) -> String {
+        let mut flags = String::new();
+        if zoomed {
+            flags.push('Z');
+        }
+        if self.output {
+            flags.push('*');
+        }
+        if self.bell {
+            flags.push('!');
+        }
+        flags
+    }
+}
+
+/// Replaces each `{name}` in `template` with the value that `lookup`
+/// returns for it.  Names that `lookup` doesn't know are left as-is.
+fn format_tab_title(template: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let mut result = String::new();
+    let mut remain = template;
+    while let Some(start) = remain.find('{') {
+        result.push_str(&remain[..start]);
+        remain = &remain[start..];
+        match remain.find('}') {
+            Some(end) => {
+                match lookup(&remain[1..end]) {
+                    Some(value) => result.push_str(&value),
+                    None => result.push_str(&remain[..=end]),
+                }
+                remain = &remain[end + 1..];
+            }
+            None => break,
+        }
+    }
+    result.push_str(remain);
+    result
+}
+
+/// Returns the last component of the path of `url`
+fn cwd_basename(url: &url::Url) -> String {
+    let path = url.path();
+    let name = path.rsplit('/').find(|s| !s.is_empty()).unwrap_or(path);
+    percent_encoding::percent_decode_str(name)
+        .decode_utf8_lossy()
+        .into_owned()
+}
+
+/// Returns the value of the tab title variable `name`
+fn tab_title_variable(
+    name: &str,
+    index: usize,
+    tab: &Rc<Tab>,
+    pane: &Rc<dyn Pane>,
+    activity: TabActivity,
+) -> Option<String> {
+    let value = match name {
+        "title" => pane.get_title(),
+        "index" => index.to_string(),
+        "cwd" => pane
+            .get_current_working_dir()
+            .map(|url| cwd_basename(&url))
+            .unwrap_or_default(),
+        "process" => pane.get_foreground_process_name().unwrap_or_default(),
+        "domain" => Mux::get()
+            .and_then(|mux| mux.get_domain(pane.domain_id()))
+            .map(|domain| domain.domain_name().to_string())
+            .unwrap_or_default(),
+        "flags" => activity.flags(tab.is_zoomed()),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Shortens `title` so that it is no wider than `max_width` cells
+pub fn truncate_title(title: &str, max_width: usize, truncation: TabTitleTruncation) -> String {
+    if unicode_column_width(title) <= max_width {
+        return title.to_string();
+    }
+
+    /// Returns as many graphemes from `graphemes` as fit in `width`
+    fn take<'a>(graphemes: impl Iterator<Item = &'a str>, width: usize) -> Vec<&'a str> {
+        let mut used = 0;
+        graphemes
+            .take_while(|g| {
+                used += unicode_column_width(g);
+                used <= width
+            })
+            .collect()
+    }
+
+    const ELLIPSIS: &str = "…";
+    let graphemes: Vec<&str> = title.graphemes(true).collect();
+    match truncation {
+        TabTitleTruncation::Right => take(graphemes.iter().copied(), max_width).concat(),
+        _ if max_width == 0 => String::new(),
+        TabTitleTruncation::Left => {
+            let mut tail = take(graphemes.iter().rev().copied(), max_width - 1);
+            tail.reverse();
+            format!("{}{}", ELLIPSIS, tail.concat())
+        }
+        TabTitleTruncation::Middle => {
+            let available = max_width - 1;
+            let head = take(graphemes.iter().copied(), (available + 1) / 2);
+            let mut tail = take(graphemes.iter().rev().copied(), available / 2);
+            tail.reverse();
+            format!("{}{}{}", head.concat(), ELLIPSIS, tail.concat())
+        }
+    }
+}
+
 impl TabBarState {
     pub fn default() -> Self {
         Self {
@@ -46,6 +170,7 @@ impl TabBarState {
     /// mouse_x is some if the mouse is on the same row as the tab bar.
     /// title_width is the total number of cell columns in the window.
     /// window allows access to the tabs associated with the window.
+    /// tab_activity holds the activity flags for inactive tabs.
     pub fn new(
         title_width: usize,
         mouse_x: Option<usize>,
@@ -53,6 +178,7 @@ impl TabBarState {
         colors: Option<&TabBarColors>,
         config: &ConfigHandle,
         right_status: &str,
+        tab_activity: &HashMap<TabId, TabActivity>,
     ) -> Self {
         let colors = colors.cloned().unwrap_or_else(TabBarColors::default);
 
@@ -113,18 +239,23 @@ impl TabBarState {
             .enumerate()
             .map(|(idx, tab)| {
                 if let Some(pane) = tab.get_active_pane() {
-                    let mut title = pane.get_title();
-                    if config.show_tab_index_in_tab_bar {
-                        title = format!(
-                            "{}: {}",
-                            idx + if config.tab_and_split_indices_are_zero_based {
-                                0
-                            } else {
-                                1
-                            },
-                            title
-                        );
-                    }
+                    let index = idx
+                        + if config.tab_and_split_indices_are_zero_based {
+                            0
+                        } else {
+                            1
+                        };
+                    let mut title = match config.tab_title_format.as_ref() {
+                        Some(template) => format_tab_title(template, |name| {
+                            let activity =
+                                tab_activity.get(&tab.tab_id()).copied().unwrap_or_default();
+                            tab_title_variable(name, index, tab, &pane, activity)
+                        }),
+                        None if config.show_tab_index_in_tab_bar => {
+                            format!("{}: {}", index, pane.get_title())
+                        }
+                        None => pane.get_title(),
+                    };
                     // We have a preferred soft minimum on tab width to make it
                     // easier to click on tab titles, but we'll still go below
                     // this if there are too many tabs to fit the window at
@@ -162,7 +293,8 @@ impl TabBarState {
         let mut items = vec![];
 
         for (tab_idx, tab_title) in tab_titles.iter().enumerate() {
-            let tab_title_len = unicode_column_width(tab_title).min(tab_width_max);
+            let tab_title = truncate_title(tab_title, tab_width_max, config.tab_title_truncation);
+            let tab_title_len = unicode_column_width(&tab_title);
 
             let active = tab_idx == active_tab_no;
             let hover = !active
@@ -332,6 +464,9 @@ fn parse_status_text(text: &str, default_cell: CellAttributes) -> Vec<Cell> {
                         Sgr::Overline(o) => {
                             pen.set_overline(o);
                         }
+                        Sgr::VerticalAlign(o) => {
+                            pen.set_vertical_align(o);
+                        }
                         Sgr::Blink(b) => {
                             pen.set_blink(b);
                         }
@@ -364,7 +499,7 @@ fn parse_status_text(text: &str, default_cell: CellAttributes) -> Vec<Cell> {
                         Sgr::UnderlineColor(col) => {
                             pen.set_underline_color(col);
                         }
-                        Sgr::Font(_) => {}
+                        Sgr::Font(_) | Sgr::Push(_) | Sgr::Pop => {}
                     },
                     _ => {}
                 }
@@ -386,3 +521,44 @@ fn parse_status_text(text: &str, default_cell: CellAttributes) -> Vec<Cell> {
     flush_print(&mut print_buffer, &mut cells, &pen);
     cells
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn format() {
+        let lookup = |name: &str| match name {
+            "index" => Some("2".to_string()),
+            "cwd" => Some("src".to_string()),
+            "flags" => Some(String::new()),
+            _ => None,
+        };
+        assert_eq!(format_tab_title("{index}: {cwd}{flags}", lookup), "2: src");
+        assert_eq!(format_tab_title("{nope} {index", lookup), "{nope} {index");
+
+        let url = url::Url::parse("file://host/home/me/my%20project/").unwrap();
+        assert_eq!(cwd_basename(&url), "my project");
+    }
+
+    #[test]
+    fn truncate() {
+        let title = "abcdefghij";
+        assert_eq!(truncate_title(title, 10, TabTitleTruncation::Middle), title);
+        assert_eq!(
+            truncate_title(title, 6, TabTitleTruncation::Right),
+            "abcdef"
+        );
+        assert_eq!(truncate_title(title, 6, TabTitleTruncation::Left), "…fghij");
+        assert_eq!(
+            truncate_title(title, 6, TabTitleTruncation::Middle),
+            "abc…ij"
+        );
+        assert_eq!(truncate_title(title, 0, TabTitleTruncation::Middle), "");
+        // Double width characters are not split
+        assert_eq!(
+            truncate_title("ab日本語", 5, TabTitleTruncation::Middle),
+            "ab…語"
+        );
+    }
+}
@@ -0,0 +1,417 @@
+//! Bidirectional text support.
+//! The terminal model always holds text in logical order.  When bidi is
+//! enabled, each logical line is analyzed using the Unicode Bidirectional
+//! Algorithm to determine the order in which its cells are displayed.
+//! Rendering places cells at their visual column and mouse positions are
+//! mapped from their visual column back to the logical column.
+use config::BidiDirection;
+use mux::pane::Pane;
+use std::ops::Range;
+use std::rc::Rc;
+use unicode_bidi::{bidi_class, BidiClass, BidiInfo, Level};
+use wezterm_term::{Cell, Line, StableRowIndex};
+
+/// The display order of the cells in a physical line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BidiRow {
+    /// Maps a visual column to the logical column displayed there
+    visual_to_logical: Vec<usize>,
+    /// Maps a logical column to the visual column where it is displayed
+    logical_to_visual: Vec<usize>,
+    /// Whether the cell in each logical column is part of a right to
+    /// left run, and so should have its brackets mirrored
+    rtl: Vec<bool>,
+}
+
+impl BidiRow {
+    fn new(visual_to_logical: Vec<usize>, rtl: Vec<bool>) -> Self {
+        let mut logical_to_visual = vec![0; visual_to_logical.len()];
+        for (visual, &logical) in visual_to_logical.iter().enumerate() {
+            logical_to_visual[logical] = visual;
+        }
+        Self {
+            visual_to_logical,
+            logical_to_visual,
+            rtl,
+        }
+    }
+
+    /// Returns the visual column at which the logical column is displayed
+    pub fn visual_col(&self, logical: usize) -> usize {
+        self.logical_to_visual
+            .get(logical)
+            .copied()
+            .unwrap_or(logical)
+    }
+
+    /// Returns the logical column that is displayed at the visual column
+    pub fn logical_col(&self, visual: usize) -> usize {
+        self.visual_to_logical
+            .get(visual)
+            .copied()
+            .unwrap_or(visual)
+    }
+
+    /// Replaces brackets in right to left runs with their mirror image,
+    /// as described by rule L4 of the bidi algorithm.  This is applied
+    /// to the copy of the line that is being displayed.
+    pub fn mirror_line(&self, line: &mut Line) {
+        let mut mirrored = vec![];
+        for (idx, cell) in line.visible_cells() {
+            if !self.rtl.get(idx).copied().unwrap_or(false) {
+                continue;
+            }
+            if let Some(c) = mirror_char(cell.str()) {
+                mirrored.push((idx, Cell::new(c, cell.attrs().clone())));
+            }
+        }
+        for (idx, cell) in mirrored {
+            line.set_cell(idx, cell);
+        }
+    }
+}
+
+fn mirror_char(text: &str) -> Option<char> {
+    let mut chars = text.chars();
+    let c = chars.next()?;
+    if chars.next().is_some() {
+        return None;
+    }
+    Some(match c {
+        '(' => ')',
+        ')' => '(',
+        '[' => ']',
+        ']' => '[',
+        '{' => '}',
+        '}' => '{',
+        '<' => '>',
+        '>' => '<',
+        '«' => '»',
+        '»' => '«',
+        '‹' => '›',
+        '›' => '‹',
+        _ => return None,
+    })
+}
+
+/// Determines the paragraph embedding level for `text`.  The automatic
+/// directions use the first strongly directional character, falling back
+/// to the direction in their name if there isn't one.
+fn paragraph_level(text: &str, direction: BidiDirection) -> Level {
+    let fallback = match direction {
+        BidiDirection::LeftToRight => return Level::ltr(),
+        BidiDirection::RightToLeft => return Level::rtl(),
+        BidiDirection::AutoLeftToRight => Level::ltr(),
+        BidiDirection::AutoRightToLeft => Level::rtl(),
+    };
+    for c in text.chars() {
+        match bidi_class(c) {
+            BidiClass::L => return Level::ltr(),
+            BidiClass::R | BidiClass::AL => return Level::rtl(),
+            _ => {}
+        }
+    }
+    fallback
+}
+
+/// A grapheme and the logical columns that it occupies
+struct Unit {
+    col: usize,
+    width: usize,
+    bytes: Range<usize>,
+}
+
+fn push_unit(text: &mut String, units: &mut Vec<Unit>, col: usize, width: usize, s: &str) {
+    let start = text.len();
+    for c in s.chars() {
+        // Paragraph separators would otherwise split the analysis
+        // into multiple paragraphs
+        let c = if bidi_class(c) == BidiClass::B {
+            ' '
+        } else {
+            c
+        };
+        text.push(c);
+    }
+    units.push(Unit {
+        col,
+        width,
+        bytes: start..text.len(),
+    });
+}
+
+/// Computes the display order of the physical lines that make up a
+/// logical line.  Each physical line is treated as though it were padded
+/// with spaces to `cols` columns, so that a right to left paragraph is
+/// aligned to the right edge.  The result has an entry for each physical
+/// line, which is None if its cells are displayed in logical order.
+pub fn analyze_logical_line(
+    physical_lines: &[Line],
+    cols: usize,
+    direction: BidiDirection,
+) -> Vec<Option<BidiRow>> {
+    let mut text = String::new();
+    let mut lines: Vec<Vec<Unit>> = vec![];
+
+    for line in physical_lines {
+        let mut units = vec![];
+        let mut next_col = 0;
+        for (idx, cell) in line.visible_cells() {
+            if idx >= cols {
+                break;
+            }
+            let width = cell.width().max(1).min(cols - idx);
+            push_unit(&mut text, &mut units, idx, width, cell.str());
+            next_col = idx + width;
+        }
+        for col in next_col..cols {
+            push_unit(&mut text, &mut units, col, 1, " ");
+        }
+        lines.push(units);
+    }
+
+    let level = paragraph_level(&text, direction);
+    let needs_reordering = level.is_rtl()
+        || text.chars().any(|c| {
+            matches!(
+                bidi_class(c),
+                BidiClass::R | BidiClass::AL | BidiClass::RLE | BidiClass::RLO | BidiClass::RLI
+            )
+        });
+    if !needs_reordering {
+        return physical_lines.iter().map(|_| None).collect();
+    }
+
+    let info = BidiInfo::new(&text, Some(level));
+    lines
+        .iter()
+        .map(|units| {
+            let visual = reorder_line(units, &info.levels, &text, level);
+
+            let mut visual_to_logical = vec![];
+            let mut rtl = vec![false; cols];
+            for (unit, level) in visual {
+                // The cells of a wide character remain in order so
+                // that its glyph is drawn intact
+                for col in unit.col..unit.col + unit.width {
+                    visual_to_logical.push(col);
+                    rtl[col] = level % 2 == 1;
+                }
+            }
+
+            let is_identity = visual_to_logical
+                .iter()
+                .enumerate()
+                .all(|(visual, &logical)| visual == logical);
+            if is_identity {
+                None
+            } else {
+                Some(BidiRow::new(visual_to_logical, rtl))
+            }
+        })
+        .collect()
+}
+
+/// Returns true if `text` consists of characters that are reset to
+/// the paragraph level at the end of a line by rule L1
+fn is_whitespace(text: &str) -> bool {
+    text.chars().all(|c| {
+        matches!(
+            bidi_class(c),
+            BidiClass::WS
+                | BidiClass::BN
+                | BidiClass::LRE
+                | BidiClass::RLE
+                | BidiClass::LRO
+                | BidiClass::RLO
+                | BidiClass::PDF
+                | BidiClass::LRI
+                | BidiClass::RLI
+                | BidiClass::FSI
+                | BidiClass::PDI
+        )
+    })
+}
+
+/// Applies rules L1 and L2 to a single line, returning its units in
+/// visual order along with their resolved embedding levels
+fn reorder_line<'a>(
+    units: &'a [Unit],
+    levels: &[Level],
+    text: &str,
+    para_level: Level,
+) -> Vec<(&'a Unit, u8)> {
+    let mut line: Vec<(&Unit, u8)> = units
+        .iter()
+        .map(|unit| (unit, levels[unit.bytes.start].number()))
+        .collect();
+
+    // L1: whitespace at the end of the line takes the paragraph level
+    for (unit, level) in line.iter_mut().rev() {
+        if !is_whitespace(&text[unit.bytes.clone()]) {
+            break;
+        }
+        *level = para_level.number();
+    }
+
+    // L2: from the highest level to the lowest odd level, reverse
+    // each sequence of units at that level or higher
+    let highest = line.iter().map(|(_, level)| *level).max().unwrap_or(0);
+    let lowest_odd = line.iter().map(|(_, level)| *level).min().unwrap_or(0) | 1;
+    for level in (lowest_odd..=highest).rev() {
+        let mut idx = 0;
+        while idx < line.len() {
+            if line[idx].1 < level {
+                idx += 1;
+                continue;
+            }
+            let start = idx;
+            while idx < line.len() && line[idx].1 >= level {
+                idx += 1;
+            }
+            line[start..idx].reverse();
+        }
+    }
+
+    line
+}
+
+impl super::TermWindow {
+    /// Returns the display order for each of the rows in `rows`,
+    /// analyzing the complete logical lines that they belong to.
+    /// The result is empty if bidi is disabled.
+    pub fn bidi_rows(
+        &self,
+        pane: &Rc<dyn Pane>,
+        rows: Range<StableRowIndex>,
+        cols: usize,
+    ) -> Vec<Option<BidiRow>> {
+        if !self.config.bidi_enabled {
+            return vec![];
+        }
+
+        let mut result = vec![None; (rows.end - rows.start).max(0) as usize];
+        for logical in pane.get_logical_lines(rows.clone()) {
+            let analyzed =
+                analyze_logical_line(&logical.physical_lines, cols, self.config.bidi_direction);
+            for (idx, row) in analyzed.into_iter().enumerate() {
+                let stable_row = logical.first_row + idx as StableRowIndex;
+                if stable_row >= rows.start && stable_row < rows.end {
+                    result[(stable_row - rows.start) as usize] = row;
+                }
+            }
+        }
+        result
+    }
+
+    /// Maps the column under the mouse, which is in visual order, to the
+    /// logical column in the model
+    pub fn bidi_logical_col(
+        &self,
+        pane: &Rc<dyn Pane>,
+        stable_row: StableRowIndex,
+        visual: usize,
+    ) -> usize {
+        if !self.config.bidi_enabled {
+            return visual;
+        }
+        let cols = pane.get_dimensions().cols;
+        match self.bidi_rows(pane, stable_row..stable_row + 1, cols).pop() {
+            Some(Some(row)) => row.logical_col(visual),
+            _ => visual,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wezterm_term::CellAttributes;
+
+    fn line(s: &str) -> Line {
+        Line::from_text(s, &CellAttributes::default())
+    }
+
+    fn display(lines: &[Line], cols: usize, direction: BidiDirection) -> Vec<String> {
+        let analyzed = analyze_logical_line(lines, cols, direction);
+        lines
+            .iter()
+            .zip(analyzed.iter())
+            .map(|(line, row)| {
+                let mut line = line.clone();
+                line.resize(cols);
+                let cells = line.cells();
+                (0..cols)
+                    .map(|visual| {
+                        let logical = row
+                            .as_ref()
+                            .map(|row| row.logical_col(visual))
+                            .unwrap_or(visual);
+                        cells[logical].str().to_string()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ltr_is_unchanged() {
+        let lines = vec![line("hello")];
+        assert_eq!(
+            analyze_logical_line(&lines, 8, BidiDirection::AutoLeftToRight),
+            vec![None]
+        );
+    }
+
+    #[test]
+    fn rtl_run_in_ltr_paragraph() {
+        let lines = vec![line("ab אבג cd")];
+        assert_eq!(
+            display(&lines, 9, BidiDirection::AutoLeftToRight),
+            vec!["ab גבא cd"]
+        );
+    }
+
+    #[test]
+    fn rtl_paragraph() {
+        let lines = vec![line("אבג ab")];
+        assert_eq!(
+            display(&lines, 8, BidiDirection::AutoLeftToRight),
+            vec!["  ab גבא"]
+        );
+        // Forcing the direction keeps the hebrew to the left
+        assert_eq!(
+            display(&lines, 8, BidiDirection::LeftToRight),
+            vec!["גבא ab  "]
+        );
+    }
+
+    #[test]
+    fn wrapped_line_uses_first_strong_char() {
+        // The second physical line has no strong characters of its
+        // own, but belongs to a right to left paragraph
+        let mut first = line("אבג");
+        first.set_last_cell_was_wrapped(true);
+        let lines = vec![first, line("12")];
+        assert_eq!(
+            display(&lines, 3, BidiDirection::AutoLeftToRight),
+            vec!["גבא", " 12"]
+        );
+    }
+
+    #[test]
+    fn mapping_and_mirroring() {
+        let lines = vec![line("א(ב)")];
+        let row = analyze_logical_line(&lines, 4, BidiDirection::RightToLeft)
+            .pop()
+            .unwrap()
+            .unwrap();
+        assert_eq!(row.visual_col(0), 3);
+        assert_eq!(row.logical_col(3), 0);
+        assert_eq!(row.visual_col(10), 10);
+
+        let mut mirrored = lines[0].clone();
+        row.mirror_line(&mut mirrored);
+        assert_eq!(mirrored.as_str(), "א)ב(");
+    }
+}
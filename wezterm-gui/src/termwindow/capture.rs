@@ -0,0 +1,134 @@
+//! Saves an image of a pane, or of the whole window, as a PNG file.
+//! The capture is taken while painting the next frame, by drawing the
+//! same quads that are presented in the window into an offscreen
+//! texture and reading it back.
+use ::window::glium::framebuffer::SimpleFrameBuffer;
+use ::window::glium::texture::{MipmapsOption, RawImage2d, SrgbFormat, SrgbTexture2d};
+use ::window::WindowOps;
+use anyhow::anyhow;
+use config::keyassignment::CaptureArguments;
+use mux::pane::PaneId;
+use std::path::PathBuf;
+use wezterm_toast_notification::persistent_toast_notification;
+
+pub struct PendingCapture {
+    /// The pane to capture, or None to capture the whole window
+    pane_id: Option<PaneId>,
+    include_tab_bar: bool,
+    path: PathBuf,
+}
+
+impl super::TermWindow {
+    /// Arranges for an image of `pane_id`, or of the whole window, to
+    /// be saved when the next frame is painted
+    pub fn capture(&mut self, pane_id: PaneId, whole_window: bool, args: &CaptureArguments) {
+        self.pending_capture.replace(PendingCapture {
+            pane_id: if whole_window { None } else { Some(pane_id) },
+            include_tab_bar: args.include_tab_bar,
+            path: args.resolve_path(),
+        });
+        if let Some(window) = self.window.as_ref() {
+            window.invalidate();
+        }
+    }
+
+    /// Called by `paint_impl` once the quads for the frame are ready
+    pub fn save_pending_capture(&mut self) {
+        let capture = match self.pending_capture.take() {
+            Some(capture) => capture,
+            None => return,
+        };
+        match self.capture_to_png(&capture) {
+            Ok(()) => {
+                log::info!("Saved capture to {}", capture.path.display());
+                persistent_toast_notification("Capture saved", &capture.path.display().to_string());
+            }
+            Err(err) => {
+                log::error!(
+                    "Failed to save capture to {}: {:#}",
+                    capture.path.display(),
+                    err
+                );
+                persistent_toast_notification("Capture failed", &format!("{:#}", err));
+            }
+        }
+    }
+
+    /// Returns the left, top, width and height of the area to capture,
+    /// in pixels from the top left corner of the window
+    fn capture_rect(&mut self, capture: &PendingCapture) -> anyhow::Result<(u32, u32, u32, u32)> {
+        let width = self.dimensions.pixel_width as u32;
+        let height = self.dimensions.pixel_height as u32;
+        let cell_width = self.render_metrics.cell_size.width as u32;
+        let cell_height = self.render_metrics.cell_size.height as u32;
+        let padding_left = self.config.window_padding.left as u32;
+        let padding_top = self.config.window_padding.top as u32;
+
+        let pane_id = match capture.pane_id {
+            Some(pane_id) => pane_id,
+            None if capture.include_tab_bar || !self.show_tab_bar => {
+                return Ok((0, 0, width, height));
+            }
+            None => {
+                let top = (padding_top + cell_height).min(height);
+                return Ok((0, top, width, height - top));
+            }
+        };
+
+        // The pane may currently be displaying an overlay
+        let overlay = self
+            .pane_state(pane_id)
+            .overlay
+            .as_ref()
+            .map(|overlay| overlay.pane.pane_id());
+        let panes = self.get_panes_to_render();
+        let pos = panes
+            .iter()
+            .find(|pos| {
+                let id = pos.pane.pane_id();
+                id == pane_id || Some(id) == overlay
+            })
+            .ok_or_else(|| anyhow!("pane {} is not visible in its window", pane_id))?;
+
+        let left = (padding_left + pos.left as u32 * cell_width).min(width);
+        let top =
+            (padding_top + (pos.top + self.rows_above_panes()) as u32 * cell_height).min(height);
+        Ok((
+            left,
+            top,
+            (pos.width as u32 * cell_width).min(width - left),
+            (pos.height as u32 * cell_height).min(height - top),
+        ))
+    }
+
+    fn capture_to_png(&mut self, capture: &PendingCapture) -> anyhow::Result<()> {
+        let (left, top, width, height) = self.capture_rect(capture)?;
+        anyhow::ensure!(width > 0 && height > 0, "there is nothing to capture");
+
+        let context = match self.render_state.as_ref() {
+            Some(gl_state) => gl_state.context.clone(),
+            None => anyhow::bail!("no OpenGL context"),
+        };
+        let texture = SrgbTexture2d::empty_with_format(
+            &context,
+            SrgbFormat::U8U8U8U8,
+            MipmapsOption::NoMipmap,
+            self.dimensions.pixel_width as u32,
+            self.dimensions.pixel_height as u32,
+        )?;
+        {
+            let mut target = SimpleFrameBuffer::new(&context, &texture)?;
+            self.clear_to_background(&mut target);
+            self.draw_quads(&mut target)?;
+        }
+
+        let raw: RawImage2d<u8> = texture.read();
+        let image = image::RgbaImage::from_raw(raw.width, raw.height, raw.data.into_owned())
+            .ok_or_else(|| anyhow!("the captured image has an unexpected size"))?;
+        // OpenGL numbers rows from the bottom of the texture
+        let image = image::imageops::flip_vertical(&image);
+        let image = image::imageops::crop_imm(&image, left, top, width, height).to_image();
+        image.save_with_format(&capture.path, image::ImageFormat::Png)?;
+        Ok(())
+    }
+}
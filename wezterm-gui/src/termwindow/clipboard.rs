@@ -1,13 +1,37 @@
+use crate::overlay::{
+    confirm_clipboard_read, remember_clipboard_text, start_overlay_pane, OverlayState,
+};
 use crate::TermWindow;
 use config::keyassignment::{ClipboardCopyDestination, ClipboardPasteSource};
-use mux::pane::Pane;
+use config::{ClipboardReadPermission, ConfigHandle};
+use mux::pane::{Pane, PaneId};
 use mux::window::WindowId as MuxWindowId;
 use mux::Mux;
+use std::io::Write;
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
+use termwiz::escape::osc::Selection;
+use termwiz::escape::OperatingSystemCommand;
 use wezterm_term::ClipboardSelection;
 use window::{Clipboard, Window, WindowOps};
 
+/// Normalize pasted text prior to sending it to the pane.
+/// CRLF line endings are converted to LF; the terminal will
+/// convert them back if the platform requires it.
+/// If configured, the newline following a single line of text
+/// is removed so that the shell doesn't immediately run it.
+fn prepare_paste(text: &str, config: &ConfigHandle) -> String {
+    let mut text = text.replace("\r\n", "\n");
+    if config.strip_trailing_newline_from_single_line_paste
+        && text.ends_with('\n')
+        && !text[..text.len() - 1].contains('\n')
+    {
+        text.pop();
+    }
+    text
+}
+
 /// ClipboardHelper bridges between the window crate clipboard
 /// manipulation and the term crate clipboard interface
 #[derive(Clone)]
@@ -73,6 +97,7 @@ impl TermWindow {
     }
 
     pub fn copy_to_clipboard(&self, clipboard: ClipboardCopyDestination, text: String) {
+        remember_clipboard_text(&text, self.config.clipboard_history_size);
         let clipboard = match clipboard {
             ClipboardCopyDestination::Clipboard => [Some(Clipboard::Clipboard), None],
             ClipboardCopyDestination::PrimarySelection => [Some(Clipboard::PrimarySelection), None],
@@ -101,15 +126,18 @@ impl TermWindow {
             if let Ok(clip) = future.await {
                 window
                     .apply(move |term_window, _window| {
-                        let clip = clip.clone();
                         if let Some(term_window) = term_window.downcast_mut::<TermWindow>() {
-                            if let Some(pane) =
-                                term_window.pane_state(pane_id).overlay.clone().or_else(|| {
+                            if let Some(pane) = term_window
+                                .pane_state(pane_id)
+                                .overlay
+                                .as_ref()
+                                .map(|overlay| Rc::clone(&overlay.pane))
+                                .or_else(|| {
                                     let mux = Mux::get().unwrap();
                                     mux.get_pane(pane_id)
                                 })
                             {
-                                pane.trickle_paste(clip).ok();
+                                term_window.paste_text(&pane, &clip);
                             }
                         }
                         Ok(())
@@ -120,4 +148,163 @@ impl TermWindow {
         })
         .detach();
     }
+
+    /// Called when a program in `pane_id` uses OSC 52 to ask for the
+    /// contents of the clipboard.  Depending on the configuration, the
+    /// query is answered, ignored, or the user is asked whether programs
+    /// in that pane may read the clipboard.
+    pub fn clipboard_query(&mut self, pane_id: PaneId, selection: ClipboardSelection) {
+        let mux = Mux::get().unwrap();
+        let pane = match mux.get_pane(pane_id) {
+            Some(pane) => pane,
+            None => return,
+        };
+        let allow = match self.config.clipboard_read_permission {
+            ClipboardReadPermission::Deny => false,
+            ClipboardReadPermission::Allow => true,
+            ClipboardReadPermission::Prompt => {
+                let allowed_domain = mux
+                    .get_domain(pane.domain_id())
+                    .map(|domain| {
+                        self.config
+                            .clipboard_read_allowed_domains
+                            .iter()
+                            .any(|name| name == domain.domain_name())
+                    })
+                    .unwrap_or(false);
+                let answer = self.pane_state(pane_id).clipboard_read;
+                match answer {
+                    Some(answer) => answer,
+                    None if allowed_domain => true,
+                    None => {
+                        self.prompt_for_clipboard_read(&pane, selection);
+                        return;
+                    }
+                }
+            }
+        };
+        if allow {
+            self.respond_to_clipboard_query(pane_id, selection);
+        } else {
+            log::info!("ignoring OSC 52 clipboard query from pane {}", pane_id);
+        }
+    }
+
+    fn prompt_for_clipboard_read(&mut self, pane: &Rc<dyn Pane>, selection: ClipboardSelection) {
+        let pane_id = pane.pane_id();
+        if self.pane_state(pane_id).overlay.is_some() {
+            // Don't interrupt whatever is already happening in the pane,
+            // which may be an earlier prompt; ask again next time
+            log::info!(
+                "ignoring OSC 52 clipboard query from pane {} while it has an overlay",
+                pane_id
+            );
+            return;
+        }
+        let window = self.window.clone().unwrap();
+        let (overlay, future) = start_overlay_pane(self, pane, move |pane_id, term| {
+            confirm_clipboard_read(pane_id, term, window, selection)
+        });
+        self.assign_overlay_for_pane(pane_id, OverlayState::new(overlay));
+        promise::spawn::spawn(future).detach();
+    }
+
+    /// Called with the answer given at the clipboard read prompt, which
+    /// is remembered for the rest of the session
+    pub fn clipboard_read_answered(
+        &mut self,
+        pane_id: PaneId,
+        allow: bool,
+        selection: ClipboardSelection,
+    ) {
+        self.pane_state(pane_id).clipboard_read.replace(allow);
+        if allow {
+            self.respond_to_clipboard_query(pane_id, selection);
+        }
+    }
+
+    /// Sends the contents of the clipboard to `pane_id` as an OSC 52 response
+    fn respond_to_clipboard_query(&self, pane_id: PaneId, selection: ClipboardSelection) {
+        let window = self.window.as_ref().unwrap().clone();
+        let (clipboard, osc_selection) = match selection {
+            ClipboardSelection::Clipboard => (Clipboard::Clipboard, Selection::CLIPBOARD),
+            ClipboardSelection::PrimarySelection => {
+                (Clipboard::PrimarySelection, Selection::PRIMARY)
+            }
+        };
+        let future = window.get_clipboard(clipboard);
+
+        promise::spawn::spawn(async move {
+            let clip = future.await?;
+            let mux = Mux::get().unwrap();
+            if let Some(pane) = mux.get_pane(pane_id) {
+                let response = OperatingSystemCommand::SetSelection(osc_selection, clip);
+                write!(pane.writer(), "{}", response)?;
+            }
+            Ok::<(), anyhow::Error>(())
+        })
+        .detach();
+    }
+
+    /// Pastes `text` into the panes that receive input for `pane`
+    fn paste_text(&self, pane: &Rc<dyn Pane>, text: &str) {
+        let text = prepare_paste(text, &self.config);
+        for target in self.input_targets(pane) {
+            target.trickle_paste(text.clone()).ok();
+        }
+    }
+
+    /// Called by overlays that paste text once an entry has been
+    /// chosen.  The overlay is dismissed before pasting so that the
+    /// text is sent to the pane beneath it rather than to the overlay.
+    pub fn schedule_paste_to_pane(window: Window, pane_id: PaneId, text: String) {
+        window.apply(move |myself, _| {
+            if let Some(myself) = myself.downcast_mut::<Self>() {
+                myself.cancel_overlay_for_pane(pane_id);
+                let mux = Mux::get().unwrap();
+                if let Some(pane) = mux.get_pane(pane_id) {
+                    myself.paste_text(&pane, &text);
+                }
+            }
+            Ok(())
+        });
+    }
+
+    /// Called when files are dropped onto the window.
+    /// Pastes their quoted paths into the active pane, or changes
+    /// to a dropped directory when cd_on_dropped_directory is enabled
+    /// and the pane is sitting at a shell prompt.
+    pub fn paste_dropped_files(&mut self, paths: Vec<PathBuf>) {
+        let pane = match self.get_active_pane_or_overlay() {
+            Some(pane) => pane,
+            None => return,
+        };
+        if paths.is_empty() {
+            return;
+        }
+        let quoting = self.config.quote_dropped_files;
+
+        if self.config.cd_on_dropped_directory
+            && paths.len() == 1
+            && paths[0].is_dir()
+            && !pane.is_alt_screen_active()
+            && pane.can_close_without_prompting()
+        {
+            let cd = format!("cd {}\r", quoting.quote(&paths[0].to_string_lossy()));
+            for target in self.input_targets(&pane) {
+                target.writer().write_all(cd.as_bytes()).ok();
+            }
+            return;
+        }
+
+        let mut text = paths
+            .iter()
+            .map(|path| quoting.quote(&path.to_string_lossy()))
+            .collect::<Vec<_>>()
+            .join(" ");
+        text.push(' ');
+        for target in self.input_targets(&pane) {
+            target.trickle_paste(text.clone()).ok();
+        }
+    }
 }
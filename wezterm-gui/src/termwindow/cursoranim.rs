@@ -0,0 +1,238 @@
+//! Tracks the movement of the text cursor so that it can glide
+//! between cells, optionally leaving a fading trail behind it.
+use crate::quad::CURSOR_TRAIL_QUADS;
+use config::EasingFunction;
+use mux::pane::PaneId;
+use std::time::{Duration, Instant};
+
+/// A position measured in cells, relative to the top left of the
+/// window.  Fractional values are used while the cursor is moving.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CellPoint {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl CellPoint {
+    fn lerp(self, other: Self, amount: f32) -> Self {
+        Self {
+            x: self.x + (other.x - self.x) * amount,
+            y: self.y + (other.y - self.y) * amount,
+        }
+    }
+}
+
+/// Where to draw the cursor for the current frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimatedCursor {
+    pub position: CellPoint,
+    /// Earlier positions, oldest first, along with their opacity
+    pub trail: Vec<(CellPoint, f32)>,
+}
+
+#[derive(Clone)]
+pub struct CursorAnimation {
+    pane_id: Option<PaneId>,
+    from: CellPoint,
+    to: CellPoint,
+    started: Instant,
+    trail: Vec<(CellPoint, Instant)>,
+}
+
+impl CursorAnimation {
+    pub fn new() -> Self {
+        let origin = CellPoint { x: 0., y: 0. };
+        Self {
+            pane_id: None,
+            from: origin,
+            to: origin,
+            started: Instant::now(),
+            trail: vec![],
+        }
+    }
+
+    /// Forget the current position, so that the next update
+    /// places the cursor without animating it
+    pub fn reset(&mut self) {
+        self.pane_id.take();
+        self.trail.clear();
+    }
+
+    fn position_at(&self, now: Instant, duration: Duration, easing: EasingFunction) -> CellPoint {
+        let elapsed = now.saturating_duration_since(self.started);
+        let progress = elapsed.as_secs_f32() / duration.as_secs_f32();
+        self.from.lerp(self.to, easing.evaluate(progress))
+    }
+
+    /// Called for each frame in which the cursor of `pane_id` resides
+    /// in the cell at `to`.  Returns None if the cursor is at rest and
+    /// should be drawn in its cell in the usual way, otherwise returns
+    /// where the cursor and its trail should be drawn.
+    pub fn update(
+        &mut self,
+        pane_id: PaneId,
+        to: CellPoint,
+        duration: Duration,
+        easing: EasingFunction,
+        trail: bool,
+        now: Instant,
+    ) -> Option<AnimatedCursor> {
+        if duration == Duration::from_millis(0) || self.pane_id != Some(pane_id) {
+            self.pane_id = Some(pane_id);
+            self.from = to;
+            self.to = to;
+            self.trail.clear();
+            return None;
+        }
+
+        if to != self.to {
+            // Start from wherever we are currently drawn, so that
+            // a move that interrupts another doesn't jump
+            self.from = self.position_at(now, duration, easing);
+            self.to = to;
+            self.started = now;
+        }
+
+        self.trail
+            .retain(|(_, when)| now.saturating_duration_since(*when) < duration);
+
+        let moving = now.saturating_duration_since(self.started) < duration;
+        if !moving && self.trail.is_empty() {
+            return None;
+        }
+
+        let position = if moving {
+            self.position_at(now, duration, easing)
+        } else {
+            to
+        };
+
+        if trail && moving {
+            self.trail.push((position, now));
+            if self.trail.len() > CURSOR_TRAIL_QUADS {
+                self.trail.remove(0);
+            }
+        }
+
+        Some(AnimatedCursor {
+            position,
+            trail: self
+                .trail
+                .iter()
+                .map(|(point, when)| {
+                    let age = now.saturating_duration_since(*when).as_secs_f32();
+                    (*point, 0.5 * (1.0 - age / duration.as_secs_f32()))
+                })
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn point(x: f32, y: f32) -> CellPoint {
+        CellPoint { x, y }
+    }
+
+    #[test]
+    fn easing() {
+        for easing in &[
+            EasingFunction::Linear,
+            EasingFunction::EaseIn,
+            EasingFunction::EaseOut,
+            EasingFunction::EaseInOut,
+        ] {
+            assert_eq!(easing.evaluate(-1.0), 0.0, "{:?}", easing);
+            assert_eq!(easing.evaluate(0.0), 0.0, "{:?}", easing);
+            assert_eq!(easing.evaluate(1.0), 1.0, "{:?}", easing);
+            assert_eq!(easing.evaluate(2.0), 1.0, "{:?}", easing);
+        }
+        assert!(EasingFunction::EaseIn.evaluate(0.5) < 0.5);
+        assert!(EasingFunction::EaseOut.evaluate(0.5) > 0.5);
+        assert_eq!(EasingFunction::EaseInOut.evaluate(0.5), 0.5);
+    }
+
+    /// Updates `anim` at `millis` after `start` with a linear easing
+    fn step(
+        anim: &mut CursorAnimation,
+        pane_id: PaneId,
+        to: CellPoint,
+        duration: u64,
+        trail: bool,
+        start: Instant,
+        millis: u64,
+    ) -> Option<AnimatedCursor> {
+        anim.update(
+            pane_id,
+            to,
+            Duration::from_millis(duration),
+            EasingFunction::Linear,
+            trail,
+            start + Duration::from_millis(millis),
+        )
+    }
+
+    #[test]
+    fn movement() {
+        let start = Instant::now();
+        let mut anim = CursorAnimation::new();
+        let mut update = |to, millis| step(&mut anim, 1, to, 100, false, start, millis);
+
+        // The first position is never animated
+        assert_eq!(update(point(0., 0.), 0), None);
+
+        let moving = update(point(10., 0.), 10).unwrap();
+        assert_eq!(moving.position, point(0., 0.));
+        let moving = update(point(10., 0.), 60).unwrap();
+        assert_eq!(moving.position, point(5., 0.));
+
+        // Moving again part way through starts from where it is drawn
+        let moving = update(point(10., 10.), 60).unwrap();
+        assert_eq!(moving.position, point(5., 0.));
+        let moving = update(point(10., 10.), 110).unwrap();
+        assert_eq!(moving.position, point(7.5, 5.));
+
+        assert_eq!(update(point(10., 10.), 160), None);
+    }
+
+    #[test]
+    fn trail_fades() {
+        let start = Instant::now();
+        let mut anim = CursorAnimation::new();
+        let mut update = |to, millis| step(&mut anim, 1, to, 100, true, start, millis);
+
+        update(point(0., 0.), 0);
+        let moving = update(point(4., 0.), 0).unwrap();
+        assert_eq!(moving.trail, vec![(point(0., 0.), 0.5)]);
+
+        let moving = update(point(4., 0.), 50).unwrap();
+        assert_eq!(
+            moving.trail,
+            vec![(point(0., 0.), 0.25), (point(2., 0.), 0.5)]
+        );
+
+        // Once the cursor has arrived, the trail remains until it fades
+        let resting = update(point(4., 0.), 120).unwrap();
+        assert_eq!(resting.position, point(4., 0.));
+        assert_eq!(resting.trail.len(), 1);
+
+        assert_eq!(update(point(4., 0.), 200), None);
+    }
+
+    #[test]
+    fn disabled_or_changing_pane() {
+        let start = Instant::now();
+        let mut anim = CursorAnimation::new();
+
+        step(&mut anim, 1, point(0., 0.), 0, true, start, 0);
+        assert_eq!(step(&mut anim, 1, point(5., 5.), 0, true, start, 10), None);
+
+        step(&mut anim, 1, point(0., 0.), 100, true, start, 20);
+        assert_eq!(
+            step(&mut anim, 2, point(5., 5.), 100, true, start, 30),
+            None
+        );
+    }
+}
@@ -0,0 +1,115 @@
+//! Implements the drop down window that is summoned and dismissed
+//! by the global hotkey configured via `drop_down_window`.
+use ::window::{Connection, ConnectionOps, ScreenPoint, ScreenRect, Window, WindowOps};
+use config::{configuration, DropDownWindow};
+use std::time::{Duration, Instant};
+
+/// How often the window is moved while it is sliding
+const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+impl super::TermWindow {
+    /// Called when the drop down hotkey is pressed.
+    /// A hidden window slides down into view from the top of the
+    /// active monitor, a visible window that doesn't have the focus
+    /// is focused, and a focused window slides back up and is hidden.
+    /// The mux window, and thus its tabs, remains alive while
+    /// the window is hidden.
+    pub fn toggle_drop_down(&mut self) {
+        let config = configuration();
+        let drop_down = match &config.drop_down_window {
+            Some(drop_down) => drop_down.clone(),
+            None => return,
+        };
+        let window = match self.window.clone() {
+            Some(window) => window,
+            None => return,
+        };
+
+        let showing = if self.drop_down_hidden {
+            true
+        } else if self.focused.is_none() {
+            window.focus();
+            return;
+        } else {
+            false
+        };
+        self.drop_down_hidden = !showing;
+
+        let conn = Connection::get().unwrap();
+        let screens = conn.screens();
+        let screen = match drop_down.monitor {
+            Some(monitor) => screens.get(monitor),
+            None => conn
+                .pointer_position()
+                .and_then(|pointer| screens.iter().find(|screen| screen.contains(pointer))),
+        };
+        let screen = match screen.or_else(|| screens.first()) {
+            Some(screen) => *screen,
+            None => {
+                // We don't know where the monitor is, so we can't
+                // animate; just toggle the visibility
+                if showing {
+                    window.show();
+                    window.focus();
+                } else {
+                    window.hide();
+                }
+                return;
+            }
+        };
+
+        if showing {
+            let (width, height) = drop_down_size(&drop_down, &screen);
+            window.set_inner_size(width as usize, height as usize);
+            window.set_window_position(drop_down_position(&drop_down, &screen, 0.));
+            window.show();
+            window.focus();
+        }
+        animate_drop_down(window, screen, drop_down, showing);
+    }
+}
+
+fn drop_down_size(drop_down: &DropDownWindow, screen: &ScreenRect) -> (isize, isize) {
+    (
+        drop_down.width.evaluate(screen.size.width).max(1),
+        drop_down.height.evaluate(screen.size.height).max(1),
+    )
+}
+
+/// Computes the position of the window when `visible` (from 0.0
+/// to 1.0) of its height has slid down from the top of the screen
+fn drop_down_position(
+    drop_down: &DropDownWindow,
+    screen: &ScreenRect,
+    visible: f32,
+) -> ScreenPoint {
+    let (width, height) = drop_down_size(drop_down, screen);
+    ScreenPoint::new(
+        screen.origin.x + (screen.size.width - width) / 2,
+        screen.origin.y - height + (height as f32 * visible) as isize,
+    )
+}
+
+fn animate_drop_down(window: Window, screen: ScreenRect, drop_down: DropDownWindow, showing: bool) {
+    let duration = Duration::from_millis(drop_down.animation_duration_milliseconds);
+    promise::spawn::spawn(async move {
+        let start = Instant::now();
+        loop {
+            let progress = if duration.as_millis() == 0 {
+                1.0
+            } else {
+                (start.elapsed().as_secs_f32() / duration.as_secs_f32()).min(1.0)
+            };
+            let visible = if showing { progress } else { 1.0 - progress };
+            window.set_window_position(drop_down_position(&drop_down, &screen, visible));
+            if progress >= 1.0 {
+                break;
+            }
+            smol::Timer::after(FRAME_INTERVAL).await;
+        }
+        if !showing {
+            window.hide();
+        }
+    })
+    .detach();
+}
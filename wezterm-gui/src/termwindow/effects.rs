@@ -0,0 +1,108 @@
+//! Short-lived visual effects that are drawn over the top of the
+//! window or of a pane, such as the visual bell.
+//! Features trigger an effect via `TermWindow::flash` and the paint
+//! path takes care of animating it and scheduling the frames that
+//! are needed until it has finished.
+use config::EasingFunction;
+use mux::pane::PaneId;
+use std::time::{Duration, Instant};
+use wezterm_term::color::RgbColor;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EffectTarget {
+    /// Covers the whole window, including the tab bar and padding
+    Window,
+    /// Covers the area occupied by the specified pane
+    Pane(PaneId),
+}
+
+/// Briefly tints the target with `color`, which then fades out
+#[derive(Debug, Clone, Copy)]
+pub struct Flash {
+    pub target: EffectTarget,
+    pub color: RgbColor,
+    /// The opacity at the start of the flash, in the range 0.0..=1.0
+    pub opacity: f32,
+    pub duration: Duration,
+    /// The easing function that is applied to the fade out
+    pub easing: EasingFunction,
+}
+
+impl Flash {
+    /// Returns the opacity of the flash at `elapsed`, or None once it
+    /// has finished
+    fn opacity_at(&self, elapsed: Duration) -> Option<f32> {
+        if elapsed >= self.duration {
+            return None;
+        }
+        let progress = elapsed.as_secs_f32() / self.duration.as_secs_f32();
+        Some(self.opacity * (1.0 - self.easing.evaluate(progress)))
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Effects {
+    flashes: Vec<(Flash, Instant)>,
+}
+
+impl Effects {
+    /// Starts a flash, replacing any flash that is in progress for
+    /// the same target
+    pub fn flash(&mut self, flash: Flash, now: Instant) {
+        self.flashes.retain(|(f, _)| f.target != flash.target);
+        self.flashes.push((flash, now));
+    }
+
+    /// Returns the target, color and opacity of each effect that is
+    /// visible at `now`, forgetting those that have finished
+    pub fn visible(&mut self, now: Instant) -> Vec<(EffectTarget, RgbColor, f32)> {
+        let mut visible = vec![];
+        self.flashes.retain(|(flash, started)| {
+            match flash.opacity_at(now.saturating_duration_since(*started)) {
+                Some(opacity) => {
+                    visible.push((flash.target, flash.color, opacity));
+                    true
+                }
+                None => false,
+            }
+        });
+        visible
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn flash_fades_out() {
+        let start = Instant::now();
+        let close = |a: f32, b: f32| (a - b).abs() < 0.001;
+        let at = |ms| start + Duration::from_millis(ms);
+        let flash = |target| Flash {
+            target,
+            color: RgbColor::new(0xff, 0xff, 0xff),
+            opacity: 0.5,
+            duration: Duration::from_millis(100),
+            easing: EasingFunction::Linear,
+        };
+
+        let mut effects = Effects::default();
+        effects.flash(flash(EffectTarget::Window), start);
+        effects.flash(flash(EffectTarget::Pane(1)), at(50));
+
+        let visible = effects.visible(at(50));
+        assert_eq!(visible.len(), 2);
+        assert!(close(visible[0].2, 0.25));
+        assert!(close(visible[1].2, 0.5));
+
+        // Flashing the same target again restarts it
+        effects.flash(flash(EffectTarget::Pane(1)), at(100));
+        let visible = effects.visible(at(100));
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].0, EffectTarget::Pane(1));
+        assert!(close(visible[0].2, 0.5));
+
+        assert!(effects.visible(at(200)).is_empty());
+    }
+}
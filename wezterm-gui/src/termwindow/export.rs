@@ -0,0 +1,157 @@
+//! Serializes lines from a pane along with their colors and attributes,
+//! so that they can be copied as HTML or as text containing ANSI escape
+//! sequences.  The latter is shared with the mux, which uses it to save
+//! the contents of panes.
+pub use mux::export::{to_ansi, ExportLine};
+use wezterm_term::color::ColorPalette;
+use wezterm_term::{CellAttributes, ColorAttribute, Intensity, Underline, VerticalAlign};
+
+fn escape_html(text: &str, s: &mut String) {
+    for c in text.chars() {
+        match c {
+            '&' => s.push_str("&amp;"),
+            '<' => s.push_str("&lt;"),
+            '>' => s.push_str("&gt;"),
+            '"' => s.push_str("&quot;"),
+            c => s.push(c),
+        }
+    }
+}
+
+/// Returns the inline CSS that styles text with `attrs`, resolving
+/// colors through `palette`
+fn css_for_attrs(attrs: &CellAttributes, palette: &ColorPalette) -> String {
+    let mut fg = palette.resolve_fg(attrs.foreground);
+    let mut bg = palette.resolve_bg(attrs.background);
+    let mut default_bg = attrs.background == ColorAttribute::Default;
+    if attrs.reverse() {
+        std::mem::swap(&mut fg, &mut bg);
+        default_bg = false;
+    }
+
+    let mut css = vec![];
+    if fg != palette.foreground {
+        css.push(format!("color:{}", fg.to_rgb_string()));
+    }
+    if !default_bg {
+        css.push(format!("background-color:{}", bg.to_rgb_string()));
+    }
+    match attrs.intensity() {
+        Intensity::Normal => {}
+        Intensity::Bold => css.push("font-weight:bold".to_string()),
+        Intensity::Half => css.push("opacity:0.5".to_string()),
+    }
+    if attrs.italic() {
+        css.push("font-style:italic".to_string());
+    }
+    match attrs.vertical_align() {
+        VerticalAlign::BaseLine => {}
+        VerticalAlign::SuperScript => css.push("vertical-align:super".to_string()),
+        VerticalAlign::SubScript => css.push("vertical-align:sub".to_string()),
+    }
+
+    let mut decoration = vec![];
+    if attrs.underline() != Underline::None {
+        decoration.push("underline");
+    }
+    if attrs.strikethrough() {
+        decoration.push("line-through");
+    }
+    if attrs.overline() {
+        decoration.push("overline");
+    }
+    if !decoration.is_empty() {
+        css.push(format!("text-decoration:{}", decoration.join(" ")));
+    }
+    if attrs.invisible() {
+        css.push("visibility:hidden".to_string());
+    }
+    css.join(";")
+}
+
+/// Returns an HTML fragment that displays `lines` in the colors of
+/// `palette`, with hyperlinks turned into links
+pub fn to_html(lines: &[ExportLine], palette: &ColorPalette) -> String {
+    let mut s = format!(
+        "<pre style=\"background-color:{};color:{};font-family:monospace\">",
+        palette.background.to_rgb_string(),
+        palette.foreground.to_rgb_string()
+    );
+
+    for (idx, export) in lines.iter().enumerate() {
+        for cluster in export.line.cluster() {
+            if let Some(link) = cluster.attrs.hyperlink() {
+                s.push_str("<a href=\"");
+                escape_html(link.uri(), &mut s);
+                s.push_str("\">");
+            }
+            let css = css_for_attrs(&cluster.attrs, palette);
+            if css.is_empty() {
+                escape_html(&cluster.text, &mut s);
+            } else {
+                s.push_str("<span style=\"");
+                s.push_str(&css);
+                s.push_str("\">");
+                escape_html(&cluster.text, &mut s);
+                s.push_str("</span>");
+            }
+            if cluster.attrs.hyperlink().is_some() {
+                s.push_str("</a>");
+            }
+        }
+        if !export.wrapped && idx + 1 < lines.len() {
+            s.push('\n');
+        }
+    }
+
+    s.push_str("</pre>");
+    s
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Arc;
+    use termwiz::hyperlink::Hyperlink;
+    use wezterm_term::Line;
+
+    fn styled_lines() -> Vec<ExportLine> {
+        let mut bold = CellAttributes::default();
+        bold.set_intensity(Intensity::Bold)
+            .set_foreground(ColorAttribute::PaletteIndex(1));
+        let mut link = CellAttributes::default();
+        link.set_hyperlink(Some(Arc::new(Hyperlink::new("https://example.com/?a&b"))));
+
+        let mut cells = Line::from_text("a<b ", &CellAttributes::default())
+            .cells()
+            .to_vec();
+        cells.extend_from_slice(Line::from_text("red", &bold).cells());
+
+        vec![
+            ExportLine {
+                line: Line::from_cells(cells),
+                wrapped: false,
+            },
+            ExportLine {
+                line: Line::from_text("link", &link),
+                wrapped: false,
+            },
+        ]
+    }
+
+    #[test]
+    fn html() {
+        let palette = ColorPalette::default();
+        assert_eq!(
+            to_html(&styled_lines(), &palette),
+            format!(
+                "<pre style=\"background-color:{};color:{};font-family:monospace\">\
+                 a&lt;b <span style=\"color:{};font-weight:bold\">red</span>\n\
+                 <a href=\"https://example.com/?a&amp;b\">link</a></pre>",
+                palette.background.to_rgb_string(),
+                palette.foreground.to_rgb_string(),
+                palette.colors.0[1].to_rgb_string(),
+            )
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! Tracks whether a window has gone without input or output for long
+//! enough to be considered idle, so that it can be dimmed and can stop
+//! doing work that only matters while someone is looking at it.
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+pub struct IdleState {
+    last_activity: Instant,
+    idle: bool,
+}
+
+impl IdleState {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            last_activity: now,
+            idle: false,
+        }
+    }
+
+    /// Records input or output.  Returns true if this ends the idle state.
+    pub fn activity(&mut self, now: Instant) -> bool {
+        self.last_activity = now;
+        std::mem::replace(&mut self.idle, false)
+    }
+
+    /// Returns true if the window has just become idle.
+    /// A zero `timeout` disables idle detection.
+    pub fn check(&mut self, timeout: Duration, now: Instant) -> bool {
+        if self.idle || timeout == Duration::from_secs(0) {
+            return false;
+        }
+        self.idle = now.saturating_duration_since(self.last_activity) >= timeout;
+        self.idle
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.idle
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn becomes_idle() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let timeout = Duration::from_secs(60);
+
+        let mut state = IdleState::new(start);
+        assert!(!state.check(timeout, at(30)));
+        assert!(!state.activity(at(40)));
+        assert!(!state.check(timeout, at(90)));
+        assert!(state.check(timeout, at(100)));
+        assert!(state.is_idle());
+        // Only the transition is reported
+        assert!(!state.check(timeout, at(200)));
+
+        assert!(state.activity(at(300)));
+        assert!(!state.is_idle());
+
+        // Disabled
+        assert!(!state.check(Duration::from_secs(0), at(1000)));
+    }
+}
@@ -0,0 +1,98 @@
+//! Marks characters that don't produce any visible output, such as
+//! zero-width spaces and bidi controls, so that text that reads
+//! differently from the way that it is displayed can be spotted.
+use wezterm_term::color::{ColorAttribute, RgbColor};
+use wezterm_term::Line;
+
+const ZERO_WIDTH_JOINER: char = '\u{200d}';
+const ZERO_WIDTH_NON_JOINER: char = '\u{200c}';
+
+/// Returns true if `c` is a zero-width, invisible formatting or
+/// bidirectional control character
+fn is_invisible_char(c: char) -> bool {
+    matches!(
+        c,
+        // Soft hyphen
+        '\u{ad}'
+        // Combining grapheme joiner
+        | '\u{34f}'
+        // Arabic letter mark
+        | '\u{61c}'
+        // Mongolian vowel separator
+        | '\u{180e}'
+        // Zero width space, non-joiner, joiner, LRM and RLM
+        | '\u{200b}'..='\u{200f}'
+        // Bidi embeddings and overrides
+        | '\u{202a}'..='\u{202e}'
+        // Word joiner and invisible math operators
+        | '\u{2060}'..='\u{2064}'
+        // Bidi isolates
+        | '\u{2066}'..='\u{2069}'
+        // Deprecated format characters
+        | '\u{206a}'..='\u{206f}'
+        // Zero width no-break space (BOM)
+        | '\u{feff}'
+    )
+}
+
+/// Returns true if the grapheme `text`, which occupies `width` cells,
+/// contains an invisible character.  Joiners are used to build up
+/// emoji sequences, so they are only considered to be invisible when
+/// they appear in a grapheme that isn't wide enough to be an emoji.
+fn has_invisible_char(text: &str, width: usize) -> bool {
+    text.chars().any(|c| match c {
+        ZERO_WIDTH_JOINER | ZERO_WIDTH_NON_JOINER => width < 2,
+        c => is_invisible_char(c),
+    })
+}
+
+/// Changes the background of any cells in `lines` that contain an
+/// invisible character to `color`
+pub fn highlight_invisible_characters(lines: &mut [Line], color: RgbColor) {
+    for line in lines {
+        for cell in line.cells_mut_for_attr_changes_only() {
+            if has_invisible_char(cell.str(), cell.width()) {
+                cell.attrs_mut()
+                    .set_background(ColorAttribute::TrueColorWithDefaultFallback(color));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wezterm_term::CellAttributes;
+
+    #[test]
+    fn highlight() {
+        let marker = RgbColor::new(0xff, 0, 0);
+        let mut lines = vec![Line::from_text(
+            "a\u{200b}b\u{202e}c\u{1f468}\u{200d}\u{1f4bb}",
+            &CellAttributes::default(),
+        )];
+        highlight_invisible_characters(&mut lines, marker);
+
+        let highlighted: Vec<bool> = lines[0]
+            .cells()
+            .iter()
+            .map(|cell| {
+                cell.attrs().background() == ColorAttribute::TrueColorWithDefaultFallback(marker)
+            })
+            .collect();
+        // The emoji sequence occupies two cells and is left alone
+        assert_eq!(
+            highlighted,
+            vec![false, true, false, true, false, false, false]
+        );
+    }
+
+    #[test]
+    fn joiners() {
+        assert!(has_invisible_char("a\u{200d}", 1));
+        assert!(has_invisible_char("\u{200c}", 1));
+        assert!(!has_invisible_char("\u{1f468}\u{200d}\u{1f4bb}", 2));
+        assert!(has_invisible_char("\u{1f468}\u{200b}", 2));
+        assert!(!has_invisible_char("abc", 1));
+    }
+}
@@ -1,4 +1,11 @@
+use super::mru::MruKind;
 use ::window::{KeyCode, KeyEvent, Modifiers, WindowOps};
+use config::keyassignment::{describe_key, ActivateKeyTable, KeyAssignment, PaneInputMode};
+use config::KeyNoAction;
+use mux::pane::Pane;
+use mux::Mux;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
 
 pub fn window_mods_to_termwiz_mods(modifiers: ::window::Modifiers) -> termwiz::input::Modifiers {
     let mut result = termwiz::input::Modifiers::NONE;
@@ -26,6 +33,22 @@ pub fn window_mods_to_termwiz_mods(modifiers: ::window::Modifiers) -> termwiz::i
     result
 }
 
+/// The key table that was activated by `ActivateKeyTable`
+#[derive(Debug)]
+pub struct KeyTableState {
+    pub name: String,
+    pub expiry: Option<Instant>,
+    /// Descriptions of the keys that have been pressed so far in
+    /// the current sequence; these are shown in the tab bar
+    pub pending: Vec<String>,
+}
+
+impl KeyTableState {
+    pub fn is_expired(&self, now: Instant) -> bool {
+        self.expiry.map(|expiry| now >= expiry).unwrap_or(false)
+    }
+}
+
 #[derive(Debug)]
 pub enum Key {
     Code(::termwiz::input::KeyCode),
@@ -35,6 +58,7 @@ pub enum Key {
 
 impl super::TermWindow {
     pub fn key_event_impl(&mut self, window_key: &KeyEvent, context: &dyn WindowOps) -> bool {
+        self.mru_key_event(window_key);
         if !window_key.key_is_down {
             return false;
         }
@@ -50,6 +74,43 @@ impl super::TermWindow {
             None => return false,
         };
 
+        if self.pane_select.is_some() {
+            self.pane_select_key_event(window_key, context);
+            return true;
+        }
+
+        // While a key table is active, only the keys defined in that
+        // table are recognized.  Pressing one of them performs its
+        // assignment and deactivates the table, unless that assignment
+        // activates another table, which is how multi-key sequences work.
+        if let Some(state) = self.key_table_state.take() {
+            if state.is_expired(Instant::now()) {
+                // Timed out; process this key as though the
+                // table had never been activated
+                self.update_title_impl();
+            } else if window_key.key.is_modifier() {
+                // Wait for the rest of the key press
+                self.key_table_state.replace(state);
+                return true;
+            } else {
+                if let Some(assignment) = self.lookup_key_table(&state.name, window_key) {
+                    self.perform_key_assignment_for_key(
+                        &pane,
+                        &assignment,
+                        window_key,
+                        state.pending,
+                    );
+                }
+                // A key that isn't part of the table is swallowed and
+                // cancels the sequence, similar to the leader key
+                if self.key_table_state.is_none() {
+                    self.update_title_impl();
+                }
+                context.invalidate();
+                return true;
+            }
+        }
+
         // The leader key is a kind of modal modifier key.
         // It is allowed to be active for up to the leader timeout duration,
         // after which it auto-deactivates.
@@ -95,7 +156,7 @@ impl super::TermWindow {
                 .input_map
                 .lookup_key(&raw_code_key, window_key.raw_modifiers | leader_mod)
             {
-                self.perform_key_assignment(&pane, &assignment).ok();
+                self.perform_key_assignment_for_key(&pane, &assignment, window_key, vec![]);
                 context.invalidate();
 
                 if leader_active {
@@ -124,7 +185,7 @@ impl super::TermWindow {
                 .input_map
                 .lookup_key(key, window_key.raw_modifiers | leader_mod)
             {
-                self.perform_key_assignment(&pane, &assignment).ok();
+                self.perform_key_assignment_for_key(&pane, &assignment, window_key, vec![]);
                 context.invalidate();
 
                 if leader_active {
@@ -160,7 +221,7 @@ impl super::TermWindow {
                         && !config.send_composed_key_when_alt_is_pressed);
 
                 if let Key::Code(term_key) = self.win_key_code_to_termwiz_key_code(&key) {
-                    if bypass_compose && pane.key_down(term_key, raw_modifiers).is_ok() {
+                    if bypass_compose && self.send_key_down(&pane, term_key, raw_modifiers) {
                         if !key.is_modifier() && self.pane_state(pane.pane_id()).overlay.is_none() {
                             self.maybe_scroll_to_bottom_for_input(&pane);
                         }
@@ -189,7 +250,7 @@ impl super::TermWindow {
             .input_map
             .lookup_key(&window_key.key, window_key.modifiers | leader_mod)
         {
-            self.perform_key_assignment(&pane, &assignment).ok();
+            self.perform_key_assignment_for_key(&pane, &assignment, window_key, vec![]);
             context.invalidate();
             if leader_active {
                 // A successful leader key-lookup cancels the leader
@@ -209,7 +270,7 @@ impl super::TermWindow {
             let key = self.win_key_code_to_termwiz_key_code(&window_key.key);
             match key {
                 Key::Code(key) => {
-                    if pane.key_down(key, modifiers).is_ok() {
+                    if self.send_key_down(&pane, key, modifiers) {
                         if !key.is_modifier() && self.pane_state(pane.pane_id()).overlay.is_none() {
                             self.maybe_scroll_to_bottom_for_input(&pane);
                         }
@@ -227,7 +288,9 @@ impl super::TermWindow {
                         // the leader modifier.
                         self.leader_is_down.take();
                     } else {
-                        pane.writer().write_all(s.as_bytes()).ok();
+                        for target in self.input_targets(&pane) {
+                            target.writer().write_all(s.as_bytes()).ok();
+                        }
                         self.maybe_scroll_to_bottom_for_input(&pane);
                         context.invalidate();
                     }
@@ -238,6 +301,129 @@ impl super::TermWindow {
         }
     }
 
+    fn lookup_key_table(&self, name: &str, window_key: &KeyEvent) -> Option<KeyAssignment> {
+        if let Some(raw_code) = window_key.raw_code {
+            if let Some(assignment) = self.input_map.lookup_key_in_table(
+                name,
+                &KeyCode::RawCode(raw_code),
+                window_key.raw_modifiers,
+            ) {
+                return Some(assignment);
+            }
+        }
+        if let Some(key) = &window_key.raw_key {
+            if let Some(assignment) =
+                self.input_map
+                    .lookup_key_in_table(name, key, window_key.raw_modifiers)
+            {
+                return Some(assignment);
+            }
+        }
+        self.input_map
+            .lookup_key_in_table(name, &window_key.key, window_key.modifiers)
+    }
+
+    /// Performs an assignment that was triggered by `window_key`.
+    /// If the assignment activated a key table, remember the keys
+    /// that make up the sequence so far so that they can be shown
+    /// in the tab bar while the table is active.
+    fn perform_key_assignment_for_key(
+        &mut self,
+        pane: &Rc<dyn Pane>,
+        assignment: &KeyAssignment,
+        window_key: &KeyEvent,
+        mut pending: Vec<String>,
+    ) {
+        // Cycling continues for as long as the modifiers are held
+        match assignment {
+            KeyAssignment::ActivateTabRelativeMRU(n) => {
+                self.cycle_mru(MruKind::Tab, *n, window_key.modifiers)
+            }
+            KeyAssignment::ActivatePaneRelativeMRU(n) => {
+                self.cycle_mru(MruKind::Pane, *n, window_key.modifiers)
+            }
+            _ => {
+                self.end_mru_cycle();
+                self.perform_key_assignment(pane, assignment).ok();
+            }
+        }
+        if let Some(state) = self.key_table_state.as_mut() {
+            pending.push(describe_key(&window_key.key, window_key.modifiers));
+            state.pending = pending;
+            self.update_title_impl();
+        }
+    }
+
+    pub fn activate_key_table(&mut self, activation: &ActivateKeyTable) {
+        if !self.input_map.has_key_table(&activation.name) {
+            log::error!(
+                "ActivateKeyTable: there is no key table named {}",
+                activation.name
+            );
+            return;
+        }
+        self.key_table_state.replace(KeyTableState {
+            name: activation.name.clone(),
+            expiry: activation
+                .timeout_milliseconds
+                .map(|ms| Instant::now() + Duration::from_millis(ms)),
+            pending: vec![],
+        });
+    }
+
+    /// Returns the panes that should receive keyboard or paste input
+    /// that is directed at `pane`.  Overlays always receive their own
+    /// input, otherwise the input mode of the pane is respected.
+    pub fn input_targets(&self, pane: &Rc<dyn Pane>) -> Vec<Rc<dyn Pane>> {
+        let pane_id = pane.pane_id();
+        let mux = Mux::get().unwrap();
+        if self.pane_state(pane_id).overlay.is_some()
+            || mux.get_pane_input_mode(pane_id) == PaneInputMode::Normal
+        {
+            return vec![Rc::clone(pane)];
+        }
+        mux.resolve_input_targets(pane_id)
+    }
+
+    /// Sends a key press to the panes that should receive input for `pane`.
+    /// Returns true if the key press was consumed; key presses directed
+    /// at a read-only pane are swallowed.
+    fn send_key_down(
+        &self,
+        pane: &Rc<dyn Pane>,
+        key: ::termwiz::input::KeyCode,
+        mods: termwiz::input::Modifiers,
+    ) -> bool {
+        let targets = self.input_targets(pane);
+        if targets.is_empty() {
+            return true;
+        }
+        let mut consumed = false;
+        for target in targets {
+            if target.key_down(key, mods).is_ok() {
+                consumed = true;
+            }
+        }
+        consumed
+    }
+
+    /// Sends the key press described by a `SendKey` assignment to the
+    /// pane, encoding it in the same way as a real key press.
+    pub fn send_synthesized_key(&mut self, pane: &Rc<dyn Pane>, key: &KeyNoAction) {
+        match self.win_key_code_to_termwiz_key_code(&key.key) {
+            Key::Code(code) => {
+                self.send_key_down(pane, code, window_mods_to_termwiz_mods(key.mods));
+            }
+            Key::Composed(s) => {
+                for target in self.input_targets(pane) {
+                    target.writer().write_all(s.as_bytes()).ok();
+                }
+            }
+            Key::None => {}
+        }
+        self.maybe_scroll_to_bottom_for_input(pane);
+    }
+
     fn win_key_code_to_termwiz_key_code(&self, key: &::window::KeyCode) -> Key {
         use ::termwiz::input::KeyCode as KC;
         use ::window::KeyCode as WK;
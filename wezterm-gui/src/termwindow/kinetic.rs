@@ -0,0 +1,151 @@
+//! Converts high resolution touchpad scrolling, which is measured in
+//! pixels, into whole rows, and continues the scroll with momentum
+//! after the fingers are lifted.
+use std::time::{Duration, Instant};
+
+/// How quickly the momentum decays; the velocity falls by a factor
+/// of e over this period
+const MOMENTUM_TIME_CONSTANT: f32 = 0.325;
+/// Below this speed, in pixels per second, coasting stops
+const MIN_VELOCITY: f32 = 30.;
+/// If the fingers rest for longer than this before being lifted,
+/// the scroll ends without any momentum
+const MAX_RELEASE_DELAY: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Default)]
+pub struct PixelScroll {
+    /// Pixels that have been scrolled but don't yet add up to a row
+    remainder: f32,
+    /// The estimated speed of the scroll, in pixels per second
+    velocity: f32,
+    last_event: Option<Instant>,
+    /// When the momentum was last applied, while coasting
+    coasting: Option<Instant>,
+}
+
+impl PixelScroll {
+    /// Takes the whole rows out of the accumulated remainder
+    fn take_rows(&mut self, cell_height: f32) -> i16 {
+        let rows = (self.remainder / cell_height).trunc();
+        self.remainder -= rows * cell_height;
+        rows as i16
+    }
+
+    /// Accumulates a scroll of `pixels` and returns the number of whole
+    /// rows that should now be scrolled.  Positive values scroll up.
+    pub fn scroll(&mut self, pixels: f32, cell_height: f32, now: Instant) -> i16 {
+        self.coasting.take();
+
+        let elapsed = self
+            .last_event
+            .map(|last| now.saturating_duration_since(last))
+            .unwrap_or(Duration::from_secs(1));
+        if elapsed > MAX_RELEASE_DELAY {
+            // This is the start of a new gesture
+            self.remainder = 0.;
+            self.velocity = 0.;
+        } else {
+            let instantaneous = pixels / elapsed.as_secs_f32().max(0.001);
+            self.velocity = (self.velocity + instantaneous) / 2.;
+        }
+        self.last_event = Some(now);
+
+        self.remainder += pixels;
+        self.take_rows(cell_height)
+    }
+
+    /// Called when the fingers are lifted from the touchpad.
+    /// Returns true if the scroll should continue with momentum.
+    pub fn release(&mut self, now: Instant) -> bool {
+        let recent = self
+            .last_event
+            .map(|last| now.saturating_duration_since(last) <= MAX_RELEASE_DELAY)
+            .unwrap_or(false);
+        if recent && self.velocity.abs() >= MIN_VELOCITY {
+            self.coasting = Some(now);
+        } else {
+            self.stop();
+        }
+        self.is_coasting()
+    }
+
+    /// Cancels any momentum, such as when the mouse is clicked
+    pub fn stop(&mut self) {
+        self.coasting.take();
+        self.velocity = 0.;
+    }
+
+    pub fn is_coasting(&self) -> bool {
+        self.coasting.is_some()
+    }
+
+    /// Applies the momentum up until `now`, returning the number of
+    /// whole rows that should now be scrolled
+    pub fn coast(&mut self, cell_height: f32, now: Instant) -> i16 {
+        let last = match self.coasting {
+            Some(last) => last,
+            None => return 0,
+        };
+        let elapsed = now.saturating_duration_since(last).as_secs_f32();
+        let decay = (-elapsed / MOMENTUM_TIME_CONSTANT).exp();
+        self.remainder += self.velocity * MOMENTUM_TIME_CONSTANT * (1. - decay);
+        self.velocity *= decay;
+        self.coasting = Some(now);
+
+        let rows = self.take_rows(cell_height);
+        if self.velocity.abs() < MIN_VELOCITY {
+            self.stop();
+        }
+        rows
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn millis(start: Instant, ms: u64) -> Instant {
+        start + Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn accumulates_partial_rows() {
+        let start = Instant::now();
+        let mut scroll = PixelScroll::default();
+        assert_eq!(scroll.scroll(6., 10., start), 0);
+        assert_eq!(scroll.scroll(6., 10., millis(start, 10)), 1);
+        assert_eq!(scroll.scroll(-4., 10., millis(start, 20)), 0);
+        assert_eq!(scroll.scroll(-14., 10., millis(start, 30)), -1);
+
+        // A pause starts a new gesture, discarding the remainder
+        assert_eq!(scroll.scroll(8., 10., millis(start, 500)), 0);
+        assert_eq!(scroll.scroll(2., 10., millis(start, 1000)), 0);
+    }
+
+    #[test]
+    fn momentum() {
+        let start = Instant::now();
+        let mut scroll = PixelScroll::default();
+        for i in 0..5 {
+            scroll.scroll(20., 10., millis(start, i * 10));
+        }
+        assert!(scroll.release(millis(start, 50)));
+
+        let mut total = 0;
+        let mut ms = 50;
+        while scroll.is_coasting() {
+            ms += 16;
+            let rows = scroll.coast(10., millis(start, ms));
+            assert!(rows >= 0);
+            total += rows;
+            assert!(ms < 5000, "momentum never stopped");
+        }
+        assert!(total > 0);
+
+        // Resting before lifting the fingers doesn't coast
+        scroll.scroll(20., 10., millis(start, 6000));
+        scroll.scroll(20., 10., millis(start, 6010));
+        assert!(!scroll.release(millis(start, 6500)));
+        assert_eq!(scroll.coast(10., millis(start, 6516)), 0);
+    }
+}
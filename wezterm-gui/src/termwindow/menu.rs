@@ -0,0 +1,284 @@
+//! The menus of the terminal window: the menu bar, on systems that
+//! have one, and the context menu that is shown by the right mouse
+//! button.  Both offer the same handful of common actions, each
+//! labelled with the key binding that performs it.
+use crate::overlay::{context_menu, start_overlay_pane, OverlayState};
+use crate::termwindow::TermWindow;
+use config::keyassignment::{
+    ClipboardCopyDestination, ClipboardPasteSource, InputMap, KeyAssignment, ScrollbackEraseMode,
+    SpawnCommand, SpawnTabDomain,
+};
+use mux::pane::Pane;
+use mux::Mux;
+use std::rc::Rc;
+use window::{Connection, ConnectionOps, Menu, MenuEntry, Point, WindowOps};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MenuItem {
+    Copy,
+    Paste,
+    SplitHorizontal,
+    SplitVertical,
+    NewTab,
+    NewWindow,
+    ClearScrollback,
+    OpenConfigFile,
+}
+
+/// The id of an item is its position in this list
+const ALL_ITEMS: [MenuItem; 8] = [
+    MenuItem::Copy,
+    MenuItem::Paste,
+    MenuItem::SplitHorizontal,
+    MenuItem::SplitVertical,
+    MenuItem::NewTab,
+    MenuItem::NewWindow,
+    MenuItem::ClearScrollback,
+    MenuItem::OpenConfigFile,
+];
+
+/// The layout of the context menu; None is a separator
+const CONTEXT_MENU: &[Option<MenuItem>] = &[
+    Some(MenuItem::Copy),
+    Some(MenuItem::Paste),
+    None,
+    Some(MenuItem::SplitHorizontal),
+    Some(MenuItem::SplitVertical),
+    None,
+    Some(MenuItem::NewTab),
+    Some(MenuItem::NewWindow),
+    None,
+    Some(MenuItem::ClearScrollback),
+    None,
+    Some(MenuItem::OpenConfigFile),
+];
+
+const SHELL_MENU: &[Option<MenuItem>] = &[
+    Some(MenuItem::NewTab),
+    Some(MenuItem::NewWindow),
+    None,
+    Some(MenuItem::SplitHorizontal),
+    Some(MenuItem::SplitVertical),
+];
+
+const EDIT_MENU: &[Option<MenuItem>] = &[
+    Some(MenuItem::Copy),
+    Some(MenuItem::Paste),
+    None,
+    Some(MenuItem::ClearScrollback),
+    None,
+    Some(MenuItem::OpenConfigFile),
+];
+
+fn split_command() -> SpawnCommand {
+    SpawnCommand {
+        domain: SpawnTabDomain::CurrentPaneDomain,
+        ..Default::default()
+    }
+}
+
+impl MenuItem {
+    fn id(self) -> usize {
+        ALL_ITEMS.iter().position(|&item| item == self).unwrap()
+    }
+
+    fn from_id(id: usize) -> Option<Self> {
+        ALL_ITEMS.get(id).copied()
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Copy => "Copy",
+            Self::Paste => "Paste",
+            Self::SplitHorizontal => "Split Horizontally",
+            Self::SplitVertical => "Split Vertically",
+            Self::NewTab => "New Tab",
+            Self::NewWindow => "New Window",
+            Self::ClearScrollback => "Clear Scrollback",
+            Self::OpenConfigFile => "Open Configuration",
+        }
+    }
+
+    /// The assignment that is performed when the item is selected,
+    /// followed by those that are close enough to it that a key that
+    /// is bound to them is shown as the key for the item
+    fn actions(self) -> Vec<KeyAssignment> {
+        use KeyAssignment::*;
+        match self {
+            Self::Copy => vec![
+                CopyTo(ClipboardCopyDestination::Clipboard),
+                CopyTo(ClipboardCopyDestination::ClipboardAndPrimarySelection),
+                Copy,
+            ],
+            Self::Paste => vec![PasteFrom(ClipboardPasteSource::Clipboard), Paste],
+            Self::SplitHorizontal => vec![SplitHorizontal(split_command())],
+            Self::SplitVertical => vec![SplitVertical(split_command())],
+            Self::NewTab => vec![SpawnTab(SpawnTabDomain::CurrentPaneDomain)],
+            Self::NewWindow => vec![SpawnWindow],
+            Self::ClearScrollback => vec![ClearScrollback(ScrollbackEraseMode::ScrollbackOnly)],
+            Self::OpenConfigFile => vec![OpenConfigFile],
+        }
+    }
+}
+
+fn menu_entries(input_map: &InputMap, layout: &[Option<MenuItem>]) -> Vec<MenuEntry> {
+    layout
+        .iter()
+        .map(|item| match item {
+            Some(item) => MenuEntry::Item {
+                id: item.id(),
+                label: item.label().to_string(),
+                key_hint: input_map.describe_key_for(&item.actions()),
+            },
+            None => MenuEntry::Separator,
+        })
+        .collect()
+}
+
+/// Replaces the menu bar with one that reflects the key bindings
+/// of `input_map`
+pub fn update_menu_bar(input_map: &InputMap) {
+    if let Some(conn) = Connection::get() {
+        conn.set_menu_bar(vec![
+            Menu {
+                title: "Shell".to_string(),
+                entries: menu_entries(input_map, SHELL_MENU),
+            },
+            Menu {
+                title: "Edit".to_string(),
+                entries: menu_entries(input_map, EDIT_MENU),
+            },
+        ]);
+    }
+}
+
+/// Opens the configuration file in the editor that is associated
+/// with it.  If there is no configuration file at all, an empty one
+/// is created in the preferred location first.
+pub fn open_config_file() {
+    let (path, exists) = config::config_file_path();
+    // `open` can block, and on Windows must not be called from within
+    // the window procedure, so it runs on a thread of its own
+    std::thread::spawn(move || {
+        if !exists {
+            if let Some(dir) = path.parent() {
+                if let Err(err) = config::create_user_owned_dirs(dir) {
+                    log::error!("failed to create {}: {:#}", dir.display(), err);
+                    return;
+                }
+            }
+            // Don't clobber a file that appeared in the meantime
+            let created = std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+                .and_then(|mut file| {
+                    use std::io::Write;
+                    file.write_all(b"return {\n}\n")
+                });
+            if let Err(err) = created {
+                if err.kind() != std::io::ErrorKind::AlreadyExists {
+                    log::error!("failed to create {}: {:#}", path.display(), err);
+                    return;
+                }
+            }
+        }
+        if let Err(err) = open::that(&path) {
+            log::error!("failed to open {}: {:?}", path.display(), err);
+        }
+    });
+}
+
+impl TermWindow {
+    /// Shows the context menu at the position of the mouse.
+    /// Where the system has no native context menus, the menu is
+    /// shown as an overlay instead.
+    pub fn show_context_menu(&mut self, pane: &Rc<dyn Pane>) {
+        let entries = menu_entries(&self.input_map, CONTEXT_MENU);
+        let coords = self
+            .current_mouse_event
+            .as_ref()
+            .map(|event| event.coords)
+            .unwrap_or_else(|| Point::new(0, 0));
+        let window = self.window.clone().unwrap();
+        let pane_id = pane.pane_id();
+        promise::spawn::spawn(async move {
+            if let Err(err) = window.show_context_menu(entries.clone(), coords).await {
+                log::trace!("{:#}; showing the context menu as an overlay", err);
+                window
+                    .apply(move |tw, _ops| {
+                        let pane = Mux::get().unwrap().get_pane(pane_id);
+                        if let (Some(term_window), Some(pane)) =
+                            (tw.downcast_mut::<TermWindow>(), pane)
+                        {
+                            term_window.show_context_menu_overlay(&pane, entries.clone());
+                        }
+                        Ok(())
+                    })
+                    .await
+                    .ok();
+            }
+        })
+        .detach();
+    }
+
+    fn show_context_menu_overlay(&mut self, pane: &Rc<dyn Pane>, entries: Vec<MenuEntry>) {
+        let pane_id = pane.pane_id();
+        let (overlay, future) =
+            start_overlay_pane(self, pane, move |_, term| context_menu(term, entries));
+        self.assign_overlay_for_pane(pane_id, OverlayState::new(overlay));
+        let window = self.window.clone().unwrap();
+        promise::spawn::spawn(async move {
+            if let Ok(Some(id)) = future.await {
+                window
+                    .apply(move |tw, _ops| {
+                        if let Some(term_window) = tw.downcast_mut::<TermWindow>() {
+                            term_window.perform_menu_item(id);
+                        }
+                        Ok(())
+                    })
+                    .await
+                    .ok();
+            }
+        })
+        .detach();
+    }
+
+    /// Performs the action of the menu item `id` in the active pane
+    pub fn perform_menu_item(&mut self, id: usize) {
+        let item = match MenuItem::from_id(id) {
+            Some(item) => item,
+            None => {
+                log::error!("unknown menu item {}", id);
+                return;
+            }
+        };
+        let action = item.actions().remove(0);
+        if let Err(err) = self.perform_action_in_active_pane(&action) {
+            log::error!("{}: {:#}", item.label(), err);
+        }
+    }
+
+    /// Performs `action` as though its key was pressed in the active
+    /// pane, for actions that come from outside of the pane, such as
+    /// from a menu
+    pub fn perform_action_in_active_pane(&mut self, action: &KeyAssignment) -> anyhow::Result<()> {
+        match self.get_active_pane_or_overlay() {
+            Some(pane) => self.perform_key_assignment(&pane, action),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn item_ids() {
+        for item in &ALL_ITEMS {
+            assert_eq!(MenuItem::from_id(item.id()), Some(*item));
+        }
+        assert_eq!(MenuItem::from_id(ALL_ITEMS.len()), None);
+    }
+}
@@ -2,24 +2,28 @@
 use super::quad::*;
 use super::renderstate::*;
 use super::utilsprites::RenderMetrics;
+use crate::frontend::front_end;
 use crate::glium::texture::SrgbTexture2d;
 use crate::overlay::{
-    confirm_close_pane, confirm_close_tab, confirm_close_window, confirm_quit_program, launcher,
-    start_overlay, start_overlay_pane, tab_navigator, CopyOverlay, SearchOverlay,
+    char_select, command_history, confirm_close_pane, confirm_close_tab, confirm_close_window,
+    confirm_quit_program, confirm_restart_pane, debug_overlay, launcher, list_panes,
+    pane_navigator, paste_from_history, start_overlay, start_overlay_pane, tab_navigator,
+    CommandHistoryEntry, CopyOverlay, OverlayState, SearchOverlay,
 };
 use crate::scripting::guiwin::GuiWin;
 use crate::scripting::pane::PaneObject;
 use crate::scrollbar::*;
-use crate::selection::Selection;
+use crate::selection::{Selection, SelectionCoordinate, SelectionRange};
 use crate::shapecache::*;
-use crate::tabbar::TabBarState;
+use crate::tabbar::{TabActivity, TabBarState};
 use ::wezterm_term::input::MouseButton as TMB;
 use ::window::*;
 use anyhow::{anyhow, ensure};
 use config::keyassignment::{
-    ClipboardCopyDestination, ClipboardPasteSource, InputMap, KeyAssignment, SpawnCommand,
+    ClipboardCopyDestination, ClipboardPasteSource, InputMap, KeyAssignment, PaneInputMode,
+    SpawnCommand,
 };
-use config::{configuration, ConfigHandle, WindowCloseConfirmation};
+use config::{configuration, ConfigHandle, VisualBellTarget, WindowCloseConfirmation};
 use lru::LruCache;
 use mux::activity::Activity;
 use mux::domain::{DomainId, DomainState};
@@ -30,7 +34,7 @@ use mux::window::WindowId as MuxWindowId;
 use mux::{Mux, MuxNotification};
 use portable_pty::PtySize;
 use std::any::Any;
-use std::cell::{RefCell, RefMut};
+use std::cell::{Cell, RefCell, RefMut};
 use std::collections::HashMap;
 use std::ops::Add;
 use std::rc::Rc;
@@ -44,28 +48,62 @@ use wezterm_term::color::ColorPalette;
 use wezterm_term::input::LastMouseClick;
 use wezterm_term::{StableRowIndex, TerminalConfiguration};
 
+mod bidi;
+mod capture;
 pub mod clipboard;
+mod cursoranim;
+mod dropdown;
+pub mod effects;
+mod export;
+mod idle;
+mod invisible;
 mod keyevent;
+mod kinetic;
+mod menu;
 mod mouseevent;
+mod mru;
+mod openwith;
+mod paneselect;
 mod prevcursor;
 mod render;
 pub mod resize;
+mod scrollanim;
 mod selection;
 pub mod spawn;
+use capture::PendingCapture;
 use clipboard::ClipboardHelper;
+use cursoranim::CursorAnimation;
+use effects::{EffectTarget, Effects, Flash};
+use idle::IdleState;
+use keyevent::KeyTableState;
+use kinetic::PixelScroll;
+use mru::{MruCycle, MruKind};
+use paneselect::PaneSelectState;
 use prevcursor::PrevCursorPos;
+use scrollanim::ScrollAnimation;
 use spawn::SpawnWhere;
 
 const ATLAS_SIZE: usize = 128;
 
 lazy_static::lazy_static! {
-    static ref WINDOW_CLASS: Mutex<String> = Mutex::new("org.wezfurlong.wezterm".to_owned());
+    static ref WINDOW_CLASS: Mutex<Option<String>> = Mutex::new(None);
 }
 
 pub const ICON_DATA: &'static [u8] = include_bytes!("../../../assets/icon/terminal.png");
 
 pub fn set_window_class(cls: &str) {
-    *WINDOW_CLASS.lock().unwrap() = cls.to_owned();
+    WINDOW_CLASS.lock().unwrap().replace(cls.to_owned());
+}
+
+/// Returns the class for new windows: that given by `--class`,
+/// falling back to `window_class` from the configuration
+fn window_class(config: &ConfigHandle) -> String {
+    WINDOW_CLASS
+        .lock()
+        .unwrap()
+        .clone()
+        .or_else(|| config.window_class.clone())
+        .unwrap_or_else(|| "org.wezfurlong.wezterm".to_owned())
 }
 
 #[derive(Default, Clone)]
@@ -78,7 +116,22 @@ pub struct PaneState {
     /// If is_some(), rather than display the actual tab
     /// contents, we're overlaying a little internal application
     /// tab.  We'll also route input to it.
-    pub overlay: Option<Rc<dyn Pane>>,
+    pub overlay: Option<OverlayState>,
+    /// The physical top of the pane when it was last painted;
+    /// used to notice new output arriving below the viewport
+    last_physical_top: Option<StableRowIndex>,
+    /// When new output last arrived below the scrolled viewport
+    scroll_indicator_flash: Option<Instant>,
+    /// The top of the scrollback when we last checked for evicted rows
+    scrollback_top: Option<StableRowIndex>,
+    /// Overrides the show_invisible_characters configuration for
+    /// this pane once it has been toggled
+    show_invisible_characters: Option<bool>,
+    /// Set while the displayed viewport is gliding towards `viewport`
+    scroll_animation: Option<ScrollAnimation>,
+    /// Whether programs in this pane may read the clipboard, once
+    /// that has been answered at the prompt
+    clipboard_read: Option<bool>,
 }
 
 #[derive(Default, Clone)]
@@ -122,6 +175,16 @@ pub struct TermWindow {
     input_map: InputMap,
     /// If is_some, the LEADER modifier is active until the specified instant.
     leader_is_down: Option<std::time::Instant>,
+    /// If is_some, only the keys from the named key table are recognized
+    key_table_state: Option<KeyTableState>,
+    /// If is_some, the panes are labelled and the next key press
+    /// selects one of them
+    pane_select: Option<PaneSelectState>,
+    /// If is_some, the tabs or panes are being cycled in most recently
+    /// used order until the modifiers are released
+    mru_cycle: Option<MruCycle>,
+    /// The resize increments most recently advised to the window
+    resize_increments: Option<ResizeIncrement>,
     show_tab_bar: bool,
     show_scroll_bar: bool,
     tab_bar: TabBarState,
@@ -132,13 +195,33 @@ pub struct TermWindow {
     split_drag_start: Option<PositionedSplit>,
     window_drag_position: Option<MouseEvent>,
     current_mouse_event: Option<MouseEvent>,
+    pixel_scroll: PixelScroll,
+    /// Pinch magnification that hasn't yet triggered a gesture binding
+    pinch_magnification: f64,
     prev_cursor: PrevCursorPos,
+    cursor_animation: RefCell<CursorAnimation>,
+    effects: Effects,
+    pending_capture: Option<PendingCapture>,
+    idle: IdleState,
+    /// When the window was last painted, for limiting the frame rate
+    last_paint: Instant,
+    /// A repaint has been scheduled for when max_fps allows
+    paint_deferred: bool,
+    /// When multiplexer panes were last polled for changes
+    last_dirty_poll: Instant,
+    /// Output and bells in inactive tabs, which are shared with the
+    /// mux subscription so that they can be recorded without waking
+    /// the window for every output event
+    tab_activity: Rc<RefCell<HashMap<TabId, TabActivity>>>,
     last_scroll_info: RenderableDimensions,
 
     tab_state: RefCell<HashMap<TabId, TabState>>,
     pane_state: RefCell<HashMap<PaneId, PaneState>>,
 
     window_background: Option<Arc<ImageData>>,
+    /// How many of the spare rows of quads have been taken by panes
+    /// during the frame that is being painted
+    spare_rows_used: Cell<usize>,
 
     /// Gross workaround for managing async keyboard fetching
     /// just for middle mouse button paste function
@@ -159,9 +242,14 @@ pub struct TermWindow {
     last_status_call: Instant,
 
     palette: Option<ColorPalette>,
+    /// The palette from the global configuration, without the
+    /// overrides of this window; used by `pane_palette`
+    global_palette: Option<ColorPalette>,
 
     event_states: HashMap<String, EventState>,
     has_animation: RefCell<Option<Instant>>,
+    /// Whether this window was hidden by `toggle_drop_down`
+    drop_down_hidden: bool,
 }
 
 impl WindowCallbacks for TermWindow {
@@ -206,13 +294,24 @@ impl WindowCallbacks for TermWindow {
         self
     }
 
+    fn destroy(&mut self) {
+        if let Some(fe) = front_end() {
+            fe.forget_known_window(self.mux_window_id);
+        }
+    }
+
     fn focus_change(&mut self, focused: bool) {
         log::trace!("Setting focus to {:?}", focused);
         self.focused = if focused { Some(Instant::now()) } else { None };
+        if focused {
+            self.note_activity();
+        }
 
         if self.focused.is_none() {
             self.last_mouse_click = None;
             self.current_mouse_button = None;
+            // We won't see the modifiers being released
+            self.end_mru_cycle();
         }
 
         // Reset the cursor blink phase
@@ -226,10 +325,30 @@ impl WindowCallbacks for TermWindow {
         }
     }
 
+    fn dropped_files(&mut self, paths: Vec<std::path::PathBuf>) {
+        self.paste_dropped_files(paths);
+    }
+
+    fn appearance_changed(&mut self, appearance: config::Appearance) {
+        log::trace!("Appearance changed to {:?}", appearance);
+        config::set_appearance(appearance);
+    }
+
     fn mouse_event(&mut self, event: &MouseEvent, context: &dyn WindowOps) {
+        self.note_activity();
         self.mouse_event_impl(event, context)
     }
 
+    fn gesture_event(&mut self, event: &GestureEvent, context: &dyn WindowOps) {
+        self.note_activity();
+        self.gesture_event_impl(event, context)
+    }
+
+    fn menu_item_selected(&mut self, id: usize, _context: &dyn WindowOps) {
+        self.note_activity();
+        self.perform_menu_item(id);
+    }
+
     fn resize(&mut self, dimensions: Dimensions, is_full_screen: bool) {
         log::trace!(
             "resize event, current cells: {:?}, new dims: {:?} is_full_screen:{}",
@@ -252,6 +371,7 @@ impl WindowCallbacks for TermWindow {
     }
 
     fn key_event(&mut self, window_key: &KeyEvent, context: &dyn WindowOps) -> bool {
+        self.note_activity();
         self.key_event_impl(window_key, context)
     }
 
@@ -271,7 +391,9 @@ impl WindowCallbacks for TermWindow {
             config: self.config.clone(),
             config_overrides: self.config_overrides.clone(),
             window_background: self.window_background.clone(),
+            spare_rows_used: Cell::new(0),
             palette: None,
+            global_palette: None,
             focused: None,
             mux_window_id,
             fonts: Rc::clone(&self.fonts),
@@ -282,6 +404,10 @@ impl WindowCallbacks for TermWindow {
             render_state,
             input_map: InputMap::new(),
             leader_is_down: None,
+            key_table_state: None,
+            pane_select: None,
+            mru_cycle: None,
+            resize_increments: None,
             show_tab_bar: self.show_tab_bar,
             show_scroll_bar: self.show_scroll_bar,
             tab_bar: self.tab_bar.clone(),
@@ -293,6 +419,16 @@ impl WindowCallbacks for TermWindow {
             window_drag_position: None,
             current_mouse_event: None,
             prev_cursor: self.prev_cursor.clone(),
+            cursor_animation: RefCell::new(CursorAnimation::new()),
+            effects: Effects::default(),
+            pending_capture: None,
+            idle: IdleState::new(Instant::now()),
+            last_paint: Instant::now(),
+            paint_deferred: false,
+            last_dirty_poll: Instant::now(),
+            tab_activity: Rc::new(RefCell::new(HashMap::new())),
+            pixel_scroll: PixelScroll::default(),
+            pinch_magnification: 0.,
             last_scroll_info: self.last_scroll_info.clone(),
             clipboard_contents: Arc::clone(&clipboard_contents),
             tab_state: RefCell::new(self.tab_state.borrow().clone()),
@@ -305,14 +441,15 @@ impl WindowCallbacks for TermWindow {
             last_status_call: Instant::now(),
             event_states: HashMap::new(),
             has_animation: RefCell::new(None),
+            drop_down_hidden: false,
         });
         prior_window.close();
 
         promise::spawn::spawn(async move {
             smol::Timer::after(Duration::from_millis(300)).await;
-            log::error!("now try making that new window");
+            log::trace!("now try making that new window");
             let window = Window::new_window(
-                &*WINDOW_CLASS.lock().unwrap(),
+                &window_class(&config),
                 "wezterm",
                 dimensions.pixel_width,
                 dimensions.pixel_height,
@@ -338,6 +475,9 @@ impl WindowCallbacks for TermWindow {
         ctx: std::rc::Rc<glium::backend::Context>,
     ) -> anyhow::Result<()> {
         self.window.replace(window.clone());
+        if let Some(fe) = front_end() {
+            fe.record_known_window(window.clone(), self.mux_window_id);
+        }
 
         self.render_state = None;
 
@@ -351,6 +491,11 @@ impl WindowCallbacks for TermWindow {
             self.dimensions.pixel_height,
         ) {
             Ok(gl) => {
+                crate::crashreport::set_renderer_info(format!(
+                    "{} {}",
+                    gl.context.get_opengl_renderer_string(),
+                    gl.context.get_opengl_version_string()
+                ));
                 log::info!(
                     "OpenGL initialized! {} {} is_context_loss_possible={} wezterm version: {}",
                     gl.context.get_opengl_renderer_string(),
@@ -443,7 +588,7 @@ impl TermWindow {
         let size = match mux.get_active_tab_for_window(mux_window_id) {
             Some(tab) => tab.get_size(),
             None => {
-                log::error!("new_window has no tabs... yet?");
+                log::debug!("new_window has no tabs... yet?");
                 Default::default()
             }
         };
@@ -464,7 +609,9 @@ impl TermWindow {
         // for the tab bar state.
         let show_tab_bar = config.enable_tab_bar && !config.hide_tab_bar_if_only_one_tab;
 
-        let rows_with_tab_bar = if show_tab_bar { 1 } else { 0 } + terminal_size.rows;
+        let rows_with_tab_bar = if show_tab_bar { 1 } else { 0 }
+            + if config.pane_title_bar { 1 } else { 0 }
+            + terminal_size.rows;
 
         let dimensions = Dimensions {
             pixel_width: ((terminal_size.cols * render_metrics.cell_size.width as u16)
@@ -489,16 +636,18 @@ impl TermWindow {
         let clipboard_contents = Arc::new(Mutex::new(None));
 
         let window = Window::new_window(
-            &*WINDOW_CLASS.lock().unwrap(),
+            &window_class(&config),
             "wezterm",
             dimensions.pixel_width,
             dimensions.pixel_height,
             Box::new(Self {
                 window: None,
                 window_background,
+                spare_rows_used: Cell::new(0),
                 config: config.clone(),
                 config_overrides: serde_json::Value::default(),
                 palette: None,
+                global_palette: None,
                 focused: None,
                 mux_window_id,
                 fonts: fontconfig,
@@ -509,6 +658,10 @@ impl TermWindow {
                 render_state,
                 input_map: InputMap::new(),
                 leader_is_down: None,
+                key_table_state: None,
+                pane_select: None,
+                mru_cycle: None,
+                resize_increments: None,
                 show_tab_bar,
                 show_scroll_bar: config.enable_scroll_bar,
                 tab_bar: TabBarState::default(),
@@ -520,6 +673,16 @@ impl TermWindow {
                 window_drag_position: None,
                 current_mouse_event: None,
                 prev_cursor: PrevCursorPos::new(),
+                cursor_animation: RefCell::new(CursorAnimation::new()),
+                effects: Effects::default(),
+                pending_capture: None,
+                idle: IdleState::new(Instant::now()),
+                last_paint: Instant::now(),
+                paint_deferred: false,
+                last_dirty_poll: Instant::now(),
+                tab_activity: Rc::new(RefCell::new(HashMap::new())),
+                pixel_scroll: PixelScroll::default(),
+                pinch_magnification: 0.,
                 last_scroll_info: RenderableDimensions::default(),
                 clipboard_contents: Arc::clone(&clipboard_contents),
                 tab_state: RefCell::new(HashMap::new()),
@@ -532,6 +695,7 @@ impl TermWindow {
                 last_status_call: Instant::now(),
                 event_states: HashMap::new(),
                 has_animation: RefCell::new(None),
+                drop_down_hidden: false,
             }),
             Some(&config),
         )?;
@@ -539,7 +703,14 @@ impl TermWindow {
         Self::apply_icon(&window)?;
         Self::setup_clipboard(&window, mux_window_id, clipboard_contents);
 
+        if let Some(position) = front_end().and_then(|fe| fe.take_startup_position(mux_window_id)) {
+            window.set_window_position(position);
+        }
+
         crate::update::start_update_checker();
+        crate::power::start_power_monitor();
+        crate::statuscmd::start_status_commands();
+        menu::update_menu_bar(&InputMap::new());
         Ok(())
     }
 
@@ -606,25 +777,173 @@ impl TermWindow {
     }
 
     fn mux_pane_output_event(&mut self, pane_id: PaneId) {
-        if let Some(pane) = self.get_active_pane_or_overlay() {
-            if pane.pane_id() == pane_id {
-                let mux = Mux::get().expect("mux started and running on main thread");
+        let pos = match self
+            .get_panes_to_render()
+            .into_iter()
+            .find(|pos| pos.pane.pane_id() == pane_id)
+        {
+            Some(pos) => pos,
+            None => return,
+        };
+        self.note_activity();
 
-                if let Some(mut mux_window) = mux.get_window_mut(self.mux_window_id) {
-                    mux_window.check_and_reset_invalidated();
-                }
-                if let Some(ref win) = self.window {
-                    win.invalidate();
-                }
+        let mux = Mux::get().expect("mux started and running on main thread");
+        if let Some(mut mux_window) = mux.get_window_mut(self.mux_window_id) {
+            mux_window.check_and_reset_invalidated();
+        }
+
+        // Any selection that intersects with the changed lines needs
+        // to be cleared, which is why we check for dirty lines here
+        // rather than simply invalidating.  Panes that notify us of
+        // their changes are not polled by periodic_window_maintenance.
+        self.check_for_evicted_rows(&pos);
+        self.check_for_dirty_lines_and_invalidate_selection(&pos);
+        self.invalidate_for_output();
+    }
+
+    /// Repaints the window for new output, but no more often than
+    /// `max_fps` allows
+    fn invalidate_for_output(&mut self) {
+        let window = match self.window.clone() {
+            Some(window) => window,
+            None => return,
+        };
+        let due = self.last_paint + self.min_frame_interval();
+        if Instant::now() >= due {
+            window.invalidate();
+        } else if !self.paint_deferred {
+            self.paint_deferred = true;
+            promise::spawn::spawn(async move {
+                smol::Timer::at(due).await;
+                window.invalidate();
+            })
+            .detach();
+        }
+    }
+
+    /// Called when a long running command finishes in `pane_id`.
+    /// Shows a notification unless the user is looking at that pane.
+    pub fn command_complete(
+        &mut self,
+        pane_id: PaneId,
+        command: Option<String>,
+        status: i32,
+        duration: Duration,
+    ) {
+        let is_focused_pane = self.focused.is_some()
+            && self
+                .get_active_pane_no_overlay()
+                .map(|pane| pane.pane_id() == pane_id)
+                .unwrap_or(false);
+        if is_focused_pane {
+            return;
+        }
+
+        let title = if status == 0 {
+            "Command finished".to_string()
+        } else {
+            format!("Command failed with exit status {}", status)
+        };
+        let secs = duration.as_secs();
+        let elapsed = if secs >= 60 {
+            format!("{}m {}s", secs / 60, secs % 60)
+        } else {
+            format!("{}s", secs)
+        };
+        let body = match command {
+            Some(command) => format!("{} (took {})", command, elapsed),
+            None => format!("took {}", elapsed),
+        };
+        wezterm_toast_notification::persistent_toast_notification(&title, &body);
+        self.request_attention();
+    }
+
+    /// Asks the windowing system to draw attention to this window,
+    /// if it isn't focused and the configuration allows it
+    pub fn request_attention(&self) {
+        if self.focused.is_none() && self.config.request_attention_on_alert {
+            if let Some(window) = self.window.as_ref() {
+                window.request_attention();
             }
         }
     }
 
+    /// Starts `flash`; it is animated by the paint path until it has
+    /// faded out
+    pub fn flash(&mut self, flash: Flash) {
+        if flash.duration == Duration::from_millis(0) {
+            return;
+        }
+        self.effects.flash(flash, Instant::now());
+        if let Some(window) = self.window.as_ref() {
+            window.invalidate();
+        }
+    }
+
+    /// Returns a flash of `target` in the `visual_bell` color of the
+    /// palette used by `pane_id`, lasting for `duration` milliseconds
+    fn visual_bell_flash(&mut self, pane_id: PaneId, target: EffectTarget, duration: u64) -> Flash {
+        let color = match Mux::get().and_then(|mux| mux.get_pane(pane_id)) {
+            Some(pane) => self.pane_palette(&*pane).visual_bell,
+            None => self.palette().visual_bell,
+        };
+        Flash {
+            target,
+            color,
+            opacity: self.config.visual_bell.opacity,
+            duration: Duration::from_millis(duration),
+            easing: self.config.visual_bell.easing,
+        }
+    }
+
+    /// Called when the bell rings in `pane_id`
+    pub fn bell(&mut self, pane_id: PaneId) {
+        let mux = Mux::get().expect("mux started and running on main thread");
+        if let Some(mux_window) = mux.get_window(self.mux_window_id) {
+            let inactive_tab = mux_window
+                .iter()
+                .enumerate()
+                .find(|(idx, tab)| {
+                    *idx != mux_window.get_active_idx() && tab.contains_pane(pane_id)
+                })
+                .map(|(_, tab)| tab.tab_id());
+            drop(mux_window);
+            if let Some(tab_id) = inactive_tab {
+                self.tab_activity
+                    .borrow_mut()
+                    .entry(tab_id)
+                    .or_default()
+                    .bell = true;
+                self.update_title();
+            }
+        }
+
+        let visual_bell = self.config.visual_bell;
+        let target = match visual_bell.target {
+            VisualBellTarget::Pane => EffectTarget::Pane(pane_id),
+            VisualBellTarget::Window => EffectTarget::Window,
+        };
+        let flash = self.visual_bell_flash(pane_id, target, visual_bell.duration);
+        self.flash(flash);
+        self.request_attention();
+    }
+
+    /// Called when a search in `pane_id` wraps around
+    pub fn search_wrapped(&mut self, pane_id: PaneId) {
+        let flash = self.visual_bell_flash(
+            pane_id,
+            EffectTarget::Pane(pane_id),
+            self.config.search_wrap_flash_duration,
+        );
+        self.flash(flash);
+    }
+
     fn mux_pane_output_event_callback(
         n: MuxNotification,
         window: &Window,
         mux_window_id: MuxWindowId,
         dead: &Arc<AtomicBool>,
+        tab_activity: &Rc<RefCell<HashMap<TabId, TabActivity>>>,
     ) -> bool {
         if dead.load(Ordering::Relaxed) {
             // Subscription cancelled asynchronously
@@ -633,13 +952,22 @@ impl TermWindow {
 
         if let MuxNotification::PaneOutput(pane_id) = n {
             let mut pane_in_window = false;
+            let mut newly_active = false;
 
             let mux = Mux::get().expect("mux is calling us");
             if let Some(mux_window) = mux.get_window(mux_window_id) {
-                for tab in mux_window.iter() {
-                    if tab.contains_pane(pane_id) {
-                        pane_in_window = true;
-                        break;
+                // Only the active tab is visible, so there's no need
+                // to wake up the window for output in other tabs
+                if let Some(tab) = mux_window.get_active() {
+                    pane_in_window = tab.contains_pane(pane_id);
+                }
+                // ...other than to flag the first output in the tab bar
+                if !pane_in_window {
+                    if let Some(tab) = mux_window.iter().find(|tab| tab.contains_pane(pane_id)) {
+                        let mut tab_activity = tab_activity.borrow_mut();
+                        let activity = tab_activity.entry(tab.tab_id()).or_default();
+                        newly_active = !activity.output;
+                        activity.output = true;
                     }
                 }
             } else {
@@ -647,6 +975,15 @@ impl TermWindow {
                 return false;
             }
 
+            if newly_active {
+                window.apply(move |myself, _window| {
+                    if let Some(myself) = myself.downcast_mut::<Self>() {
+                        myself.update_title();
+                    }
+                    Ok(())
+                });
+            }
+
             if pane_in_window {
                 let dead = Arc::clone(dead);
                 window.apply(move |myself, _window| {
@@ -669,8 +1006,9 @@ impl TermWindow {
         let mux_window_id = self.mux_window_id;
         let mux = Mux::get().expect("mux started and running on main thread");
         let dead = Arc::new(AtomicBool::new(false));
+        let tab_activity = Rc::clone(&self.tab_activity);
         mux.subscribe(move |n| {
-            Self::mux_pane_output_event_callback(n, &window, mux_window_id, &dead)
+            Self::mux_pane_output_event_callback(n, &window, mux_window_id, &dead, &tab_activity)
         });
     }
 
@@ -787,6 +1125,14 @@ impl TermWindow {
         // and render any changes
         self.check_for_config_reload();
 
+        // The mux window may have been removed out from under us,
+        // for example by the last tab being killed from another
+        // window, so tear down the corresponding GUI window.
+        if mux.get_window(self.mux_window_id).is_none() {
+            self.window.as_ref().unwrap().close();
+            return Ok(());
+        }
+
         let panes = self.get_panes_to_render();
         if panes.is_empty() {
             self.window.as_ref().unwrap().close();
@@ -794,6 +1140,16 @@ impl TermWindow {
         }
 
         let now = Instant::now();
+
+        // Deactivate a timed out key table so that its pending
+        // keys are removed from the tab bar
+        if let Some(state) = self.key_table_state.as_ref() {
+            if state.is_expired(now) {
+                self.key_table_state.take();
+                self.update_title_impl();
+            }
+        }
+
         if now.duration_since(self.last_status_call)
             > Duration::from_millis(self.config.status_update_interval)
         {
@@ -801,10 +1157,30 @@ impl TermWindow {
             self.schedule_status_update();
         }
 
+        if self
+            .idle
+            .check(Duration::from_secs(self.config.idle.timeout_seconds), now)
+        {
+            log::trace!("window is now idle");
+            // Repaint to dim the window
+            needs_invalidate = true;
+        }
+        let animations_paused = self.animations_paused();
+        let mut polling_paused = self.idle.is_idle() && self.config.idle.pause_remote_polling;
+        if self.saving_battery_power() {
+            let interval = Duration::from_millis(self.config.battery_power_saving.poll_interval);
+            if now.duration_since(self.last_dirty_poll) < interval {
+                polling_paused = true;
+            }
+        }
+        if !polling_paused {
+            self.last_dirty_poll = now;
+        }
+
         // If self.has_animation is some, then the last render detected
         // image attachments with multiple frames, so we also need to
         // invalidate the viewport when the next frame is due
-        if self.focused.is_some() {
+        if self.focused.is_some() && !animations_paused {
             if let Some(next_due) = *self.has_animation.borrow() {
                 if now >= next_due {
                     needs_invalidate = true;
@@ -820,7 +1196,11 @@ impl TermWindow {
             // This is pretty heavyweight: it would be nice to only invalidate
             // the line on which the cursor resides, and then only if the cursor
             // is within the viewport.
-            if self.config.cursor_blink_rate != 0 && pos.is_active && self.focused.is_some() {
+            if self.config.cursor_blink_rate != 0
+                && pos.is_active
+                && self.focused.is_some()
+                && !animations_paused
+            {
                 let shape = self
                     .config
                     .default_cursor_style
@@ -835,8 +1215,14 @@ impl TermWindow {
                 }
             }
 
-            // If the model is dirty, arrange to re-paint
-            if self.check_for_dirty_lines_and_invalidate_selection(&pos) {
+            // If the model is dirty, arrange to re-paint.
+            // Panes that notify us of changes are handled by
+            // mux_pane_output_event instead.
+            if !pos.pane.notifies_on_change()
+                && !polling_paused
+                && (self.check_for_evicted_rows(&pos)
+                    | self.check_for_dirty_lines_and_invalidate_selection(&pos))
+            {
                 needs_invalidate = true;
             }
         }
@@ -856,6 +1242,24 @@ impl TermWindow {
         Ok(())
     }
 
+    /// When output causes rows to be evicted from the top of the
+    /// scrollback, drop the selection and overlay state that refers
+    /// to them so that it doesn't linger on as a stale highlight.
+    /// Returns true if the window needs to be repainted as a result.
+    fn check_for_evicted_rows(&mut self, pos: &PositionedPane) -> bool {
+        let first_row = pos.pane.get_dimensions().scrollback_top;
+        let mut state = self.pane_state(pos.pane.pane_id());
+        match state.scrollback_top.replace(first_row) {
+            Some(prior) if prior >= first_row => return false,
+            _ => {}
+        }
+
+        if let Some(overlay) = state.overlay.as_ref() {
+            overlay.rows_evicted(first_row);
+        }
+        state.selection.evict_rows_before(first_row)
+    }
+
     fn check_for_dirty_lines_and_invalidate_selection(&mut self, pos: &PositionedPane) -> bool {
         let dims = pos.pane.get_dimensions();
         let viewport = self
@@ -906,11 +1310,62 @@ impl TermWindow {
 
     fn palette(&mut self) -> &ColorPalette {
         if self.palette.is_none() {
-            self.palette.replace(config::TermConfig.color_palette());
+            self.palette
+                .replace(self.config.resolved_palette.clone().into());
         }
         self.palette.as_ref().unwrap()
     }
 
+    /// Returns the palette to use when rendering `pane`.
+    /// Panes take their colors from the global configuration, so
+    /// if this window has overridden its colors then the window
+    /// palette is used in place of the pane palette, unless the pane
+    /// has changed its colors via escape sequences or a color scheme
+    /// of its own.
+    pub fn pane_palette(&mut self, pane: &dyn Pane) -> ColorPalette {
+        let palette = pane.palette();
+        if self.config_overrides.is_null() || palette != *self.global_palette() {
+            return palette;
+        }
+        self.palette().clone()
+    }
+
+    /// Returns the palette from the global configuration.  It is
+    /// cached until the configuration is reloaded, as building it
+    /// for every pane on every frame is relatively expensive.
+    fn global_palette(&mut self) -> &ColorPalette {
+        if self.global_palette.is_none() {
+            self.global_palette
+                .replace(config::TermConfig.color_palette());
+        }
+        self.global_palette.as_ref().unwrap()
+    }
+
+    /// Overrides `window_background_image` for this window, as requested
+    /// by a program via the `WEZTERM_BACKGROUND` user var.  `None` reverts
+    /// to the image from the configuration.
+    pub fn set_background_image(&mut self, path: Option<String>) {
+        let mut overrides = match self.config_overrides.take() {
+            serde_json::Value::Object(map) => map,
+            _ => serde_json::Map::new(),
+        };
+        match path {
+            Some(path) => {
+                overrides.insert(
+                    "window_background_image".to_string(),
+                    serde_json::Value::String(path),
+                );
+            }
+            None => {
+                overrides.remove("window_background_image");
+            }
+        }
+        if !overrides.is_empty() {
+            self.config_overrides = serde_json::Value::Object(overrides);
+        }
+        self.config_was_reloaded();
+    }
+
     pub fn config_was_reloaded(&mut self) {
         log::debug!(
             "config was reloaded, overrides: {:?}",
@@ -929,6 +1384,7 @@ impl TermWindow {
         };
         self.config = config.clone();
         self.palette.take();
+        self.global_palette.take();
 
         self.window_background = reload_background_image(&config, &self.window_background);
 
@@ -947,6 +1403,7 @@ impl TermWindow {
         self.shape_cache.borrow_mut().clear();
         self.input_map = InputMap::new();
         self.leader_is_down = None;
+        self.key_table_state = None;
         let dimensions = self.dimensions;
 
         if let Err(err) = self.fonts.config_changed(&config) {
@@ -958,6 +1415,7 @@ impl TermWindow {
             window.config_did_change(&config);
             window.invalidate();
         }
+        menu::update_menu_bar(&self.input_map);
 
         self.emit_window_event("window-config-reloaded");
     }
@@ -1005,6 +1463,27 @@ impl TermWindow {
             _ => return,
         };
 
+        // Show the keys of an in-progress sequence ahead of the status
+        let mut right_status = match self.key_table_state.as_ref() {
+            Some(state) if !state.pending.is_empty() => {
+                format!("{} {}", state.pending.join(" "), self.right_status)
+            }
+            _ => self.right_status.clone(),
+        };
+        let command_status = crate::statuscmd::tab_bar_text();
+        if !command_status.is_empty() {
+            if !right_status.is_empty() {
+                right_status.push(' ');
+            }
+            right_status.push_str(&command_status);
+        }
+
+        // The active tab has been seen, and closed tabs are forgotten
+        let active_tab_id = window.get_active().map(|tab| tab.tab_id());
+        self.tab_activity.borrow_mut().retain(|tab_id, _| {
+            Some(*tab_id) != active_tab_id && window.iter().any(|tab| tab.tab_id() == *tab_id)
+        });
+
         let new_tab_bar = TabBarState::new(
             self.terminal_size.cols as usize,
             if self.last_mouse_coords.1 == 0 {
@@ -1015,7 +1494,8 @@ impl TermWindow {
             &window,
             self.config.colors.as_ref().and_then(|c| c.tab_bar.as_ref()),
             &self.config,
-            &self.right_status,
+            &right_status,
+            &self.tab_activity.borrow(),
         );
         if new_tab_bar != self.tab_bar {
             self.tab_bar = new_tab_bar;
@@ -1031,26 +1511,54 @@ impl TermWindow {
         }
 
         let tab_no = window.get_active_idx();
+        let name = window
+            .get_name()
+            .map(|name| format!("{} - ", name))
+            .unwrap_or_default();
         drop(window);
 
         let panes = self.get_panes_to_render();
         if let Some(pos) = panes.iter().find(|p| p.is_active) {
             let title = pos.pane.get_title();
+            let input_mode = match mux.get_pane_input_mode(pos.pane.pane_id()) {
+                PaneInputMode::Normal => "",
+                PaneInputMode::ReadOnly => "[RO] ",
+                PaneInputMode::Broadcast => "[B] ",
+                PaneInputMode::BroadcastToSelected => "[BS] ",
+            };
+            let escape_debug = if mux.is_pane_escape_debug_enabled(pos.pane.pane_id()) {
+                "[ESC] "
+            } else {
+                ""
+            };
+            let paused = if mux.is_pane_output_paused(pos.pane.pane_id()) {
+                "[PAUSED] "
+            } else {
+                ""
+            };
 
             if let Some(window) = self.window.as_ref() {
                 let show_tab_bar;
                 if num_tabs == 1 {
                     window.set_title(&format!(
-                        "{}{}",
+                        "{}{}{}{}{}{}",
                         if pos.is_zoomed { "[Z] " } else { "" },
+                        input_mode,
+                        escape_debug,
+                        paused,
+                        name,
                         title
                     ));
                     show_tab_bar =
                         self.config.enable_tab_bar && !self.config.hide_tab_bar_if_only_one_tab;
                 } else {
                     window.set_title(&format!(
-                        "{}[{}/{}] {}",
+                        "{}{}{}{}{}[{}/{}] {}",
                         if pos.is_zoomed { "[Z] " } else { "" },
+                        input_mode,
+                        escape_debug,
+                        paused,
+                        name,
                         tab_no + 1,
                         num_tabs,
                         title
@@ -1073,7 +1581,7 @@ impl TermWindow {
         let cursor = pane.get_cursor_position();
         if let Some(win) = self.window.as_ref() {
             let config = &self.config;
-            let top = pane.get_dimensions().physical_top + if self.show_tab_bar { -1 } else { 0 };
+            let top = pane.get_dimensions().physical_top - self.rows_above_panes() as isize;
             let r = Rect::new(
                 Point::new(
                     (cursor.x.max(0) as isize * self.render_metrics.cell_size.width)
@@ -1088,35 +1596,55 @@ impl TermWindow {
     }
 
     fn activate_tab(&mut self, tab_idx: isize) -> anyhow::Result<()> {
-        if let Some(tab) = self.get_active_pane_or_overlay() {
-            tab.focus_changed(false);
-        }
-
         let mux = Mux::get().unwrap();
-        let mut window = mux
-            .get_window_mut(self.mux_window_id)
+        let window = mux
+            .get_window(self.mux_window_id)
             .ok_or_else(|| anyhow!("no such window"))?;
 
         let max = window.len();
+        drop(window);
 
+        // Negative indices count back from the end, with -1 being
+        // the last tab
         let tab_idx = if tab_idx < 0 {
-            max.saturating_sub(tab_idx.abs() as usize)
+            match max.checked_sub(tab_idx.abs() as usize) {
+                Some(idx) => idx,
+                None => return Ok(()),
+            }
         } else {
             tab_idx as usize
         };
 
         if tab_idx < max {
-            window.save_and_then_set_active(tab_idx);
+            self.switch_to_tab(tab_idx, true)?;
+        }
+        Ok(())
+    }
 
-            drop(window);
+    /// Makes `tab_idx` the active tab.  If `save` is true, the tab
+    /// that was active is recorded as the most recently used tab.
+    fn switch_to_tab(&mut self, tab_idx: usize, save: bool) -> anyhow::Result<()> {
+        if let Some(tab) = self.get_active_pane_or_overlay() {
+            tab.focus_changed(false);
+        }
 
-            if let Some(tab) = self.get_active_pane_or_overlay() {
-                tab.focus_changed(true);
-            }
+        let mux = Mux::get().unwrap();
+        let mut window = mux
+            .get_window_mut(self.mux_window_id)
+            .ok_or_else(|| anyhow!("no such window"))?;
+        if save {
+            window.save_and_then_set_active(tab_idx);
+        } else {
+            window.set_active_without_saving(tab_idx);
+        }
+        drop(window);
 
-            self.update_title();
-            self.update_scrollbar();
+        if let Some(tab) = self.get_active_pane_or_overlay() {
+            tab.focus_changed(true);
         }
+
+        self.update_title();
+        self.update_scrollbar();
         Ok(())
     }
 
@@ -1136,6 +1664,28 @@ impl TermWindow {
         self.activate_tab((tab as usize % max) as isize)
     }
 
+    fn activate_window(&mut self, window_idx: usize) -> anyhow::Result<()> {
+        let windows = front_end().unwrap().gui_windows();
+        if let Some((_, window)) = windows.get(window_idx) {
+            window.focus();
+        }
+        Ok(())
+    }
+
+    fn activate_window_relative(&mut self, delta: isize) -> anyhow::Result<()> {
+        let windows = front_end().unwrap().gui_windows();
+        let max = windows.len();
+        ensure!(max > 0, "no more windows");
+
+        let active = windows
+            .iter()
+            .position(|(id, _)| *id == self.mux_window_id)
+            .ok_or_else(|| anyhow!("current window is not known to the frontend"))?
+            as isize;
+        let idx = (active + delta).rem_euclid(max as isize) as usize;
+        self.activate_window(idx)
+    }
+
     fn activate_last_tab(&mut self) -> anyhow::Result<()> {
         let mux = Mux::get().unwrap();
         let window = mux
@@ -1210,6 +1760,30 @@ impl TermWindow {
         promise::spawn::spawn(future).detach();
     }
 
+    /// Shows an overlay listing the panes of every window, so that any
+    /// of them can be found and activated
+    fn show_pane_navigator(&mut self) {
+        let mux = Mux::get().unwrap();
+        let tab = match mux.get_active_tab_for_window(self.mux_window_id) {
+            Some(tab) => tab,
+            None => return,
+        };
+        let pane_id = match tab.get_active_pane() {
+            Some(pane) => pane.pane_id(),
+            None => return,
+        };
+
+        // As with the tab navigator, the list is captured up front
+        // because the overlay runs in a different thread
+        let panes = list_panes();
+
+        let (overlay, future) = start_overlay(self, &tab, move |_tab_id, term| {
+            pane_navigator(pane_id, term, panes)
+        });
+        self.assign_overlay(tab.tab_id(), overlay);
+        promise::spawn::spawn(future).detach();
+    }
+
     fn show_launcher(&mut self) {
         let mux = Mux::get().unwrap();
         let tab = match mux.get_active_tab_for_window(self.mux_window_id) {
@@ -1316,6 +1890,20 @@ impl TermWindow {
         Ok(())
     }
 
+    fn scroll_to_top(&mut self) {
+        if let Some(pane) = self.get_active_pane_or_overlay() {
+            let dims = pane.get_dimensions();
+            self.set_viewport(pane.pane_id(), Some(dims.scrollback_top), dims);
+        }
+    }
+
+    fn scroll_to_bottom(&mut self) {
+        if let Some(pane) = self.get_active_pane_or_overlay() {
+            let dims = pane.get_dimensions();
+            self.set_viewport(pane.pane_id(), None, dims);
+        }
+    }
+
     fn scroll_by_line(&mut self, amount: isize) -> anyhow::Result<()> {
         let pane = match self.get_active_pane_or_overlay() {
             Some(pane) => pane,
@@ -1356,6 +1944,55 @@ impl TermWindow {
         self.move_tab(tab)
     }
 
+    /// Opens `uri`, which was found in `pane`.
+    /// We need to ensure that we spawn the `open` call outside of the context
+    /// of our window loop; on Windows it can cause a panic due to
+    /// triggering our WndProc recursively.
+    /// We get that assurance for free as part of the async dispatch that we
+    /// perform below; here we allow the user to define an `open-uri` event
+    /// handler that can bypass the normal `open::that` functionality.
+    /// Links matched by `uri_handlers` are routed before any of that.
+    pub fn open_link(&mut self, pane: &Rc<dyn Pane>, uri: &str) {
+        if self.open_uri_with_handler(pane, uri) {
+            return;
+        }
+        let window = GuiWin::new(self);
+        let pane = PaneObject::new(pane);
+
+        async fn open_uri(
+            lua: Option<Rc<mlua::Lua>>,
+            window: GuiWin,
+            pane: PaneObject,
+            link: String,
+        ) -> anyhow::Result<()> {
+            let default_click = match lua {
+                Some(lua) => {
+                    let args = lua.pack_multi((window, pane, link.clone()))?;
+                    config::lua::emit_event(&lua, ("open-uri".to_string(), args))
+                        .await
+                        .map_err(|e| {
+                            log::error!("while processing open-uri event: {:#}", e);
+                            e
+                        })?
+                }
+                None => true,
+            };
+            if default_click {
+                log::info!("clicking {}", link);
+                if let Err(err) = open::that(&link) {
+                    log::error!("failed to open {}: {:?}", link, err);
+                }
+            }
+            Ok(())
+        }
+
+        let link = uri.to_string();
+        promise::spawn::spawn(config::with_lua_config_on_main_thread(move |lua| {
+            open_uri(lua, window, pane, link)
+        }))
+        .detach();
+    }
+
     pub fn perform_key_assignment(
         &mut self,
         pane: &Rc<dyn Pane>,
@@ -1377,11 +2014,17 @@ impl TermWindow {
             }
             SplitHorizontal(spawn) => {
                 log::trace!("SplitHorizontal {:?}", spawn);
-                self.spawn_command(spawn, SpawnWhere::SplitPane(SplitDirection::Horizontal));
+                self.spawn_command(
+                    spawn,
+                    SpawnWhere::SplitPane(SplitDirection::Horizontal.into()),
+                );
             }
             SplitVertical(spawn) => {
                 log::trace!("SplitVertical {:?}", spawn);
-                self.spawn_command(spawn, SpawnWhere::SplitPane(SplitDirection::Vertical));
+                self.spawn_command(
+                    spawn,
+                    SpawnWhere::SplitPane(SplitDirection::Vertical.into()),
+                );
             }
             ToggleFullScreen => {
                 self.window.as_ref().unwrap().toggle_fullscreen();
@@ -1397,6 +2040,14 @@ impl TermWindow {
                 let text = self.selection_text(pane);
                 self.copy_to_clipboard(*dest, text);
             }
+            CopyAsHtml(dest) => {
+                let text = self.selection_html(pane);
+                self.copy_to_clipboard(*dest, text);
+            }
+            CopyAsAnsi(dest) => {
+                let text = self.selection_ansi(pane);
+                self.copy_to_clipboard(*dest, text);
+            }
             Paste => {
                 self.paste_from_clipboard(pane, ClipboardPasteSource::Clipboard);
             }
@@ -1410,6 +2061,7 @@ impl TermWindow {
                 self.activate_tab_relative(*n)?;
             }
             ActivateLastTab => self.activate_last_tab()?,
+            ActivateTabRelativeMRU(n) => self.cycle_mru(MruKind::Tab, *n, Modifiers::NONE),
             DecreaseFontSize => self.decrease_font_size(),
             IncreaseFontSize => self.increase_font_size(),
             ResetFontSize => self.reset_font_size(),
@@ -1417,7 +2069,20 @@ impl TermWindow {
             ActivateTab(n) => {
                 self.activate_tab(*n)?;
             }
-            SendString(s) => pane.writer().write_all(s.as_bytes())?,
+            ActivateWindow(n) => {
+                self.activate_window(*n)?;
+            }
+            ActivateWindowRelative(n) => {
+                self.activate_window_relative(*n)?;
+            }
+            SendString(s) => {
+                for target in self.input_targets(pane) {
+                    if let Err(err) = target.writer().write_all(s.as_bytes()) {
+                        log::error!("SendString to pane {}: {:#}", target.pane_id(), err);
+                    }
+                }
+            }
+            SendKey(key) => self.send_synthesized_key(pane, key),
             Hide => {
                 if let Some(w) = self.window.as_ref() {
                     w.hide();
@@ -1430,6 +2095,7 @@ impl TermWindow {
             }
             CloseCurrentTab { confirm } => self.close_current_tab(*confirm),
             CloseCurrentPane { confirm } => self.close_current_pane(*confirm),
+            RestartPane { confirm } => self.restart_current_pane(*confirm),
             Nop | DisableDefaultAssignment => {}
             ReloadConfiguration => config::reload(),
             MoveTab(n) => self.move_tab(*n)?,
@@ -1437,12 +2103,23 @@ impl TermWindow {
             ScrollByPage(n) => self.scroll_by_page(*n)?,
             ScrollByLine(n) => self.scroll_by_line(*n)?,
             ScrollToPrompt(n) => self.scroll_to_prompt(*n)?,
+            ScrollToTop => self.scroll_to_top(),
+            ScrollToBottom => self.scroll_to_bottom(),
             ShowTabNavigator => self.show_tab_navigator(),
+            ShowPaneNavigator => self.show_pane_navigator(),
             ShowLauncher => self.show_launcher(),
+            ShowCommandHistory => self.show_command_history(pane)?,
             HideApplication => {
                 let con = Connection::get().expect("call on gui thread");
                 con.hide_application();
             }
+            ToggleSecureKeyboardEntry => {
+                let con = Connection::get().expect("call on gui thread");
+                con.set_secure_keyboard_entry(!con.is_secure_keyboard_entry_enabled())?;
+            }
+            ShowContextMenu => self.show_context_menu(pane),
+            OpenConfigFile => menu::open_config_file(),
+            ShowDebugOverlay => self.show_debug_overlay(pane),
             QuitApplication => {
                 let mux = Mux::get().unwrap();
                 let config = &self.config;
@@ -1476,49 +2153,11 @@ impl TermWindow {
             }
             OpenLinkAtMouseCursor => {
                 // They clicked on a link, so let's open it!
-                // We need to ensure that we spawn the `open` call outside of the context
-                // of our window loop; on Windows it can cause a panic due to
-                // triggering our WndProc recursively.
-                // We get that assurance for free as part of the async dispatch that we
-                // perform below; here we allow the user to define an `open-uri` event
-                // handler that can bypass the normal `open::that` functionality.
                 if let Some(link) = self.current_highlight.as_ref().cloned() {
-                    let window = GuiWin::new(self);
-                    let pane = PaneObject::new(pane);
-
-                    async fn open_uri(
-                        lua: Option<Rc<mlua::Lua>>,
-                        window: GuiWin,
-                        pane: PaneObject,
-                        link: String,
-                    ) -> anyhow::Result<()> {
-                        let default_click = match lua {
-                            Some(lua) => {
-                                let args = lua.pack_multi((window, pane, link.clone()))?;
-                                config::lua::emit_event(&lua, ("open-uri".to_string(), args))
-                                    .await
-                                    .map_err(|e| {
-                                        log::error!("while processing open-uri event: {:#}", e);
-                                        e
-                                    })?
-                            }
-                            None => true,
-                        };
-                        if default_click {
-                            log::info!("clicking {}", link);
-                            if let Err(err) = open::that(&link) {
-                                log::error!("failed to open {}: {:?}", link, err);
-                            }
-                        }
-                        Ok(())
-                    }
-
-                    promise::spawn::spawn(config::with_lua_config_on_main_thread(move |lua| {
-                        open_uri(lua, window, pane, link.uri().to_string())
-                    }))
-                    .detach();
+                    self.open_link(pane, link.uri());
                 }
             }
+            OpenWith(command) => self.open_with(pane, command),
             EmitEvent(name) => {
                 self.emit_window_event(name);
             }
@@ -1549,13 +2188,23 @@ impl TermWindow {
             Search(pattern) => {
                 if let Some(pane) = self.get_active_pane_no_overlay() {
                     let search = SearchOverlay::with_pane(self, &pane, pattern.clone());
-                    self.assign_overlay_for_pane(pane.pane_id(), search);
+                    self.assign_overlay_for_pane(pane.pane_id(), OverlayState::with_hooks(search));
                 }
             }
             ActivateCopyMode => {
                 if let Some(pane) = self.get_active_pane_no_overlay() {
+                    // If a search is in progress, start from its current match
+                    let search_match = self
+                        .pane_state(pane.pane_id())
+                        .overlay
+                        .as_ref()
+                        .and_then(|overlay| overlay.pane.downcast_ref::<SearchOverlay>())
+                        .and_then(|search| search.current_result());
                     let copy = CopyOverlay::with_pane(self, &pane);
-                    self.assign_overlay_for_pane(pane.pane_id(), copy);
+                    if let Some(result) = search_match {
+                        copy.move_cursor_to(result.start_x, result.start_y);
+                    }
+                    self.assign_overlay_for_pane(pane.pane_id(), OverlayState::with_hooks(copy));
                 }
             }
             AdjustPaneSize(direction, amount) => {
@@ -1584,6 +2233,7 @@ impl TermWindow {
                     tab.activate_pane_direction(*direction);
                 }
             }
+            ActivatePaneRelativeMRU(n) => self.cycle_mru(MruKind::Pane, *n, Modifiers::NONE),
             TogglePaneZoomState => {
                 let mux = Mux::get().unwrap();
                 let tab = match mux.get_active_tab_for_window(self.mux_window_id) {
@@ -1592,10 +2242,174 @@ impl TermWindow {
                 };
                 tab.toggle_zoom();
             }
+            ActivateKeyTable(activation) => self.activate_key_table(activation),
+            CharSelect => self.show_char_select(pane),
+            PasteFromHistory => self.show_paste_history(pane),
+            PaneSelect(args) => self.show_pane_select(args),
+            TogglePaneInputMode(mode) => {
+                if let Some(pane) = self.get_active_pane_no_overlay() {
+                    let mux = Mux::get().unwrap();
+                    let pane_id = pane.pane_id();
+                    let mode = if mux.get_pane_input_mode(pane_id) == *mode {
+                        PaneInputMode::Normal
+                    } else {
+                        *mode
+                    };
+                    mux.set_pane_input_mode(pane_id, mode);
+                    self.update_title();
+                }
+            }
+            TogglePaneEscapeDebugMode => {
+                if let Some(pane) = self.get_active_pane_no_overlay() {
+                    let mux = Mux::get().unwrap();
+                    let pane_id = pane.pane_id();
+                    let enable = !mux.is_pane_escape_debug_enabled(pane_id);
+                    mux.set_pane_escape_debug(pane_id, enable);
+                    self.update_title();
+                }
+            }
+            CapturePane(args) => self.capture(pane.pane_id(), false, args),
+            CaptureWindow(args) => self.capture(pane.pane_id(), true, args),
+            ToggleInvisibleCharacters => {
+                if let Some(pane) = self.get_active_pane_no_overlay() {
+                    let show = !self.show_invisible_characters(pane.pane_id());
+                    self.pane_state(pane.pane_id())
+                        .show_invisible_characters
+                        .replace(show);
+                    if let Some(window) = self.window.as_ref() {
+                        window.invalidate();
+                    }
+                }
+            }
+            SetWindowPosition(args) => self.set_window_position(args),
+            SpawnTabLayout(name) => self.spawn_tab_layout(name),
+            SpawnCommandInFloatingPane(spawn) => {
+                self.spawn_command(spawn, SpawnWhere::Floating);
+            }
+            DismissFloatingPane => {
+                let mux = Mux::get().unwrap();
+                if let Some(tab) = mux.get_active_tab_for_window(self.mux_window_id) {
+                    if let Some(floating) = tab.get_floating_pane() {
+                        tab.kill_pane(floating.pane_id());
+                        if let Some(window) = self.window.as_ref() {
+                            window.invalidate();
+                        }
+                    }
+                }
+            }
+            BalancePanes(direction) => {
+                let mux = Mux::get().unwrap();
+                let tab = match mux.get_active_tab_for_window(self.mux_window_id) {
+                    Some(tab) => tab,
+                    None => return Ok(()),
+                };
+
+                if self.tab_state(tab.tab_id()).overlay.is_none() {
+                    tab.balance_panes(*direction);
+                }
+            }
+            ArrangePanes(arrangement) => {
+                let mux = Mux::get().unwrap();
+                let tab = match mux.get_active_tab_for_window(self.mux_window_id) {
+                    Some(tab) => tab,
+                    None => return Ok(()),
+                };
+
+                if self.tab_state(tab.tab_id()).overlay.is_none()
+                    && tab.arrange_panes(*arrangement).is_none()
+                {
+                    log::error!(
+                        "Unable to arrange the panes in tab {} as {:?}",
+                        tab.tab_id(),
+                        arrangement
+                    );
+                }
+            }
+            TogglePauseOutput => {
+                if let Some(pane) = self.get_active_pane_no_overlay() {
+                    let mux = Mux::get().unwrap();
+                    let pane_id = pane.pane_id();
+                    let paused = !mux.is_pane_output_paused(pane_id);
+                    mux.set_pane_output_paused(pane_id, paused);
+                    self.update_title();
+                }
+            }
         };
         Ok(())
     }
 
+    fn show_debug_overlay(&mut self, pane: &Rc<dyn Pane>) {
+        let pane_id = pane.pane_id();
+        let (overlay, future) = start_overlay_pane(self, pane, move |_, term| debug_overlay(term));
+        self.assign_overlay_for_pane(pane_id, OverlayState::new(overlay));
+        promise::spawn::spawn(future).detach();
+    }
+
+    fn show_char_select(&mut self, pane: &Rc<dyn Pane>) {
+        let pane_id = pane.pane_id();
+        let (overlay, future) =
+            start_overlay_pane(self, pane, move |pane_id, term| char_select(pane_id, term));
+        self.assign_overlay_for_pane(pane_id, OverlayState::new(overlay));
+        promise::spawn::spawn(future).detach();
+    }
+
+    fn show_paste_history(&mut self, pane: &Rc<dyn Pane>) {
+        let pane_id = pane.pane_id();
+        let window = self.window.clone().unwrap();
+        let (overlay, future) = start_overlay_pane(self, pane, move |pane_id, term| {
+            paste_from_history(pane_id, term, window)
+        });
+        self.assign_overlay_for_pane(pane_id, OverlayState::new(overlay));
+        promise::spawn::spawn(future).detach();
+    }
+
+    /// Shows an overlay listing the commands that were run in `pane`,
+    /// most recent first, using the Input zones that are defined by
+    /// shell integration
+    fn show_command_history(&mut self, pane: &Rc<dyn Pane>) -> anyhow::Result<()> {
+        let zones = pane.get_semantic_zones()?;
+        let mut commands = vec![];
+        for (idx, zone) in zones.iter().enumerate() {
+            if zone.semantic_type != wezterm_term::SemanticType::Input {
+                continue;
+            }
+            let range = SelectionRange {
+                start: SelectionCoordinate {
+                    x: zone.start_x,
+                    y: zone.start_y,
+                },
+                end: SelectionCoordinate {
+                    x: zone.end_x,
+                    y: zone.end_y,
+                },
+            };
+            let command = self.text_for_range(pane, &range).trim().to_string();
+            if command.is_empty() {
+                continue;
+            }
+            let output_row = match zones.get(idx + 1) {
+                Some(next) if next.semantic_type == wezterm_term::SemanticType::Output => {
+                    next.start_y
+                }
+                _ => zone.start_y,
+            };
+            commands.push(CommandHistoryEntry {
+                command,
+                output_row,
+            });
+        }
+        commands.reverse();
+
+        let pane_id = pane.pane_id();
+        let window = self.window.clone().unwrap();
+        let (overlay, future) = start_overlay_pane(self, pane, move |pane_id, term| {
+            command_history(pane_id, term, commands, window)
+        });
+        self.assign_overlay_for_pane(pane_id, OverlayState::new(overlay));
+        promise::spawn::spawn(future).detach();
+        Ok(())
+    }
+
     fn close_current_pane(&mut self, confirm: bool) {
         let mux_window_id = self.mux_window_id;
         let mux = Mux::get().unwrap();
@@ -1614,13 +2428,38 @@ impl TermWindow {
             let (overlay, future) = start_overlay_pane(self, &pane, move |pane_id, term| {
                 confirm_close_pane(pane_id, term, mux_window_id, window)
             });
-            self.assign_overlay_for_pane(pane_id, overlay);
+            self.assign_overlay_for_pane(pane_id, OverlayState::new(overlay));
             promise::spawn::spawn(future).detach();
         } else {
             tab.kill_pane(pane_id);
         }
     }
 
+    fn restart_current_pane(&mut self, confirm: bool) {
+        let mux = Mux::get().unwrap();
+        let tab = match mux.get_active_tab_for_window(self.mux_window_id) {
+            Some(tab) => tab,
+            None => return,
+        };
+        let pane = match tab.get_active_pane() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let pane_id = pane.pane_id();
+        // A pane whose process has exited can be restarted without asking
+        if confirm && !pane.can_close_without_prompting() {
+            let window = self.window.clone().unwrap();
+            let (overlay, future) = start_overlay_pane(self, &pane, move |pane_id, term| {
+                confirm_restart_pane(pane_id, term, window)
+            });
+            self.assign_overlay_for_pane(pane_id, OverlayState::new(overlay));
+            promise::spawn::spawn(future).detach();
+        } else if let Err(err) = mux.restart_pane(pane_id) {
+            log::error!("Failed to restart pane {}: {:#}", pane_id, err);
+        }
+    }
+
     fn close_current_tab(&mut self, confirm: bool) {
         let mux = Mux::get().unwrap();
         let tab = match mux.get_active_tab_for_window(self.mux_window_id) {
@@ -1657,6 +2496,14 @@ impl TermWindow {
         })
     }
 
+    /// Returns true if invisible characters should be marked in the
+    /// specified pane
+    fn show_invisible_characters(&self, pane_id: PaneId) -> bool {
+        self.pane_state(pane_id)
+            .show_invisible_characters
+            .unwrap_or(self.config.show_invisible_characters)
+    }
+
     pub fn tab_state(&self, tab_id: TabId) -> RefMut<TabState> {
         RefMut::map(self.tab_state.borrow_mut(), |state| {
             state.entry(tab_id).or_insert_with(TabState::default)
@@ -1689,18 +2536,23 @@ impl TermWindow {
             None => None,
         };
 
+        let now = Instant::now();
+        let displayed = self.displayed_viewport_top(pane_id, &dims, now);
+        let animate = self.scroll_animation_duration() != Duration::from_millis(0);
+
         let mut state = self.pane_state(pane_id);
         if pos != state.viewport {
             state.viewport = pos;
+            // Start from wherever the viewport is currently drawn, so
+            // that a scroll that interrupts another doesn't jump
+            state.scroll_animation = if animate {
+                Some(ScrollAnimation::new(displayed, now))
+            } else {
+                None
+            };
 
-            // This is a bit gross.  If we add other overlays that need this information,
-            // this should get extracted out into a trait
             if let Some(overlay) = state.overlay.as_ref() {
-                if let Some(search_overlay) = overlay.downcast_ref::<SearchOverlay>() {
-                    search_overlay.viewport_changed(pos);
-                } else if let Some(copy) = overlay.downcast_ref::<CopyOverlay>() {
-                    copy.viewport_changed(pos);
-                }
+                overlay.viewport_changed(pos);
             }
             self.window.as_ref().unwrap().invalidate();
         }
@@ -1708,26 +2560,109 @@ impl TermWindow {
 
     fn maybe_scroll_to_bottom_for_input(&mut self, pane: &Rc<dyn Pane>) {
         if self.config.scroll_to_bottom_on_input {
-            self.scroll_to_bottom(pane);
+            self.scroll_pane_to_bottom(pane);
+        }
+    }
+
+    fn scroll_pane_to_bottom(&mut self, pane: &Rc<dyn Pane>) {
+        self.pixel_scroll.stop();
+        let mut state = self.pane_state(pane.pane_id());
+        state.viewport = None;
+        state.scroll_animation.take();
+    }
+
+    fn scroll_animation_duration(&self) -> Duration {
+        if self.reduce_motion() {
+            Duration::from_millis(0)
+        } else {
+            Duration::from_millis(self.config.scroll_animation_duration)
+        }
+    }
+
+    /// Returns the (fractional) top row of the viewport as it is
+    /// drawn at `now`, clearing the scroll animation once it has
+    /// finished.
+    fn displayed_viewport_top(
+        &self,
+        pane_id: PaneId,
+        dims: &RenderableDimensions,
+        now: Instant,
+    ) -> f32 {
+        let mut state = self.pane_state(pane_id);
+        let to = state.viewport.unwrap_or(dims.physical_top);
+        let animated = state.scroll_animation.and_then(|anim| {
+            anim.top_at(
+                to,
+                self.scroll_animation_duration(),
+                self.config.scroll_animation_easing,
+                now,
+            )
+        });
+        if animated.is_none() {
+            state.scroll_animation.take();
+        }
+        animated.unwrap_or(to as f32)
+    }
+
+    /// Returns the top row of the viewport as it is drawn, which lags
+    /// behind `get_viewport` while a scroll animation is in progress,
+    /// along with the fraction of that row that has scrolled out of
+    /// view above the pane.  The fraction is 0 when the viewport is at
+    /// rest.
+    pub fn get_displayed_viewport_with_offset(
+        &self,
+        pane_id: PaneId,
+        dims: &RenderableDimensions,
+    ) -> (Option<StableRowIndex>, f32) {
+        let viewport = self.get_viewport(pane_id);
+        if self.pane_state(pane_id).scroll_animation.is_none() {
+            return (viewport, 0.);
         }
+        let top = self
+            .displayed_viewport_top(pane_id, dims, Instant::now())
+            .max(dims.scrollback_top as f32)
+            .min(dims.physical_top as f32);
+        let row = top.floor();
+        let offset = top - row;
+        let row = row as StableRowIndex;
+        if viewport.is_none() && row == dims.physical_top {
+            (None, 0.)
+        } else {
+            (Some(row), offset)
+        }
+    }
+
+    /// Returns the top row of the viewport as it is drawn; see
+    /// `get_displayed_viewport_with_offset`.  This is used to map the
+    /// mouse position to a row, so that the cell under the mouse is
+    /// the one that is displayed there.
+    pub fn get_displayed_viewport(
+        &self,
+        pane_id: PaneId,
+        dims: &RenderableDimensions,
+    ) -> Option<StableRowIndex> {
+        self.get_displayed_viewport_with_offset(pane_id, dims).0
     }
 
-    fn scroll_to_bottom(&mut self, pane: &Rc<dyn Pane>) {
-        self.pane_state(pane.pane_id()).viewport = None;
+    /// Returns true if the viewport of the pane is still moving
+    fn is_scroll_animating(&self, pane_id: PaneId) -> bool {
+        self.pane_state(pane_id).scroll_animation.is_some()
     }
 
+    /// Returns the pane that has the focus in the active tab; this is
+    /// the floating pane, if the tab has one, or the active pane.
     fn get_active_pane_no_overlay(&self) -> Option<Rc<dyn Pane>> {
         let mux = Mux::get().unwrap();
         mux.get_active_tab_for_window(self.mux_window_id)
-            .and_then(|tab| tab.get_active_pane())
+            .and_then(|tab| tab.get_floating_pane().or_else(|| tab.get_active_pane()))
     }
 
     /// Returns a Pane that we can interact with; this will typically be
     /// the active tab for the window, but if the window has a tab-wide
     /// overlay (such as the launcher / tab navigator),
-    /// then that will be returned instead.  Otherwise, if the pane has
-    /// an active overlay (such as search or copy mode) then that will
-    /// be returned.
+    /// then that will be returned instead.  Otherwise, if the tab has
+    /// a floating pane, or the pane has an active overlay (such as
+    /// search or copy mode) then that will be returned.
     fn get_active_pane_or_overlay(&self) -> Option<Rc<dyn Pane>> {
         let mux = Mux::get().unwrap();
         let tab = match mux.get_active_tab_for_window(self.mux_window_id) {
@@ -1740,11 +2675,12 @@ impl TermWindow {
         if let Some(tab_overlay) = self.tab_state(tab_id).overlay.clone() {
             Some(tab_overlay)
         } else {
-            let pane = tab.get_active_pane()?;
+            let pane = tab.get_floating_pane().or_else(|| tab.get_active_pane())?;
             let pane_id = pane.pane_id();
             self.pane_state(pane_id)
                 .overlay
-                .clone()
+                .as_ref()
+                .map(|overlay| Rc::clone(&overlay.pane))
                 .or_else(|| Some(pane))
         }
     }
@@ -1765,6 +2701,96 @@ impl TermWindow {
         }
     }
 
+    /// Called for input and for output in one of the visible panes
+    fn note_activity(&mut self) {
+        if self.idle.activity(Instant::now()) {
+            log::trace!("window is no longer idle");
+            if let Some(window) = self.window.as_ref() {
+                window.invalidate();
+            }
+        }
+    }
+
+    /// Returns true if rendering is being throttled because the system
+    /// is running on battery power
+    pub fn saving_battery_power(&self) -> bool {
+        self.config.battery_power_saving.enabled && crate::power::on_battery_power()
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.idle.is_idle()
+    }
+
+    /// Returns true if the cursor blink and image animations are
+    /// stopped, either because the window is idle or to save power
+    fn animations_paused(&self) -> bool {
+        (self.idle.is_idle() && self.config.idle.pause_animations)
+            || (self.saving_battery_power() && self.config.battery_power_saving.pause_animations)
+    }
+
+    /// Returns true if animated scrolling and cursor movement are
+    /// disabled, either by `reduce_motion` or to save power
+    fn reduce_motion(&self) -> bool {
+        self.config.reduce_motion
+            || (self.saving_battery_power() && self.config.battery_power_saving.pause_animations)
+    }
+
+    /// Returns the shortest time to allow between repaints for output
+    fn min_frame_interval(&self) -> Duration {
+        let mut max_fps = self.config.max_fps;
+        if self.saving_battery_power() {
+            max_fps = max_fps.min(self.config.battery_power_saving.max_fps);
+        }
+        Duration::from_secs(1) / max_fps.max(1) as u32
+    }
+
+    /// Returns the number of rows above the panes, which hold the
+    /// tab bar and the row that is reserved for pane titles
+    fn rows_above_panes(&self) -> usize {
+        let mut rows = 0;
+        if self.show_tab_bar {
+            rows += 1;
+        }
+        if self.config.pane_title_bar {
+            rows += 1;
+        }
+        rows
+    }
+
+    /// Returns the edges of the border that is drawn around the
+    /// floating pane of the active tab, if it has one
+    fn get_floating_pane_border(&self) -> Vec<PositionedSplit> {
+        let mux = Mux::get().unwrap();
+        let tab = match mux.get_active_tab_for_window(self.mux_window_id) {
+            Some(tab) => tab,
+            None => return vec![],
+        };
+        if self.tab_state(tab.tab_id()).overlay.is_some() {
+            return vec![];
+        }
+        let pos = match tab.floating_pane_position() {
+            Some(pos) if pos.left > 0 && pos.top > 0 => pos,
+            _ => return vec![],
+        };
+
+        let edge = |direction, left, top, size| PositionedSplit {
+            index: pos.index,
+            direction,
+            left,
+            top,
+            size,
+        };
+        let (left, right) = (pos.left - 1, pos.left + pos.width);
+        let (top, bottom) = (pos.top - 1, pos.top + pos.height);
+        let width = pos.width + 2;
+        vec![
+            edge(SplitDirection::Vertical, left, top, width),
+            edge(SplitDirection::Vertical, left, bottom, width),
+            edge(SplitDirection::Horizontal, left, pos.top, pos.height),
+            edge(SplitDirection::Horizontal, right, pos.top, pos.height),
+        ]
+    }
+
     fn get_panes_to_render(&mut self) -> Vec<PositionedPane> {
         let mux = Mux::get().unwrap();
         let tab = match mux.get_active_tab_for_window(self.mux_window_id) {
@@ -1790,9 +2816,19 @@ impl TermWindow {
             }]
         } else {
             let mut panes = tab.iter_panes();
+            // The floating pane is rendered last, so that it is drawn
+            // over the top of the others, and it has the focus
+            if let Some(floating) = tab.floating_pane_position() {
+                for p in &mut panes {
+                    p.is_active = false;
+                }
+                panes.push(floating);
+            }
             for p in &mut panes {
                 if let Some(overlay) = self.pane_state(p.pane.pane_id()).overlay.as_ref() {
-                    p.pane = Rc::clone(overlay);
+                    p.pane = Rc::clone(&overlay.pane);
+                } else if let Some(debug) = mux.get_pane_escape_debug(p.pane.pane_id()) {
+                    p.pane = debug;
                 }
             }
             panes
@@ -1825,14 +2861,21 @@ impl TermWindow {
         });
     }
 
-    fn cancel_overlay_for_pane(&self, pane_id: PaneId) {
-        if let Some(pane) = self.pane_state(pane_id).overlay.take() {
+    fn cancel_overlay_for_pane(&mut self, pane_id: PaneId) {
+        let overlay = self.pane_state(pane_id).overlay.take();
+        if let Some(overlay) = overlay {
             // Ungh, when I built the CopyOverlay, its pane doesn't get
             // added to the mux and instead it reports the overlaid
             // pane id.  Take care to avoid killing ourselves off
             // when closing the CopyOverlay
-            if pane_id != pane.pane_id() {
-                Mux::get().unwrap().remove_pane(pane.pane_id());
+            if pane_id != overlay.pane.pane_id() {
+                Mux::get().unwrap().remove_pane(overlay.pane.pane_id());
+            }
+            if let Some(viewport) = overlay.viewport_to_restore() {
+                let mux = Mux::get().unwrap();
+                if let Some(pane) = mux.get_pane(pane_id) {
+                    self.set_viewport(pane_id, viewport, pane.get_dimensions());
+                }
             }
         }
         if let Some(window) = self.window.as_ref() {
@@ -1849,12 +2892,35 @@ impl TermWindow {
         });
     }
 
-    pub fn assign_overlay_for_pane(&mut self, pane_id: PaneId, overlay: Rc<dyn Pane>) {
-        if let Some(prior) = self.pane_state(pane_id).overlay.replace(overlay) {
-            if pane_id != prior.pane_id() {
-                Mux::get().unwrap().remove_pane(prior.pane_id());
+    /// Dismisses the overlay for `pane_id` and then scrolls the
+    /// viewport so that `row` is at the top
+    pub fn schedule_scroll_to_row(window: Window, pane_id: PaneId, row: StableRowIndex) {
+        window.apply(move |myself, _| {
+            if let Some(myself) = myself.downcast_mut::<Self>() {
+                myself.cancel_overlay_for_pane(pane_id);
+                let mux = Mux::get().unwrap();
+                if let Some(pane) = mux.get_pane(pane_id) {
+                    myself.set_viewport(pane_id, Some(row), pane.get_dimensions());
+                }
+            }
+            Ok(())
+        });
+    }
+
+    pub fn assign_overlay_for_pane(&mut self, pane_id: PaneId, mut overlay: OverlayState) {
+        let viewport = self.get_viewport(pane_id);
+        let prior = self.pane_state(pane_id).overlay.take();
+        match prior {
+            Some(prior) => {
+                // Preserve the viewport from before the original overlay
+                overlay.set_saved_viewport(prior.saved_viewport());
+                if pane_id != prior.pane.pane_id() {
+                    Mux::get().unwrap().remove_pane(prior.pane.pane_id());
+                }
             }
+            None => overlay.set_saved_viewport(viewport),
         }
+        self.pane_state(pane_id).overlay.replace(overlay);
         self.update_title();
     }
 
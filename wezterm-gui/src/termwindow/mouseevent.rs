@@ -2,10 +2,10 @@ use crate::tabbar::TabBarItem;
 use crate::termwindow::keyevent::window_mods_to_termwiz_mods;
 use crate::termwindow::{ScrollHit, TMB};
 use ::window::{
-    Modifiers, MouseButtons as WMB, MouseCursor, MouseEvent, MouseEventKind as WMEK, MousePress,
-    WindowOps,
+    GestureEvent, Modifiers, MouseButtons as WMB, MouseCursor, MouseEvent, MouseEventKind as WMEK,
+    MousePress, WindowOps,
 };
-use config::keyassignment::{MouseEventTrigger, SpawnTabDomain};
+use config::keyassignment::{GestureTrigger, MouseEventTrigger, SpawnTabDomain};
 use mux::pane::Pane;
 use mux::tab::SplitDirection;
 use mux::Mux;
@@ -13,12 +13,53 @@ use std::convert::TryInto;
 use std::ops::Sub;
 use std::rc::Rc;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use wezterm_term::input::MouseEventKind as TMEK;
 use wezterm_term::{LastMouseClick, StableRowIndex};
 
 impl super::TermWindow {
+    /// Converts high resolution scroll events into whole rows, returning
+    /// None if the event doesn't need any further processing
+    fn translate_pixel_scroll(
+        &mut self,
+        event: &MouseEvent,
+        context: &dyn WindowOps,
+    ) -> Option<MouseEvent> {
+        let cell_height = self.render_metrics.cell_size.height as f32;
+        let now = Instant::now();
+        let kind = match event.kind {
+            WMEK::VertPixelWheel(pixels) => {
+                match self.pixel_scroll.scroll(pixels as f32, cell_height, now) {
+                    0 => return None,
+                    rows => WMEK::VertWheel(rows),
+                }
+            }
+            WMEK::PixelWheelStop => {
+                if self.config.kinetic_scrolling && self.pixel_scroll.release(now) {
+                    // The momentum is applied as frames are painted
+                    context.invalidate();
+                }
+                return None;
+            }
+            WMEK::Press(_) => {
+                self.pixel_scroll.stop();
+                return Some(event.clone());
+            }
+            _ => return Some(event.clone()),
+        };
+        Some(MouseEvent {
+            kind,
+            ..event.clone()
+        })
+    }
+
     pub fn mouse_event_impl(&mut self, event: &MouseEvent, context: &dyn WindowOps) {
+        let event = match self.translate_pixel_scroll(event, context) {
+            Some(event) => event,
+            None => return,
+        };
+        let event = &event;
+
         let pane = match self.get_active_pane_or_overlay() {
             Some(pane) => pane,
             None => return,
@@ -45,7 +86,7 @@ impl super::TermWindow {
             .max(0)
             / self.render_metrics.cell_size.height) as i64;
 
-        let first_line_offset = if self.show_tab_bar { 1 } else { 0 };
+        let first_line_offset = self.rows_above_panes() as i64;
         self.last_mouse_coords = (x, y);
 
         let in_tab_bar = self.show_tab_bar && y == 0 && event.coords.y >= 0;
@@ -286,8 +327,19 @@ impl super::TermWindow {
         event: &MouseEvent,
         context: &dyn WindowOps,
     ) {
+        // When pane title bars are enabled, the row above each pane
+        // holds its title, and clicking on it activates the pane
+        let title_rows = if self.config.pane_title_bar { 1 } else { 0 };
+
+        // The title row takes precedence over any split divider that
+        // runs through it, so that it can't start a resize
+        let over_title = title_rows > 0
+            && self.get_panes_to_render().iter().any(|pos| {
+                y == pos.top as i64 - title_rows && x >= pos.left && x <= pos.left + pos.width
+            });
+
         let mut on_split = None;
-        if y >= 0 {
+        if y >= 0 && !over_title {
             let y = y as usize;
 
             for split in self.get_splits() {
@@ -322,8 +374,10 @@ impl super::TermWindow {
             }
         }
 
-        for pos in self.get_panes_to_render() {
-            if y >= pos.top as i64
+        // Panes are rendered in order, so search them in reverse to find
+        // the topmost, which matters when there is a floating pane
+        for pos in self.get_panes_to_render().into_iter().rev() {
+            if y >= pos.top as i64 - title_rows
                 && y <= (pos.top + pos.height) as i64
                 && x >= pos.left
                 && x <= pos.left + pos.width
@@ -342,7 +396,16 @@ impl super::TermWindow {
                         WMEK::Release(_) => {}
                         WMEK::VertWheel(_) => {}
                         WMEK::HorzWheel(_) => {}
+                        WMEK::VertPixelWheel(_) | WMEK::PixelWheelStop => {}
+                    }
+                }
+                if y < pos.top as i64 {
+                    // Over the title rather than the content of the pane
+                    context.set_cursor(Some(MouseCursor::Arrow));
+                    if let WMEK::Press(_) = &event.kind {
+                        context.invalidate();
                     }
+                    return;
                 }
                 x = x.saturating_sub(pos.left);
                 y = y.saturating_sub(pos.top as i64);
@@ -352,10 +415,14 @@ impl super::TermWindow {
 
         let dims = pane.get_dimensions();
         let stable_row = self
-            .get_viewport(pane.pane_id())
+            .get_displayed_viewport(pane.pane_id(), &dims)
             .unwrap_or(dims.physical_top)
             + y as StableRowIndex;
 
+        // When bidi is enabled the mouse is over a visual column, but
+        // everything else works with the logical column in the model
+        let x = self.bidi_logical_col(&pane, stable_row, x);
+
         self.last_mouse_terminal_coords = (x, stable_row); // FIXME: per-pane
 
         let (top, mut lines) = pane.get_lines_with_hyperlinks_applied(
@@ -445,7 +512,10 @@ impl super::TermWindow {
                     None
                 }
             }
-            WMEK::VertWheel(_) | WMEK::HorzWheel(_) => None,
+            WMEK::VertWheel(_)
+            | WMEK::HorzWheel(_)
+            | WMEK::VertPixelWheel(_)
+            | WMEK::PixelWheelStop => None,
         };
 
         let ignore_grab_modifier = Modifiers::SHIFT;
@@ -473,7 +543,11 @@ impl super::TermWindow {
         let mouse_event = wezterm_term::MouseEvent {
             kind: match event.kind {
                 WMEK::Move => TMEK::Move,
-                WMEK::VertWheel(_) | WMEK::HorzWheel(_) | WMEK::Press(_) => TMEK::Press,
+                WMEK::VertWheel(_)
+                | WMEK::HorzWheel(_)
+                | WMEK::VertPixelWheel(_)
+                | WMEK::PixelWheelStop
+                | WMEK::Press(_) => TMEK::Press,
                 WMEK::Release(_) => TMEK::Release,
             },
             button: match event.kind {
@@ -496,7 +570,7 @@ impl super::TermWindow {
                         TMB::WheelDown((-amount) as usize)
                     }
                 }
-                WMEK::HorzWheel(_) => TMB::None,
+                WMEK::HorzWheel(_) | WMEK::VertPixelWheel(_) | WMEK::PixelWheelStop => TMB::None,
             },
             x,
             y,
@@ -512,6 +586,41 @@ impl super::TermWindow {
             }
         }
     }
+
+    pub fn gesture_event_impl(&mut self, event: &GestureEvent, context: &dyn WindowOps) {
+        // How far the fingers pinch before a pinch binding triggers;
+        // this is similar to the scale applied by IncreaseFontSize
+        const PINCH_STEP: f64 = 0.1;
+
+        let trigger = match event {
+            GestureEvent::Pinch { delta } => {
+                self.pinch_magnification += delta;
+                if self.pinch_magnification.abs() < PINCH_STEP {
+                    return;
+                }
+                let trigger = if self.pinch_magnification > 0. {
+                    GestureTrigger::PinchOut
+                } else {
+                    GestureTrigger::PinchIn
+                };
+                self.pinch_magnification = 0.;
+                trigger
+            }
+            GestureEvent::Swipe { direction, fingers } => GestureTrigger::Swipe {
+                direction: *direction,
+                fingers: *fingers,
+            },
+        };
+
+        let pane = match self.get_active_pane_or_overlay() {
+            Some(pane) => pane,
+            None => return,
+        };
+        if let Some(action) = self.input_map.lookup_gesture(&trigger) {
+            self.perform_key_assignment(&pane, &action).ok();
+            context.invalidate();
+        }
+    }
 }
 
 fn mouse_press_to_tmb(press: &MousePress) -> TMB {
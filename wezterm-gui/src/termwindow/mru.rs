@@ -0,0 +1,299 @@
+//! Cycles through the tabs of a window, or the panes of a tab, in
+//! most recently used order, in the manner of Alt-Tab.
+//! Each press activates the next entry in the list, but the history is
+//! only updated once the modifiers that were held are released, so that
+//! repeated presses reach further back into it.
+use ::window::{KeyCode, KeyEvent, Modifiers};
+use anyhow::anyhow;
+use config::TabTitleTruncation;
+use mux::renderable::RenderableDimensions;
+use mux::tab::TabId;
+use mux::Mux;
+use termwiz::cell::unicode_column_width;
+use wezterm_term::{CellAttributes, Line};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MruKind {
+    Tab,
+    Pane,
+}
+
+pub struct MruCycle {
+    kind: MruKind,
+    /// The tab whose panes are being cycled
+    tab_id: TabId,
+    /// The ids of the tabs or panes, most recently used first
+    items: Vec<usize>,
+    titles: Vec<String>,
+    selected: usize,
+    /// The cycle ends when any of these modifiers is released
+    mods: Modifiers,
+}
+
+impl MruCycle {
+    /// Draws the list in a box over the center of the viewport `lines`
+    /// of a pane, highlighting the selected entry.  When the list is too
+    /// long to fit, it is scrolled to keep the selection in view.
+    pub fn draw(
+        &self,
+        lines: &mut [Line],
+        dims: &RenderableDimensions,
+        truncation: TabTitleTruncation,
+    ) {
+        if dims.cols < 6 || lines.len() < 3 {
+            return;
+        }
+        let inner_width = self
+            .titles
+            .iter()
+            .map(|title| unicode_column_width(title))
+            .max()
+            .unwrap_or(0)
+            .min(dims.cols - 4);
+        let visible = self.titles.len().min(lines.len() - 2);
+        let first = (self.selected + 1).saturating_sub(visible);
+        let left = (dims.cols - (inner_width + 4)) / 2;
+        let top = (lines.len() - (visible + 2)) / 2;
+
+        let plain = CellAttributes::default();
+        let mut highlight = CellAttributes::default();
+        highlight.set_reverse(true);
+
+        let border = "─".repeat(inner_width + 2);
+        lines[top].overlay_text_with_attribute(left, &format!("╭{}╮", border), plain.clone());
+        for (row, idx) in (first..first + visible).enumerate() {
+            let title = crate::tabbar::truncate_title(&self.titles[idx], inner_width, truncation);
+            let padding = " ".repeat(inner_width - unicode_column_width(&title));
+            let attrs = if idx == self.selected {
+                &highlight
+            } else {
+                &plain
+            };
+            let line = &mut lines[top + 1 + row];
+            line.overlay_text_with_attribute(left, "│ ", plain.clone());
+            line.overlay_text_with_attribute(
+                left + 2,
+                &format!("{}{}", title, padding),
+                attrs.clone(),
+            );
+            line.overlay_text_with_attribute(left + 2 + inner_width, " │", plain.clone());
+        }
+        lines[top + visible + 1].overlay_text_with_attribute(left, &format!("╰{}╯", border), plain);
+    }
+}
+
+/// Folds the left and right variants of the modifiers together and
+/// drops those that can't be released, such as LEADER
+fn normalize_modifiers(mods: Modifiers) -> Modifiers {
+    let mut mods = mods;
+    if mods.intersects(Modifiers::LEFT_ALT | Modifiers::RIGHT_ALT) {
+        mods |= Modifiers::ALT;
+    }
+    mods & (Modifiers::SHIFT | Modifiers::ALT | Modifiers::CTRL | Modifiers::SUPER)
+}
+
+/// Returns the modifiers whose release ends a cycle that was started
+/// while `mods` were held.  SHIFT is commonly used to reverse the
+/// direction, so it only counts if it is the only modifier.
+fn held_modifiers(mods: Modifiers) -> Modifiers {
+    let mods = normalize_modifiers(mods);
+    let without_shift = mods - Modifiers::SHIFT;
+    if without_shift.is_empty() {
+        mods
+    } else {
+        without_shift
+    }
+}
+
+/// Returns the modifier that corresponds to `key`
+fn modifier_for_key(key: &KeyCode) -> Modifiers {
+    match key {
+        KeyCode::Shift | KeyCode::LeftShift | KeyCode::RightShift => Modifiers::SHIFT,
+        KeyCode::Control | KeyCode::LeftControl | KeyCode::RightControl => Modifiers::CTRL,
+        KeyCode::Alt | KeyCode::LeftAlt | KeyCode::RightAlt | KeyCode::Meta => Modifiers::ALT,
+        KeyCode::Super | KeyCode::Hyper | KeyCode::LeftWindows | KeyCode::RightWindows => {
+            Modifiers::SUPER
+        }
+        _ => Modifiers::NONE,
+    }
+}
+
+impl super::TermWindow {
+    /// Activates the tab or pane that is `delta` steps away in the most
+    /// recently used list.  While `mods` are held, subsequent calls step
+    /// through the same list; if no modifiers are held the selection
+    /// is recorded immediately.
+    pub fn cycle_mru(&mut self, kind: MruKind, delta: isize, mods: Modifiers) {
+        let mut cycle = match self.mru_cycle.take() {
+            Some(cycle) if cycle.kind == kind => cycle,
+            prior => {
+                if let Some(prior) = prior {
+                    self.commit_mru_cycle(prior);
+                }
+                match self.start_mru_cycle(kind, mods) {
+                    Some(cycle) => cycle,
+                    None => return,
+                }
+            }
+        };
+
+        let len = cycle.items.len() as isize;
+        cycle.selected = (cycle.selected as isize + delta).rem_euclid(len) as usize;
+        if let Err(err) = self.activate_mru_item(&cycle) {
+            log::error!("Unable to activate the most recently used item: {:#}", err);
+        }
+
+        if cycle.mods.is_empty() {
+            self.commit_mru_cycle(cycle);
+        } else {
+            self.mru_cycle.replace(cycle);
+        }
+        if let Some(window) = self.window.as_ref() {
+            window.invalidate();
+        }
+    }
+
+    fn start_mru_cycle(&self, kind: MruKind, mods: Modifiers) -> Option<MruCycle> {
+        let mux = Mux::get().unwrap();
+        let tab = mux.get_active_tab_for_window(self.mux_window_id)?;
+
+        let (items, titles): (Vec<usize>, Vec<String>) = match kind {
+            MruKind::Tab => {
+                let window = mux.get_window(self.mux_window_id)?;
+                window
+                    .iter_by_recent_use()
+                    .into_iter()
+                    .filter_map(|idx| window.get_by_idx(idx))
+                    .map(|tab| {
+                        let title = tab
+                            .get_active_pane()
+                            .map(|pane| pane.get_title())
+                            .unwrap_or_default();
+                        (tab.tab_id(), title)
+                    })
+                    .unzip()
+            }
+            MruKind::Pane => {
+                if self.tab_state(tab.tab_id()).overlay.is_some() {
+                    return None;
+                }
+                tab.iter_panes_by_recent_use()
+                    .into_iter()
+                    .map(|pos| (pos.pane.pane_id(), pos.pane.get_title()))
+                    .unzip()
+            }
+        };
+
+        if items.len() < 2 {
+            return None;
+        }
+        Some(MruCycle {
+            kind,
+            tab_id: tab.tab_id(),
+            items,
+            titles,
+            selected: 0,
+            mods: held_modifiers(mods),
+        })
+    }
+
+    fn activate_mru_item(&mut self, cycle: &MruCycle) -> anyhow::Result<()> {
+        let mux = Mux::get().unwrap();
+        let id = cycle.items[cycle.selected];
+        match cycle.kind {
+            MruKind::Tab => {
+                let tab_idx = mux
+                    .get_window(self.mux_window_id)
+                    .and_then(|window| window.idx_by_id(id))
+                    .ok_or_else(|| anyhow!("tab {} is no longer in this window", id))?;
+                self.switch_to_tab(tab_idx, false)
+            }
+            MruKind::Pane => {
+                let tab = mux
+                    .get_tab(cycle.tab_id)
+                    .ok_or_else(|| anyhow!("no such tab {}", cycle.tab_id))?;
+                let pos = tab
+                    .iter_panes()
+                    .into_iter()
+                    .find(|pos| pos.pane.pane_id() == id)
+                    .ok_or_else(|| anyhow!("pane {} is no longer in this tab", id))?;
+                tab.set_active_idx_without_saving(pos.index);
+                Ok(())
+            }
+        }
+    }
+
+    /// Records the entry that was active when the cycle started as the
+    /// most recently used one, so that the selected entry now follows
+    /// the one it was selected from
+    fn commit_mru_cycle(&mut self, cycle: MruCycle) {
+        if cycle.selected == 0 {
+            return;
+        }
+        let mux = Mux::get().unwrap();
+        let original = cycle.items[0];
+        match cycle.kind {
+            MruKind::Tab => {
+                if let Some(mut window) = mux.get_window_mut(self.mux_window_id) {
+                    window.mark_used(original);
+                }
+            }
+            MruKind::Pane => {
+                if let Some(tab) = mux.get_tab(cycle.tab_id) {
+                    tab.mark_used(original);
+                }
+            }
+        }
+    }
+
+    /// Ends the current cycle, if any
+    pub fn end_mru_cycle(&mut self) {
+        if let Some(cycle) = self.mru_cycle.take() {
+            self.commit_mru_cycle(cycle);
+            if let Some(window) = self.window.as_ref() {
+                window.invalidate();
+            }
+        }
+    }
+
+    /// Called for each key event while a cycle is in progress.
+    /// Releasing one of the modifiers that started the cycle ends it.
+    /// Not every system reports the release of a modifier key, so a
+    /// key press without those modifiers also ends it.
+    pub fn mru_key_event(&mut self, window_key: &KeyEvent) {
+        let cycle = match self.mru_cycle.as_ref() {
+            Some(cycle) => cycle,
+            None => return,
+        };
+        let ended = if window_key.key_is_down {
+            !window_key.key.is_modifier()
+                && !normalize_modifiers(window_key.modifiers).contains(cycle.mods)
+        } else {
+            cycle.mods.intersects(modifier_for_key(&window_key.key))
+        };
+        if ended {
+            self.end_mru_cycle();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn held() {
+        assert_eq!(held_modifiers(Modifiers::NONE), Modifiers::NONE);
+        assert_eq!(held_modifiers(Modifiers::CTRL), Modifiers::CTRL);
+        assert_eq!(
+            held_modifiers(Modifiers::CTRL | Modifiers::SHIFT),
+            Modifiers::CTRL
+        );
+        assert_eq!(held_modifiers(Modifiers::SHIFT), Modifiers::SHIFT);
+        assert_eq!(
+            held_modifiers(Modifiers::LEFT_ALT | Modifiers::LEADER),
+            Modifiers::ALT
+        );
+        assert!(held_modifiers(Modifiers::ALT).intersects(modifier_for_key(&KeyCode::RightAlt)));
+    }
+}
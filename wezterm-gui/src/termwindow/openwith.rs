@@ -0,0 +1,365 @@
+use crate::frontend::front_end;
+use crate::termwindow::spawn::SpawnWhere;
+use ::window::WindowOps;
+use config::keyassignment::{
+    OpenWithCommand, OpenWithSource, OpenWithTarget, SpawnCommand, SpawnTabDomain,
+};
+use config::{UriHandler, UriHandlerAction};
+use lazy_static::lazy_static;
+use mux::pane::{Pane, PaneId};
+use mux::Mux;
+use regex::Regex;
+use std::path::PathBuf;
+use std::rc::Rc;
+use url::Url;
+
+lazy_static! {
+    /// Recognizers for locations emitted by common tools.
+    /// Each has a `file` capture and optional `line` and `column` captures.
+    /// They are tried in order and the first match wins.
+    static ref RECOGNIZERS: Vec<Regex> = [
+        // Python tracebacks: `File "foo.py", line 12`
+        r#"^\s*File "(?P<file>[^"]+)", line (?P<line>\d+)"#,
+        // rustc diagnostics: `--> src/main.rs:12:5`
+        r"^\s*--> (?P<file>.+?):(?P<line>\d+):(?P<column>\d+)",
+        // MSVC and C#: `foo.cpp(12,5)` or `foo.cpp(12)`
+        r"^(?P<file>(?:[A-Za-z]:)?[^:()]+)\((?P<line>\d+)(?:,(?P<column>\d+))?\)",
+        // gcc, clang, go, rustc --message-format=short, grep -n:
+        // `foo.c:12:5` or `foo.c:12`
+        r"^(?P<file>(?:[A-Za-z]:)?[^:\s][^:]*?):(?P<line>\d+)(?::(?P<column>\d+))?",
+    ]
+    .iter()
+    .map(|re| Regex::new(re).expect("builtin recognizer regex to be valid"))
+    .collect();
+}
+
+/// The components of the text being opened, used to expand
+/// the `OpenWith` argument template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenWithLocation {
+    pub url: String,
+    pub file: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+/// Break down `text` into file/line/column using the built-in recognizers.
+/// If none of them match, the whole text is treated as the file.
+pub fn recognize(text: &str) -> OpenWithLocation {
+    let text = text.trim();
+
+    if let Ok(url) = Url::parse(text) {
+        if url.scheme() == "file" {
+            if let Ok(path) = url.to_file_path() {
+                return OpenWithLocation {
+                    url: text.to_string(),
+                    file: path.to_string_lossy().to_string(),
+                    line: None,
+                    column: None,
+                };
+            }
+        }
+    }
+
+    for re in RECOGNIZERS.iter() {
+        if let Some(caps) = re.captures(text) {
+            let number = |name: &str| caps.name(name).and_then(|m| m.as_str().parse().ok());
+            return OpenWithLocation {
+                url: text.to_string(),
+                file: caps["file"].to_string(),
+                line: number("line"),
+                column: number("column"),
+            };
+        }
+    }
+
+    OpenWithLocation {
+        url: text.to_string(),
+        file: text.to_string(),
+        line: None,
+        column: None,
+    }
+}
+
+/// Expand the argument template for the recognized target.
+pub fn expand_args(template: &[String], target: &OpenWithLocation) -> anyhow::Result<Vec<String>> {
+    let line = target.line.unwrap_or(1).to_string();
+    let column = target.column.unwrap_or(1).to_string();
+
+    let mut args = vec![];
+    for arg in template {
+        if let Some(name) = arg.strip_prefix('$') {
+            let value = std::env::var(name)
+                .map_err(|_| anyhow::anyhow!("environment variable {} is not set", name))?;
+            args.extend(value.split_whitespace().map(str::to_string));
+            continue;
+        }
+        args.push(
+            arg.replace("{url}", &target.url)
+                .replace("{file}", &target.file)
+                .replace("{line}", &line)
+                .replace("{column}", &column),
+        );
+    }
+    Ok(args)
+}
+
+/// Returns the first of `handlers` that matches `uri`
+fn find_uri_handler<'a>(handlers: &'a [UriHandler], uri: &str) -> Option<&'a UriHandler> {
+    let scheme = Url::parse(uri).ok().map(|url| url.scheme().to_string());
+    handlers.iter().find(|handler| {
+        if let Some(want) = handler.scheme.as_ref() {
+            match scheme.as_ref() {
+                Some(scheme) if scheme.eq_ignore_ascii_case(want) => {}
+                _ => return false,
+            }
+        }
+        if let Some(re) = handler.regex.as_ref() {
+            match Regex::new(re) {
+                Ok(re) if re.is_match(uri) => {}
+                Ok(_) => return false,
+                Err(err) => {
+                    log::error!("uri_handlers: invalid regex {}: {:#}", re, err);
+                    return false;
+                }
+            }
+        }
+        true
+    })
+}
+
+/// Extracts the pane id from a link such as `pane://3`
+fn pane_id_from_uri(uri: &str) -> Option<PaneId> {
+    Url::parse(uri).ok()?.host_str()?.parse().ok()
+}
+
+impl super::TermWindow {
+    pub fn open_with(&mut self, pane: &Rc<dyn Pane>, command: &OpenWithCommand) {
+        let text = match command.source {
+            OpenWithSource::LinkAtMouseCursor => match self.current_highlight.as_ref() {
+                Some(link) => link.uri().to_string(),
+                None => return,
+            },
+            OpenWithSource::Selection => self.selection_text(pane),
+        };
+        if text.trim().is_empty() {
+            return;
+        }
+        self.run_open_with(pane, &text, &command.args, command.target);
+    }
+
+    /// Expands the `args` template for `text` and runs the resulting
+    /// command in the specified `target`
+    fn run_open_with(
+        &mut self,
+        pane: &Rc<dyn Pane>,
+        text: &str,
+        args: &[String],
+        target: OpenWithTarget,
+    ) {
+        let location = recognize(text);
+        let args = match expand_args(args, &location) {
+            Ok(args) if !args.is_empty() => args,
+            Ok(_) => {
+                log::error!("OpenWith: empty command line");
+                return;
+            }
+            Err(err) => {
+                log::error!("OpenWith: {:#}", err);
+                return;
+            }
+        };
+
+        // Relative paths are relative to the working directory of the pane
+        let cwd = pane
+            .get_current_working_dir()
+            .and_then(|url| url.to_file_path().ok());
+
+        match target {
+            OpenWithTarget::Background => {
+                // Spawn outside of the window loop; see the commentary in
+                // the OpenLinkAtMouseCursor handler.
+                promise::spawn::spawn(async move {
+                    log::info!("OpenWith: {:?}", args);
+                    if let Err(err) = run_in_background(&args, cwd) {
+                        log::error!("OpenWith: failed to run {:?}: {:#}", args, err);
+                    }
+                })
+                .detach();
+            }
+            OpenWithTarget::NewTab | OpenWithTarget::NewWindow => {
+                let spawn = SpawnCommand {
+                    args: Some(args),
+                    cwd,
+                    domain: SpawnTabDomain::CurrentPaneDomain,
+                    ..Default::default()
+                };
+                let spawn_where = if target == OpenWithTarget::NewTab {
+                    SpawnWhere::NewTab
+                } else {
+                    SpawnWhere::NewWindow
+                };
+                self.spawn_command(&spawn, spawn_where);
+            }
+        }
+    }
+
+    /// Passes `uri` to the first matching entry in the `uri_handlers`
+    /// configuration.  Returns false if there is no matching handler,
+    /// or it specifies that the platform opener be used.
+    pub fn open_uri_with_handler(&mut self, pane: &Rc<dyn Pane>, uri: &str) -> bool {
+        let config = self.config.clone();
+        let handler = match find_uri_handler(&config.uri_handlers, uri) {
+            Some(handler) => handler,
+            None => return false,
+        };
+        match &handler.action {
+            UriHandlerAction::Default => false,
+            UriHandlerAction::Run { args, target } => {
+                self.run_open_with(pane, uri, args, *target);
+                true
+            }
+            UriHandlerAction::ActivatePane => {
+                match pane_id_from_uri(uri) {
+                    Some(pane_id) => self.activate_pane_by_id(pane_id),
+                    None => log::error!("uri_handlers: no pane id in {}", uri),
+                }
+                true
+            }
+        }
+    }
+
+    /// Activates the tab and pane with the specified id, and focuses
+    /// the window that contains it
+    fn activate_pane_by_id(&mut self, pane_id: PaneId) {
+        let mux = Mux::get().unwrap();
+        let (pane, (_domain_id, window_id, tab_id)) =
+            match (mux.get_pane(pane_id), mux.resolve_pane_id(pane_id)) {
+                (Some(pane), Some(ids)) => (pane, ids),
+                _ => {
+                    log::error!("uri_handlers: pane {} doesn't exist", pane_id);
+                    return;
+                }
+            };
+
+        if let Some(mut window) = mux.get_window_mut(window_id) {
+            if let Some(idx) = window.idx_by_id(tab_id) {
+                window.save_and_then_set_active(idx);
+            }
+        }
+        if let Some(tab) = mux.get_tab(tab_id) {
+            tab.set_active_pane(&pane);
+        }
+
+        if window_id == self.mux_window_id {
+            if let Some(window) = self.window.as_ref() {
+                window.invalidate();
+            }
+        } else if let Some(fe) = front_end() {
+            for (mux_window_id, window) in fe.gui_windows() {
+                if mux_window_id == window_id {
+                    window.focus();
+                    window.invalidate();
+                }
+            }
+        }
+    }
+}
+
+fn run_in_background(args: &[String], cwd: Option<PathBuf>) -> anyhow::Result<()> {
+    let mut cmd = std::process::Command::new(&args[0]);
+    cmd.args(&args[1..]);
+    if let Some(cwd) = cwd {
+        cmd.current_dir(cwd);
+    }
+    let mut child = cmd.spawn()?;
+    let args = args.to_vec();
+    // Wait for the child on another thread, so that it doesn't
+    // linger as a zombie once it exits
+    std::thread::spawn(move || match child.wait() {
+        Ok(status) if !status.success() => {
+            log::warn!("{:?} exited with {}", args, status);
+        }
+        Ok(_) => {}
+        Err(err) => log::error!("waiting for {:?}: {:#}", args, err),
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn rec(text: &str) -> (String, Option<usize>, Option<usize>) {
+        let loc = recognize(text);
+        (loc.file, loc.line, loc.column)
+    }
+
+    #[test]
+    fn recognizers() {
+        assert_eq!(
+            rec("src/main.rs:12:5: error"),
+            ("src/main.rs".to_string(), Some(12), Some(5))
+        );
+        assert_eq!(rec("foo.c:3"), ("foo.c".to_string(), Some(3), None));
+        assert_eq!(
+            rec("  --> src/lib.rs:10:2"),
+            ("src/lib.rs".to_string(), Some(10), Some(2))
+        );
+        assert_eq!(
+            rec(r#"  File "/tmp/x.py", line 7, in <module>"#),
+            ("/tmp/x.py".to_string(), Some(7), None)
+        );
+        assert_eq!(
+            rec(r"C:\src\foo.cpp(12,5): error C2065"),
+            (r"C:\src\foo.cpp".to_string(), Some(12), Some(5))
+        );
+        assert_eq!(
+            rec(r"C:\src\foo.cpp:12"),
+            (r"C:\src\foo.cpp".to_string(), Some(12), None)
+        );
+        assert_eq!(rec("README.md"), ("README.md".to_string(), None, None));
+    }
+
+    #[test]
+    fn expand() {
+        let loc = recognize("foo.rs:4:2");
+        let args = expand_args(
+            &[
+                "vim".to_string(),
+                "+{line}".to_string(),
+                "{file}".to_string(),
+            ],
+            &loc,
+        )
+        .unwrap();
+        assert_eq!(args, vec!["vim", "+4", "foo.rs"]);
+    }
+
+    #[test]
+    fn uri_handlers() {
+        let handler = |scheme: Option<&str>, regex: Option<&str>| UriHandler {
+            scheme: scheme.map(str::to_string),
+            regex: regex.map(str::to_string),
+            action: UriHandlerAction::ActivatePane,
+        };
+        let handlers = vec![
+            handler(Some("mailto"), None),
+            handler(Some("https"), Some(r"^https://github\.com/")),
+            handler(None, Some(r"\.pdf$")),
+        ];
+        let find = |uri: &str| {
+            find_uri_handler(&handlers, uri)
+                .and_then(|found| handlers.iter().position(|h| std::ptr::eq(h, found)))
+        };
+
+        assert_eq!(find("MAILTO:someone@example.com"), Some(0));
+        assert_eq!(find("https://github.com/wez/wezterm"), Some(1));
+        assert_eq!(find("https://example.com/"), None);
+        assert_eq!(find("file:///tmp/doc.pdf"), Some(2));
+        assert_eq!(find("https://example.com/doc.pdf"), Some(2));
+
+        assert_eq!(pane_id_from_uri("pane://3"), Some(3));
+        assert_eq!(pane_id_from_uri("pane://bogus"), None);
+    }
+}
@@ -0,0 +1,177 @@
+//! PaneSelect labels each pane in the active tab with a large
+//! character, and then waits for one of those labels to be typed
+//! in order to activate, or swap with, the corresponding pane.
+use ::window::{KeyCode, KeyEvent, WindowOps};
+use config::keyassignment::{PaneSelectArguments, PaneSelectMode};
+use mux::pane::PaneId;
+use mux::renderable::RenderableDimensions;
+use mux::Mux;
+use wezterm_term::{Cell, CellAttributes, Line};
+
+/// Bitmaps for the large labels; each glyph is 3 pixels wide
+/// and 5 pixels tall.  A pixel is drawn as a pair of cells so
+/// that the glyph looks roughly square on the screen.
+const GLYPH_ROWS: usize = 5;
+const GLYPH_COLS: usize = 3;
+const CELLS_PER_PIXEL: usize = 2;
+
+fn glyph_for_char(c: char) -> Option<[&'static str; GLYPH_ROWS]> {
+    Some(match c.to_ascii_uppercase() {
+        '0' => ["###", "#.#", "#.#", "#.#", "###"],
+        '1' => [".#.", "##.", ".#.", ".#.", "###"],
+        '2' => ["###", "..#", "###", "#..", "###"],
+        '3' => ["###", "..#", "###", "..#", "###"],
+        '4' => ["#.#", "#.#", "###", "..#", "..#"],
+        '5' => ["###", "#..", "###", "..#", "###"],
+        '6' => ["###", "#..", "###", "#.#", "###"],
+        '7' => ["###", "..#", "..#", "..#", "..#"],
+        '8' => ["###", "#.#", "###", "#.#", "###"],
+        '9' => ["###", "#.#", "###", "..#", "###"],
+        'A' => ["###", "#.#", "###", "#.#", "#.#"],
+        'B' => ["##.", "#.#", "##.", "#.#", "##."],
+        'C' => ["###", "#..", "#..", "#..", "###"],
+        'D' => ["##.", "#.#", "#.#", "#.#", "##."],
+        'E' => ["###", "#..", "###", "#..", "###"],
+        'F' => ["###", "#..", "###", "#..", "#.."],
+        'G' => ["###", "#..", "#.#", "#.#", "###"],
+        'H' => ["#.#", "#.#", "###", "#.#", "#.#"],
+        'I' => ["###", ".#.", ".#.", ".#.", "###"],
+        'J' => ["..#", "..#", "..#", "#.#", "###"],
+        'K' => ["#.#", "#.#", "##.", "#.#", "#.#"],
+        'L' => ["#..", "#..", "#..", "#..", "###"],
+        'M' => ["#.#", "###", "###", "#.#", "#.#"],
+        'N' => ["##.", "#.#", "#.#", "#.#", "#.#"],
+        'O' => [".#.", "#.#", "#.#", "#.#", ".#."],
+        'P' => ["###", "#.#", "###", "#..", "#.."],
+        'Q' => ["###", "#.#", "#.#", "###", "..#"],
+        'R' => ["##.", "#.#", "##.", "#.#", "#.#"],
+        'S' => [".##", "#..", ".#.", "..#", "##."],
+        'T' => ["###", ".#.", ".#.", ".#.", ".#."],
+        'U' => ["#.#", "#.#", "#.#", "#.#", "###"],
+        'V' => ["#.#", "#.#", "#.#", "#.#", ".#."],
+        'W' => ["#.#", "#.#", "###", "###", "#.#"],
+        'X' => ["#.#", "#.#", ".#.", "#.#", "#.#"],
+        'Y' => ["#.#", "#.#", ".#.", ".#.", ".#."],
+        'Z' => ["###", "..#", ".#.", "#..", "###"],
+        _ => return None,
+    })
+}
+
+/// Draws `label` over the center of the viewport `lines` of a pane.
+/// The label is drawn using large block glyphs where it will fit,
+/// falling back to a single highlighted cell otherwise.
+pub fn draw_label(label: char, lines: &mut [Line], dims: &RenderableDimensions) {
+    let mut attrs = CellAttributes::default();
+    attrs.set_reverse(true);
+
+    let glyph_width = GLYPH_COLS * CELLS_PER_PIXEL;
+    match glyph_for_char(label) {
+        Some(glyph) if dims.cols >= glyph_width + 2 && lines.len() >= GLYPH_ROWS + 2 => {
+            let left = (dims.cols - glyph_width) / 2;
+            let top = (lines.len() - GLYPH_ROWS) / 2;
+
+            for (row, bits) in glyph.iter().enumerate() {
+                let line = &mut lines[top + row];
+                for (col, bit) in bits.chars().enumerate() {
+                    if bit != '#' {
+                        continue;
+                    }
+                    for i in 0..CELLS_PER_PIXEL {
+                        line.set_cell(
+                            left + col * CELLS_PER_PIXEL + i,
+                            Cell::new(' ', attrs.clone()),
+                        );
+                    }
+                }
+            }
+        }
+        _ => {
+            if let Some(line) = lines.get_mut(lines.len() / 2) {
+                line.set_cell(dims.cols / 2, Cell::new(label, attrs));
+            }
+        }
+    }
+}
+
+/// Tracks the labels assigned to the panes while PaneSelect is active
+pub struct PaneSelectState {
+    mode: PaneSelectMode,
+    /// The label for each pane, along with its index in the tab
+    labels: Vec<(char, usize, PaneId)>,
+}
+
+impl PaneSelectState {
+    pub fn label_for_pane(&self, pane_id: PaneId) -> Option<char> {
+        self.labels
+            .iter()
+            .find(|(_, _, id)| *id == pane_id)
+            .map(|(label, _, _)| *label)
+    }
+
+    fn index_for_label(&self, c: char) -> Option<usize> {
+        self.labels
+            .iter()
+            .find(|(label, _, _)| label.eq_ignore_ascii_case(&c))
+            .map(|(_, index, _)| *index)
+    }
+}
+
+impl super::TermWindow {
+    pub fn show_pane_select(&mut self, args: &PaneSelectArguments) {
+        let mux = Mux::get().unwrap();
+        let tab = match mux.get_active_tab_for_window(self.mux_window_id) {
+            Some(tab) => tab,
+            None => return,
+        };
+
+        let labels = args
+            .alphabet
+            .chars()
+            .zip(tab.iter_panes())
+            .map(|(label, pos)| (label, pos.index, pos.pane.pane_id()))
+            .collect();
+
+        self.pane_select.replace(PaneSelectState {
+            mode: args.mode,
+            labels,
+        });
+        if let Some(window) = self.window.as_ref() {
+            window.invalidate();
+        }
+    }
+
+    /// Called while PaneSelect is active to process a key press.
+    /// Typing a label selects the corresponding pane; any other
+    /// key cancels the selection.
+    pub fn pane_select_key_event(&mut self, window_key: &KeyEvent, context: &dyn WindowOps) {
+        if window_key.key.is_modifier() {
+            return;
+        }
+
+        let state = match self.pane_select.take() {
+            Some(state) => state,
+            None => return,
+        };
+        context.invalidate();
+
+        let pane_index = match &window_key.key {
+            KeyCode::Char(c) => match state.index_for_label(*c) {
+                Some(index) => index,
+                None => return,
+            },
+            _ => return,
+        };
+
+        let mux = Mux::get().unwrap();
+        let tab = match mux.get_active_tab_for_window(self.mux_window_id) {
+            Some(tab) => tab,
+            None => return,
+        };
+        match state.mode {
+            PaneSelectMode::Activate => tab.set_active_idx(pane_index),
+            PaneSelectMode::SwapWithActive => {
+                tab.swap_active_with_index(pane_index);
+            }
+        }
+    }
+}
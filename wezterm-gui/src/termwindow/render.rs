@@ -1,6 +1,10 @@
 use crate::glium::texture::SrgbTexture2d;
 use crate::glyphcache::{BlockKey, CachedGlyph, GlyphCache};
+use crate::quad::{CURSOR_TRAIL_QUADS, EFFECT_QUADS};
 use crate::shapecache::*;
+use crate::termwindow::bidi::BidiRow;
+use crate::termwindow::cursoranim::{AnimatedCursor, CellPoint};
+use crate::termwindow::effects::EffectTarget;
 use crate::termwindow::{BorrowedShapeCacheKey, MappedQuads, RenderState, ScrollHit, ShapedInfo};
 use ::window::bitmaps::atlas::OutOfTextureSpace;
 use ::window::bitmaps::{TextureCoord, TextureRect, TextureSize};
@@ -9,23 +13,24 @@ use ::window::glium::uniforms::{
     MagnifySamplerFilter, MinifySamplerFilter, Sampler, SamplerWrapFunction,
 };
 use ::window::glium::{uniform, BlendingFunction, LinearBlendingFactor, Surface};
-use ::window::WindowOps;
+use ::window::{MouseEvent, MouseEventKind, Rect, Size, WindowOps};
 use anyhow::anyhow;
 use config::ConfigHandle;
 use config::TextStyle;
 use mux::pane::Pane;
 use mux::renderable::{RenderableDimensions, StableCursorPosition};
 use mux::tab::{PositionedPane, PositionedSplit, SplitDirection};
+use std::convert::TryFrom;
 use std::ops::Range;
 use std::rc::Rc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use termwiz::cellcluster::CellCluster;
 use termwiz::surface::{CursorShape, CursorVisibility};
 use wezterm_font::units::PixelLength;
-use wezterm_font::{ClearShapeCache, GlyphInfo};
+use wezterm_font::{ClearShapeCache, GlyphInfo, MISSING_GLYPH};
 use wezterm_term::color::{ColorAttribute, ColorPalette, RgbColor};
-use wezterm_term::{CellAttributes, Line, StableRowIndex};
-use window::bitmaps::atlas::SpriteSlice;
+use wezterm_term::{Cell, CellAttributes, Line, StableRowIndex, VerticalAlign};
+use window::bitmaps::atlas::{Sprite, SpriteSlice};
 use window::bitmaps::Texture2d;
 use window::color::LinearRgba;
 
@@ -44,10 +49,27 @@ pub struct RenderScreenLineOpenGLParams<'a> {
     pub foreground: LinearRgba,
     pub is_active: bool,
 
-    pub selection_fg: LinearRgba,
+    /// When None, selected text keeps its own color
+    pub selection_fg: Option<LinearRgba>,
     pub selection_bg: LinearRgba,
     pub cursor_fg: LinearRgba,
     pub cursor_bg: LinearRgba,
+
+    /// The display order of the cells when bidi is enabled and
+    /// the line isn't displayed in logical order
+    pub bidi: Option<&'a BidiRow>,
+}
+
+impl<'a> RenderScreenLineOpenGLParams<'a> {
+    /// Returns the quad column used to display the cell with the
+    /// logical index `cell_idx`
+    fn quad_col(&self, cell_idx: usize) -> usize {
+        let col = match self.bidi {
+            Some(bidi) => bidi.visual_col(cell_idx),
+            None => cell_idx,
+        };
+        col + self.pos.left
+    }
 }
 
 pub struct ComputeCellFgBgParams<'a> {
@@ -60,16 +82,22 @@ pub struct ComputeCellFgBgParams<'a> {
     pub palette: &'a ColorPalette,
     pub is_active_pane: bool,
     pub config: &'a ConfigHandle,
-    pub selection_fg: LinearRgba,
+    pub selection_fg: Option<LinearRgba>,
     pub selection_bg: LinearRgba,
     pub cursor_fg: LinearRgba,
     pub cursor_bg: LinearRgba,
+    pub cursor_border_color: LinearRgba,
+    /// Draw the cursor by swapping the colors of the cell beneath it,
+    /// rather than in the cursor colors
+    pub reverse_video_cursor: bool,
 }
 
 pub struct ComputeCellFgBgResult {
-    pub fg_color: LinearRgba,
+    /// The color to use in place of the color of the text, if any
+    pub fg_color: Option<LinearRgba>,
     pub bg_color: LinearRgba,
     pub cursor_shape: Option<CursorShape>,
+    pub cursor_border_color: LinearRgba,
 }
 
 impl super::TermWindow {
@@ -77,18 +105,15 @@ impl super::TermWindow {
         // If nothing on screen needs animating, then we can avoid
         // invalidating as frequently
         *self.has_animation.borrow_mut() = None;
+        self.last_paint = Instant::now();
+        self.paint_deferred = false;
+
+        self.apply_scroll_momentum();
 
         self.check_for_config_reload();
         let start = Instant::now();
 
-        {
-            let background_alpha = (self.config.window_background_opacity * 255.0) as u8;
-            let palette = self.palette();
-            let background = rgbcolor_alpha_to_window_color(palette.background, background_alpha);
-
-            let (r, g, b, a) = background.tuple();
-            frame.clear_color(r, g, b, a);
-        }
+        self.clear_to_background(frame);
 
         for pass in 0.. {
             match self.paint_opengl_pass() {
@@ -128,12 +153,23 @@ impl super::TermWindow {
         }
         log::debug!("paint_impl before call_draw elapsed={:?}", start.elapsed());
 
+        self.save_pending_capture();
         self.call_draw(frame).ok();
         log::debug!("paint_impl elapsed={:?}", start.elapsed());
         metrics::histogram!("gui.paint.opengl", start.elapsed());
         self.update_title_post_status();
     }
 
+    /// Fills `surface` with the window background color
+    pub fn clear_to_background<S: Surface>(&mut self, surface: &mut S) {
+        let background_alpha = (self.config.window_background_opacity * 255.0) as u8;
+        let palette = self.palette();
+        let background = rgbcolor_alpha_to_window_color(palette.background, background_alpha);
+
+        let (r, g, b, a) = background.tuple();
+        surface.clear_color(r, g, b, a);
+    }
+
     fn update_next_frame_time(&self, next_due: Option<Instant>) {
         if let Some(next_due) = next_due {
             let mut has_anim = self.has_animation.borrow_mut();
@@ -149,6 +185,133 @@ impl super::TermWindow {
         }
     }
 
+    /// When the viewport of a pane is scrolled back, draw a badge in the
+    /// top right corner of the pane showing how many lines are below it.
+    /// The badge is highlighted when new output arrives below the viewport,
+    /// and the highlight fades out over the configured flash duration.
+    fn render_scroll_position_indicator(
+        &self,
+        pos: &PositionedPane,
+        viewport: Option<StableRowIndex>,
+        dims: &RenderableDimensions,
+        palette: &ColorPalette,
+        bidi: Option<&BidiRow>,
+        lines: &mut [Line],
+    ) {
+        let now = Instant::now();
+        let flash_started = {
+            let mut state = self.pane_state(pos.pane.pane_id());
+            let new_output = state
+                .last_physical_top
+                .map(|top| top != dims.physical_top)
+                .unwrap_or(false);
+            state.last_physical_top.replace(dims.physical_top);
+
+            match viewport {
+                Some(top) if top < dims.physical_top => {}
+                _ => {
+                    state.scroll_indicator_flash.take();
+                    return;
+                }
+            }
+            if new_output {
+                state.scroll_indicator_flash.replace(now);
+            }
+            state.scroll_indicator_flash
+        };
+
+        if !self.config.show_scroll_position_indicator || lines.is_empty() {
+            return;
+        }
+
+        let lines_below = dims.physical_top - viewport.unwrap_or(dims.physical_top);
+        let text = scroll_position_text(lines_below);
+        let width = text.chars().count();
+        if width >= dims.cols {
+            return;
+        }
+
+        // 1.0 just as new output arrives, fading to 0.0 once the
+        // flash duration has elapsed
+        let duration = Duration::from_millis(self.config.scroll_position_indicator_flash_duration);
+        let highlight = match flash_started {
+            Some(started) if duration.as_millis() > 0 => {
+                let elapsed = now.duration_since(started);
+                if elapsed < duration {
+                    1.0 - (elapsed.as_secs_f32() / duration.as_secs_f32())
+                } else {
+                    0.0
+                }
+            }
+            _ => 0.0,
+        };
+        if highlight > 0.0 {
+            // Keep repainting until the highlight has faded
+            self.update_next_frame_time(Some(now + Duration::from_millis(50)));
+        }
+
+        let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * highlight).round() as u8;
+        let base = palette.foreground;
+        let flash = palette.cursor_bg;
+        let bg = RgbColor::new(
+            mix(base.red, flash.red),
+            mix(base.green, flash.green),
+            mix(base.blue, flash.blue),
+        );
+
+        let mut attrs = CellAttributes::default();
+        attrs
+            .set_foreground(ColorAttribute::TrueColorWithDefaultFallback(
+                palette.background,
+            ))
+            .set_background(ColorAttribute::TrueColorWithDefaultFallback(bg));
+
+        overlay_badge(&mut lines[0], &text, dims.cols - width, bidi, &attrs);
+    }
+
+    /// Draws a badge in the bottom right corner of panes that can only
+    /// be viewed, so that it is clear why typing into them does nothing,
+    /// and of shared panes that another client has most recently typed
+    /// into, so that it is clear who is driving
+    fn render_pane_badge(
+        &self,
+        pos: &PositionedPane,
+        dims: &RenderableDimensions,
+        palette: &ColorPalette,
+        bidi: Option<&BidiRow>,
+        lines: &mut [Line],
+    ) {
+        let mut parts = vec![];
+        if pos.pane.is_read_only() {
+            parts.push("VIEWER".to_string());
+        }
+        if let Some(collaborator) = pos.pane.collaborator() {
+            parts.push(format!("\u{270e} {}", collaborator));
+        }
+        if parts.is_empty() {
+            return;
+        }
+        let text: String = format!(" {} ", parts.join(" \u{b7} "))
+            .chars()
+            .take(dims.cols.saturating_sub(1))
+            .collect();
+        let width = text.chars().count();
+        let line = match lines.last_mut() {
+            Some(line) if width > 0 => line,
+            _ => return,
+        };
+
+        let mut attrs = CellAttributes::default();
+        attrs
+            .set_foreground(ColorAttribute::TrueColorWithDefaultFallback(
+                palette.background,
+            ))
+            .set_background(ColorAttribute::TrueColorWithDefaultFallback(
+                palette.cursor_bg,
+            ));
+        overlay_badge(line, text, dims.cols - width, bidi, &attrs);
+    }
+
     pub fn paint_pane_opengl(&mut self, pos: &PositionedPane) -> anyhow::Result<()> {
         // We typically check this periodically in the background as part
         // of deciding whether to repaint, but there are some situations
@@ -158,20 +321,25 @@ impl super::TermWindow {
         // not clear the selection when we should.
         self.check_for_dirty_lines_and_invalidate_selection(pos);
 
+        let palette = self.pane_palette(&*pos.pane);
         let config = &self.config;
-        let palette = pos.pane.palette();
 
         let background_color = palette.resolve_bg(wezterm_term::color::ColorAttribute::Default);
-        let first_line_offset = if self.show_tab_bar { 1 } else { 0 };
+        let first_line_offset = self.rows_above_panes();
 
         let cursor = pos.pane.get_cursor_position();
         if pos.is_active {
             self.prev_cursor.update(&cursor);
         }
 
-        let current_viewport = self.get_viewport(pos.pane.pane_id());
-        let (stable_top, lines);
         let dims = pos.pane.get_dimensions();
+        let (current_viewport, scroll_offset) =
+            self.get_displayed_viewport_with_offset(pos.pane.pane_id(), &dims);
+        if self.is_scroll_animating(pos.pane.pane_id()) {
+            // Keep painting frames until the viewport comes to rest
+            self.update_next_frame_time(Some(Instant::now() + Duration::from_millis(16)));
+        }
+        let (stable_top, mut lines);
 
         {
             let stable_range = match current_viewport {
@@ -186,6 +354,114 @@ impl super::TermWindow {
             lines = vp_lines;
         }
 
+        let bidi_rows = self.bidi_rows(
+            &pos.pane,
+            stable_top..stable_top + lines.len() as StableRowIndex,
+            dims.cols,
+        );
+        for (line, bidi) in lines.iter_mut().zip(bidi_rows.iter()) {
+            if let Some(bidi) = bidi {
+                bidi.mirror_line(line);
+            }
+        }
+
+        // While the viewport is scrolled part way through a row, the
+        // line below the viewport is partially visible at the bottom.
+        // It is drawn in a spare row of quads that this pane has to
+        // itself for the rest of the frame.
+        let mut spare_line = None;
+        let spare_row = if scroll_offset > 0. {
+            self.take_spare_row()
+        } else {
+            None
+        };
+        if let Some(spare_row) = spare_row {
+            let row = stable_top + lines.len() as StableRowIndex;
+            let (_, mut spare) = pos
+                .pane
+                .get_lines_with_hyperlinks_applied(row..row + 1, &self.config.hyperlink_rules);
+            if let Some(mut line) = spare.pop() {
+                let bidi = self
+                    .bidi_rows(&pos.pane, row..row + 1, dims.cols)
+                    .pop()
+                    .flatten();
+                if let Some(bidi) = &bidi {
+                    bidi.mirror_line(&mut line);
+                }
+                spare_line = Some((row, line, bidi, spare_row));
+            }
+        }
+
+        if self.show_invisible_characters(pos.pane.pane_id()) {
+            super::invisible::highlight_invisible_characters(
+                &mut lines,
+                palette.invisible_character,
+            );
+            if let Some((_, line, _, _)) = spare_line.as_mut() {
+                super::invisible::highlight_invisible_characters(
+                    std::slice::from_mut(line),
+                    palette.invisible_character,
+                );
+            }
+        }
+
+        self.render_scroll_position_indicator(
+            pos,
+            current_viewport,
+            &dims,
+            &palette,
+            bidi_rows.first().and_then(Option::as_ref),
+            &mut lines,
+        );
+        self.render_pane_badge(
+            pos,
+            &dims,
+            &palette,
+            bidi_rows.last().and_then(Option::as_ref),
+            &mut lines,
+        );
+
+        // While the cursor is gliding between cells, it is drawn using
+        // its own quads rather than as part of the cell that it occupies
+        let animated_cursor = if pos.is_active {
+            let visual_cursor = StableCursorPosition {
+                x: usize::try_from(cursor.y - stable_top)
+                    .ok()
+                    .and_then(|row| bidi_rows.get(row))
+                    .and_then(Option::as_ref)
+                    .map(|bidi| bidi.visual_col(cursor.x))
+                    .unwrap_or(cursor.x),
+                ..cursor
+            };
+            self.animate_cursor(pos, &visual_cursor, stable_top, &dims, first_line_offset)
+        } else {
+            None
+        };
+        let cursor = if animated_cursor.is_some() {
+            StableCursorPosition {
+                visibility: CursorVisibility::Hidden,
+                ..cursor
+            }
+        } else {
+            cursor
+        };
+
+        // While selecting a pane, every pane is dimmed and has its
+        // label drawn over it
+        let is_active = pos.is_active && self.pane_select.is_none();
+        if let Some(label) = self
+            .pane_select
+            .as_ref()
+            .and_then(|state| state.label_for_pane(pos.pane.pane_id()))
+        {
+            super::paneselect::draw_label(label, &mut lines, &dims);
+        }
+        if pos.is_active && config.show_mru_overlay {
+            if let Some(cycle) = self.mru_cycle.as_ref() {
+                cycle.draw(&mut lines, &dims, config.tab_title_truncation);
+            }
+        }
+
         let gl_state = self.render_state.as_ref().unwrap();
         let mut vb = gl_state.glyph_vertex_buffer.borrow_mut();
 
@@ -215,10 +491,11 @@ impl super::TermWindow {
                     foreground,
                     pos,
                     is_active: true,
-                    selection_fg: LinearRgba::default(),
+                    selection_fg: None,
                     selection_bg: LinearRgba::default(),
                     cursor_fg: LinearRgba::default(),
                     cursor_bg: LinearRgba::default(),
+                    bidi: None,
                 },
                 &mut quads,
             )?;
@@ -303,7 +580,7 @@ impl super::TermWindow {
         let selrange = self.selection(pos.pane.pane_id()).range.clone();
 
         let start = Instant::now();
-        let selection_fg = rgbcolor_to_window_color(palette.selection_fg);
+        let selection_fg = palette.selection_fg.map(rgbcolor_to_window_color);
         let selection_bg = rgbcolor_to_window_color(palette.selection_bg);
         let cursor_fg = rgbcolor_to_window_color(palette.cursor_fg);
         let cursor_bg = rgbcolor_to_window_color(palette.cursor_bg);
@@ -325,17 +602,92 @@ impl super::TermWindow {
                     cursor_border_color,
                     foreground,
                     pos,
-                    is_active: pos.is_active,
+                    is_active,
+                    selection_fg,
+                    selection_bg,
+                    cursor_fg,
+                    cursor_bg,
+                    bidi: bidi_rows.get(line_idx).and_then(Option::as_ref),
+                },
+                &mut quads,
+            )?;
+        }
+        if let Some((stable_row, line, bidi, spare_row)) = spare_line.as_ref() {
+            let selrange = selrange.map_or(0..0, |sel| sel.cols_for_row(*stable_row));
+            self.render_screen_line_opengl(
+                RenderScreenLineOpenGLParams {
+                    line_idx: spare_row - pos.top,
+                    stable_line_idx: Some(*stable_row),
+                    line,
+                    selection: selrange,
+                    cursor: &cursor,
+                    palette: &palette,
+                    dims: &dims,
+                    config: &config,
+                    cursor_border_color,
+                    foreground,
+                    pos,
+                    is_active,
                     selection_fg,
                     selection_bg,
                     cursor_fg,
                     cursor_bg,
+                    bidi: bidi.as_ref(),
                 },
                 &mut quads,
             )?;
         }
+        self.position_pane_quads(
+            pos,
+            &dims,
+            lines.len(),
+            spare_line.as_ref().map(|(_, _, _, spare_row)| *spare_row),
+            scroll_offset,
+            &mut quads,
+        );
         log::trace!("lines elapsed {:?}", start.elapsed());
 
+        // The title is drawn into the row above the pane, which is either
+        // a split or the row that is reserved for titles at the top
+        if config.pane_title_bar {
+            let title = self.pane_title_line(pos, &palette);
+            self.render_screen_line_opengl(
+                RenderScreenLineOpenGLParams {
+                    line_idx: first_line_offset - 1,
+                    stable_line_idx: None,
+                    line: &title,
+                    selection: 0..0,
+                    cursor: &cursor,
+                    palette: &palette,
+                    dims: &RenderableDimensions {
+                        cols: title.cells().len(),
+                        ..dims
+                    },
+                    config: &config,
+                    cursor_border_color,
+                    foreground,
+                    pos,
+                    is_active: true,
+                    selection_fg: None,
+                    selection_bg: LinearRgba::default(),
+                    cursor_fg: LinearRgba::default(),
+                    cursor_bg: LinearRgba::default(),
+                    bidi: None,
+                },
+                &mut quads,
+            )?;
+        }
+
+        if pos.is_active {
+            self.render_animated_cursor(
+                animated_cursor.as_ref(),
+                &cursor,
+                &palette,
+                gl_state,
+                &mut quads,
+            );
+        }
+
         let start = Instant::now();
         drop(quads);
         log::trace!("quad drop elapsed {:?}", start.elapsed());
@@ -343,9 +695,265 @@ impl super::TermWindow {
         Ok(())
     }
 
+    /// Positions the quads of the rows of a pane, moving them up by
+    /// `scroll_offset` rows while the viewport is scrolled part way
+    /// through a row, and clipping them to the pane.  `spare_row`, if
+    /// any, is the row of quads that holds the line below the last of
+    /// the `num_lines` rows.
+    /// The rows are repositioned on every frame, rather than only while
+    /// they are offset, so that none of the vertex buffers are left
+    /// holding the positions from an earlier frame.
+    fn position_pane_quads(
+        &self,
+        pos: &PositionedPane,
+        dims: &RenderableDimensions,
+        num_lines: usize,
+        spare_row: Option<usize>,
+        scroll_offset: f32,
+        quads: &mut MappedQuads,
+    ) {
+        let cell_width = self.render_metrics.cell_size.width as f32;
+        let cell_height = self.render_metrics.cell_size.height as f32;
+        let origin_x =
+            (self.dimensions.pixel_width as f32 / -2.0) + self.config.window_padding.left as f32;
+        let origin_y =
+            (self.dimensions.pixel_height as f32 / -2.0) + self.config.window_padding.top as f32;
+        let first_line_offset = self.rows_above_panes();
+        let pane_top = origin_y + (pos.top + first_line_offset) as f32 * cell_height;
+        let pane_bottom = pane_top + dims.viewport_rows as f32 * cell_height;
+
+        let mut rows: Vec<(usize, usize)> = (0..num_lines)
+            .map(|line_idx| (line_idx, pos.top + first_line_offset + line_idx))
+            .collect();
+        if let Some(spare_row) = spare_row {
+            rows.push((num_lines, spare_row));
+        }
+
+        for (line_idx, quad_row) in rows {
+            let top = pane_top + (line_idx as f32 - scroll_offset) * cell_height;
+            for x in pos.left..pos.left + dims.cols {
+                let mut quad = match quads.cell(x, quad_row) {
+                    Ok(quad) => quad,
+                    Err(_) => break,
+                };
+                let left = origin_x + x as f32 * cell_width;
+                quad.set_position_clipped(
+                    left,
+                    top,
+                    left + cell_width,
+                    top + cell_height,
+                    pane_top,
+                    pane_bottom,
+                );
+            }
+        }
+    }
+
+    /// Collapses the quads of the spare rows, so that they are not
+    /// drawn unless a pane makes use of them in this frame, and makes
+    /// all of them available to the panes again
+    fn hide_spare_rows_opengl(&self) {
+        self.spare_rows_used.set(0);
+        let gl_state = self.render_state.as_ref().unwrap();
+        let mut vb = gl_state.glyph_vertex_buffer.borrow_mut();
+        let mut quads = gl_state.quads.map(&mut vb);
+        for spare_row in gl_state.quads.spare_rows.clone() {
+            for x in 0..gl_state.quads.cols {
+                if let Ok(mut quad) = quads.cell(x, spare_row) {
+                    quad.set_position(0., 0., 0., 0.);
+                }
+            }
+        }
+    }
+
+    /// Returns the next spare row that no pane has used in this frame
+    fn take_spare_row(&self) -> Option<usize> {
+        let spare_rows = self.render_state.as_ref()?.quads.spare_rows.clone();
+        let row = spare_rows.start + self.spare_rows_used.get();
+        if row < spare_rows.end {
+            self.spare_rows_used.set(self.spare_rows_used.get() + 1);
+            Some(row)
+        } else {
+            None
+        }
+    }
+
+    /// Advances the cursor animation for the active pane, returning
+    /// where the cursor should be drawn if it is in motion.
+    /// Animation is suspended while the cursor is hidden, out of view
+    /// or the window doesn't have focus.
+    fn animate_cursor(
+        &self,
+        pos: &PositionedPane,
+        cursor: &StableCursorPosition,
+        stable_top: StableRowIndex,
+        dims: &RenderableDimensions,
+        first_line_offset: usize,
+    ) -> Option<AnimatedCursor> {
+        let in_viewport =
+            cursor.y >= stable_top && cursor.y < stable_top + dims.viewport_rows as StableRowIndex;
+        if cursor.visibility != CursorVisibility::Visible
+            || !in_viewport
+            || self.focused.is_none()
+            || self.pane_select.is_some()
+        {
+            self.cursor_animation.borrow_mut().reset();
+            return None;
+        }
+
+        let duration = if self.reduce_motion() {
+            Duration::from_millis(0)
+        } else {
+            Duration::from_millis(self.config.cursor_animation_duration)
+        };
+        let to = CellPoint {
+            x: (cursor.x + pos.left) as f32,
+            y: ((cursor.y - stable_top) as usize + pos.top + first_line_offset) as f32,
+        };
+
+        let now = Instant::now();
+        let animated = self.cursor_animation.borrow_mut().update(
+            pos.pane.pane_id(),
+            to,
+            duration,
+            self.config.cursor_animation_easing,
+            self.config.cursor_trail,
+            now,
+        );
+        if animated.is_some() {
+            // Keep painting frames until it comes to rest
+            self.update_next_frame_time(Some(now + Duration::from_millis(16)));
+        }
+        animated
+    }
+
+    /// Continues a touchpad scroll after the fingers have been lifted,
+    /// by replaying the most recent mouse event as a wheel movement
+    fn apply_scroll_momentum(&mut self) {
+        if !self.pixel_scroll.is_coasting() {
+            return;
+        }
+        let now = Instant::now();
+        let cell_height = self.render_metrics.cell_size.height as f32;
+        let rows = self.pixel_scroll.coast(cell_height, now);
+        if self.pixel_scroll.is_coasting() {
+            self.update_next_frame_time(Some(now + Duration::from_millis(16)));
+        }
+        if rows == 0 {
+            return;
+        }
+        let event = match self.current_mouse_event.as_ref() {
+            Some(event) => MouseEvent {
+                kind: MouseEventKind::VertWheel(rows),
+                ..event.clone()
+            },
+            None => return,
+        };
+        if let Some(window) = self.window.clone() {
+            self.mouse_event_impl(&event, &window);
+        }
+    }
+
+    /// Positions the quads used for the animated cursor and its trail,
+    /// collapsing any that are not needed so that they are not drawn.
+    fn render_animated_cursor(
+        &self,
+        animated: Option<&AnimatedCursor>,
+        cursor: &StableCursorPosition,
+        palette: &ColorPalette,
+        gl_state: &RenderState,
+        quads: &mut MappedQuads,
+    ) {
+        let white_space = gl_state.util_sprites.white_space.texture_coords();
+        let cell_width = self.render_metrics.cell_size.width as f32;
+        let cell_height = self.render_metrics.cell_size.height as f32;
+        let origin_x =
+            (self.dimensions.pixel_width as f32 / -2.0) + self.config.window_padding.left as f32;
+        let origin_y =
+            (self.dimensions.pixel_height as f32 / -2.0) + self.config.window_padding.top as f32;
+        let shape = self
+            .config
+            .default_cursor_style
+            .effective_shape(cursor.shape);
+        let is_block = matches!(shape, CursorShape::BlinkingBlock | CursorShape::SteadyBlock);
+
+        let mut cells: Vec<Option<(CellPoint, f32)>> = vec![None; CURSOR_TRAIL_QUADS + 1];
+        if let Some(animated) = animated {
+            // The most recent trail positions get the quads that are
+            // closest to the cursor itself
+            let first = CURSOR_TRAIL_QUADS.saturating_sub(animated.trail.len());
+            for (idx, point) in animated.trail.iter().enumerate() {
+                if let Some(cell) = cells.get_mut(first + idx) {
+                    *cell = Some(*point);
+                }
+            }
+            cells[CURSOR_TRAIL_QUADS] = Some((animated.position, 1.0));
+        }
+
+        for (idx, cell) in cells.into_iter().enumerate() {
+            let mut quad = quads.animated_cursor(idx);
+            quad.set_texture(white_space);
+            quad.set_texture_adjust(0., 0., 0., 0.);
+            quad.set_underline(white_space);
+            quad.set_has_color(false);
+            quad.set_hsv(None);
+
+            let (point, alpha) = match cell {
+                Some(cell) => cell,
+                None => {
+                    quad.set_position(0., 0., 0., 0.);
+                    continue;
+                }
+            };
+
+            let left = origin_x + point.x * cell_width;
+            let top = origin_y + point.y * cell_height;
+            quad.set_position(left, top, left + cell_width, top + cell_height);
+
+            let alpha = (alpha * 255.0) as u8;
+            if is_block {
+                let color = rgbcolor_alpha_to_window_color(palette.cursor_bg, alpha);
+                quad.set_bg_color(color);
+                // Matching fg and bg prevents the glyph pass from
+                // drawing anything over the text beneath the cursor
+                quad.set_fg_color(color);
+                quad.set_underline_color(color);
+                quad.set_cursor(white_space);
+                quad.set_cursor_color(color);
+            } else {
+                let transparent = LinearRgba::default();
+                quad.set_bg_color(transparent);
+                quad.set_fg_color(transparent);
+                quad.set_underline_color(transparent);
+                quad.set_cursor(
+                    gl_state
+                        .util_sprites
+                        .cursor_sprite(Some(shape))
+                        .texture_coords(),
+                );
+                quad.set_cursor_color(rgbcolor_alpha_to_window_color(palette.cursor_border, alpha));
+            }
+        }
+    }
+
     pub fn call_draw(&mut self, frame: &mut glium::Frame) -> anyhow::Result<()> {
+        self.draw_quads(frame)?;
+
         let gl_state = self.render_state.as_ref().unwrap();
         let mut vb = gl_state.glyph_vertex_buffer.borrow_mut();
+        vb.index += 1;
+        if vb.index >= 3 {
+            vb.index = 0;
+        }
+
+        Ok(())
+    }
+
+    /// Draws the quads that were prepared by `paint_opengl_pass`
+    /// onto `surface`
+    pub fn draw_quads<S: Surface>(&self, surface: &mut S) -> anyhow::Result<()> {
+        let gl_state = self.render_state.as_ref().unwrap();
+        let vb = gl_state.glyph_vertex_buffer.borrow();
 
         let tex = gl_state.glyph_cache.borrow().atlas.texture();
         let projection = euclid::Transform3D::<f32, f32, f32>::ortho(
@@ -395,7 +1003,7 @@ impl super::TermWindow {
         );
 
         // Pass 1: Draw backgrounds
-        frame.draw(
+        surface.draw(
             &vb.bufs[vb.index],
             &gl_state.glyph_index_buffer,
             &gl_state.background_prog,
@@ -408,7 +1016,7 @@ impl super::TermWindow {
         )?;
 
         // Pass 2: strikethrough and underline
-        frame.draw(
+        surface.draw(
             &vb.bufs[vb.index],
             &gl_state.glyph_index_buffer,
             &gl_state.line_prog,
@@ -462,7 +1070,7 @@ impl super::TermWindow {
         };
 
         // Pass 3: Draw glyphs
-        frame.draw(
+        surface.draw(
             &vb.bufs[vb.index],
             &gl_state.glyph_index_buffer,
             &gl_state.glyph_prog,
@@ -475,19 +1083,47 @@ impl super::TermWindow {
             &blend_but_set_alpha_to_one,
         )?;
 
-        vb.index += 1;
-        if vb.index >= 3 {
-            vb.index = 0;
+        Ok(())
+    }
+
+    /// Builds the line that is drawn above `pos` when pane title bars
+    /// are enabled; the index and title of the pane, padded out to the
+    /// width of the pane with a horizontal line
+    fn pane_title_line(&self, pos: &PositionedPane, palette: &ColorPalette) -> Line {
+        let color = if pos.is_active {
+            palette.active_split
+        } else {
+            palette.split
+        };
+        let mut attrs = CellAttributes::default();
+        attrs.set_foreground(ColorAttribute::TrueColorWithDefaultFallback(color));
+
+        // In the reserved row there is no split to fill the gap
+        // between a pane and its neighbor to the right
+        let mut width = pos.width;
+        if pos.top == 0 && pos.left + pos.width < self.terminal_size.cols as usize {
+            width += 1;
         }
 
-        Ok(())
+        let title = format!("─ {}: {} ", pos.index, pos.pane.get_title());
+        let title_width = Line::from_text(&title, &attrs).cells().len();
+        let mut line = Line::from_text(
+            &format!("{}{}", title, "─".repeat(width.saturating_sub(title_width))),
+            &attrs,
+        );
+        line.resize(width);
+        line
     }
 
+    /// Draws `split`.  The parts of it that border `active`, if any,
+    /// are drawn using the `active_split` color.
     pub fn paint_split_opengl(
         &mut self,
         split: &PositionedSplit,
         pane: &Rc<dyn Pane>,
+        active: Option<&PositionedPane>,
     ) -> anyhow::Result<()> {
+        let palette = self.pane_palette(&**pane);
         let gl_state = self.render_state.as_ref().unwrap();
         let mut vb = gl_state.glyph_vertex_buffer.borrow_mut();
         let mut quads = gl_state.quads.map(&mut vb);
@@ -497,8 +1133,8 @@ impl super::TermWindow {
         } else {
             "─"
         };
-        let palette = pane.palette();
-        let foreground = rgbcolor_to_window_color(palette.split);
+        let inactive_foreground = rgbcolor_to_window_color(palette.split);
+        let active_foreground = rgbcolor_to_window_color(palette.active_split);
         let background = rgbcolor_alpha_to_window_color(
             palette.background,
             if self.window_background.is_some() || config.window_background_opacity != 1.0 {
@@ -510,7 +1146,11 @@ impl super::TermWindow {
 
         let style = self.fonts.match_style(&config, &CellAttributes::default());
         let glyph_info = {
-            let key = BorrowedShapeCacheKey { style, text };
+            let key = BorrowedShapeCacheKey {
+                style,
+                text,
+                vertical_align: VerticalAlign::BaseLine,
+            };
             match self.lookup_cached_shape(&key) {
                 Some(Ok(info)) => info,
                 Some(Err(err)) => return Err(err),
@@ -552,7 +1192,7 @@ impl super::TermWindow {
                 }
             }
         };
-        let first_row_offset = if self.show_tab_bar { 1 } else { 0 };
+        let first_row_offset = self.rows_above_panes();
 
         for info in glyph_info.iter() {
             let glyph = &info.glyph;
@@ -576,27 +1216,34 @@ impl super::TermWindow {
                 Box::new((split.left..split.left + split.size).zip(std::iter::repeat(split.top)))
             };
             for (x, y) in x_y_iter {
-                let slice = SpriteSlice {
-                    cell_idx: 0,
-                    num_cells: info.pos.num_cells as usize,
-                    cell_width: self.render_metrics.cell_size.width as usize,
-                    scale: glyph.scale as f32,
-                    left_offset: left,
-                };
-
-                let pixel_rect = slice.pixel_rect(texture);
+                let (pixel_rect, (left, top, right, bottom)) = glyph_slice(
+                    texture,
+                    0,
+                    info.pos.num_cells as usize,
+                    self.render_metrics.cell_size,
+                    glyph.scale as f32,
+                    left,
+                    top,
+                );
                 let texture_rect = texture.texture.to_texture_coords(pixel_rect);
 
-                let bottom = (pixel_rect.size.height as f32 * glyph.scale as f32) + top
-                    - self.render_metrics.cell_size.height as f32;
-                let right = pixel_rect.size.width as f32 + left
-                    - self.render_metrics.cell_size.width as f32;
-
                 let mut quad = match quads.cell(x, y + first_row_offset) {
                     Ok(quad) => quad,
                     Err(_) => break,
                 };
 
+                // Cells that are within one cell of the active pane border it
+                let foreground = match active {
+                    Some(pos)
+                        if x + 1 >= pos.left
+                            && x <= pos.left + pos.width
+                            && y + 1 >= pos.top
+                            && y <= pos.top + pos.height =>
+                    {
+                        active_foreground
+                    }
+                    _ => inactive_foreground,
+                };
                 quad.set_fg_color(foreground);
                 quad.set_underline_color(foreground);
                 quad.set_bg_color(background);
@@ -614,24 +1261,122 @@ impl super::TermWindow {
 
     pub fn paint_opengl_pass(&mut self) -> anyhow::Result<()> {
         let panes = self.get_panes_to_render();
+        let active = panes.iter().find(|pos| pos.is_active).cloned();
 
         if let Some(pane) = self.get_active_pane_or_overlay() {
             let splits = self.get_splits();
             for split in &splits {
-                self.paint_split_opengl(split, &pane)?;
+                self.paint_split_opengl(split, &pane, active.as_ref())?;
             }
         }
 
-        for pos in panes {
+        self.hide_spare_rows_opengl();
+
+        let border = self.get_floating_pane_border();
+        for pos in &panes {
             if pos.is_active {
                 self.update_text_cursor(&pos.pane);
             }
-            self.paint_pane_opengl(&pos)?;
+            // The border of a floating pane is drawn over the panes below it
+            if border.first().map(|edge| edge.index) == Some(pos.index) {
+                for edge in &border {
+                    self.paint_split_opengl(edge, &pos.pane, Some(pos))?;
+                }
+            }
+            self.paint_pane_opengl(pos)?;
         }
 
+        self.paint_effects_opengl(&panes);
+
         Ok(())
     }
 
+    /// Positions the quads used for the visual effects over their
+    /// targets, collapsing any that are not needed so that they are
+    /// not drawn.  The effects are blended over the top of whatever
+    /// is beneath them, so a flash over a transparent window remains
+    /// translucent.
+    fn paint_effects_opengl(&mut self, panes: &[PositionedPane]) {
+        let now = Instant::now();
+        let mut visible = self.effects.visible(now);
+        if !visible.is_empty() {
+            self.update_next_frame_time(Some(now + Duration::from_millis(16)));
+        }
+        if self.idle.is_idle() && self.config.idle.dim_opacity > 0. {
+            visible.push((
+                EffectTarget::Window,
+                RgbColor::new(0, 0, 0),
+                self.config.idle.dim_opacity,
+            ));
+        }
+
+        let gl_state = self.render_state.as_ref().unwrap();
+        let mut vb = gl_state.glyph_vertex_buffer.borrow_mut();
+        let mut quads = gl_state.quads.map(&mut vb);
+        let white_space = gl_state.util_sprites.white_space.texture_coords();
+        let cell_width = self.render_metrics.cell_size.width as f32;
+        let cell_height = self.render_metrics.cell_size.height as f32;
+        let width = self.dimensions.pixel_width as f32;
+        let height = self.dimensions.pixel_height as f32;
+        let origin_x = (width / -2.0) + self.config.window_padding.left as f32;
+        let origin_y = (height / -2.0) + self.config.window_padding.top as f32;
+        let first_line_offset = self.rows_above_panes();
+
+        let mut effects = visible.into_iter().filter_map(|(target, color, opacity)| {
+            let rect = match target {
+                EffectTarget::Window => (width / -2.0, height / -2.0, width / 2.0, height / 2.0),
+                EffectTarget::Pane(pane_id) => {
+                    // The pane may currently be displaying an overlay
+                    let overlay = self
+                        .pane_state(pane_id)
+                        .overlay
+                        .as_ref()
+                        .map(|overlay| overlay.pane.pane_id());
+                    let pos = panes.iter().find(|pos| {
+                        let id = pos.pane.pane_id();
+                        id == pane_id || Some(id) == overlay
+                    })?;
+                    let left = origin_x + pos.left as f32 * cell_width;
+                    let top = origin_y + (pos.top + first_line_offset) as f32 * cell_height;
+                    (
+                        left,
+                        top,
+                        left + pos.width as f32 * cell_width,
+                        top + pos.height as f32 * cell_height,
+                    )
+                }
+            };
+            Some((rect, color, opacity))
+        });
+
+        for idx in 0..EFFECT_QUADS {
+            let mut quad = quads.effect(idx);
+            quad.set_texture(white_space);
+            quad.set_texture_adjust(0., 0., 0., 0.);
+            quad.set_underline(white_space);
+            quad.set_cursor(white_space);
+            quad.set_has_color(false);
+            quad.set_hsv(None);
+
+            let ((left, top, right, bottom), color, opacity) = match effects.next() {
+                Some(effect) => effect,
+                None => {
+                    quad.set_position(0., 0., 0., 0.);
+                    continue;
+                }
+            };
+            quad.set_position(left, top, right, bottom);
+
+            let color = rgbcolor_alpha_to_window_color(color, (opacity * 255.0) as u8);
+            quad.set_bg_color(color);
+            // Matching fg and bg prevents the glyph pass from drawing
+            // anything over the text beneath the effect
+            quad.set_fg_color(color);
+            quad.set_underline_color(color);
+            quad.set_cursor_color(color);
+        }
+    }
+
     fn invalidate_post_font_resolve(window: ::window::Window) {
         promise::spawn::spawn_into_main_thread(async move {
             window
@@ -786,6 +1531,7 @@ impl super::TermWindow {
                 let key = BorrowedShapeCacheKey {
                     style,
                     text: &cluster.text,
+                    vertical_align: attrs.vertical_align(),
                 };
                 match self.lookup_cached_shape(&key) {
                     Some(Ok(info)) => info,
@@ -855,9 +1601,10 @@ impl super::TermWindow {
                     last_cell_idx = cell_idx;
 
                     let ComputeCellFgBgResult {
-                        fg_color: glyph_color,
+                        fg_color,
                         bg_color,
                         cursor_shape,
+                        cursor_border_color,
                     } = self.compute_cell_fg_bg(ComputeCellFgBgParams {
                         stable_line_idx: params.stable_line_idx,
                         cell_idx,
@@ -872,7 +1619,10 @@ impl super::TermWindow {
                         selection_bg: params.selection_bg,
                         cursor_fg: params.cursor_fg,
                         cursor_bg: params.cursor_bg,
+                        cursor_border_color: params.cursor_border_color,
+                        reverse_video_cursor: params.config.force_reverse_video_cursor,
                     });
+                    let glyph_color = fg_color.unwrap_or(glyph_color);
 
                     if let Some(image) = attrs.image() {
                         self.populate_image_quad(
@@ -883,6 +1633,7 @@ impl super::TermWindow {
                             &params,
                             hsv,
                             cursor_shape,
+                            cursor_border_color,
                             glyph_color,
                             underline_color,
                             bg_color,
@@ -901,6 +1652,7 @@ impl super::TermWindow {
                                 &params,
                                 hsv,
                                 cursor_shape,
+                                cursor_border_color,
                                 glyph_color,
                                 underline_color,
                                 bg_color,
@@ -916,25 +1668,19 @@ impl super::TermWindow {
                         .unwrap_or(&gl_state.util_sprites.white_space);
 
                     let left = info.pos.x_offset.get() as f32 + info.pos.bearing_x;
-                    let slice = SpriteSlice {
-                        cell_idx: glyph_idx,
-                        num_cells: info.pos.num_cells as usize,
-                        cell_width: self.render_metrics.cell_size.width as usize,
-                        scale: glyph.scale as f32,
-                        left_offset: left,
-                    };
-
-                    let pixel_rect = slice.pixel_rect(texture);
+                    let (pixel_rect, (left, top, right, bottom)) = glyph_slice(
+                        texture,
+                        glyph_idx,
+                        info.pos.num_cells as usize,
+                        self.render_metrics.cell_size,
+                        glyph.scale as f32,
+                        left,
+                        top,
+                    );
                     let texture_rect = texture.texture.to_texture_coords(pixel_rect);
 
-                    let left = if glyph_idx == 0 { left } else { 0.0 };
-                    let bottom = (pixel_rect.size.height as f32 * glyph.scale as f32) + top
-                        - self.render_metrics.cell_size.height as f32;
-                    let right = pixel_rect.size.width as f32 + left
-                        - self.render_metrics.cell_size.width as f32;
-
                     let mut quad = match quads
-                        .cell(cell_idx + params.pos.left, params.line_idx + params.pos.top)
+                        .cell(params.quad_col(cell_idx), params.line_idx + params.pos.top)
                     {
                         Ok(quad) => quad,
                         Err(_) => break,
@@ -954,7 +1700,7 @@ impl super::TermWindow {
                             .cursor_sprite(cursor_shape)
                             .texture_coords(),
                     );
-                    quad.set_cursor_color(params.cursor_border_color);
+                    quad.set_cursor_color(cursor_border_color);
                 }
             }
         }
@@ -981,9 +1727,10 @@ impl super::TermWindow {
             // the colors in the usual way.
 
             let ComputeCellFgBgResult {
-                fg_color: glyph_color,
+                fg_color,
                 bg_color,
                 cursor_shape,
+                cursor_border_color,
             } = self.compute_cell_fg_bg(ComputeCellFgBgParams {
                 stable_line_idx: params.stable_line_idx,
                 cell_idx,
@@ -998,10 +1745,13 @@ impl super::TermWindow {
                 selection_bg: params.selection_bg,
                 cursor_fg: params.cursor_fg,
                 cursor_bg: params.cursor_bg,
+                cursor_border_color: params.cursor_border_color,
+                reverse_video_cursor: params.config.force_reverse_video_cursor,
             });
+            let glyph_color = fg_color.unwrap_or(params.foreground);
 
             let mut quad =
-                match quads.cell(cell_idx + params.pos.left, params.line_idx + params.pos.top) {
+                match quads.cell(params.quad_col(cell_idx), params.line_idx + params.pos.top) {
                     Ok(quad) => quad,
                     Err(_) => break,
                 };
@@ -1020,7 +1770,7 @@ impl super::TermWindow {
                     .cursor_sprite(cursor_shape)
                     .texture_coords(),
             );
-            quad.set_cursor_color(params.cursor_border_color);
+            quad.set_cursor_color(cursor_border_color);
         }
 
         Ok(())
@@ -1035,6 +1785,7 @@ impl super::TermWindow {
         params: &RenderScreenLineOpenGLParams,
         hsv: Option<config::HsbTransform>,
         cursor_shape: Option<CursorShape>,
+        cursor_border_color: LinearRgba,
         glyph_color: LinearRgba,
         underline_color: LinearRgba,
         bg_color: LinearRgba,
@@ -1046,11 +1797,11 @@ impl super::TermWindow {
             .cached_block(block)?
             .texture_coords();
 
-        let mut quad =
-            match quads.cell(cell_idx + params.pos.left, params.line_idx + params.pos.top) {
-                Ok(quad) => quad,
-                Err(_) => return Ok(()),
-            };
+        let col = params.quad_col(cell_idx);
+        let mut quad = match quads.cell(col, params.line_idx + params.pos.top) {
+            Ok(quad) => quad,
+            Err(_) => return Ok(()),
+        };
 
         quad.set_hsv(hsv);
         quad.set_fg_color(glyph_color);
@@ -1066,7 +1817,7 @@ impl super::TermWindow {
                 .cursor_sprite(cursor_shape)
                 .texture_coords(),
         );
-        quad.set_cursor_color(params.cursor_border_color);
+        quad.set_cursor_color(cursor_border_color);
 
         Ok(())
     }
@@ -1081,6 +1832,7 @@ impl super::TermWindow {
         params: &RenderScreenLineOpenGLParams,
         hsv: Option<config::HsbTransform>,
         cursor_shape: Option<CursorShape>,
+        cursor_border_color: LinearRgba,
         glyph_color: LinearRgba,
         underline_color: LinearRgba,
         bg_color: LinearRgba,
@@ -1127,11 +1879,11 @@ impl super::TermWindow {
 
         let texture_rect = TextureRect::new(origin, size);
 
-        let mut quad =
-            match quads.cell(cell_idx + params.pos.left, params.line_idx + params.pos.top) {
-                Ok(quad) => quad,
-                Err(_) => return Ok(()),
-            };
+        let col = params.quad_col(cell_idx);
+        let mut quad = match quads.cell(col, params.line_idx + params.pos.top) {
+            Ok(quad) => quad,
+            Err(_) => return Ok(()),
+        };
 
         quad.set_hsv(hsv);
         quad.set_fg_color(glyph_color);
@@ -1147,84 +1899,31 @@ impl super::TermWindow {
                 .cursor_sprite(cursor_shape)
                 .texture_coords(),
         );
-        quad.set_cursor_color(params.cursor_border_color);
+        quad.set_cursor_color(cursor_border_color);
 
         Ok(())
     }
 
     pub fn compute_cell_fg_bg(&self, params: ComputeCellFgBgParams) -> ComputeCellFgBgResult {
-        let selected = params.selection.contains(&params.cell_idx);
-
-        let is_cursor =
-            params.stable_line_idx == Some(params.cursor.y) && params.cursor.x == params.cell_idx;
-
-        let (cursor_shape, visibility) =
-            if is_cursor && params.cursor.visibility == CursorVisibility::Visible {
-                // This logic figures out whether the cursor is visible or not.
-                // If the cursor is explicitly hidden then it is obviously not
-                // visible.
-                // If the cursor is set to a blinking mode then we are visible
-                // depending on the current time.
-                let shape = params
-                    .config
-                    .default_cursor_style
-                    .effective_shape(params.cursor.shape);
-                // Work out the blinking shape if its a blinking cursor and it hasn't been disabled
-                // and the window is focused.
-                let blinking = params.is_active_pane
-                    && shape.is_blinking()
-                    && params.config.cursor_blink_rate != 0
-                    && self.focused.is_some();
-                if blinking {
-                    // Divide the time since we last moved by the blink rate.
-                    // If the result is even then the cursor is "on", else it
-                    // is "off"
-                    let now = std::time::Instant::now();
-                    let milli_uptime = now
-                        .duration_since(self.prev_cursor.last_cursor_movement())
-                        .as_millis();
-                    let ticks = milli_uptime / params.config.cursor_blink_rate as u128;
-                    (
-                        shape,
-                        if (ticks & 1) == 0 {
-                            CursorVisibility::Visible
-                        } else {
-                            CursorVisibility::Hidden
-                        },
-                    )
-                } else {
-                    (shape, CursorVisibility::Visible)
-                }
-            } else {
-                (params.cursor.shape, CursorVisibility::Hidden)
-            };
-
-        let (fg_color, bg_color) = match (
-            selected,
-            self.focused.is_some() && params.is_active_pane,
-            cursor_shape,
-            visibility,
-        ) {
-            // Selected text overrides colors
-            (true, _, _, CursorVisibility::Hidden) => (params.selection_fg, params.selection_bg),
-            // Cursor cell overrides colors
-            (_, true, CursorShape::BlinkingBlock, CursorVisibility::Visible)
-            | (_, true, CursorShape::SteadyBlock, CursorVisibility::Visible) => {
-                (params.cursor_fg, params.cursor_bg)
+        let blink_rate = params.config.cursor_blink_rate;
+        cell_fg_bg(params, self.focused.is_some(), || {
+            if self.animations_paused() {
+                return CursorVisibility::Visible;
             }
-            // Normally, render the cell as configured (or if the window is unfocused)
-            _ => (params.fg_color, params.bg_color),
-        };
-
-        ComputeCellFgBgResult {
-            fg_color,
-            bg_color,
-            cursor_shape: if visibility == CursorVisibility::Visible {
-                Some(cursor_shape)
+            // Divide the time since we last moved by the blink rate.
+            // If the result is even then the cursor is "on", else it
+            // is "off"
+            let now = std::time::Instant::now();
+            let milli_uptime = now
+                .duration_since(self.prev_cursor.last_cursor_movement())
+                .as_millis();
+            let ticks = milli_uptime / blink_rate as u128;
+            if (ticks & 1) == 0 {
+                CursorVisibility::Visible
             } else {
-                None
-            },
-        }
+                CursorVisibility::Hidden
+            }
+        })
     }
 
     fn glyph_infos_to_glyphs(
@@ -1243,7 +1942,22 @@ impl super::TermWindow {
                 None => false,
             };
 
-            glyphs.push(glyph_cache.cached_glyph(info, &style, followed_by_space)?);
+            if info.glyph_pos == MISSING_GLYPH {
+                let c = line
+                    .cells()
+                    .get(cell_idx)
+                    .and_then(|cell| cell.str().chars().next())
+                    .unwrap_or(std::char::REPLACEMENT_CHARACTER);
+                glyphs.push(glyph_cache.cached_missing_glyph(c, info.num_cells)?);
+                continue;
+            }
+
+            glyphs.push(glyph_cache.cached_glyph(
+                info,
+                &style,
+                followed_by_space,
+                cluster.attrs.vertical_align(),
+            )?);
         }
         Ok(glyphs)
     }
@@ -1277,6 +1991,94 @@ impl super::TermWindow {
     }
 }
 
+/// Decides the colors and cursor shape for a cell.
+/// This doesn't need a window, so that it can be exercised by tests;
+/// `window_focused` and `blink_phase` supply the window state that it
+/// depends upon.  `blink_phase` is only called for a blinking cursor.
+fn cell_fg_bg(
+    params: ComputeCellFgBgParams,
+    window_focused: bool,
+    blink_phase: impl FnOnce() -> CursorVisibility,
+) -> ComputeCellFgBgResult {
+    let selected = params.selection.contains(&params.cell_idx);
+
+    let is_cursor =
+        params.stable_line_idx == Some(params.cursor.y) && params.cursor.x == params.cell_idx;
+
+    let (cursor_shape, visibility) =
+        if is_cursor && params.cursor.visibility == CursorVisibility::Visible {
+            // This logic figures out whether the cursor is visible or not.
+            // If the cursor is explicitly hidden then it is obviously not
+            // visible.
+            // If the cursor is set to a blinking mode then we are visible
+            // depending on the current time.
+            let shape = params
+                .config
+                .default_cursor_style
+                .effective_shape(params.cursor.shape);
+            // Work out the blinking shape if its a blinking cursor and it hasn't been disabled
+            // and the window is focused.
+            let blinking = params.is_active_pane
+                && shape.is_blinking()
+                && params.config.cursor_blink_rate != 0
+                && window_focused;
+            if blinking {
+                (shape, blink_phase())
+            } else {
+                (shape, CursorVisibility::Visible)
+            }
+        } else {
+            (params.cursor.shape, CursorVisibility::Hidden)
+        };
+
+    let (fg_color, bg_color) = match (
+        selected,
+        window_focused && params.is_active_pane,
+        cursor_shape,
+        visibility,
+    ) {
+        // Selected text overrides colors, although the text may
+        // keep its own color
+        (true, _, _, CursorVisibility::Hidden) => (params.selection_fg, params.selection_bg),
+        // Cursor cell overrides colors
+        (_, true, CursorShape::BlinkingBlock, CursorVisibility::Visible)
+        | (_, true, CursorShape::SteadyBlock, CursorVisibility::Visible) => {
+            if params.reverse_video_cursor {
+                (Some(opaque(params.bg_color)), opaque(params.fg_color))
+            } else {
+                (Some(params.cursor_fg), params.cursor_bg)
+            }
+        }
+        // Normally, render the cell as configured (or if the window is unfocused)
+        _ => (None, params.bg_color),
+    };
+
+    let cursor_border_color =
+        if params.reverse_video_cursor && visibility == CursorVisibility::Visible {
+            opaque(params.fg_color)
+        } else {
+            params.cursor_border_color
+        };
+
+    ComputeCellFgBgResult {
+        fg_color,
+        bg_color,
+        cursor_border_color,
+        cursor_shape: if visibility == CursorVisibility::Visible {
+            Some(cursor_shape)
+        } else {
+            None
+        },
+    }
+}
+
+/// Returns `color` without any transparency, so that the transparent
+/// default background can be used as the color of the cursor text
+fn opaque(color: LinearRgba) -> LinearRgba {
+    let (r, g, b, _) = color.tuple();
+    LinearRgba::with_components(r, g, b, 1.0)
+}
+
 fn rgbcolor_to_window_color(color: RgbColor) -> LinearRgba {
     rgbcolor_alpha_to_window_color(color, 0xff)
 }
@@ -1287,3 +2089,336 @@ fn rgbcolor_alpha_to_window_color(color: RgbColor, alpha: u8) -> LinearRgba {
     // with_srgba.
     LinearRgba::with_rgba(color.red, color.green, color.blue, alpha)
 }
+
+/// Draws `text` over `line` starting at the visual column `left`.
+/// The badge is placed by visual column so that it reads correctly
+/// even when bidi reorders the line.
+fn overlay_badge(
+    line: &mut Line,
+    text: &str,
+    left: usize,
+    bidi: Option<&BidiRow>,
+    attrs: &CellAttributes,
+) {
+    for (idx, c) in text.chars().enumerate() {
+        let col = match bidi {
+            Some(bidi) => bidi.logical_col(left + idx),
+            None => left + idx,
+        };
+        line.set_cell(col, Cell::new(c, attrs.clone()));
+    }
+}
+
+/// Returns the pixel rect of the portion of `texture` that is drawn
+/// in the `glyph_idx`th of the `num_cells` cells spanned by a glyph,
+/// along with the left, top, right and bottom adjustments that stretch
+/// the quad for that cell to fit it.  `left` and `top` are the offsets
+/// of the glyph relative to the origin of its first cell.
+fn glyph_slice<T: Texture2d>(
+    texture: &Sprite<T>,
+    glyph_idx: usize,
+    num_cells: usize,
+    cell_size: Size,
+    scale: f32,
+    left: f32,
+    top: f32,
+) -> (Rect, (f32, f32, f32, f32)) {
+    let slice = SpriteSlice {
+        cell_idx: glyph_idx,
+        num_cells,
+        cell_width: cell_size.width as usize,
+        scale,
+        left_offset: left,
+    };
+    let pixel_rect = slice.pixel_rect(texture);
+
+    let left = if glyph_idx == 0 { left } else { 0.0 };
+    let bottom = (pixel_rect.size.height as f32 * scale) + top - cell_size.height as f32;
+    let right = pixel_rect.size.width as f32 + left - cell_size.width as f32;
+    (pixel_rect, (left, top, right, bottom))
+}
+
+/// The text of the scroll position badge for a viewport that is
+/// `lines_below` lines above the bottom of the scrollback
+fn scroll_position_text(lines_below: StableRowIndex) -> String {
+    if lines_below == 1 {
+        " 1 line \u{2193} ".to_string()
+    } else {
+        format!(" {} lines \u{2193} ", lines_below)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::window::bitmaps::ImageTexture;
+    use ::window::Point;
+
+    fn color(n: u8) -> LinearRgba {
+        LinearRgba::with_rgba(n, n, n, 0xff)
+    }
+
+    fn cursor(shape: CursorShape, visibility: CursorVisibility) -> StableCursorPosition {
+        StableCursorPosition {
+            x: 4,
+            y: 0,
+            shape,
+            visibility,
+        }
+    }
+
+    /// Records the colors chosen for each cell of a row as a string,
+    /// using `.` for the normal colors, `S` for the selection colors
+    /// and `C` for the cursor colors, followed by the cursor shape
+    /// that would be drawn.
+    fn render_row(
+        cursor: StableCursorPosition,
+        selection: Range<usize>,
+        window_focused: bool,
+        blink_phase: CursorVisibility,
+    ) -> String {
+        let config = config::configuration();
+        let palette = ColorPalette::default();
+        let normal = color(1);
+        let selected = color(2);
+        let cursor_color = color(3);
+
+        let mut cells = String::new();
+        let mut shape = None;
+        for cell_idx in 0..8 {
+            let result = cell_fg_bg(
+                ComputeCellFgBgParams {
+                    stable_line_idx: Some(0),
+                    cell_idx,
+                    cursor: &cursor,
+                    selection: &selection,
+                    fg_color: normal,
+                    bg_color: normal,
+                    palette: &palette,
+                    is_active_pane: true,
+                    config: &config,
+                    selection_fg: Some(selected),
+                    selection_bg: selected,
+                    cursor_fg: cursor_color,
+                    cursor_bg: cursor_color,
+                    cursor_border_color: cursor_color,
+                    reverse_video_cursor: false,
+                },
+                window_focused,
+                || blink_phase,
+            );
+
+            let fg = result.fg_color.unwrap_or(normal).tuple();
+            cells.push(if fg == normal.tuple() {
+                '.'
+            } else if fg == selected.tuple() {
+                'S'
+            } else if fg == cursor_color.tuple() {
+                'C'
+            } else {
+                '?'
+            });
+            if result.cursor_shape.is_some() {
+                shape = result.cursor_shape;
+            }
+        }
+        format!("{} {:?}", cells, shape)
+    }
+
+    #[test]
+    fn block_cursor_over_selection() {
+        assert_eq!(
+            render_row(
+                cursor(CursorShape::Default, CursorVisibility::Visible),
+                2..5,
+                true,
+                CursorVisibility::Visible
+            ),
+            "..SSC... Some(SteadyBlock)"
+        );
+    }
+
+    #[test]
+    fn unfocused_window() {
+        assert_eq!(
+            render_row(
+                cursor(CursorShape::Default, CursorVisibility::Visible),
+                2..5,
+                false,
+                CursorVisibility::Visible
+            ),
+            "..SS.... Some(SteadyBlock)"
+        );
+    }
+
+    #[test]
+    fn bar_cursor_keeps_cell_colors() {
+        assert_eq!(
+            render_row(
+                cursor(CursorShape::SteadyBar, CursorVisibility::Visible),
+                0..0,
+                true,
+                CursorVisibility::Visible
+            ),
+            "........ Some(SteadyBar)"
+        );
+    }
+
+    #[test]
+    fn blinking_cursor() {
+        let blinking = cursor(CursorShape::BlinkingBlock, CursorVisibility::Visible);
+        assert_eq!(
+            render_row(blinking, 2..5, true, CursorVisibility::Visible),
+            "..SSC... Some(BlinkingBlock)"
+        );
+        assert_eq!(
+            render_row(blinking, 2..5, true, CursorVisibility::Hidden),
+            "..SSS... None"
+        );
+    }
+
+    #[test]
+    fn reverse_video_cursor() {
+        let config = config::configuration();
+        let palette = ColorPalette::default();
+        let cursor_color = color(3);
+        // A transparent background, as used for the default background
+        // of a transparent window
+        let bg = LinearRgba::with_components(0.5, 0.5, 0.5, 0.0);
+        let fg = color(1);
+
+        let compute = |shape| {
+            cell_fg_bg(
+                ComputeCellFgBgParams {
+                    stable_line_idx: Some(0),
+                    cell_idx: 4,
+                    cursor: &cursor(shape, CursorVisibility::Visible),
+                    selection: &(0..0),
+                    fg_color: fg,
+                    bg_color: bg,
+                    palette: &palette,
+                    is_active_pane: true,
+                    config: &config,
+                    selection_fg: Some(color(2)),
+                    selection_bg: color(2),
+                    cursor_fg: cursor_color,
+                    cursor_bg: cursor_color,
+                    cursor_border_color: cursor_color,
+                    reverse_video_cursor: true,
+                },
+                true,
+                || CursorVisibility::Visible,
+            )
+        };
+
+        let block = compute(CursorShape::SteadyBlock);
+        assert_eq!(block.fg_color.unwrap().tuple(), (0.5, 0.5, 0.5, 1.0));
+        assert_eq!(block.bg_color.tuple(), fg.tuple());
+        assert_eq!(block.cursor_border_color.tuple(), fg.tuple());
+
+        let bar = compute(CursorShape::SteadyBar);
+        assert_eq!(bar.fg_color.unwrap_or(fg).tuple(), fg.tuple());
+        assert_eq!(bar.bg_color.tuple(), bg.tuple());
+        assert_eq!(bar.cursor_border_color.tuple(), fg.tuple());
+    }
+
+    #[test]
+    fn selection_keeps_text_color() {
+        let config = config::configuration();
+        let palette = ColorPalette::default();
+        let selected = color(2);
+
+        let result = cell_fg_bg(
+            ComputeCellFgBgParams {
+                stable_line_idx: Some(0),
+                cell_idx: 2,
+                cursor: &cursor(CursorShape::Default, CursorVisibility::Visible),
+                selection: &(2..5),
+                fg_color: color(1),
+                bg_color: color(1),
+                palette: &palette,
+                is_active_pane: true,
+                config: &config,
+                selection_fg: None,
+                selection_bg: selected,
+                cursor_fg: color(3),
+                cursor_bg: color(3),
+                cursor_border_color: color(3),
+                reverse_video_cursor: false,
+            },
+            true,
+            || CursorVisibility::Visible,
+        );
+        assert!(result.fg_color.is_none());
+        assert_eq!(result.bg_color.tuple(), selected.tuple());
+    }
+
+    #[test]
+    fn hidden_cursor() {
+        assert_eq!(
+            render_row(
+                cursor(CursorShape::Default, CursorVisibility::Hidden),
+                2..5,
+                true,
+                CursorVisibility::Visible
+            ),
+            "..SSS... None"
+        );
+    }
+
+    fn sprite(x: isize, y: isize, width: isize, height: isize) -> Sprite<ImageTexture> {
+        Sprite {
+            texture: Rc::new(ImageTexture::new(64, 64)),
+            coords: Rect::new(Point::new(x, y), Size::new(width, height)),
+        }
+    }
+
+    fn rect(x: isize, y: isize, width: isize, height: isize) -> Rect {
+        Rect::new(Point::new(x, y), Size::new(width, height))
+    }
+
+    #[test]
+    fn narrow_glyph_slice() {
+        let cell = Size::new(8, 16);
+        let (pixel_rect, adjust) = glyph_slice(&sprite(0, 0, 6, 10), 0, 1, cell, 1.0, 1.0, 4.0);
+        assert_eq!(pixel_rect, rect(0, 0, 6, 10));
+        assert_eq!(adjust, (1.0, 4.0, -1.0, -2.0));
+
+        // A glyph rasterized at twice the size is scaled down to fit
+        let (pixel_rect, adjust) = glyph_slice(&sprite(4, 4, 12, 20), 0, 1, cell, 0.5, 0.0, 2.0);
+        assert_eq!(pixel_rect, rect(4, 4, 6, 20));
+        assert_eq!(adjust, (0.0, 2.0, -2.0, -4.0));
+    }
+
+    #[test]
+    fn wide_glyph_slices() {
+        let cell = Size::new(8, 16);
+        let texture = sprite(10, 20, 16, 14);
+
+        // The first cell is shortened by the left bearing of the glyph
+        let (pixel_rect, adjust) = glyph_slice(&texture, 0, 2, cell, 1.0, 1.0, 3.0);
+        assert_eq!(pixel_rect, rect(10, 20, 7, 14));
+        assert_eq!(adjust, (1.0, 3.0, 0.0, 1.0));
+
+        // and the second picks up where the first left off, taking
+        // whatever remains, even if that overflows the cell
+        let (pixel_rect, adjust) = glyph_slice(&texture, 1, 2, cell, 1.0, 1.0, 3.0);
+        assert_eq!(pixel_rect, rect(17, 20, 9, 14));
+        assert_eq!(adjust, (0.0, 3.0, 1.0, 1.0));
+
+        // Cells in the middle of a wider glyph are exactly one cell wide
+        let texture = sprite(0, 0, 24, 14);
+        let (pixel_rect, adjust) = glyph_slice(&texture, 1, 3, cell, 1.0, 0.0, 3.0);
+        assert_eq!(pixel_rect, rect(8, 0, 8, 14));
+        assert_eq!(adjust, (0.0, 3.0, 0.0, 1.0));
+        let (pixel_rect, _) = glyph_slice(&texture, 2, 3, cell, 1.0, 0.0, 3.0);
+        assert_eq!(pixel_rect, rect(16, 0, 8, 14));
+    }
+
+    #[test]
+    fn scroll_position_badge() {
+        assert_eq!(scroll_position_text(1), " 1 line \u{2193} ");
+        assert_eq!(scroll_position_text(2), " 2 lines \u{2193} ");
+        assert_eq!(scroll_position_text(120), " 120 lines \u{2193} ");
+    }
+}
@@ -1,5 +1,6 @@
 use crate::utilsprites::RenderMetrics;
-use ::window::{Dimensions, WindowOps};
+use ::window::{Connection, ConnectionOps, Dimensions, ResizeIncrement, ScreenPoint, WindowOps};
+use config::keyassignment::SetWindowPositionArguments;
 use config::ConfigHandle;
 use mux::Mux;
 use portable_pty::PtySize;
@@ -81,7 +82,7 @@ impl super::TermWindow {
                 pixel_width: cell_dims.cols as u16 * self.render_metrics.cell_size.width as u16,
             };
 
-            let rows = size.rows + if self.show_tab_bar { 1 } else { 0 };
+            let rows = size.rows + self.rows_above_panes() as u16;
             let cols = size.cols;
 
             let pixel_height = (rows * self.render_metrics.cell_size.height as u16)
@@ -107,7 +108,7 @@ impl super::TermWindow {
             );
 
             let rows = (avail_height / self.render_metrics.cell_size.height as usize)
-                .saturating_sub(if self.show_tab_bar { 1 } else { 0 });
+                .saturating_sub(self.rows_above_panes());
             let cols = avail_width / self.render_metrics.cell_size.width as usize;
 
             let size = PtySize {
@@ -153,6 +154,7 @@ impl super::TermWindow {
             }
         };
         self.update_title();
+        self.update_resize_increments();
 
         // Queue up a speculative resize in order to preserve the number of rows+cols
         if let Some(cell_dims) = scale_changed_cells {
@@ -163,6 +165,29 @@ impl super::TermWindow {
         }
     }
 
+    /// Advise the window of the cell size so that interactive resizes
+    /// snap to cell boundaries when `use_resize_increments` is enabled
+    fn update_resize_increments(&mut self) {
+        let incr = if self.config.use_resize_increments && !self.is_full_screen {
+            Some(ResizeIncrement {
+                x: self.render_metrics.cell_size.width as u16,
+                y: self.render_metrics.cell_size.height as u16,
+                base_width: self.config.window_padding.left
+                    + self.effective_right_padding(&self.config),
+                base_height: self.config.window_padding.top + self.config.window_padding.bottom,
+            })
+        } else {
+            None
+        };
+        if incr == self.resize_increments {
+            return;
+        }
+        if let Some(window) = self.window.as_ref() {
+            window.set_resize_increments(incr);
+            self.resize_increments = incr;
+        }
+    }
+
     pub fn current_cell_dimensions(&self) -> RowsAndCols {
         RowsAndCols {
             rows: self.terminal_size.rows as usize,
@@ -175,10 +200,19 @@ impl super::TermWindow {
         let scale_changed =
             dimensions.dpi != self.dimensions.dpi || font_scale != self.fonts.get_font_scale();
 
+        // When only the dpi changed, preserve the number of rows and
+        // columns unless the user prefers to keep the window size
+        let preserve_cells =
+            font_scale != self.fonts.get_font_scale() || self.config.window_size_is_cells;
+
         let scale_changed_cells = if scale_changed {
             let cell_dims = self.current_cell_dimensions();
             self.apply_scale_change(&dimensions, font_scale);
-            Some(cell_dims)
+            if preserve_cells {
+                Some(cell_dims)
+            } else {
+                None
+            }
         } else {
             None
         };
@@ -228,7 +262,9 @@ impl super::TermWindow {
 
         let show_tab_bar = config.enable_tab_bar && !config.hide_tab_bar_if_only_one_tab;
 
-        let rows_with_tab_bar = if show_tab_bar { 1 } else { 0 } + terminal_size.rows;
+        let rows_with_tab_bar = if show_tab_bar { 1 } else { 0 }
+            + if config.pane_title_bar { 1 } else { 0 }
+            + terminal_size.rows;
         let dimensions = Dimensions {
             pixel_width: ((terminal_size.cols * render_metrics.cell_size.width as u16)
                 + config.window_padding.left
@@ -251,6 +287,44 @@ impl super::TermWindow {
         Ok(())
     }
 
+    /// Moves and/or resizes the window relative to a monitor
+    pub fn set_window_position(&mut self, args: &SetWindowPositionArguments) {
+        let window = match self.window.as_ref() {
+            Some(window) => window,
+            None => return,
+        };
+        let screens = Connection::get().unwrap().screens();
+        let screen = match screens.get(args.monitor) {
+            Some(screen) => screen,
+            None => {
+                log::error!(
+                    "SetWindowPosition: monitor {} is not available (found {} monitors)",
+                    args.monitor,
+                    screens.len()
+                );
+                return;
+            }
+        };
+
+        if args.width.is_some() || args.height.is_some() {
+            let width = args
+                .width
+                .map(|w| w.evaluate(screen.size.width))
+                .unwrap_or(self.dimensions.pixel_width as isize);
+            let height = args
+                .height
+                .map(|h| h.evaluate(screen.size.height))
+                .unwrap_or(self.dimensions.pixel_height as isize);
+            window.set_inner_size(width.max(1) as usize, height.max(1) as usize);
+        }
+
+        if args.x.is_some() || args.y.is_some() {
+            let x = args.x.map(|x| x.evaluate(screen.size.width)).unwrap_or(0);
+            let y = args.y.map(|y| y.evaluate(screen.size.height)).unwrap_or(0);
+            window.set_window_position(ScreenPoint::new(screen.origin.x + x, screen.origin.y + y));
+        }
+    }
+
     pub fn effective_right_padding(&self, config: &ConfigHandle) -> u16 {
         effective_right_padding(config, &self.render_metrics)
     }
@@ -0,0 +1,69 @@
+//! Animates changes to the viewport, so that scrolling through the
+//! scrollback glides between positions rather than jumping.
+use config::EasingFunction;
+use std::time::{Duration, Instant};
+use wezterm_term::StableRowIndex;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ScrollAnimation {
+    /// The top row that was displayed when the animation started
+    from: f32,
+    started: Instant,
+}
+
+impl ScrollAnimation {
+    pub fn new(from: f32, now: Instant) -> Self {
+        Self { from, started: now }
+    }
+
+    /// Returns the (fractional) top row to display at `now` while
+    /// moving towards `to`, or None once the animation has finished.
+    /// `to` is supplied on each call rather than being captured when
+    /// the animation starts, so that output that moves the bottom of
+    /// the scrollback is tracked while scrolling back down to it.
+    pub fn top_at(
+        &self,
+        to: StableRowIndex,
+        duration: Duration,
+        easing: EasingFunction,
+        now: Instant,
+    ) -> Option<f32> {
+        let elapsed = now.saturating_duration_since(self.started);
+        if duration == Duration::from_millis(0) || elapsed >= duration {
+            return None;
+        }
+        let progress = easing.evaluate(elapsed.as_secs_f32() / duration.as_secs_f32());
+        Some(self.from + (to as f32 - self.from) * progress)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn glides_to_target() {
+        let start = Instant::now();
+        let duration = Duration::from_millis(100);
+        let at = |anim: &ScrollAnimation, to, millis| {
+            anim.top_at(
+                to,
+                duration,
+                EasingFunction::Linear,
+                start + Duration::from_millis(millis),
+            )
+        };
+
+        let anim = ScrollAnimation::new(100., start);
+        assert_eq!(at(&anim, 50, 0), Some(100.));
+        assert_eq!(at(&anim, 50, 50), Some(75.));
+        // The target can move while the animation is in progress
+        assert_eq!(at(&anim, 60, 50), Some(80.));
+        assert_eq!(at(&anim, 50, 100), None);
+
+        assert_eq!(
+            anim.top_at(50, Duration::from_millis(0), EasingFunction::Linear, start),
+            None
+        );
+    }
+}
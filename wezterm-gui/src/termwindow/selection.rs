@@ -1,38 +1,115 @@
 use crate::selection::{SelectionCoordinate, SelectionMode, SelectionRange};
+use crate::termwindow::export::{self, ExportLine};
 use ::window::WindowOps;
 use mux::pane::Pane;
 use std::rc::Rc;
-use wezterm_term::StableRowIndex;
+use wezterm_term::{ColorAttribute, Line, StableRowIndex};
 
 impl super::TermWindow {
     pub fn selection_text(&self, pane: &Rc<dyn Pane>) -> String {
+        match self.selection(pane.pane_id()).range.as_ref() {
+            Some(sel) => self.text_for_range(pane, sel),
+            None => String::new(),
+        }
+    }
+
+    /// Returns the text of `pane` within the specified range,
+    /// joining wrapped lines together
+    pub fn text_for_range(&self, pane: &Rc<dyn Pane>, range: &SelectionRange) -> String {
         let mut s = String::new();
-        if let Some(sel) = self
-            .selection(pane.pane_id())
-            .range
-            .as_ref()
-            .map(|r| r.normalize())
-        {
-            let mut last_was_wrapped = false;
-            let (first_row, lines) =
-                pane.get_lines_with_hyperlinks_applied(sel.rows(), &self.config.hyperlink_rules);
-            for (idx, line) in lines.iter().enumerate() {
-                let cols = sel.cols_for_row(first_row + idx as StableRowIndex);
-                let last_col_idx = cols.end.min(line.cells().len()).saturating_sub(1);
-                if !s.is_empty() && !last_was_wrapped {
-                    s.push('\n');
-                }
-                s.push_str(line.columns_as_str(cols).trim_end());
+        let sel = range.normalize();
+        let mut last_was_wrapped = false;
+        let (first_row, lines) =
+            pane.get_lines_with_hyperlinks_applied(sel.rows(), &self.config.hyperlink_rules);
+        for (idx, line) in lines.iter().enumerate() {
+            let cols = sel.cols_for_row(first_row + idx as StableRowIndex);
+            let last_col_idx = cols.end.min(line.cells().len()).saturating_sub(1);
+            if !s.is_empty() && !last_was_wrapped {
+                s.push('\n');
+            }
+            s.push_str(line.columns_as_str(cols).trim_end());
 
-                let last_cell = &line.cells()[last_col_idx];
+            // An empty line cannot have wrapped
+            last_was_wrapped = match line.cells().get(last_col_idx) {
                 // TODO: should really test for any unicode whitespace
-                last_was_wrapped = last_cell.attrs().wrapped() && last_cell.str() != " ";
-            }
+                Some(last_cell) => last_cell.attrs().wrapped() && last_cell.str() != " ",
+                None => false,
+            };
         }
 
         s
     }
 
+    /// Returns the selection in `pane` as HTML, or the viewport if
+    /// nothing is selected
+    pub fn selection_html(&mut self, pane: &Rc<dyn Pane>) -> String {
+        let lines = self.export_lines(pane);
+        let palette = self.pane_palette(&**pane);
+        export::to_html(&lines, &palette)
+    }
+
+    /// Returns the selection in `pane` as text with ANSI escape
+    /// sequences, or the viewport if nothing is selected
+    pub fn selection_ansi(&self, pane: &Rc<dyn Pane>) -> String {
+        export::to_ansi(&self.export_lines(pane))
+    }
+
+    fn export_lines(&self, pane: &Rc<dyn Pane>) -> Vec<ExportLine> {
+        let range = self.selection(pane.pane_id()).range;
+        let range = range.unwrap_or_else(|| {
+            let dims = pane.get_dimensions();
+            let top = self
+                .get_viewport(pane.pane_id())
+                .unwrap_or(dims.physical_top);
+            SelectionRange {
+                start: SelectionCoordinate { x: 0, y: top },
+                end: SelectionCoordinate {
+                    x: usize::max_value(),
+                    y: top + dims.viewport_rows as StableRowIndex - 1,
+                },
+            }
+        });
+        self.export_lines_for_range(pane, &range)
+    }
+
+    /// Returns the lines of `pane` within the specified range, keeping
+    /// their attributes but dropping the trailing blank cells
+    fn export_lines_for_range(
+        &self,
+        pane: &Rc<dyn Pane>,
+        range: &SelectionRange,
+    ) -> Vec<ExportLine> {
+        let sel = range.normalize();
+        let (first_row, lines) =
+            pane.get_lines_with_hyperlinks_applied(sel.rows(), &self.config.hyperlink_rules);
+        lines
+            .iter()
+            .enumerate()
+            .map(|(idx, line)| {
+                let cols = sel.cols_for_row(first_row + idx as StableRowIndex);
+                let end = cols.end.min(line.cells().len());
+                let mut cells = line.cells()[cols.start.min(end)..end].to_vec();
+                let wrapped = cells
+                    .last()
+                    .map(|cell| cell.attrs().wrapped() && cell.str() != " ")
+                    .unwrap_or(false);
+                while cells
+                    .last()
+                    .map(|cell| {
+                        cell.str() == " " && cell.attrs().background == ColorAttribute::Default
+                    })
+                    .unwrap_or(false)
+                {
+                    cells.pop();
+                }
+                ExportLine {
+                    line: Line::from_cells(cells),
+                    wrapped,
+                }
+            })
+            .collect()
+    }
+
     pub fn extend_selection_at_mouse_cursor(
         &mut self,
         mode: Option<SelectionMode>,
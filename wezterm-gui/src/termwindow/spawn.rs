@@ -1,18 +1,21 @@
 use crate::termwindow::{ClipboardHelper, MuxWindowId};
 use anyhow::{anyhow, bail};
-use config::keyassignment::{SpawnCommand, SpawnTabDomain};
+use config::keyassignment::{PaneLayout, SpawnCommand, SpawnTabDomain};
 use mux::activity::Activity;
 use mux::domain::DomainState;
-use mux::tab::SplitDirection;
+use mux::pane::Pane;
+use mux::tab::SplitRequest;
 use mux::Mux;
-use portable_pty::{CommandBuilder, PtySize};
+use portable_pty::PtySize;
+use std::rc::Rc;
 use std::sync::Arc;
 
 #[derive(Copy, Debug, Clone, Eq, PartialEq)]
 pub enum SpawnWhere {
     NewWindow,
     NewTab,
-    SplitPane(SplitDirection),
+    SplitPane(SplitRequest),
+    Floating,
 }
 
 impl super::TermWindow {
@@ -60,7 +63,7 @@ impl super::TermWindow {
         size: PtySize,
         src_window_id: MuxWindowId,
         clipboard: ClipboardHelper,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<Rc<dyn Pane>> {
         let mux = Mux::get().unwrap();
         let activity = Activity::new();
         let mux_builder;
@@ -142,34 +145,35 @@ impl super::TermWindow {
             }
         };
 
-        let cmd_builder = if let Some(args) = spawn.args {
-            let mut builder = CommandBuilder::from_argv(args.iter().map(Into::into).collect());
-            for (k, v) in spawn.set_environment_variables.iter() {
-                builder.env(k, v);
-            }
-            if let Some(cwd) = spawn.cwd {
-                builder.cwd(cwd);
-            }
-            Some(builder)
-        } else {
-            None
-        };
+        let cmd_builder = spawn.command_builder();
 
-        match spawn_where {
-            SpawnWhere::SplitPane(direction) => {
+        let pane = match spawn_where {
+            SpawnWhere::SplitPane(request) => {
                 let mux = Mux::get().unwrap();
-                if let Some(tab) = mux.get_active_tab_for_window(target_window_id) {
-                    let pane = tab
-                        .get_active_pane()
-                        .ok_or_else(|| anyhow!("tab to have a pane"))?;
+                let tab = mux
+                    .get_active_tab_for_window(target_window_id)
+                    .ok_or_else(|| anyhow!("there is no active tab while splitting pane!?"))?;
+                let pane = tab
+                    .get_active_pane()
+                    .ok_or_else(|| anyhow!("tab to have a pane"))?;
 
-                    log::trace!("doing split_pane");
-                    domain
-                        .split_pane(cmd_builder, cwd, tab.tab_id(), pane.pane_id(), direction)
-                        .await?;
-                } else {
-                    log::error!("there is no active tab while splitting pane!?");
-                }
+                log::trace!("doing split_pane");
+                domain
+                    .split_pane(cmd_builder, cwd, tab.tab_id(), pane.pane_id(), request)
+                    .await?
+            }
+            SpawnWhere::Floating => {
+                let mux = Mux::get().unwrap();
+                let tab = mux
+                    .get_active_tab_for_window(target_window_id)
+                    .ok_or_else(|| anyhow!("there is no active tab for the floating pane!?"))?;
+
+                let pane = domain
+                    .spawn_floating_pane(cmd_builder, cwd, tab.tab_id())
+                    .await?;
+                let clipboard: Arc<dyn wezterm_term::Clipboard> = Arc::new(clipboard);
+                pane.set_clipboard(&clipboard);
+                pane
             }
             _ => {
                 let tab = domain
@@ -190,11 +194,115 @@ impl super::TermWindow {
                         window.save_and_then_set_active(idx);
                     }
                 }
+                pane
             }
         };
 
         drop(activity);
 
+        Ok(pane)
+    }
+
+    pub fn spawn_tab_layout(&mut self, name: &str) {
+        let layout = match self.config.tab_layouts.get(name) {
+            Some(layout) => layout.clone(),
+            None => {
+                log::error!("There is no tab layout named {}", name);
+                return;
+            }
+        };
+        Self::spawn_layout_impl(
+            &layout,
+            self.terminal_size,
+            self.mux_window_id,
+            ClipboardHelper {
+                window: self.window.as_ref().unwrap().clone(),
+                clipboard_contents: Arc::clone(&self.clipboard_contents),
+            },
+        )
+    }
+
+    pub fn spawn_layout_impl(
+        layout: &PaneLayout,
+        size: PtySize,
+        src_window_id: MuxWindowId,
+        clipboard: ClipboardHelper,
+    ) {
+        let layout = layout.clone();
+
+        promise::spawn::spawn(async move {
+            if let Err(err) =
+                Self::spawn_layout_internal(layout, size, src_window_id, clipboard).await
+            {
+                log::error!("Failed to spawn layout: {:#}", err);
+            }
+        })
+        .detach();
+    }
+
+    /// Spawns a new tab running the command from `layout`, and then
+    /// creates the splits that it describes.  The splits of each pane
+    /// are carved out of that pane in order, so the remaining part of
+    /// the pane shrinks as each split is created.
+    async fn spawn_layout_internal(
+        layout: PaneLayout,
+        size: PtySize,
+        src_window_id: MuxWindowId,
+        clipboard: ClipboardHelper,
+    ) -> anyhow::Result<()> {
+        let mux = Mux::get().unwrap();
+        let activity = Activity::new();
+
+        let root = Self::spawn_command_internal(
+            layout.command.clone(),
+            SpawnWhere::NewTab,
+            size,
+            src_window_id,
+            clipboard,
+        )
+        .await?;
+
+        let mut pending = vec![(Rc::clone(&root), &layout)];
+        while let Some((pane, layout)) = pending.pop() {
+            let (domain_id, _window_id, tab_id) = mux
+                .resolve_pane_id(pane.pane_id())
+                .ok_or_else(|| anyhow!("pane {} has no tab!?", pane.pane_id()))?;
+            let domain = mux
+                .get_domain(domain_id)
+                .ok_or_else(|| anyhow!("pane {} has no domain!?", pane.pane_id()))?;
+
+            for split in &layout.splits {
+                let command = &split.layout.command;
+                let cwd = command
+                    .cwd
+                    .as_ref()
+                    .and_then(|cwd| cwd.to_str().map(|s| s.to_owned()));
+                let new_pane = domain
+                    .split_pane(
+                        command.command_builder(),
+                        cwd,
+                        tab_id,
+                        pane.pane_id(),
+                        SplitRequest {
+                            direction: split.direction,
+                            size: split.size,
+                        },
+                    )
+                    .await?;
+                pending.push((new_pane, &split.layout));
+            }
+        }
+
+        // Leave the focus on the first pane, rather than on
+        // whichever split happened to be created last
+        if let Some((_, _, tab_id)) = mux.resolve_pane_id(root.pane_id()) {
+            if let Some(tab) = mux.get_tab(tab_id) {
+                tab.set_active_pane(&root);
+            }
+        }
+
+        drop(activity);
+
         Ok(())
     }
 
@@ -0,0 +1,135 @@
+//! The tray icon that is shown when `enable_tray_icon` is set.
+//! Its menu opens a new window, shows or hides the windows, activates
+//! a particular window or quits, which is handy when the windows are
+//! hidden, such as while the drop down window is dismissed.
+use crate::frontend::front_end;
+use crate::TermWindow;
+use ::window::{Connection, ConnectionOps, MenuEntry, Window, WindowOps};
+use config::keyassignment::KeyAssignment;
+use mux::window::WindowId as MuxWindowId;
+use mux::Mux;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+const NEW_WINDOW: usize = 0;
+const SHOW_HIDE: usize = 1;
+const QUIT: usize = 2;
+/// The items that activate a window follow the fixed items
+const FIRST_WINDOW: usize = 3;
+
+/// Describes a window by the title of its active pane
+fn window_label(idx: usize, mux_window_id: MuxWindowId) -> String {
+    let title = Mux::get()
+        .unwrap()
+        .get_active_tab_for_window(mux_window_id)
+        .and_then(|tab| tab.get_active_pane())
+        .map(|pane| pane.get_title())
+        .unwrap_or_default();
+    format!("{}: {}", idx + 1, title)
+}
+
+fn item(id: usize, label: &str) -> MenuEntry {
+    MenuEntry::Item {
+        id,
+        label: label.to_string(),
+        key_hint: None,
+    }
+}
+
+fn gui_windows() -> Vec<(MuxWindowId, Window)> {
+    front_end().map(|fe| fe.gui_windows()).unwrap_or_default()
+}
+
+/// Performs `action` in the first window, which is shown so that any
+/// prompt that results is visible
+fn perform_in_first_window(action: KeyAssignment) {
+    if let Some((_, window)) = gui_windows().into_iter().next() {
+        window.show();
+        window.focus();
+        window.apply(move |tw, _ops| {
+            if let Some(term_window) = tw.downcast_mut::<TermWindow>() {
+                term_window.perform_action_in_active_pane(&action)?;
+            }
+            Ok(())
+        });
+    }
+}
+
+fn show_or_hide(hidden: &Cell<bool>) {
+    if config::configuration().drop_down_window.is_some() {
+        if let Some(fe) = front_end() {
+            fe.toggle_drop_down();
+        }
+        return;
+    }
+    let hide = !hidden.get();
+    for (_, window) in gui_windows() {
+        if hide {
+            window.hide();
+        } else {
+            window.show();
+            window.focus();
+        }
+    }
+    hidden.set(hide);
+}
+
+/// Adds the tray icon
+pub fn add_tray_icon(conn: &Connection) -> anyhow::Result<()> {
+    // The windows that are listed in the menu, in the order in which
+    // they are listed, so that a selection can be mapped back to the
+    // window that it was showing
+    let listed = Rc::new(RefCell::new(vec![]));
+    let hidden = Rc::new(Cell::new(false));
+
+    let entries = {
+        let listed = Rc::clone(&listed);
+        let hidden = Rc::clone(&hidden);
+        move || {
+            let windows = gui_windows();
+            let mut entries = vec![
+                item(NEW_WINDOW, "New Window"),
+                item(
+                    SHOW_HIDE,
+                    if hidden.get() {
+                        "Show Windows"
+                    } else {
+                        "Hide Windows"
+                    },
+                ),
+            ];
+            if !windows.is_empty() {
+                entries.push(MenuEntry::Separator);
+            }
+            for (idx, (mux_window_id, _)) in windows.iter().enumerate() {
+                entries.push(item(FIRST_WINDOW + idx, &window_label(idx, *mux_window_id)));
+            }
+            entries.push(MenuEntry::Separator);
+            entries.push(item(QUIT, "Quit WezTerm"));
+
+            *listed.borrow_mut() = windows.into_iter().map(|(id, _)| id).collect();
+            entries
+        }
+    };
+
+    let callback = move |id| match id {
+        NEW_WINDOW => perform_in_first_window(KeyAssignment::SpawnWindow),
+        SHOW_HIDE => show_or_hide(&hidden),
+        QUIT => perform_in_first_window(KeyAssignment::QuitApplication),
+        id => {
+            let mux_window_id = listed.borrow().get(id - FIRST_WINDOW).copied();
+            let window = mux_window_id.and_then(|mux_window_id| {
+                gui_windows()
+                    .into_iter()
+                    .find(|(id, _)| *id == mux_window_id)
+                    .map(|(_, window)| window)
+            });
+            if let Some(window) = window {
+                window.show();
+                window.focus();
+            }
+        }
+    };
+
+    conn.set_tray_icon(entries, callback)
+}
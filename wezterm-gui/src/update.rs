@@ -145,6 +145,37 @@ pub fn get_nightly_release_info() -> anyhow::Result<Release> {
     get_github_release_info("https://api.github.com/repos/wez/wezterm/releases/tags/nightly")
 }
 
+/// Returns the link to the changelog entry for `release`
+pub fn changelog_url(release: &Release) -> String {
+    format!(
+        "https://wezfurlong.org/wezterm/changelog.html#{}",
+        release.tag_name
+    )
+}
+
+/// Returns true if `release` is newer than this build
+pub fn is_newer_than_current(release: &Release) -> bool {
+    release.tag_name.as_str() > wezterm_version()
+}
+
+/// Returns true if the release notification should be shown for
+/// `latest`.  A notification is shown only the first time that a
+/// release is seen, so that dismissing it dismisses it for good.
+fn should_notify(latest: &Release, previous: Option<&Release>) -> bool {
+    is_newer_than_current(latest)
+        && previous.map_or(true, |previous| previous.tag_name != latest.tag_name)
+}
+
+fn update_file_name() -> std::path::PathBuf {
+    config::RUNTIME_DIR.join("check_update")
+}
+
+/// Returns the release info that was recorded by the most recent check
+fn load_last_release_info() -> Option<Release> {
+    let data = std::fs::read(update_file_name()).ok()?;
+    serde_json::from_slice(&data).ok()
+}
+
 lazy_static::lazy_static! {
     static ref UPDATER_WINDOW: Mutex<Option<ConnectionUI>> = Mutex::new(None);
 }
@@ -175,10 +206,7 @@ fn show_update_available(release: Release) {
         "https://wezfurlong.org/wezterm/installation.html"
     };
 
-    let change_log = format!(
-        "https://wezfurlong.org/wezterm/changelog.html#{}",
-        release.tag_name
-    );
+    let change_log = changelog_url(&release);
 
     let brief_blurb = release
         .body
@@ -261,16 +289,9 @@ pub fn load_last_release_info_and_set_banner() {
         return;
     }
 
-    let update_file_name = config::RUNTIME_DIR.join("check_update");
-    if let Ok(data) = std::fs::read(update_file_name) {
-        let latest: Release = match serde_json::from_slice(&data) {
-            Ok(d) => d,
-            Err(_) => return,
-        };
-
-        let current = wezterm_version();
+    if let Some(latest) = load_last_release_info() {
         let force_ui = std::env::var_os("WEZTERM_ALWAYS_SHOW_UPDATE_UI").is_some();
-        if latest.tag_name.as_str() <= current && !force_ui {
+        if !is_newer_than_current(&latest) && !force_ui {
             return;
         }
 
@@ -280,10 +301,7 @@ pub fn load_last_release_info_and_set_banner() {
 
 fn set_banner_from_release_info(latest: &Release) {
     let mux = crate::Mux::get().unwrap();
-    let url = format!(
-        "https://wezfurlong.org/wezterm/changelog.html#{}",
-        latest.tag_name
-    );
+    let url = changelog_url(latest);
 
     let icon = ITermFileData {
         name: None,
@@ -322,8 +340,7 @@ fn set_banner_from_release_info(latest: &Release) {
 }
 
 fn schedule_set_banner_from_release_info(latest: &Release) {
-    let current = wezterm_version();
-    if latest.tag_name.as_str() <= current {
+    if !is_newer_than_current(latest) {
         return;
     }
     promise::spawn::spawn_into_main_thread({
@@ -346,7 +363,7 @@ fn update_checker() {
 
     let force_ui = std::env::var_os("WEZTERM_ALWAYS_SHOW_UPDATE_UI").is_some();
 
-    let update_file_name = config::RUNTIME_DIR.join("check_update");
+    let update_file_name = update_file_name();
     let delay = update_file_name
         .metadata()
         .and_then(|metadata| metadata.modified())
@@ -360,25 +377,26 @@ fn update_checker() {
     std::thread::sleep(if force_ui { initial_interval } else { delay });
 
     loop {
+        // Checking can be turned off again by reloading the configuration
+        if !configuration().check_for_updates {
+            std::thread::sleep(update_interval);
+            continue;
+        }
+
         if let Ok(latest) = get_latest_release_info() {
             schedule_set_banner_from_release_info(&latest);
-            let current = wezterm_version();
-            if latest.tag_name.as_str() > current || force_ui {
+            let previous = load_last_release_info();
+            if should_notify(&latest, previous.as_ref()) || force_ui {
                 log::info!(
                     "latest release {} is newer than current build {}",
                     latest.tag_name,
-                    current
-                );
-
-                let url = format!(
-                    "https://wezfurlong.org/wezterm/changelog.html#{}",
-                    latest.tag_name
+                    wezterm_version()
                 );
 
                 persistent_toast_notification_with_click_to_open_url(
                     "WezTerm Update Available",
                     "Click to see what's new",
-                    &url,
+                    &changelog_url(&latest),
                 );
 
                 show_update_available(latest.clone());
@@ -419,6 +437,26 @@ pub fn start_update_checker() {
 mod test {
     use super::*;
 
+    fn release(tag_name: &str) -> Release {
+        Release {
+            url: String::new(),
+            body: String::new(),
+            html_url: String::new(),
+            tag_name: tag_name.to_string(),
+            assets: vec![],
+        }
+    }
+
+    #[test]
+    fn notify_once_per_release() {
+        let newer = release("99990101-000000-00000000");
+        let older = release("20000101-000000-00000000");
+        assert!(should_notify(&newer, None));
+        assert!(should_notify(&newer, Some(&older)));
+        assert!(!should_notify(&newer, Some(&newer)));
+        assert!(!should_notify(&older, None));
+    }
+
     #[test]
     fn classify_names() {
         assert_eq!(
@@ -6,6 +6,7 @@ pub struct PixelUnit;
 pub struct ScreenPixelUnit;
 pub type Point = euclid::Point2D<isize, PixelUnit>;
 pub type ScreenPoint = euclid::Point2D<isize, ScreenPixelUnit>;
+pub type ScreenRect = euclid::Rect<isize, ScreenPixelUnit>;
 
 /// Which key is pressed.  Not all of these are probable to appear
 /// on most systems.  A lot of this list is @wez trawling docs and
@@ -158,6 +159,15 @@ pub enum MouseEventKind {
     Release(MousePress),
     VertWheel(i16),
     HorzWheel(i16),
+    /// A high resolution vertical scroll, such as from a touchpad,
+    /// measured in pixels rather than lines.  As with VertWheel,
+    /// positive values scroll up.
+    VertPixelWheel(i16),
+    /// The fingers were lifted from the touchpad, ending a sequence
+    /// of VertPixelWheel events.  This is only sent on systems that
+    /// don't apply momentum to the scroll themselves, so that the
+    /// application can continue the scroll.
+    PixelWheelStop,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -171,6 +181,29 @@ pub struct MouseEvent {
     pub modifiers: Modifiers,
 }
 
+/// The direction in which the fingers moved during a swipe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// A touchpad gesture
+#[derive(Debug, Clone, PartialEq)]
+pub enum GestureEvent {
+    /// The fingers are pinching.  `delta` is the change in
+    /// magnification since the previous event; positive values
+    /// mean that the fingers are moving apart to zoom in.
+    Pinch { delta: f64 },
+    /// The fingers swiped in `direction` and were lifted
+    Swipe {
+        direction: SwipeDirection,
+        fingers: u8,
+    },
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct KeyEvent {
     /// Which key was pressed.
@@ -0,0 +1,303 @@
+//! Decides what each client of the mux server is allowed to do.
+//! Clients of a unix socket are identified by the uid of the peer
+//! process, while TLS clients may be required to present a shared
+//! secret in addition to their certificate.
+use anyhow::bail;
+use codec::*;
+use config::{TlsDomainServer, UnixDomain};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AccessLevel {
+    /// The client must authenticate before it can see any panes
+    Unauthenticated,
+    /// The client may view panes, but not send input to them,
+    /// change them or spawn new ones
+    ReadOnly,
+    Full,
+}
+
+impl AccessLevel {
+    /// Returns the access that is needed to process `pdu`
+    pub fn required_for(pdu: &Pdu) -> Self {
+        match pdu {
+            Pdu::Ping(_)
+            | Pdu::GetCodecVersion(_)
+            | Pdu::Handshake(_)
+            | Pdu::Authenticate(_)
+            | Pdu::SetReadOnly(_)
+            | Pdu::Invalid { .. } => Self::Unauthenticated,
+
+            // These are only ever sent to the client, and are
+            // rejected if we receive them
+            Pdu::ErrorResponse(_)
+            | Pdu::Pong(_)
+            | Pdu::ListPanesResponse(_)
+            | Pdu::SpawnResponse(_)
+            | Pdu::UnitResponse(_)
+            | Pdu::SetClipboard(_)
+            | Pdu::GetLinesResponse(_)
+            | Pdu::GetPaneRenderChangesResponse(_)
+            | Pdu::GetCodecVersionResponse(_)
+            | Pdu::GetTlsCredsResponse(_)
+            | Pdu::LivenessResponse(_)
+            | Pdu::SearchScrollbackResponse(_)
+            | Pdu::HandshakeResponse(_)
+            | Pdu::AuthenticateResponse(_)
+            | Pdu::PaneWriterChanged(_) => Self::Unauthenticated,
+
+            Pdu::ListPanes(_)
+            | Pdu::GetLines(_)
+            | Pdu::GetPaneRenderChanges(_)
+            | Pdu::SearchScrollbackRequest(_)
+            | Pdu::SetClientName(_) => Self::ReadOnly,
+
+            Pdu::Spawn(_)
+            | Pdu::SpawnV2(_)
+            | Pdu::SplitPane(_)
+            | Pdu::WriteToPane(_)
+            | Pdu::SendKeyDown(_)
+            | Pdu::SendMouseEvent(_)
+            | Pdu::SendPaste(_)
+            | Pdu::Resize(_)
+            | Pdu::KillPane(_)
+            | Pdu::SetPaneZoomed(_)
+            | Pdu::SetPaneInputMode(_)
+            | Pdu::SetWindowPosition(_)
+            | Pdu::CapturePane(_)
+            | Pdu::SetLogFilters(_)
+            // The credentials allow connecting with full access
+            | Pdu::GetTlsCreds(_) => Self::Full,
+        }
+    }
+}
+
+/// The access granted to a client when it connects, along with the
+/// shared secrets that it can present to change it
+#[derive(Debug, Clone)]
+pub struct AccessPolicy {
+    pub initial: AccessLevel,
+    token: Option<String>,
+    read_only_token: Option<String>,
+    /// The uid of a unix peer that is running as a different user
+    /// than the server, as reported by the operating system
+    other_peer_uid: Option<u32>,
+}
+
+impl AccessPolicy {
+    /// Clients that are allowed to connect at all have full access
+    pub fn full() -> Self {
+        Self::with_level(AccessLevel::Full)
+    }
+
+    pub fn with_level(initial: AccessLevel) -> Self {
+        Self {
+            initial,
+            token: None,
+            read_only_token: None,
+            other_peer_uid: None,
+        }
+    }
+
+    /// Clients must present one of the tokens configured for the
+    /// server, if any, to gain access
+    pub fn for_tls_server(tls_server: &TlsDomainServer) -> Self {
+        let token = tls_server.auth_token.clone();
+        let read_only_token = tls_server.read_only_auth_token.clone();
+        Self {
+            initial: if token.is_some() || read_only_token.is_some() {
+                AccessLevel::Unauthenticated
+            } else {
+                AccessLevel::Full
+            },
+            token,
+            read_only_token,
+            other_peer_uid: None,
+        }
+    }
+
+    /// Returns the access granted to the peer with `uid`, or an
+    /// error if it may not connect at all
+    pub fn for_unix_peer(unix_dom: &UnixDomain, uid: u32, our_uid: u32) -> anyhow::Result<Self> {
+        if uid == our_uid {
+            Ok(Self::full())
+        } else if unix_dom.read_only_peer_uids.contains(&uid) {
+            Ok(Self {
+                other_peer_uid: Some(uid),
+                ..Self::with_level(AccessLevel::ReadOnly)
+            })
+        } else {
+            bail!(
+                "peer uid {} is not permitted to connect to the server running as uid {}",
+                uid,
+                our_uid
+            );
+        }
+    }
+
+    /// Returns the access granted by presenting `token`.
+    /// If the server doesn't use tokens, the current access is kept.
+    pub fn authenticate(&self, token: &str, current: AccessLevel) -> anyhow::Result<AccessLevel> {
+        if self.token.is_none() && self.read_only_token.is_none() {
+            return Ok(current);
+        }
+        if let Some(expected) = &self.token {
+            if constant_time_eq(expected.as_bytes(), token.as_bytes()) {
+                return Ok(AccessLevel::Full);
+            }
+        }
+        if let Some(expected) = &self.read_only_token {
+            if constant_time_eq(expected.as_bytes(), token.as_bytes()) {
+                return Ok(AccessLevel::ReadOnly);
+            }
+        }
+        bail!("the authentication token was not accepted");
+    }
+
+    /// Returns the name that is shown to other clients for the client
+    /// with `client_id`.  `claimed` is whatever the client said its
+    /// name is, so it is only ever used for display: it is stripped
+    /// of control characters, limited in length and, for a peer that
+    /// is running as another user, qualified with its actual uid.
+    pub fn client_label(&self, claimed: &str, client_id: usize) -> String {
+        let name: String = claimed
+            .chars()
+            .filter(|c| !c.is_control())
+            .take(MAX_CLIENT_NAME_LEN)
+            .collect();
+        let name = name.trim();
+        let name = if name.is_empty() {
+            format!("client {}", client_id)
+        } else {
+            name.to_string()
+        };
+        match self.other_peer_uid {
+            Some(uid) => format!("{} (uid {})", name, uid),
+            None => name,
+        }
+    }
+}
+
+const MAX_CLIENT_NAME_LEN: usize = 64;
+
+/// Compares two secrets without revealing, through the time taken,
+/// how long a prefix of them matches
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter()
+        .zip(b.iter())
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+        == 0
+}
+
+/// Returns the uid of the process at the other end of `stream`
+#[cfg(unix)]
+pub fn peer_uid(stream: &crate::UnixStream) -> anyhow::Result<u32> {
+    use anyhow::Context;
+    use std::os::unix::io::AsRawFd;
+
+    let fd = stream.as_raw_fd();
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        let mut cred: libc::ucred = unsafe { std::mem::zeroed() };
+        let mut len = std::mem::size_of::<libc::ucred>() as libc::socklen_t;
+        let res = unsafe {
+            libc::getsockopt(
+                fd,
+                libc::SOL_SOCKET,
+                libc::SO_PEERCRED,
+                &mut cred as *mut libc::ucred as *mut libc::c_void,
+                &mut len,
+            )
+        };
+        if res != 0 {
+            return Err(std::io::Error::last_os_error()).context("getsockopt SO_PEERCRED");
+        }
+        Ok(cred.uid)
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    {
+        let mut uid = 0;
+        let mut gid = 0;
+        if unsafe { libc::getpeereid(fd, &mut uid, &mut gid) } != 0 {
+            return Err(std::io::Error::last_os_error()).context("getpeereid");
+        }
+        Ok(uid)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tokens() {
+        let open = AccessPolicy::for_tls_server(&TlsDomainServer::default());
+        assert_eq!(open.initial, AccessLevel::Full);
+        assert_eq!(
+            open.authenticate("anything", open.initial).unwrap(),
+            AccessLevel::Full
+        );
+
+        let tls_server = TlsDomainServer {
+            auth_token: Some("secret".to_string()),
+            read_only_auth_token: Some("viewer".to_string()),
+            ..Default::default()
+        };
+        let policy = AccessPolicy::for_tls_server(&tls_server);
+        assert_eq!(policy.initial, AccessLevel::Unauthenticated);
+        assert_eq!(
+            policy.authenticate("secret", policy.initial).unwrap(),
+            AccessLevel::Full
+        );
+        assert_eq!(
+            policy.authenticate("viewer", policy.initial).unwrap(),
+            AccessLevel::ReadOnly
+        );
+        assert!(policy.authenticate("secre", policy.initial).is_err());
+        assert!(policy.authenticate("", policy.initial).is_err());
+    }
+
+    #[test]
+    fn unix_peers() {
+        let unix_dom = UnixDomain {
+            read_only_peer_uids: vec![1001],
+            ..Default::default()
+        };
+        assert_eq!(
+            AccessPolicy::for_unix_peer(&unix_dom, 1000, 1000)
+                .unwrap()
+                .initial,
+            AccessLevel::Full
+        );
+        assert_eq!(
+            AccessPolicy::for_unix_peer(&unix_dom, 1001, 1000)
+                .unwrap()
+                .initial,
+            AccessLevel::ReadOnly
+        );
+        assert!(AccessPolicy::for_unix_peer(&unix_dom, 1002, 1000).is_err());
+    }
+
+    #[test]
+    fn client_labels() {
+        let owner = AccessPolicy::full();
+        assert_eq!(owner.client_label("wez@host", 3), "wez@host");
+        assert_eq!(owner.client_label(" \x1b[31mwez\n ", 3), "[31mwez");
+        assert_eq!(owner.client_label("\u{7}", 3), "client 3");
+        assert_eq!(
+            owner.client_label(&"x".repeat(100), 3).len(),
+            MAX_CLIENT_NAME_LEN
+        );
+
+        let unix_dom = UnixDomain {
+            read_only_peer_uids: vec![1001],
+            ..Default::default()
+        };
+        let other = AccessPolicy::for_unix_peer(&unix_dom, 1001, 1000).unwrap();
+        assert_eq!(other.client_label("root@host", 4), "root@host (uid 1001)");
+    }
+}
@@ -1,3 +1,4 @@
+use crate::access::AccessPolicy;
 use crate::sessionhandler::{PduSender, SessionHandler};
 use crate::UnixStream;
 use anyhow::Context;
@@ -23,7 +24,7 @@ enum Item {
     Readable,
 }
 
-pub async fn process<T>(stream: T) -> anyhow::Result<()>
+pub async fn process<T>(stream: T, access: AccessPolicy) -> anyhow::Result<()>
 where
     T: 'static,
     T: std::io::Read,
@@ -32,10 +33,10 @@ where
     T: std::fmt::Debug,
 {
     let stream = smol::Async::new(stream)?;
-    process_async(stream).await
+    process_async(stream, access).await
 }
 
-pub async fn process_async<T>(mut stream: Async<T>) -> anyhow::Result<()>
+pub async fn process_async<T>(mut stream: Async<T>, access: AccessPolicy) -> anyhow::Result<()>
 where
     T: 'static,
     T: std::io::Read,
@@ -54,7 +55,7 @@ where
                 .map_err(|e| anyhow::anyhow!("{:?}", e))
         }
     });
-    let mut handler = SessionHandler::new(pdu_sender);
+    let mut handler = SessionHandler::new(pdu_sender, access);
 
     {
         let mux = Mux::get().expect("to be running on gui thread");
@@ -85,7 +86,17 @@ where
                 // FIXME: queue notification to send to client!
                 handler.schedule_pane_push(pane_id);
             }
+            Ok(Item::Notif(MuxNotification::PaneWriterChanged {
+                pane_id,
+                client_id,
+                client_name,
+            })) => {
+                handler.pane_writer_changed(pane_id, client_id, client_name);
+            }
             Ok(Item::Notif(MuxNotification::WindowCreated(_window_id))) => {}
+            Ok(Item::Notif(MuxNotification::SetWindowPosition { .. })) => {}
+            Ok(Item::Notif(MuxNotification::Capture { .. })) => {}
+            Ok(Item::Notif(MuxNotification::WindowRemoved(_window_id))) => {}
             Err(err) => {
                 log::error!("process_async Err {}", err);
                 return Ok(());
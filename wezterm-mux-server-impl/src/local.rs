@@ -1,28 +1,61 @@
-use crate::UnixListener;
+use crate::access::AccessPolicy;
+use crate::{UnixListener, UnixStream};
 use anyhow::{anyhow, Context as _};
 use config::{create_user_owned_dirs, UnixDomain};
 use promise::spawn::spawn_into_main_thread;
 
 pub struct LocalListener {
     listener: UnixListener,
+    unix_dom: UnixDomain,
 }
 
 impl LocalListener {
-    pub fn new(listener: UnixListener) -> Self {
-        Self { listener }
+    pub fn new(listener: UnixListener, unix_dom: &UnixDomain) -> Self {
+        Self {
+            listener,
+            unix_dom: unix_dom.clone(),
+        }
     }
 
     pub fn with_domain(unix_dom: &UnixDomain) -> anyhow::Result<Self> {
         let listener = safely_create_sock_path(unix_dom)?;
-        Ok(Self::new(listener))
+        Ok(Self::new(listener, unix_dom))
+    }
+
+    /// Determines what the process at the other end of `stream`
+    /// is allowed to do, based on the user that it is running as.
+    /// The permissions on the socket directory are the only
+    /// protection on Windows, where the peer can't be identified,
+    /// and under WSL, where the peer may be a Windows process.
+    fn access_for_peer(&self, stream: &UnixStream) -> anyhow::Result<AccessPolicy> {
+        #[cfg(unix)]
+        {
+            if config::running_under_wsl() || self.unix_dom.skip_permissions_check {
+                return Ok(AccessPolicy::full());
+            }
+            let uid = crate::access::peer_uid(stream)?;
+            AccessPolicy::for_unix_peer(&self.unix_dom, uid, unsafe { libc::getuid() })
+        }
+        #[cfg(windows)]
+        {
+            let _ = stream;
+            Ok(AccessPolicy::full())
+        }
     }
 
     pub fn run(&mut self) {
         for stream in self.listener.incoming() {
             match stream {
                 Ok(stream) => {
+                    let access = match self.access_for_peer(&stream) {
+                        Ok(access) => access,
+                        Err(err) => {
+                            log::error!("rejecting connection: {:#}", err);
+                            continue;
+                        }
+                    };
                     spawn_into_main_thread(async move {
-                        crate::dispatch::process(stream).await.map_err(|e| {
+                        crate::dispatch::process(stream, access).await.map_err(|e| {
                             log::error!("{:#}", e);
                             e
                         })
@@ -52,26 +85,7 @@ fn safely_create_sock_path(unix_dom: &UnixDomain) -> anyhow::Result<UnixListener
 
     create_user_owned_dirs(sock_dir)?;
 
-    #[cfg(unix)]
-    {
-        use config::running_under_wsl;
-        use std::os::unix::fs::PermissionsExt;
-
-        if !running_under_wsl() && !unix_dom.skip_permissions_check {
-            // Let's be sure that the ownership looks sane
-            let meta = sock_dir.symlink_metadata()?;
-
-            let permissions = meta.permissions();
-            if (permissions.mode() & 0o22) != 0 {
-                anyhow::bail!(
-                    "The permissions for {} are insecure and currently \
-                     allow other users to write to it (permissions={:?})",
-                    sock_dir.display(),
-                    permissions
-                );
-            }
-        }
-    }
+    unix_dom.check_socket_permissions()?;
 
     // We want to remove the socket if it exists.
     // However, on windows, we can't tell if the unix domain socket
@@ -1,3 +1,4 @@
+use crate::access::{AccessLevel, AccessPolicy};
 use crate::PKI;
 use anyhow::anyhow;
 use codec::*;
@@ -5,15 +6,17 @@ use config::keyassignment::SpawnTabDomain;
 use mux::pane::{Pane, PaneId};
 use mux::renderable::{RenderableDimensions, StableCursorPosition};
 use mux::tab::TabId;
-use mux::Mux;
+use mux::{Mux, MuxNotification};
 use portable_pty::PtySize;
 use promise::spawn::spawn_into_main_thread;
 use rangeset::RangeSet;
 use std::collections::HashMap;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 use url::Url;
+use wezterm_term::color::{ColorPalette, RgbColor};
 use wezterm_term::terminal::{Clipboard, ClipboardSelection};
 use wezterm_term::StableRowIndex;
 
@@ -43,9 +46,31 @@ struct PerPane {
     dimensions: RenderableDimensions,
     dirty_lines: RangeSet<StableRowIndex>,
     mouse_grabbed: bool,
+    cursor_color: Option<RgbColor>,
+    /// The cursor color from the configuration, along with the
+    /// generation of the configuration that it was taken from
+    configured_cursor_color: Option<(usize, RgbColor)>,
+    /// The bonus lines that were most recently sent to the client
+    sent_lines: LineDeltaBase,
 }
 
 impl PerPane {
+    /// Returns the cursor color from the configuration.  Building the
+    /// palette is relatively expensive, so it is only done when the
+    /// configuration has been reloaded.
+    fn configured_cursor_color(&mut self) -> RgbColor {
+        let config = config::configuration();
+        match self.configured_cursor_color {
+            Some((generation, color)) if generation == config.generation() => color,
+            _ => {
+                let palette: ColorPalette = config.resolved_palette.clone().into();
+                self.configured_cursor_color
+                    .replace((config.generation(), palette.cursor_bg));
+                palette.cursor_bg
+            }
+        }
+    }
+
     fn compute_changes(
         &mut self,
         pane: &Rc<dyn Pane>,
@@ -77,6 +102,15 @@ impl PerPane {
             changed = true;
         }
 
+        // Only a color that differs from the configuration is sent,
+        // so that the client continues to use its own configured color
+        // until the application picks one
+        let configured = self.configured_cursor_color();
+        let cursor_color = Some(pane.palette().cursor_bg).filter(|c| *c != configured);
+        if cursor_color != self.cursor_color {
+            changed = true;
+        }
+
         let mut all_dirty_lines =
             pane.get_dirty_lines(0..dims.physical_top + dims.viewport_rows as StableRowIndex);
         let dirty_delta = all_dirty_lines.difference(&self.dirty_lines);
@@ -114,9 +148,10 @@ impl PerPane {
         self.dimensions = dims;
         self.dirty_lines = all_dirty_lines;
         self.mouse_grabbed = mouse_grabbed;
+        self.cursor_color = cursor_color;
 
         let dirty_lines = dirty_delta.iter().cloned().collect();
-        let bonus_lines = bonus_lines.into();
+        let bonus_lines = SerializedLines::with_deltas(bonus_lines, &mut self.sent_lines);
         Some(GetPaneRenderChangesResponse {
             pane_id: pane.pane_id(),
             mouse_grabbed,
@@ -127,6 +162,7 @@ impl PerPane {
             bonus_lines,
             working_dir: working_dir.map(Into::into),
             input_serial: force_with_input_serial,
+            cursor_color,
         })
     }
 
@@ -153,15 +189,104 @@ fn maybe_push_pane_changes(
 pub struct SessionHandler {
     to_write_tx: PduSender,
     per_pane: HashMap<TabId, Arc<Mutex<PerPane>>>,
+    /// Populated by the client's Handshake; clients that predate
+    /// the handshake are assumed to have no optional capabilities.
+    peer_capabilities: Vec<String>,
+    policy: AccessPolicy,
+    /// What the client is currently allowed to do
+    access: AccessLevel,
+    /// Identifies this client to the other clients that share its panes
+    client_id: usize,
+    /// The name shown to the other clients; see AccessPolicy::client_label
+    client_name: String,
+}
+
+static NEXT_CLIENT_ID: AtomicUsize = AtomicUsize::new(1);
+
+lazy_static::lazy_static! {
+    /// The client that most recently sent input to each pane
+    static ref PANE_WRITERS: Mutex<HashMap<PaneId, usize>> = Mutex::new(HashMap::new());
+}
+
+/// Records that the client identified by `client_id` sent input to
+/// `pane_id`, letting the other clients know if that is a change
+fn note_pane_writer(pane_id: PaneId, client_id: usize, client_name: &str) {
+    let mux = Mux::get().unwrap();
+    let changed = {
+        let mut writers = PANE_WRITERS.lock().unwrap();
+        writers.retain(|&id, _| id == pane_id || mux.get_pane(id).is_some());
+        writers.insert(pane_id, client_id) != Some(client_id)
+    };
+    if changed {
+        mux.notify(MuxNotification::PaneWriterChanged {
+            pane_id,
+            client_id,
+            client_name: client_name.to_string(),
+        });
+    }
+}
+
+/// Delivers input that was directed at `pane_id` to each of the panes
+/// that should receive it, according to their input modes.  A failure
+/// to deliver to one of them is logged rather than preventing the
+/// input from reaching the others.
+fn send_to_input_targets<F>(mux: &Mux, pane_id: PaneId, mut send: F)
+where
+    F: FnMut(&Rc<dyn Pane>) -> anyhow::Result<()>,
+{
+    for target in mux.resolve_input_targets(pane_id) {
+        if let Err(err) = send(&target) {
+            log::error!(
+                "sending input for pane {} to pane {}: {:#}",
+                pane_id,
+                target.pane_id(),
+                err
+            );
+        }
+    }
 }
 
 impl SessionHandler {
-    pub fn new(to_write_tx: PduSender) -> Self {
+    pub fn new(to_write_tx: PduSender, policy: AccessPolicy) -> Self {
+        let client_id = NEXT_CLIENT_ID.fetch_add(1, Ordering::Relaxed);
+        let client_name = policy.client_label("", client_id);
         Self {
             to_write_tx,
             per_pane: HashMap::new(),
+            peer_capabilities: vec![],
+            access: policy.initial,
+            policy,
+            client_id,
+            client_name,
         }
     }
+
+    /// Returns true if both the client and this server support
+    /// the named optional protocol feature
+    pub fn peer_has_capability(&self, capability: &str) -> bool {
+        CODEC_CAPABILITIES.contains(&capability)
+            && self.peer_capabilities.iter().any(|c| c == capability)
+    }
+
+    /// Lets the client know that a different client, or it, has
+    /// started sending input to a pane that it is displaying
+    pub fn pane_writer_changed(&mut self, pane_id: PaneId, client_id: usize, client_name: String) {
+        if !self.per_pane.contains_key(&pane_id) || !self.peer_has_capability("pane_writer") {
+            return;
+        }
+        let writer = if client_id == self.client_id {
+            None
+        } else {
+            Some(client_name)
+        };
+        self.to_write_tx
+            .send(DecodedPdu {
+                pdu: Pdu::PaneWriterChanged(PaneWriterChanged { pane_id, writer }),
+                serial: 0,
+            })
+            .ok();
+    }
+
     fn per_pane(&mut self, pane_id: PaneId) -> Arc<Mutex<PerPane>> {
         Arc::clone(
             self.per_pane
@@ -170,9 +295,16 @@ impl SessionHandler {
         )
     }
 
+    /// Pushes changes for pane_id to the client, but only if the client
+    /// has previously asked about that pane.  Clients only poll the panes
+    /// that they are displaying, so this avoids computing and sending
+    /// render changes for the potentially many panes that they are not.
     pub fn schedule_pane_push(&mut self, pane_id: PaneId) {
         let sender = self.to_write_tx.clone();
-        let per_pane = self.per_pane(pane_id);
+        let per_pane = match self.per_pane.get(&pane_id) {
+            Some(per_pane) => Arc::clone(per_pane),
+            None => return,
+        };
         spawn_into_main_thread(async move {
             let mux = Mux::get().unwrap();
             let pane = mux
@@ -208,6 +340,21 @@ impl SessionHandler {
             send_response(f());
         }
 
+        let required = AccessLevel::required_for(&decoded.pdu);
+        if self.access < required {
+            log::warn!(
+                "denied {:?} access to a client with {:?} access",
+                required,
+                self.access
+            );
+            send_response(Err(if self.access == AccessLevel::Unauthenticated {
+                anyhow!("permission denied: the server requires authentication")
+            } else {
+                anyhow!("permission denied: this client may only view panes")
+            }));
+            return;
+        }
+
         match decoded.pdu {
             Pdu::Ping(Ping {}) => send_response(Ok(Pdu::Pong(Pong {}))),
             Pdu::ListPanes(ListPanes {}) => {
@@ -234,6 +381,8 @@ impl SessionHandler {
             Pdu::WriteToPane(WriteToPane { pane_id, data }) => {
                 let sender = self.to_write_tx.clone();
                 let per_pane = self.per_pane(pane_id);
+                let client_id = self.client_id;
+                let client_name = self.client_name.clone();
                 spawn_into_main_thread(async move {
                     catch(
                         move || {
@@ -241,7 +390,11 @@ impl SessionHandler {
                             let pane = mux
                                 .get_pane(pane_id)
                                 .ok_or_else(|| anyhow!("no such pane {}", pane_id))?;
-                            pane.writer().write_all(&data)?;
+                            note_pane_writer(pane_id, client_id, &client_name);
+                            send_to_input_targets(&mux, pane_id, |target| {
+                                target.writer().write_all(&data)?;
+                                Ok(())
+                            });
                             maybe_push_pane_changes(&pane, sender, per_pane)?;
                             Ok(Pdu::UnitResponse(UnitResponse {}))
                         },
@@ -273,6 +426,8 @@ impl SessionHandler {
             Pdu::SendPaste(SendPaste { pane_id, data }) => {
                 let sender = self.to_write_tx.clone();
                 let per_pane = self.per_pane(pane_id);
+                let client_id = self.client_id;
+                let client_name = self.client_name.clone();
                 spawn_into_main_thread(async move {
                     catch(
                         move || {
@@ -280,7 +435,8 @@ impl SessionHandler {
                             let pane = mux
                                 .get_pane(pane_id)
                                 .ok_or_else(|| anyhow!("no such pane {}", pane_id))?;
-                            pane.send_paste(&data)?;
+                            note_pane_writer(pane_id, client_id, &client_name);
+                            send_to_input_targets(&mux, pane_id, |target| target.send_paste(&data));
                             maybe_push_pane_changes(&pane, sender, per_pane)?;
                             Ok(Pdu::UnitResponse(UnitResponse {}))
                         },
@@ -339,6 +495,66 @@ impl SessionHandler {
                 .detach();
             }
 
+            Pdu::SetPaneInputMode(SetPaneInputMode { pane_id, mode }) => {
+                spawn_into_main_thread(async move {
+                    catch(
+                        move || {
+                            let mux = Mux::get().unwrap();
+                            mux.get_pane(pane_id)
+                                .ok_or_else(|| anyhow!("no such pane {}", pane_id))?;
+                            mux.set_pane_input_mode(pane_id, mode);
+                            Ok(Pdu::UnitResponse(UnitResponse {}))
+                        },
+                        send_response,
+                    )
+                })
+                .detach();
+            }
+
+            Pdu::SetWindowPosition(SetWindowPosition { pane_id, position }) => {
+                spawn_into_main_thread(async move {
+                    catch(
+                        move || {
+                            let mux = Mux::get().unwrap();
+                            let (_domain_id, window_id, _tab_id) = mux
+                                .resolve_pane_id(pane_id)
+                                .ok_or_else(|| anyhow!("no such pane {}", pane_id))?;
+                            mux.notify(MuxNotification::SetWindowPosition {
+                                window_id,
+                                position,
+                            });
+                            Ok(Pdu::UnitResponse(UnitResponse {}))
+                        },
+                        send_response,
+                    )
+                })
+                .detach();
+            }
+
+            Pdu::CapturePane(CapturePane {
+                pane_id,
+                whole_window,
+                args,
+            }) => {
+                spawn_into_main_thread(async move {
+                    catch(
+                        move || {
+                            let mux = Mux::get().unwrap();
+                            mux.get_pane(pane_id)
+                                .ok_or_else(|| anyhow!("no such pane {}", pane_id))?;
+                            mux.notify(MuxNotification::Capture {
+                                pane_id,
+                                whole_window,
+                                args,
+                            });
+                            Ok(Pdu::UnitResponse(UnitResponse {}))
+                        },
+                        send_response,
+                    )
+                })
+                .detach();
+            }
+
             Pdu::Resize(Resize {
                 containing_tab_id,
                 pane_id,
@@ -371,6 +587,8 @@ impl SessionHandler {
             }) => {
                 let sender = self.to_write_tx.clone();
                 let per_pane = self.per_pane(pane_id);
+                let client_id = self.client_id;
+                let client_name = self.client_name.clone();
                 spawn_into_main_thread(async move {
                     catch(
                         move || {
@@ -378,7 +596,10 @@ impl SessionHandler {
                             let pane = mux
                                 .get_pane(pane_id)
                                 .ok_or_else(|| anyhow!("no such pane {}", pane_id))?;
-                            pane.key_down(event.key, event.modifiers)?;
+                            note_pane_writer(pane_id, client_id, &client_name);
+                            send_to_input_targets(&mux, pane_id, |target| {
+                                target.key_down(event.key, event.modifiers)
+                            });
 
                             // For a key press, we want to always send back the
                             // cursor position so that the predictive echo doesn't
@@ -499,12 +720,76 @@ impl SessionHandler {
             }
 
             Pdu::GetCodecVersion(_) => {
+                // Clients that predate the Handshake require an exact
+                // match, so report the oldest version that we can still
+                // speak; if that is their version, we can talk to them.
                 send_response(Ok(Pdu::GetCodecVersionResponse(GetCodecVersionResponse {
-                    codec_vers: CODEC_VERSION,
+                    codec_vers: CODEC_MIN_COMPATIBLE_VERSION,
                     version_string: config::wezterm_version().to_owned(),
                 })))
             }
 
+            Pdu::Handshake(peer) => {
+                let local = Handshake::local();
+                if peer.is_compatible() {
+                    log::trace!(
+                        "client version is {} (codec version {}, capabilities {:?})",
+                        peer.version_string,
+                        peer.codec_vers,
+                        peer.capabilities
+                    );
+                    self.peer_capabilities = peer.capabilities;
+                    send_response(Ok(Pdu::HandshakeResponse(local.into())))
+                } else {
+                    send_response(Err(anyhow!(
+                        "The client version is {} (codec version {}, compatible with {} and newer), \
+                         which is not compatible with the server version {} \
+                         (codec version {}, compatible with {} and newer)",
+                        peer.version_string,
+                        peer.codec_vers,
+                        peer.min_codec_vers,
+                        local.version_string,
+                        local.codec_vers,
+                        local.min_codec_vers
+                    )))
+                }
+            }
+
+            Pdu::Authenticate(Authenticate { token }) => {
+                match self.policy.authenticate(&token, self.access) {
+                    Ok(access) => {
+                        self.access = access;
+                        send_response(Ok(Pdu::AuthenticateResponse(AuthenticateResponse {
+                            read_only: access == AccessLevel::ReadOnly,
+                        })))
+                    }
+                    Err(err) => {
+                        log::warn!("client failed to authenticate: {:#}", err);
+                        send_response(Err(err))
+                    }
+                }
+            }
+
+            Pdu::SetReadOnly(SetReadOnly {}) => {
+                self.access = self.access.min(AccessLevel::ReadOnly);
+                send_response(Ok(Pdu::UnitResponse(UnitResponse {})))
+            }
+
+            Pdu::SetClientName(SetClientName { name }) => {
+                self.client_name = self.policy.client_label(&name, self.client_id);
+                send_response(Ok(Pdu::UnitResponse(UnitResponse {})))
+            }
+
+            Pdu::SetLogFilters(SetLogFilters { filters }) => {
+                let result = env_bootstrap::set_log_filters(&filters)
+                    .map(|()| {
+                        log::info!("log filters changed to `{}`", filters);
+                        Pdu::UnitResponse(UnitResponse {})
+                    })
+                    .map_err(|err| anyhow!("{}", err));
+                send_response(result)
+            }
+
             Pdu::GetTlsCreds(_) => {
                 catch(
                     move || {
@@ -530,6 +815,9 @@ impl SessionHandler {
             | Pdu::SearchScrollbackResponse { .. }
             | Pdu::GetLinesResponse { .. }
             | Pdu::GetCodecVersionResponse { .. }
+            | Pdu::HandshakeResponse { .. }
+            | Pdu::AuthenticateResponse { .. }
+            | Pdu::PaneWriterChanged { .. }
             | Pdu::GetTlsCredsResponse { .. }
             | Pdu::ErrorResponse { .. } => {
                 send_response(Err(anyhow!("expected a request, got {:?}", decoded.pdu)))
@@ -613,7 +901,7 @@ async fn split_pane(split: SplitPane, sender: PduSender) -> anyhow::Result<Pdu>
             split.command_dir,
             tab_id,
             split.pane_id,
-            split.direction,
+            split.request,
         )
         .await?;
     let dims = pane.get_dimensions();
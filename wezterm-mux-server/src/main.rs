@@ -8,6 +8,7 @@ use std::process::Command;
 use std::rc::Rc;
 use std::sync::Arc;
 use std::thread;
+use std::time::Duration;
 use structopt::*;
 use wezterm_gui_subcommands::*;
 
@@ -100,6 +101,7 @@ fn run() -> anyhow::Result<()> {
         if opts.skip_config {
             cmd.arg("-n");
         }
+        cmd.args(config::config_override_args());
         if let Some(cwd) = opts.cwd {
             cmd.arg("--cwd");
             cmd.arg(cwd);
@@ -165,6 +167,7 @@ fn run() -> anyhow::Result<()> {
     Mux::set_mux(&mux);
 
     let executor = promise::spawn::SimpleExecutor::new();
+    Mux::start_garbage_collection();
 
     spawn_listener().map_err(|e| {
         log::error!("problem spawning listeners: {:?}", e);
@@ -193,6 +196,32 @@ async fn async_run(cmd: Option<CommandBuilder>) -> anyhow::Result<()> {
     domain.attach().await?;
 
     let config = config::configuration();
+    if config.mux_server_persist_scrollback {
+        // The session is named after the domain that the server listens
+        // on, so that servers for different domains don't share it
+        let domain_name = config
+            .unix_domains
+            .first()
+            .map(|unix_dom| unix_dom.name.as_str())
+            .unwrap_or("unix");
+        let num_restored = match mux::persist::restore_session(domain_name) {
+            Ok(num_tabs) => num_tabs,
+            Err(err) => {
+                log::error!("Unable to restore the saved panes: {:#}", err);
+                0
+            }
+        };
+        mux::persist::start_periodic_save(
+            domain_name,
+            Duration::from_secs(config.mux_server_persist_interval_seconds.max(1)),
+        );
+        // An explicitly requested program is always spawned
+        if num_restored > 0 && cmd.is_none() {
+            return Ok(());
+        }
+    }
+
+    let cmd = cmd.or_else(|| config.build_initial_prog());
     let window_id = mux.new_empty_window();
     let _tab = mux
         .default_domain()
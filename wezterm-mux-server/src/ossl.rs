@@ -7,18 +7,21 @@ use promise::spawn::spawn_into_main_thread;
 use std::net::TcpListener;
 use std::path::Path;
 use std::sync::Arc;
+use wezterm_mux_server_impl::access::AccessPolicy;
 use wezterm_mux_server_impl::PKI;
 
 struct OpenSSLNetListener {
     acceptor: Arc<SslAcceptor>,
     listener: TcpListener,
+    access: AccessPolicy,
 }
 
 impl OpenSSLNetListener {
-    pub fn new(listener: TcpListener, acceptor: SslAcceptor) -> Self {
+    pub fn new(listener: TcpListener, acceptor: SslAcceptor, access: AccessPolicy) -> Self {
         Self {
             listener,
             acceptor: Arc::new(acceptor),
+            access,
         }
     }
 
@@ -82,11 +85,13 @@ impl OpenSSLNetListener {
                                 log::error!("problem with peer cert: {}", err);
                                 break;
                             }
+                            let access = self.access.clone();
                             spawn_into_main_thread(async move {
-                                log::error!("Making new AsyncSslStream");
-                                wezterm_mux_server_impl::dispatch::process(AsyncSslStream::new(
-                                    stream,
-                                ))
+                                log::debug!("Making new AsyncSslStream");
+                                wezterm_mux_server_impl::dispatch::process(
+                                    AsyncSslStream::new(stream),
+                                    access,
+                                )
                                 .await
                                 .map_err(|e| {
                                     log::error!("process: {:?}", e);
@@ -180,6 +185,7 @@ pub fn spawn_tls_listener(tls_server: &TlsDomainServer) -> Result<(), Error> {
             )
         })?,
         acceptor,
+        AccessPolicy::for_tls_server(tls_server),
     );
     std::thread::spawn(move || {
         net_listener.run();
@@ -61,6 +61,9 @@ fn main() {
 
         while let Ok(event) = events.recv().await {
             match event {
+                SessionEvent::Progress(message) => {
+                    log::trace!("{}", message);
+                }
                 SessionEvent::Banner(banner) => {
                     if let Some(banner) = banner {
                         log::trace!("{}", banner);
@@ -42,6 +42,7 @@ impl crate::session::SessionInner {
             // If the agent is around, we can proceed with other methods
             return Ok(false);
         }
+        self.progress("Trying the keys held by the ssh agent".to_string())?;
 
         agent.list_identities()?;
         let identities = agent.identities()?;
@@ -68,6 +69,7 @@ impl crate::session::SessionInner {
                 if !file.exists() {
                     continue;
                 }
+                self.progress(format!("Trying public key {}", file.display()))?;
 
                 let pubkey = if pubkey.exists() {
                     Some(pubkey.as_ref())
@@ -110,8 +110,10 @@ impl ParsedConfigFile {
 
                 if k == "host" {
                     let mut patterns = vec![];
-                    for p in v.split(',') {
-                        let p = p.trim();
+                    for p in v
+                        .split(|c: char| c == ',' || c.is_whitespace())
+                        .filter(|p| !p.is_empty())
+                    {
                         if p.starts_with('!') {
                             patterns.push(Pattern::new(&p[1..], true));
                         } else {
@@ -125,16 +127,11 @@ impl ParsedConfigFile {
                     continue;
                 }
 
-                if let Some(group) = groups.last_mut() {
-                    group
-                        .options
-                        .entry(k.to_string())
-                        .or_insert_with(|| v.to_string());
-                } else {
-                    options
-                        .entry(k.to_string())
-                        .or_insert_with(|| v.to_string());
-                }
+                let target = match groups.last_mut() {
+                    Some(group) => &mut group.options,
+                    None => &mut options,
+                };
+                apply_option(target, &k, v);
             }
         }
 
@@ -146,18 +143,35 @@ impl ParsedConfigFile {
     /// semantics are that the first match wins
     fn apply_matches(&self, hostname: &str, target: &mut ConfigMap) {
         for (k, v) in &self.options {
-            target.entry(k.to_string()).or_insert_with(|| v.to_string());
+            apply_option(target, k, v);
         }
         for group in &self.groups {
             if group.is_match(hostname) {
                 for (k, v) in &group.options {
-                    target.entry(k.to_string()).or_insert_with(|| v.to_string());
+                    apply_option(target, k, v);
                 }
             }
         }
     }
 }
 
+/// Assigns the value for an option unless it is already present,
+/// because the semantics are that the first match wins.
+/// The exception is `IdentityFile`, which may be specified multiple
+/// times to add to the list of identities that are tried.
+fn apply_option(target: &mut ConfigMap, k: &str, v: &str) {
+    match target.get_mut(k) {
+        Some(existing) if k == "identityfile" => {
+            existing.push(' ');
+            existing.push_str(v);
+        }
+        Some(_) => {}
+        None => {
+            target.insert(k.to_string(), v.to_string());
+        }
+    }
+}
+
 /// A context for resolving configuration values.
 /// Holds a combination of environment and token expansion state,
 /// as well as the set of configs that should be consulted.
@@ -219,12 +233,12 @@ impl Config {
     }
 
     /// Convenience method for adding the ~/.ssh/config and system-wide
-    /// `/etc/ssh/config` files to the list of configs
+    /// `/etc/ssh/ssh_config` files to the list of configs
     pub fn add_default_config_files(&mut self) {
         if let Some(home) = dirs_next::home_dir() {
             self.add_config_file(home.join(".ssh").join("config"));
         }
-        self.add_config_file("/etc/ssh/config");
+        self.add_config_file("/etc/ssh/ssh_config");
     }
 
     /// Resolve the configuration for a given host.
@@ -243,32 +257,58 @@ impl Config {
             config.apply_matches(host, &mut result);
         }
 
-        for (k, v) in &mut result {
-            if let Some(tokens) = self.should_expand_tokens(k) {
-                self.expand_tokens(v, tokens);
-            }
-
-            if self.should_expand_environment(k) {
-                self.expand_environment(v);
-            }
-        }
-
-        result
-            .entry("hostname".to_string())
+        // In HostName, %h is the name that was used to look up the
+        // config; everywhere else it is the resolved hostname
+        let mut hostname = result
+            .remove("hostname")
+            .unwrap_or_else(|| host.to_string());
+        let mut tokens = self.tokens.clone();
+        tokens
+            .entry("%h".to_string())
             .or_insert_with(|| host.to_string());
+        self.expand_tokens(&mut hostname, &["%h"], &tokens);
+        if !self.tokens.contains_key("%h") {
+            tokens.insert("%h".to_string(), hostname.clone());
+        }
+        result.insert("hostname".to_string(), hostname);
 
         result
             .entry("port".to_string())
             .or_insert_with(|| "22".to_string());
 
-        result.entry("user".to_string()).or_insert_with(|| {
-            for user in &["USER", "USERNAME"] {
-                if let Some(user) = self.resolve_env(user) {
-                    return user;
-                }
+        let local_user = self.local_user();
+        result
+            .entry("user".to_string())
+            .or_insert_with(|| local_user.clone());
+
+        if let Some(proxy_command) = self.proxy_jump_command(&result) {
+            result.insert("proxycommand".to_string(), proxy_command);
+        }
+
+        for (token, value) in &[
+            ("%n", host.to_string()),
+            ("%p", result["port"].clone()),
+            ("%r", result["user"].clone()),
+            ("%u", local_user),
+        ] {
+            tokens
+                .entry(token.to_string())
+                .or_insert_with(|| value.to_string());
+        }
+
+        for (k, v) in &mut result {
+            if k == "hostname" {
+                continue;
+            }
+            if let Some(names) = self.should_expand_tokens(k) {
+                self.expand_tokens(v, names, &tokens);
+            }
+
+            if self.should_expand_environment(k) {
+                self.expand_environment(v);
+                self.expand_tilde(v);
             }
-            "unknown-user".to_string()
-        });
+        }
 
         if !result.contains_key("userknownhostsfile") {
             if let Some(home) = self.resolve_home() {
@@ -300,6 +340,43 @@ impl Config {
         result
     }
 
+    /// Resolve the name of the local user
+    fn local_user(&self) -> String {
+        for user in &["USER", "USERNAME"] {
+            if let Some(user) = self.resolve_env(user) {
+                return user;
+            }
+        }
+        "unknown-user".to_string()
+    }
+
+    /// Translate `ProxyJump` into the equivalent `ProxyCommand`, which
+    /// runs `ssh` to connect to the last jump host, via any prior jump
+    /// hosts, and forward the connection from there.
+    /// `ProxyCommand` takes precedence if both are specified.
+    fn proxy_jump_command(&self, config: &ConfigMap) -> Option<String> {
+        if config.contains_key("proxycommand") {
+            return None;
+        }
+        let jumps = config.get("proxyjump")?;
+        if jumps.is_empty() || jumps == "none" {
+            return None;
+        }
+        let mut jumps: Vec<&str> = jumps.split(',').map(str::trim).collect();
+        let last = jumps.pop()?;
+        // Brackets are needed to separate an IPv6 address from the port
+        let target = if config["hostname"].contains(':') {
+            "[%h]:%p"
+        } else {
+            "%h:%p"
+        };
+        Some(if jumps.is_empty() {
+            format!("ssh -W {} {}", target, last)
+        } else {
+            format!("ssh -J {} -W {} {}", jumps.join(","), target, last)
+        })
+    }
+
     /// Return true if a given option name is subject to environment variable
     /// expansion.
     fn should_expand_environment(&self, key: &str) -> bool {
@@ -314,7 +391,7 @@ impl Config {
     fn should_expand_tokens(&self, key: &str) -> Option<&[&str]> {
         match key {
             "certificatefile" | "controlpath" | "identityagent" | "identityfile"
-            | "localforward" | "remotecommand" | "remoteforward" | "userknownkostsfile" => {
+            | "localforward" | "remotecommand" | "remoteforward" | "userknownhostsfile" => {
                 Some(&["%C", "%d", "%h", "%i", "%L", "%l", "%n", "%p", "%r", "%u"])
             }
             "hostname" => Some(&["%h"]),
@@ -344,9 +421,9 @@ impl Config {
     }
 
     /// Perform token substitution
-    fn expand_tokens(&self, value: &mut String, tokens: &[&str]) {
-        for &t in tokens {
-            if let Some(v) = self.tokens.get(t) {
+    fn expand_tokens(&self, value: &mut String, names: &[&str], tokens: &ConfigMap) {
+        for &t in names {
+            if let Some(v) = tokens.get(t) {
                 *value = value.replace(t, v);
             } else if t == "%d" {
                 if let Some(home) = self.resolve_home() {
@@ -358,6 +435,27 @@ impl Config {
         *value = value.replace("%%", "%");
     }
 
+    /// Replace a leading `~` in each of the space separated paths in
+    /// `value` with the home directory
+    fn expand_tilde(&self, value: &mut String) {
+        if !value.contains('~') {
+            return;
+        }
+        if let Some(home) = self.resolve_home() {
+            *value = value
+                .split(' ')
+                .map(|path| {
+                    if path == "~" || path.starts_with("~/") {
+                        format!("{}{}", home, &path[1..])
+                    } else {
+                        path.to_string()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+        }
+    }
+
     /// Resolve an environment variable; if an override is set use that,
     /// otherwise read from the real environment.
     fn resolve_env(&self, name: &str) -> Option<String> {
@@ -548,4 +646,51 @@ Config {
 "#
         );
     }
+
+    #[test]
+    fn aliases_and_proxies() {
+        let mut config = Config::new();
+
+        let mut fake_env = ConfigMap::new();
+        fake_env.insert("HOME".to_string(), "/home/me".to_string());
+        fake_env.insert("USER".to_string(), "me".to_string());
+        config.assign_environment(fake_env);
+
+        config.add_config_string(
+            r#"
+        Host work web
+            HostName %h.internal
+            User deploy
+            ProxyJump bastion
+            IdentityFile ~/.ssh/id_work
+            IdentityFile ~/.ssh/id_other
+
+        Host db
+            ProxyJump first,second
+
+        Host *.proxied
+            ProxyCommand nc -X connect -x proxy:8080 %h %p
+            "#,
+        );
+
+        let opts = config.for_host("web");
+        assert_eq!(opts["hostname"], "web.internal");
+        assert_eq!(opts["user"], "deploy");
+        assert_eq!(opts["proxycommand"], "ssh -W web.internal:22 bastion");
+        assert_eq!(
+            opts["identityfile"],
+            "/home/me/.ssh/id_work /home/me/.ssh/id_other"
+        );
+
+        let opts = config.for_host("db");
+        assert_eq!(opts["proxycommand"], "ssh -J first -W db:22 second");
+
+        config.set_option("Port", "2222");
+        let opts = config.for_host("host.proxied");
+        assert_eq!(opts["user"], "me");
+        assert_eq!(
+            opts["proxycommand"],
+            "nc -X connect -x proxy:8080 host.proxied 2222"
+        );
+    }
 }
@@ -17,6 +17,8 @@ use std::time::Duration;
 
 #[derive(Debug)]
 pub enum SessionEvent {
+    /// Describes a step in establishing the connection
+    Progress(String),
     Banner(Option<String>),
     HostVerify(HostVerificationEvent),
     Authenticate(AuthenticationEvent),
@@ -123,6 +125,10 @@ impl SessionInner {
                     None
                 }
             }) {
+            self.progress(format!(
+                "Connecting to {} using ProxyCommand: {}",
+                remote_address, proxy_command
+            ))?;
             let mut cmd;
             if cfg!(windows) {
                 let comspec = std::env::var("COMSPEC").unwrap_or_else(|_| "cmd".to_string());
@@ -153,6 +159,7 @@ impl SessionInner {
                 TcpStream::from_raw_socket(a.into_raw_socket())
             }
         } else {
+            self.progress(format!("Connecting to {}", remote_address))?;
             let socket = TcpStream::connect((hostname.as_str(), port))
                 .with_context(|| format!("connecting to {}", remote_address))?;
             socket
@@ -175,9 +182,24 @@ impl SessionInner {
         self.host_verification(&sess, &hostname, port, &remote_address)
             .context("host verification")?;
 
+        self.progress(format!("Authenticating as {}", user))?;
         self.authenticate(&sess, &user, &hostname)
             .context("authentication")?;
 
+        if self
+            .config
+            .get("forwardagent")
+            .map_or(false, |v| v == "yes")
+        {
+            // libssh2 has no way to accept the channels that the server
+            // opens to reach the agent, so we can't honor this
+            self.progress(
+                "ForwardAgent is not supported by this ssh client; \
+                 the agent will not be forwarded"
+                    .to_string(),
+            )?;
+        }
+
         self.tx_event
             .try_send(SessionEvent::Authenticated)
             .context("notifying user that session is authenticated")?;
@@ -186,6 +208,12 @@ impl SessionInner {
         self.request_loop(sess)
     }
 
+    pub(crate) fn progress(&self, message: String) -> anyhow::Result<()> {
+        self.tx_event
+            .try_send(SessionEvent::Progress(message))
+            .context("notifying user of progress")
+    }
+
     fn request_loop(&mut self, sess: ssh2::Session) -> anyhow::Result<()> {
         let mut sleep_delay = Duration::from_millis(100);
 
@@ -294,8 +322,10 @@ impl SessionInner {
                         None
                     }
 
-                    let status = if let Some(_sig) = has_signal(&chan.channel) {
-                        Some(ExitStatus::with_exit_code(1))
+                    let status = if let Some(sig) = has_signal(&chan.channel) {
+                        // The signal is named without its SIG prefix
+                        let name = sig.exit_signal.unwrap_or_default();
+                        Some(ExitStatus::with_signal(&format!("SIG{}", name)))
                     } else if let Ok(status) = chan.channel.exit_status() {
                         Some(ExitStatus::with_exit_code(status as _))
                     } else {
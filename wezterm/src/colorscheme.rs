@@ -0,0 +1,488 @@
+//! Previewing the configured color palette, and converting color
+//! schemes from other terminals into wezterm's color scheme format.
+use anyhow::{anyhow, Context};
+use config::{ColorOrNone, ColorSchemeFile, Palette};
+use std::collections::HashMap;
+use std::path::Path;
+use termwiz::color::{ColorSpec, RgbColor};
+use termwiz::escape::csi::{Sgr, CSI};
+use wezterm_term::color::ColorPalette;
+
+/// The names of the ANSI colors, in palette order
+const ANSI_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+fn swatch(color: RgbColor) -> String {
+    format!(
+        "{}      {}",
+        CSI::Sgr(Sgr::Background(ColorSpec::TrueColor(color))),
+        CSI::Sgr(Sgr::Reset)
+    )
+}
+
+/// Print the colors of `palette` as a set of swatches
+pub fn show_palette(palette: &ColorPalette) {
+    let named = [
+        ("foreground", Some(palette.foreground)),
+        ("background", Some(palette.background)),
+        ("cursor_fg", Some(palette.cursor_fg)),
+        ("cursor_bg", Some(palette.cursor_bg)),
+        ("cursor_border", Some(palette.cursor_border)),
+        ("selection_fg", palette.selection_fg),
+        ("selection_bg", Some(palette.selection_bg)),
+        ("scrollbar_thumb", Some(palette.scrollbar_thumb)),
+        ("split", Some(palette.split)),
+        ("active_split", Some(palette.active_split)),
+    ];
+    for (name, color) in &named {
+        match color {
+            Some(color) => println!("{} {} {}", swatch(*color), color.to_rgb_string(), name),
+            None => println!("{:6} {:7} {}", "", "none", name),
+        }
+    }
+
+    println!();
+    for (label, offset) in &[("ansi", 0), ("brights", 8)] {
+        println!("{}:", label);
+        for (idx, name) in ANSI_NAMES.iter().enumerate() {
+            let color = palette.colors.0[idx + offset];
+            println!(
+                "{} {} {:>2} {}",
+                swatch(color),
+                color.to_rgb_string(),
+                idx + offset,
+                name
+            );
+        }
+    }
+
+    println!();
+    println!(
+        "{}{} The quick brown fox jumps over the lazy dog {}",
+        CSI::Sgr(Sgr::Foreground(ColorSpec::TrueColor(palette.foreground))),
+        CSI::Sgr(Sgr::Background(ColorSpec::TrueColor(palette.background))),
+        CSI::Sgr(Sgr::Reset)
+    );
+    println!(
+        "{}{} Selected text {}",
+        CSI::Sgr(Sgr::Foreground(ColorSpec::TrueColor(
+            palette.selection_fg.unwrap_or(palette.foreground)
+        ))),
+        CSI::Sgr(Sgr::Background(ColorSpec::TrueColor(palette.selection_bg))),
+        CSI::Sgr(Sgr::Reset)
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemeFormat {
+    /// An iTerm2 `.itermcolors` property list
+    ITermColors,
+    /// A base16 scheme in YAML
+    Base16,
+    /// An alacritty configuration file in YAML
+    Alacritty,
+}
+
+impl std::str::FromStr for SchemeFormat {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "iterm2" => Ok(Self::ITermColors),
+            "base16" => Ok(Self::Base16),
+            "alacritty" => Ok(Self::Alacritty),
+            _ => Err(format!(
+                "Expected one of iterm2, base16 or alacritty, but got {}",
+                s
+            )),
+        }
+    }
+}
+
+impl SchemeFormat {
+    /// Guess the format from the file name and content
+    fn detect(path: &Path, text: &str) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("itermcolors") => Self::ITermColors,
+            _ if text.trim_start().starts_with("<?xml") => Self::ITermColors,
+            _ if flatten_yaml(text).contains_key("base00") => Self::Base16,
+            _ => Self::Alacritty,
+        }
+    }
+}
+
+/// Load the color scheme at `path` and convert it to a wezterm
+/// color scheme file, returned as TOML.  `format` is guessed
+/// if it isn't specified.
+pub fn import_scheme(path: &Path, format: Option<SchemeFormat>) -> anyhow::Result<String> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("reading color scheme {}", path.display()))?;
+    let format = format.unwrap_or_else(|| SchemeFormat::detect(path, &text));
+
+    let (name, palette) = match format {
+        SchemeFormat::ITermColors => (None, palette_from_itermcolors(&text)?),
+        SchemeFormat::Base16 => {
+            let values = flatten_yaml(&text);
+            (values.get("scheme").cloned(), palette_from_base16(&values)?)
+        }
+        SchemeFormat::Alacritty => (None, palette_from_alacritty(&flatten_yaml(&text))?),
+    };
+
+    let name = name.unwrap_or_else(|| {
+        path.file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default()
+    });
+    let toml = toml::to_string(&ColorSchemeFile { colors: palette })?;
+    Ok(format!("# {}\n{}", name, toml))
+}
+
+/// Parse a color of the form `RRGGBB`, `#RRGGBB` or `0xRRGGBB`
+fn parse_hex_color(s: &str) -> Option<RgbColor> {
+    let hex = s
+        .trim_start_matches('#')
+        .trim_start_matches("0x")
+        .trim_start_matches("0X");
+    if hex.len() != 6 {
+        return None;
+    }
+    RgbColor::from_rgb_str(&format!("#{}", hex))
+}
+
+/// Collect the colors named by `keys` into a set of 8 ANSI colors.
+/// Returns None unless all of them are present.
+fn ansi_colors(
+    keys: impl Iterator<Item = String>,
+    lookup: impl Fn(&str) -> Option<RgbColor>,
+) -> Option<[RgbColor; 8]> {
+    let mut colors = [RgbColor::default(); 8];
+    for (idx, key) in keys.take(8).enumerate() {
+        colors[idx] = lookup(&key)?;
+    }
+    Some(colors)
+}
+
+fn palette_from_itermcolors(text: &str) -> anyhow::Result<Palette> {
+    let colors = parse_itermcolors(text);
+    let color = |name: &str| colors.get(name).copied();
+
+    let palette = Palette {
+        foreground: color("Foreground Color"),
+        background: color("Background Color"),
+        cursor_fg: color("Cursor Text Color"),
+        cursor_bg: color("Cursor Color"),
+        cursor_border: color("Cursor Color"),
+        selection_fg: color("Selected Text Color").map(ColorOrNone::Color),
+        selection_bg: color("Selection Color"),
+        ansi: ansi_colors((0..8).map(|n| format!("Ansi {} Color", n)), color),
+        brights: ansi_colors((8..16).map(|n| format!("Ansi {} Color", n)), color),
+        ..Palette::default()
+    };
+    if palette.ansi.is_none() {
+        anyhow::bail!("no ANSI colors were found; is this an .itermcolors file?");
+    }
+    Ok(palette)
+}
+
+fn palette_from_base16(values: &HashMap<String, String>) -> anyhow::Result<Palette> {
+    let base = |n: usize| -> anyhow::Result<RgbColor> {
+        let key = format!("base{:02X}", n);
+        values
+            .get(&key)
+            .and_then(|value| parse_hex_color(value))
+            .ok_or_else(|| anyhow!("missing or invalid {}", key))
+    };
+
+    // This is the mapping used by base16-shell
+    let ansi = [
+        base(0x00)?,
+        base(0x08)?,
+        base(0x0B)?,
+        base(0x0A)?,
+        base(0x0D)?,
+        base(0x0E)?,
+        base(0x0C)?,
+        base(0x05)?,
+    ];
+    let mut brights = ansi;
+    brights[0] = base(0x03)?;
+    brights[7] = base(0x07)?;
+
+    Ok(Palette {
+        foreground: Some(base(0x05)?),
+        background: Some(base(0x00)?),
+        cursor_fg: Some(base(0x00)?),
+        cursor_bg: Some(base(0x05)?),
+        cursor_border: Some(base(0x05)?),
+        selection_fg: Some(ColorOrNone::Color(base(0x05)?)),
+        selection_bg: Some(base(0x02)?),
+        ansi: Some(ansi),
+        brights: Some(brights),
+        ..Palette::default()
+    })
+}
+
+fn palette_from_alacritty(values: &HashMap<String, String>) -> anyhow::Result<Palette> {
+    let color = |key: &str| {
+        values
+            .get(&format!("colors.{}", key))
+            .and_then(|value| parse_hex_color(value))
+    };
+
+    let palette = Palette {
+        foreground: color("primary.foreground"),
+        background: color("primary.background"),
+        cursor_fg: color("cursor.text"),
+        cursor_bg: color("cursor.cursor"),
+        cursor_border: color("cursor.cursor"),
+        // CellForeground keeps the color of the selected text
+        selection_fg: match values.get("colors.selection.text").map(String::as_str) {
+            Some("CellForeground") => Some(ColorOrNone::None),
+            _ => color("selection.text").map(ColorOrNone::Color),
+        },
+        selection_bg: color("selection.background"),
+        ansi: ansi_colors(
+            ANSI_NAMES.iter().map(|name| format!("normal.{}", name)),
+            color,
+        ),
+        brights: ansi_colors(
+            ANSI_NAMES.iter().map(|name| format!("bright.{}", name)),
+            color,
+        ),
+        ..Palette::default()
+    };
+    if palette.ansi.is_none() {
+        anyhow::bail!("no colors.normal section was found; is this an alacritty config file?");
+    }
+    Ok(palette)
+}
+
+/// Remove a trailing `# comment`, taking care not to treat the `#`
+/// in a quoted color value as the start of a comment
+fn strip_yaml_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut prior = ' ';
+    for (idx, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '\'') | (None, '"') => quote = Some(c),
+            (Some(q), c) if q == c => quote = None,
+            (None, '#') if prior.is_whitespace() => return &line[..idx],
+            _ => {}
+        }
+        prior = c;
+    }
+    line
+}
+
+fn unquote(s: &str) -> &str {
+    let s = s.trim();
+    for quote in &['\'', '"'] {
+        if s.len() >= 2 && s.starts_with(*quote) && s.ends_with(*quote) {
+            return &s[1..s.len() - 1];
+        }
+    }
+    s
+}
+
+/// Flatten the nested mappings of a YAML document into a map from
+/// dotted key paths to their scalar values.  This handles only the
+/// subset of YAML that appears in base16 and alacritty color schemes;
+/// sequences, anchors and multi-line values are ignored.
+fn flatten_yaml(text: &str) -> HashMap<String, String> {
+    let mut values = HashMap::new();
+    let mut parents: Vec<(usize, String)> = vec![];
+
+    for line in text.lines() {
+        let line = strip_yaml_comment(line);
+        let content = line.trim();
+        if content.is_empty() || content.starts_with('-') || content == "---" {
+            continue;
+        }
+        let colon = match content.find(':') {
+            Some(colon) => colon,
+            None => continue,
+        };
+        let indent = line.len() - line.trim_start().len();
+        while parents.last().map_or(false, |(level, _)| *level >= indent) {
+            parents.pop();
+        }
+
+        let key = unquote(&content[..colon]).to_string();
+        let value = unquote(&content[colon + 1..]);
+        if value.is_empty() {
+            parents.push((indent, key));
+        } else {
+            let mut path: Vec<&str> = parents.iter().map(|(_, key)| key.as_str()).collect();
+            path.push(&key);
+            values.insert(path.join("."), value.to_string());
+        }
+    }
+
+    values
+}
+
+/// Extract the colors from an iTerm2 `.itermcolors` property list.
+/// The top level dictionary maps color names to dictionaries that
+/// hold the red, green and blue components as reals in the range
+/// 0.0 to 1.0.
+fn parse_itermcolors(text: &str) -> HashMap<String, RgbColor> {
+    let mut colors = HashMap::new();
+    let mut depth = 0;
+    let mut key: Option<String> = None;
+    let mut color_name: Option<String> = None;
+    let mut components: HashMap<String, f32> = HashMap::new();
+    let mut element = "";
+
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        let content = rest[..start].trim();
+        if !content.is_empty() {
+            match element {
+                "key" => key = Some(content.to_string()),
+                "real" | "integer" if depth == 2 => {
+                    if let (Some(name), Ok(value)) = (key.take(), content.parse()) {
+                        components.insert(name, value);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let end = match rest[start..].find('>') {
+            Some(end) => start + end,
+            None => break,
+        };
+        let tag = &rest[start + 1..end];
+        rest = &rest[end + 1..];
+
+        if tag.starts_with('?') || tag.starts_with('!') || tag.ends_with('/') {
+            continue;
+        }
+        if let Some(closing) = tag.strip_prefix('/') {
+            if closing.trim() == "dict" {
+                if depth == 2 {
+                    let component = |name: &str| {
+                        components
+                            .get(name)
+                            .map(|value| (value.max(0.).min(1.) * 255.).round() as u8)
+                    };
+                    if let (Some(name), Some(red), Some(green), Some(blue)) = (
+                        color_name.take(),
+                        component("Red Component"),
+                        component("Green Component"),
+                        component("Blue Component"),
+                    ) {
+                        colors.insert(name, RgbColor::new(red, green, blue));
+                    }
+                }
+                depth -= 1;
+            }
+            element = "";
+            continue;
+        }
+
+        element = tag.split_whitespace().next().unwrap_or("");
+        if element == "dict" {
+            depth += 1;
+            if depth == 2 {
+                color_name = key.take();
+                components.clear();
+            }
+        }
+    }
+
+    colors
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn yaml() {
+        let values = flatten_yaml(
+            "# A comment\n\
+             colors:\n  \
+               primary:\n    \
+                 background: '#1d1f21' # trailing comment\n    \
+                 foreground: \"0xc5c8c6\"\n  \
+               cursor:\n    \
+                 text: CellBackground\n\
+             font:\n  \
+               size: 12\n",
+        );
+        assert_eq!(values["colors.primary.background"], "#1d1f21");
+        assert_eq!(values["colors.primary.foreground"], "0xc5c8c6");
+        assert_eq!(values["colors.cursor.text"], "CellBackground");
+        assert_eq!(values["font.size"], "12");
+        assert_eq!(values.len(), 4);
+    }
+
+    #[test]
+    fn base16() {
+        let mut text = "scheme: \"Test\"\nauthor: \"someone\"\n".to_string();
+        for n in 0..16 {
+            text.push_str(&format!("base{:02X}: \"{:02x}{:02x}{:02x}\"\n", n, n, n, n));
+        }
+        let values = flatten_yaml(&text);
+        let palette = palette_from_base16(&values).unwrap();
+        let grey = |n: u8| RgbColor::new(n, n, n);
+        assert_eq!(palette.foreground, Some(grey(5)));
+        assert_eq!(palette.background, Some(grey(0)));
+        assert_eq!(palette.selection_bg, Some(grey(2)));
+        assert_eq!(palette.ansi.unwrap()[1], grey(8));
+        assert_eq!(palette.brights.unwrap()[0], grey(3));
+        assert_eq!(palette.brights.unwrap()[7], grey(7));
+    }
+
+    #[test]
+    fn alacritty() {
+        let mut text = "colors:\n  primary:\n    background: '0x000000'\n  \
+                        selection:\n    text: CellForeground\n"
+            .to_string();
+        for section in &["normal", "bright"] {
+            text.push_str(&format!("  {}:\n", section));
+            for (idx, name) in ANSI_NAMES.iter().enumerate() {
+                text.push_str(&format!("    {}: '0x0000{:02x}'\n", name, idx));
+            }
+        }
+        let palette = palette_from_alacritty(&flatten_yaml(&text)).unwrap();
+        assert_eq!(palette.background, Some(RgbColor::new(0, 0, 0)));
+        assert_eq!(palette.foreground, None);
+        assert_eq!(palette.selection_fg, Some(ColorOrNone::None));
+        assert_eq!(palette.ansi.unwrap()[4], RgbColor::new(0, 0, 4));
+        assert_eq!(palette.brights.unwrap()[7], RgbColor::new(0, 0, 7));
+    }
+
+    #[test]
+    fn itermcolors() {
+        let colors = parse_itermcolors(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>Ansi 1 Color</key>
+	<dict>
+		<key>Alpha Component</key>
+		<real>1</real>
+		<key>Blue Component</key>
+		<real>0.0</real>
+		<key>Color Space</key>
+		<string>sRGB</string>
+		<key>Green Component</key>
+		<real>0.5</real>
+		<key>Red Component</key>
+		<real>1</real>
+	</dict>
+	<key>Incomplete Color</key>
+	<dict>
+		<key>Red Component</key>
+		<real>1</real>
+	</dict>
+</dict>
+</plist>"#,
+        );
+        assert_eq!(colors.len(), 1);
+        assert_eq!(colors["Ansi 1 Color"], RgbColor::new(0xff, 0x80, 0));
+    }
+}
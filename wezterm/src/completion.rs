@@ -0,0 +1,240 @@
+//! Generates shell completion scripts for the wezterm command line.
+//! clap produces the scripts from the definition of the options, and
+//! they are then extended so that the values of the options that take
+//! a pane or window id are completed with the ids listed by
+//! `wezterm cli list`.
+use structopt::clap::{App, Shell};
+
+/// Prints the id of each pane known to the mux server, one per line.
+/// The server isn't started just to complete a command line.
+const PANE_IDS: &str = "wezterm cli --no-auto-start list 2>/dev/null | awk 'NR > 1 { print $3 }'";
+
+/// Prints the id of each window known to the mux server, one per line
+const WINDOW_IDS: &str =
+    "wezterm cli --no-auto-start list 2>/dev/null | awk 'NR > 1 { print $1 }' | sort -u";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+    /// PowerShell
+    Pwsh,
+}
+
+impl std::str::FromStr for CompletionShell {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "bash" => Ok(Self::Bash),
+            "zsh" => Ok(Self::Zsh),
+            "fish" => Ok(Self::Fish),
+            "pwsh" | "powershell" => Ok(Self::Pwsh),
+            _ => Err(format!(
+                "Expected one of bash, zsh, fish or pwsh, but got {}",
+                s
+            )),
+        }
+    }
+}
+
+impl CompletionShell {
+    fn clap_shell(self) -> Shell {
+        match self {
+            Self::Bash => Shell::Bash,
+            Self::Zsh => Shell::Zsh,
+            Self::Fish => Shell::Fish,
+            Self::Pwsh => Shell::PowerShell,
+        }
+    }
+}
+
+/// Returns the completion script for `app` in the language of `shell`
+pub fn completion_script(mut app: App, shell: CompletionShell) -> anyhow::Result<String> {
+    let mut script = vec![];
+    app.gen_completions_to("wezterm", shell.clap_shell(), &mut script);
+    let script = String::from_utf8(script)?;
+
+    Ok(match shell {
+        CompletionShell::Bash => bash_with_ids(script),
+        CompletionShell::Zsh => zsh_with_ids(&script),
+        CompletionShell::Fish => fish_with_ids(script),
+        CompletionShell::Pwsh => pwsh_with_ids(&script),
+    })
+}
+
+/// Wraps the generated `_wezterm` function with one that completes
+/// ids, and registers that in its place
+fn bash_with_ids(mut script: String) -> String {
+    script.push_str(&format!(
+        r#"
+_wezterm_with_ids() {{
+    local ids
+    case "${{COMP_WORDS[COMP_CWORD-1]}}" in
+        --pane-id)
+            ids="$({pane_ids})"
+            ;;
+        --window-id)
+            ids="$({window_ids})"
+            ;;
+        *)
+            _wezterm "$@"
+            return $?
+            ;;
+    esac
+    COMPREPLY=($(compgen -W "${{ids}}" -- "${{COMP_WORDS[COMP_CWORD]}}"))
+}}
+
+complete -F _wezterm_with_ids -o bashdefault -o default wezterm
+"#,
+        pane_ids = PANE_IDS,
+        window_ids = WINDOW_IDS
+    ));
+    script
+}
+
+/// Replaces the action of the `_arguments` spec for `option` with
+/// `action`, leaving the rest of `line` intact.  A spec looks like
+/// `'--pane-id=[description]: :_files' \`, where the part following
+/// the description is optional.
+fn replace_zsh_action(line: &str, option: &str, action: &str) -> Option<String> {
+    let start = line.find(&format!("{}=[", option))?;
+    let desc_end = start + line[start..].rfind(']')?;
+    let spec_end = line.rfind('\'').filter(|&end| end > desc_end)?;
+    Some(format!(
+        "{}:{}{}",
+        &line[..=desc_end],
+        action,
+        &line[spec_end..]
+    ))
+}
+
+fn zsh_with_ids(script: &str) -> String {
+    let helpers = format!(
+        r#"
+_wezterm_pane_ids() {{
+    local -a ids
+    ids=(${{(f)"$({pane_ids})"}})
+    compadd -a ids
+}}
+
+_wezterm_window_ids() {{
+    local -a ids
+    ids=(${{(f)"$({window_ids})"}})
+    compadd -a ids
+}}
+"#,
+        pane_ids = PANE_IDS,
+        window_ids = WINDOW_IDS
+    );
+
+    let mut result = String::new();
+    for (idx, line) in script.lines().enumerate() {
+        let line = replace_zsh_action(line, "--pane-id", "pane id:_wezterm_pane_ids")
+            .or_else(|| replace_zsh_action(line, "--window-id", "window id:_wezterm_window_ids"))
+            .unwrap_or_else(|| line.to_string());
+        result.push_str(&line);
+        result.push('\n');
+        // The helpers follow the `#compdef` line, so that they are
+        // defined before the script calls `_wezterm`
+        if idx == 0 {
+            result.push_str(&helpers);
+        }
+    }
+    result
+}
+
+fn fish_with_ids(mut script: String) -> String {
+    script.push_str(&format!(
+        r#"
+function __wezterm_pane_ids
+    {pane_ids}
+end
+
+function __wezterm_window_ids
+    {window_ids}
+end
+
+complete -c wezterm -n "__fish_seen_subcommand_from cli" -l pane-id -x -a "(__wezterm_pane_ids)"
+complete -c wezterm -n "__fish_seen_subcommand_from cli" -l window-id -x -a "(__wezterm_window_ids)"
+"#,
+        pane_ids = PANE_IDS,
+        window_ids = WINDOW_IDS
+    ));
+    script
+}
+
+/// PowerShell only keeps the last completer registered for a command,
+/// so the id completion is inserted at the start of the generated one
+fn pwsh_with_ids(script: &str) -> String {
+    const IDS: &str = r#"
+    $previous = $commandAst.CommandElements |
+        Where-Object { $_.Extent.EndOffset -lt $cursorPosition } |
+        Select-Object -Last 1
+    $idField = switch ("$previous") {
+        '--pane-id' { 'pane_id' }
+        '--window-id' { 'window_id' }
+    }
+    if ($idField) {
+        wezterm cli --no-auto-start list --format json 2>$null |
+            ConvertFrom-Json |
+            ForEach-Object { "$($_.$idField)" } |
+            Sort-Object -Unique |
+            Where-Object { $_ -like "$wordToComplete*" } |
+            ForEach-Object {
+                [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_)
+            }
+        return
+    }
+"#;
+
+    let mut result = String::new();
+    let mut inserted = false;
+    for line in script.lines() {
+        result.push_str(line);
+        result.push('\n');
+        if !inserted && line.trim_start().starts_with("param($wordToComplete") {
+            result.push_str(IDS);
+            inserted = true;
+        }
+    }
+    if !inserted {
+        log::warn!("unable to add pane id completion to the PowerShell script");
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn zsh_actions() {
+        assert_eq!(
+            replace_zsh_action(
+                "'--pane-id=[Specify the target pane]' \\",
+                "--pane-id",
+                "pane id:_ids"
+            )
+            .unwrap(),
+            "'--pane-id=[Specify the target pane]:pane id:_ids' \\"
+        );
+        assert_eq!(
+            replace_zsh_action(
+                "'--window-id=[The \\[window\\]]: :_files' \\",
+                "--window-id",
+                "window id:_ids"
+            )
+            .unwrap(),
+            "'--window-id=[The \\[window\\]]:window id:_ids' \\"
+        );
+        assert!(replace_zsh_action("'--cwd=[The directory]' \\", "--pane-id", "_ids").is_none());
+    }
+
+    #[test]
+    fn shells() {
+        assert_eq!("pwsh".parse(), Ok(CompletionShell::Pwsh));
+        assert_eq!("zsh".parse(), Ok(CompletionShell::Zsh));
+        assert!("tcsh".parse::<CompletionShell>().is_err());
+    }
+}
@@ -0,0 +1,138 @@
+//! `wezterm --headless run` spawns a command into a mux that has no
+//! frontend, applies its output to the terminal model in the usual way
+//! and, once the command has finished, prints the resulting screen.
+//! This makes it possible to test escape sequence handling, or to use
+//! wezterm in CI, without a display.
+use crate::RunCommand;
+use anyhow::anyhow;
+use mux::domain::{Domain, LocalDomain};
+use mux::localpane::LocalPane;
+use mux::pane::Pane;
+use mux::Mux;
+use portable_pty::{CommandBuilder, PtySize};
+use std::io::Write;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Returns the text of the pane, with trailing whitespace removed
+/// from each line and trailing blank lines removed
+fn screen_text(pane: &Rc<dyn Pane>, include_scrollback: bool) -> String {
+    let dims = pane.get_dimensions();
+    let first_row = if include_scrollback {
+        dims.scrollback_top
+    } else {
+        dims.physical_top
+    };
+    let (_, lines) = pane.get_lines(first_row..dims.physical_top + dims.viewport_rows as isize);
+
+    let mut text: Vec<String> = lines
+        .iter()
+        .map(|line| line.as_str().trim_end().to_string())
+        .collect();
+    while text.last().map(|l| l.is_empty()).unwrap_or(false) {
+        text.pop();
+    }
+
+    let mut result = text.join("\n");
+    result.push('\n');
+    result
+}
+
+/// The text of the screen once the command has finished, and the
+/// code that it exited with
+struct Capture {
+    text: String,
+    exit_code: i32,
+}
+
+async fn run_and_capture(domain: Arc<dyn Domain>, cmd: RunCommand) -> anyhow::Result<Capture> {
+    let mux = Mux::get().unwrap();
+    let size = PtySize {
+        rows: cmd.rows,
+        cols: cmd.cols,
+        pixel_width: 0,
+        pixel_height: 0,
+    };
+    let builder = if cmd.prog.is_empty() {
+        None
+    } else {
+        Some(CommandBuilder::from_argv(cmd.prog))
+    };
+    let cwd = cmd.cwd.and_then(|c| c.to_str().map(|s| s.to_string()));
+
+    let window_id = mux.new_empty_window();
+    let tab = domain.spawn(size, builder, cwd, *window_id).await?;
+    let pane = tab
+        .get_active_pane()
+        .ok_or_else(|| anyhow!("spawned tab has no pane"))?;
+    let pane_id = pane.pane_id();
+
+    // The pane is removed from the mux once the command has exited
+    // and all of its output has been applied.  We hold our own
+    // reference so that we can still read its content after that.
+    let deadline = cmd
+        .timeout
+        .map(|secs| Instant::now() + Duration::from_secs(secs));
+    while mux.get_pane(pane_id).is_some() {
+        if let Some(deadline) = deadline {
+            if Instant::now() >= deadline {
+                pane.kill();
+                anyhow::bail!("timed out waiting for the command to complete");
+            }
+        }
+        smol::Timer::after(POLL_INTERVAL).await;
+    }
+
+    // The pane is removed once the pty has been closed, which can be
+    // a moment before the process has finished exiting
+    let local_pane = pane
+        .downcast_ref::<LocalPane>()
+        .ok_or_else(|| anyhow!("spawned pane is not a local pane"))?;
+    let status = loop {
+        local_pane.is_dead();
+        if let Some(status) = local_pane.exit_status() {
+            break status;
+        }
+        if let Some(deadline) = deadline {
+            if Instant::now() >= deadline {
+                anyhow::bail!("timed out waiting for the command to exit");
+            }
+        }
+        smol::Timer::after(POLL_INTERVAL).await;
+    };
+    let exit_code = status.exit_code() as i32;
+
+    Ok(Capture {
+        text: screen_text(&pane, cmd.scrollback),
+        exit_code,
+    })
+}
+
+pub fn run(cmd: RunCommand) -> anyhow::Result<()> {
+    let executor = promise::spawn::SimpleExecutor::new();
+
+    let domain: Arc<dyn Domain> = Arc::new(LocalDomain::new("local")?);
+    let mux = Rc::new(Mux::new(Some(domain.clone())));
+    Mux::set_mux(&mux);
+
+    promise::spawn::spawn(async move {
+        match run_and_capture(domain, cmd).await {
+            Ok(capture) => {
+                let stdout = std::io::stdout();
+                let mut stdout = stdout.lock();
+                stdout.write_all(capture.text.as_bytes()).ok();
+                stdout.flush().ok();
+                std::process::exit(capture.exit_code);
+            }
+            Err(err) => crate::terminate_with_error(err),
+        }
+    })
+    .detach();
+
+    loop {
+        executor.tick()?;
+    }
+}
@@ -1,12 +1,15 @@
 use anyhow::{anyhow, Context};
-use config::keyassignment::SpawnTabDomain;
+use config::keyassignment::{
+    CaptureArguments, PaneInputMode, SetWindowPositionArguments, SpawnTabDomain, WindowDimension,
+};
 use config::wezterm_version;
 use mux::activity::Activity;
 use mux::pane::PaneId;
-use mux::tab::SplitDirection;
+use mux::tab::{SplitDirection, SplitRequest, SplitSize, TabId};
 use mux::window::WindowId;
 use mux::Mux;
 use portable_pty::cmdbuilder::CommandBuilder;
+use serde::Serialize;
 use std::ffi::OsString;
 use std::io::{Read, Write};
 use std::rc::Rc;
@@ -16,6 +19,11 @@ use umask::UmaskSaver;
 use wezterm_client::client::{unix_connect_with_retry, Client};
 use wezterm_gui_subcommands::*;
 
+mod colorscheme;
+mod completion;
+mod headless;
+mod tui;
+
 //    let message = "; ❤ 😍🤢\n\x1b[91;mw00t\n\x1b[37;104;m bleet\x1b[0;m.";
 
 #[derive(Debug, StructOpt)]
@@ -46,6 +54,11 @@ struct Opt {
         number_of_values = 1)]
     config_override: Vec<(String, String)>,
 
+    /// Run without any frontend.  This is only meaningful
+    /// in conjunction with the `run` subcommand.
+    #[structopt(long = "headless")]
+    headless: bool,
+
     #[structopt(subcommand)]
     cmd: Option<SubCommand>,
 }
@@ -64,18 +77,121 @@ enum SubCommand {
     #[structopt(name = "connect", about = "Connect to wezterm multiplexer")]
     Connect(ConnectCommand),
 
+    #[structopt(
+        name = "attach",
+        about = "Attach to wezterm multiplexer from within this terminal"
+    )]
+    Attach(AttachCommand),
+
+    #[structopt(
+        name = "run",
+        about = "Run a command in a headless pane and print the resulting screen"
+    )]
+    Run(RunCommand),
+
     #[structopt(name = "cli", about = "Interact with experimental mux server")]
     Cli(CliCommand),
 
     #[structopt(name = "imgcat", about = "Output an image to the terminal")]
     ImageCat(ImgCatCommand),
 
+    #[structopt(
+        name = "set-background",
+        about = "Ask wezterm to use an image as the window background"
+    )]
+    SetBackground(SetBackgroundCommand),
+
     #[structopt(
         name = "set-working-directory",
         about = "Advise the terminal of the current working directory by \
                  emitting an OSC 7 escape sequence"
     )]
     SetCwd(SetCwdCommand),
+
+    #[structopt(
+        name = "terminfo",
+        about = "Print the wezterm terminfo entry, or install it using tic"
+    )]
+    Terminfo(TerminfoCommand),
+
+    #[structopt(
+        name = "ls-fonts",
+        about = "Display information about fonts and how they are resolved"
+    )]
+    LsFonts(LsFontsCommand),
+
+    #[structopt(
+        name = "show-colors",
+        about = "Display the colors of the configured palette or a named color scheme"
+    )]
+    ShowColors(ShowColorsCommand),
+
+    #[structopt(
+        name = "import-colors",
+        about = "Convert an iTerm2, base16 or alacritty color scheme into a \
+                 wezterm color scheme file"
+    )]
+    ImportColors(ImportColorsCommand),
+
+    #[structopt(
+        name = "check-config",
+        about = "Evaluate the configuration file and report any problems"
+    )]
+    CheckConfig(CheckConfigCommand),
+
+    #[structopt(
+        name = "shell-completion",
+        about = "Generate a script that completes wezterm command lines in your shell"
+    )]
+    ShellCompletion(ShellCompletionCommand),
+
+    #[structopt(
+        name = "version",
+        about = "Print the version, and optionally check for a newer release"
+    )]
+    Version(VersionCommand),
+}
+
+#[derive(Debug, StructOpt, Clone)]
+struct AttachCommand {
+    /// Render the multiplexer into the current terminal using a
+    /// text based UI, rather than opening a GUI window.
+    /// This is currently required.
+    #[structopt(long = "tui")]
+    tui: bool,
+
+    /// Name of the multiplexer domain section from the configuration
+    /// to which you'd like to attach.  Defaults to the first configured
+    /// multiplexer domain.
+    domain_name: Option<String>,
+}
+
+#[derive(Debug, StructOpt, Clone)]
+struct RunCommand {
+    /// The number of columns in the pane
+    #[structopt(long = "cols", default_value = "80")]
+    cols: u16,
+
+    /// The number of rows in the pane
+    #[structopt(long = "rows", default_value = "24")]
+    rows: u16,
+
+    /// Specify the current working directory for the command
+    #[structopt(long = "cwd", parse(from_os_str))]
+    cwd: Option<OsString>,
+
+    /// Print the scrollback as well as the visible screen
+    #[structopt(long = "scrollback")]
+    scrollback: bool,
+
+    /// Give up, and exit with an error, if the command is still
+    /// running after this many seconds
+    #[structopt(long = "timeout")]
+    timeout: Option<u64>,
+
+    /// The command to run.  If omitted, your shell is run.
+    #[structopt(parse(from_os_str))]
+    prog: Vec<OsString>,
 }
 
 #[derive(Debug, StructOpt, Clone)]
@@ -91,7 +207,11 @@ struct CliCommand {
 #[derive(Debug, StructOpt, Clone)]
 enum CliSubCommand {
     #[structopt(name = "list", about = "list windows, tabs and panes")]
-    List,
+    List {
+        /// How to present the results; one of `table` or `json`
+        #[structopt(long = "format", default_value = "table")]
+        format: OutputFormat,
+    },
 
     #[structopt(name = "proxy", about = "start rpc proxy pipe")]
     Proxy,
@@ -115,6 +235,15 @@ Outputs the pane-id for the newly created pane on success"
         #[structopt(long = "horizontal")]
         horizontal: bool,
 
+        /// The size of the new pane, either as a number of cells or
+        /// as a percentage of the pane being split; eg: `20` or `30%`
+        #[structopt(long = "size", default_value = "50%")]
+        size: SplitSize,
+
+        /// How to present the results; one of `table` or `json`
+        #[structopt(long = "format", default_value = "table")]
+        format: OutputFormat,
+
         /// Specify the current working directory for the initially
         /// spawned program
         #[structopt(long = "cwd", parse(from_os_str))]
@@ -127,6 +256,86 @@ Outputs the pane-id for the newly created pane on success"
         prog: Vec<OsString>,
     },
 
+    #[structopt(
+        name = "set-input-mode",
+        about = "Change how keyboard and paste input is handled by a pane"
+    )]
+    SetInputMode {
+        /// Specify the target pane.
+        /// The default is to use the current pane based on the
+        /// environment variable WEZTERM_PANE.
+        #[structopt(long = "pane-id")]
+        pane_id: Option<PaneId>,
+
+        /// One of `Normal`, `ReadOnly`, `Broadcast` or `BroadcastToSelected`.
+        /// `ReadOnly` discards input sent to the pane, `Broadcast` fans
+        /// out input to all of the panes in the same tab, while
+        /// `BroadcastToSelected` adds the pane to the selected set of
+        /// its tab and fans out input to the other selected panes.
+        mode: PaneInputMode,
+    },
+
+    #[structopt(
+        name = "set-window-position",
+        about = "Move and/or resize the GUI window containing a pane.
+Positions and sizes are relative to the monitor, and may be specified
+in pixels, eg: `800`, or as a percentage of the monitor size, eg: `50%`"
+    )]
+    SetWindowPosition {
+        /// Specify the target pane.
+        /// The default is to use the current pane based on the
+        /// environment variable WEZTERM_PANE.
+        #[structopt(long = "pane-id")]
+        pane_id: Option<PaneId>,
+
+        /// The monitor to position the window on; 0 is the
+        /// primary monitor
+        #[structopt(long = "monitor", default_value = "0")]
+        monitor: usize,
+
+        /// The position of the left edge of the window
+        #[structopt(long = "x")]
+        x: Option<WindowDimension>,
+
+        /// The position of the top edge of the window
+        #[structopt(long = "y")]
+        y: Option<WindowDimension>,
+
+        /// The width of the window
+        #[structopt(long = "width")]
+        width: Option<WindowDimension>,
+
+        /// The height of the window
+        #[structopt(long = "height")]
+        height: Option<WindowDimension>,
+    },
+
+    #[structopt(
+        name = "capture",
+        about = "Save an image of a pane, or of the GUI window containing it,
+as a PNG file.  The pane must be visible in its window."
+    )]
+    Capture {
+        /// Specify the target pane.
+        /// The default is to use the current pane based on the
+        /// environment variable WEZTERM_PANE.
+        #[structopt(long = "pane-id")]
+        pane_id: Option<PaneId>,
+
+        /// Capture the whole window rather than just the pane
+        #[structopt(long = "window")]
+        window: bool,
+
+        /// Leave the tab bar out of a window capture
+        #[structopt(long = "no-tab-bar")]
+        no_tab_bar: bool,
+
+        /// The PNG file to write.  The default is to create a file
+        /// named after the current time in the home directory.
+        #[structopt(parse(from_os_str))]
+        path: Option<std::path::PathBuf>,
+    },
+
     #[structopt(
         name = "spawn",
         about = "Spawn a command into a new window or tab
@@ -155,17 +364,109 @@ Outputs the pane-id for the newly created pane on success"
         #[structopt(long = "cwd", parse(from_os_str))]
         cwd: Option<OsString>,
 
+        /// Spawn the named layout from the `tab_layouts` section of
+        /// the configuration, rather than a single program
+        #[structopt(long = "layout", conflicts_with = "prog")]
+        layout: Option<String>,
+
+        /// How to present the results; one of `table` or `json`
+        #[structopt(long = "format", default_value = "table")]
+        format: OutputFormat,
+
         /// Instead of executing your shell, run PROG.
         /// For example: `wezterm start -- bash -l` will spawn bash
         /// as if it were a login shell.
         #[structopt(parse(from_os_str))]
         prog: Vec<OsString>,
     },
+
+    #[structopt(
+        name = "set-log-level",
+        about = "Change which messages the server logs, without restarting it"
+    )]
+    SetLogLevel {
+        /// The filters, using the same syntax as the WEZTERM_LOG
+        /// environment variable; eg: `debug`, or `wezterm_gui=debug,info`
+        /// to log debug messages from the GUI and informational
+        /// messages from everything else
+        filters: String,
+    },
 }
 
+use termwiz::caps::Capabilities;
+use termwiz::escape::csi::{Cursor, CSI};
+use termwiz::escape::esc::{Esc, EscCode};
 use termwiz::escape::osc::{
     ITermDimension, ITermFileData, ITermProprietary, OperatingSystemCommand,
 };
+use termwiz::escape::OneBased;
+use termwiz::terminal::{new_terminal, ScreenSize, Terminal};
+
+/// Parses an `X,Y` cell position
+fn parse_cell_position(arg: &str) -> Result<(usize, usize), String> {
+    let mut fields = arg.splitn(2, ',');
+    match (fields.next(), fields.next()) {
+        (Some(x), Some(y)) => {
+            let x = x
+                .trim()
+                .parse()
+                .map_err(|err| format!("invalid column `{}`: {}", x, err))?;
+            let y = y
+                .trim()
+                .parse()
+                .map_err(|err| format!("invalid row `{}`: {}", y, err))?;
+            Ok((x, y))
+        }
+        _ => Err(format!("Expected X,Y, but got {}", arg)),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TmuxPassthrough {
+    /// Wrap the output if the TMUX environment variable is set
+    Detect,
+    Enable,
+    Disable,
+}
+
+impl std::str::FromStr for TmuxPassthrough {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "detect" => Ok(Self::Detect),
+            "enable" => Ok(Self::Enable),
+            "disable" => Ok(Self::Disable),
+            _ => Err(format!(
+                "Expected one of detect, enable or disable, but got {}",
+                s
+            )),
+        }
+    }
+}
+
+impl TmuxPassthrough {
+    fn enabled(self) -> bool {
+        match self {
+            Self::Detect => std::env::var_os("TMUX").is_some(),
+            Self::Enable => true,
+            Self::Disable => false,
+        }
+    }
+}
+
+/// Wrap an escape sequence in a DCS passthrough sequence, so that tmux
+/// will forward it to the terminal that tmux is running in.  Any ESC
+/// characters in the sequence need to be doubled up.
+fn tmux_passthrough(seq: &str) -> String {
+    format!("\x1bPtmux;{}\x1b\\", seq.replace('\x1b', "\x1b\x1b"))
+}
+
+/// Returns the size of the terminal attached to this process, if any
+fn terminal_size() -> Option<ScreenSize> {
+    let caps = Capabilities::new_from_env().ok()?;
+    let mut terminal = new_terminal(caps).ok()?;
+    terminal.get_screen_size().ok()
+}
 
 #[derive(Debug, StructOpt, Clone)]
 struct ImgCatCommand {
@@ -185,6 +486,17 @@ struct ImgCatCommand {
     /// ratio
     #[structopt(long = "no-preserve-aspect-ratio")]
     no_preserve_aspect_ratio: bool,
+    /// Place the top left corner of the image at the cell `X,Y`, where
+    /// `0,0` is the top left of the terminal, rather than at the cursor.
+    /// The cursor is returned to its prior position afterwards.
+    #[structopt(long = "position", name = "X,Y", parse(try_from_str = parse_cell_position))]
+    position: Option<(usize, usize)>,
+    /// Whether to wrap the image so that tmux passes it through to the
+    /// terminal that it is running in; one of `detect`, `enable` or
+    /// `disable`.  The default, `detect`, wraps the image when the
+    /// TMUX environment variable is set.
+    #[structopt(long = "tmux-passthrough", default_value = "detect")]
+    tmux_passthrough: TmuxPassthrough,
     /// The name of the image file to be displayed.
     /// If omitted, will attempt to read it from stdin.
     #[structopt(parse(from_os_str))]
@@ -205,19 +517,118 @@ impl ImgCatCommand {
 
         let data = data.into_boxed_slice();
 
+        let size = terminal_size();
+        let tmux = self.tmux_passthrough.enabled();
+
+        let mut width = self.width.unwrap_or_else(Default::default);
+        let mut height = self.height.unwrap_or_else(Default::default);
+        if let (true, Some(size)) = (tmux, size) {
+            // The outer terminal would size percentages relative to its
+            // whole window rather than to this tmux pane, so resolve them
+            // to a number of cells here
+            fn resolve(dim: ITermDimension, cells: usize) -> ITermDimension {
+                match dim {
+                    ITermDimension::Percent(n) => ITermDimension::Cells(n * cells as i64 / 100),
+                    dim => dim,
+                }
+            }
+            width = resolve(width, size.cols);
+            height = resolve(height, size.rows);
+        }
+
+        if let (Some((x, y)), Some(size)) = (self.position, size) {
+            if x >= size.cols || y >= size.rows {
+                anyhow::bail!(
+                    "--position {},{} is outside of the {}x{} terminal",
+                    x,
+                    y,
+                    size.cols,
+                    size.rows
+                );
+            }
+        }
+
         let osc = OperatingSystemCommand::ITermProprietary(ITermProprietary::File(Box::new(
             ITermFileData {
                 name: None,
                 size: Some(data.len()),
-                width: self.width.unwrap_or_else(Default::default),
-                height: self.height.unwrap_or_else(Default::default),
+                width,
+                height,
                 preserve_aspect_ratio: !self.no_preserve_aspect_ratio,
                 inline: true,
                 data,
             },
-        )));
-        println!("{}", osc);
+        )))
+        .to_string();
+        let image = if tmux { tmux_passthrough(&osc) } else { osc };
+
+        let mut stdout = std::io::stdout();
+        match self.position {
+            Some((x, y)) => {
+                write!(
+                    stdout,
+                    "{}{}{}{}",
+                    Esc::Code(EscCode::DecSaveCursorPosition),
+                    CSI::Cursor(Cursor::Position {
+                        line: OneBased::from_zero_based(y as u32),
+                        col: OneBased::from_zero_based(x as u32),
+                    }),
+                    image,
+                    Esc::Code(EscCode::DecRestoreCursorPosition),
+                )?;
+            }
+            None => writeln!(stdout, "{}", image)?,
+        }
+        stdout.flush()?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, StructOpt, Clone)]
+struct SetBackgroundCommand {
+    /// Whether to wrap the request so that tmux passes it through to the
+    /// terminal that it is running in; one of `detect`, `enable` or
+    /// `disable`.  The default, `detect`, wraps it when the TMUX
+    /// environment variable is set.
+    #[structopt(long = "tmux-passthrough", default_value = "detect")]
+    tmux_passthrough: TmuxPassthrough,
+    /// The image file to use.  The path is resolved here, but the file
+    /// is loaded by the wezterm GUI, so it must be accessible on the
+    /// machine where that is running.
+    /// If omitted, reverts to the window_background_image from the
+    /// configuration.
+    #[structopt(parse(from_os_str))]
+    file_name: Option<OsString>,
+}
 
+impl SetBackgroundCommand {
+    fn run(&self) -> anyhow::Result<()> {
+        let value = match self.file_name.as_ref() {
+            Some(file_name) => {
+                let path = std::fs::canonicalize(file_name)
+                    .with_context(|| anyhow!("resolving image file: {:?}", file_name))?;
+                path.to_str()
+                    .ok_or_else(|| anyhow!("{} is not valid UTF-8", path.display()))?
+                    .to_string()
+            }
+            None => String::new(),
+        };
+
+        let osc = OperatingSystemCommand::ITermProprietary(ITermProprietary::SetUserVar {
+            name: "WEZTERM_BACKGROUND".to_string(),
+            value,
+        })
+        .to_string();
+        let osc = if self.tmux_passthrough.enabled() {
+            tmux_passthrough(&osc)
+        } else {
+            osc
+        };
+
+        let mut stdout = std::io::stdout();
+        write!(stdout, "{}", osc)?;
+        stdout.flush()?;
         Ok(())
     }
 }
@@ -257,6 +668,165 @@ impl SetCwdCommand {
     }
 }
 
+#[derive(Debug, StructOpt, Clone)]
+struct ShowColorsCommand {
+    /// The name of a color scheme to show, rather than the colors
+    /// from your configuration
+    #[structopt(long = "scheme")]
+    scheme: Option<String>,
+}
+
+impl ShowColorsCommand {
+    fn run(&self, config: config::ConfigHandle) -> anyhow::Result<()> {
+        let palette = match &self.scheme {
+            Some(name) => config
+                .color_scheme_by_name(name)
+                .ok_or_else(|| anyhow!("there is no color scheme named {}", name))?
+                .clone(),
+            None => config.resolved_palette.clone(),
+        };
+        colorscheme::show_palette(&palette.into());
+        Ok(())
+    }
+}
+
+#[derive(Debug, StructOpt, Clone)]
+struct ImportColorsCommand {
+    /// The format of the color scheme; one of `iterm2`, `base16` or
+    /// `alacritty`.  If omitted, it is guessed from the file.
+    #[structopt(long = "format")]
+    format: Option<colorscheme::SchemeFormat>,
+
+    /// The color scheme to convert.  The result is printed in the
+    /// format used by the files in `color_scheme_dirs`.
+    #[structopt(parse(from_os_str))]
+    file_name: OsString,
+}
+
+impl ImportColorsCommand {
+    fn run(&self) -> anyhow::Result<()> {
+        let scheme =
+            colorscheme::import_scheme(std::path::Path::new(&self.file_name), self.format)?;
+        print!("{}", scheme);
+        Ok(())
+    }
+}
+
+#[derive(Debug, StructOpt, Clone)]
+struct CheckConfigCommand {
+    /// Print the effective configuration, after the defaults and
+    /// any overrides have been applied, as JSON
+    #[structopt(long = "print")]
+    print: bool,
+}
+
+impl CheckConfigCommand {
+    fn run(&self) -> anyhow::Result<()> {
+        let (loaded, unknown_fields) = luahelper::collect_unknown_fields(config::Config::load);
+        let loaded = loaded?;
+
+        let file_name = match loaded.file_name() {
+            Some(path) => path.display().to_string(),
+            None => "<defaults>".to_string(),
+        };
+
+        if self.print {
+            println!("{}", serde_json::to_string_pretty(loaded.config())?);
+        }
+
+        for unknown in &unknown_fields {
+            eprintln!("{}: {}", file_name, unknown);
+        }
+        if !unknown_fields.is_empty() {
+            anyhow::bail!(
+                "{} has {} unknown field(s)",
+                file_name,
+                unknown_fields.len()
+            );
+        }
+        eprintln!("{}: OK", file_name);
+        Ok(())
+    }
+}
+
+#[derive(Debug, StructOpt, Clone)]
+struct ShellCompletionCommand {
+    /// The shell to generate the script for; one of `bash`, `zsh`,
+    /// `fish` or `pwsh`
+    #[structopt(long = "shell")]
+    shell: completion::CompletionShell,
+}
+
+impl ShellCompletionCommand {
+    fn run(&self) -> anyhow::Result<()> {
+        print!(
+            "{}",
+            completion::completion_script(Opt::clap(), self.shell)?
+        );
+        Ok(())
+    }
+}
+
+#[derive(Debug, StructOpt, Clone)]
+struct TerminfoCommand {
+    /// Compile the entry with `tic` and install it into ~/.terminfo,
+    /// rather than printing its source
+    #[structopt(long = "install")]
+    install: bool,
+}
+
+impl TerminfoCommand {
+    fn run(&self) -> anyhow::Result<()> {
+        use wezterm_term::terminfo::{WEZTERM_TERMINFO, WEZTERM_TERMINFO_NAME};
+
+        if !self.install {
+            print!("{}", WEZTERM_TERMINFO);
+            return Ok(());
+        }
+
+        // tic needs to read the source from a file.  It is written to
+        // our private runtime directory rather than to the shared temp
+        // directory, where another user could place a file or symlink
+        // at a predictable name ahead of us.
+        let runtime_dir = &*config::RUNTIME_DIR;
+        config::create_user_owned_dirs(runtime_dir)
+            .with_context(|| format!("creating {}", runtime_dir.display()))?;
+        let source = runtime_dir.join(format!(
+            "{}-{}.terminfo",
+            WEZTERM_TERMINFO_NAME,
+            std::process::id()
+        ));
+        std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&source)
+            .and_then(|mut file| file.write_all(WEZTERM_TERMINFO.as_bytes()))
+            .with_context(|| format!("writing {}", source.display()))?;
+
+        let dest = config::HOME_DIR.join(".terminfo");
+        let status = std::process::Command::new("tic")
+            .arg("-x")
+            .arg("-o")
+            .arg(&dest)
+            .arg(&source)
+            .status();
+        std::fs::remove_file(&source).ok();
+
+        let status = status.context("running tic; is ncurses installed?")?;
+        if !status.success() {
+            anyhow::bail!("tic failed: {}", status);
+        }
+        println!(
+            "Installed the {} terminfo entry into {}; \
+             set `term = \"{}\"` in your configuration to use it",
+            WEZTERM_TERMINFO_NAME,
+            dest.display(),
+            WEZTERM_TERMINFO_NAME
+        );
+        Ok(())
+    }
+}
+
 fn terminate_with_error_message(err: &str) -> ! {
     log::error!("{}; terminating", err);
     std::process::exit(1);
@@ -281,11 +851,16 @@ fn run() -> anyhow::Result<()> {
     let saver = UmaskSaver::new();
 
     let opts = Opt::from_args();
-    config::common_init(
-        opts.config_file.as_ref(),
-        &opts.config_override,
-        opts.skip_config,
-    );
+    let mut config_override = opts.config_override.clone();
+    if opts.headless {
+        // Close the pane, rather than appending a completion message,
+        // when the command exits; that is how we know that we're done.
+        config_override.push(("exit_behavior".to_string(), "\"Close\"".to_string()));
+    }
+    // check-config loads the configuration for itself so that it
+    // can report the problems; avoid logging them here as well
+    let skip_config = opts.skip_config || matches!(opts.cmd, Some(SubCommand::CheckConfig(_)));
+    config::common_init(opts.config_file.as_ref(), &config_override, skip_config);
     let config = config::configuration();
 
     match opts
@@ -297,10 +872,38 @@ fn run() -> anyhow::Result<()> {
         SubCommand::Start(_)
         | SubCommand::Ssh(_)
         | SubCommand::Serial(_)
-        | SubCommand::Connect(_) => delegate_to_gui(saver),
+        | SubCommand::Connect(_)
+        | SubCommand::LsFonts(_) => delegate_to_gui(saver),
+        // Checking requires the http client that is linked into the gui
+        SubCommand::Version(cmd) if cmd.check => delegate_to_gui(saver),
+        SubCommand::Version(_) => {
+            println!("wezterm {}", wezterm_version());
+            Ok(())
+        }
         SubCommand::ImageCat(cmd) => cmd.run(),
+        SubCommand::SetBackground(cmd) => cmd.run(),
         SubCommand::SetCwd(cmd) => cmd.run(),
+        SubCommand::Terminfo(cmd) => cmd.run(),
+        SubCommand::ShowColors(cmd) => cmd.run(config),
+        SubCommand::ImportColors(cmd) => cmd.run(),
+        SubCommand::CheckConfig(cmd) => cmd.run(),
+        SubCommand::ShellCompletion(cmd) => cmd.run(),
         SubCommand::Cli(cli) => run_cli(config, cli),
+        SubCommand::Run(cmd) => {
+            if !opts.headless {
+                anyhow::bail!("`wezterm run` is only supported with --headless");
+            }
+            headless::run(cmd)
+        }
+        SubCommand::Attach(cmd) => {
+            if !cmd.tui {
+                anyhow::bail!(
+                    "`wezterm attach` requires --tui; \
+                     use `wezterm connect` to attach from a GUI window"
+                );
+            }
+            tui::run(config, cmd.domain_name.as_deref())
+        }
     }
 }
 
@@ -346,12 +949,61 @@ fn delegate_to_gui(saver: UmaskSaver) -> anyhow::Result<()> {
     }
 }
 
+/// A pane as described by `wezterm cli list --format json`.
+/// Scripts depend on this, so fields should only ever be added.
+#[derive(Debug, Serialize)]
+struct CliListEntry {
+    window_id: WindowId,
+    tab_id: TabId,
+    pane_id: PaneId,
+    size: CliPaneSize,
+    title: String,
+    cwd: Option<String>,
+    is_active: bool,
+    is_zoomed: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct CliPaneSize {
+    rows: u16,
+    cols: u16,
+}
+
+impl From<&mux::tab::PaneEntry> for CliListEntry {
+    fn from(entry: &mux::tab::PaneEntry) -> Self {
+        Self {
+            window_id: entry.window_id,
+            tab_id: entry.tab_id,
+            pane_id: entry.pane_id,
+            size: CliPaneSize {
+                rows: entry.size.rows,
+                cols: entry.size.cols,
+            },
+            title: entry.title.clone(),
+            cwd: entry
+                .working_dir
+                .as_ref()
+                .map(|url| url.url.as_str().to_string()),
+            is_active: entry.is_active_pane,
+            is_zoomed: entry.is_zoomed_pane,
+        }
+    }
+}
+
+/// Reports the id of a pane created by `split-pane` or `spawn`
+fn print_spawned_pane(pane_id: PaneId, format: OutputFormat) {
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::json!({ "pane_id": pane_id })),
+        OutputFormat::Table => println!("{}", pane_id),
+    }
+}
+
 async fn run_cli_async(config: config::ConfigHandle, cli: CliCommand) -> anyhow::Result<()> {
     let initial = true;
     let mut ui = mux::connui::ConnectionUI::new_headless();
     let client = Client::new_default_unix_domain(initial, &mut ui)?;
     match cli.sub {
-        CliSubCommand::List => {
+        CliSubCommand::List { format } => {
             let cols = vec![
                 Column {
                     name: "WINID".to_string(),
@@ -378,7 +1030,7 @@ async fn run_cli_async(config: config::ConfigHandle, cli: CliCommand) -> anyhow:
                     alignment: Alignment::Left,
                 },
             ];
-            let mut data = vec![];
+            let mut entries = vec![];
             let panes = client.list_panes().await?;
 
             for tabroot in panes.tabs {
@@ -386,19 +1038,7 @@ async fn run_cli_async(config: config::ConfigHandle, cli: CliCommand) -> anyhow:
 
                 loop {
                     if let Some(entry) = cursor.leaf_mut() {
-                        data.push(vec![
-                            entry.window_id.to_string(),
-                            entry.tab_id.to_string(),
-                            entry.pane_id.to_string(),
-                            format!("{}x{}", entry.size.cols, entry.size.rows),
-                            entry.title.clone(),
-                            entry
-                                .working_dir
-                                .as_ref()
-                                .map(|url| url.url.as_str())
-                                .unwrap_or("")
-                                .to_string(),
-                        ]);
+                        entries.push(CliListEntry::from(&*entry));
                     }
                     match cursor.preorder_next() {
                         Ok(c) => cursor = c,
@@ -407,13 +1047,35 @@ async fn run_cli_async(config: config::ConfigHandle, cli: CliCommand) -> anyhow:
                 }
             }
 
-            tabulate_output(&cols, &data, &mut std::io::stdout().lock())?;
+            match format {
+                OutputFormat::Json => {
+                    println!("{}", serde_json::to_string_pretty(&entries)?);
+                }
+                OutputFormat::Table => {
+                    let data: Vec<Vec<String>> = entries
+                        .into_iter()
+                        .map(|entry| {
+                            vec![
+                                entry.window_id.to_string(),
+                                entry.tab_id.to_string(),
+                                entry.pane_id.to_string(),
+                                format!("{}x{}", entry.size.cols, entry.size.rows),
+                                entry.title,
+                                entry.cwd.unwrap_or_default(),
+                            ]
+                        })
+                        .collect();
+                    tabulate_output(&cols, &data, &mut std::io::stdout().lock())?;
+                }
+            }
         }
         CliSubCommand::SplitPane {
             pane_id,
             cwd,
             prog,
             horizontal,
+            size,
+            format,
         } => {
             let pane_id: PaneId = match pane_id {
                 Some(p) => p,
@@ -430,10 +1092,13 @@ async fn run_cli_async(config: config::ConfigHandle, cli: CliCommand) -> anyhow:
             let spawned = client
                 .split_pane(codec::SplitPane {
                     pane_id,
-                    direction: if horizontal {
-                        SplitDirection::Horizontal
-                    } else {
-                        SplitDirection::Vertical
+                    request: SplitRequest {
+                        direction: if horizontal {
+                            SplitDirection::Horizontal
+                        } else {
+                            SplitDirection::Vertical
+                        },
+                        size,
                     },
                     domain: config::keyassignment::SpawnTabDomain::CurrentPaneDomain,
                     command: if prog.is_empty() {
@@ -447,7 +1112,93 @@ async fn run_cli_async(config: config::ConfigHandle, cli: CliCommand) -> anyhow:
                 .await?;
 
             log::debug!("{:?}", spawned);
-            println!("{}", spawned.pane_id);
+            print_spawned_pane(spawned.pane_id, format);
+        }
+        CliSubCommand::SetInputMode { pane_id, mode } => {
+            let pane_id: PaneId = match pane_id {
+                Some(p) => p,
+                None => std::env::var("WEZTERM_PANE")
+                    .map_err(|_| {
+                        anyhow!(
+                            "--pane-id was not specified and $WEZTERM_PANE
+                                    is not set in the environment"
+                        )
+                    })?
+                    .parse()?,
+            };
+
+            client
+                .set_input_mode(codec::SetPaneInputMode { pane_id, mode })
+                .await?;
+        }
+        CliSubCommand::SetWindowPosition {
+            pane_id,
+            monitor,
+            x,
+            y,
+            width,
+            height,
+        } => {
+            let pane_id: PaneId = match pane_id {
+                Some(p) => p,
+                None => std::env::var("WEZTERM_PANE")
+                    .map_err(|_| {
+                        anyhow!(
+                            "--pane-id was not specified and $WEZTERM_PANE
+                                    is not set in the environment"
+                        )
+                    })?
+                    .parse()?,
+            };
+
+            client
+                .set_window_position(codec::SetWindowPosition {
+                    pane_id,
+                    position: SetWindowPositionArguments {
+                        monitor,
+                        x,
+                        y,
+                        width,
+                        height,
+                    },
+                })
+                .await?;
+        }
+        CliSubCommand::Capture {
+            pane_id,
+            window,
+            no_tab_bar,
+            path,
+        } => {
+            let pane_id: PaneId = match pane_id {
+                Some(p) => p,
+                None => std::env::var("WEZTERM_PANE")
+                    .map_err(|_| {
+                        anyhow!(
+                            "--pane-id was not specified and $WEZTERM_PANE
+                                    is not set in the environment"
+                        )
+                    })?
+                    .parse()?,
+            };
+
+            // The image is written by the GUI, which may have a
+            // different working directory
+            let path = match path {
+                Some(path) => Some(std::env::current_dir()?.join(path)),
+                None => None,
+            };
+
+            client
+                .capture_pane(codec::CapturePane {
+                    pane_id,
+                    whole_window: window,
+                    args: CaptureArguments {
+                        path,
+                        include_tab_bar: !no_tab_bar,
+                    },
+                })
+                .await?;
         }
         CliSubCommand::SpawnCommand {
             cwd,
@@ -455,7 +1206,20 @@ async fn run_cli_async(config: config::ConfigHandle, cli: CliCommand) -> anyhow:
             pane_id,
             domain_name,
             window_id,
+            layout,
+            format,
         } => {
+            let layout = match layout {
+                Some(name) => Some(
+                    config
+                        .tab_layouts
+                        .get(&name)
+                        .cloned()
+                        .ok_or_else(|| anyhow!("there is no tab layout named {}", name))?,
+                ),
+                None => None,
+            };
+
             let window_id = match window_id {
                 Some(w) => Some(w),
                 None => {
@@ -499,19 +1263,53 @@ async fn run_cli_async(config: config::ConfigHandle, cli: CliCommand) -> anyhow:
                         SpawnTabDomain::DomainName(name)
                     }),
                     window_id,
-                    command: if prog.is_empty() {
-                        None
-                    } else {
-                        let builder = CommandBuilder::from_argv(prog);
-                        Some(builder)
+                    command: match &layout {
+                        Some(layout) => layout.command.command_builder(),
+                        None if prog.is_empty() => None,
+                        None => Some(CommandBuilder::from_argv(prog)),
                     },
-                    command_dir: cwd.and_then(|c| c.to_str().map(|s| s.to_string())),
+                    command_dir: cwd
+                        .or_else(|| {
+                            layout
+                                .as_ref()
+                                .and_then(|layout| layout.command.cwd.clone())
+                                .map(Into::into)
+                        })
+                        .and_then(|c| c.to_str().map(|s| s.to_string())),
                     size: config::configuration().initial_size(),
                 })
                 .await?;
 
             log::debug!("{:?}", spawned);
-            println!("{}", spawned.pane_id);
+
+            if let Some(layout) = &layout {
+                // Carve the splits out of each pane in turn
+                let mut pending = vec![(spawned.pane_id, layout)];
+                while let Some((pane_id, layout)) = pending.pop() {
+                    for split in &layout.splits {
+                        let command = &split.layout.command;
+                        let split_pane = client
+                            .split_pane(codec::SplitPane {
+                                pane_id,
+                                request: SplitRequest {
+                                    direction: split.direction,
+                                    size: split.size,
+                                },
+                                domain: SpawnTabDomain::CurrentPaneDomain,
+                                command: command.command_builder(),
+                                command_dir: command
+                                    .cwd
+                                    .as_ref()
+                                    .and_then(|c| c.to_str().map(|s| s.to_string())),
+                            })
+                            .await?;
+                        log::debug!("{:?}", split_pane);
+                        pending.push((split_pane.pane_id, &split.layout));
+                    }
+                }
+            }
+
+            print_spawned_pane(spawned.pane_id, format);
         }
         CliSubCommand::Proxy => {
             // The client object we created above will have spawned
@@ -548,6 +1346,11 @@ async fn run_cli_async(config: config::ConfigHandle, cli: CliCommand) -> anyhow:
             let creds = client.get_tls_creds().await?;
             codec::Pdu::GetTlsCredsResponse(creds).encode(std::io::stdout().lock(), 0)?;
         }
+        CliSubCommand::SetLogLevel { filters } => {
+            client
+                .set_log_filters(codec::SetLogFilters { filters })
+                .await?;
+        }
     }
     Ok(())
 }
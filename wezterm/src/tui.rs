@@ -0,0 +1,540 @@
+//! A frontend that renders the mux into the terminal that wezterm was
+//! started from, rather than into a GUI window.  This allows attaching
+//! to a multiplexer domain from a system that has no display, such as
+//! from inside an ssh session.
+//!
+//! The active tab of a single mux window is drawn below a one line tab
+//! bar.  Keys are routed to the active pane, except for those that
+//! follow the prefix key (CTRL-B), which are interpreted as commands
+//! in the style of tmux.
+use anyhow::anyhow;
+use config::ConfigHandle;
+use mux::domain::{Domain, DomainId};
+use mux::tab::{PositionedPane, SplitDirection, Tab};
+use mux::window::WindowId;
+use mux::Mux;
+use portable_pty::PtySize;
+use promise::spawn::{Runnable, ScheduleFunc};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::{mpsc, Arc, Mutex};
+use termwiz::caps::Capabilities;
+use termwiz::cell::{unicode_column_width, AttributeChange, CellAttributes};
+use termwiz::input::{InputEvent, KeyCode, KeyEvent, Modifiers};
+use termwiz::surface::{Change, CursorVisibility, Position};
+use termwiz::terminal::buffered::BufferedTerminal;
+use termwiz::terminal::{new_terminal, Terminal, TerminalWaker};
+use wezterm_client::domain::{ClientDomain, ClientDomainConfig};
+use wezterm_term::StableRowIndex;
+
+const PREFIX_KEY: KeyCode = KeyCode::Char('b');
+const PREFIX_MODS: Modifiers = Modifiers::CTRL;
+
+/// Routes futures scheduled via the promise crate into a queue that is
+/// drained by the event loop, and wakes the loop from its input poll
+/// so that it gets to run them.
+fn install_scheduler(waker: TerminalWaker) -> mpsc::Receiver<Runnable> {
+    let (tx, rx) = mpsc::channel();
+    let tx = Arc::new(Mutex::new(tx));
+
+    fn schedule(tx: Arc<Mutex<mpsc::Sender<Runnable>>>, waker: TerminalWaker) -> ScheduleFunc {
+        Box::new(move |runnable| {
+            tx.lock().unwrap().send(runnable).ok();
+            waker.wake().ok();
+        })
+    }
+
+    promise::spawn::set_schedulers(
+        schedule(Arc::clone(&tx), waker.clone()),
+        schedule(tx, waker),
+    );
+    rx
+}
+
+fn client_domain_config(
+    config: &ConfigHandle,
+    domain_name: Option<&str>,
+) -> anyhow::Result<ClientDomainConfig> {
+    let mut domains = vec![];
+    for unix_dom in &config.unix_domains {
+        domains.push(ClientDomainConfig::Unix(unix_dom.clone()));
+    }
+    for ssh_dom in &config.ssh_domains {
+        domains.push(ClientDomainConfig::Ssh(ssh_dom.clone()));
+    }
+    for tls_client in &config.tls_clients {
+        domains.push(ClientDomainConfig::Tls(tls_client.clone()));
+    }
+
+    match domain_name {
+        Some(name) => domains
+            .into_iter()
+            .find(|c| c.name() == name)
+            .ok_or_else(|| {
+                anyhow!(
+                    "no multiplexer domain with name `{}` was found in the configuration",
+                    name
+                )
+            }),
+        None => domains
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("no multiplexer domains are configured")),
+    }
+}
+
+/// Attaches to the domain, spawning an initial tab if the
+/// domain doesn't have any panes yet
+async fn attach(domain: Arc<dyn Domain>, size: PtySize) -> anyhow::Result<()> {
+    domain.attach().await?;
+
+    let mux = Mux::get().unwrap();
+    let have_panes_in_domain = mux
+        .iter_panes()
+        .iter()
+        .any(|p| p.domain_id() == domain.domain_id());
+    if !have_panes_in_domain {
+        let window_id = mux.new_empty_window();
+        domain.spawn(size, None, None, *window_id).await?;
+    }
+    Ok(())
+}
+
+fn tab_size(cols: usize, rows: usize) -> PtySize {
+    PtySize {
+        // Leave room for the tab bar
+        rows: rows.saturating_sub(1).max(1) as u16,
+        cols: cols.max(1) as u16,
+        pixel_width: 0,
+        pixel_height: 0,
+    }
+}
+
+struct Tui {
+    domain_id: DomainId,
+    /// The window being displayed.  This is only latched once the
+    /// domain is attached; until then we show the most recently created
+    /// window, which is where the connection UI shows its progress.
+    window_id: Option<WindowId>,
+    attached: Rc<Cell<bool>>,
+    error: Rc<RefCell<Option<anyhow::Error>>>,
+    size: PtySize,
+    prefix_pending: bool,
+    detach: bool,
+}
+
+fn window_has_domain(mux: &Mux, window_id: WindowId, domain_id: DomainId) -> bool {
+    match mux.get_window(window_id) {
+        Some(window) => window.iter().any(|tab| {
+            tab.iter_panes()
+                .iter()
+                .any(|p| p.pane.domain_id() == domain_id)
+        }),
+        None => false,
+    }
+}
+
+impl Tui {
+    fn current_window(&mut self) -> Option<WindowId> {
+        let mux = Mux::get().unwrap();
+        if let Some(window_id) = self.window_id {
+            if mux
+                .get_window(window_id)
+                .map(|w| !w.is_empty())
+                .unwrap_or(false)
+            {
+                return Some(window_id);
+            }
+            self.window_id = None;
+        }
+
+        let windows = mux.iter_windows().into_iter();
+        if self.attached.get() {
+            let domain_id = self.domain_id;
+            self.window_id = windows
+                .filter(|&id| window_has_domain(&mux, id, domain_id))
+                .max();
+            self.window_id
+        } else {
+            windows.max()
+        }
+    }
+
+    fn current_tab(&mut self) -> Option<(WindowId, Rc<Tab>)> {
+        let window_id = self.current_window()?;
+        let tab = Mux::get().unwrap().get_active_tab_for_window(window_id)?;
+        Some((window_id, tab))
+    }
+
+    fn should_exit(&mut self) -> bool {
+        self.detach
+            || self.error.borrow().is_some()
+            || (self.attached.get() && self.current_window().is_none())
+    }
+
+    fn resize<T: Terminal>(&mut self, buf: &mut BufferedTerminal<T>, cols: usize, rows: usize) {
+        buf.add_change(Change::ClearScreen(Default::default()));
+        buf.resize(cols, rows);
+        self.size = tab_size(cols, rows);
+    }
+
+    fn render<T: Terminal>(&mut self, buf: &mut BufferedTerminal<T>) {
+        buf.add_change(Change::ClearScreen(Default::default()));
+
+        let window_id = match self.current_window() {
+            Some(id) => id,
+            None => {
+                buf.add_change(Change::CursorVisibility(CursorVisibility::Hidden));
+                buf.add_change("Connecting...");
+                return;
+            }
+        };
+
+        let mux = Mux::get().unwrap();
+        let (tabs, active_idx) = match mux.get_window(window_id) {
+            Some(window) => (
+                window.iter().cloned().collect::<Vec<_>>(),
+                window.get_active_idx(),
+            ),
+            None => return,
+        };
+
+        for tab in &tabs {
+            if tab.get_size() != self.size {
+                tab.resize(self.size);
+            }
+        }
+
+        self.render_tab_bar(buf, &tabs, active_idx);
+
+        let tab = match tabs.get(active_idx) {
+            Some(tab) => tab,
+            None => return,
+        };
+
+        // Panes are drawn from left to right because rendering a line
+        // may clear through to the right edge of the screen
+        let mut panes = tab.iter_panes();
+        panes.sort_by_key(|p| (p.left, p.top));
+        for pos in &panes {
+            self.render_pane(buf, pos);
+        }
+        self.render_split_lines(buf, &panes);
+
+        match panes.iter().find(|p| p.is_active) {
+            Some(pos) => {
+                buf.add_change(Change::Title(pos.pane.get_title()));
+                self.position_cursor(buf, pos);
+            }
+            None => {
+                buf.add_change(Change::CursorVisibility(CursorVisibility::Hidden));
+            }
+        }
+    }
+
+    fn render_tab_bar<T: Terminal>(
+        &self,
+        buf: &mut BufferedTerminal<T>,
+        tabs: &[Rc<Tab>],
+        active_idx: usize,
+    ) {
+        let (cols, _) = buf.dimensions();
+        let mut remaining = cols;
+
+        buf.add_change(Change::CursorPosition {
+            x: Position::Absolute(0),
+            y: Position::Absolute(0),
+        });
+        for (idx, tab) in tabs.iter().enumerate() {
+            let title = tab
+                .get_active_pane()
+                .map(|p| p.get_title())
+                .unwrap_or_default();
+            let mut label = format!(" {}: {} ", idx + 1, title);
+            while unicode_column_width(&label) > remaining {
+                label.pop();
+            }
+            if label.is_empty() {
+                break;
+            }
+            remaining -= unicode_column_width(&label);
+
+            buf.add_change(Change::AllAttributes(CellAttributes::default()));
+            if idx == active_idx {
+                buf.add_change(AttributeChange::Reverse(true));
+            }
+            buf.add_change(label);
+        }
+        buf.add_change(Change::AllAttributes(CellAttributes::default()));
+    }
+
+    fn render_pane<T: Terminal>(&self, buf: &mut BufferedTerminal<T>, pos: &PositionedPane) {
+        let dims = pos.pane.get_dimensions();
+        let top = dims.physical_top;
+        let (_, lines) = pos.pane.get_lines(top..top + pos.height as StableRowIndex);
+
+        for (idx, mut line) in lines.into_iter().take(pos.height).enumerate() {
+            line.resize(pos.width);
+            buf.add_change(Change::AllAttributes(CellAttributes::default()));
+            buf.add_change(Change::CursorPosition {
+                x: Position::Absolute(pos.left),
+                y: Position::Absolute(pos.top + 1 + idx),
+            });
+            buf.add_changes(line.changes(&CellAttributes::default()));
+        }
+    }
+
+    fn render_split_lines<T: Terminal>(
+        &self,
+        buf: &mut BufferedTerminal<T>,
+        panes: &[PositionedPane],
+    ) {
+        let (cols, rows) = buf.dimensions();
+        buf.add_change(Change::AllAttributes(CellAttributes::default()));
+        for pos in panes {
+            if pos.left + pos.width < cols {
+                for y in pos.top..pos.top + pos.height {
+                    buf.add_change(Change::CursorPosition {
+                        x: Position::Absolute(pos.left + pos.width),
+                        y: Position::Absolute(y + 1),
+                    });
+                    buf.add_change("\u{2502}");
+                }
+            }
+            if pos.top + pos.height + 1 < rows {
+                buf.add_change(Change::CursorPosition {
+                    x: Position::Absolute(pos.left),
+                    y: Position::Absolute(pos.top + pos.height + 1),
+                });
+                buf.add_change("\u{2500}".repeat(pos.width));
+            }
+        }
+    }
+
+    fn position_cursor<T: Terminal>(&self, buf: &mut BufferedTerminal<T>, pos: &PositionedPane) {
+        let cursor = pos.pane.get_cursor_position();
+        let dims = pos.pane.get_dimensions();
+        let row = cursor.y - dims.physical_top;
+
+        if row < 0 || row as usize >= pos.height || cursor.x >= pos.width {
+            buf.add_change(Change::CursorVisibility(CursorVisibility::Hidden));
+            return;
+        }
+        buf.add_change(Change::CursorPosition {
+            x: Position::Absolute(pos.left + cursor.x),
+            y: Position::Absolute(pos.top + 1 + row as usize),
+        });
+        buf.add_change(Change::CursorShape(cursor.shape));
+        buf.add_change(Change::CursorVisibility(cursor.visibility));
+    }
+
+    fn key_event(&mut self, event: KeyEvent) -> anyhow::Result<()> {
+        let is_prefix = event.key == PREFIX_KEY && event.modifiers == PREFIX_MODS;
+
+        if self.prefix_pending {
+            self.prefix_pending = false;
+            if !is_prefix {
+                self.perform_command(event.key);
+                return Ok(());
+            }
+            // Pressing the prefix twice sends it to the pane
+        } else if is_prefix {
+            self.prefix_pending = true;
+            return Ok(());
+        }
+
+        match self
+            .current_tab()
+            .and_then(|(_, tab)| tab.get_active_pane())
+        {
+            Some(pane) => pane.key_down(event.key, event.modifiers),
+            None => Ok(()),
+        }
+    }
+
+    fn perform_command(&mut self, key: KeyCode) {
+        let (window_id, tab) = match self.current_tab() {
+            Some(current) => current,
+            None => return,
+        };
+        let mux = Mux::get().unwrap();
+
+        match key {
+            KeyCode::Char('c') => self.spawn_tab(window_id),
+            KeyCode::Char('n') => self.activate_tab_relative(window_id, 1),
+            KeyCode::Char('p') => self.activate_tab_relative(window_id, -1),
+            KeyCode::Char(c @ '1'..='9') => {
+                let idx = c as usize - '1' as usize;
+                if let Some(mut window) = mux.get_window_mut(window_id) {
+                    if idx < window.len() {
+                        window.save_and_then_set_active(idx);
+                    }
+                }
+            }
+            KeyCode::Char('%') => self.split_pane(&tab, SplitDirection::Horizontal),
+            KeyCode::Char('"') => self.split_pane(&tab, SplitDirection::Vertical),
+            KeyCode::Char('o') => {
+                let num_panes = tab.count_panes();
+                if num_panes > 0 {
+                    tab.set_active_idx((tab.get_active_idx() + 1) % num_panes);
+                }
+            }
+            KeyCode::Char('z') => tab.toggle_zoom(),
+            KeyCode::Char('x') => {
+                if let Some(pane) = tab.get_active_pane() {
+                    tab.kill_pane(pane.pane_id());
+                }
+            }
+            KeyCode::Char('d') => self.detach = true,
+            _ => {}
+        }
+    }
+
+    fn activate_tab_relative(&self, window_id: WindowId, delta: isize) {
+        let mux = Mux::get().unwrap();
+        if let Some(mut window) = mux.get_window_mut(window_id) {
+            let max = window.len() as isize;
+            if max > 0 {
+                let idx = (window.get_active_idx() as isize + delta).rem_euclid(max);
+                window.save_and_then_set_active(idx as usize);
+            }
+        }
+    }
+
+    fn spawn_tab(&self, window_id: WindowId) {
+        let mux = Mux::get().unwrap();
+        let domain = match mux.get_domain(self.domain_id) {
+            Some(domain) => domain,
+            None => return,
+        };
+        let size = self.size;
+        promise::spawn::spawn(async move {
+            let tab = match domain.spawn(size, None, None, window_id).await {
+                Ok(tab) => tab,
+                Err(err) => {
+                    log::error!("Failed to spawn tab: {:#}", err);
+                    return;
+                }
+            };
+            let mux = Mux::get().unwrap();
+            if let Some(mut window) = mux.get_window_mut(window_id) {
+                if let Some(idx) = window.idx_by_id(tab.tab_id()) {
+                    window.save_and_then_set_active(idx);
+                }
+            }
+        })
+        .detach();
+    }
+
+    fn split_pane(&self, tab: &Rc<Tab>, direction: SplitDirection) {
+        let mux = Mux::get().unwrap();
+        let pane = match tab.get_active_pane() {
+            Some(pane) => pane,
+            None => return,
+        };
+        let domain = match mux.get_domain(pane.domain_id()) {
+            Some(domain) => domain,
+            None => return,
+        };
+        let tab_id = tab.tab_id();
+        let pane_id = pane.pane_id();
+        promise::spawn::spawn(async move {
+            if let Err(err) = domain
+                .split_pane(None, None, tab_id, pane_id, direction.into())
+                .await
+            {
+                log::error!("Failed to split pane: {:#}", err);
+            }
+        })
+        .detach();
+    }
+
+    fn input_event<T: Terminal>(
+        &mut self,
+        buf: &mut BufferedTerminal<T>,
+        event: InputEvent,
+    ) -> anyhow::Result<()> {
+        match event {
+            InputEvent::Key(key) => self.key_event(key)?,
+            InputEvent::Paste(text) => {
+                if let Some(pane) = self
+                    .current_tab()
+                    .and_then(|(_, tab)| tab.get_active_pane())
+                {
+                    pane.send_paste(&text)?;
+                }
+            }
+            InputEvent::Resized { cols, rows } => self.resize(buf, cols, rows),
+            InputEvent::Mouse(_) | InputEvent::Wake => {}
+        }
+        Ok(())
+    }
+}
+
+fn event_loop<T: Terminal>(
+    tui: &mut Tui,
+    buf: &mut BufferedTerminal<T>,
+    runnables: &mpsc::Receiver<Runnable>,
+) -> anyhow::Result<()> {
+    loop {
+        while let Ok(runnable) = runnables.try_recv() {
+            runnable.run();
+        }
+
+        if let Some(err) = tui.error.borrow_mut().take() {
+            return Err(err);
+        }
+        if tui.should_exit() {
+            return Ok(());
+        }
+
+        tui.render(buf);
+        buf.flush()?;
+
+        if let Some(event) = buf.terminal().poll_input(None)? {
+            tui.input_event(buf, event)?;
+        }
+    }
+}
+
+pub fn run(config: ConfigHandle, domain_name: Option<&str>) -> anyhow::Result<()> {
+    let client_config = client_domain_config(&config, domain_name)?;
+
+    let caps = Capabilities::new_from_env()?;
+    let mut terminal = new_terminal(caps)?;
+    terminal.set_raw_mode()?;
+    terminal.enter_alternate_screen()?;
+    let runnables = install_scheduler(terminal.waker());
+    let mut buf = BufferedTerminal::new(terminal)?;
+
+    let domain: Arc<dyn Domain> = Arc::new(ClientDomain::new(client_config));
+    let mux = Rc::new(Mux::new(Some(domain.clone())));
+    Mux::set_mux(&mux);
+
+    let (cols, rows) = buf.dimensions();
+    let mut tui = Tui {
+        domain_id: domain.domain_id(),
+        window_id: None,
+        attached: Rc::new(Cell::new(false)),
+        error: Rc::new(RefCell::new(None)),
+        size: tab_size(cols, rows),
+        prefix_pending: false,
+        detach: false,
+    };
+
+    let attached = Rc::clone(&tui.attached);
+    let error = Rc::clone(&tui.error);
+    promise::spawn::spawn(async move {
+        match attach(domain, tab_size(cols, rows)).await {
+            Ok(()) => attached.set(true),
+            Err(err) => *error.borrow_mut() = Some(err),
+        }
+    })
+    .detach();
+
+    let result = event_loop(&mut tui, &mut buf, &runnables);
+
+    // Restore the terminal before any error is reported
+    drop(buf);
+    result
+}
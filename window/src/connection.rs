@@ -1,4 +1,4 @@
-use crate::Connection;
+use crate::{Connection, KeyCode, Menu, MenuEntry, Modifiers, ScreenPoint, ScreenRect};
 use anyhow::Result as Fallible;
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -41,6 +41,83 @@ pub trait ConnectionOps {
     /// focus away from it.
     fn hide_application(&self) {}
 
+    /// Returns the current system light/dark appearance.
+    /// Systems that don't have a notion of appearance report `Light`.
+    fn get_appearance(&self) -> config::Appearance {
+        config::Appearance::Light
+    }
+
+    /// Returns the bounds of each of the monitors, in screen
+    /// coordinates, with the primary monitor first.
+    /// Systems that are unable to report this return an empty list.
+    fn screens(&self) -> Vec<ScreenRect> {
+        vec![]
+    }
+
+    /// Returns the position of the mouse pointer, in screen coordinates.
+    /// Systems that are unable to report this return None.
+    fn pointer_position(&self) -> Option<ScreenPoint> {
+        None
+    }
+
+    /// Registers a system wide hotkey; `callback` is invoked whenever
+    /// `key` is pressed in combination with `mods`, regardless of
+    /// which application has the keyboard focus.
+    /// Systems that don't support global hotkeys return an error.
+    fn register_global_hotkey<F: FnMut() + 'static>(
+        &self,
+        key: KeyCode,
+        mods: Modifiers,
+        callback: F,
+    ) -> Fallible<()> {
+        drop(callback);
+        anyhow::bail!(
+            "global hotkeys ({:?} {:?}) are not supported on this system",
+            mods,
+            key
+        )
+    }
+
+    /// Enables or disables secure keyboard entry, which prevents other
+    /// processes from observing the keys that are typed.
+    /// Systems that don't support it return an error.
+    fn set_secure_keyboard_entry(&self, enable: bool) -> Fallible<()> {
+        anyhow::ensure!(
+            !enable,
+            "secure keyboard entry is not supported on this system"
+        );
+        Ok(())
+    }
+
+    /// Returns whether secure keyboard entry is enabled
+    fn is_secure_keyboard_entry_enabled(&self) -> bool {
+        false
+    }
+
+    /// Replaces the application menu bar with `menus`.  Selected items
+    /// are passed to `WindowCallbacks::menu_item_selected` of the
+    /// focused window.  Systems without a global menu bar ignore this.
+    fn set_menu_bar(&self, _menus: Vec<Menu>) {}
+
+    /// Shows an icon in the system tray, or in the status area of the
+    /// menu bar, that has a menu.  `entries` is called each time that
+    /// the menu is about to be shown, so that it can reflect the
+    /// current state of the application, and `callback` is invoked
+    /// with the id of the item that is selected.
+    /// Calling this again replaces the icon.
+    /// Systems that don't have a tray return an error.
+    fn set_tray_icon<E, F>(&self, entries: E, callback: F) -> Fallible<()>
+    where
+        E: FnMut() -> Vec<MenuEntry> + 'static,
+        F: FnMut(usize) + 'static,
+    {
+        drop((entries, callback));
+        anyhow::bail!("tray icons are not supported on this system")
+    }
+
+    /// Removes the icon that was added by `set_tray_icon`
+    fn remove_tray_icon(&self) {}
+
     // TODO: return a handle that can be used to cancel the timer
     fn schedule_timer<F: FnMut() + 'static>(&self, interval: std::time::Duration, callback: F);
 }
@@ -47,6 +47,40 @@ pub struct Dimensions {
     pub dpi: usize,
 }
 
+/// Describes the grid that the size of the client area should snap to
+/// when the window is interactively resized.  The client area is sized
+/// to `base_width + n * x` by `base_height + n * y` pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResizeIncrement {
+    pub x: u16,
+    pub y: u16,
+    pub base_width: u16,
+    pub base_height: u16,
+}
+
+/// An entry in a menu; see `WindowOps::show_context_menu` and
+/// `ConnectionOps::set_menu_bar`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MenuEntry {
+    /// An item that, when selected, is passed to
+    /// `WindowCallbacks::menu_item_selected` as `id`.
+    /// `key_hint` describes the key binding that performs the
+    /// same action, and is shown alongside the label.
+    Item {
+        id: usize,
+        label: String,
+        key_hint: Option<String>,
+    },
+    Separator,
+}
+
+/// A titled menu of the menu bar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Menu {
+    pub title: String,
+    pub entries: Vec<MenuEntry>,
+}
+
 pub type Rect = euclid::Rect<isize, PixelUnit>;
 pub type Size = euclid::Size2D<isize, PixelUnit>;
 
@@ -77,6 +111,15 @@ pub trait WindowCallbacks: Any {
     /// Called when window gains/loses focus
     fn focus_change(&mut self, focused: bool) {}
 
+    /// Called when files are dragged from another application
+    /// and dropped onto the window.
+    /// This is only implemented on macOS and Windows; X11 and Wayland
+    /// windows don't accept drops yet, so it is never called there.
+    fn dropped_files(&mut self, paths: Vec<std::path::PathBuf>) {}
+
+    /// Called when the system light/dark appearance changes
+    fn appearance_changed(&mut self, appearance: config::Appearance) {}
+
     /// Called when the window has opengl mode enabled and the window
     /// contents need painting.
     fn paint(&mut self, frame: &mut glium::Frame) {
@@ -101,6 +144,13 @@ pub trait WindowCallbacks: Any {
         context.set_cursor(Some(MouseCursor::Arrow));
     }
 
+    /// Called when a touchpad gesture is recognized
+    fn gesture_event(&mut self, event: &GestureEvent, context: &dyn WindowOps) {}
+
+    /// Called when an item of a context menu, or of the menu bar while
+    /// this window is focused, is selected
+    fn menu_item_selected(&mut self, id: usize, context: &dyn WindowOps) {}
+
     /// Called when the window is created and allows the embedding
     /// app to reference the window and operate upon it.
     fn created(
@@ -126,6 +176,12 @@ pub trait WindowOps {
     /// Hide a visible window
     fn hide(&self) -> Future<()>;
 
+    /// Raise the window and ask the windowing system to give it
+    /// the keyboard focus
+    fn focus(&self) -> Future<()> {
+        self.show()
+    }
+
     /// Schedule the window to be closed
     fn close(&self) -> Future<()>;
 
@@ -149,6 +205,13 @@ pub trait WindowOps {
         Future::ok(())
     }
 
+    /// Advise the windowing system of the increments by which the
+    /// window should be resized, or `None` to allow any size.
+    /// Not all systems support this.
+    fn set_resize_increments(&self, _incr: Option<ResizeIncrement>) -> Future<()> {
+        Future::ok(())
+    }
+
     /// inform the windowing system of the current textual
     /// cursor input location.  This is used primarily for
     /// the platform specific input method editor
@@ -185,6 +248,25 @@ pub trait WindowOps {
         Future::ok(())
     }
 
+    /// Ask the windowing system to draw the user's attention to the
+    /// window, for example by setting the urgency hint on X11,
+    /// bouncing the dock icon on macOS or flashing the taskbar button
+    /// on Windows.  The request ends when the window is focused.
+    fn request_attention(&self) -> Future<()> {
+        Future::ok(())
+    }
+
+    /// Shows a popup menu with its top left corner at `coords`, which
+    /// are relative to the client area.  The future resolves once the
+    /// menu has been shown; the selection, if any, is passed to
+    /// `WindowCallbacks::menu_item_selected`.
+    /// Systems that don't have native context menus return an error.
+    fn show_context_menu(&self, _entries: Vec<MenuEntry>, _coords: Point) -> Future<()> {
+        Future::err(anyhow::anyhow!(
+            "context menus are not supported on this system"
+        ))
+    }
+
     fn config_did_change(&self, _config: &config::ConfigHandle) -> Future<()> {
         Future::ok(())
     }
@@ -223,6 +305,10 @@ pub trait WindowOpsMut {
     /// client area.
     fn set_window_position(&self, _coords: ScreenPoint) {}
 
+    /// Advise the windowing system of the increments by which the
+    /// window should be resized, or `None` to allow any size.
+    fn set_resize_increments(&mut self, _incr: Option<ResizeIncrement>) {}
+
     /// Set the icon for the window.
     /// Depending on the system this may be shown in its titlebar
     /// and/or in the task manager/task switcher
@@ -230,5 +316,9 @@ pub trait WindowOpsMut {
 
     fn toggle_fullscreen(&mut self) {}
 
+    /// Ask the windowing system to draw the user's attention to the
+    /// window until it is focused
+    fn request_attention(&mut self) {}
+
     fn config_did_change(&mut self, _config: &config::ConfigHandle) {}
 }
@@ -4,8 +4,10 @@
 use super::window::WindowInner;
 use crate::connection::ConnectionOps;
 use crate::spawn::*;
-use cocoa::appkit::{NSApp, NSApplication, NSApplicationActivationPolicyRegular};
+use crate::{Menu, MenuEntry, ScreenPoint, ScreenRect};
+use cocoa::appkit::{NSApp, NSApplication, NSApplicationActivationPolicyRegular, NSScreen};
 use cocoa::base::{id, nil};
+use cocoa::foundation::NSArray;
 use core_foundation::date::CFAbsoluteTimeGetCurrent;
 use core_foundation::runloop::*;
 use objc::*;
@@ -30,6 +32,7 @@ impl Connection {
         unsafe {
             let ns_app = NSApp();
             ns_app.setActivationPolicy_(NSApplicationActivationPolicyRegular);
+            super::menu::create_app_menu();
             let conn = Self {
                 ns_app,
                 windows: RefCell::new(HashMap::new()),
@@ -73,6 +76,21 @@ impl Connection {
     }
 }
 
+/// Map an NSAppearance to our light/dark appearance.
+/// Any of the dark variants (including the high contrast and vibrant
+/// flavors) have "Dark" in their name.
+pub(crate) unsafe fn appearance_from_nsappearance(appearance: id) -> config::Appearance {
+    if appearance == nil {
+        return config::Appearance::Light;
+    }
+    let name: id = msg_send![appearance, name];
+    if super::nsstring_to_str(name).contains("Dark") {
+        config::Appearance::Dark
+    } else {
+        config::Appearance::Light
+    }
+}
+
 impl ConnectionOps for Connection {
     fn terminate_message_loop(&self) {
         unsafe {
@@ -97,6 +115,64 @@ impl ConnectionOps for Connection {
         }
     }
 
+    fn screens(&self) -> Vec<ScreenRect> {
+        unsafe {
+            // Cocoa places the origin at the bottom left of the primary
+            // screen, which is always the first in the list, and measures
+            // in points rather than pixels
+            let screens = NSScreen::screens(nil);
+            let primary = NSScreen::frame(screens.objectAtIndex(0));
+            let backing = NSScreen::convertRectToBacking_(screens.objectAtIndex(0), primary);
+            let scale = backing.size.height / primary.size.height;
+
+            (0..screens.count())
+                .map(|idx| {
+                    let frame = NSScreen::frame(screens.objectAtIndex(idx));
+                    let top = primary.size.height - (frame.origin.y + frame.size.height);
+                    ScreenRect::new(
+                        ScreenPoint::new((frame.origin.x * scale) as isize, (top * scale) as isize),
+                        euclid::size2(
+                            (frame.size.width * scale) as isize,
+                            (frame.size.height * scale) as isize,
+                        ),
+                    )
+                })
+                .collect()
+        }
+    }
+
+    fn get_appearance(&self) -> config::Appearance {
+        unsafe {
+            let appearance: id = msg_send![self.ns_app, effectiveAppearance];
+            appearance_from_nsappearance(appearance)
+        }
+    }
+
+    fn set_secure_keyboard_entry(&self, enable: bool) -> anyhow::Result<()> {
+        super::menu::set_secure_keyboard_entry(enable)
+    }
+
+    fn is_secure_keyboard_entry_enabled(&self) -> bool {
+        super::menu::is_secure_keyboard_entry_enabled()
+    }
+
+    fn set_menu_bar(&self, menus: Vec<Menu>) {
+        super::menu::set_menu_bar(&menus);
+    }
+
+    fn set_tray_icon<E, F>(&self, entries: E, callback: F) -> anyhow::Result<()>
+    where
+        E: FnMut() -> Vec<MenuEntry> + 'static,
+        F: FnMut(usize) + 'static,
+    {
+        super::menu::set_tray_icon(Box::new(entries), Box::new(callback));
+        Ok(())
+    }
+
+    fn remove_tray_icon(&self) {
+        super::menu::remove_tray_icon();
+    }
+
     fn schedule_timer<F: FnMut() + 'static>(&self, interval: std::time::Duration, callback: F) {
         let secs_f64 =
             (interval.as_secs() as f64) + (f64::from(interval.subsec_nanos()) / 1_000_000_000_f64);
@@ -0,0 +1,313 @@
+//! The application menu, the menus that the application adds to the
+//! menu bar and shows as context menus, and the status item that
+//! stands in for a tray icon.
+//! wezterm processes keyboard shortcuts through its own key
+//! assignments, so the menus deliberately have no key equivalents that
+//! would take keys away from the terminal; the key bindings are shown
+//! as part of the titles of the items instead.
+// let () = msg_send! is a common pattern for objc
+#![allow(clippy::let_unit_value)]
+
+use super::nsstring;
+use crate::{Menu, MenuEntry, Point};
+use cocoa::appkit::{NSApp, NSApplication, NSMenu, NSMenuItem};
+use cocoa::base::{id, nil, BOOL};
+use cocoa::foundation::{NSInteger, NSPoint, NSRect, NSSize};
+use objc::declare::ClassDecl;
+use objc::runtime::{Class, Object, Sel};
+use objc::*;
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+const MENU_TARGET_CLS_NAME: &str = "WezTermMenuTarget";
+
+/// Identifies the Secure Keyboard Entry item within the app menu
+const SECURE_KEYBOARD_ENTRY_TAG: NSInteger = 1;
+
+const NS_OFF_STATE: NSInteger = 0;
+const NS_ON_STATE: NSInteger = 1;
+
+extern "C" {
+    fn EnableSecureEventInput() -> i32;
+    fn DisableSecureEventInput() -> i32;
+}
+
+/// Whether we have enabled secure event input.  The system keeps a
+/// count of the requests to enable it, so we only enable it once and
+/// remember that we did, rather than asking the system whether it is
+/// enabled; another process may have enabled it.
+static SECURE_KEYBOARD_ENTRY: AtomicBool = AtomicBool::new(false);
+
+pub fn is_secure_keyboard_entry_enabled() -> bool {
+    SECURE_KEYBOARD_ENTRY.load(Ordering::Relaxed)
+}
+
+pub fn set_secure_keyboard_entry(enable: bool) -> anyhow::Result<()> {
+    if SECURE_KEYBOARD_ENTRY.load(Ordering::Relaxed) == enable {
+        return Ok(());
+    }
+    let status = unsafe {
+        if enable {
+            EnableSecureEventInput()
+        } else {
+            DisableSecureEventInput()
+        }
+    };
+    anyhow::ensure!(
+        status == 0,
+        "failed to {} secure keyboard entry: OSStatus {}",
+        if enable { "enable" } else { "disable" },
+        status
+    );
+    SECURE_KEYBOARD_ENTRY.store(enable, Ordering::Relaxed);
+    update_secure_keyboard_entry_item(enable);
+    Ok(())
+}
+
+/// Shows a check mark next to the Secure Keyboard Entry menu item
+/// while it is enabled
+fn update_secure_keyboard_entry_item(enabled: bool) {
+    unsafe {
+        let menubar: id = msg_send![NSApp(), mainMenu];
+        if menubar == nil {
+            return;
+        }
+        let app_item: id = msg_send![menubar, itemAtIndex: 0 as NSInteger];
+        let app_menu: id = msg_send![app_item, submenu];
+        let item: id = msg_send![app_menu, itemWithTag: SECURE_KEYBOARD_ENTRY_TAG];
+        if item != nil {
+            let () = msg_send![item, setState: if enabled { NS_ON_STATE } else { NS_OFF_STATE }];
+        }
+    }
+}
+
+extern "C" fn toggle_secure_keyboard_entry(_this: &Object, _sel: Sel, _sender: id) {
+    if let Err(err) = set_secure_keyboard_entry(!is_secure_keyboard_entry_enabled()) {
+        log::error!("{:#}", err);
+    }
+}
+
+struct Tray {
+    status_item: id,
+    /// The delegate of the menu of the status item, and the target of
+    /// its items; neither of which retains it
+    target: id,
+    entries: Box<dyn FnMut() -> Vec<MenuEntry>>,
+    callback: Box<dyn FnMut(usize)>,
+}
+
+thread_local! {
+    static TRAY: RefCell<Option<Tray>> = RefCell::new(None);
+}
+
+/// Rebuilds the menu of the status item each time it is opened
+extern "C" fn tray_menu_needs_update(this: &Object, _sel: Sel, menu: id) {
+    let entries = TRAY.with(|tray| tray.borrow_mut().as_mut().map(|tray| (tray.entries)()));
+    if let Some(entries) = entries {
+        unsafe {
+            let () = msg_send![menu, removeAllItems];
+            add_entries(
+                menu,
+                &entries,
+                sel!(trayItemSelected:),
+                this as *const Object as id,
+            );
+        }
+    }
+}
+
+extern "C" fn tray_item_selected(_this: &Object, _sel: Sel, sender: id) {
+    let tag: NSInteger = unsafe { msg_send![sender, tag] };
+    TRAY.with(|tray| {
+        if let Some(tray) = tray.borrow_mut().as_mut() {
+            (tray.callback)(tag as usize);
+        }
+    });
+}
+
+/// Adds an item showing the application icon to the status area of
+/// the menu bar, replacing any that was added before
+pub fn set_tray_icon(entries: Box<dyn FnMut() -> Vec<MenuEntry>>, callback: Box<dyn FnMut(usize)>) {
+    remove_tray_icon();
+    unsafe {
+        let status_bar: id = msg_send![class!(NSStatusBar), systemStatusBar];
+        let status_item: id = msg_send![
+            status_bar,
+            statusItemWithLength: -1.0f64 /* NSVariableStatusItemLength */
+        ];
+        let () = msg_send![status_item, retain];
+
+        let image: id = msg_send![NSApp(), applicationIconImage];
+        let image: id = msg_send![image, copy];
+        let () = msg_send![image, setSize: NSSize::new(18., 18.)];
+        let button: id = msg_send![status_item, button];
+        let () = msg_send![button, setImage: image];
+        let () = msg_send![image, release];
+
+        let target: id = msg_send![get_menu_target_class(), new];
+        let menu = NSMenu::new(nil);
+        let () = msg_send![menu, setDelegate: target];
+        let () = msg_send![status_item, setMenu: menu];
+        let () = msg_send![menu, release];
+
+        TRAY.with(|tray| {
+            tray.borrow_mut().replace(Tray {
+                status_item,
+                target,
+                entries,
+                callback,
+            })
+        });
+    }
+}
+
+pub fn remove_tray_icon() {
+    if let Some(tray) = TRAY.with(|tray| tray.borrow_mut().take()) {
+        unsafe {
+            let status_bar: id = msg_send![class!(NSStatusBar), systemStatusBar];
+            let () = msg_send![status_bar, removeStatusItem: tray.status_item];
+            let () = msg_send![tray.status_item, release];
+            let () = msg_send![tray.target, release];
+        }
+    }
+}
+
+/// Returns the class of the object that receives the actions of the
+/// menu items
+fn get_menu_target_class() -> &'static Class {
+    Class::get(MENU_TARGET_CLS_NAME).unwrap_or_else(|| {
+        let mut cls = ClassDecl::new(MENU_TARGET_CLS_NAME, class!(NSObject))
+            .expect("Unable to register menu target class");
+        unsafe {
+            cls.add_method(
+                sel!(toggleSecureKeyboardEntry:),
+                toggle_secure_keyboard_entry as extern "C" fn(&Object, Sel, id),
+            );
+            cls.add_method(
+                sel!(menuNeedsUpdate:),
+                tray_menu_needs_update as extern "C" fn(&Object, Sel, id),
+            );
+            cls.add_method(
+                sel!(trayItemSelected:),
+                tray_item_selected as extern "C" fn(&Object, Sel, id),
+            );
+        }
+        cls.register()
+    })
+}
+
+/// Builds an NSMenu holding `entries`.  The items have no target, so
+/// that their action is sent along the responder chain to the view of
+/// the key window, which passes the tag of the item to the window
+/// callbacks.  Items are disabled while there is no such window.
+pub fn build_menu(title: &str, entries: &[MenuEntry]) -> id {
+    unsafe {
+        let menu = NSMenu::alloc(nil).initWithTitle_(*nsstring(title));
+        add_entries(menu, entries, sel!(wezMenuItemSelected:), nil);
+        menu
+    }
+}
+
+/// Appends `entries` to `menu`.  The items send `action` to `target`,
+/// with their id as the tag of the sender.
+unsafe fn add_entries(menu: id, entries: &[MenuEntry], action: Sel, target: id) {
+    for entry in entries {
+        let item = match entry {
+            MenuEntry::Separator => NSMenuItem::separatorItem(nil),
+            MenuEntry::Item {
+                id,
+                label,
+                key_hint,
+            } => {
+                let title = match key_hint {
+                    Some(hint) => format!("{}    ({})", label, hint),
+                    None => label.clone(),
+                };
+                let item = NSMenuItem::alloc(nil).initWithTitle_action_keyEquivalent_(
+                    *nsstring(&title),
+                    action,
+                    *nsstring(""),
+                );
+                let () = msg_send![item, setTarget: target];
+                let () = msg_send![item, setTag: *id as NSInteger];
+                item
+            }
+        };
+        menu.addItem_(item);
+    }
+}
+
+/// Pops up a menu holding `entries` in `view`, with its top left
+/// corner at `coords`, which are in the pixels of the view.
+/// This runs the menu to completion before returning.
+pub fn show_context_menu(view: id, entries: &[MenuEntry], coords: Point) {
+    unsafe {
+        let menu = build_menu("", entries);
+        // The view is flipped, so the point has its origin in the
+        // top left, as do the pixel coordinates
+        let rect: NSRect = msg_send![
+            view,
+            convertRectFromBacking: NSRect::new(
+                NSPoint::new(0., 0.),
+                NSSize::new(coords.x as f64, coords.y as f64),
+            )
+        ];
+        let location = NSPoint::new(rect.size.width, rect.size.height);
+        let _: BOOL = msg_send![
+            menu,
+            popUpMenuPositioningItem: nil
+            atLocation: location
+            inView: view
+        ];
+        let () = msg_send![menu, release];
+    }
+}
+
+/// Replaces the menus that follow the application menu in the menu
+/// bar with `menus`
+pub fn set_menu_bar(menus: &[Menu]) {
+    unsafe {
+        let menubar: id = msg_send![NSApp(), mainMenu];
+        if menubar == nil {
+            return;
+        }
+        loop {
+            let count: NSInteger = msg_send![menubar, numberOfItems];
+            if count <= 1 {
+                break;
+            }
+            let () = msg_send![menubar, removeItemAtIndex: count - 1];
+        }
+        for menu in menus {
+            let item = NSMenuItem::new(nil);
+            item.setSubmenu_(build_menu(&menu.title, &menu.entries));
+            menubar.addItem_(item);
+        }
+    }
+}
+
+/// Installs the application menu.
+/// Menu items don't retain their targets, so the target and the
+/// menus live for the rest of the life of the application.
+pub fn create_app_menu() {
+    unsafe {
+        let target: id = msg_send![get_menu_target_class(), new];
+
+        let menubar = NSMenu::new(nil);
+        let app_menu_item = NSMenuItem::new(nil);
+        menubar.addItem_(app_menu_item);
+
+        let app_menu = NSMenu::new(nil);
+        let secure_input = NSMenuItem::alloc(nil).initWithTitle_action_keyEquivalent_(
+            *nsstring("Secure Keyboard Entry"),
+            sel!(toggleSecureKeyboardEntry:),
+            *nsstring(""),
+        );
+        let () = msg_send![secure_input, setTarget: target];
+        let () = msg_send![secure_input, setTag: SECURE_KEYBOARD_ENTRY_TAG];
+        app_menu.addItem_(secure_input);
+        app_menu_item.setSubmenu_(app_menu);
+
+        NSApp().setMainMenu_(menubar);
+    }
+}
@@ -8,6 +8,7 @@ pub mod connection;
 pub mod window;
 
 mod keycodes;
+mod menu;
 
 pub use self::window::*;
 pub use bitmap::*;
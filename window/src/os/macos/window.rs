@@ -1,24 +1,26 @@
 // let () = msg_send! is a common pattern for objc
 #![allow(clippy::let_unit_value)]
 
+use super::connection::appearance_from_nsappearance;
 use super::{nsstring, nsstring_to_str};
 use crate::connection::ConnectionOps;
 use crate::{
-    Clipboard, Connection, Dimensions, KeyCode, KeyEvent, Modifiers, MouseButtons, MouseCursor,
-    MouseEvent, MouseEventKind, MousePress, Point, Rect, ScreenPoint, Size, WindowCallbacks,
-    WindowDecorations, WindowOps, WindowOpsMut,
+    Clipboard, Connection, Dimensions, GestureEvent, KeyCode, KeyEvent, MenuEntry, Modifiers,
+    MouseButtons, MouseCursor, MouseEvent, MouseEventKind, MousePress, Point, Rect,
+    ResizeIncrement, ScreenPoint, Size, SwipeDirection, WindowCallbacks, WindowDecorations,
+    WindowOps, WindowOpsMut,
 };
 use anyhow::{anyhow, bail, ensure};
 use cocoa::appkit::{
-    self, NSApplication, NSApplicationActivateIgnoringOtherApps, NSApplicationPresentationOptions,
-    NSBackingStoreBuffered, NSEvent, NSEventModifierFlags, NSOpenGLContext, NSOpenGLPixelFormat,
-    NSRunningApplication, NSScreen, NSView, NSViewHeightSizable, NSViewWidthSizable, NSWindow,
-    NSWindowStyleMask,
+    self, NSApp, NSApplication, NSApplicationActivateIgnoringOtherApps,
+    NSApplicationPresentationOptions, NSBackingStoreBuffered, NSEvent, NSEventModifierFlags,
+    NSOpenGLContext, NSOpenGLPixelFormat, NSRunningApplication, NSScreen, NSView,
+    NSViewHeightSizable, NSViewWidthSizable, NSWindow, NSWindowStyleMask,
 };
 use cocoa::base::*;
 use cocoa::foundation::NSAutoreleasePool;
-use cocoa::foundation::{NSArray, NSNotFound, NSPoint, NSRect, NSSize, NSUInteger};
-use config::ConfigHandle;
+use cocoa::foundation::{NSArray, NSInteger, NSNotFound, NSPoint, NSRect, NSSize, NSUInteger};
+use config::{ConfigHandle, DisplayColorSpace};
 use core_foundation::base::{CFTypeID, TCFType};
 use core_foundation::bundle::{CFBundleGetBundleWithIdentifier, CFBundleGetFunctionPointerForName};
 use core_foundation::data::{CFData, CFDataGetBytePtr, CFDataRef};
@@ -446,6 +448,11 @@ impl Window {
             window.setContentView_(*view);
             window.setDelegate_(*view);
 
+            let () = msg_send![
+                *view,
+                registerForDraggedTypes: NSArray::arrayWithObject(nil, appkit::NSFilenamesPboardType)
+            ];
+
             let frame = NSView::frame(*view);
             let backing_frame = NSView::convertRectToBacking(*view, frame);
             let width = backing_frame.size.width;
@@ -537,6 +544,13 @@ impl WindowOps for Window {
         })
     }
 
+    fn set_resize_increments(&self, incr: Option<ResizeIncrement>) -> Future<()> {
+        Connection::with_window_inner(self.0, move |inner| {
+            inner.set_resize_increments(incr);
+            Ok(())
+        })
+    }
+
     fn set_window_position(&self, coords: ScreenPoint) -> Future<()> {
         Connection::with_window_inner(self.0, move |inner| {
             inner.set_window_position(coords);
@@ -595,6 +609,34 @@ impl WindowOps for Window {
         })
     }
 
+    fn request_attention(&self) -> Future<()> {
+        Connection::with_window_inner(self.0, move |inner| {
+            inner.request_attention();
+            Ok(())
+        })
+    }
+
+    fn show_context_menu(&self, entries: Vec<MenuEntry>, coords: Point) -> Future<()> {
+        let window_id = self.0;
+        let mut prom = promise::Promise::new();
+        let future = prom.get_future().unwrap();
+        promise::spawn::spawn_into_main_thread(async move {
+            // The menu runs a nested event loop, so the window must
+            // not remain borrowed while it is shown
+            let view = match Connection::get().unwrap().window_by_id(window_id) {
+                Some(handle) => handle.borrow().view.clone(),
+                None => {
+                    prom.err(anyhow!("window {} no longer exists", window_id));
+                    return;
+                }
+            };
+            super::menu::show_context_menu(*view, &entries, coords);
+            prom.ok(());
+        })
+        .detach();
+        future
+    }
+
     fn config_did_change(&self, config: &ConfigHandle) -> Future<()> {
         let config = config.clone();
         Connection::with_window_inner(self.0, move |inner| {
@@ -747,6 +789,31 @@ impl WindowInner {
         }
     }
 
+    /// Tells the system which color space the content of the window
+    /// is in, so that it can convert it to that of the display
+    fn apply_color_space(&mut self) {
+        unsafe {
+            let srgb: id = msg_send![class!(NSColorSpace), sRGBColorSpace];
+            let color_space: id = match self.config.display_color_space {
+                DisplayColorSpace::Srgb => srgb,
+                DisplayColorSpace::Native => {
+                    // A window uses the color space of its screen, which
+                    // leaves the colors as they are, until it is told
+                    // otherwise.  Only undo an earlier switch to sRGB, so
+                    // that the window keeps following its screen.
+                    let current: id = msg_send![*self.window, colorSpace];
+                    let is_srgb: BOOL = msg_send![current, isEqual: srgb];
+                    let screen: id = msg_send![*self.window, screen];
+                    if is_srgb == NO || screen.is_null() {
+                        return;
+                    }
+                    msg_send![screen, colorSpace]
+                }
+            };
+            let _: () = msg_send![*self.window, setColorSpace: color_space];
+        }
+    }
+
     fn update_window_shadow(&mut self) {
         let is_opaque = if self.config.window_background_opacity >= 1.0 {
             YES
@@ -841,6 +908,22 @@ impl WindowOpsMut for WindowInner {
         }
     }
 
+    fn set_resize_increments(&mut self, incr: Option<ResizeIncrement>) {
+        unsafe {
+            let frame = NSView::frame(*self.view as *mut _);
+            let backing_frame = NSView::convertRectToBacking(*self.view as *mut _, frame);
+            let scale = backing_frame.size.width / frame.size.width;
+
+            // Cocoa resizes the content in increments relative to its
+            // current size, so there is no need to tell it the base size
+            let size = match incr {
+                Some(incr) => NSSize::new(incr.x as f64 / scale, incr.y as f64 / scale),
+                None => NSSize::new(1.0, 1.0),
+            };
+            let () = msg_send![*self.window, setContentResizeIncrements: size];
+        }
+    }
+
     fn set_window_position(&self, coords: ScreenPoint) {
         unsafe {
             let cartesian = screen_point_to_cartesian(coords);
@@ -886,6 +969,17 @@ impl WindowOpsMut for WindowInner {
         }
     }
 
+    fn request_attention(&mut self) {
+        unsafe {
+            // Bounce the dock icon once; macOS stops the request
+            // when the application is activated
+            let _: NSInteger = msg_send![
+                NSApp(),
+                requestUserAttention: 10 /* NSInformationalRequest */
+            ];
+        }
+    }
+
     fn config_did_change(&mut self, config: &ConfigHandle) {
         self.config = config.clone();
         if let Some(window_view) = WindowView::get_this(unsafe { &**self.view }) {
@@ -893,6 +987,7 @@ impl WindowOpsMut for WindowInner {
         }
         self.update_window_shadow();
         self.apply_decorations();
+        self.apply_color_space();
     }
 }
 
@@ -1445,6 +1540,53 @@ impl WindowView {
         }
     }
 
+    extern "C" fn dragging_entered(_this: &mut Object, _sel: Sel, _sender: id) -> NSUInteger {
+        // NSDragOperationCopy
+        1
+    }
+
+    extern "C" fn perform_drag_operation(this: &mut Object, _sel: Sel, sender: id) -> BOOL {
+        let paths = unsafe {
+            let pasteboard: id = msg_send![sender, draggingPasteboard];
+            let filenames: id =
+                msg_send![pasteboard, propertyListForType: appkit::NSFilenamesPboardType];
+            if filenames == nil {
+                return NO;
+            }
+            (0..filenames.count())
+                .map(|i| std::path::PathBuf::from(nsstring_to_str(filenames.objectAtIndex(i))))
+                .collect::<Vec<_>>()
+        };
+        if let Some(this) = Self::get_this(this) {
+            this.inner.borrow_mut().callbacks.dropped_files(paths);
+        }
+        YES
+    }
+
+    extern "C" fn did_change_effective_appearance(this: &mut Object, _sel: Sel) {
+        let appearance = unsafe {
+            let appearance: id = msg_send![this, effectiveAppearance];
+            appearance_from_nsappearance(appearance)
+        };
+        if let Some(this) = Self::get_this(this) {
+            this.inner
+                .borrow_mut()
+                .callbacks
+                .appearance_changed(appearance);
+        }
+    }
+
+    /// The action of the items of our menus; the tag of the item
+    /// is the id that was assigned to it by the application
+    extern "C" fn menu_item_selected(this: &mut Object, _sel: Sel, sender: id) {
+        let tag: NSInteger = unsafe { msg_send![sender, tag] };
+        if let Some(this) = Self::get_this(this) {
+            let mut inner = this.inner.borrow_mut();
+            let window = Window(inner.window_id);
+            inner.callbacks.menu_item_selected(tag as usize, &window);
+        }
+    }
+
     // Switch the coordinate system to have 0,0 in the top left
     extern "C" fn is_flipped(_this: &Object, _sel: Sel) -> BOOL {
         YES
@@ -1526,11 +1668,33 @@ impl WindowView {
 
     extern "C" fn scroll_wheel(this: &mut Object, _sel: Sel, nsevent: id) {
         let precise = unsafe { nsevent.hasPreciseScrollingDeltas() } == YES;
+        if precise {
+            let vert_points = unsafe { nsevent.scrollingDeltaY() };
+            let horz_points = unsafe { nsevent.scrollingDeltaX() };
+            if vert_points.abs() > horz_points.abs() {
+                // Devices with precise deltas report the number of points
+                // scrolled; pass that along in pixels and let the terminal
+                // work out how many rows that is.  The system generates
+                // the momentum events itself, so we never report the end
+                // of the gesture.
+                let view = this as id;
+                let rect = NSRect::new(NSPoint::new(0., 0.), NSSize::new(0., vert_points.abs()));
+                let backing_rect = unsafe { NSView::convertRectToBacking(view, rect) };
+                let pixels = backing_rect.size.height.copysign(vert_points).round();
+                if pixels != 0. {
+                    Self::mouse_common(
+                        this,
+                        nsevent,
+                        MouseEventKind::VertPixelWheel(pixels as i16),
+                    );
+                }
+                return;
+            }
+        }
         let scale = if precise {
-            // Devices with precise deltas report number of pixels scrolled.
+            // Horizontal scrolling is still reported in columns.
             // At this layer we don't know how many pixels comprise a cell
-            // in the terminal widget, and our abstraction doesn't allow being
-            // told what that amount should be, so we come up with a hard
+            // in the terminal widget, so we come up with a hard
             // coded factor based on the likely default font size and dpi
             // to make the scroll speed feel a bit better.
             15.0
@@ -1587,6 +1751,45 @@ impl WindowView {
         Self::mouse_common(this, nsevent, kind);
     }
 
+    fn gesture_common(this: &mut Object, event: GestureEvent) {
+        if let Some(myself) = Self::get_this(this) {
+            let mut inner = myself.inner.borrow_mut();
+            let window = Window(inner.window_id);
+            inner.callbacks.gesture_event(&event, &window);
+        }
+    }
+
+    extern "C" fn magnify_with_event(this: &mut Object, _sel: Sel, nsevent: id) {
+        let delta = unsafe { nsevent.magnification() };
+        Self::gesture_common(this, GestureEvent::Pinch { delta });
+    }
+
+    extern "C" fn swipe_with_event(this: &mut Object, _sel: Sel, nsevent: id) {
+        // The system only reports swipes made with the number of fingers
+        // that is configured in the trackpad preferences, which is three
+        // by default.  The deltas are positive for swipes to the left
+        // and upwards.
+        let (delta_x, delta_y) = unsafe { (nsevent.deltaX(), nsevent.deltaY()) };
+        let direction = if delta_x > 0. {
+            SwipeDirection::Left
+        } else if delta_x < 0. {
+            SwipeDirection::Right
+        } else if delta_y > 0. {
+            SwipeDirection::Up
+        } else if delta_y < 0. {
+            SwipeDirection::Down
+        } else {
+            return;
+        };
+        Self::gesture_common(
+            this,
+            GestureEvent::Swipe {
+                direction,
+                fingers: 3,
+            },
+        );
+    }
+
     extern "C" fn right_mouse_down(this: &mut Object, _sel: Sel, nsevent: id) {
         Self::mouse_common(this, nsevent, MouseEventKind::Press(MousePress::Right));
     }
@@ -1993,6 +2196,22 @@ impl WindowView {
                 sel!(windowDidResignKey:),
                 Self::did_resign_key as extern "C" fn(&mut Object, Sel, id),
             );
+            cls.add_method(
+                sel!(draggingEntered:),
+                Self::dragging_entered as extern "C" fn(&mut Object, Sel, id) -> NSUInteger,
+            );
+            cls.add_method(
+                sel!(performDragOperation:),
+                Self::perform_drag_operation as extern "C" fn(&mut Object, Sel, id) -> BOOL,
+            );
+            cls.add_method(
+                sel!(viewDidChangeEffectiveAppearance),
+                Self::did_change_effective_appearance as extern "C" fn(&mut Object, Sel),
+            );
+            cls.add_method(
+                sel!(wezMenuItemSelected:),
+                Self::menu_item_selected as extern "C" fn(&mut Object, Sel, id),
+            );
 
             cls.add_method(
                 sel!(mouseMoved:),
@@ -2034,6 +2253,14 @@ impl WindowView {
                 sel!(scrollWheel:),
                 Self::scroll_wheel as extern "C" fn(&mut Object, Sel, id),
             );
+            cls.add_method(
+                sel!(magnifyWithEvent:),
+                Self::magnify_with_event as extern "C" fn(&mut Object, Sel, id),
+            );
+            cls.add_method(
+                sel!(swipeWithEvent:),
+                Self::swipe_with_event as extern "C" fn(&mut Object, Sel, id),
+            );
 
             cls.add_method(
                 sel!(keyDown:),
@@ -13,13 +13,24 @@ use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::atomic::AtomicUsize;
 use std::time::{Duration, Instant};
-use toolkit::environment::Environment;
+use toolkit::environment::{Environment, SimpleGlobal};
 use toolkit::reexports::calloop::{EventLoop, EventSource, Interest, Mode, Poll, Readiness, Token};
 use toolkit::reexports::client::Display;
+use toolkit::reexports::protocols::unstable::pointer_gestures::v1::client::zwp_pointer_gestures_v1::ZwpPointerGesturesV1;
+use toolkit::reexports::protocols::viewporter::client::wp_viewporter::WpViewporter;
 use toolkit::seat::SeatListener;
 use toolkit::WaylandSource;
 
-toolkit::default_environment!(MyEnvironment, desktop);
+toolkit::default_environment!(MyEnvironment, desktop,
+    fields = [
+        pointer_gestures: SimpleGlobal<ZwpPointerGesturesV1>,
+        viewporter: SimpleGlobal<WpViewporter>,
+    ],
+    singles = [
+        ZwpPointerGesturesV1 => pointer_gestures,
+        WpViewporter => viewporter,
+    ],
+);
 
 pub struct WaylandConnection {
     should_terminate: RefCell<bool>,
@@ -46,8 +57,10 @@ pub struct WaylandConnection {
 
 impl WaylandConnection {
     pub fn create_new() -> anyhow::Result<Self> {
-        let (environment, display, event_q) =
-            toolkit::new_default_environment!(MyEnvironment, desktop)?;
+        let (environment, display, event_q) = toolkit::new_default_environment!(MyEnvironment, desktop, fields = [
+            pointer_gestures: SimpleGlobal::new(),
+            viewporter: SimpleGlobal::new(),
+        ])?;
         let event_loop = toolkit::reexports::calloop::EventLoop::<()>::new()?;
 
         let keyboard = KeyboardDispatcher::new();
@@ -72,6 +85,7 @@ impl WaylandConnection {
                         environment.require_global(),
                         environment.require_global(),
                         environment.require_global(),
+                        environment.get_global(),
                     )?);
                 }
             }
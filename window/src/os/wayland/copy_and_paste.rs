@@ -1,16 +1,20 @@
 use anyhow::{anyhow, Error};
 use filedescriptor::{FileDescriptor, Pipe};
 use smithay_client_toolkit as toolkit;
-use std::os::unix::io::AsRawFd;
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd};
 use std::sync::{Arc, Mutex};
+use toolkit::primary_selection::PrimarySelectionSource;
 use toolkit::reexports::client::protocol::wl_data_offer::{Event as DataOfferEvent, WlDataOffer};
 use toolkit::reexports::client::protocol::wl_data_source::WlDataSource;
+use toolkit::reexports::client::protocol::wl_seat::WlSeat;
 use wayland_client::Attached;
 
 #[derive(Default)]
 pub struct CopyAndPaste {
     data_offer: Option<WlDataOffer>,
     last_serial: u32,
+    /// The source of the primary selection that we most recently set
+    primary_selection_source: Option<PrimarySelectionSource>,
 }
 
 impl std::fmt::Debug for CopyAndPaste {
@@ -70,6 +74,65 @@ impl CopyAndPaste {
         self.data_offer.replace(offer);
     }
 
+    /// Returns the seat whose primary selection is used.
+    /// The primary selection belongs to a seat, and wezterm only
+    /// tracks the serial of the most recent input, so the first
+    /// seat with a keyboard is used.
+    fn primary_selection_seat() -> anyhow::Result<Attached<WlSeat>> {
+        use crate::connection::ConnectionOps;
+        crate::Connection::get()
+            .unwrap()
+            .wayland()
+            .environment
+            .borrow()
+            .get_all_seats()
+            .into_iter()
+            .find(|seat| {
+                toolkit::seat::with_seat_data(seat, |data| data.has_keyboard && !data.defunct)
+                    .unwrap_or(false)
+            })
+            .ok_or_else(|| anyhow!("no seat has a keyboard"))
+    }
+
+    pub fn get_primary_selection_data(&self) -> anyhow::Result<FileDescriptor> {
+        use crate::connection::ConnectionOps;
+        let seat = Self::primary_selection_seat()?;
+        let mut result = Err(anyhow!("the primary selection is empty"));
+        crate::Connection::get()
+            .unwrap()
+            .wayland()
+            .environment
+            .borrow()
+            .with_primary_selection(&seat, |device| {
+                device.with_selection(|offer| {
+                    if let Some(offer) = offer {
+                        result = offer
+                            .receive(TEXT_MIME_TYPE.to_string())
+                            .map(|pipe| unsafe { FileDescriptor::from_raw_fd(pipe.into_raw_fd()) })
+                            .map_err(Error::msg);
+                    }
+                })
+            })
+            .map_err(|_| anyhow!("the compositor doesn't support the primary selection"))?;
+        result
+    }
+
+    pub fn set_primary_selection(&mut self, source: PrimarySelectionSource) -> anyhow::Result<()> {
+        use crate::connection::ConnectionOps;
+        let seat = Self::primary_selection_seat()?;
+        let serial = self.last_serial;
+        let source = Some(source);
+        crate::Connection::get()
+            .unwrap()
+            .wayland()
+            .environment
+            .borrow()
+            .with_primary_selection(&seat, |device| device.set_selection(&source, serial))
+            .map_err(|_| anyhow!("the compositor doesn't support the primary selection"))?;
+        self.primary_selection_source = source;
+        Ok(())
+    }
+
     pub fn set_selection(&mut self, source: &Attached<WlDataSource>) {
         use crate::connection::ConnectionOps;
         crate::Connection::get()
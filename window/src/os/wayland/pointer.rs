@@ -11,6 +11,11 @@ use toolkit::reexports::client::protocol::wl_pointer::{
     self, Axis, AxisSource, Event as PointerEvent,
 };
 use toolkit::reexports::client::protocol::wl_surface::WlSurface;
+use toolkit::reexports::protocols::unstable::pointer_gestures::v1::client::{
+    zwp_pointer_gesture_pinch_v1::{Event as PinchEvent, ZwpPointerGesturePinchV1},
+    zwp_pointer_gesture_swipe_v1::{Event as SwipeEvent, ZwpPointerGestureSwipeV1},
+    zwp_pointer_gestures_v1::ZwpPointerGesturesV1,
+};
 use toolkit::seat::pointer::{ThemeManager, ThemeSpec, ThemedPointer};
 use wayland_client::protocol::wl_compositor::WlCompositor;
 use wayland_client::protocol::wl_data_device_manager::WlDataDeviceManager;
@@ -19,11 +24,25 @@ use wayland_client::protocol::wl_shm::WlShm;
 use wayland_client::{Attached, Main};
 use wezterm_input_types::*;
 
+/// A swipe must move at least this far, in surface coordinates,
+/// before it is reported
+const MIN_SWIPE_DISTANCE: f64 = 50.;
+
+/// Tracks a swipe gesture that is in progress
+struct Swipe {
+    fingers: u32,
+    dx: f64,
+    dy: f64,
+}
+
 #[derive(Default)]
 struct Inner {
     active_surface_id: u32,
     surface_to_pending: HashMap<u32, Arc<Mutex<PendingMouse>>>,
     serial: u32,
+    swipe: Option<Swipe>,
+    /// The scale reported by the previous pinch event
+    pinch_scale: f64,
 }
 
 impl Inner {
@@ -46,6 +65,81 @@ impl Inner {
         }
     }
 
+    fn dispatch_gesture(&self, event: GestureEvent) {
+        if let Some(pending) = self.surface_to_pending.get(&self.active_surface_id) {
+            let window_id = pending.lock().unwrap().window_id;
+            WaylandConnection::with_window_inner(window_id, move |inner| {
+                inner.dispatch_gesture(&event);
+                Ok(())
+            });
+        }
+    }
+
+    fn handle_swipe_event(&mut self, evt: SwipeEvent) {
+        match evt {
+            SwipeEvent::Begin { fingers, .. } => {
+                self.swipe.replace(Swipe {
+                    fingers,
+                    dx: 0.,
+                    dy: 0.,
+                });
+            }
+            SwipeEvent::Update { dx, dy, .. } => {
+                if let Some(swipe) = self.swipe.as_mut() {
+                    swipe.dx += dx;
+                    swipe.dy += dy;
+                }
+            }
+            SwipeEvent::End { cancelled, .. } => {
+                let swipe = match self.swipe.take() {
+                    Some(swipe) if cancelled == 0 => swipe,
+                    _ => return,
+                };
+                let direction = if swipe.dx.abs() >= swipe.dy.abs() {
+                    if swipe.dx.abs() < MIN_SWIPE_DISTANCE {
+                        return;
+                    }
+                    if swipe.dx < 0. {
+                        SwipeDirection::Left
+                    } else {
+                        SwipeDirection::Right
+                    }
+                } else {
+                    if swipe.dy.abs() < MIN_SWIPE_DISTANCE {
+                        return;
+                    }
+                    if swipe.dy < 0. {
+                        SwipeDirection::Up
+                    } else {
+                        SwipeDirection::Down
+                    }
+                };
+                self.dispatch_gesture(GestureEvent::Swipe {
+                    direction,
+                    fingers: swipe.fingers.min(u8::max_value() as u32) as u8,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_pinch_event(&mut self, evt: PinchEvent) {
+        match evt {
+            PinchEvent::Begin { .. } => {
+                self.pinch_scale = 1.0;
+            }
+            PinchEvent::Update { scale, .. } => {
+                // The scale is relative to the start of the pinch
+                let delta = scale - self.pinch_scale;
+                self.pinch_scale = scale;
+                if delta != 0. {
+                    self.dispatch_gesture(GestureEvent::Pinch { delta });
+                }
+            }
+            _ => {}
+        }
+    }
+
     fn resolve_copy_and_paste(&mut self) -> Option<Arc<Mutex<CopyAndPaste>>> {
         if let Some(pending) = self.surface_to_pending.get(&self.active_surface_id) {
             Some(Arc::clone(&pending.lock().unwrap().copy_and_paste))
@@ -97,6 +191,11 @@ pub struct PointerDispatcher {
     auto_pointer: ThemedPointer,
     #[allow(dead_code)]
     themer: ThemeManager,
+    #[allow(dead_code)]
+    gestures: Option<(
+        Main<ZwpPointerGestureSwipeV1>,
+        Main<ZwpPointerGesturePinchV1>,
+    )>,
 }
 
 #[derive(Clone, Debug)]
@@ -106,6 +205,12 @@ pub struct PendingMouse {
     surface_coords: Option<(f64, f64)>,
     button: Vec<(MousePress, DebuggableButtonState)>,
     scroll: Option<(f64, f64)>,
+    /// The number of wheel clicks in the pending vertical scroll
+    discrete_scroll: Option<i32>,
+    /// The device that is producing the scroll events
+    scroll_source: Option<AxisSource>,
+    /// Set when the fingers are lifted from the touchpad
+    scroll_stopped: bool,
 }
 
 impl PendingMouse {
@@ -115,6 +220,9 @@ impl PendingMouse {
             copy_and_paste: Arc::clone(copy_and_paste),
             button: vec![],
             scroll: None,
+            discrete_scroll: None,
+            scroll_source: None,
+            scroll_stopped: false,
             surface_coords: None,
         }))
     }
@@ -186,6 +294,27 @@ impl PendingMouse {
                 self.scroll.replace((x + value, y));
                 changed
             }
+            SendablePointerEvent::AxisDiscrete {
+                axis: Axis::VerticalScroll,
+                discrete,
+            } => {
+                let changed = self.scroll.is_none();
+                self.discrete_scroll
+                    .replace(self.discrete_scroll.unwrap_or(0) + discrete);
+                changed
+            }
+            SendablePointerEvent::AxisSource { axis_source } => {
+                self.scroll_source.replace(axis_source);
+                false
+            }
+            SendablePointerEvent::AxisStop {
+                axis: Axis::VerticalScroll,
+                ..
+            } => {
+                let changed = !self.scroll_stopped && self.scroll.is_none();
+                self.scroll_stopped = true;
+                changed
+            }
             _ => false,
         }
     }
@@ -206,6 +335,25 @@ impl PendingMouse {
     pub fn scroll(pending: &Arc<Mutex<Self>>) -> Option<(f64, f64)> {
         pending.lock().unwrap().scroll.take()
     }
+
+    /// Returns the number of wheel clicks that produced the pending
+    /// vertical scroll, if it came from a wheel
+    pub fn discrete_scroll(pending: &Arc<Mutex<Self>>) -> Option<i32> {
+        pending.lock().unwrap().discrete_scroll.take()
+    }
+
+    /// Returns true if the scroll is coming from a touchpad, or some
+    /// other device that reports continuous positions
+    pub fn is_continuous_scroll(pending: &Arc<Mutex<Self>>) -> bool {
+        matches!(
+            pending.lock().unwrap().scroll_source,
+            Some(AxisSource::Finger) | Some(AxisSource::Continuous)
+        )
+    }
+
+    pub fn scroll_stopped(pending: &Arc<Mutex<Self>>) -> bool {
+        std::mem::replace(&mut pending.lock().unwrap().scroll_stopped, false)
+    }
 }
 
 impl PointerDispatcher {
@@ -214,6 +362,7 @@ impl PointerDispatcher {
         compositor: Attached<WlCompositor>,
         shm: Attached<WlShm>,
         dev_mgr: Attached<WlDataDeviceManager>,
+        pointer_gestures: Option<Attached<ZwpPointerGesturesV1>>,
     ) -> anyhow::Result<Self> {
         let inner = Arc::new(Mutex::new(Inner::default()));
         let pointer = seat.get_pointer();
@@ -224,6 +373,26 @@ impl PointerDispatcher {
             }
         });
 
+        // Touchpad gestures are only available if the compositor
+        // implements the pointer gestures protocol
+        let gestures = pointer_gestures.map(|pointer_gestures| {
+            let swipe = pointer_gestures.get_swipe_gesture(&pointer);
+            swipe.quick_assign({
+                let inner = Arc::clone(&inner);
+                move |_, evt, _| {
+                    inner.lock().unwrap().handle_swipe_event(evt);
+                }
+            });
+            let pinch = pointer_gestures.get_pinch_gesture(&pointer);
+            pinch.quick_assign({
+                let inner = Arc::clone(&inner);
+                move |_, evt, _| {
+                    inner.lock().unwrap().handle_pinch_event(evt);
+                }
+            });
+            (swipe, pinch)
+        });
+
         let themer = ThemeManager::init(ThemeSpec::System, compositor, shm);
         let auto_pointer = themer.theme_pointer(pointer.detach());
 
@@ -240,6 +409,7 @@ impl PointerDispatcher {
             data_device,
             themer,
             auto_pointer,
+            gestures,
         })
     }
 
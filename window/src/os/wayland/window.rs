@@ -9,7 +9,7 @@ use crate::{
     WindowOps, WindowOpsMut,
 };
 use anyhow::{anyhow, bail, Context};
-use config::ConfigHandle;
+use config::{ConfigHandle, WaylandDecorations};
 use filedescriptor::FileDescriptor;
 use promise::{Future, Promise};
 use smithay_client_toolkit as toolkit;
@@ -17,14 +17,20 @@ use std::any::Any;
 use std::cell::RefCell;
 use std::convert::TryInto;
 use std::io::{Read, Write};
-use std::os::unix::io::{AsRawFd, FromRawFd};
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd};
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 use toolkit::get_surface_scale_factor;
+use toolkit::primary_selection::PrimarySelectionSourceEvent;
 use toolkit::reexports::client::protocol::wl_data_source::Event as DataSourceEvent;
 use toolkit::reexports::client::protocol::wl_surface::WlSurface;
-use toolkit::window::{ButtonColorSpec, ColorSpec, ConceptConfig, ConceptFrame, Event, State};
+use toolkit::reexports::protocols::viewporter::client::wp_viewport::WpViewport;
+use toolkit::reexports::protocols::viewporter::client::wp_viewporter::WpViewporter;
+use toolkit::window::{
+    ButtonColorSpec, ColorSpec, ConceptConfig, ConceptFrame, Decorations, Event, State,
+};
 use wayland_client::protocol::wl_data_device_manager::WlDataDeviceManager;
+use wayland_client::Main;
 use wayland_egl::{is_available as egl_is_available, WlEglSurface};
 use wezterm_input_types::*;
 
@@ -78,8 +84,21 @@ fn frame_config() -> ConceptConfig {
     }
 }
 
+/// Returns the decorations that the configuration asks for
+fn decorations(config: &ConfigHandle) -> Decorations {
+    if !config.window_decorations.contains(WindowDecorations::TITLE) {
+        return Decorations::None;
+    }
+    match config.wayland_decorations {
+        WaylandDecorations::FollowServer => Decorations::FollowServer,
+        WaylandDecorations::ClientSide => Decorations::ClientSide,
+        WaylandDecorations::ServerSide => Decorations::ServerSide,
+    }
+}
+
 pub struct WaylandWindowInner {
     window_id: usize,
+    config: ConfigHandle,
     callbacks: Box<dyn WindowCallbacks>,
     surface: WlSurface,
     copy_and_paste: Arc<Mutex<CopyAndPaste>>,
@@ -92,6 +111,9 @@ pub struct WaylandWindowInner {
     modifiers: Modifiers,
     pending_event: Arc<Mutex<PendingEvent>>,
     pending_mouse: Arc<Mutex<PendingMouse>>,
+    /// Used to present the surface at a fractional scale;
+    /// see `apply_scale`
+    viewport: Option<Main<WpViewport>>,
     // wegl_surface is listed before gl_state because it
     // must be dropped before gl_state otherwise the underlying
     // libraries will segfault on shutdown
@@ -167,8 +189,12 @@ impl WaylandWindow {
         width: usize,
         height: usize,
         callbacks: Box<dyn WindowCallbacks>,
-        _config: Option<&ConfigHandle>,
+        config: Option<&ConfigHandle>,
     ) -> anyhow::Result<Window> {
+        let config = match config {
+            Some(c) => c.clone(),
+            None => config::configuration(),
+        };
         let conn = WaylandConnection::get()
             .ok_or_else(|| {
                 anyhow!(
@@ -235,6 +261,7 @@ impl WaylandWindow {
         window.set_resizable(true);
         window.set_title(name.to_string());
         window.set_frame_config(frame_config());
+        window.set_decorate(decorations(&config));
         window.set_min_size(Some((32, 32)));
 
         // window.new_seat(&conn.seat);
@@ -248,6 +275,7 @@ impl WaylandWindow {
         let inner = Rc::new(RefCell::new(WaylandWindowInner {
             copy_and_paste,
             window_id,
+            config,
             callbacks,
             surface: surface.detach(),
             window: Some(window),
@@ -259,6 +287,7 @@ impl WaylandWindow {
             modifiers: Modifiers::NONE,
             pending_event,
             pending_mouse,
+            viewport: None,
             gl_state: None,
             wegl_surface: None,
         }));
@@ -396,54 +425,117 @@ impl WaylandWindowInner {
         }
 
         if let Some((value_x, value_y)) = PendingMouse::scroll(&pending_mouse) {
-            let factor = self.get_dpi_factor() as f64;
+            let factor = self.get_dpi_factor();
             let discrete_x = value_x.trunc() * factor;
             if discrete_x != 0. {
-                let event = MouseEvent {
-                    kind: MouseEventKind::HorzWheel(-discrete_x as i16),
-                    coords: self.last_mouse_coords,
-                    screen_coords: ScreenPoint::new(
-                        self.last_mouse_coords.x + self.dimensions.pixel_width as isize,
-                        self.last_mouse_coords.y + self.dimensions.pixel_height as isize,
-                    ),
-                    mouse_buttons: self.mouse_buttons,
-                    modifiers: self.modifiers,
-                };
-                self.callbacks
-                    .mouse_event(&event, &Window::Wayland(WaylandWindow(self.window_id)));
+                self.dispatch_wheel_event(MouseEventKind::HorzWheel(-discrete_x as i16));
             }
 
-            let discrete_y = value_y.trunc() * factor;
-            if discrete_y != 0. {
-                let event = MouseEvent {
-                    kind: MouseEventKind::VertWheel(-discrete_y as i16),
-                    coords: self.last_mouse_coords,
-                    screen_coords: ScreenPoint::new(
-                        self.last_mouse_coords.x + self.dimensions.pixel_width as isize,
-                        self.last_mouse_coords.y + self.dimensions.pixel_height as isize,
-                    ),
-                    mouse_buttons: self.mouse_buttons,
-                    modifiers: self.modifiers,
-                };
-                self.callbacks
-                    .mouse_event(&event, &Window::Wayland(WaylandWindow(self.window_id)));
+            if let Some(clicks) = PendingMouse::discrete_scroll(&pending_mouse) {
+                let lines = clicks * config::configuration().scroll_lines_per_tick as i32;
+                if lines != 0 {
+                    self.dispatch_wheel_event(MouseEventKind::VertWheel(-lines as i16));
+                }
+            } else if PendingMouse::is_continuous_scroll(&pending_mouse) {
+                // Let the terminal decide how many pixels make up a line
+                let pixels = (value_y * factor).round();
+                if pixels != 0. {
+                    self.dispatch_wheel_event(MouseEventKind::VertPixelWheel(-pixels as i16));
+                }
+            } else {
+                let discrete_y = value_y.trunc() * factor;
+                if discrete_y != 0. {
+                    self.dispatch_wheel_event(MouseEventKind::VertWheel(-discrete_y as i16));
+                }
             }
         }
+
+        if PendingMouse::scroll_stopped(&pending_mouse) {
+            self.dispatch_wheel_event(MouseEventKind::PixelWheelStop);
+        }
+    }
+
+    pub(crate) fn dispatch_gesture(&mut self, event: &GestureEvent) {
+        self.callbacks
+            .gesture_event(event, &Window::Wayland(WaylandWindow(self.window_id)));
     }
 
-    fn get_dpi_factor(&self) -> i32 {
-        self.dimensions.dpi as i32 / crate::DEFAULT_DPI as i32
+    fn dispatch_wheel_event(&mut self, kind: MouseEventKind) {
+        let event = MouseEvent {
+            kind,
+            coords: self.last_mouse_coords,
+            screen_coords: ScreenPoint::new(
+                self.last_mouse_coords.x + self.dimensions.pixel_width as isize,
+                self.last_mouse_coords.y + self.dimensions.pixel_height as isize,
+            ),
+            mouse_buttons: self.mouse_buttons,
+            modifiers: self.modifiers,
+        };
+        self.callbacks
+            .mouse_event(&event, &Window::Wayland(WaylandWindow(self.window_id)));
+    }
+
+    fn get_dpi_factor(&self) -> f64 {
+        self.dimensions.dpi as f64 / crate::DEFAULT_DPI
     }
 
     fn surface_to_pixels(&self, surface: i32) -> i32 {
-        surface * self.get_dpi_factor()
+        (surface as f64 * self.get_dpi_factor()).round() as i32
     }
 
     fn pixels_to_surface(&self, pixels: i32) -> i32 {
         // Take care to round up, otherwise we can lose a pixel
         // and that can effectively lose the final row of the
         // terminal
-        ((pixels as f64) / (self.get_dpi_factor() as f64)).ceil() as i32
+        ((pixels as f64) / self.get_dpi_factor()).ceil() as i32
+    }
+
+    fn viewport(&mut self) -> Option<Main<WpViewport>> {
+        if self.viewport.is_none() {
+            let conn = Connection::get().unwrap().wayland();
+            let viewporter = conn.environment.borrow().get_global::<WpViewporter>()?;
+            self.viewport
+                .replace(viewporter.get_viewport(&self.surface));
+        }
+        self.viewport.clone()
+    }
+
+    /// Sets up the surface to be presented at `width` x `height`
+    /// surface units, and returns the scale at which its contents
+    /// are to be rendered
+    fn apply_scale(&mut self, width: u32, height: u32) -> f64 {
+        if let Some(scale) = self.config.wayland_fractional_scale {
+            if scale > 0. {
+                if let Some(viewport) = self.viewport() {
+                    // Render at full resolution and have the
+                    // compositor present the buffer at the logical
+                    // size of the surface
+                    self.surface.set_buffer_scale(1);
+                    viewport.set_destination(width as i32, height as i32);
+                    return scale;
+                }
+                static WARNED: std::sync::Once = std::sync::Once::new();
+                WARNED.call_once(|| {
+                    log::warn!(
+                        "wayland_fractional_scale is set, but the compositor \
+                         doesn't support the viewporter protocol"
+                    )
+                });
+            }
+        }
+
+        if let Some(viewport) = self.viewport.as_ref() {
+            // Unset any destination from a prior fractional scale
+            viewport.set_destination(-1, -1);
+        }
+
+        // Avoid blurring by matching the scaling factor of the
+        // compositor; if it is going to double the size then
+        // we render at double the size anyway and tell it that
+        // the buffer is already doubled
+        let factor = get_surface_scale_factor(&self.surface);
+        self.surface.set_buffer_scale(factor);
+        factor as f64
     }
 
     fn dispatch_pending_event(&mut self) {
@@ -478,16 +570,10 @@ impl WaylandWindowInner {
 
         if let Some((w, h)) = pending.configure.take() {
             if self.window.is_some() {
-                let factor = get_surface_scale_factor(&self.surface);
+                let scale = self.apply_scale(w, h);
 
-                let pixel_width = self.surface_to_pixels(w.try_into().unwrap());
-                let pixel_height = self.surface_to_pixels(h.try_into().unwrap());
-
-                // Avoid blurring by matching the scaling factor of the
-                // compositor; if it is going to double the size then
-                // we render at double the size anyway and tell it that
-                // the buffer is already doubled
-                self.surface.set_buffer_scale(factor);
+                let pixel_width = (w as f64 * scale).round() as i32;
+                let pixel_height = (h as f64 * scale).round() as i32;
 
                 // Update the window decoration size
                 self.window.as_mut().unwrap().resize(w, h);
@@ -496,7 +582,7 @@ impl WaylandWindowInner {
                 let new_dimensions = Dimensions {
                     pixel_width: pixel_width.try_into().unwrap(),
                     pixel_height: pixel_height.try_into().unwrap(),
-                    dpi: factor as usize * crate::DEFAULT_DPI as usize,
+                    dpi: (scale * crate::DEFAULT_DPI).round() as usize,
                 };
                 // Only trigger a resize if the new dimensions are different;
                 // this makes things more efficient and a little more smooth
@@ -683,12 +769,26 @@ impl WindowOps for WaylandWindow {
         })
     }
 
-    fn get_clipboard(&self, _clipboard: Clipboard) -> Future<String> {
+    fn config_did_change(&self, config: &ConfigHandle) -> Future<()> {
+        let config = config.clone();
+        WaylandConnection::with_window_inner(self.0, move |inner| {
+            inner.config_did_change(&config);
+            Ok(())
+        })
+    }
+
+    fn get_clipboard(&self, clipboard: Clipboard) -> Future<String> {
         let mut promise = Promise::new();
         let future = promise.get_future().unwrap();
         let promise = Arc::new(Mutex::new(promise));
         WaylandConnection::with_window_inner(self.0, move |inner| {
-            let read = inner.copy_and_paste.lock().unwrap().get_clipboard_data()?;
+            let read = {
+                let mut copy_and_paste = inner.copy_and_paste.lock().unwrap();
+                match clipboard {
+                    Clipboard::Clipboard => copy_and_paste.get_clipboard_data()?,
+                    Clipboard::PrimarySelection => copy_and_paste.get_primary_selection_data()?,
+                }
+            };
             let promise = Arc::clone(&promise);
             std::thread::spawn(move || {
                 let mut promise = promise.lock().unwrap();
@@ -710,11 +810,30 @@ impl WindowOps for WaylandWindow {
         future
     }
 
-    fn set_clipboard(&self, _clipboard: Clipboard, text: String) -> Future<()> {
+    fn set_clipboard(&self, clipboard: Clipboard, text: String) -> Future<()> {
         WaylandConnection::with_window_inner(self.0, move |inner| {
             let text = text.clone();
             let conn = Connection::get().unwrap().wayland();
 
+            if clipboard == Clipboard::PrimarySelection {
+                let source = conn.environment.borrow().new_primary_selection_source(
+                    vec![TEXT_MIME_TYPE.to_string()],
+                    move |event, _dispatch_data| {
+                        if let PrimarySelectionSourceEvent::Send { pipe, .. } = event {
+                            let fd = unsafe { FileDescriptor::from_raw_fd(pipe.into_raw_fd()) };
+                            if let Err(e) = write_pipe_with_timeout(fd, text.as_bytes()) {
+                                log::error!("while sending primary selection to pipe: {}", e);
+                            }
+                        }
+                    },
+                );
+                return inner
+                    .copy_and_paste
+                    .lock()
+                    .unwrap()
+                    .set_primary_selection(source);
+            }
+
             let source = conn
                 .environment
                 .borrow()
@@ -883,6 +1002,23 @@ impl WindowOpsMut for WaylandWindowInner {
 
     fn set_window_position(&self, _coords: ScreenPoint) {}
 
+    fn config_did_change(&mut self, config: &ConfigHandle) {
+        self.config = config.clone();
+        if let Some(window) = self.window.as_mut() {
+            window.set_decorate(decorations(config));
+        }
+        // Synthesize a configure event at the current size, so that
+        // a change to the fractional scale takes effect
+        let surface_width = self.pixels_to_surface(self.dimensions.pixel_width as i32) as u32;
+        let surface_height = self.pixels_to_surface(self.dimensions.pixel_height as i32) as u32;
+        self.pending_event
+            .lock()
+            .unwrap()
+            .configure
+            .replace((surface_width, surface_height));
+        self.dispatch_pending_event();
+    }
+
     /// Change the title for the window manager
     fn set_title(&mut self, title: &str) {
         if let Some(window) = self.window.as_ref() {
@@ -1,11 +1,13 @@
 //! The connection to the GUI subsystem
+use super::tray::TrayIcon;
 use super::{HWindow, WindowInner};
 use crate::connection::ConnectionOps;
 use crate::spawn::*;
+use crate::{KeyCode, MenuEntry, Modifiers, ScreenPoint, ScreenRect};
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::convert::TryInto;
-use std::ptr::null_mut;
+use std::ptr::{null, null_mut};
 use std::rc::Rc;
 use winapi::shared::basetsd::UINT_PTR;
 use winapi::shared::minwindef::*;
@@ -19,6 +21,9 @@ pub struct Connection {
     pub(crate) windows: RefCell<HashMap<HWindow, Rc<RefCell<WindowInner>>>>,
     timers: RefCell<HashMap<UINT_PTR, UINT_PTR>>,
     pub(crate) gl_connection: RefCell<Option<Rc<crate::egl::GlConnection>>>,
+    /// Callbacks for global hotkeys, indexed by their hotkey id
+    hotkeys: RefCell<Vec<Rc<RefCell<dyn FnMut()>>>>,
+    pub(crate) tray: RefCell<Option<TrayIcon>>,
 }
 
 impl ConnectionOps for Connection {
@@ -28,6 +33,58 @@ impl ConnectionOps for Connection {
         }
     }
 
+    fn get_appearance(&self) -> config::Appearance {
+        super::get_appearance()
+    }
+
+    fn screens(&self) -> Vec<ScreenRect> {
+        unsafe extern "system" fn callback(
+            monitor: HMONITOR,
+            _hdc: HDC,
+            _rect: LPRECT,
+            data: LPARAM,
+        ) -> BOOL {
+            let screens = &mut *(data as *mut Vec<(bool, ScreenRect)>);
+            let mut mi: MONITORINFO = std::mem::zeroed();
+            mi.cbSize = std::mem::size_of::<MONITORINFO>() as u32;
+            if GetMonitorInfoW(monitor, &mut mi) != 0 {
+                let rect = mi.rcMonitor;
+                screens.push((
+                    (mi.dwFlags & MONITORINFOF_PRIMARY) != 0,
+                    ScreenRect::new(
+                        ScreenPoint::new(rect.left as isize, rect.top as isize),
+                        euclid::size2(
+                            (rect.right - rect.left) as isize,
+                            (rect.bottom - rect.top) as isize,
+                        ),
+                    ),
+                ));
+            }
+            TRUE
+        }
+
+        let mut screens: Vec<(bool, ScreenRect)> = vec![];
+        unsafe {
+            EnumDisplayMonitors(
+                null_mut(),
+                null(),
+                Some(callback),
+                &mut screens as *mut _ as LPARAM,
+            );
+        }
+        // Place the primary monitor first
+        screens.sort_by_key(|(primary, _)| !primary);
+        screens.into_iter().map(|(_, rect)| rect).collect()
+    }
+
+    fn pointer_position(&self) -> Option<ScreenPoint> {
+        let mut point: POINT = unsafe { std::mem::zeroed() };
+        if unsafe { GetCursorPos(&mut point) } == 0 {
+            return None;
+        }
+        Some(ScreenPoint::new(point.x as isize, point.y as isize))
+    }
+
     fn run_message_loop(&self) -> anyhow::Result<()> {
         let mut msg: MSG = unsafe { std::mem::zeroed() };
         loop {
@@ -43,6 +100,22 @@ impl ConnectionOps for Connection {
                     return Ok(());
                 }
 
+                if msg.message == WM_HOTKEY {
+                    // Hotkeys are registered against the thread rather
+                    // than a window, so we need to pick them out here
+                    let callback = self
+                        .hotkeys
+                        .borrow()
+                        .get(msg.wParam as usize)
+                        .map(Rc::clone);
+                    // The hotkeys are no longer borrowed, so the
+                    // callback is free to register more of them
+                    if let Some(callback) = callback {
+                        (&mut *callback.borrow_mut())();
+                    }
+                    continue;
+                }
+
                 unsafe {
                     // We don't want to call TranslateMessage here
                     // unconditionally.  Instead, we perform translation
@@ -55,6 +128,84 @@ impl ConnectionOps for Connection {
         }
     }
 
+    fn register_global_hotkey<F: FnMut() + 'static>(
+        &self,
+        key: KeyCode,
+        mods: Modifiers,
+        callback: F,
+    ) -> anyhow::Result<()> {
+        let vk = match key {
+            KeyCode::Char('\u{1b}') => VK_ESCAPE,
+            KeyCode::Char('\t') => VK_TAB,
+            KeyCode::Char('\r') => VK_RETURN,
+            KeyCode::Char('\u{8}') => VK_BACK,
+            KeyCode::Char('\u{7f}') => VK_DELETE,
+            KeyCode::Char(c) if c.is_ascii_alphanumeric() || c == ' ' => {
+                c.to_ascii_uppercase() as i32
+            }
+            KeyCode::Char(c) if c.is_ascii() => {
+                // Punctuation, such as the backtick, depends on the layout
+                let scan = unsafe { VkKeyScanW(c as u16) };
+                if scan == -1 {
+                    anyhow::bail!("{:?} is not present in the keyboard layout", key);
+                }
+                (scan & 0xff) as i32
+            }
+            KeyCode::Function(n) if (1..=24).contains(&n) => VK_F1 + i32::from(n) - 1,
+            KeyCode::Insert => VK_INSERT,
+            KeyCode::Pause => VK_PAUSE,
+            KeyCode::Home => VK_HOME,
+            KeyCode::End => VK_END,
+            KeyCode::PageUp => VK_PRIOR,
+            KeyCode::PageDown => VK_NEXT,
+            KeyCode::ScrollLock => VK_SCROLL,
+            _ => anyhow::bail!("{:?} cannot be used as a global hotkey", key),
+        };
+
+        let mut modifiers = MOD_NOREPEAT;
+        if mods.contains(Modifiers::SHIFT) {
+            modifiers |= MOD_SHIFT;
+        }
+        if mods.contains(Modifiers::CTRL) {
+            modifiers |= MOD_CONTROL;
+        }
+        if mods.contains(Modifiers::ALT) {
+            modifiers |= MOD_ALT;
+        }
+        if mods.contains(Modifiers::SUPER) {
+            modifiers |= MOD_WIN;
+        }
+
+        let mut hotkeys = self.hotkeys.borrow_mut();
+        let id = hotkeys.len() as i32;
+        if unsafe { RegisterHotKey(null_mut(), id, modifiers as u32, vk as u32) } == 0 {
+            anyhow::bail!(
+                "RegisterHotKey for {:?} {:?} failed: {}; is another application using it?",
+                mods,
+                key,
+                std::io::Error::last_os_error()
+            );
+        }
+        hotkeys.push(Rc::new(RefCell::new(callback)));
+        Ok(())
+    }
+
+    fn set_tray_icon<E, F>(&self, entries: E, callback: F) -> anyhow::Result<()>
+    where
+        E: FnMut() -> Vec<MenuEntry> + 'static,
+        F: FnMut(usize) + 'static,
+    {
+        // Remove any prior icon before adding its replacement
+        self.tray.borrow_mut().take();
+        let tray = TrayIcon::new(Box::new(entries), Box::new(callback))?;
+        self.tray.borrow_mut().replace(tray);
+        Ok(())
+    }
+
+    fn remove_tray_icon(&self) {
+        self.tray.borrow_mut().take();
+    }
+
     fn schedule_timer<F: FnMut() + 'static>(&self, interval: std::time::Duration, callback: F) {
         let millis = interval
             .as_millis()
@@ -99,6 +250,8 @@ impl Connection {
             windows: RefCell::new(HashMap::new()),
             timers: RefCell::new(HashMap::new()),
             gl_connection: RefCell::new(None),
+            hotkeys: RefCell::new(vec![]),
+            tray: RefCell::new(None),
         })
     }
 
@@ -1,5 +1,6 @@
 pub mod connection;
 pub mod event;
+mod tray;
 mod wgl;
 pub mod window;
 
@@ -16,6 +17,23 @@ fn wide_string(s: &str) -> Vec<u16> {
         .collect()
 }
 
+/// Returns the current light/dark appearance by consulting the
+/// per-user app theme preference in the registry.
+pub fn get_appearance() -> config::Appearance {
+    use winapi::shared::minwindef::DWORD;
+    use winreg::{enums::HKEY_CURRENT_USER, RegKey};
+
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let light: Option<DWORD> = hkcu
+        .open_subkey("Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize")
+        .and_then(|key| key.get_value("AppsUseLightTheme"))
+        .ok();
+    match light {
+        Some(0) => config::Appearance::Dark,
+        _ => config::Appearance::Light,
+    }
+}
+
 /// Returns true if we are running in an RDP session.
 /// See <https://docs.microsoft.com/en-us/windows/win32/termserv/detecting-the-terminal-services-environment>
 pub fn is_running_in_rdp_session() -> bool {
@@ -0,0 +1,168 @@
+//! The notification area ("system tray") icon.
+//! The icon needs a window to deliver its messages to, so a hidden
+//! message-only window is created for it.
+use super::window::track_popup_menu;
+use super::{wide_string, Connection};
+use crate::connection::ConnectionOps;
+use crate::{MenuEntry, ScreenPoint};
+use std::io::Error as IoError;
+use std::ptr::{null, null_mut};
+use winapi::shared::minwindef::*;
+use winapi::shared::windef::*;
+use winapi::um::libloaderapi::GetModuleHandleW;
+use winapi::um::shellapi::*;
+use winapi::um::winuser::*;
+
+/// The message that the icon sends to its window
+const WM_TRAY_ICON: UINT = WM_APP + 1;
+
+const TRAY_CLASS_NAME: &str = "wezterm tray icon window";
+
+pub(crate) struct TrayIcon {
+    hwnd: HWND,
+    pub entries: Box<dyn FnMut() -> Vec<MenuEntry>>,
+    pub callback: Box<dyn FnMut(usize)>,
+}
+
+impl TrayIcon {
+    pub fn new(
+        entries: Box<dyn FnMut() -> Vec<MenuEntry>>,
+        callback: Box<dyn FnMut(usize)>,
+    ) -> anyhow::Result<Self> {
+        let class_name = wide_string(TRAY_CLASS_NAME);
+        let h_inst = unsafe { GetModuleHandleW(null()) };
+        let class = WNDCLASSW {
+            style: 0,
+            lpfnWndProc: Some(tray_wnd_proc),
+            cbClsExtra: 0,
+            cbWndExtra: 0,
+            hInstance: h_inst,
+            hIcon: null_mut(),
+            hCursor: null_mut(),
+            hbrBackground: null_mut(),
+            lpszMenuName: null(),
+            lpszClassName: class_name.as_ptr(),
+        };
+        if unsafe { RegisterClassW(&class) } == 0 {
+            let err = IoError::last_os_error();
+            match err.raw_os_error() {
+                Some(code)
+                    if code == winapi::shared::winerror::ERROR_CLASS_ALREADY_EXISTS as i32 => {}
+                _ => return Err(err.into()),
+            }
+        }
+
+        let hwnd = unsafe {
+            CreateWindowExW(
+                0,
+                class_name.as_ptr(),
+                class_name.as_ptr(),
+                0,
+                0,
+                0,
+                0,
+                0,
+                HWND_MESSAGE,
+                null_mut(),
+                h_inst,
+                null_mut(),
+            )
+        };
+        if hwnd.is_null() {
+            anyhow::bail!("CreateWindowExW: {}", IoError::last_os_error());
+        }
+
+        let mut data = notify_icon_data(hwnd);
+        data.uFlags = NIF_ICON | NIF_MESSAGE | NIF_TIP;
+        data.uCallbackMessage = WM_TRAY_ICON;
+        // The ID is defined in assets/windows/resource.rc
+        data.hIcon = unsafe { LoadIconW(h_inst, MAKEINTRESOURCEW(0x101)) };
+        let tip = wide_string("WezTerm");
+        data.szTip[..tip.len()].copy_from_slice(&tip);
+        if unsafe { Shell_NotifyIconW(NIM_ADD, &mut data) } == 0 {
+            unsafe { DestroyWindow(hwnd) };
+            anyhow::bail!("Shell_NotifyIconW failed to add the icon");
+        }
+
+        Ok(Self {
+            hwnd,
+            entries,
+            callback,
+        })
+    }
+}
+
+impl Drop for TrayIcon {
+    fn drop(&mut self) {
+        unsafe {
+            let mut data = notify_icon_data(self.hwnd);
+            Shell_NotifyIconW(NIM_DELETE, &mut data);
+            DestroyWindow(self.hwnd);
+        }
+    }
+}
+
+fn notify_icon_data(hwnd: HWND) -> NOTIFYICONDATAW {
+    let mut data: NOTIFYICONDATAW = unsafe { std::mem::zeroed() };
+    data.cbSize = std::mem::size_of::<NOTIFYICONDATAW>() as u32;
+    data.hWnd = hwnd;
+    data.uID = 1;
+    data
+}
+
+/// Shows the menu of the icon at the mouse position
+fn show_tray_menu(hwnd: HWND) {
+    let conn = match Connection::get() {
+        Some(conn) => conn,
+        None => return,
+    };
+    let entries = match conn.tray.borrow_mut().as_mut() {
+        Some(tray) => (tray.entries)(),
+        None => return,
+    };
+
+    let mut point = POINT { x: 0, y: 0 };
+    unsafe {
+        GetCursorPos(&mut point);
+        // The menu is only dismissed by clicking elsewhere if the
+        // window that owns it is in the foreground
+        SetForegroundWindow(hwnd);
+    }
+    let selected = track_popup_menu(
+        hwnd,
+        &entries,
+        ScreenPoint::new(point.x as isize, point.y as isize),
+    );
+    unsafe {
+        PostMessageW(hwnd, WM_NULL, 0, 0);
+    }
+
+    if let Some(id) = selected {
+        if let Some(tray) = conn.tray.borrow_mut().as_mut() {
+            (tray.callback)(id);
+        }
+    }
+}
+
+unsafe extern "system" fn tray_wnd_proc(
+    hwnd: HWND,
+    msg: UINT,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    if msg == WM_TRAY_ICON {
+        match lparam as UINT {
+            WM_LBUTTONUP | WM_RBUTTONUP | WM_CONTEXTMENU => {
+                // Showing the menu runs a nested message loop, which
+                // is best done outside of the window procedure
+                promise::spawn::spawn(async move {
+                    show_tray_menu(hwnd);
+                })
+                .detach();
+            }
+            _ => {}
+        }
+        return 0;
+    }
+    DefWindowProcW(hwnd, msg, wparam, lparam)
+}
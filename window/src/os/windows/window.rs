@@ -1,9 +1,9 @@
 use super::*;
 use crate::connection::ConnectionOps;
 use crate::{
-    Clipboard, Dimensions, KeyCode, KeyEvent, Modifiers, MouseButtons, MouseCursor, MouseEvent,
-    MouseEventKind, MousePress, Point, Rect, ScreenPoint, WindowCallbacks, WindowDecorations,
-    WindowOps, WindowOpsMut,
+    Clipboard, Dimensions, KeyCode, KeyEvent, MenuEntry, Modifiers, MouseButtons, MouseCursor,
+    MouseEvent, MouseEventKind, MousePress, Point, Rect, ResizeIncrement, ScreenPoint,
+    WindowCallbacks, WindowDecorations, WindowOps, WindowOpsMut,
 };
 use anyhow::{bail, Context};
 use config::ConfigHandle;
@@ -17,6 +17,7 @@ use std::convert::TryInto;
 use std::ffi::OsString;
 use std::io::{self, Error as IoError};
 use std::os::windows::ffi::OsStringExt;
+use std::path::PathBuf;
 use std::ptr::{null, null_mut};
 use std::rc::Rc;
 use winapi::shared::minwindef::*;
@@ -24,6 +25,7 @@ use winapi::shared::ntdef::*;
 use winapi::shared::windef::*;
 use winapi::um::imm::*;
 use winapi::um::libloaderapi::GetModuleHandleW;
+use winapi::um::shellapi::{DragAcceptFiles, DragFinish, DragQueryFileW, HDROP};
 use winapi::um::winuser::*;
 use winreg::{enums::HKEY_CURRENT_USER, RegKey};
 
@@ -50,6 +52,7 @@ pub(crate) struct WindowInner {
     in_size_move: bool,
     dead_pending: Option<(Modifiers, u32)>,
     saved_placement: Option<WINDOWPLACEMENT>,
+    resize_increments: Option<ResizeIncrement>,
 
     keyboard_info: KeyboardLayoutInfo,
 
@@ -360,6 +363,11 @@ impl Window {
             bail!("CreateWindowExW: {}", err);
         }
 
+        // Allow files to be dragged and dropped onto the window
+        unsafe {
+            DragAcceptFiles(hwnd, TRUE);
+        }
+
         // We have to re-apply the styles otherwise they don't
         // completely stick
         schedule_apply_decoration(hwnd, decorations);
@@ -390,6 +398,7 @@ impl Window {
             in_size_move: false,
             dead_pending: None,
             saved_placement: None,
+            resize_increments: None,
             config: config.clone(),
         }));
 
@@ -432,6 +441,18 @@ fn schedule_show_window(hwnd: HWindow, show: bool) {
     .detach();
 }
 
+fn schedule_focus_window(hwnd: HWindow) {
+    promise::spawn::spawn(async move {
+        unsafe {
+            if IsIconic(hwnd.0) != 0 {
+                ShowWindow(hwnd.0, SW_RESTORE);
+            }
+            SetForegroundWindow(hwnd.0);
+        }
+    })
+    .detach();
+}
+
 impl WindowOpsMut for WindowInner {
     fn close(&mut self) {
         let hwnd = self.hwnd;
@@ -486,6 +507,10 @@ impl WindowOpsMut for WindowInner {
         .detach();
     }
 
+    fn set_resize_increments(&mut self, incr: Option<ResizeIncrement>) {
+        self.resize_increments = incr;
+    }
+
     fn set_window_position(&self, coords: ScreenPoint) {
         let hwnd = self.hwnd.0;
         promise::spawn::spawn(async move {
@@ -532,6 +557,21 @@ impl WindowOpsMut for WindowInner {
         self.apply_decoration();
     }
 
+    fn request_attention(&mut self) {
+        // Flash the taskbar button until the window comes to the
+        // foreground
+        let mut info = FLASHWINFO {
+            cbSize: std::mem::size_of::<FLASHWINFO>() as u32,
+            hwnd: self.hwnd.0,
+            dwFlags: FLASHW_TRAY | FLASHW_TIMERNOFG,
+            uCount: 0,
+            dwTimeout: 0,
+        };
+        unsafe {
+            FlashWindowEx(&mut info);
+        }
+    }
+
     fn toggle_fullscreen(&mut self) {
         unsafe {
             let hwnd = self.hwnd.0;
@@ -601,6 +641,11 @@ impl WindowOps for Window {
         Future::ok(()) // FIXME: this is a lie!
     }
 
+    fn focus(&self) -> Future<()> {
+        schedule_focus_window(self.0);
+        Future::ok(()) // FIXME: this is a lie!
+    }
+
     fn set_cursor(&self, cursor: Option<MouseCursor>) -> Future<()> {
         Connection::with_window_inner(self.0, move |inner| {
             inner.set_cursor(cursor);
@@ -632,6 +677,13 @@ impl WindowOps for Window {
         })
     }
 
+    fn request_attention(&self) -> Future<()> {
+        Connection::with_window_inner(self.0, move |inner| {
+            inner.request_attention();
+            Ok(())
+        })
+    }
+
     fn config_did_change(&self, config: &ConfigHandle) -> Future<()> {
         let config = config.clone();
         Connection::with_window_inner(self.0, move |inner| {
@@ -640,6 +692,29 @@ impl WindowOps for Window {
         })
     }
 
+    fn show_context_menu(&self, entries: Vec<MenuEntry>, coords: Point) -> Future<()> {
+        let hwnd = self.0;
+        let mut prom = promise::Promise::new();
+        let future = prom.get_future().unwrap();
+        // TrackPopupMenu runs a nested message loop, so it must not be
+        // called while the window is borrowed; the selection is
+        // dispatched once it has returned
+        promise::spawn::spawn_into_main_thread(async move {
+            let point = client_to_screen(hwnd.0, coords);
+            let selected = track_popup_menu(hwnd.0, &entries, point);
+            prom.ok(());
+            if let Some(id) = selected {
+                Connection::with_window_inner(hwnd, move |inner| {
+                    let window = Window(inner.hwnd);
+                    inner.callbacks.borrow_mut().menu_item_selected(id, &window);
+                    Ok(())
+                });
+            }
+        })
+        .detach();
+        future
+    }
+
     fn set_text_cursor_position(&self, cursor: Rect) -> Future<()> {
         Connection::with_window_inner(self.0, move |inner| {
             inner.set_text_cursor_position(cursor);
@@ -654,6 +729,13 @@ impl WindowOps for Window {
         })
     }
 
+    fn set_resize_increments(&self, incr: Option<ResizeIncrement>) -> Future<()> {
+        Connection::with_window_inner(self.0, move |inner| {
+            inner.set_resize_increments(incr);
+            Ok(())
+        })
+    }
+
     fn set_window_position(&self, coords: ScreenPoint) -> Future<()> {
         Connection::with_window_inner(self.0, move |inner| {
             inner.set_window_position(coords);
@@ -824,6 +906,44 @@ unsafe fn wm_enter_exit_size_move(
     Some(0)
 }
 
+/// Windows has no notion of resize increments, so while the window is
+/// being interactively resized we snap the proposed window rectangle
+/// such that its client area is a whole number of increments.
+unsafe fn wm_sizing(hwnd: HWND, _msg: UINT, wparam: WPARAM, lparam: LPARAM) -> Option<LRESULT> {
+    let inner = rc_from_hwnd(hwnd)?;
+    let inner = inner.borrow();
+    let incr = inner.resize_increments?;
+
+    let rect = &mut *(lparam as *mut RECT);
+    let style = decorations_to_style(inner.config.window_decorations);
+    let (frame_width, frame_height) = adjust_client_to_window_dimensions(style, 0, 0);
+
+    fn snap(size: i32, base: u16, step: u16) -> i32 {
+        let base = base as i32;
+        if step == 0 || size <= base {
+            return size;
+        }
+        base + ((size - base) / step as i32) * step as i32
+    }
+
+    let width = snap(rect_width(rect) - frame_width, incr.base_width, incr.x) + frame_width;
+    let height = snap(rect_height(rect) - frame_height, incr.base_height, incr.y) + frame_height;
+
+    let edge = wparam as u32;
+    if edge == WMSZ_LEFT as u32 || edge == WMSZ_TOPLEFT as u32 || edge == WMSZ_BOTTOMLEFT as u32 {
+        rect.left = rect.right - width;
+    } else {
+        rect.right = rect.left + width;
+    }
+    if edge == WMSZ_TOP as u32 || edge == WMSZ_TOPLEFT as u32 || edge == WMSZ_TOPRIGHT as u32 {
+        rect.top = rect.bottom - height;
+    } else {
+        rect.bottom = rect.top + height;
+    }
+
+    Some(1)
+}
+
 /// We handle WM_WINDOWPOSCHANGED and dispatch directly to our wm_size as it
 /// is a bit more efficient than letting DefWindowProcW parse this and
 /// trigger WM_SIZE.
@@ -879,6 +999,61 @@ unsafe fn wm_kill_focus(
     None
 }
 
+unsafe fn wm_drop_files(
+    hwnd: HWND,
+    _msg: UINT,
+    wparam: WPARAM,
+    _lparam: LPARAM,
+) -> Option<LRESULT> {
+    let hdrop = wparam as HDROP;
+    let count = DragQueryFileW(hdrop, 0xFFFF_FFFF, null_mut(), 0);
+    let mut paths = vec![];
+    for idx in 0..count {
+        let len = DragQueryFileW(hdrop, idx, null_mut(), 0) as usize;
+        let mut buf = vec![0u16; len + 1];
+        DragQueryFileW(hdrop, idx, buf.as_mut_ptr(), buf.len() as UINT);
+        paths.push(PathBuf::from(OsString::from_wide(&buf[..len])));
+    }
+    DragFinish(hdrop);
+
+    if let Some(inner) = rc_from_hwnd(hwnd) {
+        let inner = inner.borrow();
+        inner.callbacks.borrow_mut().dropped_files(paths);
+    }
+    Some(0)
+}
+
+unsafe fn wm_setting_change(
+    hwnd: HWND,
+    _msg: UINT,
+    _wparam: WPARAM,
+    lparam: LPARAM,
+) -> Option<LRESULT> {
+    if lparam == 0 {
+        return None;
+    }
+    // The theme preference is signalled via the "ImmersiveColorSet" area
+    let area = wstr_to_string(lparam as *const u16);
+    if area == "ImmersiveColorSet" {
+        if let Some(inner) = rc_from_hwnd(hwnd) {
+            let inner = inner.borrow();
+            inner
+                .callbacks
+                .borrow_mut()
+                .appearance_changed(super::get_appearance());
+        }
+    }
+    None
+}
+
+unsafe fn wstr_to_string(ptr: *const u16) -> String {
+    let mut len = 0;
+    while *ptr.add(len) != 0 {
+        len += 1;
+    }
+    String::from_utf16_lossy(std::slice::from_raw_parts(ptr, len))
+}
+
 unsafe fn wm_paint(hwnd: HWND, _msg: UINT, _wparam: WPARAM, _lparam: LPARAM) -> Option<LRESULT> {
     if let Some(inner) = rc_from_hwnd(hwnd) {
         let inner = inner.borrow();
@@ -981,6 +1156,59 @@ fn client_to_screen(hwnd: HWND, point: Point) -> ScreenPoint {
     ScreenPoint::new(point.x.try_into().unwrap(), point.y.try_into().unwrap())
 }
 
+/// Shows a popup menu holding `entries` at `point` and waits for it
+/// to be dismissed, returning the id of the selected item, if any
+pub(crate) fn track_popup_menu(
+    hwnd: HWND,
+    entries: &[MenuEntry],
+    point: ScreenPoint,
+) -> Option<usize> {
+    unsafe {
+        let menu = CreatePopupMenu();
+        if menu.is_null() {
+            log::error!("CreatePopupMenu failed: {}", IoError::last_os_error());
+            return None;
+        }
+        for entry in entries {
+            match entry {
+                MenuEntry::Separator => {
+                    AppendMenuW(menu, MF_SEPARATOR, 0, null());
+                }
+                MenuEntry::Item {
+                    id,
+                    label,
+                    key_hint,
+                } => {
+                    // A tab right-aligns the remainder of the label,
+                    // which is the conventional place for the key
+                    let label = match key_hint {
+                        Some(hint) => wide_string(&format!("{}\t{}", label, hint)),
+                        None => wide_string(label),
+                    };
+                    // The command id 0 means that nothing was
+                    // selected, so the ids are offset by one
+                    AppendMenuW(menu, MF_STRING, *id + 1, label.as_ptr());
+                }
+            }
+        }
+        let selected = TrackPopupMenu(
+            menu,
+            TPM_RETURNCMD | TPM_RIGHTBUTTON,
+            point.x as i32,
+            point.y as i32,
+            0,
+            hwnd,
+            null(),
+        );
+        DestroyMenu(menu);
+        if selected > 0 {
+            Some(selected as usize - 1)
+        } else {
+            None
+        }
+    }
+}
+
 fn apply_mouse_cursor(cursor: Option<MouseCursor>) {
     match cursor {
         None => unsafe {
@@ -1900,8 +2128,11 @@ unsafe fn do_wnd_proc(hwnd: HWND, msg: UINT, wparam: WPARAM, lparam: LPARAM) ->
         WM_PAINT => wm_paint(hwnd, msg, wparam, lparam),
         WM_ENTERSIZEMOVE | WM_EXITSIZEMOVE => wm_enter_exit_size_move(hwnd, msg, wparam, lparam),
         WM_WINDOWPOSCHANGED => wm_windowposchanged(hwnd, msg, wparam, lparam),
+        WM_SIZING => wm_sizing(hwnd, msg, wparam, lparam),
         WM_SETFOCUS => wm_set_focus(hwnd, msg, wparam, lparam),
         WM_KILLFOCUS => wm_kill_focus(hwnd, msg, wparam, lparam),
+        WM_SETTINGCHANGE => wm_setting_change(hwnd, msg, wparam, lparam),
+        WM_DROPFILES => wm_drop_files(hwnd, msg, wparam, lparam),
         WM_DEADCHAR | WM_KEYDOWN | WM_KEYUP | WM_SYSCHAR | WM_CHAR | WM_IME_CHAR | WM_SYSKEYUP
         | WM_SYSKEYDOWN => key(hwnd, msg, wparam, lparam),
         WM_IME_COMPOSITION => ime_composition(hwnd, msg, wparam, lparam),
@@ -1,9 +1,11 @@
 use super::keyboard::Keyboard;
 use crate::connection::ConnectionOps;
 use crate::os::x11::window::XWindowInner;
+use crate::os::xkeysyms::keycode_to_keysym;
 use crate::os::Connection;
 use crate::spawn::*;
 use crate::timerlist::{TimerEntry, TimerList};
+use crate::{KeyCode, Modifiers, ScreenPoint, ScreenRect};
 use anyhow::{anyhow, bail, Context as _};
 use mio::unix::EventedFd;
 use mio::{Evented, Events, Poll, PollOpt, Ready, Token};
@@ -37,8 +39,20 @@ pub struct XConnection {
     pub(crate) visual: xcb::xproto::Visualtype,
     pub(crate) depth: u8,
     pub(crate) gl_connection: RefCell<Option<Rc<crate::egl::GlConnection>>>,
+    hotkeys: RefCell<Vec<GlobalHotkey>>,
 }
 
+/// A key combination that was grabbed on the root window
+struct GlobalHotkey {
+    keycode: xcb::Keycode,
+    state: u16,
+    callback: Rc<RefCell<dyn FnMut()>>,
+}
+
+/// The lock modifiers that we ignore when matching global hotkeys;
+/// CapsLock and NumLock (which is conventionally Mod2)
+const HOTKEY_IGNORED_MODS: u16 = (xcb::MOD_MASK_LOCK | xcb::MOD_MASK_2) as u16;
+
 impl std::ops::Deref for XConnection {
     type Target = xcb::Connection;
 
@@ -152,6 +166,98 @@ impl ConnectionOps for XConnection {
         self.default_dpi
     }
 
+    fn screens(&self) -> Vec<ScreenRect> {
+        // We don't use xrandr, so we can only report the root window,
+        // which spans all of the monitors, as a single screen
+        match xcb::get_geometry(self.conn(), self.root).get_reply() {
+            Ok(geom) => vec![ScreenRect::new(
+                ScreenPoint::new(geom.x() as isize, geom.y() as isize),
+                euclid::size2(geom.width() as isize, geom.height() as isize),
+            )],
+            Err(err) => {
+                log::error!("failed to query root window geometry: {:?}", err);
+                vec![]
+            }
+        }
+    }
+
+    fn pointer_position(&self) -> Option<ScreenPoint> {
+        match xcb::query_pointer(self.conn(), self.root).get_reply() {
+            Ok(pointer) => Some(ScreenPoint::new(
+                pointer.root_x() as isize,
+                pointer.root_y() as isize,
+            )),
+            Err(err) => {
+                log::error!("failed to query pointer position: {:?}", err);
+                None
+            }
+        }
+    }
+
+    fn register_global_hotkey<F: FnMut() + 'static>(
+        &self,
+        key: KeyCode,
+        mods: Modifiers,
+        callback: F,
+    ) -> anyhow::Result<()> {
+        let keysym = keycode_to_keysym(&key)
+            .ok_or_else(|| anyhow!("{:?} cannot be used as a global hotkey", key))?;
+        let keycode = self
+            .keysym_to_keycode(keysym)?
+            .ok_or_else(|| anyhow!("{:?} is not present in the keyboard mapping", key))?;
+
+        let mut state = 0;
+        if mods.contains(Modifiers::SHIFT) {
+            state |= xcb::MOD_MASK_SHIFT;
+        }
+        if mods.contains(Modifiers::CTRL) {
+            state |= xcb::MOD_MASK_CONTROL;
+        }
+        if mods.contains(Modifiers::ALT) {
+            state |= xcb::MOD_MASK_1;
+        }
+        if mods.contains(Modifiers::SUPER) {
+            state |= xcb::MOD_MASK_4;
+        }
+        let state = state as u16;
+
+        // The grab only matches the exact modifier state, so we also
+        // need to grab the variations with the lock modifiers active.
+        // owner_events is false so that the key is always reported
+        // against the root window, even when one of our own windows
+        // has the focus; process_global_hotkey relies on that.
+        for lock in &[
+            0,
+            xcb::MOD_MASK_LOCK as u16,
+            xcb::MOD_MASK_2 as u16,
+            HOTKEY_IGNORED_MODS,
+        ] {
+            xcb::grab_key_checked(
+                self.conn(),
+                false,
+                self.root,
+                state | lock,
+                keycode,
+                xcb::GRAB_MODE_ASYNC as u8,
+                xcb::GRAB_MODE_ASYNC as u8,
+            )
+            .request_check()
+            .with_context(|| {
+                format!(
+                    "grabbing {:?} {:?}; is another application using it?",
+                    mods, key
+                )
+            })?;
+        }
+
+        self.hotkeys.borrow_mut().push(GlobalHotkey {
+            keycode,
+            state,
+            callback: Rc::new(RefCell::new(callback)),
+        });
+        Ok(())
+    }
+
     fn run_message_loop(&self) -> anyhow::Result<()> {
         self.conn.flush();
 
@@ -261,6 +367,9 @@ impl XConnection {
     }
 
     fn process_xcb_event(&self, event: &xcb::GenericEvent) -> anyhow::Result<()> {
+        if self.process_global_hotkey(event) {
+            return Ok(());
+        }
         if let Some(window_id) = window_id_from_event(event) {
             self.process_window_event(window_id, event)?;
         } else {
@@ -276,6 +385,51 @@ impl XConnection {
         Ok(())
     }
 
+    /// If `event` is a key press for one of our global hotkeys, run
+    /// its callback and return true.
+    fn process_global_hotkey(&self, event: &xcb::GenericEvent) -> bool {
+        if event.response_type() & 0x7f != xcb::KEY_PRESS {
+            return false;
+        }
+        let key_press: &xcb::KeyPressEvent = unsafe { xcb::cast_event(event) };
+        if key_press.event() != self.root {
+            return false;
+        }
+        let state = key_press.state() & !HOTKEY_IGNORED_MODS;
+        let callback = self
+            .hotkeys
+            .borrow()
+            .iter()
+            .find(|hotkey| hotkey.keycode == key_press.detail() && hotkey.state == state)
+            .map(|hotkey| Rc::clone(&hotkey.callback));
+        match callback {
+            Some(callback) => {
+                // The hotkeys are no longer borrowed, so the callback
+                // is free to register more of them
+                (&mut *callback.borrow_mut())();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Find the first keycode whose mapping produces `keysym`
+    fn keysym_to_keycode(&self, keysym: u32) -> anyhow::Result<Option<xcb::Keycode>> {
+        let setup = self.conn.get_setup();
+        let min_keycode = setup.min_keycode();
+        let max_keycode = setup.max_keycode();
+        let mapping =
+            xcb::get_keyboard_mapping(self.conn(), min_keycode, max_keycode - min_keycode + 1)
+                .get_reply()
+                .context("querying keyboard mapping")?;
+        let per_keycode = mapping.keysyms_per_keycode().max(1) as usize;
+        Ok(mapping
+            .keysyms()
+            .chunks(per_keycode)
+            .position(|syms| syms.contains(&keysym))
+            .map(|idx| min_keycode + idx as xcb::Keycode))
+    }
+
     fn window_by_id(&self, window_id: xcb::xproto::Window) -> Option<Arc<Mutex<XWindowInner>>> {
         self.windows.borrow().get(&window_id).map(Arc::clone)
     }
@@ -397,6 +551,7 @@ impl XConnection {
             depth,
             visual,
             gl_connection: RefCell::new(None),
+            hotkeys: RefCell::new(vec![]),
         };
 
         Ok(conn)
@@ -5,7 +5,8 @@ use crate::os::xkeysyms;
 use crate::os::{Connection, Window};
 use crate::{
     Clipboard, Dimensions, MouseButtons, MouseCursor, MouseEvent, MouseEventKind, MousePress,
-    Point, Rect, ScreenPoint, Size, WindowCallbacks, WindowDecorations, WindowOps, WindowOpsMut,
+    Point, Rect, ResizeIncrement, ScreenPoint, Size, WindowCallbacks, WindowDecorations, WindowOps,
+    WindowOpsMut,
 };
 use anyhow::{anyhow, Context as _};
 use config::ConfigHandle;
@@ -60,6 +61,8 @@ pub(crate) struct XWindowInner {
     copy_and_paste: CopyAndPaste,
     config: ConfigHandle,
     gl_state: Option<Rc<glium::backend::Context>>,
+    /// Whether the urgency hint is set
+    urgent: bool,
 }
 
 fn enclosing_boundary_with(a: &Rect, b: &Rect) -> Rect {
@@ -244,15 +247,12 @@ impl XWindowInner {
                             return Ok(());
                         }
 
-                        // Ideally this would be configurable, but it's currently a bit
-                        // awkward to configure this layer, so let's just improve the
-                        // default for now!
-                        const LINES_PER_TICK: i16 = 5;
+                        let lines_per_tick = config::configuration().scroll_lines_per_tick as i16;
 
                         MouseEventKind::VertWheel(if b == 4 {
-                            LINES_PER_TICK
+                            lines_per_tick
                         } else {
-                            -LINES_PER_TICK
+                            -lines_per_tick
                         })
                     }
                     _ => {
@@ -304,6 +304,9 @@ impl XWindowInner {
                 );
             }
             xcb::FOCUS_IN => {
+                if self.urgent {
+                    self.set_urgency_hint(false);
+                }
                 log::trace!("Calling focus_change(true)");
                 self.callbacks.focus_change(true);
             }
@@ -489,6 +492,17 @@ impl XWindowInner {
         Ok(())
     }
 
+    /// Sets or clears the urgency hint, which asks the window manager
+    /// to draw the user's attention to the window
+    fn set_urgency_hint(&mut self, urgent: bool) {
+        let mut hints = xcb_util::icccm::WmHints::empty();
+        if urgent {
+            hints = hints.is_urgent();
+        }
+        xcb_util::icccm::set_wm_hints(self.conn().conn(), self.window_id, &hints.build());
+        self.urgent = urgent;
+    }
+
     fn is_fullscreen(&self) -> anyhow::Result<bool> {
         let conn = self.conn();
 
@@ -613,6 +627,22 @@ impl XWindowInner {
         Ok(())
     }
 
+    /// Ask the window manager to activate the window.
+    /// Using the EWMH request rather than set_input_focus allows
+    /// the window manager to switch desktops and raise the window.
+    fn focus(&mut self) {
+        let conn = self.conn();
+        xcb::map_window(conn.conn(), self.window_id);
+        xcb_util::ewmh::request_change_active_window(
+            conn.ewmh_conn(),
+            conn.screen_num,
+            self.window_id,
+            2, // pager; we're switching in response to a user request
+            xcb::CURRENT_TIME,
+            xcb::NONE,
+        );
+    }
+
     fn conn(&self) -> Rc<XConnection> {
         self.conn.upgrade().expect("XConnection to be alive")
     }
@@ -721,10 +751,12 @@ impl XWindow {
                 cursors: CursorInfo::new(&conn),
                 gl_state: None,
                 config: config.clone(),
+                urgent: false,
             }))
         };
 
-        xcb_util::icccm::set_wm_class(&*conn, window_id, class_name, class_name);
+        let instance_name = config.window_instance.as_deref().unwrap_or(class_name);
+        xcb_util::icccm::set_wm_class(&*conn, window_id, instance_name, class_name);
         xcb_util::ewmh::set_wm_pid(conn.ewmh_conn(), window_id, unsafe {
             libc::getpid() as u32
         });
@@ -788,6 +820,17 @@ impl WindowOpsMut for XWindowInner {
         let _ = self.adjust_decorations(config.window_decorations);
     }
 
+    fn set_resize_increments(&mut self, incr: Option<ResizeIncrement>) {
+        let hints = match incr {
+            Some(incr) => xcb_util::icccm::SizeHints::empty()
+                .base(incr.base_width as i32, incr.base_height as i32)
+                .resize(incr.x as i32, incr.y as i32)
+                .build(),
+            None => xcb_util::icccm::SizeHints::empty().build(),
+        };
+        xcb_util::icccm::set_wm_normal_hints(self.conn().conn(), self.window_id, &hints);
+    }
+
     fn set_inner_size(&mut self, width: usize, height: usize) {
         xcb::configure_window(
             self.conn().conn(),
@@ -827,6 +870,10 @@ impl WindowOpsMut for XWindowInner {
         xcb_util::icccm::set_wm_name(self.conn().conn(), self.window_id, title);
     }
 
+    fn request_attention(&mut self) {
+        self.set_urgency_hint(true);
+    }
+
     fn set_icon(&mut self, image: &dyn BitmapImage) {
         let (width, height) = image.image_dimensions();
 
@@ -870,6 +917,13 @@ impl WindowOps for XWindow {
         })
     }
 
+    fn request_attention(&self) -> Future<()> {
+        XConnection::with_window_inner(self.0, |inner| {
+            inner.request_attention();
+            Ok(())
+        })
+    }
+
     fn config_did_change(&self, config: &ConfigHandle) -> Future<()> {
         let config = config.clone();
         XConnection::with_window_inner(self.0, move |inner| {
@@ -885,6 +939,13 @@ impl WindowOps for XWindow {
         })
     }
 
+    fn focus(&self) -> Future<()> {
+        XConnection::with_window_inner(self.0, |inner| {
+            inner.focus();
+            Ok(())
+        })
+    }
+
     fn set_cursor(&self, cursor: Option<MouseCursor>) -> Future<()> {
         XConnection::with_window_inner(self.0, move |inner| {
             let _ = inner.set_cursor(cursor);
@@ -914,6 +975,13 @@ impl WindowOps for XWindow {
         })
     }
 
+    fn set_resize_increments(&self, incr: Option<ResizeIncrement>) -> Future<()> {
+        XConnection::with_window_inner(self.0, move |inner| {
+            inner.set_resize_increments(incr);
+            Ok(())
+        })
+    }
+
     fn set_window_position(&self, coords: ScreenPoint) -> Future<()> {
         XConnection::with_window_inner(self.0, move |inner| {
             inner.set_window_position(coords);
@@ -7,7 +7,10 @@ use crate::os::wayland::connection::WaylandConnection;
 use crate::os::wayland::window::WaylandWindow;
 use crate::os::x11::connection::XConnection;
 use crate::os::x11::window::XWindow;
-use crate::{Clipboard, MouseCursor, ScreenPoint, WindowCallbacks, WindowOps};
+use crate::{
+    Clipboard, KeyCode, Modifiers, MouseCursor, ResizeIncrement, ScreenPoint, ScreenRect,
+    WindowCallbacks, WindowOps,
+};
 use config::ConfigHandle;
 use promise::*;
 use std::any::Any;
@@ -96,6 +99,35 @@ impl ConnectionOps for Connection {
             Self::Wayland(w) => w.run_message_loop(),
         }
     }
+    fn screens(&self) -> Vec<ScreenRect> {
+        match self {
+            Self::X11(x) => x.screens(),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(w) => w.screens(),
+        }
+    }
+
+    fn pointer_position(&self) -> Option<ScreenPoint> {
+        match self {
+            Self::X11(x) => x.pointer_position(),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(w) => w.pointer_position(),
+        }
+    }
+
+    fn register_global_hotkey<F: FnMut() + 'static>(
+        &self,
+        key: KeyCode,
+        mods: Modifiers,
+        callback: F,
+    ) -> anyhow::Result<()> {
+        match self {
+            Self::X11(x) => x.register_global_hotkey(key, mods, callback),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(w) => w.register_global_hotkey(key, mods, callback),
+        }
+    }
+
     fn schedule_timer<F: FnMut() + 'static>(&self, interval: std::time::Duration, callback: F) {
         match self {
             Self::X11(x) => x.schedule_timer(interval, callback),
@@ -145,6 +177,14 @@ impl WindowOps for Window {
         }
     }
 
+    fn request_attention(&self) -> Future<()> {
+        match self {
+            Self::X11(x) => x.request_attention(),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(w) => w.request_attention(),
+        }
+    }
+
     fn config_did_change(&self, config: &ConfigHandle) -> Future<()> {
         match self {
             Self::X11(x) => x.config_did_change(config),
@@ -161,6 +201,14 @@ impl WindowOps for Window {
         }
     }
 
+    fn focus(&self) -> Future<()> {
+        match self {
+            Self::X11(x) => x.focus(),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(w) => w.focus(),
+        }
+    }
+
     fn set_cursor(&self, cursor: Option<MouseCursor>) -> Future<()> {
         match self {
             Self::X11(x) => x.set_cursor(cursor),
@@ -201,6 +249,14 @@ impl WindowOps for Window {
         }
     }
 
+    fn set_resize_increments(&self, incr: Option<ResizeIncrement>) -> Future<()> {
+        match self {
+            Self::X11(x) => x.set_resize_increments(incr),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(w) => w.set_resize_increments(incr),
+        }
+    }
+
     fn set_window_position(&self, coords: ScreenPoint) -> Future<()> {
         match self {
             Self::X11(x) => x.set_window_position(coords),
@@ -120,3 +120,32 @@ pub fn keysym_to_keycode(keysym: u32) -> Option<KeyCode> {
         _ => return None,
     })
 }
+
+/// Translates a KeyCode to the X11 keysym that produces it; this is
+/// the inverse of `keysym_to_keycode` for the keys that are
+/// reasonable to use as global hotkeys.
+pub fn keycode_to_keysym(key: &KeyCode) -> Option<u32> {
+    use xkbcommon::xkb::keysyms::*;
+    Some(match key {
+        KeyCode::Char('\u{1b}') => KEY_Escape,
+        KeyCode::Char('\t') => KEY_Tab,
+        KeyCode::Char('\u{8}') => KEY_BackSpace,
+        KeyCode::Char('\r') => KEY_Return,
+        KeyCode::Char('\u{7f}') => KEY_Delete,
+        KeyCode::Char(c) if (' '..='\u{ff}').contains(c) => c.to_ascii_lowercase() as u32,
+        KeyCode::Insert => KEY_Insert,
+        KeyCode::Pause => KEY_Pause,
+        KeyCode::Print => KEY_Print,
+        KeyCode::Home => KEY_Home,
+        KeyCode::End => KEY_End,
+        KeyCode::LeftArrow => KEY_Left,
+        KeyCode::UpArrow => KEY_Up,
+        KeyCode::RightArrow => KEY_Right,
+        KeyCode::DownArrow => KEY_Down,
+        KeyCode::PageUp => KEY_Page_Up,
+        KeyCode::PageDown => KEY_Page_Down,
+        KeyCode::ScrollLock => KEY_Scroll_Lock,
+        KeyCode::Function(n) if (1..=12).contains(n) => KEY_F1 + u32::from(*n) - 1,
+        _ => return None,
+    })
+}